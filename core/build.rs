@@ -0,0 +1,16 @@
+// Generates the C header of the `ffi` feature
+fn main() {
+    #[cfg(feature = "ffi")]
+    {
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).expect("cbindgen.toml is valid");
+        cbindgen::Builder::new()
+            .with_crate(&crate_dir)
+            .with_config(config)
+            .generate()
+            .expect("the ffi module is valid for cbindgen")
+            .write_to_file(format!("{}/include/dz_validator_pda.h", crate_dir));
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+    }
+}
@@ -0,0 +1,95 @@
+/* C ABI of dz_validator_pda_core (build with --features ffi). Generated by cbindgen, do not edit. */
+
+#ifndef DZ_VALIDATOR_PDA_H
+#define DZ_VALIDATOR_PDA_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The call succeeded
+#define DZ_OK 0
+
+// A pointer argument is null or a string is not UTF-8
+#define DZ_INVALID_ARGUMENT 1
+
+// An address is not a valid base58 pubkey
+#define DZ_INVALID_ADDRESS 2
+
+// The output buffer is too small; the required length is written to `out_written`
+#define DZ_BUFFER_TOO_SMALL 3
+
+// Buffer length that fits any base58 address and its NUL terminator
+#define DZ_ADDRESS_BUFFER_LEN 45
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns the message of the last failed call on this thread (empty if none)
+//
+// The string stays valid until the next failing call on the same thread.
+const char *dz_last_error(void);
+
+// Checks that a string is a valid base58 pubkey
+//
+// # Safety
+// `address` must be null or a NUL-terminated string.
+//
+// # Returns
+// * `DZ_OK`, `DZ_INVALID_ARGUMENT` or `DZ_INVALID_ADDRESS`
+int32_t dz_validate_address(const char *address);
+
+// Derives the deposit PDA of a validator
+//
+// # Safety
+// `validator` must be null or a NUL-terminated string, `out_address` must point to
+// `out_address_len` writable bytes and `out_bump` must be null or writable.
+//
+// # Arguments
+// * `validator` - Validator identity in base58
+// * `out_address` - Receives the base58 PDA, NUL-terminated (`DZ_ADDRESS_BUFFER_LEN` bytes always suffice)
+// * `out_address_len` - Length of `out_address`
+// * `out_bump` - Receives the bump seed, if not null
+//
+// # Returns
+// * `DZ_OK`, `DZ_INVALID_ARGUMENT`, `DZ_INVALID_ADDRESS` or `DZ_BUFFER_TOO_SMALL`
+int32_t dz_generate_deposit_pda(const char *validator,
+                                char *out_address,
+                                size_t out_address_len,
+                                uint8_t *out_bump);
+
+// Builds the transfer of lamports from a wallet to the deposit PDA of a validator
+//
+// The instruction is written bincode-serialized, as Rust's `solana_instruction::Instruction`:
+// program id (32 bytes), account count (u64 LE), per account the pubkey (32 bytes), `is_signer`
+// and `is_writable` (one byte each), then data length (u64 LE) and data. A transfer is 128 bytes.
+//
+// # Safety
+// `payer` and `validator` must be null or NUL-terminated strings, `out` must point to `out_len`
+// writable bytes and `out_written` must be null or writable.
+//
+// # Arguments
+// * `payer` - Funding wallet in base58, the signer of the transfer
+// * `validator` - Validator identity in base58
+// * `lamports` - Amount in lamports
+// * `out` - Receives the serialized instruction
+// * `out_len` - Length of `out`
+// * `out_written` - Receives the serialized length, also when `out` is too small
+//
+// # Returns
+// * `DZ_OK`, `DZ_INVALID_ARGUMENT`, `DZ_INVALID_ADDRESS` or `DZ_BUFFER_TOO_SMALL`
+int32_t dz_build_fund_instruction(const char *payer,
+                                  const char *validator,
+                                  uint64_t lamports,
+                                  uint8_t *out,
+                                  size_t out_len,
+                                  size_t *out_written);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* DZ_VALIDATOR_PDA_H */
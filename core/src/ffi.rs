@@ -0,0 +1,218 @@
+//! C ABI of the core functions (`ffi` feature)
+//!
+//! Strings are NUL-terminated UTF-8 and output goes to buffers the caller owns, so nothing has to
+//! be freed on the Rust side. Every function returns a `DZ_*` status code; on failure
+//! `dz_last_error` gives the message the CLI would print. The header is generated by cbindgen into
+//! `include/dz_validator_pda.h`.
+
+use crate::{build_fund_instruction, deposit_pda, parse_pubkey, validate_base58};
+use solana_pubkey::Pubkey;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+
+/// The call succeeded
+pub const DZ_OK: i32 = 0;
+
+/// A pointer argument is null or a string is not UTF-8
+pub const DZ_INVALID_ARGUMENT: i32 = 1;
+
+/// An address is not a valid base58 pubkey
+pub const DZ_INVALID_ADDRESS: i32 = 2;
+
+/// The output buffer is too small; the required length is written to `out_written`
+pub const DZ_BUFFER_TOO_SMALL: i32 = 3;
+
+/// Buffer length that fits any base58 address and its NUL terminator
+pub const DZ_ADDRESS_BUFFER_LEN: usize = 45;
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+fn fail(status: i32, message: String) -> i32 {
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    status
+}
+
+/// Reads a pubkey from a C string, recording the error the CLI would print
+unsafe fn read_pubkey(value: *const c_char, name: &str) -> Result<Pubkey, i32> {
+    if value.is_null() {
+        return Err(fail(DZ_INVALID_ARGUMENT, format!("{} is null", name)));
+    }
+    // SAFETY: the caller passes a NUL-terminated string
+    let value = unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map_err(|_| fail(DZ_INVALID_ARGUMENT, format!("{} is not UTF-8", name)))?;
+    validate_base58(value)
+        .and_then(|_| parse_pubkey(value))
+        .map_err(|e| fail(DZ_INVALID_ADDRESS, format!("Invalid {}: {}", name, e)))
+}
+
+/// Returns the message of the last failed call on this thread (empty if none)
+///
+/// The string stays valid until the next failing call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn dz_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ptr())
+}
+
+/// Checks that a string is a valid base58 pubkey
+///
+/// # Safety
+/// `address` must be null or a NUL-terminated string.
+///
+/// # Returns
+/// * `DZ_OK`, `DZ_INVALID_ARGUMENT` or `DZ_INVALID_ADDRESS`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dz_validate_address(address: *const c_char) -> i32 {
+    match unsafe { read_pubkey(address, "address") } {
+        Ok(_) => DZ_OK,
+        Err(status) => status,
+    }
+}
+
+/// Derives the deposit PDA of a validator
+///
+/// # Safety
+/// `validator` must be null or a NUL-terminated string, `out_address` must point to
+/// `out_address_len` writable bytes and `out_bump` must be null or writable.
+///
+/// # Arguments
+/// * `validator` - Validator identity in base58
+/// * `out_address` - Receives the base58 PDA, NUL-terminated (`DZ_ADDRESS_BUFFER_LEN` bytes always suffice)
+/// * `out_address_len` - Length of `out_address`
+/// * `out_bump` - Receives the bump seed, if not null
+///
+/// # Returns
+/// * `DZ_OK`, `DZ_INVALID_ARGUMENT`, `DZ_INVALID_ADDRESS` or `DZ_BUFFER_TOO_SMALL`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dz_generate_deposit_pda(
+    validator: *const c_char,
+    out_address: *mut c_char,
+    out_address_len: usize,
+    out_bump: *mut u8,
+) -> i32 {
+    let validator = match unsafe { read_pubkey(validator, "validator") } {
+        Ok(validator) => validator,
+        Err(status) => return status,
+    };
+    if out_address.is_null() {
+        return fail(DZ_INVALID_ARGUMENT, "out_address is null".to_string());
+    }
+    let (address, bump) = deposit_pda(&validator);
+    let address = address.to_string();
+    if address.len() >= out_address_len {
+        return fail(DZ_BUFFER_TOO_SMALL, format!("out_address needs {} bytes, got {}", address.len() + 1, out_address_len));
+    }
+    // SAFETY: out_address has room for the address and its terminator, checked above
+    unsafe {
+        std::ptr::copy_nonoverlapping(address.as_ptr().cast::<c_char>(), out_address, address.len());
+        *out_address.add(address.len()) = 0;
+        if !out_bump.is_null() {
+            *out_bump = bump;
+        }
+    }
+    DZ_OK
+}
+
+/// Builds the transfer of lamports from a wallet to the deposit PDA of a validator
+///
+/// The instruction is written bincode-serialized, as Rust's `solana_instruction::Instruction`:
+/// program id (32 bytes), account count (u64 LE), per account the pubkey (32 bytes), `is_signer`
+/// and `is_writable` (one byte each), then data length (u64 LE) and data. A transfer is 128 bytes.
+///
+/// # Safety
+/// `payer` and `validator` must be null or NUL-terminated strings, `out` must point to `out_len`
+/// writable bytes and `out_written` must be null or writable.
+///
+/// # Arguments
+/// * `payer` - Funding wallet in base58, the signer of the transfer
+/// * `validator` - Validator identity in base58
+/// * `lamports` - Amount in lamports
+/// * `out` - Receives the serialized instruction
+/// * `out_len` - Length of `out`
+/// * `out_written` - Receives the serialized length, also when `out` is too small
+///
+/// # Returns
+/// * `DZ_OK`, `DZ_INVALID_ARGUMENT`, `DZ_INVALID_ADDRESS` or `DZ_BUFFER_TOO_SMALL`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dz_build_fund_instruction(
+    payer: *const c_char,
+    validator: *const c_char,
+    lamports: u64,
+    out: *mut u8,
+    out_len: usize,
+    out_written: *mut usize,
+) -> i32 {
+    let (payer, validator) = match unsafe { (read_pubkey(payer, "payer"), read_pubkey(validator, "validator")) } {
+        (Ok(payer), Ok(validator)) => (payer, validator),
+        (Err(status), _) | (_, Err(status)) => return status,
+    };
+    let bytes = match bincode::serialize(&build_fund_instruction(&payer, &validator, lamports)) {
+        Ok(bytes) => bytes,
+        Err(e) => return fail(DZ_INVALID_ARGUMENT, format!("Failed to serialize the instruction: {}", e)),
+    };
+    if !out_written.is_null() {
+        // SAFETY: checked for null above
+        unsafe { *out_written = bytes.len() };
+    }
+    if out.is_null() || bytes.len() > out_len {
+        return fail(DZ_BUFFER_TOO_SMALL, format!("out needs {} bytes, got {}", bytes.len(), out_len));
+    }
+    // SAFETY: out has room for the instruction, checked above
+    unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len()) };
+    DZ_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_instruction::Instruction;
+
+    const VALIDATOR: &CStr = c"FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL";
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(dz_last_error()) }.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_generate_deposit_pda_and_validate() {
+        let mut address = [0 as c_char; DZ_ADDRESS_BUFFER_LEN];
+        let mut bump = 0u8;
+        let status = unsafe { dz_generate_deposit_pda(VALIDATOR.as_ptr(), address.as_mut_ptr(), address.len(), &mut bump) };
+        assert_eq!(status, DZ_OK);
+        let expected = deposit_pda(&VALIDATOR.to_str().unwrap().parse().unwrap());
+        assert_eq!(unsafe { CStr::from_ptr(address.as_ptr()) }.to_str().unwrap(), expected.0.to_string());
+        assert_eq!(bump, expected.1);
+
+        let status = unsafe { dz_generate_deposit_pda(VALIDATOR.as_ptr(), address.as_mut_ptr(), 10, std::ptr::null_mut()) };
+        assert_eq!(status, DZ_BUFFER_TOO_SMALL);
+
+        assert_eq!(unsafe { dz_validate_address(VALIDATOR.as_ptr()) }, DZ_OK);
+        assert_eq!(unsafe { dz_validate_address(c"0xabc".as_ptr()) }, DZ_INVALID_ADDRESS);
+        assert_eq!(last_error(), "Invalid address: Invalid base58 character '0' found in address");
+        assert_eq!(unsafe { dz_validate_address(std::ptr::null()) }, DZ_INVALID_ARGUMENT);
+    }
+
+    #[test]
+    fn test_build_fund_instruction() {
+        let payer = Pubkey::new_unique();
+        let payer_str = CString::new(payer.to_string()).unwrap();
+        let mut written = 0usize;
+        let status = unsafe {
+            dz_build_fund_instruction(payer_str.as_ptr(), VALIDATOR.as_ptr(), 42, std::ptr::null_mut(), 0, &mut written)
+        };
+        assert_eq!((status, written), (DZ_BUFFER_TOO_SMALL, 128));
+
+        let mut out = vec![0u8; written];
+        let status = unsafe { dz_build_fund_instruction(payer_str.as_ptr(), VALIDATOR.as_ptr(), 42, out.as_mut_ptr(), out.len(), &mut written) };
+        assert_eq!(status, DZ_OK);
+        let instruction: Instruction = bincode::deserialize(&out).unwrap();
+        assert_eq!(instruction, build_fund_instruction(&payer, &VALIDATOR.to_str().unwrap().parse().unwrap(), 42));
+
+        let status = unsafe { dz_build_fund_instruction(c"payer".as_ptr(), VALIDATOR.as_ptr(), 42, out.as_mut_ptr(), out.len(), &mut written) };
+        assert_eq!(status, DZ_INVALID_ADDRESS);
+        assert!(last_error().starts_with("Invalid payer: Invalid pubkey format"));
+    }
+}
@@ -0,0 +1,299 @@
+//! Pure logic of dz_validator_pda without RPC, files or an async runtime
+//!
+//! Deposit PDA derivation, address validation, amount parsing and funding instructions, shared by
+//! the CLI and web dashboards. With the `wasm` feature the crate builds for `wasm32-unknown-unknown`
+//! and exports these functions to JavaScript (see `wasm`); with the `ffi` feature it exports a C ABI
+//! (see `ffi`).
+
+use solana_instruction::Instruction;
+use solana_pubkey::{Pubkey, MAX_SEED_LEN};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Program owning the deposit PDAs
+pub const REVENUE_DISTRIBUTION_PROGRAM_ID: Pubkey = solana_pubkey::pubkey!("dzrevZC94tBLwuHw1dyynZxaXTWyp7yocsinyEVPtt4");
+
+/// First seed of the deposit PDA, followed by the validator pubkey
+pub const DEPOSIT_PDA_SEED_PREFIX: &str = "solana_validator_deposit";
+
+/// Number of lamports in one SOL
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Number of decimal places in a SOL amount
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Characters of base58 encoding
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Derives the deposit PDA of a validator under the mainnet program
+///
+/// # Arguments
+/// * `validator_id` - The validator's public key
+///
+/// # Returns
+/// * `(Pubkey, u8)` - The deposit PDA and its bump seed
+pub fn deposit_pda(validator_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DEPOSIT_PDA_SEED_PREFIX.as_bytes(), validator_id.as_ref()], &REVENUE_DISTRIBUTION_PROGRAM_ID)
+}
+
+/// Derives the deposit PDA of a validator for a fork or staging deployment of the program
+///
+/// # Arguments
+/// * `validator_id` - The validator's public key
+/// * `seed_prefix` - First seed (`DEPOSIT_PDA_SEED_PREFIX` for the mainnet program)
+/// * `program_id` - Program owning the PDA
+///
+/// # Returns
+/// * `Result<(Pubkey, u8), String>` - The PDA and its bump seed, or an error for a prefix longer than 32 bytes
+pub fn deposit_pda_with_program(validator_id: &Pubkey, seed_prefix: &str, program_id: &Pubkey) -> Result<(Pubkey, u8), String> {
+    if seed_prefix.len() > MAX_SEED_LEN {
+        return Err(format!("Seed prefix '{}' is longer than {} bytes", seed_prefix, MAX_SEED_LEN));
+    }
+    Pubkey::try_find_program_address(&[seed_prefix.as_bytes(), validator_id.as_ref()], program_id)
+        .ok_or_else(|| format!("No valid bump seed for seed prefix '{}'", seed_prefix))
+}
+
+/// Validates if a string is a valid base58 encoded string
+///
+/// # Arguments
+/// * `address_str` - String to validate
+///
+/// # Returns
+/// * `Result<(), String>` - Validation result
+pub fn validate_base58(address_str: &str) -> Result<(), String> {
+    if address_str.trim().is_empty() {
+        return Err("Address cannot be empty".to_string());
+    }
+
+    // Check if the string contains only valid base58 characters
+    for ch in address_str.chars() {
+        if !BASE58_ALPHABET.contains(ch) {
+            return Err(format!("Invalid base58 character '{}' found in address", ch));
+        }
+    }
+
+    // Try to decode the base58 string to verify it's valid
+    bs58::decode(address_str)
+        .into_vec()
+        .map_err(|e| format!("Invalid base58 encoding: {}", e))?;
+
+    Ok(())
+}
+
+/// Parses a string into a Pubkey
+///
+/// # Arguments
+/// * `address_str` - String containing the address
+///
+/// # Returns
+/// * `Result<Pubkey, String>` - Parsing result
+pub fn parse_pubkey(address_str: &str) -> Result<Pubkey, String> {
+    address_str.parse::<Pubkey>()
+        .map_err(|e| format!("Invalid pubkey format: {}", e))
+}
+
+/// Parses a SOL amount into lamports
+///
+/// Accepted forms: `1.5` and `1.5sol` (SOL) and `1500000000lamports` (lamports). SOL amounts are
+/// converted with exact integer math, without floating point rounding. `ALL` depends on the payer
+/// balance and is left to the caller.
+///
+/// # Arguments
+/// * `amount_str` - Amount string
+///
+/// # Returns
+/// * `Result<u64, String>` - Amount in lamports, or an error if it is malformed, zero or too large
+pub fn parse_sol_amount(amount_str: &str) -> Result<u64, String> {
+    let trimmed = amount_str.trim();
+    let lowercase = trimmed.to_ascii_lowercase();
+
+    let lamports = if let Some(value) = lowercase.strip_suffix("lamports") {
+        parse_lamports(value.trim_end(), trimmed)?
+    } else if let Some(value) = lowercase.strip_suffix("sol") {
+        parse_decimal(value.trim_end(), trimmed, SOL_DECIMALS)?
+    } else {
+        parse_decimal(&lowercase, trimmed, SOL_DECIMALS)?
+    };
+
+    if lamports == 0 {
+        return Err("Amount must be greater than 0".to_string());
+    }
+    Ok(lamports)
+}
+
+/// Parses an SPL token amount in whole tokens (e.g. "2.5") into base units of a mint
+///
+/// # Arguments
+/// * `amount_str` - Amount string
+/// * `decimals` - Decimal places of the mint
+///
+/// # Returns
+/// * `Result<u64, String>` - Amount in base units, or an error if it is malformed, zero or more
+///   precise than the mint allows
+pub fn parse_token_amount(amount_str: &str, decimals: u8) -> Result<u64, String> {
+    let trimmed = amount_str.trim();
+    let amount = parse_decimal(trimmed, trimmed, decimals)?;
+    if amount == 0 {
+        return Err("Amount must be greater than 0".to_string());
+    }
+    Ok(amount)
+}
+
+/// Formats lamports as a SOL amount without floating point rounding (e.g., "1.5")
+///
+/// # Arguments
+/// * `lamports` - Amount in lamports
+///
+/// # Returns
+/// * `String` - Decimal SOL amount with trailing zeros removed
+pub fn format_sol(lamports: u64) -> String {
+    format_token_amount(lamports, SOL_DECIMALS)
+}
+
+/// Formats base units of a mint as a token amount (e.g., "2.5")
+///
+/// # Arguments
+/// * `amount` - Amount in base units
+/// * `decimals` - Decimal places of the mint
+///
+/// # Returns
+/// * `String` - Decimal token amount with trailing zeros removed
+pub fn format_token_amount(amount: u64, decimals: u8) -> String {
+    let digits = format!("{:0>width$}", amount, width = decimals as usize + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+fn invalid_amount(original: &str) -> String {
+    format!("Invalid amount: {}", original)
+}
+
+fn parse_lamports(value: &str, original: &str) -> Result<u64, String> {
+    if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid_amount(original));
+    }
+    value.parse::<u64>().map_err(|_| invalid_amount(original))
+}
+
+fn parse_decimal(value: &str, original: &str, decimals: u8) -> Result<u64, String> {
+    let (whole, fraction) = match value.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (value, ""),
+    };
+
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid_amount(original));
+    }
+    if !whole.chars().all(|c| c.is_ascii_digit()) || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid_amount(original));
+    }
+    if fraction.len() > decimals as usize {
+        return Err(format!("Invalid amount: {} has more than {} decimal places", original, decimals));
+    }
+
+    let too_large = || format!("Invalid amount: {} is too large", original);
+    let unit = 10u64.checked_pow(decimals as u32).ok_or_else(too_large)?;
+    let whole_units = if whole.is_empty() {
+        0
+    } else {
+        whole
+            .parse::<u64>()
+            .ok()
+            .and_then(|whole| whole.checked_mul(unit))
+            .ok_or_else(too_large)?
+    };
+    let fraction_units = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<width$}", fraction, width = decimals as usize).parse::<u64>().map_err(|_| invalid_amount(original))?
+    };
+
+    whole_units.checked_add(fraction_units).ok_or_else(too_large)
+}
+
+/// Returns the transfer of lamports from a wallet to the deposit PDA of a validator
+///
+/// The instruction needs no RPC access or signer, so it can be added to a transaction built
+/// elsewhere (an Anchor client, a multisig proposal, a wallet).
+///
+/// # Arguments
+/// * `payer` - Wallet the lamports come from, which must sign the transaction
+/// * `validator_id` - The validator's public key
+/// * `lamports` - Amount to transfer in lamports
+///
+/// # Returns
+/// * `Instruction` - System transfer to the deposit PDA
+pub fn build_fund_instruction(payer: &Pubkey, validator_id: &Pubkey, lamports: u64) -> Instruction {
+    solana_system_interface::instruction::transfer(payer, &deposit_pda(validator_id).0, lamports)
+}
+
+/// Returns the transfers of lamports from one wallet to the deposit PDAs of several validators
+///
+/// # Arguments
+/// * `payer` - Wallet the lamports come from
+/// * `transfers` - Validators and amounts in lamports
+///
+/// # Returns
+/// * `Vec<Instruction>` - One system transfer per validator, in order
+pub fn build_fund_instructions(payer: &Pubkey, transfers: &[(Pubkey, u64)]) -> Vec<Instruction> {
+    transfers
+        .iter()
+        .map(|(validator_id, lamports)| build_fund_instruction(payer, validator_id, *lamports))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_system_interface::instruction::SystemInstruction;
+
+    #[test]
+    fn test_deposit_pda() {
+        let validator = "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL".parse::<Pubkey>().unwrap();
+        let (address, bump) = deposit_pda(&validator);
+        assert_eq!(deposit_pda_with_program(&validator, DEPOSIT_PDA_SEED_PREFIX, &REVENUE_DISTRIBUTION_PROGRAM_ID), Ok((address, bump)));
+        assert_ne!(deposit_pda_with_program(&validator, "staging", &REVENUE_DISTRIBUTION_PROGRAM_ID).unwrap().0, address);
+        assert!(deposit_pda_with_program(&validator, &"x".repeat(33), &REVENUE_DISTRIBUTION_PROGRAM_ID).is_err());
+    }
+
+    #[test]
+    fn test_validate_and_parse_addresses() {
+        assert!(validate_base58("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL").is_ok());
+        assert_eq!(validate_base58(" "), Err("Address cannot be empty".to_string()));
+        assert_eq!(validate_base58("0xabc"), Err("Invalid base58 character '0' found in address".to_string()));
+        assert!(parse_pubkey("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL").is_ok());
+        assert!(parse_pubkey("FjYE").unwrap_err().starts_with("Invalid pubkey format"));
+    }
+
+    #[test]
+    fn test_parse_and_format_amounts() {
+        assert_eq!(parse_sol_amount("1.5"), Ok(1_500_000_000));
+        assert_eq!(parse_sol_amount("1.5 SOL"), Ok(1_500_000_000));
+        assert_eq!(parse_sol_amount("42lamports"), Ok(42));
+        assert_eq!(parse_sol_amount("0"), Err("Amount must be greater than 0".to_string()));
+        assert_eq!(parse_sol_amount("ALL"), Err("Invalid amount: ALL".to_string()));
+        assert_eq!(parse_token_amount("2.5", 6), Ok(2_500_000));
+        assert!(parse_token_amount("2.5000001", 6).is_err());
+        assert_eq!(format_sol(1_500_000_000), "1.5");
+        assert_eq!(format_token_amount(5, 6), "0.000005");
+    }
+
+    #[test]
+    fn test_build_fund_instructions() {
+        let (payer, validator_id, other) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let instruction = build_fund_instruction(&payer, &validator_id, 42);
+        assert_eq!(instruction.program_id, solana_system_interface::program::ID);
+        assert_eq!(instruction.accounts.iter().map(|meta| meta.pubkey).collect::<Vec<_>>(), vec![payer, deposit_pda(&validator_id).0]);
+        assert!(instruction.accounts[0].is_signer && !instruction.accounts[1].is_signer);
+        assert_eq!(bincode::deserialize::<SystemInstruction>(&instruction.data).unwrap(), SystemInstruction::Transfer { lamports: 42 });
+        assert_eq!(build_fund_instructions(&payer, &[(validator_id, 42), (other, 7)]), vec![instruction, build_fund_instruction(&payer, &other, 7)]);
+    }
+}
@@ -0,0 +1,134 @@
+//! JavaScript bindings of the core functions (`wasm` feature)
+//!
+//! Addresses are passed as base58 strings and amounts in lamports as `bigint`. Invalid input
+//! throws an `Error` with the same message as the CLI.
+
+use crate::{build_fund_instruction, deposit_pda, format_sol, parse_pubkey, parse_sol_amount, validate_base58};
+use wasm_bindgen::prelude::*;
+
+/// Deposit PDA of a validator and its bump seed
+#[wasm_bindgen]
+pub struct DepositPda {
+    address: String,
+    bump: u8,
+}
+
+#[wasm_bindgen]
+impl DepositPda {
+    /// Base58 address of the PDA
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    /// Bump seed of the PDA
+    #[wasm_bindgen(getter)]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+/// Account of a funding instruction
+#[wasm_bindgen]
+pub struct AccountMeta {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+#[wasm_bindgen]
+impl AccountMeta {
+    /// Base58 address of the account
+    #[wasm_bindgen(getter)]
+    pub fn pubkey(&self) -> String {
+        self.pubkey.clone()
+    }
+
+    /// Whether the account signs the transaction
+    #[wasm_bindgen(getter, js_name = isSigner)]
+    pub fn is_signer(&self) -> bool {
+        self.is_signer
+    }
+
+    /// Whether the instruction writes the account
+    #[wasm_bindgen(getter, js_name = isWritable)]
+    pub fn is_writable(&self) -> bool {
+        self.is_writable
+    }
+}
+
+/// Funding instruction in the shape of `@solana/web3.js` `TransactionInstruction` fields
+#[wasm_bindgen]
+pub struct FundInstruction {
+    program_id: String,
+    accounts: Vec<AccountMeta>,
+    data: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl FundInstruction {
+    /// Base58 address of the System Program
+    #[wasm_bindgen(getter, js_name = programId)]
+    pub fn program_id(&self) -> String {
+        self.program_id.clone()
+    }
+
+    /// Payer, then the deposit PDA
+    #[wasm_bindgen(getter)]
+    pub fn accounts(&self) -> Vec<AccountMeta> {
+        self.accounts
+            .iter()
+            .map(|meta| AccountMeta { pubkey: meta.pubkey.clone(), is_signer: meta.is_signer, is_writable: meta.is_writable })
+            .collect()
+    }
+
+    /// Serialized system transfer
+    #[wasm_bindgen(getter)]
+    pub fn data(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+}
+
+fn pubkey(address: &str) -> Result<solana_pubkey::Pubkey, JsError> {
+    validate_base58(address).and_then(|_| parse_pubkey(address)).map_err(|e| JsError::new(&e))
+}
+
+/// Derives the deposit PDA of a validator (`depositPda(validator)`)
+#[wasm_bindgen(js_name = depositPda)]
+pub fn deposit_pda_js(validator: &str) -> Result<DepositPda, JsError> {
+    let (address, bump) = deposit_pda(&pubkey(validator)?);
+    Ok(DepositPda { address: address.to_string(), bump })
+}
+
+/// Checks an address and throws with the reason it is invalid (`validateAddress(address)`)
+#[wasm_bindgen(js_name = validateAddress)]
+pub fn validate_address_js(address: &str) -> Result<(), JsError> {
+    pubkey(address).map(|_| ())
+}
+
+/// Parses a SOL amount (`1.5`, `1.5sol` or `1500000000lamports`) into lamports (`parseSolAmount(amount)`)
+#[wasm_bindgen(js_name = parseSolAmount)]
+pub fn parse_sol_amount_js(amount: &str) -> Result<u64, JsError> {
+    parse_sol_amount(amount).map_err(|e| JsError::new(&e))
+}
+
+/// Formats lamports as SOL without rounding (`formatSol(lamports)`)
+#[wasm_bindgen(js_name = formatSol)]
+pub fn format_sol_js(lamports: u64) -> String {
+    format_sol(lamports)
+}
+
+/// Builds the transfer from a wallet to the deposit PDA of a validator (`fundInstruction(payer, validator, lamports)`)
+#[wasm_bindgen(js_name = fundInstruction)]
+pub fn fund_instruction_js(payer: &str, validator: &str, lamports: u64) -> Result<FundInstruction, JsError> {
+    let instruction = build_fund_instruction(&pubkey(payer)?, &pubkey(validator)?, lamports);
+    Ok(FundInstruction {
+        program_id: instruction.program_id.to_string(),
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta { pubkey: meta.pubkey.to_string(), is_signer: meta.is_signer, is_writable: meta.is_writable })
+            .collect(),
+        data: instruction.data,
+    })
+}
@@ -0,0 +1,105 @@
+use crate::rpc::SolanaRpc;
+use crate::wallet::wait_for_confirmation;
+use crate::Error;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use std::time::Duration;
+
+/// Genesis hash of mainnet-beta, where there is no faucet and airdrops are refused up front
+pub const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+
+/// Result of a confirmed airdrop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirdropOutcome {
+    /// Airdrop transaction signature
+    pub signature: String,
+    /// Amount airdropped in lamports
+    pub amount_lamports: u64,
+    /// Balance of the recipient after the airdrop in lamports
+    pub balance_lamports: u64,
+}
+
+/// Returns true if a genesis hash is the one of mainnet-beta
+pub fn is_mainnet(genesis_hash: &Hash) -> bool {
+    genesis_hash.to_string() == MAINNET_GENESIS_HASH
+}
+
+/// Refuses to go on against a mainnet-beta endpoint
+///
+/// The cluster is told apart by its genesis hash, so custom mainnet endpoints are caught as well.
+///
+/// # Arguments
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok for any other cluster, `Error::InvalidInput` for mainnet-beta
+pub async fn check_not_mainnet(rpc: &dyn SolanaRpc) -> Result<(), Error> {
+    let genesis_hash = rpc.get_genesis_hash().await
+        .map_err(|e| Error::from_client_error("Failed to get genesis hash", &e))?;
+    if is_mainnet(&genesis_hash) {
+        return Err(Error::InvalidInput(format!(
+            "{} is a mainnet-beta endpoint; airdrops are only available on devnet, testnet and local test validators",
+            rpc.url()
+        )));
+    }
+    Ok(())
+}
+
+/// Requests an airdrop from the cluster faucet and waits until it is confirmed
+///
+/// # Arguments
+/// * `recipient` - Wallet receiving the lamports
+/// * `amount_lamports` - Amount to request, above 0
+/// * `timeout` - Maximum time to wait for confirmation
+/// * `rpc` - RPC client of a devnet, testnet or local test validator endpoint
+///
+/// # Returns
+/// * `Result<AirdropOutcome, Error>` - Confirmed airdrop, `Error::InvalidInput` on mainnet-beta or for 0 lamports, or error
+pub async fn request_airdrop(recipient: &Pubkey, amount_lamports: u64, timeout: Duration, rpc: &dyn SolanaRpc) -> Result<AirdropOutcome, Error> {
+    if amount_lamports == 0 {
+        return Err(Error::InvalidInput("Airdrop amount must be greater than 0".to_string()));
+    }
+    check_not_mainnet(rpc).await?;
+
+    let signature = rpc.request_airdrop(recipient, amount_lamports).await
+        .map_err(|e| Error::from_client_error("Airdrop request failed", &e))?
+        .to_string();
+    wait_for_confirmation(&signature, timeout, rpc).await?;
+
+    let balance_lamports = rpc.get_balance(recipient).await
+        .map_err(|e| Error::from_client_error("Failed to get wallet balance", &e))?;
+    Ok(AirdropOutcome { signature, amount_lamports, balance_lamports })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+
+    const SOL: u64 = 1_000_000_000;
+
+    #[tokio::test]
+    async fn test_request_airdrop() {
+        let recipient = Pubkey::new_unique();
+        let rpc = MockRpc::new().with_wallet(recipient, SOL);
+
+        let outcome = request_airdrop(&recipient, 2 * SOL, Duration::from_secs(5), &rpc).await.unwrap();
+        assert_eq!(outcome.amount_lamports, 2 * SOL);
+        assert_eq!(outcome.balance_lamports, 3 * SOL);
+
+        let second = request_airdrop(&recipient, SOL, Duration::from_secs(5), &rpc).await.unwrap();
+        assert_ne!(second.signature, outcome.signature);
+        assert!(request_airdrop(&recipient, 0, Duration::from_secs(5), &rpc).await.unwrap_err().is_user_error());
+    }
+
+    #[tokio::test]
+    async fn test_airdrop_refused_on_mainnet() {
+        let recipient = Pubkey::new_unique();
+        let rpc = MockRpc::new().with_genesis_hash(MAINNET_GENESIS_HASH.parse().unwrap());
+
+        let error = request_airdrop(&recipient, SOL, Duration::from_secs(5), &rpc).await.unwrap_err();
+        assert!(error.is_user_error());
+        assert!(error.to_string().contains("mainnet-beta"), "{}", error);
+        assert_eq!(rpc.get_balance(&recipient).await.unwrap(), 0);
+    }
+}
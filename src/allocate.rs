@@ -0,0 +1,324 @@
+use crate::rpc::SolanaRpc;
+use crate::{generate_deposit_pda, get_account_balances, Error};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Weights of a weights file are kept with this many fractional units per 1 (six decimals)
+pub const WEIGHT_SCALE: u64 = 1_000_000;
+
+/// How the budget of `allocate` is split between validators (`--weights`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WeightSource {
+    /// The same weight for every validator
+    Equal,
+    /// Activated stake of each validator's vote account
+    Stake,
+    /// `validator,weight` lines of a CSV file, which also lists the validators
+    File(PathBuf),
+}
+
+impl WeightSource {
+    /// Parses `equal`, `stake` or the path of a weights file
+    ///
+    /// # Arguments
+    /// * `value` - Value of `--weights`
+    ///
+    /// # Returns
+    /// * `WeightSource` - Source of the weights; a file is only read by `read_weight_file`
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "equal" => WeightSource::Equal,
+            "stake" => WeightSource::Stake,
+            _ => WeightSource::File(PathBuf::from(value.trim())),
+        }
+    }
+
+    /// Returns `equal`, `stake` or the path of the weights file
+    pub fn label(&self) -> String {
+        match self {
+            WeightSource::Equal => "equal".to_string(),
+            WeightSource::Stake => "stake".to_string(),
+            WeightSource::File(path) => path.display().to_string(),
+        }
+    }
+}
+
+/// Parses a weight of a weights file, a non-negative decimal like `3` or `0.25`
+///
+/// # Arguments
+/// * `value` - Weight with at most six decimals
+///
+/// # Returns
+/// * `Result<u64, Error>` - Weight in `WEIGHT_SCALE` units, or `Error::InvalidInput`
+pub fn parse_weight(value: &str) -> Result<u64, Error> {
+    let invalid = || Error::InvalidInput(format!("Invalid weight '{}': expected a non-negative number with at most 6 decimals", value));
+    let value = value.trim();
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) || fraction.len() > 6 {
+        return Err(invalid());
+    }
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| invalid())? };
+    let fraction: u64 = format!("{:0<6}", fraction).parse().map_err(|_| invalid())?;
+    whole.checked_mul(WEIGHT_SCALE).and_then(|whole| whole.checked_add(fraction)).ok_or_else(invalid)
+}
+
+/// Reads a CSV file of `validator,weight` lines
+///
+/// Blank lines, lines starting with `#` and a `validator,weight` header are ignored.
+///
+/// # Arguments
+/// * `path` - File path
+///
+/// # Returns
+/// * `Result<Vec<(String, u64)>, Error>` - Validators or aliases and weights in `WEIGHT_SCALE` units, in file order
+pub fn read_weight_file(path: &Path) -> Result<Vec<(String, u64)>, Error> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| Error::InvalidInput(format!("Failed to read weights file {}: {}", path.display(), e)))?;
+
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .filter(|(_, line)| !line.eq_ignore_ascii_case("validator,weight"))
+        .map(|(number, line)| {
+            let (validator, weight) = line
+                .split_once(',')
+                .ok_or_else(|| Error::InvalidInput(format!("{} line {}: expected validator,weight", path.display(), number)))?;
+            let weight = parse_weight(weight).map_err(|e| Error::InvalidInput(format!("{} line {}: {}", path.display(), number, e)))?;
+            Ok((validator.trim().to_string(), weight))
+        })
+        .collect()
+}
+
+/// Reads the activated stake of each validator as its weight
+///
+/// Stake of current and delinquent vote accounts counts alike; a validator without a vote account weighs 0.
+///
+/// # Arguments
+/// * `validators` - Validator identities
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Vec<u64>, Error>` - Activated stake in lamports in the order of `validators`, or error
+pub async fn stake_weights(validators: &[Pubkey], rpc: &dyn SolanaRpc) -> Result<Vec<u64>, Error> {
+    let vote_accounts = rpc.get_vote_accounts().await
+        .map_err(|e| Error::from_client_error("Failed to get vote accounts", &e))?;
+    let mut stakes: HashMap<String, u64> = HashMap::new();
+    for info in vote_accounts.current.iter().chain(&vote_accounts.delinquent) {
+        *stakes.entry(info.node_pubkey.clone()).or_default() += info.activated_stake;
+    }
+    Ok(validators.iter().map(|validator_id| stakes.get(&validator_id.to_string()).copied().unwrap_or(0)).collect())
+}
+
+/// Splits a budget in proportion to weights, to the lamport
+///
+/// Each share is rounded down and the lamports left over go one each to the largest remainders
+/// (earlier entries first on a tie), so the shares always add up to the budget.
+///
+/// # Arguments
+/// * `budget_lamports` - Budget to split
+/// * `weights` - Weight of each entry
+///
+/// # Returns
+/// * `Result<Vec<u64>, Error>` - Share of each entry, or `Error::InvalidInput` if every weight is 0
+pub fn split_budget(budget_lamports: u64, weights: &[u64]) -> Result<Vec<u64>, Error> {
+    let total_weight: u128 = weights.iter().map(|weight| *weight as u128).sum();
+    if total_weight == 0 {
+        return Err(Error::InvalidInput("Every validator has a weight of 0; nothing to allocate".to_string()));
+    }
+    let exact: Vec<u128> = weights.iter().map(|weight| budget_lamports as u128 * *weight as u128).collect();
+    let mut shares: Vec<u64> = exact.iter().map(|exact| (exact / total_weight) as u64).collect();
+
+    let mut by_remainder: Vec<usize> = (0..weights.len()).collect();
+    by_remainder.sort_by_key(|index| std::cmp::Reverse(exact[*index] % total_weight));
+    let left_over = budget_lamports - shares.iter().sum::<u64>();
+    for index in by_remainder.into_iter().take(left_over as usize) {
+        shares[index] += 1;
+    }
+    Ok(shares)
+}
+
+/// Allocation of the budget to one validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Allocation {
+    /// Alias of the validator, if it has one
+    pub name: Option<String>,
+    /// Validator identity
+    pub validator: String,
+    /// Deposit PDA
+    pub pda: String,
+    /// Weight (activated stake in lamports, 1 for equal weights, or `WEIGHT_SCALE` units of a weights file)
+    pub weight: u64,
+    /// Share of the total weight in basis points
+    pub share_basis_points: u64,
+    /// Balance the PDA should hold: its share of the budget
+    pub allocation_lamports: u64,
+    /// Current PDA balance
+    pub balance_lamports: u64,
+    /// Shortfall of the PDA against its allocation, which is what it gets funded
+    pub funding_lamports: u64,
+}
+
+/// Split of a treasury budget between validators (`allocate`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AllocationReport {
+    /// Budget in lamports
+    pub budget_lamports: u64,
+    /// `equal`, `stake` or the path of the weights file
+    pub weights: String,
+    /// One allocation per validator, in input order
+    pub allocations: Vec<Allocation>,
+    /// Sum of the fundings
+    pub funding_lamports: u64,
+    /// Budget that is not needed because PDAs already hold their allocation
+    pub unallocated_lamports: u64,
+}
+
+impl AllocationReport {
+    /// Returns the validators with a funding and their amounts, as `BatchPlan::create` takes them
+    pub fn transfers(&self) -> Result<Vec<(Pubkey, u64)>, Error> {
+        self.allocations
+            .iter()
+            .filter(|allocation| allocation.funding_lamports > 0)
+            .map(|allocation| {
+                let validator_id = allocation
+                    .validator
+                    .parse::<Pubkey>()
+                    .map_err(|_| Error::InvalidInput(format!("Invalid validator '{}'", allocation.validator)))?;
+                Ok((validator_id, allocation.funding_lamports))
+            })
+            .collect()
+    }
+}
+
+/// Splits a budget between validators by weight and funds each PDA up to its allocation
+///
+/// Nothing is sent: the PDA balances are read, and every PDA below its share of the budget is
+/// funded by the difference. SOL a PDA already holds counts toward its allocation, so the part of
+/// the budget it covers stays unallocated.
+///
+/// # Arguments
+/// * `budget_lamports` - Budget to split
+/// * `weights` - `equal`, `stake` or the path of the weights file, for the report
+/// * `validators` - Alias, identity and weight of each validator; an identity may appear only once
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<AllocationReport, Error>` - Allocation, or error if the weights are invalid or the balances cannot be read
+pub async fn allocate(
+    budget_lamports: u64,
+    weights: &str,
+    validators: &[(Option<String>, Pubkey, u64)],
+    rpc: &dyn SolanaRpc,
+) -> Result<AllocationReport, Error> {
+    let mut seen = HashSet::new();
+    if let Some((_, duplicate, _)) = validators.iter().find(|(_, validator_id, _)| !seen.insert(*validator_id)) {
+        return Err(Error::InvalidInput(format!("Validator {} is listed more than once", duplicate)));
+    }
+    let weight_values: Vec<u64> = validators.iter().map(|(_, _, weight)| *weight).collect();
+    let shares = split_budget(budget_lamports, &weight_values)?;
+    let total_weight: u128 = weight_values.iter().map(|weight| *weight as u128).sum();
+
+    let pdas: Vec<Pubkey> = validators.iter().map(|(_, validator_id, _)| generate_deposit_pda(validator_id)).collect();
+    let balances = get_account_balances(&pdas, rpc).await?;
+
+    let allocations: Vec<Allocation> = validators
+        .iter()
+        .zip(pdas.iter().zip(shares.iter().zip(&balances)))
+        .map(|((name, validator_id, weight), (pda, (allocation_lamports, balance_lamports)))| Allocation {
+            name: name.clone(),
+            validator: validator_id.to_string(),
+            pda: pda.to_string(),
+            weight: *weight,
+            share_basis_points: (*weight as u128 * 10_000 / total_weight) as u64,
+            allocation_lamports: *allocation_lamports,
+            balance_lamports: *balance_lamports,
+            funding_lamports: allocation_lamports.saturating_sub(*balance_lamports),
+        })
+        .collect();
+    let funding_lamports = allocations.iter().map(|allocation| allocation.funding_lamports).sum();
+    Ok(AllocationReport {
+        budget_lamports,
+        weights: weights.to_string(),
+        allocations,
+        funding_lamports,
+        unallocated_lamports: budget_lamports - funding_lamports,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+
+    #[test]
+    fn test_parse_weights() {
+        assert_eq!(WeightSource::parse("Stake"), WeightSource::Stake);
+        assert_eq!(WeightSource::parse("equal"), WeightSource::Equal);
+        assert_eq!(WeightSource::parse("weights.csv"), WeightSource::File(PathBuf::from("weights.csv")));
+        assert_eq!(parse_weight("3").unwrap(), 3_000_000);
+        assert_eq!(parse_weight("0.25").unwrap(), 250_000);
+        assert_eq!(parse_weight(".5").unwrap(), 500_000);
+        for invalid in ["-1", "1.2345678", "abc", ".", "", "1e3"] {
+            assert!(parse_weight(invalid).unwrap_err().is_user_error(), "{}", invalid);
+        }
+
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_weights_{}.csv", std::process::id()));
+        fs::write(&path, "validator,weight\n# reserve\nmainnet-01, 2\n\nFjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL,0.5\n").unwrap();
+        assert_eq!(
+            read_weight_file(&path).unwrap(),
+            vec![("mainnet-01".to_string(), 2_000_000), ("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL".to_string(), 500_000)]
+        );
+        fs::write(&path, "mainnet-01,2\nmainnet-02\n").unwrap();
+        assert!(read_weight_file(&path).unwrap_err().to_string().contains("line 2: expected validator,weight"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_split_budget() {
+        assert_eq!(split_budget(100, &[1, 1, 1]).unwrap(), vec![34, 33, 33]);
+        assert_eq!(split_budget(10, &[0, 3, 1]).unwrap(), vec![0, 8, 2]);
+        // Remainders 2/3 beat 1/3, whatever the order
+        assert_eq!(split_budget(5, &[1, 2]).unwrap(), vec![2, 3]);
+        let stakes = [400_000_000_000_000_000, 123_456_789_000_000, 7];
+        assert_eq!(split_budget(500_000_000_000, &stakes).unwrap().iter().sum::<u64>(), 500_000_000_000);
+        assert!(split_budget(100, &[0, 0]).unwrap_err().is_user_error());
+    }
+
+    #[tokio::test]
+    async fn test_allocate() {
+        let (first, second, third) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let rpc = MockRpc::new()
+            .with_vote_account(first, 100, 3_000_000_000_000, false)
+            .with_vote_account(second, 100, 1_000_000_000_000, true)
+            .with_wallet(generate_deposit_pda(&first), 100_000_000_000)
+            .with_wallet(generate_deposit_pda(&second), 30_000_000_000);
+
+        let validators = [first, second, third];
+        let stakes = stake_weights(&validators, &rpc).await.unwrap();
+        assert_eq!(stakes, vec![3_000_000_000_000, 1_000_000_000_000, 0]);
+
+        let weighted: Vec<_> = validators.iter().zip(&stakes).map(|(validator_id, stake)| (None, *validator_id, *stake)).collect();
+        let report = allocate(200_000_000_000, "stake", &weighted, &rpc).await.unwrap();
+        let rows: Vec<(u64, u64, u64, u64)> = report
+            .allocations
+            .iter()
+            .map(|allocation| (allocation.share_basis_points, allocation.allocation_lamports, allocation.balance_lamports, allocation.funding_lamports))
+            .collect();
+        // The PDAs lack 50 of their 150 SOL and 20 of their 50 SOL; the SOL they hold stays unallocated
+        assert_eq!(
+            rows,
+            vec![(7_500, 150_000_000_000, 100_000_000_000, 50_000_000_000), (2_500, 50_000_000_000, 30_000_000_000, 20_000_000_000), (0, 0, 0, 0)]
+        );
+        assert_eq!((report.funding_lamports, report.unallocated_lamports), (70_000_000_000, 130_000_000_000));
+        assert_eq!(report.transfers().unwrap(), vec![(first, 50_000_000_000), (second, 20_000_000_000)]);
+
+        let repeated = [(None, first, 1), (Some("again".to_string()), first, 1)];
+        assert!(allocate(1, "equal", &repeated, &rpc).await.unwrap_err().to_string().contains("more than once"));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::Error;
+use std::fmt;
+
+pub use dz_validator_pda_core::{format_sol, format_token_amount, LAMPORTS_PER_SOL};
+
+/// Amount to transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Amount {
+    /// Exact amount in lamports
+    Lamports(u64),
+    /// Whole payer balance minus the fee and the rent-exempt reserve
+    All,
+}
+
+impl Amount {
+    /// Resolves the amount to lamports
+    ///
+    /// # Arguments
+    /// * `payer_balance` - Current payer balance in lamports
+    /// * `fee_lamports` - Transaction fee in lamports
+    /// * `rent_reserve_lamports` - Balance the payer must keep
+    ///
+    /// # Returns
+    /// * `Result<u64, Error>` - Amount in lamports, or `Error::InsufficientFunds` if nothing is left for `ALL`
+    pub fn resolve(&self, payer_balance: u64, fee_lamports: u64, rent_reserve_lamports: u64) -> Result<u64, Error> {
+        match self {
+            Amount::Lamports(lamports) => Ok(*lamports),
+            Amount::All => {
+                let available = payer_balance
+                    .saturating_sub(fee_lamports)
+                    .saturating_sub(rent_reserve_lamports);
+                if available == 0 {
+                    return Err(Error::InsufficientFunds(format!(
+                        "Nothing to transfer: payer balance {} lamports does not exceed fee {} + rent-exempt reserve {} lamports",
+                        payer_balance, fee_lamports, rent_reserve_lamports
+                    )));
+                }
+                Ok(available)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Amount::Lamports(lamports) => write!(f, "{} SOL ({} lamports)", format_sol(*lamports), lamports),
+            Amount::All => f.write_str("ALL"),
+        }
+    }
+}
+
+/// Parses an amount string
+///
+/// Accepted forms: `1.5` and `1.5sol` (SOL), `1500000000lamports` (lamports) and `ALL`.
+/// SOL amounts are converted with exact integer math, without floating point rounding.
+///
+/// # Arguments
+/// * `amount_str` - Amount string
+///
+/// # Returns
+/// * `Result<Amount, Error>` - Parsed amount or `Error::InvalidInput`
+pub fn parse_amount(amount_str: &str) -> Result<Amount, Error> {
+    if amount_str.trim().eq_ignore_ascii_case("all") {
+        return Ok(Amount::All);
+    }
+    dz_validator_pda_core::parse_sol_amount(amount_str).map(Amount::Lamports).map_err(Error::InvalidInput)
+}
+
+/// Parses an SPL token amount in whole tokens (e.g. "2.5") into base units of a mint
+///
+/// # Arguments
+/// * `amount_str` - Amount string
+/// * `decimals` - Decimal places of the mint
+///
+/// # Returns
+/// * `Result<u64, Error>` - Amount in base units, or `Error::InvalidInput` if it is malformed,
+///   zero or more precise than the mint allows
+pub fn parse_token_amount(amount_str: &str, decimals: u8) -> Result<u64, Error> {
+    dz_validator_pda_core::parse_token_amount(amount_str, decimals).map_err(Error::InvalidInput)
+}
+
+/// Parses a percentage flag into basis points (hundredths of a percent)
+///
+/// # Arguments
+/// * `value` - Percentage above 0 and at most 100 with up to two decimals, optionally with a `%` suffix
+/// * `flag` - Flag name for the error message
+///
+/// # Returns
+/// * `Result<u64, Error>` - Basis points from 1 to 10,000, or `Error::InvalidInput`
+pub fn parse_percent(value: &str, flag: &str) -> Result<u64, Error> {
+    let invalid = || Error::InvalidInput(format!("Invalid --{} '{}': expected a percentage like 95 or 99.5", flag, value));
+    let percent = value.trim().trim_end_matches('%');
+    let (whole, fraction) = percent.split_once('.').unwrap_or((percent, ""));
+    if whole.is_empty() || fraction.len() > 2 || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let whole = whole.parse::<u64>().map_err(|_| invalid())?;
+    let fraction = format!("{:0<2}", fraction).parse::<u64>().map_err(|_| invalid())?;
+    match whole.checked_mul(100).and_then(|basis_points| basis_points.checked_add(fraction)) {
+        Some(basis_points) if basis_points > 0 && basis_points <= 10_000 => Ok(basis_points),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount_sol() {
+        assert_eq!(parse_amount("1.5"), Ok(Amount::Lamports(1_500_000_000)));
+        assert_eq!(parse_amount("1.5sol"), Ok(Amount::Lamports(1_500_000_000)));
+        assert_eq!(parse_amount("1.5 SOL"), Ok(Amount::Lamports(1_500_000_000)));
+        assert_eq!(parse_amount("2"), Ok(Amount::Lamports(2_000_000_000)));
+        assert_eq!(parse_amount(".25"), Ok(Amount::Lamports(250_000_000)));
+        assert_eq!(parse_amount("0.000000001"), Ok(Amount::Lamports(1)));
+    }
+
+    #[test]
+    fn test_parse_amount_is_exact() {
+        // 0.1 + 0.2 style values must not lose precision
+        assert_eq!(parse_amount("0.3"), Ok(Amount::Lamports(300_000_000)));
+        assert_eq!(parse_amount("1.123456789"), Ok(Amount::Lamports(1_123_456_789)));
+        assert_eq!(parse_amount("18446744073.709551615"), Ok(Amount::Lamports(u64::MAX)));
+    }
+
+    #[test]
+    fn test_parse_amount_lamports_and_all() {
+        assert_eq!(parse_amount("1500000000lamports"), Ok(Amount::Lamports(1_500_000_000)));
+        assert_eq!(parse_amount("42 lamports"), Ok(Amount::Lamports(42)));
+        assert_eq!(parse_amount("ALL"), Ok(Amount::All));
+        assert_eq!(parse_amount("all"), Ok(Amount::All));
+    }
+
+    #[test]
+    fn test_parse_amount_invalid() {
+        for invalid in ["", "abc", "-1", "1.5.5", "1e9", "1.5lamports", "1.0000000001", "18446744074", "0", "0lamports", "sol"] {
+            let result = parse_amount(invalid);
+            assert!(result.is_err(), "Amount '{}' should be invalid", invalid);
+            assert!(result.unwrap_err().is_user_error());
+        }
+    }
+
+    #[test]
+    fn test_resolve_all() {
+        assert_eq!(Amount::All.resolve(10_000_000, 5_000, 890_880), Ok(9_104_120));
+        assert_eq!(Amount::Lamports(7).resolve(0, 5_000, 0), Ok(7));
+        assert!(Amount::All.resolve(5_000, 5_000, 890_880).is_err());
+    }
+
+    #[test]
+    fn test_format_sol() {
+        assert_eq!(format_sol(0), "0");
+        assert_eq!(format_sol(1_500_000_000), "1.5");
+        assert_eq!(format_sol(1), "0.000000001");
+        assert_eq!(format_sol(2_000_000_000), "2");
+    }
+
+    #[test]
+    fn test_token_amounts_follow_mint_decimals() {
+        assert_eq!(parse_token_amount("2.5", 6), Ok(2_500_000));
+        assert_eq!(parse_token_amount("7", 0), Ok(7));
+        assert!(parse_token_amount("1.5", 0).is_err());
+        assert!(parse_token_amount("0.0000001", 6).is_err());
+        assert!(parse_token_amount("0", 6).is_err());
+        assert!(parse_token_amount("1", 20).is_err());
+
+        assert_eq!(format_token_amount(2_500_000, 6), "2.5");
+        assert_eq!(format_token_amount(1, 6), "0.000001");
+        assert_eq!(format_token_amount(7, 0), "7");
+    }
+}
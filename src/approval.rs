@@ -0,0 +1,251 @@
+use crate::config::write_atomic;
+use crate::{generate_deposit_pda, Error};
+use serde::{Deserialize, Serialize};
+use solana_sdk::hash::hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use std::fs;
+use std::path::Path;
+
+/// Version of the plan file format
+pub const PLAN_VERSION: u32 = 1;
+
+/// Default lifetime of an approval (24 hours)
+pub const DEFAULT_APPROVAL_TTL_SECS: u64 = 86_400;
+
+/// Funding prepared by one operator for approval by another
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FundingPlan {
+    /// Plan file format version
+    pub version: u32,
+    /// Validator identity pubkey
+    pub validator: String,
+    /// Deposit PDA of the validator
+    pub pda: String,
+    /// Exact amount to transfer in lamports
+    pub amount_lamports: u64,
+    /// Funding wallet pubkey; `apply` must sign as this wallet
+    pub funder: String,
+    /// Unix timestamp of plan creation
+    pub created_at: u64,
+}
+
+/// Countersignature of a plan by an approver
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Approval {
+    /// Digest of the approved plan (see `FundingPlan::digest`)
+    pub plan_digest: String,
+    /// Approver pubkey
+    pub approver: String,
+    /// Unix timestamp after which the approval is no longer valid
+    pub expires_at: u64,
+    /// Base58 signature of the approver over the digest and expiry
+    pub signature: String,
+}
+
+impl FundingPlan {
+    /// Creates a plan
+    ///
+    /// # Arguments
+    /// * `validator_id` - Validator identity pubkey
+    /// * `funder` - Funding wallet pubkey
+    /// * `amount_lamports` - Exact amount to transfer
+    /// * `created_at` - Current Unix timestamp
+    pub fn new(validator_id: &Pubkey, funder: &Pubkey, amount_lamports: u64, created_at: u64) -> Self {
+        FundingPlan {
+            version: PLAN_VERSION,
+            validator: validator_id.to_string(),
+            pda: generate_deposit_pda(validator_id).to_string(),
+            amount_lamports,
+            funder: funder.to_string(),
+            created_at,
+        }
+    }
+
+    /// Returns the base58 SHA-256 digest of the plan
+    ///
+    /// Every field is covered, so any edit of the plan file invalidates its approvals.
+    pub fn digest(&self) -> String {
+        let canonical = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            self.version, self.validator, self.pda, self.amount_lamports, self.funder, self.created_at
+        );
+        hash(canonical.as_bytes()).to_string()
+    }
+
+    /// Parses the validator and funding wallet and checks the PDA belongs to the validator
+    ///
+    /// # Returns
+    /// * `Result<(Pubkey, Pubkey), Error>` - Validator and funder pubkeys, or `Error::InvalidInput`
+    pub fn accounts(&self) -> Result<(Pubkey, Pubkey), Error> {
+        let invalid = |field: &str, value: &str| Error::InvalidInput(format!("Invalid {} '{}' in funding plan", field, value));
+        if self.version != PLAN_VERSION {
+            return Err(Error::InvalidInput(format!("Unsupported funding plan version {}", self.version)));
+        }
+        let validator_id = self.validator.parse::<Pubkey>().map_err(|_| invalid("validator", &self.validator))?;
+        let funder = self.funder.parse::<Pubkey>().map_err(|_| invalid("funder", &self.funder))?;
+        if generate_deposit_pda(&validator_id).to_string() != self.pda {
+            return Err(invalid("pda", &self.pda));
+        }
+        Ok((validator_id, funder))
+    }
+}
+
+/// Message signed by an approver
+fn approval_message(plan_digest: &str, expires_at: u64) -> Vec<u8> {
+    format!("dz_validator_pda funding approval\nplan: {}\nexpires: {}", plan_digest, expires_at).into_bytes()
+}
+
+/// Approves a plan
+///
+/// The funding wallet of the plan cannot approve it, so a plan always needs a second key.
+///
+/// # Arguments
+/// * `plan` - Plan to approve
+/// * `approver` - Approver signer
+/// * `ttl_secs` - How long the approval stays valid
+/// * `now` - Current Unix timestamp
+///
+/// # Returns
+/// * `Result<Approval, Error>` - Approval token or `Error::InvalidInput`
+pub fn approve_plan(plan: &FundingPlan, approver: &dyn Signer, ttl_secs: u64, now: u64) -> Result<Approval, Error> {
+    let (_, funder) = plan.accounts()?;
+    if approver.pubkey() == funder {
+        return Err(Error::InvalidInput(
+            "The funding wallet of a plan cannot approve it: use a different approver key".to_string(),
+        ));
+    }
+
+    let plan_digest = plan.digest();
+    let expires_at = now.saturating_add(ttl_secs);
+    let signature = approver
+        .try_sign_message(&approval_message(&plan_digest, expires_at))
+        .map_err(|e| Error::Keypair(format!("Failed to sign approval: {}", e)))?;
+
+    Ok(Approval {
+        plan_digest,
+        approver: approver.pubkey().to_string(),
+        expires_at,
+        signature: signature.to_string(),
+    })
+}
+
+/// Checks an approval against a plan without RPC access
+///
+/// # Arguments
+/// * `plan` - Plan to apply
+/// * `approval` - Approval token
+/// * `approvers` - Allowed approvers (empty allows any key other than the funding wallet)
+/// * `now` - Current Unix timestamp
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok for a valid approval, otherwise `Error::FundingCancelled`
+pub fn verify_approval(plan: &FundingPlan, approval: &Approval, approvers: &[Pubkey], now: u64) -> Result<(), Error> {
+    let (_, funder) = plan.accounts()?;
+    let rejected = |reason: String| Error::FundingCancelled(format!("Approval rejected: {}", reason));
+
+    if approval.plan_digest != plan.digest() {
+        return Err(rejected("it was issued for a different or modified plan".to_string()));
+    }
+    let approver = approval
+        .approver
+        .parse::<Pubkey>()
+        .map_err(|_| rejected(format!("invalid approver '{}'", approval.approver)))?;
+    if approver == funder {
+        return Err(rejected("the funding wallet cannot approve its own plan".to_string()));
+    }
+    if !approvers.is_empty() && !approvers.contains(&approver) {
+        return Err(rejected(format!("{} is not a configured approver", approver)));
+    }
+    let valid_signature = approval
+        .signature
+        .parse::<Signature>()
+        .is_ok_and(|signature| signature.verify(approver.as_ref(), &approval_message(&approval.plan_digest, approval.expires_at)));
+    if !valid_signature {
+        return Err(rejected(format!("the signature does not match approver {}", approver)));
+    }
+    if now >= approval.expires_at {
+        return Err(rejected(format!("it expired at {}", approval.expires_at)));
+    }
+    Ok(())
+}
+
+/// Reads a plan or approval JSON file
+///
+/// # Arguments
+/// * `path` - File path
+///
+/// # Returns
+/// * `Result<T, Error>` - Parsed file or `Error::InvalidInput`
+pub fn read_json_file<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, Error> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| Error::InvalidInput(format!("Failed to read {}: {}", path.display(), e)))?;
+    serde_json::from_str(&contents).map_err(|e| Error::InvalidInput(format!("Failed to parse {}: {}", path.display(), e)))
+}
+
+/// Writes a plan or approval as pretty JSON
+///
+/// # Arguments
+/// * `path` - File path
+/// * `value` - Plan or approval
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if the file was written
+pub fn write_json_file<T: Serialize>(path: &Path, value: &T) -> Result<(), Error> {
+    let contents = serde_json::to_string_pretty(value).map_err(|e| Error::Io(format!("Failed to serialize {}: {}", path.display(), e)))?;
+    write_atomic(path, contents.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+
+    fn plan(funder: &Keypair) -> FundingPlan {
+        FundingPlan::new(&Pubkey::new_unique(), &funder.pubkey(), 1_500_000_000, 1_000)
+    }
+
+    #[test]
+    fn test_approval_roundtrip() {
+        let (funder, approver) = (Keypair::new(), Keypair::new());
+        let plan = plan(&funder);
+        let approval = approve_plan(&plan, &approver, 600, 2_000).unwrap();
+
+        assert_eq!(approval.expires_at, 2_600);
+        assert!(verify_approval(&plan, &approval, &[], 2_599).is_ok());
+        assert!(verify_approval(&plan, &approval, &[approver.pubkey()], 2_000).is_ok());
+
+        // Expired, not a configured approver
+        assert!(verify_approval(&plan, &approval, &[], 2_600).is_err());
+        assert!(verify_approval(&plan, &approval, &[Pubkey::new_unique()], 2_000).is_err());
+    }
+
+    #[test]
+    fn test_modified_plan_or_forged_approval_is_rejected() {
+        let (funder, approver) = (Keypair::new(), Keypair::new());
+        let plan = plan(&funder);
+        let approval = approve_plan(&plan, &approver, 600, 2_000).unwrap();
+
+        let mut larger = plan.clone();
+        larger.amount_lamports += 1;
+        assert!(matches!(verify_approval(&larger, &approval, &[], 2_000), Err(Error::FundingCancelled(_))));
+
+        // Extending the expiry breaks the signature
+        let extended = Approval { expires_at: approval.expires_at + 600, ..approval.clone() };
+        assert!(verify_approval(&plan, &extended, &[], 2_000).is_err());
+
+        let impostor = Approval { approver: Keypair::new().pubkey().to_string(), ..approval };
+        assert!(verify_approval(&plan, &impostor, &[], 2_000).is_err());
+    }
+
+    #[test]
+    fn test_funder_cannot_approve_own_plan() {
+        let funder = Keypair::new();
+        let plan = plan(&funder);
+        assert!(approve_plan(&plan, &funder, 600, 2_000).unwrap_err().is_user_error());
+
+        let mut tampered = plan.clone();
+        tampered.pda = Pubkey::new_unique().to_string();
+        assert!(tampered.accounts().is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use crate::Error;
+
+/// Options that take a value
+pub const VALUE_FLAGS: &[&str] = &["require-recent-votes", "config", "profile", "new", "old", "push-metrics", "metrics-job", "output", "sort", "file", "blockhash", "nonce-account", "nonce-authority", "init-timeout", "fee-payer", "keypair", "month", "metrics-port", "out", "approver", "approval-ttl", "require-approval-file", "seed-prefix", "program-id", "trace-id", "commitment", "priority-fee", "explorer", "window", "rate-limit", "rate-burst", "gossip-cache-ttl", "gossip-cache-file", "tx-version", "alt", "pairs", "mint", "amount", "identity-keypair", "at-epoch", "memo", "url", "concurrency", "refresh", "min-activated-stake", "max-commission", "require-uptime", "lookback-epochs", "event", "status", "limit", "audit-log", "idempotency-key", "duplicate-window", "from-identity-file", "lang", "rpc-timeout", "deadline", "rpc-header", "rpc-proxy", "show-fiat", "state", "resume", "seed-spec", "squads", "reference", "max-slot-lag", "receipt", "backend", "geyser-endpoint", "interval", "grace", "approve", "days", "outfile", "starts-with", "ends-with", "starts-and-ends-with", "threads", "format", "epochs", "share", "budget", "weights", "payer"];
+
+/// Options that do not take a value
+pub const SWITCH_FLAGS: &[&str] = &["skip-gossip-check", "require-vote-account", "sign-only", "nice", "verbose", "quiet", "assume-yes", "yes", "help", "strict", "force", "consistent-snapshot", "qr", "at-epoch-boundary", "reject-delinquent", "from-local-rpc", "override-budget", "timing", "stdin", "ignore-case", "verify-delta", "pda"];
+
+/// Single-letter switches and the flags they stand for (`-vv` is `--verbose --verbose`)
+pub const SHORT_FLAGS: &[(char, &str)] = &[('v', "verbose"), ('q', "quiet"), ('h', "help"), ('y', "yes")];
+
+/// Command line arguments split into positional parameters and `--flags`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedArgs {
+    /// Positional parameters in order (operation, validator address, ...)
+    pub positionals: Vec<String>,
+    flags: Vec<(String, Option<String>)>,
+}
+
+impl ParsedArgs {
+    /// Splits arguments into positionals and flags
+    ///
+    /// Flags are written as `--name`, `--name value` or `--name=value`; switches listed in
+    /// `SHORT_FLAGS` can also be written as `-v`, `-vv` or `-q`.
+    /// Everything after a bare `--` is treated as positional.
+    ///
+    /// # Arguments
+    /// * `args` - Arguments without the program name
+    /// * `value_flags` - Flags that take a value
+    /// * `switch_flags` - Flags that do not take a value
+    ///
+    /// # Returns
+    /// * `Result<ParsedArgs, Error>` - Parsed arguments or `Error::InvalidInput` for unknown or incomplete flags
+    pub fn parse(args: &[String], value_flags: &[&str], switch_flags: &[&str]) -> Result<Self, Error> {
+        let mut parsed = ParsedArgs::default();
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            if arg == "--" {
+                parsed.positionals.extend(iter.by_ref().cloned());
+                break;
+            }
+
+            let Some(flag) = arg.strip_prefix("--") else {
+                match short_switches(arg, switch_flags) {
+                    Some(names) => parsed.flags.extend(names.into_iter().map(|name| (name.to_string(), None))),
+                    None => parsed.positionals.push(arg.clone()),
+                }
+                continue;
+            };
+
+            let (name, inline_value) = match flag.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (flag, None),
+            };
+
+            if value_flags.contains(&name) {
+                let value = match inline_value {
+                    Some(value) => value,
+                    None => iter
+                        .next()
+                        .cloned()
+                        .ok_or_else(|| Error::InvalidInput(format!("Option --{} requires a value", name)))?,
+                };
+                parsed.flags.push((name.to_string(), Some(value)));
+            } else if switch_flags.contains(&name) {
+                if inline_value.is_some() {
+                    return Err(Error::InvalidInput(format!("Option --{} does not take a value", name)));
+                }
+                parsed.flags.push((name.to_string(), None));
+            } else {
+                return Err(Error::InvalidInput(format!("Unknown option '--{}'", name)));
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Returns the positional parameter at `index`
+    pub fn positional(&self, index: usize) -> Option<&str> {
+        self.positionals.get(index).map(String::as_str)
+    }
+
+    /// Returns true if the flag was given
+    pub fn has(&self, name: &str) -> bool {
+        self.flags.iter().any(|(flag, _)| flag == name)
+    }
+
+    /// Returns how many times the flag was given
+    pub fn count(&self, name: &str) -> usize {
+        self.flags.iter().filter(|(flag, _)| flag == name).count()
+    }
+
+    /// Returns the last value given for the flag
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.flags
+            .iter()
+            .rev()
+            .find(|(flag, _)| flag == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// Returns all values given for a repeatable flag, in order
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.flags
+            .iter()
+            .filter(|(flag, _)| flag == name)
+            .filter_map(|(_, value)| value.as_deref())
+            .collect()
+    }
+
+    /// Parses the value of a flag
+    ///
+    /// # Arguments
+    /// * `name` - Flag name without the leading dashes
+    ///
+    /// # Returns
+    /// * `Result<Option<T>, Error>` - Parsed value, None if the flag was not given, or `Error::InvalidInput`
+    pub fn parsed_value<T: std::str::FromStr>(&self, name: &str) -> Result<Option<T>, Error> {
+        match self.value(name) {
+            None => Ok(None),
+            Some(value) => value
+                .parse::<T>()
+                .map(Some)
+                .map_err(|_| Error::InvalidInput(format!("Invalid value for --{}: {}", name, value))),
+        }
+    }
+}
+
+/// Expands `-vq`-style arguments into switch names; other arguments (e.g. `-1`) stay positional
+fn short_switches(arg: &str, switch_flags: &[&str]) -> Option<Vec<&'static str>> {
+    let letters = arg.strip_prefix('-').filter(|letters| !letters.is_empty())?;
+    letters
+        .chars()
+        .map(|letter| {
+            SHORT_FLAGS
+                .iter()
+                .find(|(short, name)| *short == letter && switch_flags.contains(name))
+                .map(|(_, name)| *name)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_positionals_and_flags() {
+        let args = strings(&["pda-fund-address", "--skip-gossip-check", "Validator", "--require-recent-votes", "150", "key.json", "--x=1", "1.5"]);
+        let parsed = ParsedArgs::parse(&args, &["require-recent-votes", "x"], &["skip-gossip-check"]).unwrap();
+
+        assert_eq!(parsed.positionals, strings(&["pda-fund-address", "Validator", "key.json", "1.5"]));
+        assert!(parsed.has("skip-gossip-check"));
+        assert_eq!(parsed.value("require-recent-votes"), Some("150"));
+        assert_eq!(parsed.parsed_value::<u64>("require-recent-votes").unwrap(), Some(150));
+        assert_eq!(parsed.value("x"), Some("1"));
+        assert!(!parsed.has("missing"));
+    }
+
+    #[test]
+    fn test_parse_repeated_and_separator() {
+        let args = strings(&["--h", "a", "--h=b", "--", "--not-a-flag"]);
+        let parsed = ParsedArgs::parse(&args, &["h"], &[]).unwrap();
+
+        assert_eq!(parsed.values("h"), vec!["a", "b"]);
+        assert_eq!(parsed.value("h"), Some("b"));
+        assert_eq!(parsed.positionals, strings(&["--not-a-flag"]));
+    }
+
+    #[test]
+    fn test_parse_short_switches() {
+        let args = strings(&["-vv", "pda-balance", "-q", "-1", "-x"]);
+        let parsed = ParsedArgs::parse(&args, &[], &["verbose", "quiet"]).unwrap();
+
+        assert_eq!(parsed.count("verbose"), 2);
+        assert!(parsed.has("quiet"));
+        assert_eq!(parsed.positionals, strings(&["pda-balance", "-1", "-x"]));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(ParsedArgs::parse(&strings(&["--unknown"]), &[], &[]).is_err());
+        assert!(ParsedArgs::parse(&strings(&["--value"]), &["value"], &[]).is_err());
+        assert!(ParsedArgs::parse(&strings(&["--switch=1"]), &[], &["switch"]).is_err());
+
+        let parsed = ParsedArgs::parse(&strings(&["--value", "abc"]), &["value"], &[]).unwrap();
+        assert!(parsed.parsed_value::<u64>("value").unwrap_err().is_user_error());
+    }
+}
@@ -0,0 +1,615 @@
+use crate::config::default_data_dir;
+use crate::fees::civil_from_days;
+use crate::{Error, FundingAttempt, FundingOutcome};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Event name recorded when the funding wallet is rotated
+pub const EVENT_WALLET_ROTATE: &str = "wallet-rotate";
+
+/// Event name recorded when the first funding from a rotated wallet is verified
+pub const EVENT_WALLET_VERIFY: &str = "wallet-verify";
+
+/// Event name recorded when a PDA funding transaction is sent
+pub const EVENT_PDA_FUND: &str = "pda-fund";
+
+/// Event name recorded when an SPL token deposit to a PDA's token account is sent
+pub const EVENT_PDA_FUND_TOKEN: &str = "pda-fund-token";
+
+/// Event name recorded when a funding creates the deposit PDA
+pub const EVENT_DEPOSIT_CREATED: &str = "deposit-created";
+
+/// Status of a successful operation
+pub const STATUS_SUCCESS: &str = "success";
+
+/// Status of a failed operation
+pub const STATUS_FAILED: &str = "failed";
+
+/// Status of a funding that was signed and is about to be sent; a later entry with its signature records the outcome
+pub const STATUS_PENDING: &str = "pending";
+
+/// Status of a funding that was sent but whose outcome is unknown (lost connection, confirmation timeout)
+pub const STATUS_UNCONFIRMED: &str = "unconfirmed";
+
+/// One line of the append-only JSONL audit log
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    /// Event name (e.g. "wallet-rotate")
+    pub event: String,
+    /// Outcome ("success", "failed", "pending" or "unconfirmed")
+    pub status: String,
+    /// Wallet paying for or sending the transfer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payer: Option<String>,
+    /// Receiving wallet (for transfers that do not go to a PDA)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    /// Validator identity of a PDA funding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<String>,
+    /// Amount transferred in lamports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_lamports: Option<u64>,
+    /// Transaction fee in lamports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_lamports: Option<u64>,
+    /// Transaction signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Time from sending the transaction until it was confirmed, in milliseconds (when the tool waited for it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmation_ms: Option<u64>,
+    /// Additional details or error message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Memo attached to the transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    /// Trace id of the run that wrote the entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// Idempotency key of a funding (`--idempotency-key`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// Epoch a funding was sent in (recorded when a budget is configured)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch: Option<u64>,
+}
+
+impl AuditEntry {
+    /// Creates an entry for the current time
+    ///
+    /// # Arguments
+    /// * `event` - Event name
+    /// * `status` - Outcome of the event
+    pub fn new(event: &str, status: &str) -> Self {
+        AuditEntry {
+            timestamp: crate::unix_timestamp(),
+            event: event.to_string(),
+            status: status.to_string(),
+            trace_id: crate::trace::trace_id().map(str::to_string),
+            ..AuditEntry::default()
+        }
+    }
+}
+
+static AUDIT_LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Returns the default audit log path (`~/.local/share/dz_validator_pda/audit.log` on Linux)
+pub fn default_audit_log_path() -> PathBuf {
+    default_data_dir().join("audit.log")
+}
+
+/// Sets the audit log of this process (`--audit-log` or config `audit_log`)
+///
+/// Must be called before the first entry is written or read; later calls are ignored.
+///
+/// # Arguments
+/// * `path` - Audit log path
+pub fn set_audit_log_path(path: PathBuf) {
+    let _ = AUDIT_LOG_PATH.set(path);
+}
+
+/// Returns the audit log of this process: the path set with `set_audit_log_path`, or the default
+pub fn audit_log_path() -> PathBuf {
+    AUDIT_LOG_PATH.get().cloned().unwrap_or_else(default_audit_log_path)
+}
+
+/// Appends an entry to the audit log, creating the file if needed
+///
+/// # Arguments
+/// * `path` - Audit log path
+/// * `entry` - Entry to append
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if the entry was written
+pub fn append_entry(path: &Path, entry: &AuditEntry) -> Result<(), Error> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|e| Error::Io(format!("Failed to create directory {}: {}", parent.display(), e)))?;
+    }
+
+    let line = serde_json::to_string(entry)
+        .map_err(|e| Error::Io(format!("Failed to serialize audit entry: {}", e)))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| Error::Io(format!("Failed to open audit log {}: {}", path.display(), e)))?;
+    writeln!(file, "{}", line)
+        .map_err(|e| Error::Io(format!("Failed to write audit log {}: {}", path.display(), e)))
+}
+
+/// Reads all entries from the audit log
+///
+/// # Arguments
+/// * `path` - Audit log path
+///
+/// # Returns
+/// * `Result<Vec<AuditEntry>, Error>` - Entries in file order (empty if the log does not exist)
+pub fn read_entries(path: &Path) -> Result<Vec<AuditEntry>, Error> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(path)
+        .map_err(|e| Error::Io(format!("Failed to read audit log {}: {}", path.display(), e)))?;
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                Error::Io(format!("Invalid audit log entry at {}:{}: {}", path.display(), index + 1, e))
+            })
+        })
+        .collect()
+}
+
+/// Returns true if the wallet was rotated in and no funding from it has been verified yet
+///
+/// # Arguments
+/// * `entries` - Audit log entries in file order
+/// * `wallet` - Wallet public key
+pub fn wallet_needs_verification(entries: &[AuditEntry], wallet: &str) -> bool {
+    entries
+        .iter()
+        .rev()
+        .find(|entry| {
+            entry.status == STATUS_SUCCESS
+                && ((entry.event == EVENT_WALLET_ROTATE && entry.recipient.as_deref() == Some(wallet))
+                    || (entry.event == EVENT_WALLET_VERIFY && entry.payer.as_deref() == Some(wallet)))
+        })
+        .is_some_and(|entry| entry.event == EVENT_WALLET_ROTATE)
+}
+
+/// Builds the audit entry of a PDA funding: its amount, fee and signature, or the error of a failed attempt
+///
+/// A failure after the transaction was signed keeps the payer, amount and signature of the attempt. If the
+/// transaction may still have landed (lost connection, confirmation timeout) its status is "unconfirmed",
+/// so the duplicate check and the budget keep counting it until it is settled on chain.
+///
+/// # Arguments
+/// * `validator_id` - Funded validator
+/// * `deposit_key` - Deposit PDA of the validator
+/// * `result` - Result of the funding
+/// * `attempt` - Signed transaction of the funding, if it got that far
+///
+/// # Returns
+/// * `Option<AuditEntry>` - Entry, or None for a cancelled funding (declined confirmation, failed pre-flight checks) that sent nothing
+pub fn funding_entry(
+    validator_id: &Pubkey,
+    deposit_key: &Pubkey,
+    result: &Result<FundingOutcome, Error>,
+    attempt: Option<&FundingAttempt>,
+) -> Option<AuditEntry> {
+    let entry = match result {
+        Ok(outcome) => AuditEntry {
+            validator: Some(validator_id.to_string()),
+            payer: Some(outcome.payer.clone()),
+            amount_lamports: Some(outcome.amount_lamports),
+            fee_lamports: Some(outcome.fee_lamports),
+            signature: Some(outcome.signature.clone()),
+            message: Some(format!("PDA {}", deposit_key)),
+            memo: outcome.memo.clone(),
+            epoch: outcome.epoch,
+            ..AuditEntry::new(EVENT_PDA_FUND, STATUS_SUCCESS)
+        },
+        Err(Error::FundingCancelled(_) | Error::NotInGossip(_) | Error::StrictWarning(_)) => return None,
+        Err(e) => {
+            let status = match (attempt, e) {
+                (Some(_), Error::RpcUnavailable(_) | Error::ConfirmationTimeout(_)) => STATUS_UNCONFIRMED,
+                _ => STATUS_FAILED,
+            };
+            AuditEntry {
+                validator: Some(validator_id.to_string()),
+                payer: attempt.map(|attempt| attempt.payer.clone()),
+                amount_lamports: attempt.map(|attempt| attempt.amount_lamports),
+                signature: attempt.map(|attempt| attempt.signature.clone()),
+                message: Some(format!("PDA {}: {}", deposit_key, e)),
+                epoch: attempt.and_then(|attempt| attempt.epoch),
+                ..AuditEntry::new(EVENT_PDA_FUND, status)
+            }
+        }
+    };
+    Some(entry)
+}
+
+/// Builds the entry written right before a signed funding is sent
+///
+/// # Arguments
+/// * `validator_id` - Funded validator
+/// * `deposit_key` - Deposit PDA of the validator
+/// * `attempt` - Signed transaction of the funding
+pub fn pending_funding_entry(validator_id: &Pubkey, deposit_key: &Pubkey, attempt: &FundingAttempt) -> AuditEntry {
+    AuditEntry {
+        validator: Some(validator_id.to_string()),
+        payer: Some(attempt.payer.clone()),
+        amount_lamports: Some(attempt.amount_lamports),
+        signature: Some(attempt.signature.clone()),
+        message: Some(format!("PDA {}", deposit_key)),
+        epoch: attempt.epoch,
+        ..AuditEntry::new(EVENT_PDA_FUND, STATUS_PENDING)
+    }
+}
+
+/// Returns the funding attempts whose outcome is not known yet, oldest first
+///
+/// An attempt is a pending or unconfirmed entry with a signature; it is settled by a later success or
+/// failed entry with the same signature.
+///
+/// # Arguments
+/// * `entries` - Audit log entries in file order
+pub fn unresolved_fundings(entries: &[AuditEntry]) -> Vec<&AuditEntry> {
+    let settled: HashSet<&str> = entries
+        .iter()
+        .filter(|entry| entry.event == EVENT_PDA_FUND && (entry.status == STATUS_SUCCESS || entry.status == STATUS_FAILED))
+        .filter_map(|entry| entry.signature.as_deref())
+        .collect();
+    let mut seen = HashSet::new();
+    entries
+        .iter()
+        .filter(|entry| entry.event == EVENT_PDA_FUND && (entry.status == STATUS_PENDING || entry.status == STATUS_UNCONFIRMED))
+        .filter(|entry| entry.signature.as_deref().is_some_and(|signature| !settled.contains(signature) && seen.insert(signature)))
+        .collect()
+}
+
+/// Builds the entry that settles an unresolved funding attempt once its outcome is known on chain
+///
+/// # Arguments
+/// * `attempt` - Pending or unconfirmed entry
+/// * `landed` - True if the transfer landed, false if it failed or expired
+/// * `reason` - How the outcome was found
+pub fn settled_entry(attempt: &AuditEntry, landed: bool, reason: &str) -> AuditEntry {
+    AuditEntry {
+        validator: attempt.validator.clone(),
+        payer: attempt.payer.clone(),
+        amount_lamports: attempt.amount_lamports,
+        signature: attempt.signature.clone(),
+        message: Some(reason.to_string()),
+        idempotency_key: attempt.idempotency_key.clone(),
+        epoch: attempt.epoch,
+        ..AuditEntry::new(EVENT_PDA_FUND, if landed { STATUS_SUCCESS } else { STATUS_FAILED })
+    }
+}
+
+/// Returns the SOL and token funding attempts of a validator, newest first
+///
+/// A pending entry is left out once a later entry records the outcome of its transaction.
+///
+/// # Arguments
+/// * `entries` - Audit log entries in file order
+/// * `validator` - Validator pubkey
+pub fn validator_history<'a>(entries: &'a [AuditEntry], validator: &str) -> Vec<&'a AuditEntry> {
+    let recorded: HashSet<&str> = entries
+        .iter()
+        .filter(|entry| entry.status != STATUS_PENDING)
+        .filter_map(|entry| entry.signature.as_deref())
+        .collect();
+    let mut history: Vec<&AuditEntry> = entries
+        .iter()
+        .filter(|entry| is_funding(entry) && entry.validator.as_deref() == Some(validator))
+        .filter(|entry| entry.status != STATUS_PENDING || entry.signature.as_deref().is_none_or(|signature| !recorded.contains(signature)))
+        .collect();
+    history.reverse();
+    history
+}
+
+/// Returns true for SOL and token funding entries
+pub fn is_funding(entry: &AuditEntry) -> bool {
+    entry.event == EVENT_PDA_FUND || entry.event == EVENT_PDA_FUND_TOKEN
+}
+
+/// Window of the duplicate funding check when `--duplicate-window` is not given
+pub const DEFAULT_DUPLICATE_WINDOW: &str = "1h";
+
+/// Finds an earlier funding that a new one would repeat
+///
+/// With an idempotency key, any earlier attempt with the same key is a duplicate, including a failed one whose
+/// transaction may still have landed. Without a key, a funding of the same validator from the same payer for the
+/// same amount since `since` is, if it succeeded or its outcome is still unknown (see `unresolved_fundings`); settle
+/// the attempts on chain first so that those that failed or expired no longer count.
+///
+/// # Arguments
+/// * `entries` - Audit log entries in file order
+/// * `validator` - Validator to fund
+/// * `payer` - Funding wallet
+/// * `amount_lamports` - Amount to transfer
+/// * `idempotency_key` - Key of the new funding, if any
+/// * `since` - Unix timestamp of the start of the duplicate window
+///
+/// # Returns
+/// * `Option<&AuditEntry>` - Most recent duplicate, or None
+pub fn find_duplicate_funding<'a>(
+    entries: &'a [AuditEntry],
+    validator: &str,
+    payer: &str,
+    amount_lamports: u64,
+    idempotency_key: Option<&str>,
+    since: u64,
+) -> Option<&'a AuditEntry> {
+    let unresolved: HashSet<&str> = unresolved_fundings(entries).into_iter().filter_map(|entry| entry.signature.as_deref()).collect();
+    entries.iter().rev().filter(|entry| entry.event == EVENT_PDA_FUND && entry.validator.as_deref() == Some(validator)).find(|entry| {
+        match idempotency_key {
+            Some(key) => entry.idempotency_key.as_deref() == Some(key),
+            None => {
+                (entry.status == STATUS_SUCCESS || entry.signature.as_deref().is_some_and(|signature| unresolved.contains(signature)))
+                    && entry.timestamp >= since
+                    && entry.payer.as_deref() == Some(payer)
+                    && entry.amount_lamports == Some(amount_lamports)
+            }
+        }
+    })
+}
+
+/// Status filter values accepted by `audit show --status`
+pub const STATUSES: &[&str] = &[STATUS_SUCCESS, STATUS_FAILED, STATUS_PENDING, STATUS_UNCONFIRMED];
+
+/// Filter of `audit show`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditQuery {
+    /// Only entries of this validator
+    pub validator: Option<String>,
+    /// Only entries of this event (e.g. "pda-fund")
+    pub event: Option<String>,
+    /// Only entries with this status ("success", "failed", "pending" or "unconfirmed")
+    pub status: Option<String>,
+    /// Only entries written at or after this Unix timestamp
+    pub since: u64,
+    /// At most this many entries, newest first
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    /// Returns true if an entry passes every filter
+    ///
+    /// # Arguments
+    /// * `entry` - Audit log entry
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        entry.timestamp >= self.since
+            && self.validator.as_deref().is_none_or(|validator| entry.validator.as_deref() == Some(validator))
+            && self.event.as_deref().is_none_or(|event| entry.event == event)
+            && self.status.as_deref().is_none_or(|status| entry.status == status)
+    }
+}
+
+/// Checks an `audit show --status` value
+///
+/// # Arguments
+/// * `value` - "success", "failed", "pending" or "unconfirmed"
+///
+/// # Returns
+/// * `Result<String, Error>` - Status, or `Error::InvalidInput`
+pub fn parse_status(value: &str) -> Result<String, Error> {
+    let status = value.trim().to_ascii_lowercase();
+    if !STATUSES.contains(&status.as_str()) {
+        return Err(Error::InvalidInput(format!("Invalid status '{}': expected {}", value, STATUSES.join(", "))));
+    }
+    Ok(status)
+}
+
+/// Returns the entries matching a query, newest first
+///
+/// # Arguments
+/// * `entries` - Audit log entries in file order
+/// * `query` - Filters and limit
+pub fn query_entries<'a>(entries: &'a [AuditEntry], query: &AuditQuery) -> Vec<&'a AuditEntry> {
+    entries
+        .iter()
+        .rev()
+        .filter(|entry| query.matches(entry))
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect()
+}
+
+/// Formats a Unix timestamp as `YYYY-MM-DD HH:MM:SS` in UTC
+///
+/// # Arguments
+/// * `timestamp` - Unix timestamp in seconds
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days((timestamp / 86_400) as i64);
+    let secs = timestamp % 86_400;
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day, secs / 3_600, secs % 3_600 / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotation(new_wallet: &str) -> AuditEntry {
+        AuditEntry {
+            recipient: Some(new_wallet.to_string()),
+            ..AuditEntry::new(EVENT_WALLET_ROTATE, STATUS_SUCCESS)
+        }
+    }
+
+    fn verification(wallet: &str, status: &str) -> AuditEntry {
+        AuditEntry {
+            payer: Some(wallet.to_string()),
+            ..AuditEntry::new(EVENT_WALLET_VERIFY, status)
+        }
+    }
+
+    #[test]
+    fn test_append_and_read_entries() {
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_audit_{}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let entry = AuditEntry {
+            payer: Some("Old".to_string()),
+            amount_lamports: Some(42),
+            ..rotation("New")
+        };
+        append_entry(&path, &entry).unwrap();
+        append_entry(&path, &verification("New", STATUS_SUCCESS)).unwrap();
+
+        let entries = read_entries(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], entry);
+        assert_eq!(entries[1].event, EVENT_WALLET_VERIFY);
+    }
+
+    #[test]
+    fn test_read_missing_log_is_empty() {
+        let path = std::env::temp_dir().join("dz_validator_pda_audit_missing.log");
+        assert!(read_entries(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_duplicate_funding() {
+        let funding = |timestamp: u64, status: &str, amount: u64, key: Option<&str>| AuditEntry {
+            timestamp,
+            validator: Some("V".to_string()),
+            payer: Some("P".to_string()),
+            amount_lamports: Some(amount),
+            idempotency_key: key.map(str::to_string),
+            ..AuditEntry::new(EVENT_PDA_FUND, status)
+        };
+        let attempt = |timestamp: u64, status: &str, amount: u64, signature: &str| AuditEntry {
+            signature: Some(signature.to_string()),
+            ..funding(timestamp, status, amount, None)
+        };
+        let entries = vec![
+            funding(1_000, STATUS_SUCCESS, 5, None),
+            funding(2_000, STATUS_FAILED, 7, Some("job-1")),
+            // Sent, then the confirmation timed out: the outcome is unknown
+            attempt(3_000, STATUS_PENDING, 11, "Lost"),
+            attempt(3_001, STATUS_UNCONFIRMED, 11, "Lost"),
+            // Sent and later found to have expired
+            attempt(4_000, STATUS_PENDING, 13, "Expired"),
+            attempt(4_200, STATUS_FAILED, 13, "Expired"),
+        ];
+
+        assert_eq!(find_duplicate_funding(&entries, "V", "P", 5, None, 500).unwrap().timestamp, 1_000);
+        assert!(find_duplicate_funding(&entries, "V", "P", 5, None, 1_500).is_none());
+        assert!(find_duplicate_funding(&entries, "V", "Other", 5, None, 0).is_none());
+        assert!(find_duplicate_funding(&entries, "Other", "P", 5, None, 0).is_none());
+        // A failed send attempt is no duplicate by amount, but its key is never reused
+        assert!(find_duplicate_funding(&entries, "V", "P", 7, None, 0).is_none());
+        assert_eq!(find_duplicate_funding(&entries, "V", "P", 9, Some("job-1"), u64::MAX).unwrap().timestamp, 2_000);
+        assert!(find_duplicate_funding(&entries, "V", "P", 5, Some("job-2"), 0).is_none());
+        // An attempt that may still have landed is a duplicate until it is settled
+        assert_eq!(find_duplicate_funding(&entries, "V", "P", 11, None, 2_500).unwrap().timestamp, 3_001);
+        assert!(find_duplicate_funding(&entries, "V", "P", 13, None, 0).is_none());
+
+        let unresolved: Vec<u64> = unresolved_fundings(&entries).iter().map(|entry| entry.timestamp).collect();
+        assert_eq!(unresolved, vec![3_000]);
+        let settled = settled_entry(unresolved_fundings(&entries)[0], true, "found");
+        assert_eq!((settled.status.as_str(), settled.signature.as_deref(), settled.amount_lamports), (STATUS_SUCCESS, Some("Lost"), Some(11)));
+    }
+
+    #[test]
+    fn test_query_entries() {
+        let entry = |timestamp: u64, event: &str, status: &str, validator: &str| AuditEntry {
+            timestamp,
+            validator: Some(validator.to_string()),
+            ..AuditEntry::new(event, status)
+        };
+        let entries = vec![
+            entry(100, EVENT_PDA_FUND, STATUS_SUCCESS, "A"),
+            entry(200, EVENT_PDA_FUND, STATUS_FAILED, "B"),
+            entry(300, EVENT_PDA_FUND_TOKEN, STATUS_SUCCESS, "A"),
+            entry(400, EVENT_PDA_FUND, STATUS_SUCCESS, "A"),
+        ];
+        let timestamps = |query: &AuditQuery| query_entries(&entries, query).iter().map(|entry| entry.timestamp).collect::<Vec<_>>();
+
+        assert_eq!(timestamps(&AuditQuery::default()), vec![400, 300, 200, 100]);
+        assert_eq!(timestamps(&AuditQuery { validator: Some("A".to_string()), ..AuditQuery::default() }), vec![400, 300, 100]);
+        assert_eq!(timestamps(&AuditQuery { event: Some(EVENT_PDA_FUND.to_string()), since: 150, ..AuditQuery::default() }), vec![400, 200]);
+        assert_eq!(timestamps(&AuditQuery { status: Some(STATUS_FAILED.to_string()), ..AuditQuery::default() }), vec![200]);
+        assert_eq!(timestamps(&AuditQuery { limit: Some(2), ..AuditQuery::default() }), vec![400, 300]);
+
+        assert_eq!(parse_status("Failed").unwrap(), STATUS_FAILED);
+        assert_eq!(parse_status("Unconfirmed").unwrap(), STATUS_UNCONFIRMED);
+        assert!(parse_status("landed").unwrap_err().is_user_error());
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(1_700_000_000), "2023-11-14 22:13:20");
+    }
+
+    #[test]
+    fn test_funding_entries_and_history() {
+        let validator = Pubkey::new_unique();
+        let deposit_key = Pubkey::new_unique();
+        let outcome = FundingOutcome {
+            payer: "Payer".to_string(),
+            signature: "Sig".to_string(),
+            amount_lamports: 1_000,
+            fee_lamports: 5_000,
+            warnings: Vec::new(),
+            policy_checks: Vec::new(),
+            creates_account: false,
+            memo: None,
+            epoch: Some(7),
+        };
+        let attempt = FundingAttempt { payer: "Payer".to_string(), signature: "Sig".to_string(), amount_lamports: 1_000, epoch: Some(7) };
+        let pending = pending_funding_entry(&validator, &deposit_key, &attempt);
+        assert_eq!((pending.status.as_str(), pending.payer.as_deref(), pending.amount_lamports), (STATUS_PENDING, Some("Payer"), Some(1_000)));
+        let landed = funding_entry(&validator, &deposit_key, &Ok(outcome), Some(&attempt)).unwrap();
+        assert_eq!((landed.status.as_str(), landed.signature.as_deref(), landed.epoch), (STATUS_SUCCESS, Some("Sig"), Some(7)));
+        let failed = funding_entry(&validator, &deposit_key, &Err(Error::InsufficientFunds("low".to_string())), None).unwrap();
+        assert_eq!((failed.status.as_str(), failed.payer.as_deref()), (STATUS_FAILED, None));
+        assert!(funding_entry(&validator, &deposit_key, &Err(Error::FundingCancelled("no".to_string())), None).is_none());
+        // A timed out send keeps the attempt, since its transaction may still land
+        let timed_out = funding_entry(&validator, &deposit_key, &Err(Error::RpcUnavailable("timeout".to_string())), Some(&attempt)).unwrap();
+        assert_eq!((timed_out.status.as_str(), timed_out.signature.as_deref(), timed_out.amount_lamports), (STATUS_UNCONFIRMED, Some("Sig"), Some(1_000)));
+        let rejected = funding_entry(&validator, &deposit_key, &Err(Error::TransactionFailed("rejected".to_string())), Some(&attempt)).unwrap();
+        assert_eq!(rejected.status, STATUS_FAILED);
+
+        // The pending entry of a funding with a recorded outcome is left out of the history
+        let entries = vec![pending, landed, rotation("New"), failed];
+        let history = validator_history(&entries, &validator.to_string());
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].status, STATUS_FAILED);
+        assert!(validator_history(&entries, "Other").is_empty());
+    }
+
+    #[test]
+    fn test_wallet_needs_verification() {
+        assert!(!wallet_needs_verification(&[], "New"));
+
+        let rotated = vec![rotation("New")];
+        assert!(wallet_needs_verification(&rotated, "New"));
+        assert!(!wallet_needs_verification(&rotated, "Other"));
+
+        let failed_verification = vec![rotation("New"), verification("New", STATUS_FAILED)];
+        assert!(wallet_needs_verification(&failed_verification, "New"));
+
+        let verified = vec![rotation("New"), verification("New", STATUS_SUCCESS)];
+        assert!(!wallet_needs_verification(&verified, "New"));
+
+        // Rotating back to a previously verified wallet requires a new verification
+        let rotated_again = vec![rotation("New"), verification("New", STATUS_SUCCESS), rotation("New")];
+        assert!(wallet_needs_verification(&rotated_again, "New"));
+    }
+}
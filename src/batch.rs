@@ -0,0 +1,804 @@
+use crate::rpc::SolanaRpc;
+use crate::amount::format_sol;
+use crate::health::get_validator_vote_account;
+use crate::report::csv_row;
+use crate::i18n;
+use crate::progress;
+use crate::{
+    generate_deposit_pda, get_account_balance, get_account_balance_at_slot, get_account_balances, get_account_balances_at_slot, is_validator_in_gossip,
+    retry_transient, Error,
+};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+use solana_system_interface::instruction::SystemInstruction;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+/// Number of attempts for read-only RPC calls of a batch entry
+const BATCH_RETRY_ATTEMPTS: u32 = 3;
+
+/// Validators collected at the same time by default (`--concurrency`)
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+
+/// Order of batch output rows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortKey {
+    /// By alias, then pubkey (entries without an alias last)
+    #[default]
+    Name,
+    /// By PDA balance, largest first
+    Balance,
+    /// By activated stake, largest first
+    Stake,
+}
+
+impl SortKey {
+    /// Parses `name`, `balance` or `stake`
+    ///
+    /// # Arguments
+    /// * `value` - Value of `--sort`
+    ///
+    /// # Returns
+    /// * `Result<SortKey, Error>` - Sort key or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "name" => Ok(SortKey::Name),
+            "balance" => Ok(SortKey::Balance),
+            "stake" => Ok(SortKey::Stake),
+            _ => Err(Error::InvalidInput(format!("Invalid sort key '{}': expected name, balance or stake", value))),
+        }
+    }
+}
+
+/// What to collect for each validator of a batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// Check gossip presence
+    pub check_gossip: bool,
+    /// Read the PDA balance
+    pub fetch_balance: bool,
+    /// Read the activated stake of the vote account
+    pub fetch_stake: bool,
+    /// Read every balance as of this slot or later (`--consistent-snapshot`, see `snapshot_slot`)
+    pub min_context_slot: Option<u64>,
+}
+
+/// One row of a batch report
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BatchEntry {
+    /// Alias of the validator, if configured
+    pub name: Option<String>,
+    /// Validator identity pubkey
+    pub validator: String,
+    /// Deposit PDA of the validator
+    pub pda: String,
+    /// Presence in gossip, if checked
+    pub in_gossip: Option<bool>,
+    /// PDA balance in lamports, if read
+    pub balance_lamports: Option<u64>,
+    /// Activated stake in lamports, if read (0 without a vote account)
+    pub activated_stake: Option<u64>,
+    /// Error that stopped collecting this entry
+    pub error: Option<String>,
+}
+
+/// Collects one batch row; RPC errors are recorded in the entry instead of being returned
+///
+/// # Arguments
+/// * `name` - Alias of the validator, if any
+/// * `validator_id` - Validator identity pubkey
+/// * `options` - What to collect
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `BatchEntry` - Collected row
+pub async fn collect_entry(name: Option<String>, validator_id: &Pubkey, options: &BatchOptions, rpc: &dyn SolanaRpc) -> BatchEntry {
+    let deposit_key = generate_deposit_pda(validator_id);
+    let mut entry = BatchEntry {
+        name,
+        validator: validator_id.to_string(),
+        pda: deposit_key.to_string(),
+        ..BatchEntry::default()
+    };
+
+    let result = async {
+        if options.check_gossip {
+            entry.in_gossip = Some(retry_transient(BATCH_RETRY_ATTEMPTS, || is_validator_in_gossip(validator_id, rpc)).await?);
+        }
+        if options.fetch_balance {
+            let balance = match options.min_context_slot {
+                Some(slot) => retry_transient(BATCH_RETRY_ATTEMPTS, || get_account_balance_at_slot(&deposit_key, slot, rpc)).await?,
+                None => retry_transient(BATCH_RETRY_ATTEMPTS, || get_account_balance(&deposit_key, rpc)).await?,
+            };
+            entry.balance_lamports = Some(balance);
+        }
+        if options.fetch_stake {
+            let vote_account = retry_transient(BATCH_RETRY_ATTEMPTS, || get_validator_vote_account(validator_id, rpc)).await?;
+            entry.activated_stake = Some(vote_account.map(|vote_account| vote_account.activated_stake).unwrap_or(0));
+        }
+        Ok::<(), Error>(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        entry.error = Some(e.to_string());
+    }
+    entry
+}
+
+/// Parses the value of `--concurrency`
+///
+/// # Arguments
+/// * `value` - Number of validators collected at the same time
+///
+/// # Returns
+/// * `Result<usize, Error>` - Concurrency or `Error::InvalidInput` for 0 or a non-number
+pub fn parse_concurrency(value: &str) -> Result<usize, Error> {
+    match value.trim().parse::<usize>() {
+        Ok(concurrency) if concurrency > 0 => Ok(concurrency),
+        _ => Err(Error::InvalidInput(format!("Invalid concurrency '{}': expected a number greater than 0", value))),
+    }
+}
+
+/// Collects the rows of a batch with at most `concurrency` validators in flight
+///
+/// Entries are returned in the order they finish, so callers sort them afterwards. A failing
+/// or panicking validator only marks its own entry with an error; the other rows are unaffected.
+///
+/// # Arguments
+/// * `validators` - Aliases and identity pubkeys of the validators
+/// * `options` - What to collect
+/// * `concurrency` - Highest number of validators collected at the same time (at least 1)
+/// * `rpc` - RPC client shared by the tasks
+///
+/// # Returns
+/// * `Vec<BatchEntry>` - One row per validator
+pub async fn collect_entries(
+    validators: Vec<(Option<String>, Pubkey)>,
+    options: BatchOptions,
+    concurrency: usize,
+    rpc: Arc<dyn SolanaRpc>,
+) -> Vec<BatchEntry> {
+    collect_entries_until(validators, options, concurrency, rpc, std::future::pending::<()>()).await.0
+}
+
+/// Reads the PDA balances of all validators of a batch up front, 100 PDAs per request
+///
+/// # Arguments
+/// * `validators` - Aliases and identity pubkeys of the validators
+/// * `min_context_slot` - Slot every balance must reflect at least (`--consistent-snapshot`)
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<HashMap<Pubkey, u64>, Error>` - Balance in lamports per validator identity, or error
+pub async fn prefetch_balances(
+    validators: &[(Option<String>, Pubkey)],
+    min_context_slot: Option<u64>,
+    rpc: &dyn SolanaRpc,
+) -> Result<HashMap<Pubkey, u64>, Error> {
+    let deposit_keys: Vec<Pubkey> = validators.iter().map(|(_, validator_id)| generate_deposit_pda(validator_id)).collect();
+    let balances = retry_transient(BATCH_RETRY_ATTEMPTS, || async {
+        match min_context_slot {
+            Some(slot) => get_account_balances_at_slot(&deposit_keys, slot, rpc).await,
+            None => get_account_balances(&deposit_keys, rpc).await,
+        }
+    })
+    .await?;
+    Ok(validators.iter().map(|(_, validator_id)| *validator_id).zip(balances).collect())
+}
+
+/// Collects the rows of a batch like `collect_entries`, stopping early when `stop` completes
+///
+/// On stop the validators in flight are cancelled and the ones not started yet are skipped, so
+/// only the rows finished so far are returned (e.g. for a partial summary after Ctrl-C).
+///
+/// Balances are read up front with `prefetch_balances` instead of one request per row; if that
+/// fails, every row reads its own balance and reports its own error.
+///
+/// # Arguments
+/// * `validators` - Aliases and identity pubkeys of the validators
+/// * `options` - What to collect
+/// * `concurrency` - Highest number of validators collected at the same time (at least 1)
+/// * `rpc` - RPC client shared by the tasks
+/// * `stop` - Future that cuts the batch short when it completes (Ctrl-C, a deadline, ...)
+///
+/// # Returns
+/// * `(Vec<BatchEntry>, Option<T>)` - Rows collected, and the output of `stop` if it cut the batch short
+pub async fn collect_entries_until<T>(
+    validators: Vec<(Option<String>, Pubkey)>,
+    options: BatchOptions,
+    concurrency: usize,
+    rpc: Arc<dyn SolanaRpc>,
+    stop: impl Future<Output = T>,
+) -> (Vec<BatchEntry>, Option<T>) {
+    let mut entries = Vec::with_capacity(validators.len());
+    let mut pending = HashMap::new();
+    let mut tasks = JoinSet::new();
+    tokio::pin!(stop);
+
+    let balances = if options.fetch_balance {
+        tokio::select! {
+            biased;
+            reason = &mut stop => return (entries, Some(reason)),
+            balances = prefetch_balances(&validators, options.min_context_slot, rpc.as_ref()) => balances.ok(),
+        }
+    } else {
+        None
+    };
+    let task_options = BatchOptions { fetch_balance: options.fetch_balance && balances.is_none(), ..options };
+    let bar = progress::bar(validators.len() as u64, i18n::text("progress-collecting-validators"));
+    let mut validators = validators.into_iter();
+
+    loop {
+        while tasks.len() < concurrency.max(1) {
+            let Some((name, validator_id)) = validators.next() else { break };
+            let rpc = Arc::clone(&rpc);
+            let task_name = name.clone();
+            let balance = balances.as_ref().and_then(|balances| balances.get(&validator_id).copied());
+            let handle = tasks.spawn(async move {
+                let mut entry = collect_entry(task_name, &validator_id, &task_options, rpc.as_ref()).await;
+                entry.balance_lamports = entry.balance_lamports.or(balance);
+                entry
+            });
+            pending.insert(handle.id(), (name, validator_id));
+        }
+        let result = tokio::select! {
+            biased;
+            reason = &mut stop => {
+                tasks.abort_all();
+                bar.finish_and_clear();
+                return (entries, Some(reason));
+            }
+            result = tasks.join_next_with_id() => result,
+        };
+        let Some(result) = result else { break };
+        bar.inc(1);
+        match result {
+            Ok((id, entry)) => {
+                pending.remove(&id);
+                entries.push(entry);
+            }
+            Err(e) => {
+                if let Some((name, validator_id)) = pending.remove(&e.id()) {
+                    entries.push(BatchEntry {
+                        name,
+                        validator: validator_id.to_string(),
+                        pda: generate_deposit_pda(&validator_id).to_string(),
+                        error: Some(format!("Collecting the entry failed: {}", e)),
+                        ..BatchEntry::default()
+                    });
+                }
+            }
+        }
+    }
+    bar.finish_and_clear();
+    (entries, None)
+}
+
+/// Captures the reference slot of a consistent batch report (`--consistent-snapshot`)
+///
+/// Balances read with `BatchOptions::min_context_slot` set to this slot all reflect at least this
+/// slot, so a report served by several nodes behind a load balancer does not mix fresh and stale rows.
+///
+/// # Arguments
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<u64, Error>` - Current slot of the node or error
+pub async fn snapshot_slot(rpc: &dyn SolanaRpc) -> Result<u64, Error> {
+    retry_transient(BATCH_RETRY_ATTEMPTS, || async {
+        rpc.get_slot().await.map_err(|e| Error::from_client_error("Failed to get snapshot slot", &e))
+    })
+    .await
+}
+
+/// Compares entries by alias, then pubkey; entries without an alias come last
+fn canonical_order(a: &BatchEntry, b: &BatchEntry) -> Ordering {
+    match (&a.name, &b.name) {
+        (Some(a_name), Some(b_name)) => a_name.cmp(b_name),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+    .then_with(|| a.validator.cmp(&b.validator))
+}
+
+/// Sorts batch entries deterministically
+///
+/// Balance and stake sort largest first; unknown values go last and ties fall back to the
+/// canonical alias-then-pubkey order, so the same input always produces the same output.
+///
+/// # Arguments
+/// * `entries` - Entries to sort
+/// * `key` - Sort key
+pub fn sort_entries(entries: &mut [BatchEntry], key: SortKey) {
+    let descending = |a: Option<u64>, b: Option<u64>| b.cmp(&a);
+    entries.sort_by(|a, b| {
+        match key {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Balance => descending(a.balance_lamports, b.balance_lamports),
+            SortKey::Stake => descending(a.activated_stake, b.activated_stake),
+        }
+        .then_with(|| canonical_order(a, b))
+    });
+}
+
+/// Columns of `pda-batch --output csv`
+pub const CSV_COLUMNS: &[&str] = &[
+    "name",
+    "validator",
+    "pda",
+    "balance_lamports",
+    "balance_sol",
+    "gossip",
+    "activated_stake",
+    "signature",
+    "timestamp",
+    "error",
+];
+
+/// Formats batch entries as CSV with a header row
+///
+/// Values that were not collected are left empty; `signature` is empty for read-only operations.
+///
+/// # Arguments
+/// * `entries` - Batch rows
+/// * `timestamp` - Unix timestamp of the run
+///
+/// # Returns
+/// * `String` - CSV document
+pub fn entries_to_csv(entries: &[BatchEntry], timestamp: u64) -> String {
+    let optional = |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_default();
+    let mut csv = csv_row(CSV_COLUMNS);
+    for entry in entries {
+        csv.push_str(&csv_row(&[
+            entry.name.clone().unwrap_or_default(),
+            entry.validator.clone(),
+            entry.pda.clone(),
+            optional(entry.balance_lamports),
+            entry.balance_lamports.map(format_sol).unwrap_or_default(),
+            entry.in_gossip.map(|in_gossip| in_gossip.to_string()).unwrap_or_default(),
+            optional(entry.activated_stake),
+            String::new(),
+            timestamp.to_string(),
+            entry.error.clone().unwrap_or_default(),
+        ]));
+    }
+    csv
+}
+
+/// Reads validator pubkeys or aliases from a file, one per line
+///
+/// Blank lines and lines starting with `#` are ignored.
+///
+/// # Arguments
+/// * `path` - File path
+///
+/// # Returns
+/// * `Result<Vec<String>, Error>` - Validator pubkeys or aliases in file order
+pub fn read_validator_file(path: &Path) -> Result<Vec<String>, Error> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| Error::InvalidInput(format!("Failed to read validator list {}: {}", path.display(), e)))?;
+
+    Ok(parse_validator_list(&contents))
+}
+
+/// Parses validator pubkeys or aliases, one per line (a file of `--file` or the input of `--stdin`)
+///
+/// Blank lines and lines starting with `#` are ignored. Quotes and a trailing comma around a
+/// value are dropped, so the output of `jq` without `-r` (e.g. `"FjYE...",`) can be piped as is.
+///
+/// # Arguments
+/// * `contents` - Text of the list
+///
+/// # Returns
+/// * `Vec<String>` - Validator pubkeys or aliases in input order
+pub fn parse_validator_list(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(|line| line.trim().trim_end_matches(',').trim_matches('"').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Final state of one transaction of a packed funding batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionResult {
+    /// The transaction was confirmed
+    Confirmed,
+    /// The transaction failed or was never sent; its transfers did not happen and are safe to retry
+    Failed(String),
+    /// The transaction was sent but its confirmation was not seen; it may still land, so it is not retried
+    Unconfirmed(String),
+}
+
+/// Transaction of a packed funding batch together with its result
+#[derive(Debug, Clone)]
+pub struct SentTransaction {
+    /// Signed (or at least built) transaction
+    pub transaction: Transaction,
+    /// What happened to it
+    pub result: TransactionResult,
+}
+
+/// Outcome of one validator transfer in a packed funding batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferStatus {
+    /// The transfer was not sent yet (only found in funding state files)
+    Pending,
+    /// The transfer landed
+    Confirmed,
+    /// The transfer did not happen
+    Failed,
+    /// The transfer may or may not have landed
+    Unconfirmed,
+}
+
+impl fmt::Display for TransferStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferStatus::Pending => write!(f, "pending"),
+            TransferStatus::Confirmed => write!(f, "confirmed"),
+            TransferStatus::Failed => write!(f, "failed"),
+            TransferStatus::Unconfirmed => write!(f, "unconfirmed"),
+        }
+    }
+}
+
+/// Per-validator row of a packed funding summary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferOutcome {
+    /// Validator identity pubkey
+    pub validator: String,
+    /// Deposit PDA of the validator
+    pub pda: String,
+    /// Amount of the transfer in lamports (0 if the validator was in no transaction)
+    pub amount_lamports: u64,
+    /// Signature of the transaction that carried the transfer
+    pub signature: Option<String>,
+    /// Outcome of the transfer
+    pub status: TransferStatus,
+    /// Why the transfer failed or is unconfirmed
+    pub error: Option<String>,
+}
+
+/// Returns the system transfers of a transaction as (recipient, lamports) in instruction order
+///
+/// # Arguments
+/// * `transaction` - Transaction to inspect
+///
+/// # Returns
+/// * `Vec<(Pubkey, u64)>` - Recipients and amounts; other instructions are skipped
+pub fn transaction_transfers(transaction: &Transaction) -> Vec<(Pubkey, u64)> {
+    let keys = &transaction.message.account_keys;
+    transaction
+        .message
+        .instructions
+        .iter()
+        .filter(|instruction| keys.get(instruction.program_id_index as usize) == Some(&solana_system_interface::program::ID))
+        .filter_map(|instruction| match bincode::deserialize::<SystemInstruction>(&instruction.data) {
+            Ok(SystemInstruction::Transfer { lamports }) => {
+                let recipient = keys.get(*instruction.accounts.get(1)? as usize)?;
+                Some((*recipient, lamports))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the signature, transfer status and error a packed funding transaction gives its transfers
+pub(crate) fn transfer_status(sent: &SentTransaction) -> (Option<String>, TransferStatus, Option<String>) {
+    let signature = sent
+        .transaction
+        .signatures
+        .first()
+        .filter(|signature| **signature != Signature::default())
+        .map(Signature::to_string);
+    let (status, error) = match &sent.result {
+        TransactionResult::Confirmed => (TransferStatus::Confirmed, None),
+        TransactionResult::Failed(error) => (TransferStatus::Failed, Some(error.clone())),
+        TransactionResult::Unconfirmed(error) => (TransferStatus::Unconfirmed, Some(error.clone())),
+    };
+    (signature, status, error)
+}
+
+/// Attributes the results of packed funding transactions to each validator
+///
+/// Transfers are matched to validators by their deposit PDA, so the summary reflects what each
+/// signature actually carried rather than the order the transactions were planned in.
+///
+/// # Arguments
+/// * `validators` - Validators of the batch
+/// * `sent` - Transactions of the batch with their results
+///
+/// # Returns
+/// * `Vec<TransferOutcome>` - One row per validator in input order; a validator found in no
+///   transaction is reported as failed
+pub fn attribute_outcomes(validators: &[Pubkey], sent: &[SentTransaction]) -> Vec<TransferOutcome> {
+    let mut by_pda: HashMap<Pubkey, TransferOutcome> = HashMap::new();
+    for sent_transaction in sent {
+        let (signature, status, error) = transfer_status(sent_transaction);
+        for (recipient, lamports) in transaction_transfers(&sent_transaction.transaction) {
+            by_pda.insert(recipient, TransferOutcome {
+                validator: String::new(),
+                pda: recipient.to_string(),
+                amount_lamports: lamports,
+                signature: signature.clone(),
+                status,
+                error: error.clone(),
+            });
+        }
+    }
+
+    validators
+        .iter()
+        .map(|validator_id| {
+            let deposit_key = generate_deposit_pda(validator_id);
+            let outcome = by_pda.remove(&deposit_key).unwrap_or_else(|| TransferOutcome {
+                validator: String::new(),
+                pda: deposit_key.to_string(),
+                amount_lamports: 0,
+                signature: None,
+                status: TransferStatus::Failed,
+                error: Some("Not included in any transaction".to_string()),
+            });
+            TransferOutcome { validator: validator_id.to_string(), ..outcome }
+        })
+        .collect()
+}
+
+/// Returns the transfers to send again after a partially confirmed batch
+///
+/// Only failed transfers are retried: confirmed ones already landed and unconfirmed ones may
+/// still land, so sending them again could pay a validator twice.
+///
+/// # Arguments
+/// * `outcomes` - Summary from `attribute_outcomes`
+///
+/// # Returns
+/// * `Vec<(Pubkey, u64)>` - Validators and amounts to retry
+pub fn retry_transfers(outcomes: &[TransferOutcome]) -> Vec<(Pubkey, u64)> {
+    outcomes
+        .iter()
+        .filter(|outcome| outcome.status == TransferStatus::Failed && outcome.amount_lamports > 0)
+        .filter_map(|outcome| Some((outcome.validator.parse::<Pubkey>().ok()?, outcome.amount_lamports)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+
+    fn entry(name: Option<&str>, validator: &str, balance: Option<u64>, stake: Option<u64>) -> BatchEntry {
+        BatchEntry {
+            name: name.map(str::to_string),
+            validator: validator.to_string(),
+            balance_lamports: balance,
+            activated_stake: stake,
+            ..BatchEntry::default()
+        }
+    }
+
+    fn order(entries: &[BatchEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.validator.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_by_name_then_pubkey() {
+        let mut entries = vec![
+            entry(None, "C", None, None),
+            entry(Some("beta"), "A", None, None),
+            entry(None, "B", None, None),
+            entry(Some("alpha"), "D", None, None),
+        ];
+        sort_entries(&mut entries, SortKey::Name);
+        assert_eq!(order(&entries), vec!["D", "A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_sort_by_balance_and_stake_is_stable() {
+        let entries = vec![
+            entry(None, "A", Some(5), Some(100)),
+            entry(None, "B", None, None),
+            entry(None, "C", Some(10), Some(100)),
+            entry(Some("x"), "D", Some(5), Some(300)),
+        ];
+
+        let mut by_balance = entries.clone();
+        sort_entries(&mut by_balance, SortKey::Balance);
+        assert_eq!(order(&by_balance), vec!["C", "D", "A", "B"]);
+
+        let mut by_stake = entries.clone();
+        sort_entries(&mut by_stake, SortKey::Stake);
+        assert_eq!(order(&by_stake), vec!["D", "A", "C", "B"]);
+
+        // Input order does not matter
+        let mut reversed: Vec<BatchEntry> = entries.into_iter().rev().collect();
+        sort_entries(&mut reversed, SortKey::Balance);
+        assert_eq!(reversed, by_balance);
+    }
+
+    #[test]
+    fn test_parse_sort_key() {
+        assert_eq!(SortKey::parse("balance").unwrap(), SortKey::Balance);
+        assert_eq!(SortKey::parse("STAKE").unwrap(), SortKey::Stake);
+        assert!(SortKey::parse("size").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_entries_to_csv() {
+        let mut with_error = entry(Some("mainnet, eu"), "B", None, None);
+        with_error.error = Some("Failed to get balance".to_string());
+        let entries = vec![entry(None, "A", Some(1_500_000_000), None), with_error];
+
+        let csv = entries_to_csv(&entries, 1_700_000_000);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], "name,validator,pda,balance_lamports,balance_sol,gossip,activated_stake,signature,timestamp,error");
+        assert_eq!(lines[1], ",A,,1500000000,1.5,,,,1700000000,");
+        assert_eq!(lines[2], "\"mainnet, eu\",B,,,,,,,1700000000,Failed to get balance");
+        assert_eq!(lines[3], "");
+    }
+
+    #[test]
+    fn test_read_validator_file() {
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_batch_{}.txt", std::process::id()));
+        fs::write(&path, "# validators\nFjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\n\n  mainnet-01  \n").unwrap();
+        let validators = read_validator_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(validators, vec!["FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "mainnet-01"]);
+    }
+
+    #[test]
+    fn test_parse_validator_list() {
+        let piped = "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\n\"11111111111111111111111111111112\",\n  \"mainnet-01\"\n\n# done\n";
+        assert_eq!(
+            parse_validator_list(piped),
+            vec!["FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "11111111111111111111111111111112", "mainnet-01"]
+        );
+        assert!(parse_validator_list("\n\"\"\n").is_empty());
+    }
+
+    fn packed_transaction(payer: &Pubkey, transfers: &[(Pubkey, u64)], signed: bool) -> Transaction {
+        let instructions: Vec<_> = transfers
+            .iter()
+            .map(|(validator_id, lamports)| {
+                solana_system_interface::instruction::transfer(payer, &generate_deposit_pda(validator_id), *lamports)
+            })
+            .collect();
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(payer));
+        if signed {
+            transaction.signatures[0] = Signature::from([7; 64]);
+        }
+        transaction
+    }
+
+    #[test]
+    fn test_transaction_transfers() {
+        let payer = Pubkey::new_unique();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let transaction = packed_transaction(&payer, &[(first, 10), (second, 20)], false);
+        assert_eq!(
+            transaction_transfers(&transaction),
+            vec![(generate_deposit_pda(&first), 10), (generate_deposit_pda(&second), 20)]
+        );
+    }
+
+    #[test]
+    fn test_partially_confirmed_batch() {
+        let payer = Pubkey::new_unique();
+        let validators: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let sent = vec![
+            SentTransaction {
+                transaction: packed_transaction(&payer, &[(validators[1], 100), (validators[0], 200)], true),
+                result: TransactionResult::Confirmed,
+            },
+            SentTransaction {
+                transaction: packed_transaction(&payer, &[(validators[2], 300)], true),
+                result: TransactionResult::Failed("Blockhash expired".to_string()),
+            },
+            SentTransaction {
+                transaction: packed_transaction(&payer, &[(validators[3], 400)], true),
+                result: TransactionResult::Unconfirmed("Timed out".to_string()),
+            },
+        ];
+
+        let outcomes = attribute_outcomes(&validators, &sent);
+        let statuses: Vec<TransferStatus> = outcomes.iter().map(|outcome| outcome.status).collect();
+        assert_eq!(statuses, vec![
+            TransferStatus::Confirmed,
+            TransferStatus::Confirmed,
+            TransferStatus::Failed,
+            TransferStatus::Unconfirmed,
+            TransferStatus::Failed,
+        ]);
+        assert_eq!(outcomes[0].validator, validators[0].to_string());
+        assert_eq!(outcomes[0].amount_lamports, 200);
+        assert_eq!(outcomes[0].signature, outcomes[1].signature);
+        assert_eq!(outcomes[2].error.as_deref(), Some("Blockhash expired"));
+        assert_eq!(outcomes[4].signature, None);
+
+        // Only the definitely failed transfer is retried
+        assert_eq!(retry_transfers(&outcomes), vec![(validators[2], 300)]);
+    }
+
+    #[tokio::test]
+    async fn test_collect_entry_with_consistent_snapshot() {
+        let validator_id = Pubkey::new_unique();
+        let deposit_key = generate_deposit_pda(&validator_id);
+        let rpc = MockRpc::new().with_wallet(deposit_key, 2_500_000_000);
+        let slot = snapshot_slot(&rpc).await.unwrap();
+        assert_eq!(slot, 1_000);
+
+        let options = BatchOptions { check_gossip: false, fetch_balance: true, fetch_stake: false, min_context_slot: Some(slot) };
+        let entry = collect_entry(None, &validator_id, &options, &rpc).await;
+        assert_eq!(entry.balance_lamports, Some(2_500_000_000));
+        assert_eq!(entry.error, None);
+
+        // A node behind the snapshot slot is retried, then reported instead of mixing in a stale balance
+        rpc.set_slot(999);
+        let entry = collect_entry(None, &validator_id, &options, &rpc).await;
+        assert_eq!(entry.balance_lamports, None);
+        assert!(entry.error.unwrap().contains("Failed to get balance"));
+    }
+
+    #[tokio::test]
+    async fn test_collect_entries_concurrently() {
+        let funded = Pubkey::new_unique();
+        let validators: Vec<(Option<String>, Pubkey)> = (0..20)
+            .map(|i| (Some(format!("node-{:02}", i)), if i == 0 { funded } else { Pubkey::new_unique() }))
+            .collect();
+        let rpc = Arc::new(MockRpc::new().with_wallet(generate_deposit_pda(&funded), 1_000_000_000));
+        let options = BatchOptions { check_gossip: true, fetch_balance: true, fetch_stake: false, min_context_slot: None };
+
+        let mut entries = collect_entries(validators.clone(), options, 4, rpc.clone()).await;
+        assert_eq!(entries.len(), 20);
+        // All balances come from one getMultipleAccounts request
+        assert_eq!(rpc.multiple_accounts_requests(), 1);
+        sort_entries(&mut entries, SortKey::Name);
+        assert_eq!(entries[0].name.as_deref(), Some("node-00"));
+        assert_eq!(entries[0].balance_lamports, Some(1_000_000_000));
+        assert!(entries.iter().all(|entry| entry.error.is_none() && entry.in_gossip == Some(false)));
+
+        // Every row carries its own error when the endpoint is down (all in flight, so retries overlap)
+        rpc.set_unavailable(true);
+        let entries = collect_entries(validators, options, 20, rpc).await;
+        assert_eq!(entries.len(), 20);
+        assert!(entries.iter().all(|entry| entry.error.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_collect_entries_until_stop() {
+        let validators: Vec<(Option<String>, Pubkey)> = (0..8).map(|_| (None, Pubkey::new_unique())).collect();
+        let rpc = Arc::new(MockRpc::new());
+        let options = BatchOptions { check_gossip: true, fetch_balance: true, fetch_stake: false, min_context_slot: None };
+
+        // A stop that never fires collects every row
+        let (entries, stopped) = collect_entries_until(validators.clone(), options, 2, rpc.clone(), std::future::pending::<()>()).await;
+        assert_eq!(entries.len(), 8);
+        assert_eq!(stopped, None);
+
+        // A stop that has already fired cancels the batch before any row finishes
+        let (entries, stopped) = collect_entries_until(validators, options, 2, rpc, std::future::ready("interrupted")).await;
+        assert!(entries.is_empty());
+        assert_eq!(stopped, Some("interrupted"));
+    }
+
+    #[test]
+    fn test_parse_concurrency() {
+        assert_eq!(parse_concurrency("16").unwrap(), 16);
+        assert!(parse_concurrency("0").unwrap_err().is_user_error());
+        assert!(parse_concurrency("many").is_err());
+    }
+}
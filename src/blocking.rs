@@ -0,0 +1,489 @@
+//! Synchronous counterparts of the async API (`blocking` feature)
+//!
+//! Every function runs its async counterpart to completion on an internal Tokio runtime shared by
+//! the whole process, so callers need no runtime of their own. Calling them from inside a Tokio
+//! runtime panics; async code should use the async functions directly.
+
+use crate::airdrop::AirdropOutcome;
+use crate::audit::AuditEntry;
+use crate::batch::{BatchEntry, BatchOptions, SentTransaction};
+use crate::compat::{Capability, Compatibility};
+use crate::deposit::DepositAccount;
+use crate::epoch::EpochProgress;
+use crate::fiat::{Currency, FiatQuote, PriceSource};
+use crate::fund_many::{FundingRunState, PreparedFundingBatch};
+use crate::health::VoteAccountStatus;
+use crate::metrics::{Metric, MetricsRegistry};
+use crate::notify::{NotifyConfig, NotifyEvent};
+use crate::priority::PriorityFeeStats;
+use crate::receipt::DepositReceipt;
+use crate::rpc::gossip_cache::GossipCacheFile;
+use crate::rpc::SolanaRpc;
+use crate::squads::{PreparedSquadsProposal, SquadsProposal};
+use crate::token::{PreparedTokenFunding, TokenFundingOutcome};
+use crate::uptime::Uptime;
+use crate::verify::{BalanceDelta, DepositVerification};
+use crate::wallet::SweepOutcome;
+use crate::{
+    dashboard, Amount, Error, FundingOutcome, FundingPolicy, FundingSigners, NonceConfig, PolicyCheck, PreparedFunding,
+    SignedFunding, ValidatorStatus, Warning,
+};
+use solana_sdk::message::AddressLookupTableAccount;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::epoch_info::EpochInfo;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use std::collections::HashSet;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// Worker threads of the internal runtime; they run spawned tasks (batch rows, the metrics server) in the background
+const RUNTIME_WORKER_THREADS: usize = 2;
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Runs a future to completion on the internal runtime
+///
+/// # Panics
+/// If called from inside a Tokio runtime
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(RUNTIME_WORKER_THREADS)
+                .thread_name("dz-validator-pda-blocking")
+                .enable_all()
+                .build()
+                .expect("Failed to start the runtime of the blocking API")
+        })
+        .block_on(future)
+}
+
+/// Blocking counterpart of `DzValidatorClient`
+#[derive(Clone)]
+pub struct DzValidatorClient {
+    inner: crate::DzValidatorClient,
+}
+
+impl DzValidatorClient {
+    /// Connects to an RPC endpoint (see `crate::DzValidatorClient::new`)
+    pub fn new(rpc_url: &str, commitment: CommitmentConfig) -> Result<Self, Error> {
+        Ok(DzValidatorClient { inner: crate::DzValidatorClient::new(rpc_url, commitment)? })
+    }
+
+    /// Wraps an async client
+    pub fn from_async(inner: crate::DzValidatorClient) -> Self {
+        DzValidatorClient { inner }
+    }
+
+    /// Returns the async client, e.g. for its `rpc()` or `with_audit_log`
+    pub fn as_async(&self) -> &crate::DzValidatorClient {
+        &self.inner
+    }
+
+    /// Returns the deposit PDA of a validator
+    pub fn deposit_pda(&self, validator_id: &Pubkey) -> Pubkey {
+        self.inner.deposit_pda(validator_id)
+    }
+
+    /// Reads the balance of the deposit PDA of a validator in lamports
+    pub fn balance(&self, validator_id: &Pubkey) -> Result<u64, Error> {
+        block_on(self.inner.balance(validator_id))
+    }
+
+    /// Reads the balances of the deposit PDAs of many validators in lamports (see `DzValidatorClient::balances`)
+    pub fn balances(&self, validator_ids: &[Pubkey]) -> Result<Vec<u64>, Error> {
+        block_on(self.inner.balances(validator_ids))
+    }
+
+    /// Checks whether a validator is in gossip
+    pub fn is_in_gossip(&self, validator_id: &Pubkey) -> Result<bool, Error> {
+        block_on(self.inner.is_in_gossip(validator_id))
+    }
+
+    /// Funds the deposit PDA of a validator and records the result in the audit log
+    pub fn fund(&self, validator_id: &Pubkey, signers: &FundingSigners, amount: Amount, policy: &FundingPolicy) -> Result<FundingOutcome, Error> {
+        block_on(self.inner.fund(validator_id, signers, amount, policy))
+    }
+
+    /// Returns the funding attempts of a validator from the audit log, newest first
+    pub fn history(&self, validator_id: &Pubkey) -> Result<Vec<AuditEntry>, Error> {
+        self.inner.history(validator_id)
+    }
+}
+
+/// Blocking `crate::get_account_balance`
+pub fn get_account_balance(address: &Pubkey, rpc: &dyn SolanaRpc) -> Result<u64, Error> {
+    block_on(crate::get_account_balance(address, rpc))
+}
+
+/// Blocking `crate::get_account_balance_at_slot`
+pub fn get_account_balance_at_slot(address: &Pubkey, min_context_slot: u64, rpc: &dyn SolanaRpc) -> Result<u64, Error> {
+    block_on(crate::get_account_balance_at_slot(address, min_context_slot, rpc))
+}
+
+/// Blocking `crate::get_account_balances`
+pub fn get_account_balances(addresses: &[Pubkey], rpc: &dyn SolanaRpc) -> Result<Vec<u64>, Error> {
+    block_on(crate::get_account_balances(addresses, rpc))
+}
+
+/// Blocking `crate::get_account_balances_at_slot`
+pub fn get_account_balances_at_slot(addresses: &[Pubkey], min_context_slot: u64, rpc: &dyn SolanaRpc) -> Result<Vec<u64>, Error> {
+    block_on(crate::get_account_balances_at_slot(addresses, min_context_slot, rpc))
+}
+
+/// Blocking `crate::get_rent_exempt_minimum`
+pub fn get_rent_exempt_minimum(address: &Pubkey, rpc: &dyn SolanaRpc) -> Result<u64, Error> {
+    block_on(crate::get_rent_exempt_minimum(address, rpc))
+}
+
+/// Blocking `crate::should_cancel_pda_funding`
+pub fn should_cancel_pda_funding(validator_id: &Pubkey, rpc: &dyn SolanaRpc) -> Result<bool, Error> {
+    block_on(crate::should_cancel_pda_funding(validator_id, rpc))
+}
+
+/// Blocking `crate::is_validator_in_gossip`
+pub fn is_validator_in_gossip(validator_id: &Pubkey, rpc: &dyn SolanaRpc) -> Result<bool, Error> {
+    block_on(crate::is_validator_in_gossip(validator_id, rpc))
+}
+
+/// Blocking `crate::pda_fund_address`
+pub fn pda_fund_address(
+    validator_id: &Pubkey,
+    signers: &FundingSigners,
+    amount: Amount,
+    policy: &FundingPolicy,
+    nonce: Option<&NonceConfig>,
+    rpc: &dyn SolanaRpc,
+) -> Result<FundingOutcome, Error> {
+    block_on(crate::pda_fund_address(validator_id, signers, amount, policy, nonce, rpc))
+}
+
+/// Blocking `crate::prepare_pda_funding`
+pub fn prepare_pda_funding(
+    validator_id: &Pubkey,
+    signers: &FundingSigners,
+    amount: Amount,
+    policy: &FundingPolicy,
+    nonce: Option<&NonceConfig>,
+    rpc: &dyn SolanaRpc,
+) -> Result<PreparedFunding, Error> {
+    block_on(crate::prepare_pda_funding(validator_id, signers, amount, policy, nonce, rpc))
+}
+
+/// Blocking `crate::send_pda_funding`
+pub fn send_pda_funding(prepared: PreparedFunding, rpc: &dyn SolanaRpc) -> Result<FundingOutcome, Error> {
+    block_on(crate::send_pda_funding(prepared, rpc))
+}
+
+/// Blocking `crate::send_signed_funding`
+pub fn send_signed_funding(signed: SignedFunding, rpc: &dyn SolanaRpc) -> Result<FundingOutcome, Error> {
+    block_on(crate::send_signed_funding(signed, rpc))
+}
+
+/// Blocking `crate::retry_transient`
+pub fn retry_transient<T, F, Fut>(max_attempts: u32, operation: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    block_on(crate::retry_transient(max_attempts, operation))
+}
+
+/// Blocking `health::get_validator_vote_account`
+pub fn get_validator_vote_account(validator_id: &Pubkey, rpc: &dyn SolanaRpc) -> Result<Option<VoteAccountStatus>, Error> {
+    block_on(crate::health::get_validator_vote_account(validator_id, rpc))
+}
+
+/// Blocking `health::get_validator_status`
+pub fn get_validator_status(validator_id: &Pubkey, check_gossip: bool, rpc: &dyn SolanaRpc) -> Result<ValidatorStatus, Error> {
+    block_on(crate::health::get_validator_status(validator_id, check_gossip, rpc))
+}
+
+/// Blocking `health::get_policy_status`
+pub fn get_policy_status(validator_id: &Pubkey, policy: &FundingPolicy, rpc: &dyn SolanaRpc) -> Result<ValidatorStatus, Error> {
+    block_on(crate::health::get_policy_status(validator_id, policy, rpc))
+}
+
+/// Blocking `uptime::get_validator_uptime`
+pub fn get_validator_uptime(validator_id: &Pubkey, vote_pubkey: &Pubkey, lookback_epochs: u64, rpc: &dyn SolanaRpc) -> Result<Uptime, Error> {
+    block_on(crate::uptime::get_validator_uptime(validator_id, vote_pubkey, lookback_epochs, rpc))
+}
+
+/// Blocking `health::check_funding_policy`
+pub fn check_funding_policy(validator_id: &Pubkey, policy: &FundingPolicy, rpc: &dyn SolanaRpc) -> Result<Vec<Warning>, Error> {
+    block_on(crate::health::check_funding_policy(validator_id, policy, rpc))
+}
+
+/// Blocking `health::check_funding_policy_rules`
+pub fn check_funding_policy_rules(
+    validator_id: &Pubkey,
+    policy: &FundingPolicy,
+    rpc: &dyn SolanaRpc,
+) -> Result<(Vec<Warning>, Vec<PolicyCheck>), Error> {
+    block_on(crate::health::check_funding_policy_rules(validator_id, policy, rpc))
+}
+
+/// Blocking `health::check_rpc_health`
+pub fn check_rpc_health(rpc: &dyn SolanaRpc) -> Option<Warning> {
+    block_on(crate::health::check_rpc_health(rpc))
+}
+
+/// Blocking `compat::check_compatibility`
+pub fn check_compatibility(required: &[Capability], rpc: &dyn SolanaRpc) -> Result<Compatibility, Error> {
+    block_on(crate::compat::check_compatibility(required, rpc))
+}
+
+/// Blocking `compat::is_feature_active`
+pub fn is_feature_active(feature: &Pubkey, rpc: &dyn SolanaRpc) -> Result<bool, Error> {
+    block_on(crate::compat::is_feature_active(feature, rpc))
+}
+
+/// Blocking `batch::collect_entry`
+pub fn collect_entry(name: Option<String>, validator_id: &Pubkey, options: &BatchOptions, rpc: &dyn SolanaRpc) -> BatchEntry {
+    block_on(crate::batch::collect_entry(name, validator_id, options, rpc))
+}
+
+/// Blocking `batch::collect_entries`
+pub fn collect_entries(validators: Vec<(Option<String>, Pubkey)>, options: BatchOptions, concurrency: usize, rpc: Arc<dyn SolanaRpc>) -> Vec<BatchEntry> {
+    block_on(crate::batch::collect_entries(validators, options, concurrency, rpc))
+}
+
+/// Blocking `batch::collect_entries_until`
+pub fn collect_entries_until<T>(
+    validators: Vec<(Option<String>, Pubkey)>,
+    options: BatchOptions,
+    concurrency: usize,
+    rpc: Arc<dyn SolanaRpc>,
+    stop: impl Future<Output = T>,
+) -> (Vec<BatchEntry>, Option<T>) {
+    block_on(crate::batch::collect_entries_until(validators, options, concurrency, rpc, stop))
+}
+
+/// Blocking `fiat::quote`
+pub fn quote(source: &dyn PriceSource, currency: Currency, lamports: u64, at: Option<u64>) -> Result<FiatQuote, Error> {
+    block_on(crate::fiat::quote(source, currency, lamports, at))
+}
+
+/// Blocking `gossip::take_snapshot`
+pub fn take_snapshot(rpc: &dyn SolanaRpc, fetched_at: u64) -> Result<GossipCacheFile, Error> {
+    block_on(crate::gossip::take_snapshot(rpc, fetched_at))
+}
+
+/// Blocking `verify::verify_deposit`
+pub fn verify_deposit(signature: &Signature, validator_id: &Pubkey, rpc: &dyn SolanaRpc) -> Result<DepositVerification, Error> {
+    block_on(crate::verify::verify_deposit(signature, validator_id, rpc))
+}
+
+/// Blocking `verify::verify_balance_delta`
+pub fn verify_balance_delta(signature: &Signature, validator_id: &Pubkey, expected_lamports: u64, rpc: &dyn SolanaRpc) -> Result<BalanceDelta, Error> {
+    block_on(crate::verify::verify_balance_delta(signature, validator_id, expected_lamports, rpc))
+}
+
+/// Blocking `receipt::create_receipt`
+pub fn create_receipt(signature: &Signature, validator_id: &Pubkey, payer: &dyn Signer, rpc: &dyn SolanaRpc) -> Result<DepositReceipt, Error> {
+    block_on(crate::receipt::create_receipt(signature, validator_id, payer, rpc))
+}
+
+/// Blocking `batch::snapshot_slot`
+pub fn snapshot_slot(rpc: &dyn SolanaRpc) -> Result<u64, Error> {
+    block_on(crate::batch::snapshot_slot(rpc))
+}
+
+/// Blocking `dashboard::collect_snapshot`
+pub fn collect_snapshot(
+    validators: Vec<(Option<String>, Pubkey)>,
+    payer: Option<Pubkey>,
+    audit_entries: &[AuditEntry],
+    timestamp: u64,
+    rpc: Arc<dyn SolanaRpc>,
+) -> dashboard::Snapshot {
+    block_on(dashboard::collect_snapshot(validators, payer, audit_entries, timestamp, rpc))
+}
+
+/// Blocking `versioned::get_lookup_table`
+pub fn get_lookup_table(address: &Pubkey, rpc: &dyn SolanaRpc) -> Result<AddressLookupTableAccount, Error> {
+    block_on(crate::versioned::get_lookup_table(address, rpc))
+}
+
+/// Blocking `nonce::get_nonce_blockhash`
+pub fn get_nonce_blockhash(nonce_account: &Pubkey, authority: &Pubkey, rpc: &dyn SolanaRpc) -> Result<Hash, Error> {
+    block_on(crate::nonce::get_nonce_blockhash(nonce_account, authority, rpc))
+}
+
+/// Blocking `priority::recent_priority_fees`
+pub fn recent_priority_fees(accounts: &[Pubkey], rpc: &dyn SolanaRpc) -> Result<Option<PriorityFeeStats>, Error> {
+    block_on(crate::priority::recent_priority_fees(accounts, rpc))
+}
+
+/// Blocking `token::prepare_token_funding`
+pub fn prepare_token_funding(
+    validator_id: &Pubkey,
+    mint: &Pubkey,
+    amount: &str,
+    signers: &FundingSigners,
+    policy: &FundingPolicy,
+    rpc: &dyn SolanaRpc,
+) -> Result<PreparedTokenFunding, Error> {
+    block_on(crate::token::prepare_token_funding(validator_id, mint, amount, signers, policy, rpc))
+}
+
+/// Blocking `token::send_token_funding`
+pub fn send_token_funding(prepared: PreparedTokenFunding, rpc: &dyn SolanaRpc) -> Result<TokenFundingOutcome, Error> {
+    block_on(crate::token::send_token_funding(prepared, rpc))
+}
+
+/// Blocking `squads::prepare_squads_proposal`
+pub fn prepare_squads_proposal(
+    validator_id: &Pubkey,
+    multisig: &Pubkey,
+    amount_lamports: u64,
+    signers: &FundingSigners,
+    policy: &FundingPolicy,
+    rpc: &dyn SolanaRpc,
+) -> Result<PreparedSquadsProposal, Error> {
+    block_on(crate::squads::prepare_squads_proposal(validator_id, multisig, amount_lamports, signers, policy, rpc))
+}
+
+/// Blocking `squads::send_squads_proposal`
+pub fn send_squads_proposal(prepared: PreparedSquadsProposal, rpc: &dyn SolanaRpc) -> Result<SquadsProposal, Error> {
+    block_on(crate::squads::send_squads_proposal(prepared, rpc))
+}
+
+/// Blocking `wallet::sweep_wallet`
+pub fn sweep_wallet(from: &dyn Signer, to: &Pubkey, rpc: &dyn SolanaRpc) -> Result<Option<SweepOutcome>, Error> {
+    block_on(crate::wallet::sweep_wallet(from, to, rpc))
+}
+
+/// Blocking `wallet::wait_for_confirmation`
+pub fn wait_for_confirmation(signature: &str, timeout: Duration, rpc: &dyn SolanaRpc) -> Result<(), Error> {
+    block_on(crate::wallet::wait_for_confirmation(signature, timeout, rpc))
+}
+
+/// Blocking `deposit::get_deposit_account`
+pub fn get_deposit_account(deposit_key: &Pubkey, rpc: &dyn SolanaRpc) -> Result<DepositAccount, Error> {
+    block_on(crate::deposit::get_deposit_account(deposit_key, rpc))
+}
+
+/// Blocking `deposit::confirmed_slot`
+pub fn confirmed_slot(signature: &str, timeout: Duration, rpc: &dyn SolanaRpc) -> Result<u64, Error> {
+    block_on(crate::deposit::confirmed_slot(signature, timeout, rpc))
+}
+
+/// Blocking `epoch::get_epoch_info`
+pub fn get_epoch_info(rpc: &dyn SolanaRpc) -> Result<EpochInfo, Error> {
+    block_on(crate::epoch::get_epoch_info(rpc))
+}
+
+/// Blocking `epoch::wait_for_epoch`
+pub fn wait_for_epoch(
+    target_epoch: u64,
+    max_poll_interval: Duration,
+    rpc: &dyn SolanaRpc,
+    on_progress: impl FnMut(&EpochProgress),
+) -> Result<EpochInfo, Error> {
+    block_on(crate::epoch::wait_for_epoch(target_epoch, max_poll_interval, rpc, on_progress))
+}
+
+/// Blocking `rpc::gossip_nodes`
+pub fn gossip_nodes(rpc: &dyn SolanaRpc) -> Result<Arc<HashSet<Pubkey>>, Error> {
+    block_on(crate::rpc::gossip_nodes(rpc))
+}
+
+/// Blocking `airdrop::request_airdrop`
+pub fn request_airdrop(recipient: &Pubkey, amount_lamports: u64, timeout: Duration, rpc: &dyn SolanaRpc) -> Result<AirdropOutcome, Error> {
+    block_on(crate::airdrop::request_airdrop(recipient, amount_lamports, timeout, rpc))
+}
+
+/// Blocking `airdrop::check_not_mainnet`
+pub fn check_not_mainnet(rpc: &dyn SolanaRpc) -> Result<(), Error> {
+    block_on(crate::airdrop::check_not_mainnet(rpc))
+}
+
+/// Blocking `rpc::node_identity`
+pub fn node_identity(rpc: &dyn SolanaRpc) -> Result<Pubkey, Error> {
+    block_on(crate::rpc::node_identity(rpc))
+}
+
+/// Blocking `fund_many::prepare_funding_batch`
+pub fn prepare_funding_batch(
+    transfers: &[(Pubkey, u64)],
+    signers: &FundingSigners,
+    policy: &FundingPolicy,
+    rpc: &dyn SolanaRpc,
+) -> Result<PreparedFundingBatch, Error> {
+    block_on(crate::fund_many::prepare_funding_batch(transfers, signers, policy, rpc))
+}
+
+/// Blocking `fund_many::send_funding_batch`
+pub fn send_funding_batch(prepared: &PreparedFundingBatch, timeout: Duration, rpc: &dyn SolanaRpc) -> Result<Vec<SentTransaction>, Error> {
+    block_on(crate::fund_many::send_funding_batch(prepared, timeout, rpc))
+}
+
+/// Blocking `fund_many::send_funding_batch_with_state`
+pub fn send_funding_batch_with_state(
+    prepared: &PreparedFundingBatch,
+    timeout: Duration,
+    state: &mut FundingRunState,
+    path: &Path,
+    rpc: &dyn SolanaRpc,
+) -> Result<Vec<SentTransaction>, Error> {
+    block_on(crate::fund_many::send_funding_batch_with_state(prepared, timeout, state, path, rpc))
+}
+
+/// Blocking `fund_many::resolve_unconfirmed`
+pub fn resolve_unconfirmed(state: &mut FundingRunState, rpc: &dyn SolanaRpc) -> Result<(), Error> {
+    block_on(crate::fund_many::resolve_unconfirmed(state, rpc))
+}
+
+/// Blocking `notify::send_notification`
+pub fn send_notification(config: &NotifyConfig, event: &NotifyEvent) -> Result<(), Error> {
+    block_on(crate::notify::send_notification(config, event))
+}
+
+/// Blocking `offline::broadcast_transaction`
+pub fn broadcast_transaction(transaction: &Transaction, rpc: &dyn SolanaRpc) -> Result<String, Error> {
+    block_on(crate::offline::broadcast_transaction(transaction, rpc))
+}
+
+/// Blocking `metrics::push_metrics`
+pub fn push_metrics(gateway_url: &str, job: &str, grouping: &[(&str, &str)], metrics: &[Metric]) -> Result<(), Error> {
+    block_on(crate::metrics::push_metrics(gateway_url, job, grouping, metrics))
+}
+
+/// Blocking `metrics::start_metrics_server`; the server keeps running on the internal runtime
+pub fn start_metrics_server(port: u16, registry: Arc<MetricsRegistry>) -> Result<SocketAddr, Error> {
+    block_on(crate::metrics::start_metrics_server(port, registry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_deposit_pda;
+    use crate::rpc::mock::MockRpc;
+
+    #[test]
+    fn test_blocking_calls_without_a_runtime() {
+        let validator_id = Pubkey::new_unique();
+        let rpc = Arc::new(MockRpc::new().with_gossip_node(validator_id).with_wallet(generate_deposit_pda(&validator_id), 1_000_000_000));
+
+        assert_eq!(get_account_balance(&generate_deposit_pda(&validator_id), rpc.as_ref()).unwrap(), 1_000_000_000);
+        assert!(is_validator_in_gossip(&validator_id, rpc.as_ref()).unwrap());
+        assert_eq!(snapshot_slot(rpc.as_ref()).unwrap(), 1_000);
+
+        // Spawned tasks run on the worker threads of the internal runtime
+        let options = BatchOptions { check_gossip: true, fetch_balance: true, fetch_stake: false, min_context_slot: None };
+        let entries = collect_entries(vec![(None, validator_id), (None, Pubkey::new_unique())], options, 2, rpc.clone());
+        assert_eq!(entries.len(), 2);
+
+        let client = DzValidatorClient::from_async(crate::DzValidatorClient::with_rpc(rpc));
+        assert_eq!(client.balance(&validator_id).unwrap(), 1_000_000_000);
+        assert!(client.is_in_gossip(&validator_id).unwrap());
+    }
+}
@@ -0,0 +1,328 @@
+use crate::audit::{self, AuditEntry};
+use crate::report::{Warning, WARN_BUDGET_OVERRIDDEN};
+use crate::rpc::SolanaRpc;
+use crate::pending::settle_funding_attempts;
+use crate::{epoch, format_sol, parse_amount, unix_timestamp, Amount, Error, FundingPolicy};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Length of the `max_per_day` window in seconds
+pub const BUDGET_DAY_SECS: u64 = 86_400;
+
+/// Caps as written in the config file (amounts like "5" or "500000000lamports")
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetLimits {
+    /// Largest single transfer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_per_tx: Option<String>,
+    /// Most that may be sent in any 24 hours
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_per_day: Option<String>,
+    /// Most that may be sent in one epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_per_epoch: Option<String>,
+}
+
+impl BudgetLimits {
+    /// Parses the caps
+    ///
+    /// # Arguments
+    /// * `scope` - Config section of the caps, for error messages (e.g. "budget.validators.alice")
+    ///
+    /// # Returns
+    /// * `Result<BudgetCaps, Error>` - Caps in lamports or `Error::InvalidInput` (`ALL` is not a cap)
+    pub fn caps(&self, scope: &str) -> Result<BudgetCaps, Error> {
+        let cap = |name: &str, value: &Option<String>| -> Result<Option<u64>, Error> {
+            match value.as_deref().map(parse_amount) {
+                None => Ok(None),
+                Some(Ok(Amount::Lamports(lamports))) => Ok(Some(lamports)),
+                Some(_) => Err(Error::InvalidInput(format!(
+                    "Invalid {}.{} '{}': expected an amount like 5 or 500000000lamports",
+                    scope,
+                    name,
+                    value.as_deref().unwrap_or_default()
+                ))),
+            }
+        };
+        Ok(BudgetCaps {
+            per_tx: cap("max_per_tx", &self.max_per_tx)?,
+            per_day: cap("max_per_day", &self.max_per_day)?,
+            per_epoch: cap("max_per_epoch", &self.max_per_epoch)?,
+        })
+    }
+}
+
+/// `[budget]` section of the config: global caps and `[budget.validators.<alias or pubkey>]` caps
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetConfig {
+    /// Caps on all fundings together
+    #[serde(flatten)]
+    pub limits: BudgetLimits,
+    /// Caps on the fundings of one validator
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub validators: BTreeMap<String, BudgetLimits>,
+}
+
+/// Caps in lamports (None for no cap)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BudgetCaps {
+    /// Largest single transfer
+    pub per_tx: Option<u64>,
+    /// Most that may be sent in any 24 hours
+    pub per_day: Option<u64>,
+    /// Most that may be sent in one epoch
+    pub per_epoch: Option<u64>,
+}
+
+/// Funding budget checked before anything is signed (config `[budget]`)
+///
+/// Spending is read from the audit log, which serves as the spend ledger: successful fundings, and fundings whose
+/// outcome is not known yet (recorded as pending before they are sent, or unconfirmed after a timeout), since they
+/// may have landed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Budget {
+    /// Caps on all fundings together
+    pub global: BudgetCaps,
+    /// Caps on the fundings of one validator
+    pub validators: BTreeMap<Pubkey, BudgetCaps>,
+    /// Audit log the spending is read from
+    pub ledger: PathBuf,
+}
+
+impl Budget {
+    /// Checks transfers against the caps, reading the spending so far from the ledger
+    ///
+    /// # Arguments
+    /// * `transfers` - Validators and amounts in lamports about to be sent together
+    /// * `epoch` - Current epoch (None skips the `max_per_epoch` caps)
+    /// * `now` - Current Unix timestamp
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>, Error>` - Caps the transfers would exceed (empty if none), or `Error::Io` if the ledger cannot be read
+    pub fn check(&self, transfers: &[(Pubkey, u64)], epoch: Option<u64>, now: u64) -> Result<Vec<String>, Error> {
+        let entries = audit::read_entries(&self.ledger)?;
+        Ok(budget_violations(self, &entries, transfers, epoch, now))
+    }
+}
+
+/// Checks transfers against the budget of a funding policy before they are signed
+///
+/// The current epoch is only fetched when a budget is configured; it is recorded with the funding so
+/// that later fundings in the same epoch count it. Unresolved funding attempts in the ledger are settled
+/// on chain first (see `pending::settle_funding_attempts`).
+///
+/// # Arguments
+/// * `policy` - Funding policy with the budget and `--override-budget`
+/// * `transfers` - Validators and amounts in lamports about to be sent together
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<(Option<u64>, Vec<Warning>), Error>` - Current epoch (None without a budget) and a `budget_overridden`
+///   warning per exceeded cap with `--override-budget`, or `Error::FundingCancelled` if a cap would be exceeded
+pub async fn enforce_budget(policy: &FundingPolicy, transfers: &[(Pubkey, u64)], rpc: &dyn SolanaRpc) -> Result<(Option<u64>, Vec<Warning>), Error> {
+    let Some(budget) = &policy.budget else {
+        return Ok((None, Vec::new()));
+    };
+    let epoch = epoch::get_epoch_info(rpc).await?.epoch;
+    // Attempts whose outcome is now known on chain stop counting if they did not land
+    settle_funding_attempts(&budget.ledger, unix_timestamp(), rpc).await?;
+    let violations = budget.check(transfers, Some(epoch), unix_timestamp())?;
+    if violations.is_empty() {
+        return Ok((Some(epoch), Vec::new()));
+    }
+    if !policy.override_budget {
+        return Err(Error::FundingCancelled(format!(
+            "Funding cancelled: budget exceeded ({}). Pass --override-budget to send it anyway",
+            violations.join("; ")
+        )));
+    }
+    let warnings = violations
+        .into_iter()
+        .map(|violation| Warning::new(WARN_BUDGET_OVERRIDDEN, format!("Budget exceeded (--override-budget): {}", violation)))
+        .collect();
+    Ok((Some(epoch), warnings))
+}
+
+/// Amounts already sent within the budget windows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Spent {
+    day: u64,
+    epoch: u64,
+}
+
+/// Sums the successful and unresolved fundings in the ledger, of one validator or of all
+///
+/// An unresolved funding (see `audit::unresolved_fundings`) counts once, until a later entry settles it.
+/// A funding counts toward the epoch only if its entry records the epoch, i.e. it was sent under a budget.
+fn spent(entries: &[AuditEntry], validator: Option<&Pubkey>, epoch: Option<u64>, now: u64) -> Spent {
+    let validator = validator.map(Pubkey::to_string);
+    let since = now.saturating_sub(BUDGET_DAY_SECS);
+    entries
+        .iter()
+        .filter(|entry| entry.event == audit::EVENT_PDA_FUND && entry.status == audit::STATUS_SUCCESS)
+        .chain(audit::unresolved_fundings(entries))
+        .filter(|entry| validator.is_none() || entry.validator == validator)
+        .fold(Spent::default(), |mut spent, entry| {
+            let amount = entry.amount_lamports.unwrap_or(0);
+            if entry.timestamp > since {
+                spent.day = spent.day.saturating_add(amount);
+            }
+            if epoch.is_some() && entry.epoch == epoch {
+                spent.epoch = spent.epoch.saturating_add(amount);
+            }
+            spent
+        })
+}
+
+/// Returns the caps a set of transfers would exceed
+///
+/// # Arguments
+/// * `budget` - Global and per-validator caps
+/// * `entries` - Audit log entries (the spend ledger)
+/// * `transfers` - Validators and amounts in lamports about to be sent together
+/// * `epoch` - Current epoch (None skips the `max_per_epoch` caps)
+/// * `now` - Current Unix timestamp
+///
+/// # Returns
+/// * `Vec<String>` - One reason per exceeded cap, global caps first
+pub fn budget_violations(budget: &Budget, entries: &[AuditEntry], transfers: &[(Pubkey, u64)], epoch: Option<u64>, now: u64) -> Vec<String> {
+    let mut violations = Vec::new();
+    // A total past u64::MAX exceeds every cap instead of wrapping below it
+    let total = transfers.iter().fold(0u64, |total, (_, lamports)| total.saturating_add(*lamports));
+    let largest = transfers.iter().map(|(_, lamports)| *lamports).max().unwrap_or(0);
+    exceeded(&mut violations, "global", &budget.global, largest, total, spent(entries, None, epoch, now), epoch);
+
+    for (validator_id, lamports) in transfers {
+        if let Some(caps) = budget.validators.get(validator_id) {
+            let scope = format!("validator {}", validator_id);
+            exceeded(&mut violations, &scope, caps, *lamports, *lamports, spent(entries, Some(validator_id), epoch, now), epoch);
+        }
+    }
+    violations
+}
+
+/// Adds a reason for every cap of one scope that the transfers would exceed
+fn exceeded(violations: &mut Vec<String>, scope: &str, caps: &BudgetCaps, largest: u64, total: u64, spent: Spent, epoch: Option<u64>) {
+    if let Some(cap) = caps.per_tx.filter(|cap| largest > *cap) {
+        violations.push(format!("{} max_per_tx is {} SOL, {} SOL requested", scope, format_sol(cap), format_sol(largest)));
+    }
+    if let Some(cap) = caps.per_day.filter(|cap| spent.day.saturating_add(total) > *cap) {
+        violations.push(format!(
+            "{} max_per_day is {} SOL, {} SOL already sent in the last 24 hours and {} SOL requested",
+            scope,
+            format_sol(cap),
+            format_sol(spent.day),
+            format_sol(total)
+        ));
+    }
+    let Some(epoch) = epoch else { return };
+    if let Some(cap) = caps.per_epoch.filter(|cap| spent.epoch.saturating_add(total) > *cap) {
+        violations.push(format!(
+            "{} max_per_epoch is {} SOL, {} SOL already sent in epoch {} and {} SOL requested",
+            scope,
+            format_sol(cap),
+            format_sol(spent.epoch),
+            epoch,
+            format_sol(total)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL: u64 = 1_000_000_000;
+    const NOW: u64 = 1_700_000_000;
+
+    fn funding(validator_id: &Pubkey, lamports: u64, timestamp: u64, epoch: Option<u64>) -> AuditEntry {
+        AuditEntry {
+            timestamp,
+            validator: Some(validator_id.to_string()),
+            amount_lamports: Some(lamports),
+            epoch,
+            ..AuditEntry::new(audit::EVENT_PDA_FUND, audit::STATUS_SUCCESS)
+        }
+    }
+
+    #[test]
+    fn test_parse_caps() {
+        let limits = BudgetLimits { max_per_tx: Some("2.5".to_string()), max_per_day: Some("100lamports".to_string()), max_per_epoch: None };
+        assert_eq!(limits.caps("budget").unwrap(), BudgetCaps { per_tx: Some(2_500_000_000), per_day: Some(100), per_epoch: None });
+        let all = BudgetLimits { max_per_epoch: Some("ALL".to_string()), ..BudgetLimits::default() };
+        assert!(all.caps("budget").unwrap_err().to_string().contains("budget.max_per_epoch"));
+    }
+
+    #[test]
+    fn test_budget_violations() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let budget = Budget {
+            global: BudgetCaps { per_tx: Some(5 * SOL), per_day: Some(10 * SOL), per_epoch: Some(20 * SOL) },
+            validators: BTreeMap::from([(alice, BudgetCaps { per_day: Some(3 * SOL), ..BudgetCaps::default() })]),
+            ledger: PathBuf::new(),
+        };
+        let mut failed = funding(&bob, 50 * SOL, NOW - 60, Some(7));
+        failed.status = audit::STATUS_FAILED.to_string();
+        let entries = vec![
+            funding(&alice, 2 * SOL, NOW - 3_600, Some(7)),
+            funding(&bob, 4 * SOL, NOW - 7_200, Some(7)),
+            // Older than a day, but in the current epoch
+            funding(&bob, 12 * SOL, NOW - 2 * BUDGET_DAY_SECS, Some(7)),
+            failed,
+        ];
+
+        assert!(budget_violations(&budget, &entries, &[(bob, SOL)], Some(7), NOW).is_empty());
+        assert!(budget_violations(&budget, &entries, &[(alice, SOL)], Some(7), NOW).is_empty());
+
+        // The validator cap counts only its own fundings, the global caps count all of them
+        let violations = budget_violations(&budget, &entries, &[(alice, 2 * SOL)], Some(7), NOW);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].starts_with(&format!("validator {} max_per_day", alice)));
+        let violations = budget_violations(&budget, &entries, &[(bob, 4 * SOL), (Pubkey::new_unique(), SOL)], Some(7), NOW);
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].starts_with("global max_per_day"));
+        assert!(violations[1].contains("18 SOL already sent in epoch 7 and 5 SOL requested"));
+
+        // Without a known epoch only the per-transaction and daily caps apply; a new epoch starts from zero
+        assert_eq!(budget_violations(&budget, &entries, &[(bob, 3 * SOL)], Some(7), NOW).len(), 1);
+        assert!(budget_violations(&budget, &entries, &[(bob, 3 * SOL)], None, NOW).is_empty());
+        assert!(budget_violations(&budget, &entries, &[(bob, 3 * SOL)], Some(8), NOW).is_empty());
+        let violations = budget_violations(&budget, &[], &[(bob, 6 * SOL)], Some(8), NOW);
+        assert_eq!(violations, vec!["global max_per_tx is 5 SOL, 6 SOL requested".to_string()]);
+
+        // Amounts that add up past u64::MAX exceed the caps instead of wrapping around
+        let violations = budget_violations(&budget, &[], &[(alice, u64::MAX), (bob, 2)], Some(8), NOW);
+        assert!(violations.iter().any(|violation| violation.starts_with("global max_per_day")));
+    }
+
+    #[test]
+    fn test_unresolved_fundings_count_as_spent() {
+        let alice = Pubkey::new_unique();
+        let budget = Budget {
+            global: BudgetCaps { per_day: Some(10 * SOL), ..BudgetCaps::default() },
+            validators: BTreeMap::new(),
+            ledger: PathBuf::new(),
+        };
+        let attempt = |status: &str, signature: &str| AuditEntry {
+            signature: Some(signature.to_string()),
+            status: status.to_string(),
+            ..funding(&alice, 6 * SOL, NOW - 60, Some(7))
+        };
+        // Sent, then the confirmation timed out: it may have landed
+        let mut entries = vec![attempt(audit::STATUS_PENDING, "Lost"), attempt(audit::STATUS_UNCONFIRMED, "Lost")];
+        let violations = budget_violations(&budget, &entries, &[(alice, 5 * SOL)], Some(7), NOW);
+        assert!(violations[0].contains("6 SOL already sent in the last 24 hours"), "{:?}", violations);
+
+        // Once it is found on chain it counts as the one funding it was
+        entries.push(attempt(audit::STATUS_SUCCESS, "Lost"));
+        assert_eq!(budget_violations(&budget, &entries, &[(alice, 5 * SOL)], Some(7), NOW).len(), 1);
+        assert!(budget_violations(&budget, &entries, &[(alice, 4 * SOL)], Some(7), NOW).is_empty());
+
+        // An attempt settled as failed no longer counts
+        entries.pop();
+        entries.push(attempt(audit::STATUS_FAILED, "Lost"));
+        assert!(budget_violations(&budget, &entries, &[(alice, 5 * SOL)], Some(7), NOW).is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+use crate::audit::audit_log_path;
+use crate::config::{write_atomic, Config};
+use crate::state::{default_state_path, State};
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Version of the bundle layout; bundles with a newer version are refused
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Name of the manifest inside a bundle
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+/// zstd compression level of exported bundles
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Files of the local state and their names inside a bundle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatePaths {
+    /// Config file (aliases, labels, profiles, approvers, notifications)
+    pub config: PathBuf,
+    /// State file (recently used validators)
+    pub state: PathBuf,
+    /// Audit log
+    pub audit_log: PathBuf,
+}
+
+impl StatePaths {
+    /// Default state location and the audit log of the process with the given config file
+    ///
+    /// # Arguments
+    /// * `config` - Config file path (`--config` or the default)
+    pub fn with_config(config: PathBuf) -> Self {
+        StatePaths {
+            config,
+            state: default_state_path(),
+            audit_log: audit_log_path(),
+        }
+    }
+
+    /// Returns `(name in bundle, path)` of every file
+    fn entries(&self) -> [(&'static str, &Path); 3] {
+        [
+            ("config.toml", self.config.as_path()),
+            ("state.json", self.state.as_path()),
+            ("audit.log", self.audit_log.as_path()),
+        ]
+    }
+}
+
+/// Manifest written first into every bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// Bundle layout version (`BUNDLE_FORMAT_VERSION` of the exporting tool)
+    pub format_version: u32,
+    /// Version of the tool that wrote the bundle
+    pub tool_version: String,
+    /// Unix timestamp of the export
+    pub created_at: u64,
+    /// Files in the bundle
+    pub files: Vec<String>,
+    /// Secrets left out of the bundle, to be entered again after import
+    #[serde(default)]
+    pub excluded: Vec<String>,
+}
+
+/// Writes the local state into a `.tar.zst` bundle
+///
+/// Keypair files are never included (the config only refers to their paths), and secrets in the
+/// config are stripped and listed in `BundleManifest::excluded`. Files that do not exist are skipped.
+///
+/// # Arguments
+/// * `paths` - Local state files
+/// * `out` - Bundle path
+/// * `created_at` - Unix timestamp of the export
+///
+/// # Returns
+/// * `Result<BundleManifest, Error>` - Manifest of the written bundle or error
+pub fn export_bundle(paths: &StatePaths, out: &Path, created_at: u64) -> Result<BundleManifest, Error> {
+    let mut contents = Vec::new();
+    let mut excluded = Vec::new();
+    for (name, path) in paths.entries() {
+        if !path.exists() {
+            continue;
+        }
+        let data = if name == "config.toml" {
+            let mut config = Config::load(path)?;
+            excluded = config.strip_secrets();
+            toml::to_string_pretty(&config)
+                .map_err(|e| Error::Io(format!("Failed to serialize config: {}", e)))?
+                .into_bytes()
+        } else {
+            fs::read(path).map_err(|e| Error::Io(format!("Failed to read {}: {}", path.display(), e)))?
+        };
+        contents.push((name, data));
+    }
+
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at,
+        files: contents.iter().map(|(name, _)| name.to_string()).collect(),
+        excluded,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| Error::Io(format!("Failed to serialize bundle manifest: {}", e)))?;
+
+    let mut builder = tar::Builder::new(Vec::new());
+    for (name, data) in std::iter::once((MANIFEST_NAME, manifest_json)).chain(contents) {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o600);
+        header.set_mtime(created_at);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, name, data.as_slice())
+            .map_err(|e| Error::Io(format!("Failed to write bundle entry {}: {}", name, e)))?;
+    }
+    let archive = builder.into_inner().map_err(|e| Error::Io(format!("Failed to write bundle: {}", e)))?;
+    let compressed = zstd::stream::encode_all(archive.as_slice(), COMPRESSION_LEVEL)
+        .map_err(|e| Error::Io(format!("Failed to compress bundle: {}", e)))?;
+    write_atomic(out, &compressed)?;
+    Ok(manifest)
+}
+
+/// Restores the local state from a bundle written by `export_bundle`
+///
+/// Everything is checked before the first file is written: the manifest version, that every
+/// file listed is present and that the config and state parse. Existing files are only replaced
+/// with `overwrite`.
+///
+/// # Arguments
+/// * `bundle` - Bundle path
+/// * `paths` - Local state files to restore
+/// * `overwrite` - Replace existing files (`--force`)
+///
+/// # Returns
+/// * `Result<BundleManifest, Error>` - Manifest of the imported bundle, or `Error::InvalidInput` for an unusable bundle
+pub fn import_bundle(bundle: &Path, paths: &StatePaths, overwrite: bool) -> Result<BundleManifest, Error> {
+    let compressed = fs::read(bundle).map_err(|e| Error::InvalidInput(format!("Failed to read bundle {}: {}", bundle.display(), e)))?;
+    let files = read_entries(&compressed).map_err(|e| Error::InvalidInput(format!("Invalid bundle {}: {}", bundle.display(), e)))?;
+
+    let manifest: BundleManifest = files
+        .get(MANIFEST_NAME)
+        .ok_or_else(|| Error::InvalidInput(format!("Invalid bundle {}: no {}", bundle.display(), MANIFEST_NAME)))
+        .and_then(|data| {
+            serde_json::from_slice(data).map_err(|e| Error::InvalidInput(format!("Invalid bundle manifest: {}", e)))
+        })?;
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(Error::InvalidInput(format!(
+            "Bundle format version {} (written by version {}) is newer than the supported version {}; upgrade this tool first",
+            manifest.format_version, manifest.tool_version, BUNDLE_FORMAT_VERSION
+        )));
+    }
+
+    let mut restore = Vec::new();
+    for (name, path) in paths.entries() {
+        if !manifest.files.iter().any(|file| file == name) {
+            continue;
+        }
+        let data = files
+            .get(name)
+            .ok_or_else(|| Error::InvalidInput(format!("Invalid bundle: {} is listed in the manifest but missing", name)))?;
+        let text = std::str::from_utf8(data).map_err(|_| Error::InvalidInput(format!("Invalid bundle: {} is not UTF-8", name)))?;
+        match name {
+            "config.toml" => {
+                toml::from_str::<Config>(text).map_err(|e| Error::InvalidInput(format!("Invalid config in bundle: {}", e)))?;
+            }
+            "state.json" => {
+                serde_json::from_str::<State>(text).map_err(|e| Error::InvalidInput(format!("Invalid state in bundle: {}", e)))?;
+            }
+            _ => {}
+        }
+        restore.push((path, data));
+    }
+
+    let existing: Vec<String> = restore.iter().filter(|(path, _)| path.exists()).map(|(path, _)| path.display().to_string()).collect();
+    if !existing.is_empty() && !overwrite {
+        return Err(Error::InvalidInput(format!(
+            "Refusing to overwrite {}; use --force to replace them",
+            existing.join(", ")
+        )));
+    }
+
+    for (path, data) in restore {
+        write_atomic(path, data)?;
+    }
+    Ok(manifest)
+}
+
+/// Decompresses a bundle and returns its entries by name
+fn read_entries(compressed: &[u8]) -> std::io::Result<BTreeMap<String, Vec<u8>>> {
+    let archive = zstd::stream::decode_all(compressed)?;
+    let mut entries = BTreeMap::new();
+    for entry in tar::Archive::new(archive.as_slice()).entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        entries.insert(name, data);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notify::{NotifyConfig, NotifyKind};
+
+    fn test_paths(name: &str) -> StatePaths {
+        let dir = std::env::temp_dir().join(format!("dz_validator_pda_bundle_{}_{}", std::process::id(), name));
+        StatePaths {
+            config: dir.join("config.toml"),
+            state: dir.join("state.json"),
+            audit_log: dir.join("audit.log"),
+        }
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let source = test_paths("source");
+        let config = Config {
+            keypair: Some("/secure/funder.json".to_string()),
+            validators: BTreeMap::from([("mainnet-01".to_string(), "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL".to_string())]),
+            notify: Some(NotifyConfig {
+                kind: NotifyKind::Telegram,
+                bot_token: Some("123:secret".to_string()),
+                chat_id: Some("42".to_string()),
+                ..NotifyConfig::default()
+            }),
+            ..Config::default()
+        };
+        config.save(&source.config).unwrap();
+        let mut state = State::default();
+        state.record_validator("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL");
+        state.save(&source.state).unwrap();
+        fs::write(&source.audit_log, "{\"line\":1}\n").unwrap();
+
+        let bundle = source.config.with_file_name("bundle.tar.zst");
+        let manifest = export_bundle(&source, &bundle, 1_700_000_000).unwrap();
+        assert_eq!(manifest.files, vec!["config.toml", "state.json", "audit.log"]);
+        assert_eq!(manifest.excluded, vec!["notify.bot_token"]);
+
+        let target = test_paths("target");
+        let imported = import_bundle(&bundle, &target, false).unwrap();
+        assert_eq!(imported, manifest);
+
+        // Everything but the secret comes back
+        let restored = Config::load(&target.config).unwrap();
+        assert_eq!(restored.keypair, config.keypair);
+        assert_eq!(restored.validators, config.validators);
+        assert_eq!(restored.notify.as_ref().unwrap().chat_id.as_deref(), Some("42"));
+        assert_eq!(restored.notify.as_ref().unwrap().bot_token, None);
+        assert_eq!(State::load(&target.state).unwrap(), state);
+        assert_eq!(fs::read_to_string(&target.audit_log).unwrap(), "{\"line\":1}\n");
+        assert!(!String::from_utf8_lossy(&zstd::stream::decode_all(fs::read(&bundle).unwrap().as_slice()).unwrap()).contains("123:secret"));
+
+        // Existing files are only replaced with --force
+        let error = import_bundle(&bundle, &target, false).unwrap_err();
+        assert!(error.to_string().contains("use --force"), "{}", error);
+        assert!(import_bundle(&bundle, &target, true).is_ok());
+
+        for paths in [source, target] {
+            fs::remove_dir_all(paths.config.parent().unwrap()).ok();
+        }
+    }
+
+    #[test]
+    fn test_import_refuses_newer_or_broken_bundles() {
+        let paths = test_paths("newer");
+        let bundle = paths.config.with_file_name("bundle.tar.zst");
+        export_bundle(&paths, &bundle, 0).unwrap();
+
+        // Rewrite the manifest with a future format version
+        let mut files = read_entries(&fs::read(&bundle).unwrap()).unwrap();
+        let mut manifest: BundleManifest = serde_json::from_slice(&files[MANIFEST_NAME]).unwrap();
+        assert!(manifest.files.is_empty());
+        manifest.format_version = BUNDLE_FORMAT_VERSION + 1;
+        files.insert(MANIFEST_NAME.to_string(), serde_json::to_vec(&manifest).unwrap());
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, data) in &files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_cksum();
+            builder.append_data(&mut header, name, data.as_slice()).unwrap();
+        }
+        fs::write(&bundle, zstd::stream::encode_all(builder.into_inner().unwrap().as_slice(), 0).unwrap()).unwrap();
+
+        let error = import_bundle(&bundle, &paths, false).unwrap_err();
+        assert!(error.is_user_error());
+        assert!(error.to_string().contains("newer than the supported version"), "{}", error);
+
+        fs::write(&bundle, b"not a bundle").unwrap();
+        assert!(import_bundle(&bundle, &paths, false).unwrap_err().to_string().contains("Invalid bundle"));
+        fs::remove_dir_all(paths.config.parent().unwrap()).ok();
+    }
+}
@@ -0,0 +1,187 @@
+use crate::audit::{self, AuditEntry};
+use crate::precedence::parse_rpc_url;
+use crate::rpc::{rpc_client_with_commitment, SolanaRpc};
+use crate::{
+    generate_deposit_pda, get_account_balance, get_account_balances, is_validator_in_gossip, prepare_pda_funding, send_signed_funding, sign_pda_funding, Amount, Error,
+    FundingOutcome, FundingPolicy, FundingSigners,
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Library entry point holding one RPC connection for all operations on validator PDAs
+#[derive(Clone)]
+pub struct DzValidatorClient {
+    rpc: Arc<dyn SolanaRpc>,
+    audit_log: PathBuf,
+}
+
+impl DzValidatorClient {
+    /// Connects to an RPC endpoint
+    ///
+    /// Fundings are recorded in the audit log of the process (see `audit::audit_log_path`), like fundings of the CLI.
+    ///
+    /// # Arguments
+    /// * `rpc_url` - `http://` or `https://` RPC endpoint
+    /// * `commitment` - Commitment of reads, preflight simulation and confirmation waits
+    ///
+    /// # Returns
+    /// * `Result<DzValidatorClient, Error>` - Client, or `Error::InvalidInput` for an invalid URL
+    pub fn new(rpc_url: &str, commitment: CommitmentConfig) -> Result<Self, Error> {
+        let rpc_url = parse_rpc_url(rpc_url)?;
+        Ok(Self::with_rpc(Arc::new(rpc_client_with_commitment(&rpc_url, commitment))))
+    }
+
+    /// Creates a client on top of an existing `SolanaRpc` (e.g. `MockRpc` in tests)
+    ///
+    /// # Arguments
+    /// * `rpc` - RPC client shared by all operations
+    pub fn with_rpc(rpc: Arc<dyn SolanaRpc>) -> Self {
+        DzValidatorClient { rpc, audit_log: audit::audit_log_path() }
+    }
+
+    /// Records fundings in, and reads the history from, another audit log
+    ///
+    /// # Arguments
+    /// * `path` - Audit log path
+    pub fn with_audit_log(mut self, path: &Path) -> Self {
+        self.audit_log = path.to_path_buf();
+        self
+    }
+
+    /// Returns the underlying RPC client, for the free functions of the crate
+    pub fn rpc(&self) -> &dyn SolanaRpc {
+        self.rpc.as_ref()
+    }
+
+    /// Returns the deposit PDA of a validator
+    ///
+    /// # Arguments
+    /// * `validator_id` - Validator identity pubkey
+    pub fn deposit_pda(&self, validator_id: &Pubkey) -> Pubkey {
+        generate_deposit_pda(validator_id)
+    }
+
+    /// Reads the balance of the deposit PDA of a validator
+    ///
+    /// # Arguments
+    /// * `validator_id` - Validator identity pubkey
+    ///
+    /// # Returns
+    /// * `Result<u64, Error>` - Balance in lamports (0 if the PDA does not exist) or error
+    pub async fn balance(&self, validator_id: &Pubkey) -> Result<u64, Error> {
+        get_account_balance(&self.deposit_pda(validator_id), self.rpc()).await
+    }
+
+    /// Reads the balances of the deposit PDAs of many validators
+    ///
+    /// The PDAs are read with one `getMultipleAccounts` request per 100 validators instead of one
+    /// request each.
+    ///
+    /// # Arguments
+    /// * `validator_ids` - Validator identity pubkeys
+    ///
+    /// # Returns
+    /// * `Result<Vec<u64>, Error>` - Balances in lamports in the order of `validator_ids` or error
+    pub async fn balances(&self, validator_ids: &[Pubkey]) -> Result<Vec<u64>, Error> {
+        let deposit_keys: Vec<Pubkey> = validator_ids.iter().map(generate_deposit_pda).collect();
+        get_account_balances(&deposit_keys, self.rpc()).await
+    }
+
+    /// Checks whether a validator is in gossip
+    ///
+    /// # Arguments
+    /// * `validator_id` - Validator identity pubkey
+    ///
+    /// # Returns
+    /// * `Result<bool, Error>` - True if the validator is in gossip, or error
+    pub async fn is_in_gossip(&self, validator_id: &Pubkey) -> Result<bool, Error> {
+        is_validator_in_gossip(validator_id, self.rpc()).await
+    }
+
+    /// Funds the deposit PDA of a validator (see `pda_fund_address`) and records the result in the audit log
+    ///
+    /// The signed transaction is recorded as pending before it is sent, so a funding whose outcome is lost
+    /// still counts for the duplicate check and the budget.
+    ///
+    /// # Arguments
+    /// * `validator_id` - Validator identity pubkey
+    /// * `signers` - Funding wallet, fee payer and additional signers
+    /// * `amount` - Amount to transfer
+    /// * `policy` - Health policy the validator must satisfy before funding
+    ///
+    /// # Returns
+    /// * `Result<FundingOutcome, Error>` - Signature, amount and fee, or error
+    pub async fn fund(&self, validator_id: &Pubkey, signers: &FundingSigners, amount: Amount, policy: &FundingPolicy) -> Result<FundingOutcome, Error> {
+        let deposit_key = self.deposit_pda(validator_id);
+        let signed = prepare_pda_funding(validator_id, signers, amount, policy, None, self.rpc()).await.and_then(sign_pda_funding);
+        let (attempt, result) = match signed {
+            Ok(signed) => {
+                let attempt = signed.attempt();
+                audit::append_entry(&self.audit_log, &audit::pending_funding_entry(validator_id, &deposit_key, &attempt))?;
+                (Some(attempt), send_signed_funding(signed, self.rpc()).await)
+            }
+            Err(e) => (None, Err(e)),
+        };
+        if let Some(entry) = audit::funding_entry(validator_id, &deposit_key, &result, attempt.as_ref()) {
+            audit::append_entry(&self.audit_log, &entry)?;
+        }
+        result
+    }
+
+    /// Returns the funding attempts of a validator from the audit log, newest first
+    ///
+    /// # Arguments
+    /// * `validator_id` - Validator identity pubkey
+    ///
+    /// # Returns
+    /// * `Result<Vec<AuditEntry>, Error>` - SOL and token fundings, successful and failed, or error
+    pub fn history(&self, validator_id: &Pubkey) -> Result<Vec<AuditEntry>, Error> {
+        let entries = audit::read_entries(&self.audit_log)?;
+        Ok(audit::validator_history(&entries, &validator_id.to_string()).into_iter().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    const SOL: u64 = 1_000_000_000;
+
+    #[tokio::test]
+    async fn test_client_reuses_one_connection() {
+        let validator_id = Pubkey::new_unique();
+        let payer = Keypair::new();
+        let keypair_path = std::env::temp_dir().join(format!("dz_validator_pda_client_{}.json", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&payer, &keypair_path).unwrap();
+        let audit_log = keypair_path.with_extension("log");
+        let _ = std::fs::remove_file(&audit_log);
+
+        let rpc = Arc::new(MockRpc::new().with_gossip_node(validator_id).with_wallet(payer.pubkey(), 3 * SOL));
+        let client = DzValidatorClient::with_rpc(rpc.clone()).with_audit_log(&audit_log);
+        assert_eq!(client.deposit_pda(&validator_id), generate_deposit_pda(&validator_id));
+        assert!(client.is_in_gossip(&validator_id).await.unwrap());
+        assert_eq!(client.balance(&validator_id).await.unwrap(), 0);
+        assert_eq!(client.balances(&[validator_id, Pubkey::new_unique()]).await.unwrap(), vec![0, 0]);
+
+        let signers = FundingSigners::new(keypair_path.to_str().unwrap());
+        let outcome = client.fund(&validator_id, &signers, Amount::Lamports(SOL), &FundingPolicy::default()).await.unwrap();
+        assert_eq!(rpc.sent_transactions().len(), 1);
+
+        // A funding that cannot be covered is recorded as failed
+        assert!(client.fund(&validator_id, &signers, Amount::Lamports(10 * SOL), &FundingPolicy::default()).await.is_err());
+
+        let history = client.history(&validator_id).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].status, audit::STATUS_FAILED);
+        assert_eq!(history[1].signature.as_deref(), Some(outcome.signature.as_str()));
+        assert!(client.history(&Pubkey::new_unique()).unwrap().is_empty());
+
+        assert!(DzValidatorClient::new("api.mainnet-beta.solana.com", CommitmentConfig::confirmed()).is_err());
+        std::fs::remove_file(keypair_path).ok();
+        std::fs::remove_file(audit_log).ok();
+    }
+}
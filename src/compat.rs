@@ -0,0 +1,324 @@
+use crate::args::ParsedArgs;
+use crate::report::{Warning, WARN_RPC_FALLBACK, WARN_RPC_UNSUPPORTED};
+use crate::rpc::SolanaRpc;
+use crate::Error;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::OnceLock;
+
+/// Owner of feature gate accounts
+pub const FEATURE_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("Feature111111111111111111111111111111111111");
+
+/// Feature gate that enables v0 transaction messages (`versioned_tx_message_enabled`)
+pub const VERSIONED_TX_MESSAGE_FEATURE: Pubkey = solana_sdk::pubkey!("3KZZ6Ks1885aGBQ45fwRcPXVBCtzUvxhUTkwKMR41Tca");
+
+/// An RPC method or parameter the tool relies on, and the oldest node version it is supported on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capability {
+    /// Method or parameter as named in the RPC documentation
+    pub method: &'static str,
+    /// Oldest supported node version as (major, minor, patch)
+    pub min_version: (u64, u64, u64),
+    /// Feature gate that must also be active on the cluster
+    pub feature_gate: Option<Pubkey>,
+    /// Operations and options that use it
+    pub used_by: &'static str,
+    /// What the tool does instead on a node without it, None if there is no fallback
+    pub fallback: Option<&'static str>,
+}
+
+/// Recent fee samples that `--priority-fee` is checked against
+pub const PRIORITY_FEE_SAMPLES: Capability = Capability {
+    method: "getRecentPrioritizationFees",
+    min_version: (1, 14, 0),
+    feature_gate: None,
+    used_by: "--priority-fee",
+    fallback: Some("the priority fee is sent without checking it against recent network fees"),
+};
+
+/// Reads pinned to a minimum slot
+pub const MIN_CONTEXT_SLOT: Capability = Capability {
+    method: "minContextSlot",
+    min_version: (1, 14, 0),
+    feature_gate: None,
+    used_by: "--consistent-snapshot",
+    fallback: None,
+};
+
+/// v0 transaction messages
+pub const V0_MESSAGES: Capability = Capability {
+    method: "sendTransaction with v0 messages",
+    min_version: (1, 14, 0),
+    feature_gate: Some(VERSIONED_TX_MESSAGE_FEATURE),
+    used_by: "--tx-version 0",
+    fallback: Some("the transfer is sent as a legacy transaction"),
+};
+
+/// Address lookup tables, which only v0 messages can use
+pub const LOOKUP_TABLES: Capability = Capability {
+    method: "sendTransaction with address lookup tables",
+    min_version: (1, 14, 0),
+    feature_gate: Some(VERSIONED_TX_MESSAGE_FEATURE),
+    used_by: "--alt",
+    fallback: None,
+};
+
+/// Transactions read back from the ledger, including v0 ones
+pub const TRANSACTION_HISTORY: Capability = Capability {
+    method: "getTransaction with maxSupportedTransactionVersion",
+    min_version: (1, 14, 0),
+    feature_gate: None,
+    used_by: "verify-deposit, pda-balance-history, revenue-report, --receipt and --verify-delta",
+    fallback: None,
+};
+
+/// Inflation rewards of epochs paid out with partitioned rewards
+pub const INFLATION_REWARDS: Capability = Capability {
+    method: "getInflationReward",
+    min_version: (2, 0, 0),
+    feature_gate: None,
+    used_by: "revenue-report",
+    fallback: None,
+};
+
+/// Minimum-supported matrix
+pub const CAPABILITIES: &[Capability] =
+    &[PRIORITY_FEE_SAMPLES, MIN_CONTEXT_SLOT, V0_MESSAGES, LOOKUP_TABLES, TRANSACTION_HISTORY, INFLATION_REWARDS];
+
+static COMPATIBILITY: OnceLock<Compatibility> = OnceLock::new();
+
+/// Returns the capabilities an operation needs with the given options
+///
+/// Offline signing sends nothing and reads no history, so it needs none.
+///
+/// # Arguments
+/// * `operation` - Operation name
+/// * `parsed` - Parsed options
+///
+/// # Returns
+/// * `Vec<Capability>` - Required capabilities in matrix order, empty if the run needs nothing beyond the basics
+pub fn required_capabilities(operation: &str, parsed: &ParsedArgs) -> Vec<Capability> {
+    if parsed.has("sign-only") {
+        return Vec::new();
+    }
+    let reads_history = ["verify-deposit", "pda-balance-history", "revenue-report"].contains(&operation);
+    let required = [
+        (PRIORITY_FEE_SAMPLES, parsed.has("priority-fee")),
+        (MIN_CONTEXT_SLOT, parsed.has("consistent-snapshot")),
+        (V0_MESSAGES, parsed.value("tx-version") == Some("0") && !parsed.has("alt")),
+        (LOOKUP_TABLES, parsed.has("alt")),
+        (TRANSACTION_HISTORY, reads_history || parsed.has("receipt") || parsed.has("verify-delta")),
+        (INFLATION_REWARDS, operation == "revenue-report"),
+    ];
+    required.into_iter().filter(|(_, needed)| *needed).map(|(capability, _)| capability).collect()
+}
+
+/// Parses the leading `major.minor.patch` of a node version like `2.3.6` or `1.18.26-jito`
+///
+/// # Arguments
+/// * `version` - Version reported by `getVersion`
+///
+/// # Returns
+/// * `Option<(u64, u64, u64)>` - Version, or None if it does not start with three numbers
+pub fn parse_node_version(version: &str) -> Option<(u64, u64, u64)> {
+    let numeric = version.split(|c: char| !c.is_ascii_digit() && c != '.').next()?;
+    let mut parts = numeric.split('.').map(str::parse::<u64>);
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch))) => Some((major, minor, patch)),
+        _ => None,
+    }
+}
+
+/// A required capability the node or the cluster lacks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingCapability {
+    /// The capability
+    pub capability: Capability,
+    /// Why it is missing
+    pub reason: String,
+}
+
+/// Result of the pre-flight compatibility check of an RPC node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compatibility {
+    /// Version reported by `getVersion`
+    pub version: String,
+    /// Required capabilities that are not available
+    pub missing: Vec<MissingCapability>,
+}
+
+impl Compatibility {
+    /// Returns true unless the capability was found missing
+    pub fn supports(&self, capability: &Capability) -> bool {
+        !self.missing.iter().any(|missing| missing.capability == *capability)
+    }
+
+    /// Returns one warning per missing capability
+    ///
+    /// A capability with a fallback gets `rpc_fallback` and says what the tool does instead; one
+    /// without gets `rpc_unsupported`, since the operation that needs it will probably fail.
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.missing
+            .iter()
+            .map(|missing| {
+                let capability = &missing.capability;
+                match capability.fallback {
+                    Some(fallback) => Warning::new(
+                        WARN_RPC_FALLBACK,
+                        format!("RPC node does not support {} ({}), needed by {}; {}", capability.method, missing.reason, capability.used_by, fallback),
+                    ),
+                    None => Warning::new(
+                        WARN_RPC_UNSUPPORTED,
+                        format!(
+                            "RPC node does not support {} ({}), needed by {}; the run will probably fail, use an up-to-date endpoint",
+                            capability.method, missing.reason, capability.used_by
+                        ),
+                    ),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Checks the node version and feature gates behind the required capabilities
+///
+/// Version numbers below 1.0 (Firedancer numbers its releases on its own scheme) cannot be compared
+/// with the matrix, so for those nodes only the feature gates are checked.
+///
+/// # Arguments
+/// * `required` - Capabilities the run needs (see `required_capabilities`)
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Compatibility, Error>` - Missing capabilities, or error if the node could not be queried
+pub async fn check_compatibility(required: &[Capability], rpc: &dyn SolanaRpc) -> Result<Compatibility, Error> {
+    let version = rpc.get_version().await.map_err(|e| Error::from_client_error("Failed to get node version", &e))?;
+    let comparable = parse_node_version(&version).filter(|(major, _, _)| *major > 0);
+
+    let mut missing = Vec::new();
+    for capability in required {
+        let reason = match (comparable, capability.feature_gate) {
+            (Some(node), _) if node < capability.min_version => {
+                let (major, minor, patch) = capability.min_version;
+                Some(format!("node version {} is older than {}.{}.{}", version, major, minor, patch))
+            }
+            (_, Some(feature)) if !is_feature_active(&feature, rpc).await? => {
+                Some(format!("feature gate {} is not active on the cluster", feature))
+            }
+            _ => None,
+        };
+        if let Some(reason) = reason {
+            missing.push(MissingCapability { capability: *capability, reason });
+        }
+    }
+    Ok(Compatibility { version, missing })
+}
+
+/// Returns true if a feature gate is active on the cluster
+///
+/// A feature account holds a bincode `Option<u64>` activation slot: it exists with `None` once the
+/// feature is staged, and becomes `Some` when the feature activates.
+///
+/// # Arguments
+/// * `feature` - Feature gate ID
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<bool, Error>` - True if the feature account shows an activation slot
+pub async fn is_feature_active(feature: &Pubkey, rpc: &dyn SolanaRpc) -> Result<bool, Error> {
+    let account = rpc
+        .get_account_with_context(feature)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get feature gate account", &e))?
+        .value;
+    Ok(account.is_some_and(|account| account.owner == FEATURE_PROGRAM_ID && account.data.first() == Some(&1)))
+}
+
+/// Records the compatibility check of this run, so fallbacks apply to every later request
+///
+/// Only the first check of a process is kept.
+pub fn set_compatibility(compatibility: Compatibility) {
+    let _ = COMPATIBILITY.set(compatibility);
+}
+
+/// Returns false if this run's compatibility check found the capability missing
+///
+/// Without a recorded check every capability is assumed to be available.
+pub fn is_supported(capability: &Capability) -> bool {
+    COMPATIBILITY.get().is_none_or(|compatibility| compatibility.supports(capability))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::{SWITCH_FLAGS, VALUE_FLAGS};
+    use crate::rpc::mock::MockRpc;
+    use solana_sdk::account::Account;
+
+    fn parse(args: &[&str]) -> ParsedArgs {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        ParsedArgs::parse(&args, VALUE_FLAGS, SWITCH_FLAGS).unwrap()
+    }
+
+    fn feature_account(activated_at: Option<u64>) -> Account {
+        let mut data = vec![0u8; 9];
+        if let Some(slot) = activated_at {
+            data[0] = 1;
+            data[1..].copy_from_slice(&slot.to_le_bytes());
+        }
+        Account { lamports: 1_000_000, data, owner: FEATURE_PROGRAM_ID, executable: false, rent_epoch: 0 }
+    }
+
+    #[test]
+    fn test_parse_node_version() {
+        assert_eq!(parse_node_version("2.3.6"), Some((2, 3, 6)));
+        assert_eq!(parse_node_version("1.18.26-jito"), Some((1, 18, 26)));
+        assert_eq!(parse_node_version("0.503.20214"), Some((0, 503, 20214)));
+        assert_eq!(parse_node_version("unknown"), None);
+        assert_eq!(parse_node_version("2.1"), None);
+    }
+
+    #[test]
+    fn test_required_capabilities() {
+        assert!(required_capabilities("pda-balance", &parse(&["pda-balance", "V"])).is_empty());
+        assert_eq!(
+            required_capabilities("pda-fund-address", &parse(&["--priority-fee", "5000", "--tx-version", "0", "--receipt", "r.json"])),
+            vec![PRIORITY_FEE_SAMPLES, V0_MESSAGES, TRANSACTION_HISTORY]
+        );
+        // A lookup table has no legacy fallback, so --alt replaces the v0 requirement
+        assert_eq!(required_capabilities("pda-fund-many", &parse(&["--tx-version", "0", "--alt", "T"])), vec![LOOKUP_TABLES]);
+        assert_eq!(required_capabilities("revenue-report", &parse(&[])), vec![TRANSACTION_HISTORY, INFLATION_REWARDS]);
+        assert!(required_capabilities("pda-fund-address", &parse(&["--sign-only", "--priority-fee", "5000"])).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_check_compatibility() {
+        let required = [PRIORITY_FEE_SAMPLES, V0_MESSAGES, INFLATION_REWARDS];
+        let current = MockRpc::new().with_account(VERSIONED_TX_MESSAGE_FEATURE, feature_account(Some(128)));
+        let compatibility = check_compatibility(&required, &current).await.unwrap();
+        assert_eq!((compatibility.version.as_str(), compatibility.missing.len()), ("3.0.0", 0));
+        assert!(compatibility.warnings().is_empty());
+
+        // Old node: every capability is below its minimum version
+        let old = MockRpc::new().with_version("1.13.5").with_account(VERSIONED_TX_MESSAGE_FEATURE, feature_account(Some(128)));
+        let compatibility = check_compatibility(&required, &old).await.unwrap();
+        assert_eq!(compatibility.missing.len(), 3);
+        assert!(!compatibility.supports(&PRIORITY_FEE_SAMPLES));
+        assert!(compatibility.supports(&MIN_CONTEXT_SLOT));
+        let warnings = compatibility.warnings();
+        let codes: Vec<&str> = warnings.iter().map(|warning| warning.code.as_str()).collect();
+        assert_eq!(codes, vec![WARN_RPC_FALLBACK, WARN_RPC_FALLBACK, WARN_RPC_UNSUPPORTED]);
+        assert!(warnings[0].message.contains("node version 1.13.5 is older than 1.14.0"), "{}", warnings[0].message);
+        assert!(warnings[1].message.ends_with("sent as a legacy transaction"), "{}", warnings[1].message);
+
+        // Current node on a cluster where v0 messages are only staged; Firedancer versions are not compared
+        let staged = MockRpc::new().with_version("0.503.20214").with_account(VERSIONED_TX_MESSAGE_FEATURE, feature_account(None));
+        let compatibility = check_compatibility(&required, &staged).await.unwrap();
+        assert_eq!(compatibility.missing.len(), 1);
+        assert!(compatibility.missing[0].reason.contains("is not active on the cluster"));
+        assert!(!compatibility.supports(&V0_MESSAGES));
+
+        let down = MockRpc::new();
+        down.set_unavailable(true);
+        assert!(check_compatibility(&required, &down).await.is_err());
+    }
+}
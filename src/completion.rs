@@ -0,0 +1,259 @@
+use crate::config::Config;
+use crate::state::State;
+use crate::Error;
+
+/// Top-level operations offered by shell completion
+pub const OPERATIONS: &[&str] = &[
+    "pda-address",
+    "derive",
+    "pda-balance",
+    "pda-balance-history",
+    "revenue-report",
+    "pda-fund-address",
+    "pda-fund-many",
+    "pda-fund-token",
+    "pda-watch",
+    "validator-status",
+    "pda-batch",
+    "allocate",
+    "dashboard",
+    "broadcast",
+    "wallet",
+    "airdrop",
+    "gossip-snapshot",
+    "gossip-diff",
+    "gossip-monitor",
+    "verify-deposit",
+    "verify-receipt",
+    "sign-message",
+    "verify-message",
+    "rpc-check",
+    "keygen",
+    "grind",
+    "fees",
+    "audit",
+    "alias",
+    "state",
+    "plan",
+    "approve",
+    "apply",
+    "run",
+    "completion",
+    "examples",
+];
+
+/// Operations whose second argument is a single validator pubkey or alias
+pub const VALIDATOR_OPERATIONS: &[&str] = &["pda-address", "pda-balance", "pda-balance-history", "revenue-report", "pda-fund-address", "pda-watch", "validator-status"];
+
+/// Hidden operation the completion scripts call for dynamic candidates
+pub const COMPLETE_OPERATION: &str = "__complete";
+
+/// Bash completion; `{program}`, `{operations}` and `{flags}` are substituted
+const BASH_TEMPLATE: &str = r#"_{program}() {
+    local cur prev
+    cur="${COMP_WORDS[COMP_CWORD]}"
+    prev="${COMP_WORDS[COMP_CWORD-1]}"
+
+    case "$prev" in
+        --output) COMPREPLY=($(compgen -W "text json csv" -- "$cur")); return ;;
+        --sort) COMPREPLY=($(compgen -W "name balance stake" -- "$cur")); return ;;
+        --config|--file|--new|--old|--state|--resume) COMPREPLY=($(compgen -f -- "$cur")); return ;;
+    esac
+
+    if [[ "$cur" == --* ]]; then
+        COMPREPLY=($(compgen -W "{flags}" -- "$cur"))
+        return
+    fi
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        COMPREPLY=($(compgen -W "{operations}" -- "$cur"))
+        return
+    fi
+
+    case "${COMP_WORDS[1]}" in
+        pda-batch)
+            if [[ $COMP_CWORD -eq 2 ]]; then
+                COMPREPLY=($(compgen -W "pda-address pda-balance" -- "$cur"))
+            else
+                COMPREPLY=($(compgen -W "$({program} __complete validators 2>/dev/null)" -- "$cur"))
+            fi ;;
+        dashboard|gossip-monitor) COMPREPLY=($(compgen -W "$({program} __complete validators 2>/dev/null)" -- "$cur")) ;;
+        rpc-check) ;;
+        verify-deposit) [[ $COMP_CWORD -eq 3 ]] && COMPREPLY=($(compgen -W "$({program} __complete validators 2>/dev/null)" -- "$cur")) ;;
+        wallet) [[ $COMP_CWORD -eq 2 ]] && COMPREPLY=($(compgen -W "rotate" -- "$cur")) ;;
+        audit) [[ $COMP_CWORD -eq 2 ]] && COMPREPLY=($(compgen -W "stats show" -- "$cur")) ;;
+        alias) [[ $COMP_CWORD -eq 2 ]] && COMPREPLY=($(compgen -W "add remove list" -- "$cur")) ;;
+        keygen)
+            if [[ $COMP_CWORD -eq 2 ]]; then
+                COMPREPLY=($(compgen -W "new grind encrypt" -- "$cur"))
+            else
+                COMPREPLY=($(compgen -f -- "$cur"))
+            fi ;;
+        state)
+            if [[ $COMP_CWORD -eq 2 ]]; then
+                COMPREPLY=($(compgen -W "export import" -- "$cur"))
+            else
+                COMPREPLY=($(compgen -f -- "$cur"))
+            fi ;;
+        completion) [[ $COMP_CWORD -eq 2 ]] && COMPREPLY=($(compgen -W "bash zsh fish" -- "$cur")) ;;
+        examples) [[ $COMP_CWORD -eq 2 ]] && COMPREPLY=($(compgen -W "{operations}" -- "$cur")) ;;
+        broadcast|run|pda-fund-many|airdrop|gossip-snapshot|gossip-diff|verify-receipt) COMPREPLY=($(compgen -f -- "$cur")) ;;
+        *)
+            if [[ $COMP_CWORD -eq 2 ]]; then
+                COMPREPLY=($(compgen -W "$({program} __complete validators 2>/dev/null)" -- "$cur"))
+            else
+                COMPREPLY=($(compgen -f -- "$cur"))
+            fi ;;
+    esac
+}
+complete -F _{program} {program}
+"#;
+
+/// Fish completion; `{program}`, `{operations}` and `{single}` are substituted, flags are appended
+const FISH_TEMPLATE: &str = r#"complete -c {program} -f
+complete -c {program} -n "__fish_use_subcommand" -a "{operations}"
+complete -c {program} -n "__fish_seen_subcommand_from {single}" -a "({program} __complete validators 2>/dev/null)"
+complete -c {program} -n "__fish_seen_subcommand_from pda-batch" -a "pda-address pda-balance ({program} __complete validators 2>/dev/null)"
+complete -c {program} -n "__fish_seen_subcommand_from dashboard gossip-monitor verify-deposit" -a "({program} __complete validators 2>/dev/null)"
+complete -c {program} -n "__fish_seen_subcommand_from wallet" -a "rotate"
+complete -c {program} -n "__fish_seen_subcommand_from audit" -a "stats show"
+complete -c {program} -n "__fish_seen_subcommand_from alias" -a "add remove list"
+complete -c {program} -n "__fish_seen_subcommand_from keygen" -a "new grind encrypt" -F
+complete -c {program} -n "__fish_seen_subcommand_from state" -a "export import"
+complete -c {program} -n "__fish_seen_subcommand_from completion" -a "bash zsh fish"
+complete -c {program} -n "__fish_seen_subcommand_from examples" -a "{operations}"
+complete -c {program} -n "__fish_seen_subcommand_from broadcast run pda-fund-many airdrop gossip-snapshot gossip-diff" -F
+"#;
+
+/// Shell to generate a completion script for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    /// Bash (`complete -F`)
+    Bash,
+    /// Zsh, through its bash completion compatibility layer
+    Zsh,
+    /// Fish
+    Fish,
+}
+
+impl Shell {
+    /// Parses `bash`, `zsh` or `fish`
+    ///
+    /// # Arguments
+    /// * `value` - Shell name
+    ///
+    /// # Returns
+    /// * `Result<Shell, Error>` - Shell or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(Error::InvalidInput(format!("Unsupported shell '{}': expected bash, zsh or fish", value))),
+        }
+    }
+}
+
+/// Generates a completion script
+///
+/// Validator arguments are completed at run time with `<program> __complete`, so aliases added to the
+/// config and validators used since the script was installed are offered without regenerating it.
+///
+/// # Arguments
+/// * `shell` - Target shell
+/// * `program` - Executable name
+/// * `flags` - Option names without the leading `--`
+///
+/// # Returns
+/// * `String` - Completion script
+pub fn completion_script(shell: Shell, program: &str, flags: &[&str]) -> String {
+    let operations = OPERATIONS.join(" ");
+    let bash = || {
+        let flags: Vec<String> = flags.iter().map(|flag| format!("--{}", flag)).collect();
+        BASH_TEMPLATE
+            .replace("{program}", program)
+            .replace("{operations}", &operations)
+            .replace("{flags}", &flags.join(" "))
+    };
+
+    match shell {
+        Shell::Bash => bash(),
+        Shell::Zsh => format!("autoload -U +X bashcompinit && bashcompinit\n{}", bash()),
+        Shell::Fish => {
+            let mut script = FISH_TEMPLATE
+                .replace("{program}", program)
+                .replace("{operations}", &operations)
+                .replace("{single}", &VALIDATOR_OPERATIONS.join(" "));
+            for flag in flags {
+                script.push_str(&format!("complete -c {} -l {}\n", program, flag));
+            }
+            script
+        }
+    }
+}
+
+/// Returns the dynamic completion candidates for validator arguments
+///
+/// Configured aliases come first (only when aliases are accepted), then the pubkeys of aliased validators
+/// and the recently used validators from the state store, without duplicates.
+///
+/// # Arguments
+/// * `config` - Loaded configuration
+/// * `state` - Loaded state
+/// * `include_aliases` - Whether the argument accepts aliases
+///
+/// # Returns
+/// * `Vec<String>` - Candidates in a stable order
+pub fn validator_candidates(config: &Config, state: &State, include_aliases: bool) -> Vec<String> {
+    let aliases = config.validators.keys().filter(|_| include_aliases);
+    let pubkeys = config.validators.values().chain(state.recent_validators.iter());
+
+    let mut candidates: Vec<String> = Vec::new();
+    for candidate in aliases.chain(pubkeys) {
+        if !candidates.contains(candidate) {
+            candidates.push(candidate.clone());
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shell() {
+        assert_eq!(Shell::parse("bash").unwrap(), Shell::Bash);
+        assert_eq!(Shell::parse("FISH").unwrap(), Shell::Fish);
+        assert!(Shell::parse("powershell").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_completion_script() {
+        let bash = completion_script(Shell::Bash, "dz_validator_pda", &["output", "sort"]);
+        assert!(bash.contains("complete -F _dz_validator_pda dz_validator_pda"));
+        assert!(bash.contains("\"--output --sort\""));
+        assert!(bash.contains("alias) [[ $COMP_CWORD -eq 2 ]]"));
+        assert!(!bash.contains("{program}"));
+
+        assert!(completion_script(Shell::Zsh, "dz_validator_pda", &[]).starts_with("autoload -U +X bashcompinit"));
+
+        let fish = completion_script(Shell::Fish, "dz_validator_pda", &["output"]);
+        assert!(fish.contains("__fish_seen_subcommand_from pda-address pda-balance pda-balance-history revenue-report pda-fund-address pda-watch validator-status\""));
+        assert!(fish.ends_with("complete -c dz_validator_pda -l output\n"));
+    }
+
+    #[test]
+    fn test_validator_candidates() {
+        let mut config = Config::default();
+        config.validators.insert("mainnet-01".to_string(), "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL".to_string());
+        let mut state = State::default();
+        state.record_validator("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL");
+        state.record_validator("11111111111111111111111111111112");
+
+        assert_eq!(
+            validator_candidates(&config, &state, true),
+            vec!["mainnet-01", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "11111111111111111111111111111112"]
+        );
+        assert_eq!(validator_candidates(&config, &state, false).len(), 2);
+    }
+}
@@ -0,0 +1,447 @@
+use crate::budget::{Budget, BudgetConfig};
+use crate::notify::NotifyConfig;
+use crate::{parse_amount, parse_pubkey, Amount, Error};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the directory used for configuration and data files
+pub const APP_DIR_NAME: &str = "dz_validator_pda";
+
+/// Named set of settings selected with `--profile`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Path to the funding wallet keypair
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keypair: Option<String>,
+}
+
+/// Tool configuration stored as TOML
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Config {
+    /// Path to the default funding wallet keypair
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keypair: Option<String>,
+    /// Named profiles
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+    /// Validator aliases (alias -> identity pubkey)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub validators: BTreeMap<String, String>,
+    /// Address labels for text output (pubkey -> label), merged with built-in labels and aliases
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Keys allowed to approve funding plans (empty allows any key other than the plan's funding wallet)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approvers: Vec<String>,
+    /// Webhook notifications on funding events and low balances
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<NotifyConfig>,
+    /// Balance the funding wallet always keeps for future fees (e.g. "0.05" or "50000000lamports")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reserve_sol: Option<String>,
+    /// RPC endpoint, below `--url` and `DZ_RPC_URL`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+    /// Further RPC endpoints probed by `rpc-check` next to the selected one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rpc_endpoints: Vec<String>,
+    /// Commitment level, below `--commitment` and `DZ_COMMITMENT`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<String>,
+    /// Audit log path, below `--audit-log`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_log: Option<String>,
+    /// Funding caps per transaction, day and epoch, globally and per validator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<BudgetConfig>,
+}
+
+impl Config {
+    /// Loads the configuration, returning the default configuration if the file does not exist
+    ///
+    /// # Arguments
+    /// * `path` - Path to the configuration file
+    ///
+    /// # Returns
+    /// * `Result<Config, Error>` - Loaded configuration or `Error::InvalidInput` if it cannot be parsed
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::InvalidInput(format!("Failed to read config {}: {}", path.display(), e)))?;
+        toml::from_str(&contents)
+            .map_err(|e| Error::InvalidInput(format!("Failed to parse config {}: {}", path.display(), e)))
+    }
+
+    /// Saves the configuration atomically (write to a temporary file, then rename)
+    ///
+    /// # Arguments
+    /// * `path` - Path to the configuration file
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Ok if the file was written
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let contents = toml::to_string_pretty(self)
+            .map_err(|e| Error::Io(format!("Failed to serialize config: {}", e)))?;
+        write_atomic(path, contents.as_bytes())
+    }
+
+    /// Returns the funding wallet keypair path for a profile
+    ///
+    /// # Arguments
+    /// * `profile` - Profile name, or None for the top-level settings
+    ///
+    /// # Returns
+    /// * `Result<Option<&str>, Error>` - Keypair path if configured, or `Error::InvalidInput` for an unknown profile
+    pub fn keypair(&self, profile: Option<&str>) -> Result<Option<&str>, Error> {
+        match profile {
+            None => Ok(self.keypair.as_deref()),
+            Some(name) => self
+                .profiles
+                .get(name)
+                .map(|profile| profile.keypair.as_deref().or(self.keypair.as_deref()))
+                .ok_or_else(|| Error::InvalidInput(format!("Unknown profile '{}'", name))),
+        }
+    }
+
+    /// Resolves a validator alias or pubkey
+    ///
+    /// # Arguments
+    /// * `name_or_pubkey` - Configured alias or base58 pubkey
+    ///
+    /// # Returns
+    /// * `Result<(Option<String>, Pubkey), Error>` - Alias (if the validator has one) and pubkey, or `Error::InvalidInput`
+    pub fn resolve_validator(&self, name_or_pubkey: &str) -> Result<(Option<String>, Pubkey), Error> {
+        if let Some(pubkey) = self.validators.get(name_or_pubkey) {
+            let pubkey = parse_pubkey(pubkey)
+                .map_err(|e| Error::InvalidInput(format!("Invalid pubkey for alias '{}': {}", name_or_pubkey, e)))?;
+            return Ok((Some(name_or_pubkey.to_string()), pubkey));
+        }
+
+        let pubkey = parse_pubkey(name_or_pubkey)
+            .map_err(|_| Error::InvalidInput(format!("'{}' is neither a validator pubkey nor a configured alias", name_or_pubkey)))?;
+        Ok((self.alias_for(&pubkey).map(str::to_string), pubkey))
+    }
+
+    /// Parses the configured plan approvers
+    ///
+    /// # Returns
+    /// * `Result<Vec<Pubkey>, Error>` - Approver pubkeys or `Error::InvalidInput`
+    pub fn approver_pubkeys(&self) -> Result<Vec<Pubkey>, Error> {
+        self.approvers
+            .iter()
+            .map(|approver| {
+                parse_pubkey(approver).map_err(|e| Error::InvalidInput(format!("Invalid approver '{}': {}", approver, e)))
+            })
+            .collect()
+    }
+
+    /// Returns the funding wallet reserve in lamports
+    ///
+    /// # Returns
+    /// * `Result<u64, Error>` - Reserve (0 if not configured) or `Error::InvalidInput` (`ALL` is not a reserve)
+    pub fn reserve_lamports(&self) -> Result<u64, Error> {
+        let Some(value) = &self.reserve_sol else {
+            return Ok(0);
+        };
+        match parse_amount(value) {
+            Ok(Amount::Lamports(lamports)) => Ok(lamports),
+            _ => Err(Error::InvalidInput(format!(
+                "Invalid reserve_sol '{}': expected an amount like 0.05 or 50000000lamports",
+                value
+            ))),
+        }
+    }
+
+    /// Returns the funding budget, with validator aliases resolved
+    ///
+    /// # Arguments
+    /// * `ledger` - Audit log the spending is read from
+    ///
+    /// # Returns
+    /// * `Result<Option<Budget>, Error>` - Budget (None if not configured) or `Error::InvalidInput`
+    pub fn budget(&self, ledger: &Path) -> Result<Option<Budget>, Error> {
+        let Some(budget) = &self.budget else {
+            return Ok(None);
+        };
+        let mut validators = BTreeMap::new();
+        for (name, limits) in &budget.validators {
+            let (_, validator_id) = self.resolve_validator(name)?;
+            validators.insert(validator_id, limits.caps(&format!("budget.validators.{}", name))?);
+        }
+        Ok(Some(Budget { global: budget.limits.caps("budget")?, validators, ledger: ledger.to_path_buf() }))
+    }
+
+    /// Returns the alias of a validator, if configured
+    pub fn alias_for(&self, validator: &Pubkey) -> Option<&str> {
+        let validator = validator.to_string();
+        self.validators
+            .iter()
+            .find(|(_, pubkey)| **pubkey == validator)
+            .map(|(alias, _)| alias.as_str())
+    }
+
+    /// Adds a validator alias to the `[validators]` table (`alias add`)
+    ///
+    /// An alias must not be a pubkey itself and a validator has at most one alias, so every
+    /// argument and every labeled address stays unambiguous.
+    ///
+    /// # Arguments
+    /// * `name` - Alias, e.g. `mainnet-01`
+    /// * `validator` - Validator identity pubkey
+    /// * `force` - Point an existing alias at another validator instead of failing
+    ///
+    /// # Returns
+    /// * `Result<Option<String>, Error>` - Pubkey the alias pointed at before, or `Error::InvalidInput`
+    pub fn add_alias(&mut self, name: &str, validator: &Pubkey, force: bool) -> Result<Option<String>, Error> {
+        if name.is_empty() || name.chars().any(|ch| ch.is_whitespace() || ch == ',' || ch == ':') {
+            return Err(Error::InvalidInput(format!(
+                "Invalid alias '{}': it must not be empty or contain whitespace, ',' or ':'",
+                name
+            )));
+        }
+        if parse_pubkey(name).is_ok() {
+            return Err(Error::InvalidInput(format!("Invalid alias '{}': an alias cannot be a pubkey", name)));
+        }
+        if let Some(other) = self.alias_for(validator).filter(|other| *other != name) {
+            return Err(Error::InvalidInput(format!("Validator {} already has the alias '{}'", validator, other)));
+        }
+        if let Some(existing) = self.validators.get(name).filter(|existing| **existing != validator.to_string() && !force) {
+            return Err(Error::InvalidInput(format!(
+                "Alias '{}' already points at {}; use --force to replace it",
+                name, existing
+            )));
+        }
+        Ok(self.validators.insert(name.to_string(), validator.to_string()))
+    }
+
+    /// Removes a validator alias from the `[validators]` table (`alias remove`)
+    ///
+    /// # Arguments
+    /// * `name` - Alias to remove
+    ///
+    /// # Returns
+    /// * `Result<String, Error>` - Pubkey the alias pointed at, or `Error::InvalidInput` for an unknown alias
+    pub fn remove_alias(&mut self, name: &str) -> Result<String, Error> {
+        self.validators
+            .remove(name)
+            .ok_or_else(|| Error::InvalidInput(format!("Unknown alias '{}'", name)))
+    }
+
+    /// Removes secrets (RPC URLs, webhook URL, bot token) so the config can leave the host
+    ///
+    /// # Returns
+    /// * `Vec<String>` - Names of the removed settings, e.g. `notify.bot_token`
+    pub fn strip_secrets(&mut self) -> Vec<String> {
+        let mut removed = Vec::new();
+        // Provider endpoints usually carry an API key in the URL
+        if self.rpc_url.take().is_some() {
+            removed.push("rpc_url".to_string());
+        }
+        if !self.rpc_endpoints.is_empty() {
+            self.rpc_endpoints.clear();
+            removed.push("rpc_endpoints".to_string());
+        }
+        if let Some(notify) = &mut self.notify {
+            if notify.url.take().is_some() {
+                removed.push("notify.url".to_string());
+            }
+            if notify.bot_token.take().is_some() {
+                removed.push("notify.bot_token".to_string());
+            }
+        }
+        removed
+    }
+
+    /// Replaces every reference to a keypair path (top-level and all profiles)
+    ///
+    /// # Arguments
+    /// * `old_path` - Keypair path being replaced
+    /// * `new_path` - Replacement keypair path
+    ///
+    /// # Returns
+    /// * `usize` - Number of references updated
+    pub fn replace_keypair(&mut self, old_path: &str, new_path: &str) -> usize {
+        let mut updated = 0;
+        let keypairs = std::iter::once(&mut self.keypair)
+            .chain(self.profiles.values_mut().map(|profile| &mut profile.keypair));
+        for keypair in keypairs {
+            if keypair.as_deref() == Some(old_path) {
+                *keypair = Some(new_path.to_string());
+                updated += 1;
+            }
+        }
+        updated
+    }
+}
+
+/// Returns the default configuration file path (`~/.config/dz_validator_pda/config.toml` on Linux)
+pub fn default_config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR_NAME)
+        .join("config.toml")
+}
+
+/// Returns the default data directory (`~/.local/share/dz_validator_pda` on Linux)
+pub fn default_data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR_NAME)
+}
+
+/// Writes a file atomically by writing a temporary file next to it and renaming it
+///
+/// # Arguments
+/// * `path` - Destination path
+/// * `contents` - File contents
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if the file was written
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), Error> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|e| Error::Io(format!("Failed to create directory {}: {}", parent.display(), e)))?;
+    }
+
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    fs::write(&tmp_path, contents)
+        .map_err(|e| Error::Io(format!("Failed to write {}: {}", tmp_path.display(), e)))?;
+    fs::rename(&tmp_path, path)
+        .map_err(|e| Error::Io(format!("Failed to replace {}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("dz_validator_pda_config_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_load_missing_config_is_default() {
+        let config = Config::load(&temp_path("missing.toml")).unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let path = temp_path("roundtrip.toml");
+        let mut config = Config { keypair: Some("/keys/funding.json".to_string()), ..Config::default() };
+        config.profiles.insert("staging".to_string(), Profile { keypair: Some("/keys/staging.json".to_string()) });
+
+        config.save(&path).unwrap();
+        let loaded = Config::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, config);
+        assert_eq!(loaded.keypair(Some("staging")).unwrap(), Some("/keys/staging.json"));
+        assert!(loaded.keypair(Some("unknown")).is_err());
+    }
+
+    #[test]
+    fn test_profile_falls_back_to_top_level_keypair() {
+        let mut config = Config { keypair: Some("/keys/funding.json".to_string()), ..Config::default() };
+        config.profiles.insert("empty".to_string(), Profile::default());
+
+        assert_eq!(config.keypair(Some("empty")).unwrap(), Some("/keys/funding.json"));
+        assert_eq!(config.keypair(None).unwrap(), Some("/keys/funding.json"));
+    }
+
+    #[test]
+    fn test_replace_keypair_updates_all_references() {
+        let mut config = Config { keypair: Some("/keys/old.json".to_string()), ..Config::default() };
+        config.profiles.insert("a".to_string(), Profile { keypair: Some("/keys/old.json".to_string()) });
+        config.profiles.insert("b".to_string(), Profile { keypair: Some("/keys/other.json".to_string()) });
+
+        assert_eq!(config.replace_keypair("/keys/old.json", "/keys/new.json"), 2);
+        assert_eq!(config.keypair.as_deref(), Some("/keys/new.json"));
+        assert_eq!(config.profiles["a"].keypair.as_deref(), Some("/keys/new.json"));
+        assert_eq!(config.profiles["b"].keypair.as_deref(), Some("/keys/other.json"));
+    }
+
+    #[test]
+    fn test_resolve_validator() {
+        let mut config = Config::default();
+        config.validators.insert("mainnet-01".to_string(), "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL".to_string());
+
+        let (alias, pubkey) = config.resolve_validator("mainnet-01").unwrap();
+        assert_eq!(alias.as_deref(), Some("mainnet-01"));
+        assert_eq!(pubkey.to_string(), "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL");
+
+        // A pubkey is shown with its alias
+        let (alias, _) = config.resolve_validator("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL").unwrap();
+        assert_eq!(alias.as_deref(), Some("mainnet-01"));
+
+        let (alias, _) = config.resolve_validator("11111111111111111111111111111112").unwrap();
+        assert_eq!(alias, None);
+
+        assert!(config.resolve_validator("unknown").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_add_and_remove_alias() {
+        let (validator, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut config = Config::default();
+        assert_eq!(config.add_alias("mainnet-01", &validator, false).unwrap(), None);
+        // Adding the same alias again changes nothing
+        assert_eq!(config.add_alias("mainnet-01", &validator, false).unwrap(), Some(validator.to_string()));
+
+        assert!(config.add_alias("mainnet-01", &other, false).unwrap_err().to_string().contains("--force"));
+        assert!(config.add_alias("mainnet-02", &validator, false).unwrap_err().to_string().contains("already has the alias"));
+        assert!(config.add_alias("main net", &other, false).unwrap_err().is_user_error());
+        assert!(config.add_alias(&other.to_string(), &other, false).unwrap_err().to_string().contains("cannot be a pubkey"));
+
+        assert_eq!(config.add_alias("mainnet-01", &other, true).unwrap(), Some(validator.to_string()));
+        assert_eq!(config.resolve_validator("mainnet-01").unwrap().1, other);
+        assert_eq!(config.remove_alias("mainnet-01").unwrap(), other.to_string());
+        assert!(config.remove_alias("mainnet-01").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_reserve_lamports() {
+        assert_eq!(Config::default().reserve_lamports().unwrap(), 0);
+        let config: Config = toml::from_str("reserve_sol = \"0.05\"").unwrap();
+        assert_eq!(config.reserve_lamports().unwrap(), 50_000_000);
+        let all = Config { reserve_sol: Some("ALL".to_string()), ..Config::default() };
+        assert!(all.reserve_lamports().unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_budget() {
+        let ledger = temp_path("audit.log");
+        assert_eq!(Config::default().budget(&ledger).unwrap(), None);
+        let config: Config = toml::from_str(
+            "[validators]\nmainnet-01 = \"FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\"\n\n\
+             [budget]\nmax_per_tx = \"5\"\n\n[budget.validators.mainnet-01]\nmax_per_day = \"2\"\n",
+        )
+        .unwrap();
+        let budget = config.budget(&ledger).unwrap().unwrap();
+        assert_eq!(budget.global.per_tx, Some(5_000_000_000));
+        let validator_id = parse_pubkey("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL").unwrap();
+        assert_eq!(budget.validators[&validator_id].per_day, Some(2_000_000_000));
+        assert_eq!(toml::from_str::<Config>(&toml::to_string_pretty(&config).unwrap()).unwrap(), config);
+
+        let unknown: Config = toml::from_str("[budget.validators.unknown]\nmax_per_day = \"2\"\n").unwrap();
+        assert!(unknown.budget(&ledger).unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_invalid_config_is_user_error() {
+        let path = temp_path("invalid.toml");
+        fs::write(&path, "keypair = [").unwrap();
+        let result = Config::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(result.unwrap_err().is_user_error());
+    }
+}
@@ -0,0 +1,448 @@
+use crate::amount::format_sol;
+use crate::audit::{self, AuditEntry};
+use crate::batch::{collect_entries, sort_entries, BatchEntry, BatchOptions, SortKey, DEFAULT_BATCH_CONCURRENCY};
+use crate::i18n;
+use crate::rpc::SolanaRpc;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Seconds between two refreshes of the dashboard by default (`--refresh`)
+pub const DEFAULT_REFRESH_SECS: u64 = 30;
+
+/// Vote state of a dashboard row
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VoteState {
+    /// Vote accounts could not be read
+    #[default]
+    Unknown,
+    /// The validator has no vote account
+    None,
+    /// The vote account is voting
+    Voting,
+    /// The vote account is reported as delinquent
+    Delinquent,
+}
+
+impl VoteState {
+    fn label(self) -> &'static str {
+        match self {
+            VoteState::Unknown => "?",
+            VoteState::None => "none",
+            VoteState::Voting => "voting",
+            VoteState::Delinquent => "DELINQUENT",
+        }
+    }
+}
+
+/// One validator of the dashboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DashboardRow {
+    /// Gossip presence, PDA balance and error as collected for `pda-batch`
+    pub entry: BatchEntry,
+    /// Vote state of the validator
+    pub vote: VoteState,
+    /// Unix timestamp of the last successful PDA funding in the audit log
+    pub last_deposit: Option<u64>,
+}
+
+/// Everything shown by one refresh of the dashboard
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Rows in alias-then-pubkey order
+    pub rows: Vec<DashboardRow>,
+    /// Funding wallet and its balance, if a wallet is configured and its balance could be read
+    pub payer: Option<(Pubkey, Option<u64>)>,
+    /// Unix timestamp of the refresh
+    pub refreshed_at: u64,
+}
+
+/// Collects a dashboard snapshot
+///
+/// Rows are collected like `pda-batch pda-balance` (concurrently, errors kept per row); the vote
+/// accounts are read once for all rows.
+///
+/// # Arguments
+/// * `validators` - Aliases and identity pubkeys of the validators
+/// * `payer` - Funding wallet, if configured
+/// * `audit_entries` - Audit log entries in file order, for the last deposit times
+/// * `timestamp` - Unix timestamp of the refresh
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Snapshot` - Rows and payer balance
+pub async fn collect_snapshot(
+    validators: Vec<(Option<String>, Pubkey)>,
+    payer: Option<Pubkey>,
+    audit_entries: &[AuditEntry],
+    timestamp: u64,
+    rpc: Arc<dyn SolanaRpc>,
+) -> Snapshot {
+    let options = BatchOptions { check_gossip: true, fetch_balance: true, fetch_stake: false, min_context_slot: None };
+    let mut entries = collect_entries(validators, options, DEFAULT_BATCH_CONCURRENCY, Arc::clone(&rpc)).await;
+    sort_entries(&mut entries, SortKey::Name);
+
+    let votes: Option<HashMap<String, bool>> = rpc.get_vote_accounts().await.ok().map(|status| {
+        let current = status.current.into_iter().map(|info| (info.node_pubkey, false));
+        let delinquent = status.delinquent.into_iter().map(|info| (info.node_pubkey, true));
+        delinquent.chain(current).collect()
+    });
+    let deposits = last_deposits(audit_entries);
+
+    let rows = entries
+        .into_iter()
+        .map(|entry| {
+            let vote = match votes.as_ref().map(|votes| votes.get(&entry.validator)) {
+                None => VoteState::Unknown,
+                Some(None) => VoteState::None,
+                Some(Some(false)) => VoteState::Voting,
+                Some(Some(true)) => VoteState::Delinquent,
+            };
+            let last_deposit = deposits.get(&entry.validator).copied();
+            DashboardRow { entry, vote, last_deposit }
+        })
+        .collect();
+
+    let payer = match payer {
+        Some(payer) => Some((payer, rpc.get_balance(&payer).await.ok())),
+        None => None,
+    };
+    Snapshot { rows, payer, refreshed_at: timestamp }
+}
+
+/// Returns the time of the last successful PDA funding of each validator
+///
+/// # Arguments
+/// * `entries` - Audit log entries in file order
+///
+/// # Returns
+/// * `HashMap<String, u64>` - Validator pubkey to Unix timestamp
+pub fn last_deposits(entries: &[AuditEntry]) -> HashMap<String, u64> {
+    let mut deposits = HashMap::new();
+    for entry in entries.iter().filter(|entry| audit::is_funding(entry) && entry.status == audit::STATUS_SUCCESS) {
+        if let Some(validator) = &entry.validator {
+            let last = deposits.entry(validator.clone()).or_insert(entry.timestamp);
+            *last = (*last).max(entry.timestamp);
+        }
+    }
+    deposits
+}
+
+/// Formats how long ago a Unix timestamp was, e.g. `3h 12m ago`
+pub fn format_age(timestamp: u64, now: u64) -> String {
+    let seconds = now.saturating_sub(timestamp);
+    match seconds {
+        0..60 => i18n::message("age-seconds", &[("seconds", &seconds)]),
+        60..3_600 => i18n::message("age-minutes", &[("minutes", &(seconds / 60))]),
+        3_600..86_400 => i18n::message("age-hours", &[("hours", &(seconds / 3_600)), ("minutes", &(seconds % 3_600 / 60))]),
+        _ => i18n::message("age-days", &[("days", &(seconds / 86_400)), ("hours", &(seconds % 86_400 / 3_600))]),
+    }
+}
+
+/// What the dashboard shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum View {
+    /// Table of all validators
+    #[default]
+    Table,
+    /// Funding history of the selected validator
+    History,
+}
+
+/// What the event loop has to do after a key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Nothing beyond redrawing
+    None,
+    /// Collect a new snapshot now
+    Refresh,
+    /// Top up the PDA of this validator
+    TopUp(Pubkey),
+    /// Leave the dashboard
+    Quit,
+}
+
+/// State of the dashboard between two frames
+#[derive(Debug, Default)]
+pub struct Dashboard {
+    /// Latest snapshot
+    pub snapshot: Snapshot,
+    /// Selected row
+    pub selected: usize,
+    /// Current view
+    pub view: View,
+    /// Message shown in the status line (refresh errors, top-up results)
+    pub status: Option<String>,
+    /// True while a refresh is running
+    pub refreshing: bool,
+}
+
+impl Dashboard {
+    /// Replaces the snapshot, keeping the selection on the same row where possible
+    ///
+    /// # Arguments
+    /// * `snapshot` - New snapshot
+    pub fn update(&mut self, snapshot: Snapshot) {
+        self.selected = self.selected.min(snapshot.rows.len().saturating_sub(1));
+        self.snapshot = snapshot;
+        self.refreshing = false;
+    }
+
+    /// Returns the selected row, if any
+    pub fn selected_row(&self) -> Option<&DashboardRow> {
+        self.snapshot.rows.get(self.selected)
+    }
+
+    /// Applies a key press
+    ///
+    /// Keys: Up/Down or k/j select a row, `f` tops up the selected PDA, `h` or Enter toggles its
+    /// history, `r` refreshes, Esc leaves the history and `q` quits.
+    ///
+    /// # Arguments
+    /// * `key` - Pressed key
+    ///
+    /// # Returns
+    /// * `Action` - What the event loop has to do
+    pub fn handle_key(&mut self, key: KeyCode) -> Action {
+        let last = self.snapshot.rows.len().saturating_sub(1);
+        match key {
+            KeyCode::Char('q') => return Action::Quit,
+            KeyCode::Esc if self.view == View::Table => return Action::Quit,
+            KeyCode::Esc => self.view = View::Table,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Char('h') | KeyCode::Enter => {
+                self.view = if self.view == View::Table { View::History } else { View::Table };
+            }
+            KeyCode::Char('r') if !self.refreshing => {
+                self.refreshing = true;
+                return Action::Refresh;
+            }
+            KeyCode::Char('f') => {
+                if let Some(validator) = self.selected_row().and_then(|row| row.entry.validator.parse().ok()) {
+                    return Action::TopUp(validator);
+                }
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    /// Draws the dashboard
+    ///
+    /// # Arguments
+    /// * `frame` - Frame to draw on
+    /// * `audit_entries` - Audit log entries in file order, for the history view
+    /// * `now` - Current Unix timestamp
+    pub fn render(&self, frame: &mut Frame, audit_entries: &[AuditEntry], now: u64) {
+        let [header, body, footer] = Layout::vertical([Constraint::Length(1), Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+
+        let payer = match &self.snapshot.payer {
+            Some((payer, Some(balance))) => i18n::message("dashboard-payer", &[("payer", payer), ("balance", &format_sol(*balance))]),
+            Some((payer, None)) => i18n::message("dashboard-payer-unavailable", &[("payer", payer)]),
+            None => i18n::text("dashboard-no-wallet").to_string(),
+        };
+        let refreshed = if self.refreshing {
+            i18n::text("dashboard-refreshing").to_string()
+        } else {
+            i18n::message("dashboard-refreshed", &[("age", &format_age(self.snapshot.refreshed_at, now))])
+        };
+        frame.render_widget(Paragraph::new(format!("{} | {}", payer, refreshed)), header);
+
+        match self.view {
+            View::Table => self.render_table(frame, body, now),
+            View::History => self.render_history(frame, body, audit_entries, now),
+        }
+
+        let help = i18n::text("dashboard-keys");
+        let status = self.status.as_deref().map(|status| format!("{} | {}", status, help)).unwrap_or_else(|| help.to_string());
+        frame.render_widget(Paragraph::new(Line::from(status)), footer);
+    }
+
+    fn render_table(&self, frame: &mut Frame, area: ratatui::layout::Rect, now: u64) {
+        let header = Row::new(["column-name", "column-validator", "column-gossip", "column-vote", "column-pda-balance-sol", "column-last-deposit"].map(i18n::text))
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self.snapshot.rows.iter().map(|row| {
+            let gossip = match row.entry.in_gossip {
+                Some(true) => i18n::text("gossip-yes"),
+                Some(false) => i18n::text("gossip-no"),
+                None => "?",
+            };
+            let balance = match (&row.entry.error, row.entry.balance_lamports) {
+                (Some(error), _) => format!("{}: {}", i18n::text("error-lower"), i18n::localize(error)),
+                (None, Some(balance)) => format_sol(balance),
+                (None, None) => "?".to_string(),
+            };
+            let attention = row.entry.error.is_some() || row.entry.in_gossip == Some(false) || row.vote == VoteState::Delinquent;
+            Row::new([
+                Cell::from(row.entry.name.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(row.entry.validator.clone()),
+                Cell::from(gossip),
+                Cell::from(row.vote.label()),
+                Cell::from(balance),
+                Cell::from(row.last_deposit.map(|timestamp| format_age(timestamp, now)).unwrap_or_else(|| i18n::text("dashboard-never").to_string())),
+            ])
+            .style(if attention { Style::default().fg(Color::Red) } else { Style::default() })
+        });
+        let widths = [
+            Constraint::Length(16),
+            Constraint::Length(45),
+            Constraint::Length(7),
+            Constraint::Length(11),
+            Constraint::Min(18),
+            Constraint::Length(14),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(i18n::text("dashboard-validators")))
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    fn render_history(&self, frame: &mut Frame, area: ratatui::layout::Rect, audit_entries: &[AuditEntry], now: u64) {
+        let Some(row) = self.selected_row() else { return };
+        let rows = audit::validator_history(audit_entries, &row.entry.validator).into_iter().map(|entry| {
+            let amount = entry.amount_lamports.map(format_sol).unwrap_or_default();
+            let detail = entry.signature.clone().or_else(|| entry.message.clone()).unwrap_or_default();
+            Row::new([format_age(entry.timestamp, now), entry.status.clone(), amount, detail])
+        });
+        let widths = [Constraint::Length(14), Constraint::Length(8), Constraint::Length(16), Constraint::Min(20)];
+        let title = i18n::message("dashboard-history-of", &[("validator", &row.entry.name.as_deref().unwrap_or(&row.entry.validator))]);
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(["column-when", "column-status", "column-amount-sol", "column-signature-or-error"].map(i18n::text))
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(table, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_deposit_pda;
+    use crate::rpc::mock::MockRpc;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn funding(validator: &Pubkey, timestamp: u64, status: &str) -> AuditEntry {
+        AuditEntry {
+            timestamp,
+            validator: Some(validator.to_string()),
+            amount_lamports: Some(1_000_000_000),
+            ..AuditEntry::new(audit::EVENT_PDA_FUND, status)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collect_snapshot() {
+        let voting = Pubkey::new_unique();
+        let delinquent = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let rpc = Arc::new(
+            MockRpc::new()
+                .with_gossip_node(voting)
+                .with_vote_account(voting, 990, 1_000, false)
+                .with_vote_account(delinquent, 500, 1_000, true)
+                .with_wallet(generate_deposit_pda(&voting), 2_000_000_000)
+                .with_wallet(payer, 5_000_000_000),
+        );
+        let audit_entries = [funding(&voting, 100, audit::STATUS_SUCCESS), funding(&voting, 200, audit::STATUS_FAILED)];
+        let validators = vec![(Some("b".to_string()), delinquent), (Some("a".to_string()), voting), (None, Pubkey::new_unique())];
+
+        let snapshot = collect_snapshot(validators, Some(payer), &audit_entries, 300, rpc).await;
+        assert_eq!(snapshot.payer, Some((payer, Some(5_000_000_000))));
+        assert_eq!(snapshot.rows.len(), 3);
+        assert_eq!(snapshot.rows[0].entry.name.as_deref(), Some("a"));
+        assert_eq!(snapshot.rows[0].vote, VoteState::Voting);
+        assert_eq!(snapshot.rows[0].entry.in_gossip, Some(true));
+        assert_eq!(snapshot.rows[0].entry.balance_lamports, Some(2_000_000_000));
+        // Only successful fundings count as deposits
+        assert_eq!(snapshot.rows[0].last_deposit, Some(100));
+        assert_eq!(snapshot.rows[1].vote, VoteState::Delinquent);
+        assert_eq!(snapshot.rows[2].vote, VoteState::None);
+    }
+
+    #[test]
+    fn test_dashboard_keys() {
+        let validator = Pubkey::new_unique();
+        let row = |validator: &Pubkey| DashboardRow {
+            entry: BatchEntry { validator: validator.to_string(), ..BatchEntry::default() },
+            vote: VoteState::Voting,
+            last_deposit: None,
+        };
+        let mut dashboard = Dashboard::default();
+        dashboard.update(Snapshot { rows: vec![row(&Pubkey::new_unique()), row(&validator)], ..Snapshot::default() });
+
+        assert_eq!(dashboard.handle_key(KeyCode::Down), Action::None);
+        assert_eq!(dashboard.handle_key(KeyCode::Down), Action::None);
+        assert_eq!(dashboard.selected, 1);
+        assert_eq!(dashboard.handle_key(KeyCode::Char('f')), Action::TopUp(validator));
+        assert_eq!(dashboard.handle_key(KeyCode::Char('r')), Action::Refresh);
+        // A second refresh is not started while one is running
+        assert_eq!(dashboard.handle_key(KeyCode::Char('r')), Action::None);
+
+        assert_eq!(dashboard.handle_key(KeyCode::Char('h')), Action::None);
+        assert_eq!(dashboard.view, View::History);
+        assert_eq!(dashboard.handle_key(KeyCode::Esc), Action::None);
+        assert_eq!(dashboard.view, View::Table);
+        assert_eq!(dashboard.handle_key(KeyCode::Esc), Action::Quit);
+
+        // The selection stays in range when rows disappear
+        dashboard.update(Snapshot { rows: vec![row(&validator)], ..Snapshot::default() });
+        assert_eq!(dashboard.selected, 0);
+    }
+
+    #[test]
+    fn test_dashboard_render() {
+        let validator = Pubkey::new_unique();
+        let mut dashboard = Dashboard::default();
+        dashboard.update(Snapshot {
+            rows: vec![DashboardRow {
+                entry: BatchEntry {
+                    name: Some("mainnet-01".to_string()),
+                    validator: validator.to_string(),
+                    in_gossip: Some(false),
+                    balance_lamports: Some(2_500_000_000),
+                    ..BatchEntry::default()
+                },
+                vote: VoteState::Delinquent,
+                last_deposit: Some(1_000),
+            }],
+            payer: None,
+            refreshed_at: 4_600,
+        });
+        let audit_entries = [funding(&validator, 1_000, audit::STATUS_SUCCESS)];
+
+        let mut terminal = Terminal::new(TestBackend::new(140, 8)).unwrap();
+        terminal.draw(|frame| dashboard.render(frame, &audit_entries, 4_600)).unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("mainnet-01"));
+        assert!(screen.contains("DELINQUENT"));
+        assert!(screen.contains("2.5"));
+        assert!(screen.contains("1h 0m ago"));
+        assert!(screen.contains("No funding wallet configured"));
+
+        dashboard.handle_key(KeyCode::Char('h'));
+        terminal.draw(|frame| dashboard.render(frame, &audit_entries, 4_600)).unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("History of mainnet-01"));
+        assert!(screen.contains("success"));
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(100, 130), "30s ago");
+        assert_eq!(format_age(0, 3_600 * 3 + 12 * 60), "3h 12m ago");
+        assert_eq!(format_age(0, 86_400 * 2 + 3_600), "2d 1h ago");
+        assert_eq!(format_age(200, 100), "0s ago");
+    }
+}
@@ -0,0 +1,342 @@
+use crate::labels::format_builtin;
+use crate::report::{Warning, WARN_BELOW_RENT_EXEMPTION, WARN_UNEXPECTED_OWNER};
+use crate::rpc::SolanaRpc;
+use crate::wallet::wait_for_confirmation;
+use crate::{Error, REVENUE_DISTRIBUTION_PROGRAM_ID};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::fmt;
+use std::time::Duration;
+
+/// Default time a funded deposit account may stay a bare system account before alerting (one hour)
+pub const DEFAULT_INITIALIZATION_TIMEOUT_SECS: u64 = 3600;
+
+/// Lifecycle state of a validator deposit PDA
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepositAccountState {
+    /// The account does not exist
+    Missing,
+    /// Funded, but still owned by the system program (not initialized by the revenue distribution program)
+    SystemOwned,
+    /// Owned by a program (normally the revenue distribution program)
+    ProgramOwned(Pubkey),
+}
+
+impl DepositAccountState {
+    /// Classifies an account fetched from RPC
+    ///
+    /// # Arguments
+    /// * `account` - The account, or None if it does not exist
+    pub fn from_account(account: Option<&Account>) -> Self {
+        match account {
+            None => DepositAccountState::Missing,
+            Some(account) if account.owner == solana_system_interface::program::ID => DepositAccountState::SystemOwned,
+            Some(account) => DepositAccountState::ProgramOwned(account.owner),
+        }
+    }
+}
+
+impl fmt::Display for DepositAccountState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DepositAccountState::Missing => write!(f, "missing"),
+            DepositAccountState::SystemOwned => write!(f, "system-owned"),
+            DepositAccountState::ProgramOwned(owner) if *owner == REVENUE_DISTRIBUTION_PROGRAM_ID => write!(f, "initialized"),
+            DepositAccountState::ProgramOwned(owner) => write!(f, "owned by {}", format_builtin(owner)),
+        }
+    }
+}
+
+/// Deposit PDA as seen at a slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepositAccount {
+    /// Lifecycle state
+    pub state: DepositAccountState,
+    /// Balance in lamports (0 if the account does not exist)
+    pub lamports: u64,
+    /// Slot at which the account was read
+    pub slot: u64,
+}
+
+/// Reads the state and balance of a deposit PDA
+///
+/// # Arguments
+/// * `deposit_key` - PDA address
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<DepositAccount, Error>` - Account state or error
+pub async fn get_deposit_account(deposit_key: &Pubkey, rpc: &dyn SolanaRpc) -> Result<DepositAccount, Error> {
+    let response = rpc.get_account_with_context(deposit_key).await
+        .map_err(|e| Error::from_client_error("Failed to get deposit account", &e))?;
+
+    Ok(DepositAccount {
+        state: DepositAccountState::from_account(response.value.as_ref()),
+        lamports: response.value.as_ref().map(|account| account.lamports).unwrap_or(0),
+        slot: response.context.slot,
+    })
+}
+
+/// Checks the deposit PDA before a transfer
+///
+/// An account owned by a program other than the revenue distribution program only warns. A deposit
+/// leaving the account below its rent-exempt minimum is refused unless `force` is set, in which case it warns.
+///
+/// # Arguments
+/// * `deposit_key` - PDA address
+/// * `account` - The PDA as read before funding
+/// * `amount_lamports` - Amount to deposit
+/// * `rent_exempt_minimum` - Rent-exempt minimum for the size of the account
+/// * `force` - Send even if the account stays below rent exemption (`--force`)
+///
+/// # Returns
+/// * `Result<Vec<Warning>, Error>` - Warnings, or `Error::FundingCancelled` for a deposit below rent exemption
+pub fn check_deposit_preflight(
+    deposit_key: &Pubkey,
+    account: &DepositAccount,
+    amount_lamports: u64,
+    rent_exempt_minimum: u64,
+    force: bool,
+) -> Result<Vec<Warning>, Error> {
+    let mut warnings = Vec::new();
+    if let DepositAccountState::ProgramOwned(owner) = account.state
+        && owner != REVENUE_DISTRIBUTION_PROGRAM_ID
+    {
+        warnings.push(Warning::new(
+            WARN_UNEXPECTED_OWNER,
+            format!("Deposit account {} is owned by unexpected program {}", deposit_key, format_builtin(&owner)),
+        ));
+    }
+
+    let balance_after = account.lamports.saturating_add(amount_lamports);
+    if balance_after < rent_exempt_minimum {
+        let message = format!(
+            "PDA balance after the deposit ({} lamports) would be below the rent-exempt minimum of {} lamports",
+            balance_after, rent_exempt_minimum
+        );
+        if !force {
+            return Err(Error::FundingCancelled(format!("Funding cancelled: {}; use --force to send anyway", message)));
+        }
+        warnings.push(Warning::new(WARN_BELOW_RENT_EXEMPTION, message));
+    }
+    Ok(warnings)
+}
+
+/// Waits for a funding transaction to be confirmed and returns the slot it landed in
+///
+/// # Arguments
+/// * `signature` - Transaction signature
+/// * `timeout` - Maximum time to wait for confirmation
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<u64, Error>` - Slot of the transaction, or the confirmation error
+pub async fn confirmed_slot(signature: &str, timeout: Duration, rpc: &dyn SolanaRpc) -> Result<u64, Error> {
+    wait_for_confirmation(signature, timeout, rpc).await?;
+
+    let signature = signature.parse::<Signature>()
+        .map_err(|e| Error::InvalidInput(format!("Invalid transaction signature {}: {}", signature, e)))?;
+    let statuses = rpc.get_signature_statuses(&[signature]).await
+        .map_err(|e| Error::from_client_error("Failed to get transaction status", &e))?;
+
+    statuses.value.into_iter().flatten().next()
+        .map(|status| status.slot)
+        .ok_or_else(|| Error::ConfirmationTimeout(format!("Status of transaction {} is no longer available", signature)))
+}
+
+/// Notable change of a deposit PDA
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepositEvent {
+    /// The account went from nonexistent to funded
+    Created { slot: u64, lamports: u64 },
+    /// A program took ownership of the funded account
+    Initialized { owner: Pubkey, slot: u64 },
+    /// The account is still a bare system account after the initialization timeout
+    NotInitialized { elapsed_secs: u64 },
+}
+
+impl fmt::Display for DepositEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DepositEvent::Created { slot, lamports } => {
+                write!(f, "Deposit account initialized at slot {} with {} lamports", slot, lamports)
+            }
+            DepositEvent::Initialized { owner, slot } if *owner == REVENUE_DISTRIBUTION_PROGRAM_ID => {
+                write!(f, "Revenue distribution program {} took ownership of the deposit account at slot {}", owner, slot)
+            }
+            DepositEvent::Initialized { owner, slot } => {
+                write!(f, "Deposit account was assigned to unexpected program {} at slot {}", format_builtin(owner), slot)
+            }
+            DepositEvent::NotInitialized { elapsed_secs } => write!(
+                f,
+                "Deposit account is still a bare system account {} seconds after funding; program {} has not initialized it",
+                elapsed_secs, REVENUE_DISTRIBUTION_PROGRAM_ID
+            ),
+        }
+    }
+}
+
+/// Follows a deposit PDA across polls and reports creation, program ownership and stalled initialization
+#[derive(Debug, Clone)]
+pub struct DepositLifecycle {
+    initialization_timeout_secs: u64,
+    state: Option<DepositAccountState>,
+    system_owned_since: Option<u64>,
+    alerted: bool,
+}
+
+impl DepositLifecycle {
+    /// Creates a tracker
+    ///
+    /// # Arguments
+    /// * `initialization_timeout_secs` - How long the account may stay system-owned after funding
+    pub fn new(initialization_timeout_secs: u64) -> Self {
+        DepositLifecycle {
+            initialization_timeout_secs,
+            state: None,
+            system_owned_since: None,
+            alerted: false,
+        }
+    }
+
+    /// Records an observation of the account
+    ///
+    /// The first observation only sets the baseline; an account already system-owned at that point
+    /// is timed from then on.
+    ///
+    /// # Arguments
+    /// * `account` - Account state read from RPC
+    /// * `timestamp` - Unix timestamp of the observation in seconds
+    ///
+    /// # Returns
+    /// * `Vec<DepositEvent>` - Events caused by this observation
+    pub fn observe(&mut self, account: &DepositAccount, timestamp: u64) -> Vec<DepositEvent> {
+        let mut events = Vec::new();
+        let previous = self.state.replace(account.state);
+
+        match (previous, account.state) {
+            (Some(DepositAccountState::Missing), DepositAccountState::SystemOwned | DepositAccountState::ProgramOwned(_)) => {
+                events.push(DepositEvent::Created { slot: account.slot, lamports: account.lamports });
+            }
+            (Some(DepositAccountState::SystemOwned), DepositAccountState::ProgramOwned(owner)) => {
+                events.push(DepositEvent::Initialized { owner, slot: account.slot });
+            }
+            _ => {}
+        }
+
+        if account.state == DepositAccountState::SystemOwned {
+            let since = *self.system_owned_since.get_or_insert(timestamp);
+            let elapsed_secs = timestamp.saturating_sub(since);
+            if elapsed_secs > self.initialization_timeout_secs && !self.alerted {
+                self.alerted = true;
+                events.push(DepositEvent::NotInitialized { elapsed_secs });
+            }
+        } else {
+            self.system_owned_since = None;
+            self.alerted = false;
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::Transaction;
+
+    fn observation(state: DepositAccountState, lamports: u64, slot: u64) -> DepositAccount {
+        DepositAccount { state, lamports, slot }
+    }
+
+    #[test]
+    fn test_state_from_account() {
+        assert_eq!(DepositAccountState::from_account(None), DepositAccountState::Missing);
+
+        let mut account = Account { lamports: 1_000_000, owner: solana_system_interface::program::ID, ..Account::default() };
+        assert_eq!(DepositAccountState::from_account(Some(&account)), DepositAccountState::SystemOwned);
+
+        account.owner = REVENUE_DISTRIBUTION_PROGRAM_ID;
+        let state = DepositAccountState::from_account(Some(&account));
+        assert_eq!(state, DepositAccountState::ProgramOwned(REVENUE_DISTRIBUTION_PROGRAM_ID));
+        assert_eq!(state.to_string(), "initialized");
+    }
+
+    #[test]
+    fn test_deposit_preflight() {
+        let pda = Pubkey::new_unique();
+        let initialized = observation(DepositAccountState::ProgramOwned(REVENUE_DISTRIBUTION_PROGRAM_ID), 2_000_000, 1);
+        assert!(check_deposit_preflight(&pda, &initialized, 1, 2_000_000, false).unwrap().is_empty());
+
+        // A new account must receive at least the rent-exempt minimum
+        let missing = observation(DepositAccountState::Missing, 0, 1);
+        assert!(matches!(check_deposit_preflight(&pda, &missing, 890_879, 890_880, false), Err(Error::FundingCancelled(_))));
+        assert!(check_deposit_preflight(&pda, &missing, 890_880, 890_880, false).unwrap().is_empty());
+        let forced = check_deposit_preflight(&pda, &missing, 1_000, 890_880, true).unwrap();
+        assert_eq!(forced[0].code, WARN_BELOW_RENT_EXEMPTION);
+
+        let foreign = observation(DepositAccountState::ProgramOwned(Pubkey::new_unique()), 5_000_000, 1);
+        let warnings = check_deposit_preflight(&pda, &foreign, 1, 2_000_000, false).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, WARN_UNEXPECTED_OWNER);
+    }
+
+    #[test]
+    fn test_creation_and_initialization_events() {
+        let mut lifecycle = DepositLifecycle::new(600);
+        assert!(lifecycle.observe(&observation(DepositAccountState::Missing, 0, 100), 0).is_empty());
+
+        let events = lifecycle.observe(&observation(DepositAccountState::SystemOwned, 2_000_000, 150), 60);
+        assert_eq!(events, vec![DepositEvent::Created { slot: 150, lamports: 2_000_000 }]);
+        assert!(events[0].to_string().starts_with("Deposit account initialized at slot 150"));
+
+        let owned = DepositAccountState::ProgramOwned(REVENUE_DISTRIBUTION_PROGRAM_ID);
+        let events = lifecycle.observe(&observation(owned, 2_000_000, 300), 120);
+        assert_eq!(events, vec![DepositEvent::Initialized { owner: REVENUE_DISTRIBUTION_PROGRAM_ID, slot: 300 }]);
+    }
+
+    #[test]
+    fn test_existing_account_is_not_reported_as_created() {
+        let mut lifecycle = DepositLifecycle::new(600);
+        let owned = DepositAccountState::ProgramOwned(REVENUE_DISTRIBUTION_PROGRAM_ID);
+        assert!(lifecycle.observe(&observation(owned, 5_000_000, 100), 0).is_empty());
+        assert!(lifecycle.observe(&observation(owned, 6_000_000, 200), 60).is_empty());
+    }
+
+    #[test]
+    fn test_alert_when_account_stays_system_owned() {
+        let mut lifecycle = DepositLifecycle::new(600);
+        lifecycle.observe(&observation(DepositAccountState::Missing, 0, 100), 0);
+        lifecycle.observe(&observation(DepositAccountState::SystemOwned, 1_000, 110), 60);
+
+        assert!(lifecycle.observe(&observation(DepositAccountState::SystemOwned, 1_000, 200), 660).is_empty());
+        let events = lifecycle.observe(&observation(DepositAccountState::SystemOwned, 1_000, 300), 661);
+        assert_eq!(events, vec![DepositEvent::NotInitialized { elapsed_secs: 601 }]);
+
+        // Alerted once per stall
+        assert!(lifecycle.observe(&observation(DepositAccountState::SystemOwned, 1_000, 400), 900).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_deposit_account_and_confirmed_slot() {
+        let deposit_key = Pubkey::new_unique();
+        let owned = Account { lamports: 2_000_000, owner: REVENUE_DISTRIBUTION_PROGRAM_ID, ..Account::default() };
+        let rpc = MockRpc::new().with_account(deposit_key, owned);
+
+        let account = get_deposit_account(&deposit_key, &rpc).await.unwrap();
+        assert_eq!(account.state, DepositAccountState::ProgramOwned(REVENUE_DISTRIBUTION_PROGRAM_ID));
+        assert_eq!(account.lamports, 2_000_000);
+        assert_eq!(account.slot, 1_000);
+        assert_eq!(get_deposit_account(&Pubkey::new_unique(), &rpc).await.unwrap().state, DepositAccountState::Missing);
+
+        let payer = Keypair::new();
+        let transfer = solana_system_interface::instruction::transfer(&payer.pubkey(), &deposit_key, 1);
+        let transaction = Transaction::new_signed_with_payer(&[transfer], Some(&payer.pubkey()), &[&payer], Hash::new_unique());
+        let signature = rpc.send_transaction(&transaction).await.unwrap();
+        assert_eq!(confirmed_slot(&signature.to_string(), Duration::from_secs(5), &rpc).await.unwrap(), 1_000);
+    }
+}
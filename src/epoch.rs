@@ -0,0 +1,217 @@
+use crate::rpc::SolanaRpc;
+use crate::{retry_transient, Error};
+use solana_sdk::epoch_info::EpochInfo;
+use std::fmt;
+use std::time::Duration;
+
+/// Typical slot time used to estimate when an epoch begins
+pub const DEFAULT_SLOT_MS: u64 = 400;
+
+/// Longest pause between two `getEpochInfo` polls while waiting for an epoch
+pub const MAX_EPOCH_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Number of attempts for each `getEpochInfo` poll failing with transient errors
+const EPOCH_POLL_ATTEMPTS: u32 = 3;
+
+/// Epoch a scheduled funding waits for (`--at-epoch-boundary`, `--at-epoch N`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpochTarget {
+    /// Start of the epoch after the current one
+    NextBoundary,
+    /// Start of the given epoch
+    Epoch(u64),
+}
+
+impl EpochTarget {
+    /// Parses the value of `--at-epoch`
+    ///
+    /// # Arguments
+    /// * `value` - Epoch number
+    ///
+    /// # Returns
+    /// * `Result<EpochTarget, Error>` - Target or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        value
+            .trim()
+            .parse::<u64>()
+            .map(EpochTarget::Epoch)
+            .map_err(|_| Error::InvalidInput(format!("Invalid --at-epoch '{}': expected an epoch number", value)))
+    }
+
+    /// Returns the epoch to wait for
+    ///
+    /// # Arguments
+    /// * `current_epoch` - Epoch the cluster is in now
+    ///
+    /// # Returns
+    /// * `Result<u64, Error>` - Target epoch (the current one if it was asked for and has begun),
+    ///   or `Error::InvalidInput` for an epoch that has already ended
+    pub fn resolve(&self, current_epoch: u64) -> Result<u64, Error> {
+        match *self {
+            EpochTarget::NextBoundary => Ok(current_epoch + 1),
+            EpochTarget::Epoch(epoch) if epoch < current_epoch => Err(Error::InvalidInput(format!(
+                "Epoch {} has already ended; the cluster is in epoch {}",
+                epoch, current_epoch
+            ))),
+            EpochTarget::Epoch(epoch) => Ok(epoch),
+        }
+    }
+}
+
+/// How far the cluster is from the start of the target epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochProgress {
+    /// Current epoch
+    pub epoch: u64,
+    /// Epoch being waited for
+    pub target_epoch: u64,
+    /// Slots left until the target epoch begins (0 once it has)
+    pub remaining_slots: u64,
+}
+
+impl EpochProgress {
+    /// Measures the distance from `info` to the start of `target_epoch`
+    ///
+    /// Later epochs are assumed to be as long as the current one.
+    ///
+    /// # Arguments
+    /// * `info` - Result of `getEpochInfo`
+    /// * `target_epoch` - Epoch being waited for
+    pub fn new(info: &EpochInfo, target_epoch: u64) -> Self {
+        let remaining_slots = if info.epoch >= target_epoch {
+            0
+        } else {
+            let rest_of_epoch = info.slots_in_epoch.saturating_sub(info.slot_index);
+            rest_of_epoch.saturating_add((target_epoch - info.epoch - 1).saturating_mul(info.slots_in_epoch))
+        };
+        EpochProgress { epoch: info.epoch, target_epoch, remaining_slots }
+    }
+
+    /// Returns true once the target epoch has begun
+    pub fn reached(&self) -> bool {
+        self.epoch >= self.target_epoch
+    }
+
+    /// Estimated time until the target epoch begins, at `DEFAULT_SLOT_MS` per slot
+    pub fn eta(&self) -> Duration {
+        Duration::from_millis(self.remaining_slots.saturating_mul(DEFAULT_SLOT_MS))
+    }
+}
+
+impl fmt::Display for EpochProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.reached() {
+            return write!(f, "epoch {} has begun", self.target_epoch);
+        }
+        let eta = self.eta().as_secs();
+        write!(
+            f,
+            "epoch {}, {} slots until epoch {} (ETA {}h {:02}m {:02}s)",
+            self.epoch,
+            self.remaining_slots,
+            self.target_epoch,
+            eta / 3600,
+            eta % 3600 / 60,
+            eta % 60
+        )
+    }
+}
+
+/// Reads the current epoch, retrying transient RPC errors
+///
+/// # Arguments
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<EpochInfo, Error>` - Epoch info or error
+pub async fn get_epoch_info(rpc: &dyn SolanaRpc) -> Result<EpochInfo, Error> {
+    retry_transient(EPOCH_POLL_ATTEMPTS, || async {
+        rpc.get_epoch_info().await.map_err(|e| Error::from_client_error("Failed to get epoch info", &e))
+    })
+    .await
+}
+
+/// Polls `getEpochInfo` until `target_epoch` begins
+///
+/// Polls get more frequent as the boundary approaches: the pause is half the remaining ETA,
+/// at least one second and at most `max_poll_interval`.
+///
+/// # Arguments
+/// * `target_epoch` - Epoch to wait for
+/// * `max_poll_interval` - Longest pause between polls
+/// * `rpc` - RPC client
+/// * `on_progress` - Called with the progress after every poll, including the last one
+///
+/// # Returns
+/// * `Result<EpochInfo, Error>` - Epoch info of the first poll in the target epoch, or error
+pub async fn wait_for_epoch(
+    target_epoch: u64,
+    max_poll_interval: Duration,
+    rpc: &dyn SolanaRpc,
+    mut on_progress: impl FnMut(&EpochProgress),
+) -> Result<EpochInfo, Error> {
+    loop {
+        let info = get_epoch_info(rpc).await?;
+        let progress = EpochProgress::new(&info, target_epoch);
+        on_progress(&progress);
+        if progress.reached() {
+            return Ok(info);
+        }
+        let pause = (progress.eta() / 2).max(Duration::from_secs(1)).min(max_poll_interval);
+        tokio::time::sleep(pause).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+
+    fn epoch_info(epoch: u64, slot_index: u64) -> EpochInfo {
+        EpochInfo { epoch, slot_index, slots_in_epoch: 432_000, absolute_slot: 0, block_height: 0, transaction_count: None }
+    }
+
+    #[test]
+    fn test_resolve_epoch_target() {
+        assert_eq!(EpochTarget::NextBoundary.resolve(500).unwrap(), 501);
+        assert_eq!(EpochTarget::parse("502").unwrap().resolve(500).unwrap(), 502);
+        assert_eq!(EpochTarget::Epoch(500).resolve(500).unwrap(), 500);
+        assert!(EpochTarget::Epoch(499).resolve(500).unwrap_err().to_string().contains("already ended"));
+        assert!(EpochTarget::parse("next").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_epoch_progress() {
+        let progress = EpochProgress::new(&epoch_info(500, 431_000), 501);
+        assert_eq!(progress.remaining_slots, 1_000);
+        assert_eq!(progress.eta(), Duration::from_secs(400));
+        assert_eq!(progress.to_string(), "epoch 500, 1000 slots until epoch 501 (ETA 0h 06m 40s)");
+
+        // Every epoch in between counts in full
+        assert_eq!(EpochProgress::new(&epoch_info(500, 431_000), 503).remaining_slots, 1_000 + 2 * 432_000);
+
+        let reached = EpochProgress::new(&epoch_info(501, 5), 501);
+        assert!(reached.reached());
+        assert_eq!(reached.remaining_slots, 0);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_epoch_returns_at_boundary() {
+        let rpc = MockRpc::new();
+        let mut polls = Vec::new();
+        let info = wait_for_epoch(501, Duration::from_millis(1), &rpc, |progress| {
+            polls.push(*progress);
+            // The boundary passes after the second poll
+            if polls.len() == 2 {
+                rpc.set_epoch(501);
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(info.epoch, 501);
+        assert_eq!(polls.len(), 3);
+        assert!(!polls[0].reached());
+        assert!(polls[2].reached());
+    }
+}
@@ -0,0 +1,316 @@
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_custom_error::{
+    JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET, JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED,
+    JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY, JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE,
+};
+use solana_client::rpc_request::RpcError;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+/// Exit code of a failure without a more specific code (local files, HTTP services, cancelled fundings)
+pub const EXIT_FAILURE: i32 = 1;
+
+/// Exit code of invalid arguments, amounts, addresses or keypairs
+pub const EXIT_INVALID_INPUT: i32 = 2;
+
+/// Exit code of an unavailable RPC endpoint or an RPC error response
+pub const EXIT_RPC_FAILURE: i32 = 3;
+
+/// Exit code of a funding cancelled because the validator is not in gossip
+pub const EXIT_NOT_IN_GOSSIP: i32 = 4;
+
+/// Exit code of a payer that cannot cover the transfer
+pub const EXIT_INSUFFICIENT_FUNDS: i32 = 5;
+
+/// Exit code of a rejected or failed transaction
+pub const EXIT_TRANSACTION_FAILED: i32 = 6;
+
+/// Exit code of a transaction that was sent but not confirmed in time
+pub const EXIT_CONFIRMATION_TIMEOUT: i32 = 7;
+
+/// Exit code of a run stopped by `--strict` because of a warning
+pub const EXIT_STRICT_WARNING: i32 = 8;
+
+/// Exit code of a confirmed funding that changed the PDA balance by another amount than requested (`--verify-delta`)
+pub const EXIT_BALANCE_MISMATCH: i32 = 9;
+
+/// Errors returned by the library functions
+///
+/// Every variant is classified as either retryable (transient network/RPC trouble),
+/// a user error (bad input that retrying will never fix) or fatal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Invalid input supplied by the user (address, amount, arguments)
+    InvalidInput(String),
+    /// Keypair file could not be read or is malformed
+    Keypair(String),
+    /// Payer cannot cover the transfer
+    InsufficientFunds(String),
+    /// Funding was cancelled by a safety check (e.g. a delinquent validator or a duplicate funding)
+    FundingCancelled(String),
+    /// Funding was cancelled because the validator is not in gossip
+    NotInGossip(String),
+    /// A warning was raised while `--strict` treats every warning as a failure
+    StrictWarning(String),
+    /// RPC endpoint is temporarily unavailable (connection, timeout, rate limit, node unhealthy)
+    RpcUnavailable(String),
+    /// RPC endpoint returned an error response
+    Rpc(String),
+    /// Transaction was rejected or failed
+    TransactionFailed(String),
+    /// Transaction was sent but not confirmed in time
+    ConfirmationTimeout(String),
+    /// Confirmed transaction changed the PDA balance by another amount than requested (`--verify-delta`)
+    BalanceMismatch(String),
+    /// Local file (config, audit log) could not be read or written
+    Io(String),
+    /// Request to an external HTTP service (e.g. Prometheus Pushgateway) failed
+    Http(String),
+}
+
+impl Error {
+    /// Classifies a Solana RPC client error
+    ///
+    /// # Arguments
+    /// * `context` - What was being done when the error happened (e.g. "Failed to get balance")
+    /// * `err` - The client error
+    ///
+    /// # Returns
+    /// * `Error` - Classified error with the context prepended to the message
+    pub fn from_client_error(context: &str, err: &ClientError) -> Self {
+        let message = format!("{}: {}", context, err);
+
+        match err.kind() {
+            ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) | ClientErrorKind::Middleware(_) => {
+                Error::RpcUnavailable(message)
+            }
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => match *code {
+                JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+                | JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED
+                | JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET => Error::RpcUnavailable(message),
+                JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE => Error::TransactionFailed(message),
+                _ => Error::Rpc(message),
+            },
+            ClientErrorKind::TransactionError(_) | ClientErrorKind::SigningError(_) => {
+                Error::TransactionFailed(message)
+            }
+            _ => Error::Rpc(message),
+        }
+    }
+
+    /// Returns true if the operation may succeed when retried later
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Error::RpcUnavailable(_))
+    }
+
+    /// Returns true if the error was caused by user input and must never be retried
+    pub fn is_user_error(&self) -> bool {
+        matches!(
+            self,
+            Error::InvalidInput(_) | Error::Keypair(_) | Error::InsufficientFunds(_)
+        )
+    }
+
+    /// Returns a stable machine-readable name of the variant (e.g. `rpc_unavailable`)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::InvalidInput(_) => "invalid_input",
+            Error::Keypair(_) => "keypair",
+            Error::InsufficientFunds(_) => "insufficient_funds",
+            Error::FundingCancelled(_) => "funding_cancelled",
+            Error::NotInGossip(_) => "not_in_gossip",
+            Error::StrictWarning(_) => "strict_warning",
+            Error::RpcUnavailable(_) => "rpc_unavailable",
+            Error::Rpc(_) => "rpc",
+            Error::TransactionFailed(_) => "transaction_failed",
+            Error::ConfirmationTimeout(_) => "confirmation_timeout",
+            Error::BalanceMismatch(_) => "balance_mismatch",
+            Error::Io(_) => "io",
+            Error::Http(_) => "http",
+        }
+    }
+
+    /// Returns the process exit code of the failure class, so scripts can branch on it
+    ///
+    /// # Returns
+    /// * `i32` - One of the `EXIT_*` codes; errors without a class of their own exit with `EXIT_FAILURE`
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::InvalidInput(_) | Error::Keypair(_) => EXIT_INVALID_INPUT,
+            Error::RpcUnavailable(_) | Error::Rpc(_) => EXIT_RPC_FAILURE,
+            Error::NotInGossip(_) => EXIT_NOT_IN_GOSSIP,
+            Error::InsufficientFunds(_) => EXIT_INSUFFICIENT_FUNDS,
+            Error::TransactionFailed(_) => EXIT_TRANSACTION_FAILED,
+            Error::ConfirmationTimeout(_) => EXIT_CONFIRMATION_TIMEOUT,
+            Error::StrictWarning(_) => EXIT_STRICT_WARNING,
+            Error::BalanceMismatch(_) => EXIT_BALANCE_MISMATCH,
+            Error::FundingCancelled(_) | Error::Io(_) | Error::Http(_) => EXIT_FAILURE,
+        }
+    }
+
+    /// Returns the error message without the variant name
+    pub fn message(&self) -> &str {
+        match self {
+            Error::InvalidInput(message)
+            | Error::Keypair(message)
+            | Error::InsufficientFunds(message)
+            | Error::FundingCancelled(message)
+            | Error::NotInGossip(message)
+            | Error::StrictWarning(message)
+            | Error::RpcUnavailable(message)
+            | Error::Rpc(message)
+            | Error::TransactionFailed(message)
+            | Error::ConfirmationTimeout(message)
+            | Error::BalanceMismatch(message)
+            | Error::Io(message)
+            | Error::Http(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Runs an operation, retrying it with exponential backoff while it fails with a retryable error
+///
+/// User errors and fatal errors are returned immediately.
+///
+/// # Arguments
+/// * `max_attempts` - Maximum number of attempts (at least one attempt is always made)
+/// * `operation` - Closure producing the future to run on each attempt
+///
+/// # Returns
+/// * `Result<T, Error>` - Result of the first successful attempt or the last error
+pub async fn retry_transient<T, F, Fut>(max_attempts: u32, mut operation: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Err(e) if e.is_retryable() && attempt < max_attempts => {
+                tracing::debug!(attempt, max_attempts, "Retrying after transient error: {}", e);
+                tokio::time::sleep(Duration::from_millis(500 * 2u64.pow(attempt - 1))).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client::rpc_request::RpcResponseErrorData;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn response_error(code: i64) -> ClientError {
+        ClientError::from(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code,
+            message: "test".to_string(),
+            data: RpcResponseErrorData::Empty,
+        }))
+    }
+
+    #[test]
+    fn test_error_classification() {
+        assert!(Error::RpcUnavailable("x".to_string()).is_retryable());
+        assert!(!Error::RpcUnavailable("x".to_string()).is_user_error());
+
+        for user_error in [
+            Error::InvalidInput("x".to_string()),
+            Error::Keypair("x".to_string()),
+            Error::InsufficientFunds("x".to_string()),
+        ] {
+            assert!(user_error.is_user_error());
+            assert!(!user_error.is_retryable());
+        }
+
+        for fatal in [
+            Error::FundingCancelled("x".to_string()),
+            Error::NotInGossip("x".to_string()),
+            Error::StrictWarning("x".to_string()),
+            Error::Rpc("x".to_string()),
+            Error::TransactionFailed("x".to_string()),
+            Error::ConfirmationTimeout("x".to_string()),
+            Error::Io("x".to_string()),
+            Error::Http("x".to_string()),
+        ] {
+            assert!(!fatal.is_user_error());
+            assert!(!fatal.is_retryable());
+        }
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let cases = [
+            (Error::InvalidInput("x".to_string()), EXIT_INVALID_INPUT),
+            (Error::Keypair("x".to_string()), EXIT_INVALID_INPUT),
+            (Error::RpcUnavailable("x".to_string()), EXIT_RPC_FAILURE),
+            (Error::Rpc("x".to_string()), EXIT_RPC_FAILURE),
+            (Error::NotInGossip("x".to_string()), EXIT_NOT_IN_GOSSIP),
+            (Error::InsufficientFunds("x".to_string()), EXIT_INSUFFICIENT_FUNDS),
+            (Error::TransactionFailed("x".to_string()), EXIT_TRANSACTION_FAILED),
+            (Error::ConfirmationTimeout("x".to_string()), EXIT_CONFIRMATION_TIMEOUT),
+            (Error::StrictWarning("x".to_string()), EXIT_STRICT_WARNING),
+            (Error::BalanceMismatch("x".to_string()), EXIT_BALANCE_MISMATCH),
+            (Error::FundingCancelled("x".to_string()), EXIT_FAILURE),
+            (Error::Io("x".to_string()), EXIT_FAILURE),
+        ];
+        for (error, code) in cases {
+            assert_eq!(error.exit_code(), code, "{:?}", error);
+        }
+        assert_eq!(Error::NotInGossip("x".to_string()).kind(), "not_in_gossip");
+    }
+
+    #[test]
+    fn test_from_client_error() {
+        let io = ClientError::from(ClientErrorKind::Io(std::io::Error::other("connection reset")));
+        let error = Error::from_client_error("Failed to get balance", &io);
+        assert!(error.is_retryable());
+        assert!(error.to_string().starts_with("Failed to get balance: "));
+
+        let unhealthy = Error::from_client_error("ctx", &response_error(JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY));
+        assert!(unhealthy.is_retryable());
+
+        let preflight = Error::from_client_error(
+            "ctx",
+            &response_error(JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE),
+        );
+        assert!(matches!(preflight, Error::TransactionFailed(_)));
+
+        let other = Error::from_client_error("ctx", &response_error(-32602));
+        assert!(matches!(other, Error::Rpc(_)));
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_retries_only_retryable_errors() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), Error> = retry_transient(3, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(Error::InvalidInput("bad".to_string()))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let calls = AtomicU32::new(0);
+        let result = retry_transient(3, || async {
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(Error::RpcUnavailable("timeout".to_string()))
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+        assert_eq!(result, Ok(42));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
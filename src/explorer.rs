@@ -0,0 +1,95 @@
+use crate::Error;
+use qrcode::render::unicode::Dense1x2;
+use qrcode::QrCode;
+
+/// Block explorer linked with `--explorer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Explorer {
+    /// solscan.io
+    Solscan,
+    /// solana.fm
+    SolanaFm,
+    /// Helius XRAY
+    Xray,
+}
+
+impl Explorer {
+    /// Parses an explorer name (`solscan`, `solanafm` or `xray`)
+    ///
+    /// # Arguments
+    /// * `value` - Explorer name, case-insensitive
+    ///
+    /// # Returns
+    /// * `Result<Explorer, Error>` - Explorer or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "solscan" => Ok(Explorer::Solscan),
+            "solanafm" => Ok(Explorer::SolanaFm),
+            "xray" => Ok(Explorer::Xray),
+            _ => Err(Error::InvalidInput(format!("Invalid explorer '{}': expected solscan, solanafm or xray", value))),
+        }
+    }
+
+    /// Returns the mainnet page of an account
+    pub fn account_url(&self, address: &str) -> String {
+        match self {
+            Explorer::Solscan => format!("https://solscan.io/account/{}", address),
+            Explorer::SolanaFm => format!("https://solana.fm/address/{}", address),
+            Explorer::Xray => format!("https://xray.helius.xyz/account/{}", address),
+        }
+    }
+
+    /// Returns the mainnet page of a transaction
+    pub fn transaction_url(&self, signature: &str) -> String {
+        match self {
+            Explorer::Solscan => format!("https://solscan.io/tx/{}", signature),
+            Explorer::SolanaFm => format!("https://solana.fm/tx/{}", signature),
+            Explorer::Xray => format!("https://xray.helius.xyz/tx/{}", signature),
+        }
+    }
+}
+
+/// Returns the Solana Pay transfer request for a deposit to an address, as scanned by mobile wallets
+pub fn deposit_uri(address: &str) -> String {
+    format!("solana:{}", address)
+}
+
+/// Renders text as a QR code of Unicode half blocks for a terminal
+///
+/// Light modules are drawn, so the code reads correctly on the usual dark terminal background.
+///
+/// # Arguments
+/// * `data` - Text to encode
+///
+/// # Returns
+/// * `Result<String, Error>` - Lines of the QR code, or `Error::InvalidInput` if the text does not fit
+pub fn render_qr(data: &str) -> Result<String, Error> {
+    let code = QrCode::new(data.as_bytes()).map_err(|e| Error::InvalidInput(format!("Cannot render QR code: {}", e)))?;
+    Ok(code.render::<Dense1x2>().dark_color(Dense1x2::Light).light_color(Dense1x2::Dark).build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PDA: &str = "7x59cGVPrehfD7nGvr8BjwXc6nmeTsVA6gJREtXErGsR";
+
+    #[test]
+    fn test_explorer_urls() {
+        assert_eq!(Explorer::parse("Solscan").unwrap().account_url(PDA), format!("https://solscan.io/account/{}", PDA));
+        assert_eq!(Explorer::parse("solanafm").unwrap().transaction_url("sig"), "https://solana.fm/tx/sig");
+        assert_eq!(Explorer::parse("xray").unwrap().account_url(PDA), format!("https://xray.helius.xyz/account/{}", PDA));
+        assert!(Explorer::parse("etherscan").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_render_qr() {
+        assert_eq!(deposit_uri(PDA), format!("solana:{}", PDA));
+        let qr = render_qr(&deposit_uri(PDA)).unwrap();
+        let lines: Vec<&str> = qr.lines().collect();
+        // Two modules per character row, with a quiet zone around the code
+        assert!(lines.len() > 10);
+        assert!(lines.iter().all(|line| line.chars().count() == lines[0].chars().count()));
+        assert!(qr.contains('▀') || qr.contains('▄'));
+    }
+}
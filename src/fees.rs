@@ -0,0 +1,244 @@
+use crate::audit::{AuditEntry, EVENT_PDA_FUND, EVENT_PDA_FUND_TOKEN, EVENT_WALLET_ROTATE, STATUS_SUCCESS};
+use crate::Error;
+use serde::Serialize;
+use std::fmt;
+
+/// Audit log events whose transaction fee was paid by the tool
+pub const FEE_EVENTS: &[&str] = &[EVENT_PDA_FUND, EVENT_PDA_FUND_TOKEN, EVENT_WALLET_ROTATE];
+
+/// Number of days before now used to measure the current fee cadence
+pub const FORECAST_WINDOW_DAYS: u64 = 30;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Calendar month in UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Month {
+    /// Year (e.g. 2024)
+    pub year: i64,
+    /// Month number, 1 to 12
+    pub month: u32,
+}
+
+impl Month {
+    /// Parses `YYYY-MM` (e.g. `2024-11`)
+    ///
+    /// # Arguments
+    /// * `value` - Value of `--month`
+    ///
+    /// # Returns
+    /// * `Result<Month, Error>` - Month or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        let invalid = || Error::InvalidInput(format!("Invalid month '{}': expected YYYY-MM (e.g. 2024-11)", value));
+        let (year, month) = value.trim().split_once('-').ok_or_else(invalid)?;
+        if year.len() != 4 || month.len() != 2 {
+            return Err(invalid());
+        }
+        let year = year.parse::<i64>().map_err(|_| invalid())?;
+        let month = month.parse::<u32>().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) {
+            return Err(invalid());
+        }
+        Ok(Month { year, month })
+    }
+
+    /// Returns the month containing a Unix timestamp
+    ///
+    /// # Arguments
+    /// * `timestamp` - Unix timestamp in seconds
+    pub fn from_timestamp(timestamp: u64) -> Self {
+        let (year, month, _) = civil_from_days((timestamp / SECONDS_PER_DAY) as i64);
+        Month { year, month }
+    }
+
+    /// Returns the following month
+    pub fn next(&self) -> Self {
+        if self.month == 12 {
+            Month { year: self.year + 1, month: 1 }
+        } else {
+            Month { year: self.year, month: self.month + 1 }
+        }
+    }
+
+    /// Returns the Unix timestamp of the first second of the month (0 for months before 1970)
+    pub fn start_timestamp(&self) -> u64 {
+        days_from_civil(self.year, self.month, 1).max(0) as u64 * SECONDS_PER_DAY
+    }
+
+    /// Returns the Unix timestamp of the first second of the following month
+    pub fn end_timestamp(&self) -> u64 {
+        self.next().start_timestamp()
+    }
+}
+
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date of a number of days since 1970-01-01
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Transaction fees spent in one month
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeeReport {
+    /// Month in `YYYY-MM` form
+    pub month: String,
+    /// Number of fee-paying transactions in the month
+    pub transactions: u64,
+    /// Fees spent in the month in lamports
+    pub fee_lamports: u64,
+    /// Average fees per day over the last `FORECAST_WINDOW_DAYS` days
+    pub daily_rate_lamports: f64,
+    /// Expected total for the month at the current cadence (None for a month that is over)
+    pub forecast_lamports: Option<u64>,
+}
+
+/// Returns the fee of an entry if the tool paid it
+pub(crate) fn paid_fee(entry: &AuditEntry) -> Option<u64> {
+    (entry.status == STATUS_SUCCESS && FEE_EVENTS.contains(&entry.event.as_str()))
+        .then_some(entry.fee_lamports)
+        .flatten()
+}
+
+/// Sums the fees of a month and forecasts its total from the current cadence
+///
+/// The cadence is the average daily fee over the last `FORECAST_WINDOW_DAYS` days before `now`.
+/// The forecast of the current month is the amount spent so far plus that rate over the remaining
+/// time; a future month is forecast at the rate over its whole length.
+///
+/// # Arguments
+/// * `entries` - Audit log entries
+/// * `month` - Month to report
+/// * `now` - Current Unix timestamp in seconds
+///
+/// # Returns
+/// * `FeeReport` - Fees spent and forecast
+pub fn fee_report(entries: &[AuditEntry], month: Month, now: u64) -> FeeReport {
+    let (start, end) = (month.start_timestamp(), month.end_timestamp());
+    let in_month: Vec<u64> = entries
+        .iter()
+        .filter(|entry| (start..end).contains(&entry.timestamp))
+        .filter_map(paid_fee)
+        .collect();
+
+    let window_start = now.saturating_sub(FORECAST_WINDOW_DAYS * SECONDS_PER_DAY);
+    let window_fees: u64 = entries
+        .iter()
+        .filter(|entry| (window_start..=now).contains(&entry.timestamp))
+        .filter_map(paid_fee)
+        .sum();
+    let daily_rate_lamports = window_fees as f64 / FORECAST_WINDOW_DAYS as f64;
+    let rate_per_second = daily_rate_lamports / SECONDS_PER_DAY as f64;
+
+    let fee_lamports: u64 = in_month.iter().sum();
+    let forecast_lamports = if now >= end {
+        None
+    } else {
+        let remaining_secs = end - now.max(start);
+        Some(fee_lamports + (rate_per_second * remaining_secs as f64).round() as u64)
+    };
+
+    FeeReport {
+        month: month.to_string(),
+        transactions: in_month.len() as u64,
+        fee_lamports,
+        daily_rate_lamports,
+        forecast_lamports,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::EVENT_DEPOSIT_CREATED;
+
+    fn entry(event: &str, timestamp: u64, fee_lamports: u64) -> AuditEntry {
+        AuditEntry {
+            timestamp,
+            fee_lamports: Some(fee_lamports),
+            ..AuditEntry::new(event, STATUS_SUCCESS)
+        }
+    }
+
+    #[test]
+    fn test_month_parse_and_bounds() {
+        let month = Month::parse("2024-11").unwrap();
+        assert_eq!(month, Month { year: 2024, month: 11 });
+        assert_eq!(month.to_string(), "2024-11");
+        // 2024-11-01T00:00:00Z and 2024-12-01T00:00:00Z
+        assert_eq!(month.start_timestamp(), 1_730_419_200);
+        assert_eq!(month.end_timestamp(), 1_733_011_200);
+        assert_eq!(Month::parse("2024-12").unwrap().next(), Month { year: 2025, month: 1 });
+        assert_eq!(Month::from_timestamp(1_733_011_199), month);
+        assert_eq!(Month::from_timestamp(1_709_164_800), Month { year: 2024, month: 2 });
+
+        assert!(Month::parse("2024-13").unwrap_err().is_user_error());
+        assert!(Month::parse("2024/11").is_err());
+        assert!(Month::parse("24-11").is_err());
+    }
+
+    #[test]
+    fn test_fee_report_sums_paid_fees_of_the_month() {
+        let november = Month::parse("2024-11").unwrap();
+        let start = november.start_timestamp();
+        let mut failed = entry(EVENT_PDA_FUND, start + 20, 5_000);
+        failed.status = "failed".to_string();
+        let entries = vec![
+            entry(EVENT_PDA_FUND, start - 1, 5_000),
+            entry(EVENT_PDA_FUND, start, 5_000),
+            entry(EVENT_WALLET_ROTATE, start + 10, 5_000),
+            entry(EVENT_DEPOSIT_CREATED, start + 10, 5_000),
+            failed,
+            entry(EVENT_PDA_FUND, november.end_timestamp(), 5_000),
+        ];
+
+        let report = fee_report(&entries, november, november.end_timestamp() + 1);
+        assert_eq!(report.transactions, 2);
+        assert_eq!(report.fee_lamports, 10_000);
+        assert_eq!(report.forecast_lamports, None);
+    }
+
+    #[test]
+    fn test_forecast_uses_current_cadence() {
+        let november = Month::parse("2024-11").unwrap();
+        let day = SECONDS_PER_DAY;
+        // Mid-month: 30 fees of 10_000 lamports, one per day over the last 30 days
+        let now = november.start_timestamp() + 15 * day;
+        let entries: Vec<AuditEntry> = (0..30).map(|index| entry(EVENT_PDA_FUND, now - index * day, 10_000)).collect();
+
+        let report = fee_report(&entries, november, now);
+        assert_eq!(report.transactions, 16);
+        assert_eq!(report.fee_lamports, 160_000);
+        assert_eq!(report.daily_rate_lamports, 10_000.0);
+        assert_eq!(report.forecast_lamports, Some(160_000 + 15 * 10_000));
+
+        // The next month is forecast over its whole length
+        let report = fee_report(&entries, november.next(), now);
+        assert_eq!(report.fee_lamports, 0);
+        assert_eq!(report.forecast_lamports, Some(31 * 10_000));
+    }
+}
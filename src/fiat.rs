@@ -0,0 +1,412 @@
+use crate::amount::LAMPORTS_PER_SOL;
+use crate::audit::format_timestamp;
+use crate::config::{default_data_dir, write_atomic};
+use crate::{unix_timestamp, Error};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Base URL of the public CoinGecko API
+pub const COINGECKO_API_URL: &str = "https://api.coingecko.com/api/v3";
+
+/// How long a current price is reused before it is fetched again
+pub const DEFAULT_PRICE_TTL: Duration = Duration::from_secs(300);
+
+/// Timeout of one price request
+const PRICE_TIMEOUT_SECS: u64 = 10;
+
+/// Fiat currency of `--show-fiat`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Currency {
+    /// US dollar
+    Usd,
+    /// Euro
+    Eur,
+}
+
+impl Currency {
+    /// Parses `usd` or `eur`
+    ///
+    /// # Arguments
+    /// * `value` - Currency code, case-insensitive
+    ///
+    /// # Returns
+    /// * `Result<Currency, Error>` - Currency or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "usd" => Ok(Currency::Usd),
+            "eur" => Ok(Currency::Eur),
+            _ => Err(Error::InvalidInput(format!("Unsupported currency '{}': expected usd or eur", value))),
+        }
+    }
+
+    /// Returns the lowercase code used by price APIs
+    pub fn code(self) -> &'static str {
+        match self {
+            Currency::Usd => "usd",
+            Currency::Eur => "eur",
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code().to_ascii_uppercase())
+    }
+}
+
+/// Source of SOL prices
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    /// Current price of 1 SOL
+    async fn current_price(&self, currency: Currency) -> Result<f64, Error>;
+
+    /// Price of 1 SOL on the UTC day of a Unix timestamp
+    async fn historical_price(&self, currency: Currency, timestamp: u64) -> Result<f64, Error>;
+}
+
+/// Prices from the CoinGecko HTTP API
+#[derive(Debug, Clone)]
+pub struct CoinGecko {
+    base_url: String,
+}
+
+impl CoinGecko {
+    /// Uses the public API (`COINGECKO_API_URL`)
+    pub fn new() -> Self {
+        CoinGecko::with_base_url(COINGECKO_API_URL)
+    }
+
+    /// Uses another CoinGecko-compatible endpoint, e.g. the Pro API or a local proxy
+    ///
+    /// # Arguments
+    /// * `base_url` - API base URL without a trailing slash
+    pub fn with_base_url(base_url: &str) -> Self {
+        CoinGecko { base_url: base_url.trim_end_matches('/').to_string() }
+    }
+
+    async fn get(&self, path: &str) -> Result<serde_json::Value, Error> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = reqwest::Client::new()
+            .get(&url)
+            .timeout(Duration::from_secs(PRICE_TIMEOUT_SECS))
+            .send()
+            .await
+            .map_err(|e| Error::Http(format!("Failed to get the SOL price from {}: {}", self.base_url, e.without_url())))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::Http(format!("Price API {} answered {}", self.base_url, status)));
+        }
+        response.json().await.map_err(|e| Error::Http(format!("Invalid price API response: {}", e)))
+    }
+}
+
+impl Default for CoinGecko {
+    fn default() -> Self {
+        CoinGecko::new()
+    }
+}
+
+#[async_trait]
+impl PriceSource for CoinGecko {
+    async fn current_price(&self, currency: Currency) -> Result<f64, Error> {
+        let json = self.get(&format!("/simple/price?ids=solana&vs_currencies={}", currency.code())).await?;
+        parse_simple_price(&json, currency)
+    }
+
+    async fn historical_price(&self, currency: Currency, timestamp: u64) -> Result<f64, Error> {
+        // The history endpoint takes the day as dd-mm-yyyy
+        let day = day_key(timestamp);
+        let date = format!("{}-{}-{}", &day[8..10], &day[5..7], &day[0..4]);
+        let json = self.get(&format!("/coins/solana/history?date={}&localization=false", date)).await?;
+        parse_history_price(&json, currency)
+    }
+}
+
+/// Reads the price of a `/simple/price` response (`{"solana": {"usd": 142.1}}`)
+///
+/// # Arguments
+/// * `json` - Response body
+/// * `currency` - Requested currency
+///
+/// # Returns
+/// * `Result<f64, Error>` - Price of 1 SOL or `Error::Http` if it is missing
+pub fn parse_simple_price(json: &serde_json::Value, currency: Currency) -> Result<f64, Error> {
+    json["solana"][currency.code()]
+        .as_f64()
+        .ok_or_else(|| Error::Http(format!("Price API response has no SOL price in {}", currency)))
+}
+
+/// Reads the price of a `/coins/solana/history` response (`{"market_data": {"current_price": {"usd": 142.1}}}`)
+///
+/// # Arguments
+/// * `json` - Response body
+/// * `currency` - Requested currency
+///
+/// # Returns
+/// * `Result<f64, Error>` - Price of 1 SOL on that day or `Error::Http` if it is missing
+pub fn parse_history_price(json: &serde_json::Value, currency: Currency) -> Result<f64, Error> {
+    json["market_data"]["current_price"][currency.code()]
+        .as_f64()
+        .ok_or_else(|| Error::Http(format!("Price API has no SOL price in {} for that day", currency)))
+}
+
+/// Returns the UTC day of a Unix timestamp as `YYYY-MM-DD`
+fn day_key(timestamp: u64) -> String {
+    format_timestamp(timestamp)[..10].to_string()
+}
+
+/// Current price with the time it was fetched
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CachedPrice {
+    /// Price of 1 SOL
+    pub price: f64,
+    /// Unix timestamp of the fetch
+    pub fetched_at: u64,
+}
+
+/// Prices kept between runs; current prices expire after the TTL, prices of past days never do
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceCache {
+    /// Current price per currency code
+    #[serde(default)]
+    pub current: BTreeMap<String, CachedPrice>,
+    /// Price per `currency:YYYY-MM-DD`
+    #[serde(default)]
+    pub historical: BTreeMap<String, f64>,
+}
+
+impl PriceCache {
+    /// Loads the cache file, starting empty if it does not exist
+    ///
+    /// # Arguments
+    /// * `path` - Path to the cache file
+    ///
+    /// # Returns
+    /// * `Result<PriceCache, Error>` - Cached prices or `Error::Io`
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(PriceCache::default());
+        }
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::Io(format!("Failed to read price cache {}: {}", path.display(), e)))?;
+        serde_json::from_str(&contents).map_err(|e| Error::Io(format!("Failed to parse price cache {}: {}", path.display(), e)))
+    }
+
+    /// Saves the cache file atomically
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let contents = serde_json::to_string(self).map_err(|e| Error::Io(format!("Failed to serialize price cache: {}", e)))?;
+        write_atomic(path, contents.as_bytes())
+    }
+
+    /// Returns the current price if it was fetched less than `ttl` before `now`
+    pub fn fresh_current(&self, currency: Currency, ttl: Duration, now: u64) -> Option<f64> {
+        let cached = self.current.get(currency.code())?;
+        let age = now.checked_sub(cached.fetched_at)?;
+        (age < ttl.as_secs()).then_some(cached.price)
+    }
+
+    /// Returns the price of the UTC day of `timestamp`, if cached
+    pub fn historical(&self, currency: Currency, timestamp: u64) -> Option<f64> {
+        self.historical.get(&format!("{}:{}", currency.code(), day_key(timestamp))).copied()
+    }
+}
+
+/// Returns the default price cache file
+pub fn default_price_cache_path() -> PathBuf {
+    default_data_dir().join("prices.json")
+}
+
+/// Price source that answers from a cache file when it can
+pub struct CachedPriceSource<S> {
+    source: S,
+    path: Option<PathBuf>,
+    ttl: Duration,
+    cache: Mutex<PriceCache>,
+}
+
+impl<S: PriceSource> CachedPriceSource<S> {
+    /// Wraps a source with a cache kept in `path` (None to cache in memory only)
+    ///
+    /// A cache file that cannot be read starts empty, and one that cannot be written is ignored.
+    ///
+    /// # Arguments
+    /// * `source` - Source of prices missing from the cache
+    /// * `path` - Cache file
+    /// * `ttl` - How long a current price is reused
+    pub fn new(source: S, path: Option<PathBuf>, ttl: Duration) -> Self {
+        let cache = path.as_deref().and_then(|path| PriceCache::load(path).ok()).unwrap_or_default();
+        CachedPriceSource { source, path, ttl, cache: Mutex::new(cache) }
+    }
+
+    fn update(&self, update: impl FnOnce(&mut PriceCache)) {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        update(&mut cache);
+        if let Some(path) = &self.path
+            && let Err(e) = cache.save(path)
+        {
+            tracing::warn!("Price cache not saved: {}", e);
+        }
+    }
+
+    fn cached<T>(&self, read: impl FnOnce(&PriceCache) -> Option<T>) -> Option<T> {
+        read(&self.cache.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+#[async_trait]
+impl<S: PriceSource> PriceSource for CachedPriceSource<S> {
+    async fn current_price(&self, currency: Currency) -> Result<f64, Error> {
+        let now = unix_timestamp();
+        if let Some(price) = self.cached(|cache| cache.fresh_current(currency, self.ttl, now)) {
+            return Ok(price);
+        }
+        let price = self.source.current_price(currency).await?;
+        self.update(|cache| {
+            cache.current.insert(currency.code().to_string(), CachedPrice { price, fetched_at: now });
+        });
+        Ok(price)
+    }
+
+    async fn historical_price(&self, currency: Currency, timestamp: u64) -> Result<f64, Error> {
+        if let Some(price) = self.cached(|cache| cache.historical(currency, timestamp)) {
+            return Ok(price);
+        }
+        let price = self.source.historical_price(currency, timestamp).await?;
+        // Today's price still moves, so only finished days are kept
+        if day_key(timestamp) != day_key(unix_timestamp()) {
+            self.update(|cache| {
+                cache.historical.insert(format!("{}:{}", currency.code(), day_key(timestamp)), price);
+            });
+        }
+        Ok(price)
+    }
+}
+
+/// Fiat value of a SOL amount
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FiatQuote {
+    /// Currency of the value
+    pub currency: Currency,
+    /// Price of 1 SOL used
+    pub price: f64,
+    /// Value of the amount
+    pub value: f64,
+}
+
+impl FiatQuote {
+    /// Values an amount at a price
+    ///
+    /// # Arguments
+    /// * `lamports` - Amount in lamports
+    /// * `price` - Price of 1 SOL
+    /// * `currency` - Currency of the price
+    pub fn new(lamports: u64, price: f64, currency: Currency) -> Self {
+        FiatQuote { currency, price, value: lamports as f64 / LAMPORTS_PER_SOL as f64 * price }
+    }
+}
+
+impl fmt::Display for FiatQuote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "≈ {:.2} {} at {:.2} {}/SOL", self.value, self.currency, self.price, self.currency)
+    }
+}
+
+/// Values a SOL amount at the current price, or at the price of the day of `at`
+///
+/// # Arguments
+/// * `source` - Price source
+/// * `currency` - Fiat currency
+/// * `lamports` - Amount in lamports
+/// * `at` - Unix timestamp for a historical price (None for the current price)
+///
+/// # Returns
+/// * `Result<FiatQuote, Error>` - Value and price used, or the error of the price source
+pub async fn quote(source: &dyn PriceSource, currency: Currency, lamports: u64, at: Option<u64>) -> Result<FiatQuote, Error> {
+    let price = match at {
+        Some(timestamp) => source.historical_price(currency, timestamp).await?,
+        None => source.current_price(currency).await?,
+    };
+    Ok(FiatQuote::new(lamports, price, currency))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Fixed prices that count the requests reaching the source
+    struct FixedPrices {
+        requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PriceSource for FixedPrices {
+        async fn current_price(&self, _currency: Currency) -> Result<f64, Error> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            Ok(150.0)
+        }
+
+        async fn historical_price(&self, _currency: Currency, _timestamp: u64) -> Result<f64, Error> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            Ok(20.0)
+        }
+    }
+
+    #[test]
+    fn test_parse_currency() {
+        assert_eq!(Currency::parse("USD").unwrap(), Currency::Usd);
+        assert_eq!(Currency::parse("eur").unwrap(), Currency::Eur);
+        assert!(Currency::parse("gbp").unwrap_err().is_user_error());
+        assert_eq!(Currency::Eur.to_string(), "EUR");
+    }
+
+    #[test]
+    fn test_parse_price_responses() {
+        let simple = serde_json::json!({ "solana": { "usd": 142.5 } });
+        assert_eq!(parse_simple_price(&simple, Currency::Usd).unwrap(), 142.5);
+        assert!(parse_simple_price(&simple, Currency::Eur).is_err());
+
+        let history = serde_json::json!({ "id": "solana", "market_data": { "current_price": { "eur": 18.25 } } });
+        assert_eq!(parse_history_price(&history, Currency::Eur).unwrap(), 18.25);
+        // Days before the coin was listed have no market data
+        assert!(parse_history_price(&serde_json::json!({ "id": "solana" }), Currency::Eur).is_err());
+    }
+
+    #[test]
+    fn test_quote_and_display() {
+        let quote = FiatQuote::new(1_500_000_000, 100.0, Currency::Usd);
+        assert_eq!(quote.value, 150.0);
+        assert_eq!(quote.to_string(), "≈ 150.00 USD at 100.00 USD/SOL");
+    }
+
+    #[tokio::test]
+    async fn test_cached_price_source() {
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_prices_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let source = CachedPriceSource::new(FixedPrices { requests: AtomicUsize::new(0) }, Some(path.clone()), DEFAULT_PRICE_TTL);
+
+        let current = quote(&source, Currency::Usd, LAMPORTS_PER_SOL, None).await.unwrap();
+        assert_eq!(current.value, 150.0);
+        source.current_price(Currency::Usd).await.unwrap();
+        // A past day is fetched once and kept in the file
+        let past_day = 1_700_000_000;
+        assert_eq!(source.historical_price(Currency::Usd, past_day).await.unwrap(), 20.0);
+        assert_eq!(source.historical_price(Currency::Usd, past_day + 60).await.unwrap(), 20.0);
+        assert_eq!(source.source.requests.load(Ordering::SeqCst), 2);
+
+        let cache = PriceCache::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(cache.historical(Currency::Usd, past_day), Some(20.0));
+        assert_eq!(cache.historical(Currency::Eur, past_day), None);
+        let fetched_at = cache.current["usd"].fetched_at;
+        assert_eq!(cache.fresh_current(Currency::Usd, DEFAULT_PRICE_TTL, fetched_at + 299), Some(150.0));
+        assert_eq!(cache.fresh_current(Currency::Usd, DEFAULT_PRICE_TTL, fetched_at + 300), None);
+    }
+}
@@ -0,0 +1,667 @@
+use crate::amount::{format_sol, parse_amount, Amount};
+use crate::budget::enforce_budget;
+use crate::batch::{transaction_transfers, transfer_status, SentTransaction, TransactionResult, TransferOutcome, TransferStatus};
+use crate::config::write_atomic;
+use crate::deposit::{check_deposit_preflight, get_deposit_account};
+use crate::instruction::build_fund_instructions;
+use crate::pending::pending_funding_warnings;
+use crate::priority::{self, compute_budget_instructions};
+use crate::packer::{pack_instructions, PackLimits};
+use crate::i18n;
+use crate::progress;
+use crate::report::strict_check;
+use crate::rpc::SolanaRpc;
+use crate::signer::SignerPool;
+use crate::wallet::wait_for_confirmation;
+use crate::{
+    check_funding_policy, check_funding_sufficiency, generate_deposit_pda, get_rent_exempt_minimum, load_required_signers, unix_timestamp,
+    Error, FundingAccounts, FundingPolicy, FundingSigners, Warning,
+};
+use serde::{Deserialize, Serialize};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Error recorded for transfers whose transaction is being sent
+const SENDING: &str = "Sent; the result was not recorded";
+
+/// Signatures `getSignatureStatuses` accepts in one request
+const MAX_SIGNATURE_STATUSES: usize = 256;
+
+/// Parses `--pairs validator1:1.5,validator2:2.0`
+///
+/// # Arguments
+/// * `value` - Comma-separated `validator:amount` pairs; validators may be pubkeys or aliases
+///
+/// # Returns
+/// * `Result<Vec<(String, u64)>, Error>` - Validators and amounts in lamports, or `Error::InvalidInput`
+pub fn parse_funding_pairs(value: &str) -> Result<Vec<(String, u64)>, Error> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (validator, amount) = pair
+                .rsplit_once(':')
+                .ok_or_else(|| Error::InvalidInput(format!("Invalid pair '{}': expected validator:amount", pair)))?;
+            parse_transfer(validator, amount)
+        })
+        .collect()
+}
+
+/// Reads a CSV file of `validator,amount` lines
+///
+/// Blank lines, lines starting with `#` and a `validator,amount` header are ignored.
+///
+/// # Arguments
+/// * `path` - File path
+///
+/// # Returns
+/// * `Result<Vec<(String, u64)>, Error>` - Validators and amounts in lamports in file order
+pub fn read_funding_file(path: &Path) -> Result<Vec<(String, u64)>, Error> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| Error::InvalidInput(format!("Failed to read funding list {}: {}", path.display(), e)))?;
+
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .filter(|(_, line)| !line.eq_ignore_ascii_case("validator,amount"))
+        .map(|(number, line)| {
+            let (validator, amount) = line.split_once(',').ok_or_else(|| {
+                Error::InvalidInput(format!("{} line {}: expected validator,amount", path.display(), number))
+            })?;
+            parse_transfer(validator, amount)
+                .map_err(|e| Error::InvalidInput(format!("{} line {}: {}", path.display(), number, e)))
+        })
+        .collect()
+}
+
+/// Parses one validator and amount; `ALL` is rejected because the balance is shared by every transfer
+fn parse_transfer(validator: &str, amount: &str) -> Result<(String, u64), Error> {
+    let validator = validator.trim();
+    if validator.is_empty() {
+        return Err(Error::InvalidInput(format!("Missing validator before amount '{}'", amount.trim())));
+    }
+    match parse_amount(amount.trim())? {
+        Amount::Lamports(0) => Err(Error::InvalidInput(format!("Amount for {} must be greater than zero", validator))),
+        Amount::Lamports(lamports) => Ok((validator.to_string(), lamports)),
+        Amount::All => Err(Error::InvalidInput(format!("Amount ALL is not supported for {}: give an exact amount per validator", validator))),
+    }
+}
+
+/// Formats transfers as a `--pairs` value, e.g. to retry the failed part of a run
+///
+/// # Arguments
+/// * `transfers` - Validators and amounts in lamports
+///
+/// # Returns
+/// * `String` - `validator:amount` pairs with exact SOL amounts
+pub fn format_funding_pairs(transfers: &[(Pubkey, u64)]) -> String {
+    transfers
+        .iter()
+        .map(|(validator_id, lamports)| format!("{}:{}", validator_id, format_sol(*lamports)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Packs system transfers to deposit PDAs into as few transactions as fit in a packet
+///
+/// Transfers keep their order; a transaction is closed as soon as the next transfer would break the
+/// packet size or account limit (see `packer::pack_instructions`).
+///
+/// # Arguments
+/// * `accounts` - Funding wallet and fee payer (nonces are not used)
+/// * `transfers` - Validators and amounts in lamports
+/// * `priority_fee` - Priority fee in micro-lamports per compute unit (optional, added to every transaction)
+/// * `blockhash` - Recent blockhash
+///
+/// # Returns
+/// * `Result<Vec<Message>, Error>` - Unsigned messages in transfer order, or error
+pub fn pack_transfers(
+    accounts: &FundingAccounts,
+    transfers: &[(Pubkey, u64)],
+    priority_fee: Option<u64>,
+    blockhash: &Hash,
+) -> Result<Vec<Message>, Error> {
+    let prefix = priority_fee.map(compute_budget_instructions).unwrap_or_default();
+    let instructions = build_fund_instructions(&accounts.funder, transfers);
+    pack_instructions(&accounts.fee_payer, &prefix, &instructions, blockhash, &PackLimits::default())
+}
+
+/// Checked transfers to several validator PDAs, packed into transactions that are not signed yet
+pub struct PreparedFundingBatch {
+    /// Funding wallet the lamports are transferred from
+    pub payer: Pubkey,
+    /// Validators and amounts in lamports, in input order
+    pub transfers: Vec<(Pubkey, u64)>,
+    /// Number of transactions the transfers are packed into
+    pub transaction_count: usize,
+    /// Sum of the transferred amounts in lamports
+    pub total_lamports: u64,
+    /// Sum of the transaction fees in lamports
+    pub fee_lamports: u64,
+    /// Funding wallet balance after every transfer (and the fees, if it pays them)
+    pub payer_balance_after: u64,
+    /// Soft issues found by the health policy and the pre-flight checks, prefixed with the validator
+    pub warnings: Vec<Warning>,
+    /// Current epoch, if a budget is configured
+    pub epoch: Option<u64>,
+    pool: SignerPool,
+    messages: Vec<Message>,
+}
+
+/// Runs the checks of `prepare_pda_funding` for every validator and packs the transfers
+///
+/// Any validator failing the policy or the pre-flight checks cancels the whole batch before anything is signed.
+///
+/// # Arguments
+/// * `transfers` - Validators and amounts in lamports; a validator may appear only once
+/// * `signers` - Funding wallet, fee payer and additional signers
+/// * `policy` - Health policy every validator must satisfy (only legacy transactions are packed)
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<PreparedFundingBatch, Error>` - Packed batch with its totals, or error
+pub async fn prepare_funding_batch(
+    transfers: &[(Pubkey, u64)],
+    signers: &FundingSigners,
+    policy: &FundingPolicy,
+    rpc: &dyn SolanaRpc,
+) -> Result<PreparedFundingBatch, Error> {
+    if transfers.is_empty() {
+        return Err(Error::InvalidInput("No transfers to send".to_string()));
+    }
+    let mut seen = HashSet::new();
+    if let Some((duplicate, _)) = transfers.iter().find(|(validator_id, _)| !seen.insert(*validator_id)) {
+        return Err(Error::InvalidInput(format!("Validator {} is listed more than once", duplicate)));
+    }
+    let total_lamports = transfers
+        .iter()
+        .try_fold(0u64, |total, (_, lamports)| total.checked_add(*lamports))
+        .ok_or_else(|| Error::InvalidInput("The amounts of the transfers add up to more lamports than can be sent".to_string()))?;
+
+    let mut warnings = Vec::new();
+    for (validator_id, _) in transfers {
+        let reasons = check_funding_policy(validator_id, policy, rpc).await.map_err(|e| match e {
+            Error::StrictWarning(message) => Error::StrictWarning(format!("Validator {}: {}", validator_id, message)),
+            Error::NotInGossip(message) => Error::NotInGossip(format!("Validator {}: {}", validator_id, message)),
+            e => Error::FundingCancelled(format!("Validator {}: {}", validator_id, e)),
+        })?;
+        warnings.extend(reasons.into_iter().map(|reason| prefixed(validator_id, reason)));
+    }
+
+    let (pool, accounts) = load_required_signers(signers, None)?;
+    let blockhash = rpc.get_latest_blockhash().await
+        .map_err(|e| Error::from_client_error("Failed to get recent blockhash", &e))?;
+
+    if let Some(priority_fee) = policy.priority_fee {
+        let mut fee_accounts = vec![accounts.funder];
+        fee_accounts.extend(transfers.iter().map(|(validator_id, _)| generate_deposit_pda(validator_id)));
+        let stats = priority::recent_priority_fees(&fee_accounts, rpc).await?;
+        let outlier: Vec<Warning> = priority::check_priority_fee(priority_fee, stats.as_ref(), policy.force)?.into_iter().collect();
+        if policy.strict {
+            strict_check(&outlier)?;
+        }
+        warnings.extend(outlier);
+    }
+
+    let messages = pack_transfers(&accounts, transfers, policy.priority_fee, &blockhash)?;
+    let mut fee_lamports = 0u64;
+    for message in &messages {
+        fee_lamports += rpc.get_fee_for_message(message).await
+            .map_err(|e| Error::from_client_error("Failed to estimate transaction fee", &e))?;
+    }
+
+    let payer_account = rpc.get_account_with_context(&accounts.funder).await
+        .map_err(|e| Error::from_client_error("Failed to get payer account", &e))?
+        .value;
+    let (payer_balance, rent_reserve_lamports) = match payer_account {
+        Some(account) if account.owner == solana_system_interface::program::ID => {
+            let rent_reserve = rpc.get_minimum_balance_for_rent_exemption(account.data.len()).await
+                .map_err(|e| Error::from_client_error("Failed to get rent-exempt minimum", &e))?;
+            (account.lamports, rent_reserve)
+        }
+        Some(account) => (account.lamports, 0),
+        None => (0, 0),
+    };
+
+    // The wallet reserve is not trimmed into here: cutting one transfer of a batch would be arbitrary
+    let funder_fee_lamports = if accounts.fee_payer == accounts.funder { fee_lamports } else { 0 };
+    if accounts.fee_payer != accounts.funder {
+        let fee_payer_balance = rpc.get_balance(&accounts.fee_payer).await
+            .map_err(|e| Error::from_client_error("Failed to get fee payer balance", &e))?;
+        check_funding_sufficiency(fee_payer_balance, 0, fee_lamports, 0)?;
+    }
+    check_funding_sufficiency(payer_balance, total_lamports, funder_fee_lamports, rent_reserve_lamports.max(policy.reserve_lamports))?;
+
+    // The global caps apply to the batch as a whole
+    let (epoch, over_budget) = enforce_budget(policy, transfers, rpc).await?;
+    let mut preflight_warnings = over_budget;
+    for (validator_id, lamports) in transfers {
+        let deposit_key = generate_deposit_pda(validator_id);
+        let deposit_account = get_deposit_account(&deposit_key, rpc).await?;
+        let rent_minimum = get_rent_exempt_minimum(&deposit_key, rpc).await?;
+        let checked = check_deposit_preflight(&deposit_key, &deposit_account, *lamports, rent_minimum, policy.force)
+            .map_err(|e| Error::FundingCancelled(format!("Validator {}: {}", validator_id, e)))?;
+        preflight_warnings.extend(checked.into_iter().map(|warning| prefixed(validator_id, warning)));
+    }
+    let pdas: Vec<Pubkey> = transfers.iter().map(|(validator_id, _)| generate_deposit_pda(validator_id)).collect();
+    preflight_warnings.extend(pending_funding_warnings(&accounts.funder, &pdas, rpc).await);
+    if policy.strict {
+        strict_check(&preflight_warnings)?;
+    }
+    warnings.extend(preflight_warnings);
+
+    Ok(PreparedFundingBatch {
+        payer: accounts.funder,
+        transfers: transfers.to_vec(),
+        transaction_count: messages.len(),
+        total_lamports,
+        fee_lamports,
+        payer_balance_after: payer_balance - total_lamports - funder_fee_lamports,
+        warnings,
+        epoch,
+        pool,
+        messages,
+    })
+}
+
+/// Prefixes a warning with the validator it is about
+fn prefixed(validator_id: &Pubkey, warning: Warning) -> Warning {
+    Warning::new(&warning.code, format!("Validator {}: {}", validator_id, warning.message))
+}
+
+/// Signs and sends every transaction of a prepared batch and waits for their confirmation
+///
+/// Transactions are independent: one that fails does not stop the others. Results are summarized
+/// per validator with `batch::attribute_outcomes`.
+///
+/// # Arguments
+/// * `prepared` - Batch from `prepare_funding_batch`
+/// * `timeout` - Maximum time to wait for each confirmation
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Vec<SentTransaction>, Error>` - Every transaction with its result, or `Error::Keypair` if signing failed (nothing was sent)
+pub async fn send_funding_batch(prepared: &PreparedFundingBatch, timeout: Duration, rpc: &dyn SolanaRpc) -> Result<Vec<SentTransaction>, Error> {
+    send_transactions(prepared, timeout, None, rpc).await
+}
+
+/// Like `send_funding_batch`, keeping a state file (`--state`) up to date around every transaction
+///
+/// The transfers of a transaction are saved as unconfirmed, with its signature, before it is sent, so a
+/// run that dies leaves at most one transaction whose result `resolve_unconfirmed` has to look up.
+/// The state is saved once before anything is sent; later save failures are logged and do not stop the run.
+///
+/// # Arguments
+/// * `prepared` - Batch from `prepare_funding_batch`
+/// * `timeout` - Maximum time to wait for each confirmation
+/// * `state` - State of the run; transfers of the batch it does not list yet are added as pending
+/// * `path` - State file
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Vec<SentTransaction>, Error>` - Every transaction with its result, `Error::Io` if the state
+///   could not be saved or `Error::Keypair` if signing failed (nothing was sent)
+pub async fn send_funding_batch_with_state(
+    prepared: &PreparedFundingBatch,
+    timeout: Duration,
+    state: &mut FundingRunState,
+    path: &Path,
+    rpc: &dyn SolanaRpc,
+) -> Result<Vec<SentTransaction>, Error> {
+    state.track(&prepared.transfers);
+    state.save(path)?;
+    send_transactions(prepared, timeout, Some((state, path)), rpc).await
+}
+
+async fn send_transactions(
+    prepared: &PreparedFundingBatch,
+    timeout: Duration,
+    mut progress: Option<(&mut FundingRunState, &Path)>,
+    rpc: &dyn SolanaRpc,
+) -> Result<Vec<SentTransaction>, Error> {
+    let mut transactions = Vec::with_capacity(prepared.messages.len());
+    for message in &prepared.messages {
+        let mut transaction = Transaction::new_unsigned(message.clone());
+        prepared.pool.sign_partial(&mut transaction)?;
+        transactions.push(transaction);
+    }
+
+    let mut sent = Vec::with_capacity(transactions.len());
+    let bar = progress::bar(transactions.len() as u64, i18n::text("progress-sending-transactions"));
+    for transaction in transactions {
+        if let Some((state, path)) = progress.as_mut() {
+            let sending = SentTransaction { transaction: transaction.clone(), result: TransactionResult::Unconfirmed(SENDING.to_string()) };
+            state.record(&sending);
+            if let Err(e) = state.save(path) {
+                tracing::warn!("Funding state not saved: {}", e);
+            }
+        }
+        let result = match rpc.send_transaction(&transaction).await {
+            // A rejected transaction did not land; any other send error may have reached the leader
+            Err(e) => match Error::from_client_error("Failed to send transaction", &e) {
+                Error::TransactionFailed(message) => TransactionResult::Failed(message),
+                e => TransactionResult::Unconfirmed(e.to_string()),
+            },
+            Ok(signature) => match wait_for_confirmation(&signature.to_string(), timeout, rpc).await {
+                Ok(()) => TransactionResult::Confirmed,
+                Err(Error::TransactionFailed(message)) => TransactionResult::Failed(message),
+                Err(e) => TransactionResult::Unconfirmed(e.to_string()),
+            },
+        };
+        let sent_transaction = SentTransaction { transaction, result };
+        if let Some((state, path)) = progress.as_mut() {
+            state.record(&sent_transaction);
+            if let Err(e) = state.save(path) {
+                tracing::warn!("Funding state not saved: {}", e);
+            }
+        }
+        sent.push(sent_transaction);
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+    Ok(sent)
+}
+
+/// Progress of a packed funding run, kept in the file of `--state` and `--resume`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FundingRunState {
+    /// Funding wallet of the run
+    pub payer: String,
+    /// Unix timestamp of the last save
+    pub updated_at: u64,
+    /// One row per validator with its status and signature, in input order
+    pub transfers: Vec<TransferOutcome>,
+}
+
+impl FundingRunState {
+    /// Creates the state of a new run with every transfer pending
+    ///
+    /// # Arguments
+    /// * `payer` - Funding wallet
+    /// * `transfers` - Validators and amounts in lamports
+    pub fn new(payer: &Pubkey, transfers: &[(Pubkey, u64)]) -> Self {
+        let mut state = FundingRunState { payer: payer.to_string(), updated_at: unix_timestamp(), transfers: Vec::new() };
+        state.track(transfers);
+        state
+    }
+
+    /// Loads a state file
+    ///
+    /// # Arguments
+    /// * `path` - Path to the state file
+    ///
+    /// # Returns
+    /// * `Result<FundingRunState, Error>` - State, or `Error::Io` if it is missing or malformed
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::Io(format!("Failed to read funding state {}: {}", path.display(), e)))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| Error::Io(format!("Failed to parse funding state {}: {}", path.display(), e)))
+    }
+
+    /// Saves the state file atomically and updates `updated_at`
+    ///
+    /// # Arguments
+    /// * `path` - Path to the state file
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success, or `Error::Io` if the file could not be written
+    pub fn save(&mut self, path: &Path) -> Result<(), Error> {
+        self.updated_at = unix_timestamp();
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Io(format!("Failed to serialize funding state: {}", e)))?;
+        write_atomic(path, contents.as_bytes())
+    }
+
+    /// Returns the transfers a resumed run has to send: pending and failed ones
+    ///
+    /// Confirmed transfers landed and unconfirmed ones may still land, so both are skipped.
+    pub fn remaining(&self) -> Vec<(Pubkey, u64)> {
+        self.transfers
+            .iter()
+            .filter(|row| matches!(row.status, TransferStatus::Pending | TransferStatus::Failed))
+            .filter_map(|row| Some((row.validator.parse::<Pubkey>().ok()?, row.amount_lamports)))
+            .collect()
+    }
+
+    /// Returns the number of rows with a status
+    pub fn count(&self, status: TransferStatus) -> usize {
+        self.transfers.iter().filter(|row| row.status == status).count()
+    }
+
+    /// Adds transfers not listed yet as pending and updates the amount of the others
+    fn track(&mut self, transfers: &[(Pubkey, u64)]) {
+        for (validator_id, lamports) in transfers {
+            let validator = validator_id.to_string();
+            match self.transfers.iter_mut().find(|row| row.validator == validator) {
+                Some(row) => row.amount_lamports = *lamports,
+                None => self.transfers.push(TransferOutcome {
+                    validator,
+                    pda: generate_deposit_pda(validator_id).to_string(),
+                    amount_lamports: *lamports,
+                    signature: None,
+                    status: TransferStatus::Pending,
+                    error: None,
+                }),
+            }
+        }
+    }
+
+    /// Applies the result of a transaction to the rows of the PDAs it transfers to
+    fn record(&mut self, sent: &SentTransaction) {
+        let (signature, status, error) = transfer_status(sent);
+        for (recipient, _) in transaction_transfers(&sent.transaction) {
+            let pda = recipient.to_string();
+            if let Some(row) = self.transfers.iter_mut().find(|row| row.pda == pda) {
+                row.signature = signature.clone();
+                row.status = status;
+                row.error = error.clone();
+            }
+        }
+    }
+}
+
+/// Looks up the transactions a previous run left unconfirmed (`--resume`)
+///
+/// A transaction confirmed since marks its transfers confirmed and a failed one marks them failed,
+/// so they are sent again. Transfers whose signature the node does not know stay unconfirmed.
+///
+/// # Arguments
+/// * `state` - State loaded from the file
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<(), Error>` - Success, or error if the statuses could not be fetched
+pub async fn resolve_unconfirmed(state: &mut FundingRunState, rpc: &dyn SolanaRpc) -> Result<(), Error> {
+    let mut signatures: Vec<Signature> = state
+        .transfers
+        .iter()
+        .filter(|row| row.status == TransferStatus::Unconfirmed)
+        .filter_map(|row| row.signature.as_deref()?.parse().ok())
+        .collect();
+    signatures.sort();
+    signatures.dedup();
+
+    for chunk in signatures.chunks(MAX_SIGNATURE_STATUSES) {
+        let statuses = rpc.get_signature_statuses(chunk).await
+            .map_err(|e| Error::from_client_error("Failed to get transaction statuses", &e))?
+            .value;
+        for (signature, status) in chunk.iter().zip(statuses) {
+            let (status, error) = match status {
+                Some(status) => match &status.err {
+                    Some(e) => (TransferStatus::Failed, Some(format!("Transaction {} failed: {}", signature, e))),
+                    None if status.satisfies_commitment(CommitmentConfig::confirmed()) => (TransferStatus::Confirmed, None),
+                    None => continue,
+                },
+                None => continue,
+            };
+            let signature = signature.to_string();
+            for row in state.transfers.iter_mut().filter(|row| row.signature.as_deref() == Some(signature.as_str())) {
+                row.status = status;
+                row.error = error.clone();
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch::{attribute_outcomes, transaction_transfers, TransferStatus};
+    use crate::rpc::mock::{MockRpc, MOCK_FEE_PER_SIGNATURE};
+    use crate::packer::{signed_size, MAX_TRANSACTION_SIZE};
+    use solana_sdk::signature::{Keypair, Signer};
+
+    const SOL: u64 = 1_000_000_000;
+
+    #[test]
+    fn test_parse_funding_pairs() {
+        let validator = Pubkey::new_unique().to_string();
+        let pairs = parse_funding_pairs(&format!("{}:1.5, main:2000lamports", validator)).unwrap();
+        assert_eq!(pairs, vec![(validator.clone(), 1_500_000_000), ("main".to_string(), 2_000)]);
+
+        assert!(parse_funding_pairs(&validator).is_err());
+        assert!(parse_funding_pairs(&format!("{}:ALL", validator)).unwrap_err().to_string().contains("ALL is not supported"));
+        assert!(parse_funding_pairs(&format!("{}:0", validator)).is_err());
+        assert!(parse_funding_pairs(":1").is_err());
+
+        let transfers = vec![(Pubkey::new_unique(), 1_500_000_000), (Pubkey::new_unique(), 1)];
+        let roundtrip = parse_funding_pairs(&format_funding_pairs(&transfers)).unwrap();
+        let expected: Vec<(String, u64)> = transfers.iter().map(|(validator_id, lamports)| (validator_id.to_string(), *lamports)).collect();
+        assert_eq!(roundtrip, expected);
+    }
+
+    #[test]
+    fn test_read_funding_file() {
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_fund_many_{}.csv", std::process::id()));
+        fs::write(&path, "validator,amount\n# main validators\nmain,1.5\n\nbackup, 2\n").unwrap();
+        let transfers = read_funding_file(&path).unwrap();
+        assert_eq!(transfers, vec![("main".to_string(), 1_500_000_000), ("backup".to_string(), 2 * SOL)]);
+
+        fs::write(&path, "main,1.5\nbackup\n").unwrap();
+        let error = read_funding_file(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(error.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_pack_transfers_fits_packets() {
+        let funder = Pubkey::new_unique();
+        let accounts = FundingAccounts { funder, fee_payer: funder, nonce: None };
+        let transfers: Vec<(Pubkey, u64)> = (0..60).map(|index| (Pubkey::new_unique(), index + 1)).collect();
+
+        let blockhash = Hash::new_unique();
+        let messages = pack_transfers(&accounts, &transfers, Some(5_000), &blockhash).unwrap();
+        assert!(messages.len() > 1);
+        let mut packed = Vec::new();
+        for message in &messages {
+            assert!(signed_size(message) <= MAX_TRANSACTION_SIZE);
+            packed.extend(transaction_transfers(&Transaction::new_unsigned(message.clone())));
+        }
+        // Every transfer is packed once, in order, to the validator's PDA
+        let expected: Vec<(Pubkey, u64)> = transfers.iter().map(|(validator_id, lamports)| (generate_deposit_pda(validator_id), *lamports)).collect();
+        assert_eq!(packed, expected);
+
+        // Transactions are only closed when full
+        let first = &transfers[..transaction_transfers(&Transaction::new_unsigned(messages[0].clone())).len() + 1];
+        let mut instructions = compute_budget_instructions(5_000);
+        instructions.extend(build_fund_instructions(&funder, first));
+        assert!(signed_size(&Message::new_with_blockhash(&instructions, Some(&funder), &blockhash)) > MAX_TRANSACTION_SIZE);
+        assert_eq!(pack_transfers(&accounts, &transfers[..2], None, &blockhash).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fund_many_sends_packed_transactions() {
+        let payer = Keypair::new();
+        let keypair_path = std::env::temp_dir().join(format!("dz_validator_pda_fund_many_{}.json", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&payer, &keypair_path).unwrap();
+        let keypair_path = keypair_path.to_string_lossy().to_string();
+        let transfers: Vec<(Pubkey, u64)> = (0..30).map(|_| (Pubkey::new_unique(), SOL / 10)).collect();
+        let mut rpc = MockRpc::new().with_wallet(payer.pubkey(), 10 * SOL);
+        for (validator_id, _) in &transfers {
+            rpc = rpc.with_gossip_node(*validator_id);
+        }
+
+        let prepared = prepare_funding_batch(&transfers, &FundingSigners::new(&keypair_path), &FundingPolicy::default(), &rpc).await.unwrap();
+        assert!(prepared.transaction_count > 1);
+        assert_eq!(prepared.total_lamports, 3 * SOL);
+        assert_eq!(prepared.fee_lamports, MOCK_FEE_PER_SIGNATURE * prepared.transaction_count as u64);
+        assert_eq!(prepared.payer_balance_after, 7 * SOL - prepared.fee_lamports);
+        // Every validator lacks a vote account, and the warning names it
+        assert_eq!(prepared.warnings.len(), transfers.len());
+        assert!(prepared.warnings[0].message.starts_with(&format!("Validator {}", transfers[0].0)));
+
+        let sent = send_funding_batch(&prepared, Duration::from_secs(5), &rpc).await.unwrap();
+        let transaction_count = prepared.transaction_count;
+        assert_eq!(rpc.sent_transactions().len(), transaction_count);
+        let validators: Vec<Pubkey> = transfers.iter().map(|(validator_id, _)| *validator_id).collect();
+        let outcomes = attribute_outcomes(&validators, &sent);
+        assert!(outcomes.iter().all(|outcome| outcome.status == TransferStatus::Confirmed));
+
+        // A duplicate validator, one missing from gossip or an overflowing total cancels the batch before anything is sent
+        let duplicate = vec![transfers[0], transfers[0]];
+        let error = prepare_funding_batch(&duplicate, &FundingSigners::new(&keypair_path), &FundingPolicy::default(), &rpc).await.err().unwrap();
+        assert!(error.is_user_error());
+        let unknown = vec![transfers[0], (Pubkey::new_unique(), SOL)];
+        let error = prepare_funding_batch(&unknown, &FundingSigners::new(&keypair_path), &FundingPolicy::default(), &rpc).await.err().unwrap();
+        assert!(matches!(error, Error::NotInGossip(_)), "{:?}", error);
+        // Amounts adding up past u64::MAX are refused instead of wrapping to a small total
+        let overflowing = vec![(transfers[0].0, u64::MAX), (transfers[1].0, 2)];
+        let error = prepare_funding_batch(&overflowing, &FundingSigners::new(&keypair_path), &FundingPolicy::default(), &rpc).await.err().unwrap();
+        assert!(matches!(error, Error::InvalidInput(_)), "{:?}", error);
+        assert_eq!(rpc.sent_transactions().len(), transaction_count);
+        fs::remove_file(&keypair_path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_funding_state_resume() {
+        let payer = Keypair::new();
+        let keypair_path = std::env::temp_dir().join(format!("dz_validator_pda_fund_state_{}.json", std::process::id()));
+        let state_path = std::env::temp_dir().join(format!("dz_validator_pda_fund_state_{}.run.json", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&payer, &keypair_path).unwrap();
+        let keypair_path = keypair_path.to_string_lossy().to_string();
+        let transfers: Vec<(Pubkey, u64)> = (0..4).map(|_| (Pubkey::new_unique(), SOL / 10)).collect();
+        let mut rpc = MockRpc::new().with_wallet(payer.pubkey(), 10 * SOL);
+        for (validator_id, _) in &transfers {
+            rpc = rpc.with_gossip_node(*validator_id);
+        }
+
+        // The first run only got to the first two validators
+        let prepared = prepare_funding_batch(&transfers[..2], &FundingSigners::new(&keypair_path), &FundingPolicy::default(), &rpc).await.unwrap();
+        let mut state = FundingRunState::new(&prepared.payer, &transfers);
+        send_funding_batch_with_state(&prepared, Duration::from_secs(5), &mut state, &state_path, &rpc).await.unwrap();
+        let mut state = FundingRunState::load(&state_path).unwrap();
+        assert_eq!(state.count(TransferStatus::Confirmed), 2);
+        assert_eq!(state.remaining(), transfers[2..].to_vec());
+
+        // A transaction that died between saving and its confirmation is looked up on resume
+        state.transfers[0].status = TransferStatus::Unconfirmed;
+        state.transfers[2].status = TransferStatus::Unconfirmed;
+        state.transfers[2].signature = Some(Signature::new_unique().to_string());
+        resolve_unconfirmed(&mut state, &rpc).await.unwrap();
+        assert_eq!(state.transfers[0].status, TransferStatus::Confirmed);
+        assert_eq!(state.transfers[2].status, TransferStatus::Unconfirmed);
+        assert_eq!(state.remaining(), transfers[3..].to_vec());
+
+        let prepared = prepare_funding_batch(&state.remaining(), &FundingSigners::new(&keypair_path), &FundingPolicy::default(), &rpc).await.unwrap();
+        send_funding_batch_with_state(&prepared, Duration::from_secs(5), &mut state, &state_path, &rpc).await.unwrap();
+        let state = FundingRunState::load(&state_path).unwrap();
+        fs::remove_file(&state_path).ok();
+        fs::remove_file(&keypair_path).ok();
+        assert_eq!(state.count(TransferStatus::Confirmed), 3);
+        assert!(state.remaining().is_empty());
+        assert_eq!(state.transfers.len(), transfers.len());
+    }
+}
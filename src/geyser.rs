@@ -0,0 +1,250 @@
+use crate::deposit::DepositAccount;
+use crate::Error;
+use solana_sdk::pubkey::Pubkey;
+
+/// Environment variable with the `x-token` of the Geyser endpoint, if the provider needs one
+pub const GEYSER_TOKEN_ENV: &str = "DZ_GEYSER_TOKEN";
+
+/// Source of gossip presence and PDA account updates (`--backend`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// `getClusterNodes` and polling over JSON-RPC
+    #[default]
+    Rpc,
+    /// Yellowstone gRPC (Geyser) streams, with JSON-RPC for everything else
+    Geyser,
+}
+
+impl Backend {
+    /// Parses `--backend`
+    ///
+    /// # Arguments
+    /// * `value` - `rpc` or `geyser`
+    ///
+    /// # Returns
+    /// * `Result<Backend, Error>` - Backend, or `Error::InvalidInput` (also for `geyser` in a build without the `geyser` feature)
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "rpc" => Ok(Backend::Rpc),
+            "geyser" if cfg!(feature = "geyser") => Ok(Backend::Geyser),
+            "geyser" => Err(Error::InvalidInput(
+                "--backend geyser needs a build with the geyser feature (cargo build --features geyser)".to_string(),
+            )),
+            _ => Err(Error::InvalidInput(format!("Invalid backend '{}': expected rpc or geyser", value))),
+        }
+    }
+}
+
+#[cfg(feature = "geyser")]
+mod client {
+    use super::GEYSER_TOKEN_ENV;
+    use crate::deposit::{DepositAccount, DepositAccountState};
+    use crate::Error;
+    use futures_util::stream::{BoxStream, StreamExt};
+    use solana_commitment_config::{CommitmentConfig, CommitmentLevel as RpcCommitment};
+    use solana_sdk::account::Account;
+    use solana_sdk::pubkey::Pubkey;
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
+    use yellowstone_grpc_proto::prelude::{
+        subscribe_update::UpdateOneof, subscribe_update_gossip::UpdateOneof as GossipUpdate, CommitmentLevel, SubscribeRequest,
+        SubscribeRequestFilterAccounts, SubscribeUpdate, SubscribeUpdateAccount,
+    };
+
+    /// Time to connect to the Geyser endpoint
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Connects to a Geyser endpoint, with TLS for `https://` and the token of `DZ_GEYSER_TOKEN`
+    async fn connect(endpoint: &str) -> Result<GeyserGrpcClient, Error> {
+        let failed = |e: &dyn std::fmt::Display| Error::RpcUnavailable(format!("Failed to connect to Geyser endpoint {}: {}", endpoint, e));
+        let mut builder = GeyserGrpcClient::build_from_shared(endpoint.to_string())
+            .map_err(|e| Error::InvalidInput(format!("Invalid --geyser-endpoint '{}': {}", endpoint, e)))?
+            .x_token(std::env::var(GEYSER_TOKEN_ENV).ok())
+            .map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", GEYSER_TOKEN_ENV, e)))?
+            .connect_timeout(CONNECT_TIMEOUT);
+        if endpoint.starts_with("https://") {
+            builder = builder.tls_config(ClientTlsConfig::new().with_native_roots()).map_err(|e| failed(&e))?;
+        }
+        builder.connect().await.map_err(|e| failed(&e))
+    }
+
+    pub async fn gossip_nodes(endpoint: &str) -> Result<Vec<Pubkey>, Error> {
+        let mut client = connect(endpoint).await?;
+        let mut updates = client
+            .subscribe_gossip()
+            .await
+            .map_err(|e| Error::Rpc(format!("Failed to subscribe to Geyser gossip: {}", e)))?;
+        // The server sends a full copy of its contact info table first; later updates are not needed
+        while let Some(update) = updates.next().await {
+            let update = update.map_err(|e| Error::Rpc(format!("Geyser gossip stream failed: {}", e)))?;
+            if let Some(GossipUpdate::Snapshot(topology)) = update.update_oneof {
+                return Ok(topology.nodes.iter().filter_map(|node| Pubkey::try_from(node.pubkey.as_slice()).ok()).collect());
+            }
+        }
+        Err(Error::Rpc("Geyser gossip stream ended before the node snapshot".to_string()))
+    }
+
+    pub struct Updates(BoxStream<'static, Result<DepositAccount, Error>>);
+
+    impl Updates {
+        pub async fn next(&mut self) -> Option<Result<DepositAccount, Error>> {
+            self.0.next().await
+        }
+    }
+
+    pub async fn subscribe_account(endpoint: &str, pubkey: &Pubkey, commitment: CommitmentConfig) -> Result<Updates, Error> {
+        let mut client = connect(endpoint).await?;
+        let commitment = match commitment.commitment {
+            RpcCommitment::Processed => CommitmentLevel::Processed,
+            RpcCommitment::Confirmed => CommitmentLevel::Confirmed,
+            RpcCommitment::Finalized => CommitmentLevel::Finalized,
+        };
+        let request = SubscribeRequest {
+            accounts: HashMap::from([(
+                "pda".to_string(),
+                SubscribeRequestFilterAccounts { account: vec![pubkey.to_string()], ..Default::default() },
+            )]),
+            commitment: Some(commitment as i32),
+            ..Default::default()
+        };
+        let stream = client
+            .subscribe_once(request)
+            .await
+            .map_err(|e| Error::Rpc(format!("Failed to subscribe to account {}: {}", pubkey, e)))?;
+        let updates = stream.filter_map(|update| async move {
+            match update {
+                Ok(update) => deposit_account(update).map(Ok),
+                Err(e) => Some(Err(Error::Rpc(format!("Geyser account stream failed: {}", e)))),
+            }
+        });
+        Ok(Updates(updates.boxed()))
+    }
+
+    /// Converts an account update to the deposit account it describes (None for pings and other updates)
+    fn deposit_account(update: SubscribeUpdate) -> Option<DepositAccount> {
+        let Some(UpdateOneof::Account(SubscribeUpdateAccount { account: Some(info), slot, .. })) = update.update_oneof else {
+            return None;
+        };
+        // A closed account is reported with zero lamports and no data
+        let account = (info.lamports > 0 || !info.data.is_empty()).then(|| Account {
+            lamports: info.lamports,
+            data: info.data,
+            owner: Pubkey::try_from(info.owner.as_slice()).unwrap_or_default(),
+            executable: info.executable,
+            rent_epoch: info.rent_epoch,
+        });
+        Some(DepositAccount {
+            state: DepositAccountState::from_account(account.as_ref()),
+            lamports: account.as_ref().map(|account| account.lamports).unwrap_or(0),
+            slot,
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use yellowstone_grpc_proto::prelude::{SubscribeUpdateAccountInfo, SubscribeUpdatePing};
+
+        #[test]
+        fn test_deposit_account_from_update() {
+            let owner = Pubkey::new_unique();
+            let update = |lamports: u64, owner: &Pubkey| SubscribeUpdate {
+                update_oneof: Some(UpdateOneof::Account(SubscribeUpdateAccount {
+                    account: Some(SubscribeUpdateAccountInfo { lamports, owner: owner.to_bytes().to_vec(), ..Default::default() }),
+                    slot: 42,
+                    ..Default::default()
+                })),
+                ..Default::default()
+            };
+
+            let account = deposit_account(update(5_000_000, &owner)).unwrap();
+            assert_eq!((account.lamports, account.slot), (5_000_000, 42));
+            assert_eq!(account.state, DepositAccountState::from_account(Some(&Account { lamports: 5_000_000, owner, ..Account::default() })));
+            assert_eq!(deposit_account(update(0, &Pubkey::default())).unwrap().state, DepositAccountState::Missing);
+            let ping = SubscribeUpdate { update_oneof: Some(UpdateOneof::Ping(SubscribeUpdatePing {})), ..Default::default() };
+            assert!(deposit_account(ping).is_none());
+        }
+    }
+}
+
+#[cfg(not(feature = "geyser"))]
+mod client {
+    use crate::deposit::DepositAccount;
+    use crate::Error;
+    use solana_commitment_config::CommitmentConfig;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn unavailable() -> Error {
+        Error::InvalidInput("The Geyser backend needs a build with the geyser feature (cargo build --features geyser)".to_string())
+    }
+
+    pub async fn gossip_nodes(_endpoint: &str) -> Result<Vec<Pubkey>, Error> {
+        Err(unavailable())
+    }
+
+    pub enum Updates {}
+
+    impl Updates {
+        pub async fn next(&mut self) -> Option<Result<DepositAccount, Error>> {
+            match *self {}
+        }
+    }
+
+    pub async fn subscribe_account(_endpoint: &str, _pubkey: &Pubkey, _commitment: CommitmentConfig) -> Result<Updates, Error> {
+        Err(unavailable())
+    }
+}
+
+/// Stream of changes to one account (`pda-watch --backend geyser`)
+pub struct AccountUpdates(client::Updates);
+
+impl AccountUpdates {
+    /// Waits for the next change of the account
+    ///
+    /// # Returns
+    /// * `Option<Result<DepositAccount, Error>>` - New state of the account, an error of the stream, or None once it ended
+    pub async fn next(&mut self) -> Option<Result<DepositAccount, Error>> {
+        self.0.next().await
+    }
+}
+
+/// Returns the identity pubkeys of all nodes in gossip from a Geyser endpoint
+///
+/// Subscribes to the gossip stream and takes the snapshot of the contact info table the server sends first,
+/// so the list is the same as from `getClusterNodes` without its cost and rate limits.
+///
+/// # Arguments
+/// * `endpoint` - Yellowstone gRPC endpoint (`--geyser-endpoint`)
+///
+/// # Returns
+/// * `Result<Vec<Pubkey>, Error>` - Node identities, `Error::RpcUnavailable` if the endpoint cannot be reached,
+///   or `Error::Rpc` if the stream fails
+pub async fn gossip_nodes(endpoint: &str) -> Result<Vec<Pubkey>, Error> {
+    client::gossip_nodes(endpoint).await
+}
+
+/// Subscribes to the changes of an account
+///
+/// # Arguments
+/// * `endpoint` - Yellowstone gRPC endpoint (`--geyser-endpoint`)
+/// * `pubkey` - Account to follow
+/// * `commitment` - Commitment of the reported changes
+///
+/// # Returns
+/// * `Result<AccountUpdates, Error>` - Stream of account states, or error if the subscription fails
+pub async fn subscribe_account(endpoint: &str, pubkey: &Pubkey, commitment: solana_commitment_config::CommitmentConfig) -> Result<AccountUpdates, Error> {
+    client::subscribe_account(endpoint, pubkey, commitment).await.map(AccountUpdates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_backend() {
+        assert_eq!(Backend::parse("RPC").unwrap(), Backend::Rpc);
+        assert!(Backend::parse("websocket").unwrap_err().to_string().contains("expected rpc or geyser"));
+        assert_eq!(Backend::parse("geyser").is_ok(), cfg!(feature = "geyser"));
+    }
+}
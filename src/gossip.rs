@@ -0,0 +1,270 @@
+use crate::config::{default_data_dir, write_atomic};
+use crate::i18n;
+use crate::notify::NotifyEvent;
+use crate::progress;
+use crate::rpc::gossip_cache::GossipCacheFile;
+use crate::rpc::SolanaRpc;
+use crate::stats::StatsWindow;
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Polling interval of `gossip-monitor` in seconds when `--interval` is not given
+pub const DEFAULT_MONITOR_INTERVAL_SECS: u64 = 60;
+
+/// Time a validator may be missing from gossip before `gossip-monitor` notifies, when `--grace` is not given
+pub const DEFAULT_GOSSIP_GRACE: &str = "5m";
+
+/// Validators that joined or left gossip between two node lists (`gossip-diff`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GossipDiff {
+    /// Nodes in the new list only, sorted
+    pub appeared: Vec<String>,
+    /// Nodes in the old list only, sorted
+    pub disappeared: Vec<String>,
+    /// Nodes in both lists
+    pub unchanged: usize,
+}
+
+impl GossipDiff {
+    /// Returns true if both lists hold the same nodes
+    pub fn is_empty(&self) -> bool {
+        self.appeared.is_empty() && self.disappeared.is_empty()
+    }
+}
+
+/// Fetches the cluster node list for a snapshot (`gossip-snapshot`)
+///
+/// The list is always fetched from the endpoint, bypassing the gossip cache. Snapshots use the format
+/// of `--gossip-cache-file`, so one can also seed the cache of a later run.
+///
+/// # Arguments
+/// * `rpc` - RPC client
+/// * `fetched_at` - Unix timestamp recorded in the snapshot
+///
+/// # Returns
+/// * `Result<GossipCacheFile, Error>` - Endpoint, fetch time and sorted node identities, or error
+pub async fn take_snapshot(rpc: &dyn SolanaRpc, fetched_at: u64) -> Result<GossipCacheFile, Error> {
+    let spinner = progress::spinner(i18n::text("progress-downloading-gossip"));
+    let nodes = rpc.get_cluster_nodes().await;
+    spinner.finish_and_clear();
+    let nodes: HashSet<Pubkey> = nodes
+        .map_err(|e| Error::from_client_error("Failed to get cluster nodes", &e))?
+        .into_iter()
+        .collect();
+    Ok(GossipCacheFile::new(&rpc.url(), fetched_at, &nodes))
+}
+
+/// Compares two node lists
+///
+/// # Arguments
+/// * `old` - Earlier node list
+/// * `new` - Later node list
+///
+/// # Returns
+/// * `GossipDiff` - Appeared and disappeared nodes, and the number of nodes in both
+pub fn diff_nodes(old: &HashSet<Pubkey>, new: &HashSet<Pubkey>) -> GossipDiff {
+    let sorted = |nodes: HashSet<&Pubkey>| {
+        let mut nodes: Vec<String> = nodes.into_iter().map(Pubkey::to_string).collect();
+        nodes.sort();
+        nodes
+    };
+    GossipDiff {
+        appeared: sorted(new.difference(old).collect()),
+        disappeared: sorted(old.difference(new).collect()),
+        unchanged: old.intersection(new).count(),
+    }
+}
+
+/// Returns the default state file of `gossip-monitor` (`~/.local/share/dz_validator_pda/gossip-monitor.json` on Linux)
+pub fn default_monitor_state_path() -> PathBuf {
+    default_data_dir().join("gossip-monitor.json")
+}
+
+/// Parses the grace period of `gossip-monitor` (`--grace`)
+///
+/// # Arguments
+/// * `value` - Minutes (`5m`), hours (`1h`) or days (`1d`)
+///
+/// # Returns
+/// * `Result<u64, Error>` - Grace period in seconds or `Error::InvalidInput`
+pub fn parse_grace(value: &str) -> Result<u64, Error> {
+    match StatsWindow::parse(value) {
+        Ok(StatsWindow::Last(secs)) => Ok(secs),
+        _ => Err(Error::InvalidInput(format!("Invalid --grace '{}': expected minutes (5m), hours (1h) or days (1d)", value))),
+    }
+}
+
+/// Gossip presence of one monitored validator
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GossipPresence {
+    /// Unix timestamp of the last check that found it in gossip
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<u64>,
+    /// Unix timestamp of the first check that missed it, while it is missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub absent_since: Option<u64>,
+    /// Whether its absence was already notified
+    #[serde(default)]
+    pub notified: bool,
+}
+
+/// Last-seen state of `gossip-monitor`, kept in a JSON file between runs
+///
+/// The file lets a restarted monitor keep counting an absence that began before the restart,
+/// and avoids notifying the same absence twice.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GossipMonitorState {
+    /// Presence by validator identity pubkey
+    #[serde(default)]
+    pub validators: BTreeMap<String, GossipPresence>,
+}
+
+impl GossipMonitorState {
+    /// Loads the state, returning an empty state if the file does not exist
+    ///
+    /// # Arguments
+    /// * `path` - Path to the state file
+    ///
+    /// # Returns
+    /// * `Result<GossipMonitorState, Error>` - Loaded state or `Error::Io` if it cannot be read or parsed
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(GossipMonitorState::default());
+        }
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::Io(format!("Failed to read gossip monitor state {}: {}", path.display(), e)))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| Error::Io(format!("Failed to parse gossip monitor state {}: {}", path.display(), e)))
+    }
+
+    /// Saves the state atomically
+    ///
+    /// # Arguments
+    /// * `path` - Path to the state file
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Ok if the file was written
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Io(format!("Failed to serialize gossip monitor state: {}", e)))?;
+        write_atomic(path, contents.as_bytes())
+    }
+
+    /// Records one check of the node list and returns what should be notified
+    ///
+    /// A validator missing for at least `grace_secs` is reported once as `GossipAbsent`; when it is
+    /// back in gossip after that, it is reported as `GossipReturned`. Shorter gaps (a restart, a
+    /// node list fetched mid-update) are not reported.
+    ///
+    /// # Arguments
+    /// * `validators` - Monitored validator identities
+    /// * `nodes` - Identities currently in gossip
+    /// * `now` - Unix timestamp of the check
+    /// * `grace_secs` - Time a validator may be missing before it is reported
+    ///
+    /// # Returns
+    /// * `Vec<NotifyEvent>` - Events in the order of `validators`
+    pub fn observe(&mut self, validators: &[Pubkey], nodes: &HashSet<Pubkey>, now: u64, grace_secs: u64) -> Vec<NotifyEvent> {
+        let mut events = Vec::new();
+        for validator_id in validators {
+            let validator = validator_id.to_string();
+            let presence = self.validators.entry(validator.clone()).or_default();
+            if nodes.contains(validator_id) {
+                if let Some(absent_since) = presence.absent_since.take()
+                    && presence.notified
+                {
+                    events.push(NotifyEvent::GossipReturned { validator, absent_secs: now.saturating_sub(absent_since) });
+                }
+                presence.last_seen = Some(now);
+                presence.notified = false;
+                continue;
+            }
+            let absent_since = *presence.absent_since.get_or_insert(now);
+            let absent_secs = now.saturating_sub(absent_since);
+            if !presence.notified && absent_secs >= grace_secs {
+                presence.notified = true;
+                events.push(NotifyEvent::GossipAbsent { validator, absent_secs, last_seen: presence.last_seen });
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+
+    #[test]
+    fn test_diff_nodes() {
+        let (kept, left, joined) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let old: HashSet<Pubkey> = [kept, left].into_iter().collect();
+        let new: HashSet<Pubkey> = [kept, joined].into_iter().collect();
+
+        let diff = diff_nodes(&old, &new);
+        assert_eq!(diff.appeared, vec![joined.to_string()]);
+        assert_eq!(diff.disappeared, vec![left.to_string()]);
+        assert_eq!(diff.unchanged, 1);
+        assert!(!diff.is_empty());
+        assert!(diff_nodes(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_monitor_notifies_after_grace() {
+        let (flapping, gone) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let validators = [flapping, gone];
+        let all: HashSet<Pubkey> = validators.into_iter().collect();
+        let none = HashSet::new();
+        let mut state = GossipMonitorState::default();
+
+        assert!(state.observe(&validators, &all, 1_000, 300).is_empty());
+        // A gap shorter than the grace period is not reported
+        assert!(state.observe(&validators, &none, 1_060, 300).is_empty());
+        assert!(state.observe(&validators, &[flapping].into_iter().collect(), 1_120, 300).is_empty());
+        assert!(state.observe(&validators, &[flapping].into_iter().collect(), 1_300, 300).is_empty());
+
+        let events = state.observe(&validators, &[flapping].into_iter().collect(), 1_360, 300);
+        assert_eq!(events, vec![NotifyEvent::GossipAbsent { validator: gone.to_string(), absent_secs: 300, last_seen: Some(1_000) }]);
+        // Reported once, then again when it returns
+        assert!(state.observe(&validators, &[flapping].into_iter().collect(), 1_420, 300).is_empty());
+        let events = state.observe(&validators, &all, 1_480, 300);
+        assert_eq!(events, vec![NotifyEvent::GossipReturned { validator: gone.to_string(), absent_secs: 420 }]);
+        assert_eq!(state.validators[&gone.to_string()], GossipPresence { last_seen: Some(1_480), absent_since: None, notified: false });
+
+        // A validator never seen is reported with no last-seen time
+        let mut state = GossipMonitorState::default();
+        assert!(matches!(state.observe(&[gone], &none, 0, 0)[..], [NotifyEvent::GossipAbsent { last_seen: None, .. }]));
+    }
+
+    #[test]
+    fn test_monitor_state_file() {
+        let path = std::env::temp_dir().join(format!("dz_gossip_monitor_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(GossipMonitorState::load(&path).unwrap(), GossipMonitorState::default());
+
+        let mut state = GossipMonitorState::default();
+        state.observe(&[Pubkey::new_unique()], &HashSet::new(), 1_000, 60);
+        state.save(&path).unwrap();
+        assert_eq!(GossipMonitorState::load(&path).unwrap(), state);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(parse_grace("10m").unwrap(), 600);
+        assert!(parse_grace("all").is_err());
+        assert!(parse_grace("300").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_take_snapshot() {
+        let validator_id = Pubkey::new_unique();
+        let rpc = MockRpc::new().with_gossip_node(validator_id);
+
+        let snapshot = take_snapshot(&rpc, 1_000).await.unwrap();
+        assert_eq!(snapshot.rpc_url, rpc.url());
+        assert_eq!(snapshot.fetched_at, 1_000);
+        assert_eq!(snapshot.node_set(), [validator_id].into_iter().collect());
+    }
+}
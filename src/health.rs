@@ -0,0 +1,723 @@
+use crate::report::{
+    strict_check, Warning, WARN_DELINQUENT, WARN_GOSSIP_CHECK_SKIPPED, WARN_NOT_IN_GOSSIP, WARN_NO_STAKE, WARN_NO_VOTE_ACCOUNT,
+    WARN_STALE_RPC, WARN_VOTE_POLICY,
+};
+use crate::budget::Budget;
+use crate::uptime::{format_basis_points, get_validator_uptime, Uptime, UptimeRequirement};
+use crate::{is_validator_in_gossip, parse_amount, Amount, Error, TransactionVersion};
+use crate::rpc::SolanaRpc;
+use solana_client::rpc_response::RpcVoteAccountInfo;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+/// Health policy that gates funding of a validator PDA
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundingPolicy {
+    /// Require the validator to be present in the gossip network
+    pub check_gossip: bool,
+    /// Require the validator to have a vote account
+    pub require_vote_account: bool,
+    /// Require the vote account to have voted within this many slots
+    pub require_recent_votes: Option<u64>,
+    /// Require at least this much activated stake in lamports (`--min-activated-stake`)
+    pub min_activated_stake: Option<u64>,
+    /// Require a commission of at most this many percent (`--max-commission`)
+    pub max_commission: Option<u8>,
+    /// Refuse a delinquent vote account instead of warning (`--reject-delinquent`)
+    pub reject_delinquent: bool,
+    /// Require a minimum uptime over recent epochs (`--require-uptime`, `--lookback-epochs`)
+    pub require_uptime: Option<UptimeRequirement>,
+    /// Cancel on any warning, including an unhealthy RPC node (`--strict`)
+    pub strict: bool,
+    /// Fund even if the deposit leaves the PDA below rent exemption (`--force`)
+    pub force: bool,
+    /// Balance the funding wallet must keep for future fees (config `reserve_sol`)
+    pub reserve_lamports: u64,
+    /// Priority fee in micro-lamports per compute unit (`--priority-fee`)
+    pub priority_fee: Option<u64>,
+    /// Format of the funding transaction (`--tx-version`)
+    pub transaction_version: TransactionVersion,
+    /// Address lookup table for v0 transactions (`--alt`)
+    pub lookup_table: Option<Pubkey>,
+    /// Memo attached to the funding transaction (`--memo`)
+    pub memo: Option<String>,
+    /// Funding caps and the ledger of what was spent (config `[budget]`)
+    pub budget: Option<Budget>,
+    /// Send a funding that exceeds the budget with a warning instead of refusing it (`--override-budget`)
+    pub override_budget: bool,
+}
+
+impl Default for FundingPolicy {
+    fn default() -> Self {
+        FundingPolicy {
+            check_gossip: true,
+            require_vote_account: false,
+            require_recent_votes: None,
+            min_activated_stake: None,
+            max_commission: None,
+            reject_delinquent: false,
+            require_uptime: None,
+            strict: false,
+            force: false,
+            reserve_lamports: 0,
+            priority_fee: None,
+            transaction_version: TransactionVersion::Legacy,
+            lookup_table: None,
+            memo: None,
+            budget: None,
+            override_budget: false,
+        }
+    }
+}
+
+impl FundingPolicy {
+    /// Returns true if the policy needs vote account information
+    pub fn needs_vote_account(&self) -> bool {
+        self.require_vote_account
+            || self.require_recent_votes.is_some()
+            || self.min_activated_stake.is_some()
+            || self.max_commission.is_some()
+            || self.reject_delinquent
+            || self.require_uptime.is_some()
+    }
+}
+
+/// Parses the `--min-activated-stake` value
+///
+/// # Arguments
+/// * `value` - SOL amount like `5000` or a lamports amount like `1000lamports`
+///
+/// # Returns
+/// * `Result<u64, Error>` - Stake in lamports, or `Error::InvalidInput`
+pub fn parse_min_activated_stake(value: &str) -> Result<u64, Error> {
+    match parse_amount(value) {
+        Ok(Amount::Lamports(lamports)) => Ok(lamports),
+        _ => Err(Error::InvalidInput(format!("Invalid --min-activated-stake '{}': expected an amount of SOL like 5000", value))),
+    }
+}
+
+/// Parses the `--max-commission` value
+///
+/// # Arguments
+/// * `value` - Percentage from 0 to 100, optionally with a `%` suffix
+///
+/// # Returns
+/// * `Result<u8, Error>` - Commission in percent, or `Error::InvalidInput`
+pub fn parse_max_commission(value: &str) -> Result<u8, Error> {
+    match value.trim().trim_end_matches('%').parse::<u8>() {
+        Ok(commission) if commission <= 100 => Ok(commission),
+        _ => Err(Error::InvalidInput(format!("Invalid --max-commission '{}': expected a percentage from 0 to 100", value))),
+    }
+}
+
+/// Result of one vote account rule of the funding policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PolicyCheck {
+    /// Rule name: `vote_account`, `recent_votes`, `delinquent`, `min_activated_stake`, `max_commission` or `uptime`
+    pub rule: &'static str,
+    /// True if the vote account satisfies the rule
+    pub passed: bool,
+    /// Observed value and the limit, or the reason the rule failed
+    pub detail: String,
+}
+
+impl PolicyCheck {
+    fn new(rule: &'static str, passed: bool, detail: String) -> Self {
+        PolicyCheck { rule, passed, detail }
+    }
+}
+
+/// Vote account of a validator as reported by `getVoteAccounts`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VoteAccountStatus {
+    /// Vote account address
+    pub vote_pubkey: String,
+    /// True if the vote account is reported as delinquent
+    pub delinquent: bool,
+    /// Most recent slot voted on
+    pub last_vote: u64,
+    /// Activated stake in lamports
+    pub activated_stake: u64,
+    /// Commission in percent
+    pub commission: u8,
+}
+
+impl VoteAccountStatus {
+    fn from_info(info: &RpcVoteAccountInfo, delinquent: bool) -> Self {
+        VoteAccountStatus {
+            vote_pubkey: info.vote_pubkey.clone(),
+            delinquent,
+            last_vote: info.last_vote,
+            activated_stake: info.activated_stake,
+            commission: info.commission,
+        }
+    }
+}
+
+/// Finds the vote account of a validator
+///
+/// # Arguments
+/// * `validator_id` - The validator's identity public key
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Option<VoteAccountStatus>, Error>` - Vote account if the validator has one, or error
+pub async fn get_validator_vote_account(validator_id: &Pubkey, rpc: &dyn SolanaRpc) -> Result<Option<VoteAccountStatus>, Error> {
+    let vote_accounts = rpc.get_vote_accounts().await
+        .map_err(|e| Error::from_client_error("Failed to get vote accounts", &e))?;
+
+    let validator_string = validator_id.to_string();
+    let current = vote_accounts.current.iter()
+        .find(|info| info.node_pubkey == validator_string)
+        .map(|info| VoteAccountStatus::from_info(info, false));
+    let delinquent = vote_accounts.delinquent.iter()
+        .find(|info| info.node_pubkey == validator_string)
+        .map(|info| VoteAccountStatus::from_info(info, true));
+
+    Ok(current.or(delinquent))
+}
+
+/// Evaluates each vote account rule configured in a policy
+///
+/// # Arguments
+/// * `policy` - Funding policy
+/// * `vote_account` - Vote account of the validator, if any
+/// * `current_slot` - Current cluster slot
+///
+/// # Returns
+/// * `Vec<PolicyCheck>` - One result per configured rule (a single failed `vote_account` check without a vote account)
+pub fn policy_checks(policy: &FundingPolicy, vote_account: Option<&VoteAccountStatus>, current_slot: u64) -> Vec<PolicyCheck> {
+    if !policy.needs_vote_account() {
+        return Vec::new();
+    }
+
+    let Some(vote_account) = vote_account else {
+        return vec![PolicyCheck::new("vote_account", false, "Validator has no vote account".to_string())];
+    };
+    let vote_pubkey = &vote_account.vote_pubkey;
+    let mut checks = Vec::new();
+
+    if policy.require_vote_account {
+        checks.push(PolicyCheck::new("vote_account", true, format!("Vote account {}", vote_pubkey)));
+    }
+
+    if let Some(max_slots) = policy.require_recent_votes {
+        let slots_since_vote = current_slot.saturating_sub(vote_account.last_vote);
+        checks.push(if vote_account.last_vote == 0 {
+            PolicyCheck::new("recent_votes", false, format!("Vote account {} has never voted", vote_pubkey))
+        } else if slots_since_vote > max_slots {
+            PolicyCheck::new("recent_votes", false, format!(
+                "Vote account {} last voted {} slots ago (slot {}), more than the required {} slots",
+                vote_pubkey, slots_since_vote, vote_account.last_vote, max_slots
+            ))
+        } else {
+            PolicyCheck::new("recent_votes", true, format!("Last voted {} slots ago (at most {})", slots_since_vote, max_slots))
+        });
+    }
+
+    if policy.reject_delinquent {
+        checks.push(if vote_account.delinquent {
+            PolicyCheck::new("delinquent", false, format!("Vote account {} is delinquent", vote_pubkey))
+        } else {
+            PolicyCheck::new("delinquent", true, "Not delinquent".to_string())
+        });
+    }
+
+    if let Some(min_stake) = policy.min_activated_stake {
+        let stake = vote_account.activated_stake;
+        checks.push(if stake < min_stake {
+            PolicyCheck::new("min_activated_stake", false, format!(
+                "Vote account {} has {} activated stake, less than the required {}",
+                vote_pubkey, Amount::Lamports(stake), Amount::Lamports(min_stake)
+            ))
+        } else {
+            PolicyCheck::new("min_activated_stake", true, format!("Activated stake {} (at least {})", Amount::Lamports(stake), Amount::Lamports(min_stake)))
+        });
+    }
+
+    if let Some(max_commission) = policy.max_commission {
+        let commission = vote_account.commission;
+        checks.push(if commission > max_commission {
+            PolicyCheck::new("max_commission", false, format!(
+                "Vote account {} charges {}% commission, more than the allowed {}%",
+                vote_pubkey, commission, max_commission
+            ))
+        } else {
+            PolicyCheck::new("max_commission", true, format!("Commission {}% (at most {}%)", commission, max_commission))
+        });
+    }
+
+    checks
+}
+
+/// Evaluates the vote account requirements of a policy
+///
+/// # Arguments
+/// * `policy` - Funding policy
+/// * `vote_account` - Vote account of the validator, if any
+/// * `current_slot` - Current cluster slot
+///
+/// # Returns
+/// * `Result<(), String>` - Ok if the requirements are met, or the reasons they are not
+pub fn evaluate_vote_policy(policy: &FundingPolicy, vote_account: Option<&VoteAccountStatus>, current_slot: u64) -> Result<(), String> {
+    let failures: Vec<String> = policy_checks(policy, vote_account, current_slot)
+        .into_iter()
+        .filter(|check| !check.passed)
+        .map(|check| check.detail)
+        .collect();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("; "))
+    }
+}
+
+/// Overall health verdict of a validator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verdict {
+    /// All checks passed
+    Pass,
+    /// Funding may proceed, but something needs attention
+    Warn,
+    /// Funding must not proceed
+    Fail,
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Pass => "PASS",
+            Verdict::Warn => "WARN",
+            Verdict::Fail => "FAIL",
+        })
+    }
+}
+
+/// Aggregated gossip, vote account and epoch status of a validator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorStatus {
+    /// Presence in gossip, or None if the gossip check was skipped
+    pub in_gossip: Option<bool>,
+    /// Vote account, if the validator has one
+    pub vote_account: Option<VoteAccountStatus>,
+    /// Current epoch
+    pub epoch: u64,
+    /// Current cluster slot
+    pub current_slot: u64,
+    /// Uptime over recent epochs, if the policy requires one (see `get_policy_status`)
+    pub uptime: Option<Uptime>,
+}
+
+impl ValidatorStatus {
+    /// Evaluates each vote account rule of a funding policy (see `policy_checks`), then the uptime rule
+    pub fn policy_checks(&self, policy: &FundingPolicy) -> Vec<PolicyCheck> {
+        let mut checks = policy_checks(policy, self.vote_account.as_ref(), self.current_slot);
+        if let (Some(requirement), Some(vote_account)) = (policy.require_uptime, &self.vote_account) {
+            let required = format_basis_points(requirement.min_basis_points);
+            checks.push(match self.uptime.as_ref().map(|uptime| (uptime, uptime.basis_points())) {
+                None => PolicyCheck::new("uptime", false, format!("Uptime of vote account {} was not checked", vote_account.vote_pubkey)),
+                Some((uptime, None)) => PolicyCheck::new("uptime", false, format!(
+                    "Vote account {} has no vote credits or leader slots in the last {} epochs",
+                    vote_account.vote_pubkey, uptime.lookback_epochs
+                )),
+                Some((uptime, Some(basis_points))) if basis_points < requirement.min_basis_points => PolicyCheck::new("uptime", false, format!(
+                    "Vote account {} had {} uptime, less than the required {}",
+                    vote_account.vote_pubkey, uptime.summary(), required
+                )),
+                Some((uptime, Some(_))) => PolicyCheck::new("uptime", true, format!("Uptime {} (at least {})", uptime.summary(), required)),
+            });
+        }
+        checks
+    }
+
+    /// Evaluates the status against a funding policy
+    ///
+    /// Policy violations fail; a missing, delinquent or unstaked vote account and a skipped
+    /// gossip check only warn.
+    ///
+    /// # Arguments
+    /// * `policy` - Funding policy
+    ///
+    /// # Returns
+    /// * `(Verdict, Vec<Warning>)` - Verdict and the reasons for anything other than PASS
+    pub fn evaluate(&self, policy: &FundingPolicy) -> (Verdict, Vec<Warning>) {
+        let mut failures = Vec::new();
+        let mut warnings = Vec::new();
+
+        match self.in_gossip {
+            Some(true) => {}
+            Some(false) => failures.push(Warning::new(WARN_NOT_IN_GOSSIP, "Validator is not in Solana gossip network")),
+            None => warnings.push(Warning::new(WARN_GOSSIP_CHECK_SKIPPED, "Gossip check skipped")),
+        }
+
+        for check in self.policy_checks(policy).into_iter().filter(|check| !check.passed) {
+            failures.push(Warning::new(WARN_VOTE_POLICY, check.detail));
+        }
+
+        match &self.vote_account {
+            None if !policy.needs_vote_account() => {
+                warnings.push(Warning::new(WARN_NO_VOTE_ACCOUNT, "Validator has no vote account"));
+            }
+            None => {}
+            Some(vote_account) => {
+                if vote_account.delinquent {
+                    warnings.push(Warning::new(
+                        WARN_DELINQUENT,
+                        format!("Vote account {} is delinquent", vote_account.vote_pubkey),
+                    ));
+                }
+                if vote_account.activated_stake == 0 {
+                    warnings.push(Warning::new(
+                        WARN_NO_STAKE,
+                        format!("Vote account {} has no activated stake", vote_account.vote_pubkey),
+                    ));
+                }
+            }
+        }
+
+        if !failures.is_empty() {
+            (Verdict::Fail, failures)
+        } else if !warnings.is_empty() {
+            (Verdict::Warn, warnings)
+        } else {
+            (Verdict::Pass, Vec::new())
+        }
+    }
+}
+
+/// Collects the status of a validator from `getClusterNodes`, `getVoteAccounts` and `getEpochInfo`
+///
+/// # Arguments
+/// * `validator_id` - The validator's identity public key
+/// * `check_gossip` - Query gossip presence (false leaves `in_gossip` as None)
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<ValidatorStatus, Error>` - Aggregated status or error
+pub async fn get_validator_status(validator_id: &Pubkey, check_gossip: bool, rpc: &dyn SolanaRpc) -> Result<ValidatorStatus, Error> {
+    let in_gossip = if check_gossip {
+        Some(is_validator_in_gossip(validator_id, rpc).await?)
+    } else {
+        None
+    };
+
+    let vote_account = get_validator_vote_account(validator_id, rpc).await?;
+
+    let epoch_info = rpc.get_epoch_info().await
+        .map_err(|e| Error::from_client_error("Failed to get epoch info", &e))?;
+
+    Ok(ValidatorStatus {
+        in_gossip,
+        vote_account,
+        epoch: epoch_info.epoch,
+        current_slot: epoch_info.absolute_slot,
+        uptime: None,
+    })
+}
+
+/// Collects the status of a validator with everything a funding policy checks
+///
+/// Same as `get_validator_status`, also computing the uptime when the policy requires one and the
+/// validator has a vote account.
+///
+/// # Arguments
+/// * `validator_id` - The validator's identity public key
+/// * `policy` - Funding policy
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<ValidatorStatus, Error>` - Aggregated status or error
+pub async fn get_policy_status(validator_id: &Pubkey, policy: &FundingPolicy, rpc: &dyn SolanaRpc) -> Result<ValidatorStatus, Error> {
+    let mut status = get_validator_status(validator_id, policy.check_gossip, rpc).await?;
+    if let (Some(requirement), Some(vote_account)) = (policy.require_uptime, &status.vote_account) {
+        let vote_pubkey = vote_account.vote_pubkey.parse::<Pubkey>()
+            .map_err(|e| Error::Rpc(format!("Invalid vote account {} returned by the node: {}", vote_account.vote_pubkey, e)))?;
+        status.uptime = Some(get_validator_uptime(validator_id, &vote_pubkey, requirement.lookback_epochs, rpc).await?);
+    }
+    Ok(status)
+}
+
+/// Checks a validator against the funding policy
+///
+/// Uses the same verdict as `validator-status`: FAIL cancels funding, WARN returns the reasons and proceeds.
+/// A strict policy also checks the RPC node and cancels on any warning.
+///
+/// # Arguments
+/// * `validator_id` - The validator's public key
+/// * `policy` - Funding policy to apply
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Vec<Warning>, Error>` - Warnings (empty on PASS) if funding may proceed, `Error::NotInGossip`, `Error::FundingCancelled` or `Error::StrictWarning` otherwise
+pub async fn check_funding_policy(validator_id: &Pubkey, policy: &FundingPolicy, rpc: &dyn SolanaRpc) -> Result<Vec<Warning>, Error> {
+    check_funding_policy_rules(validator_id, policy, rpc).await.map(|(warnings, _)| warnings)
+}
+
+/// Same as `check_funding_policy`, also returning the result of every configured vote account rule
+///
+/// # Arguments
+/// * `validator_id` - The validator's public key
+/// * `policy` - Funding policy to apply
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<(Vec<Warning>, Vec<PolicyCheck>), Error>` - Warnings and passed rules if funding may proceed, or the errors of `check_funding_policy`
+pub async fn check_funding_policy_rules(
+    validator_id: &Pubkey,
+    policy: &FundingPolicy,
+    rpc: &dyn SolanaRpc,
+) -> Result<(Vec<Warning>, Vec<PolicyCheck>), Error> {
+    let status = get_policy_status(validator_id, policy, rpc).await
+        .map_err(|e| Error::FundingCancelled(format!("Funding cancelled: Unable to check validator status: {}", e)))?;
+
+    let (verdict, reasons) = status.evaluate(policy);
+    if verdict == Verdict::Fail {
+        return Err(cancellation(&reasons, "Funding cancelled"));
+    }
+
+    if policy.strict {
+        let mut warnings = reasons.clone();
+        warnings.extend(check_rpc_health(rpc).await);
+        strict_check(&warnings)?;
+    }
+    Ok((reasons, status.policy_checks(policy)))
+}
+
+/// Returns the error of a FAIL verdict
+///
+/// # Arguments
+/// * `reasons` - Reasons returned by `ValidatorStatus::evaluate`
+/// * `context` - What was cancelled, prepended to the reasons
+///
+/// # Returns
+/// * `Error` - `Error::NotInGossip` if the validator is not in gossip, otherwise `Error::FundingCancelled`
+pub fn cancellation(reasons: &[Warning], context: &str) -> Error {
+    let message = format!("{}: {}", context, reasons.iter().map(|reason| reason.message.as_str()).collect::<Vec<_>>().join("; "));
+    if reasons.iter().any(|reason| reason.code == WARN_NOT_IN_GOSSIP) {
+        Error::NotInGossip(message)
+    } else {
+        Error::FundingCancelled(message)
+    }
+}
+
+/// Checks whether the RPC node is healthy and caught up with the cluster
+///
+/// # Arguments
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Option<Warning>` - Warning if `getHealth` reports the node unhealthy or behind
+pub async fn check_rpc_health(rpc: &dyn SolanaRpc) -> Option<Warning> {
+    let url = rpc.url();
+    rpc.get_health().await.err().map(|e| {
+        Warning::new(WARN_STALE_RPC, format!("RPC node {} may be stale: {}", url, e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+
+    fn vote_account(last_vote: u64) -> VoteAccountStatus {
+        VoteAccountStatus {
+            vote_pubkey: "Vote111111111111111111111111111111111111111".to_string(),
+            delinquent: false,
+            last_vote,
+            activated_stake: 1_000_000_000_000,
+            commission: 5,
+        }
+    }
+
+    #[test]
+    fn test_default_policy_only_checks_gossip() {
+        let policy = FundingPolicy::default();
+        assert!(policy.check_gossip);
+        assert!(!policy.needs_vote_account());
+        assert!(evaluate_vote_policy(&policy, None, 1_000).is_ok());
+    }
+
+    #[test]
+    fn test_require_vote_account() {
+        let policy = FundingPolicy { require_vote_account: true, ..FundingPolicy::default() };
+
+        assert!(evaluate_vote_policy(&policy, None, 1_000).unwrap_err().contains("no vote account"));
+        assert!(evaluate_vote_policy(&policy, Some(&vote_account(0)), 1_000).is_ok());
+    }
+
+    #[test]
+    fn test_require_recent_votes() {
+        let policy = FundingPolicy { require_recent_votes: Some(150), ..FundingPolicy::default() };
+
+        assert!(evaluate_vote_policy(&policy, Some(&vote_account(900)), 1_000).is_ok());
+        assert!(evaluate_vote_policy(&policy, Some(&vote_account(850)), 1_000).is_ok());
+
+        let error = evaluate_vote_policy(&policy, Some(&vote_account(800)), 1_000).unwrap_err();
+        assert!(error.contains("200 slots ago"), "Unexpected error: {}", error);
+
+        assert!(evaluate_vote_policy(&policy, Some(&vote_account(0)), 1_000).unwrap_err().contains("never voted"));
+        assert!(evaluate_vote_policy(&policy, None, 1_000).is_err());
+    }
+
+    #[test]
+    fn test_stake_commission_and_delinquency_rules() {
+        let policy = FundingPolicy {
+            min_activated_stake: Some(500_000_000_000),
+            max_commission: Some(8),
+            reject_delinquent: true,
+            ..FundingPolicy::default()
+        };
+        assert!(policy.needs_vote_account());
+
+        let checks = policy_checks(&policy, Some(&vote_account(990)), 1_000);
+        assert_eq!(checks.iter().map(|check| check.rule).collect::<Vec<_>>(), vec!["delinquent", "min_activated_stake", "max_commission"]);
+        assert!(checks.iter().all(|check| check.passed));
+        assert!(evaluate_vote_policy(&policy, Some(&vote_account(990)), 1_000).is_ok());
+
+        let bad = VoteAccountStatus { delinquent: true, activated_stake: 100_000_000_000, commission: 10, ..vote_account(990) };
+        let error = evaluate_vote_policy(&policy, Some(&bad), 1_000).unwrap_err();
+        assert!(error.contains("is delinquent"), "{}", error);
+        assert!(error.contains("less than the required 500 SOL"), "{}", error);
+        assert!(error.contains("10% commission, more than the allowed 8%"), "{}", error);
+
+        let (verdict, reasons) = status(Some(true), Some(bad)).evaluate(&policy);
+        assert_eq!(verdict, Verdict::Fail);
+        assert_eq!(reasons.len(), 3);
+        assert_eq!(policy_checks(&policy, None, 1_000), vec![PolicyCheck::new("vote_account", false, "Validator has no vote account".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_policy_limits() {
+        assert_eq!(parse_min_activated_stake("5000").unwrap(), 5_000_000_000_000);
+        assert_eq!(parse_min_activated_stake("1000lamports").unwrap(), 1_000);
+        assert!(parse_min_activated_stake("ALL").unwrap_err().is_user_error());
+        assert_eq!(parse_max_commission("10").unwrap(), 10);
+        assert_eq!(parse_max_commission("5%").unwrap(), 5);
+        assert!(parse_max_commission("101").is_err());
+        assert!(parse_max_commission("-1").is_err());
+    }
+
+    #[test]
+    fn test_uptime_rule() {
+        let policy = FundingPolicy {
+            require_uptime: Some(UptimeRequirement { min_basis_points: 9_500, lookback_epochs: 10 }),
+            ..FundingPolicy::default()
+        };
+        assert!(policy.needs_vote_account());
+        let uptime = |credits: u64, blocks_produced: u64| Uptime {
+            vote_pubkey: vote_account(990).vote_pubkey,
+            lookback_epochs: 10,
+            epochs: vec![crate::uptime::EpochCredits { epoch: 699, credits, max_credits: 1_000 }],
+            leader_slots: 100,
+            blocks_produced,
+        };
+
+        let mut status = status(Some(true), Some(vote_account(990)));
+        assert!(!status.policy_checks(&policy)[0].passed, "an unchecked uptime fails");
+        status.uptime = Some(uptime(980, 96));
+        let check = &status.policy_checks(&policy)[0];
+        assert!(check.passed && check.detail.starts_with("Uptime 96.00% over epoch 699"), "{}", check.detail);
+
+        // A validator voting well but skipping its leader slots is still underperforming
+        status.uptime = Some(uptime(980, 90));
+        let (verdict, reasons) = status.evaluate(&policy);
+        assert_eq!(verdict, Verdict::Fail);
+        assert!(reasons[0].message.contains("had 90.00% over epoch 699"), "{}", reasons[0].message);
+
+        status.uptime = Some(Uptime { epochs: Vec::new(), leader_slots: 0, ..uptime(0, 0) });
+        assert!(status.policy_checks(&policy)[0].detail.contains("no vote credits or leader slots in the last 10 epochs"));
+    }
+
+    fn status(in_gossip: Option<bool>, vote_account: Option<VoteAccountStatus>) -> ValidatorStatus {
+        ValidatorStatus { in_gossip, vote_account, epoch: 700, current_slot: 1_000, uptime: None }
+    }
+
+    #[test]
+    fn test_status_verdict_pass() {
+        let (verdict, reasons) = status(Some(true), Some(vote_account(990))).evaluate(&FundingPolicy::default());
+        assert_eq!(verdict, Verdict::Pass);
+        assert!(reasons.is_empty());
+    }
+
+    #[test]
+    fn test_status_verdict_warn() {
+        let policy = FundingPolicy::default();
+
+        let (verdict, reasons) = status(Some(true), None).evaluate(&policy);
+        assert_eq!(verdict, Verdict::Warn);
+        assert_eq!(reasons, vec![Warning::new(WARN_NO_VOTE_ACCOUNT, "Validator has no vote account")]);
+
+        let delinquent = VoteAccountStatus { delinquent: true, activated_stake: 0, ..vote_account(500) };
+        let (verdict, reasons) = status(Some(true), Some(delinquent)).evaluate(&policy);
+        assert_eq!(verdict, Verdict::Warn);
+        assert_eq!(reasons.len(), 2);
+
+        let skipped = FundingPolicy { check_gossip: false, ..FundingPolicy::default() };
+        let (verdict, reasons) = status(None, Some(vote_account(990))).evaluate(&skipped);
+        assert_eq!(verdict, Verdict::Warn);
+        assert_eq!(reasons, vec![Warning::new(WARN_GOSSIP_CHECK_SKIPPED, "Gossip check skipped")]);
+    }
+
+    #[test]
+    fn test_status_verdict_fail() {
+        let (verdict, reasons) = status(Some(false), Some(vote_account(990))).evaluate(&FundingPolicy::default());
+        assert_eq!(verdict, Verdict::Fail);
+        assert_eq!(reasons, vec![Warning::new(WARN_NOT_IN_GOSSIP, "Validator is not in Solana gossip network")]);
+
+        // Policy violations fail and hide warnings
+        let policy = FundingPolicy { require_recent_votes: Some(150), ..FundingPolicy::default() };
+        let stale = VoteAccountStatus { delinquent: true, ..vote_account(800) };
+        let (verdict, reasons) = status(Some(true), Some(stale)).evaluate(&policy);
+        assert_eq!(verdict, Verdict::Fail);
+        assert_eq!(reasons.len(), 1);
+        assert_eq!(reasons[0].code, WARN_VOTE_POLICY);
+        assert!(reasons[0].message.contains("200 slots ago"));
+
+        assert_eq!(Verdict::Fail.to_string(), "FAIL");
+        assert!(Verdict::Fail > Verdict::Warn && Verdict::Warn > Verdict::Pass);
+    }
+
+    #[tokio::test]
+    async fn test_check_funding_policy() {
+        let healthy = Pubkey::new_unique();
+        let delinquent = Pubkey::new_unique();
+        let absent = Pubkey::new_unique();
+        let rpc = MockRpc::new()
+            .with_gossip_node(healthy)
+            .with_gossip_node(delinquent)
+            .with_vote_account(healthy, 990, 1_000_000_000_000, false)
+            .with_vote_account(delinquent, 500, 0, true);
+        let policy = FundingPolicy::default();
+
+        let status = get_validator_status(&delinquent, true, &rpc).await.unwrap();
+        assert_eq!(status.in_gossip, Some(true));
+        assert!(status.vote_account.as_ref().unwrap().delinquent);
+        assert_eq!(status.current_slot, 1_000);
+
+        assert!(check_funding_policy(&healthy, &policy, &rpc).await.unwrap().is_empty());
+        let warnings = check_funding_policy(&delinquent, &policy, &rpc).await.unwrap();
+        assert_eq!(warnings.iter().map(|warning| warning.code.as_str()).collect::<Vec<_>>(), vec![WARN_DELINQUENT, WARN_NO_STAKE]);
+        assert!(matches!(check_funding_policy(&absent, &policy, &rpc).await, Err(Error::NotInGossip(_))));
+
+        let recent = FundingPolicy { require_recent_votes: Some(150), ..FundingPolicy::default() };
+        assert!(matches!(check_funding_policy(&delinquent, &recent, &rpc).await, Err(Error::FundingCancelled(_))));
+
+        // Delinquency and stake rules turn the warnings into a cancelled funding
+        let gated = FundingPolicy { reject_delinquent: true, min_activated_stake: Some(1_000_000_000), max_commission: Some(10), ..FundingPolicy::default() };
+        let (warnings, checks) = check_funding_policy_rules(&healthy, &gated, &rpc).await.unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(checks.len(), 3);
+        let Err(Error::FundingCancelled(reason)) = check_funding_policy(&delinquent, &gated, &rpc).await else {
+            panic!("Delinquent validator was not rejected");
+        };
+        assert!(reason.contains("is delinquent") && reason.contains("less than the required 1 SOL"), "{}", reason);
+
+        // Strict: any warning, including a stale RPC node, stops funding
+        let strict = FundingPolicy { strict: true, ..FundingPolicy::default() };
+        assert!(matches!(check_funding_policy(&delinquent, &strict, &rpc).await, Err(Error::StrictWarning(_))));
+        assert!(check_funding_policy(&healthy, &strict, &rpc).await.is_ok());
+        let stale = MockRpc::new().with_gossip_node(healthy).with_vote_account(healthy, 990, 1_000_000_000_000, false).unhealthy();
+        assert_eq!(check_rpc_health(&stale).await.unwrap().code, WARN_STALE_RPC);
+        assert!(matches!(check_funding_policy(&healthy, &strict, &stale).await, Err(Error::StrictWarning(_))));
+    }
+}
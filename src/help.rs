@@ -0,0 +1,827 @@
+use crate::i18n::{self, Lang};
+use crate::template::template_fields;
+use crate::Error;
+
+/// Usage and summary of a top-level operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandHelp {
+    /// Operation name as typed on the command line
+    pub name: &'static str,
+    /// Arguments after the program name
+    pub usage: &'static str,
+    /// One-line description
+    pub summary: &'static str,
+    /// One-line description in Russian
+    pub summary_ru: &'static str,
+}
+
+impl CommandHelp {
+    /// Returns the description in the language of this process
+    pub fn summary_text(&self) -> &'static str {
+        match i18n::lang() {
+            Lang::En => self.summary,
+            Lang::Ru => self.summary_ru,
+        }
+    }
+}
+
+/// A copy-pasteable invocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    /// Operation the example belongs to
+    pub command: &'static str,
+    /// What the example does
+    pub description: &'static str,
+    /// What the example does, in Russian
+    pub description_ru: &'static str,
+    /// Arguments after the program name
+    pub args: &'static str,
+}
+
+impl Example {
+    /// Returns the description in the language of this process
+    pub fn description_text(&self) -> &'static str {
+        match i18n::lang() {
+            Lang::En => self.description,
+            Lang::Ru => self.description_ru,
+        }
+    }
+}
+
+/// Top-level operations in the order they are listed in the usage text
+pub const COMMANDS: &[CommandHelp] = &[
+    CommandHelp {
+        name: "pda-address",
+        usage: "pda-address <validator_or_alias> [--seed-prefix <seed>] [--program-id <pubkey>] [--format <template>]",
+        summary: "Generate PDA address for validator",
+        summary_ru: "Сгенерировать адрес PDA для валидатора",
+    },
+    CommandHelp {
+        name: "derive",
+        usage: "derive --seed-spec <kind:value,...> [--program-id <pubkey>]",
+        summary: "Derive any PDA of the revenue distribution program (or another program) from a seed list",
+        summary_ru: "Вывести любой PDA программы распределения дохода (или другой программы) из списка сидов",
+    },
+    CommandHelp {
+        name: "pda-balance",
+        usage: "pda-balance <validator_or_alias> | pda-balance --stdin [--format <template>]",
+        summary: "Show balance of PDA address for validator",
+        summary_ru: "Показать баланс адреса PDA валидатора",
+    },
+    CommandHelp {
+        name: "pda-balance-history",
+        usage: "pda-balance-history <validator_or_alias> [--days <n>] [--output text|json|csv] [--out <path>]",
+        summary: "Show the PDA balance over time, rebuilt from the balance changes of its transactions",
+        summary_ru: "Показать баланс PDA во времени, восстановленный по изменениям баланса в его транзакциях",
+    },
+    CommandHelp {
+        name: "revenue-report",
+        usage: "revenue-report <validator_or_alias> --epochs <first..last> [--share <percent>] [--output text|json]",
+        summary: "Reconcile PDA deposits and claims with the validator's inflation rewards per epoch",
+        summary_ru: "Сверить депозиты и выводы PDA с инфляционными наградами валидатора по эпохам",
+    },
+    CommandHelp {
+        name: "pda-fund-address",
+        usage: "pda-fund-address <validator_or_alias> [keypair_path] <amount> [--yes] [--identity-keypair <path>] [--at-epoch-boundary | --at-epoch <epoch>] [--memo <text>] [--squads <multisig>] [--receipt <path>] [--verify-delta]",
+        summary: "Fund validator PDA from keypair",
+        summary_ru: "Пополнить PDA валидатора с ключа",
+    },
+    CommandHelp {
+        name: "pda-fund-many",
+        usage: "pda-fund-many [keypair_path] --pairs <validator:amount,...> | --file <transfers.csv> | --resume <run.json> [--state <run.json>] [--yes]",
+        summary: "Fund several validator PDAs, packing the transfers into as few transactions as fit",
+        summary_ru: "Пополнить несколько PDA валидаторов, упаковав переводы в минимум транзакций",
+    },
+    CommandHelp {
+        name: "pda-fund-token",
+        usage: "pda-fund-token <validator_or_alias> [keypair_path] --mint <mint> --amount <tokens> [--yes]",
+        summary: "Deposit SPL tokens to the associated token account of a validator PDA",
+        summary_ru: "Внести SPL-токены на ассоциированный токен-аккаунт PDA валидатора",
+    },
+    CommandHelp {
+        name: "pda-watch",
+        usage: "pda-watch <validator_or_alias> [interval_secs] [max_outflow_sol_per_hour]",
+        summary: "Watch PDA balance and alert on fast outflow",
+        summary_ru: "Следить за балансом PDA и предупреждать о быстром оттоке",
+    },
+    CommandHelp {
+        name: "validator-status",
+        usage: "validator-status <validator_or_alias>",
+        summary: "Show gossip, vote account and stake status with a PASS/WARN/FAIL verdict",
+        summary_ru: "Показать статус gossip, голосующего аккаунта и стейка с итогом PASS/WARN/FAIL",
+    },
+    CommandHelp {
+        name: "broadcast",
+        usage: "broadcast <tx.b64> [<tx.b64> ...]",
+        summary: "Submit a transaction signed with pda-fund-address --sign-only, combining partial signatures",
+        summary_ru: "Отправить транзакцию, подписанную через pda-fund-address --sign-only, объединяя частичные подписи",
+    },
+    CommandHelp {
+        name: "pda-batch",
+        usage: "pda-batch <pda-address|pda-balance> [validator_or_alias ...] [--file <path>] [--stdin] [--sort name|balance|stake] [--consistent-snapshot] [--concurrency <n>] [--format <template>]",
+        summary: "Run an operation for many validators",
+        summary_ru: "Выполнить операцию для многих валидаторов",
+    },
+    CommandHelp {
+        name: "allocate",
+        usage: "allocate --budget <amount> --weights stake|equal|<weights.csv> [validator_or_alias ...] [--file <path>] [--payer <keypair_or_pubkey>] [--out plan.yaml]",
+        summary: "Split a treasury budget between validators and show the funding each PDA needs to reach its share",
+        summary_ru: "Разделить бюджет казначейства между валидаторами и показать пополнение, нужное каждому PDA до его доли",
+    },
+    CommandHelp {
+        name: "dashboard",
+        usage: "dashboard [validator_or_alias ...] [--file <path>] [--refresh <seconds>] [--amount <SOL>]",
+        summary: "Live table of gossip, vote and PDA balance status with top-ups and funding history per validator",
+        summary_ru: "Живая таблица статуса gossip, голосования и баланса PDA с пополнениями и историей пополнений по валидатору",
+    },
+    CommandHelp {
+        name: "completion",
+        usage: "completion <bash|zsh|fish>",
+        summary: "Print a shell completion script",
+        summary_ru: "Вывести скрипт автодополнения для оболочки",
+    },
+    CommandHelp {
+        name: "wallet",
+        usage: "wallet rotate --new <keypair_path> [--old <keypair_path>] [--profile <name>]",
+        summary: "Move funds to a new funding wallet and update the config",
+        summary_ru: "Перевести средства на новый кошелёк пополнения и обновить конфигурацию",
+    },
+    CommandHelp {
+        name: "airdrop",
+        usage: "airdrop <keypair_or_pubkey> <amount>",
+        summary: "Request SOL from the devnet/testnet faucet and wait for confirmation (refused on mainnet)",
+        summary_ru: "Запросить SOL у крана devnet/testnet и дождаться подтверждения (в mainnet запрещено)",
+    },
+    CommandHelp {
+        name: "gossip-snapshot",
+        usage: "gossip-snapshot --out <path>",
+        summary: "Save the cluster node list, to compare later with gossip-diff",
+        summary_ru: "Сохранить список узлов кластера для последующего сравнения через gossip-diff",
+    },
+    CommandHelp {
+        name: "gossip-diff",
+        usage: "gossip-diff <old.json> [new.json]",
+        summary: "Show validators that joined or left gossip since a snapshot (or between two snapshots)",
+        summary_ru: "Показать валидаторов, которые появились в gossip или пропали из него после снимка (или между двумя снимками)",
+    },
+    CommandHelp {
+        name: "gossip-monitor",
+        usage: "gossip-monitor [validator_or_alias ...] [--file <path>] [--interval <seconds>] [--grace <5m>] [--state <path>]",
+        summary: "Notify when a validator has been missing from gossip longer than the grace period, until Ctrl-C",
+        summary_ru: "Уведомлять, когда валидатор отсутствует в gossip дольше льготного периода, до Ctrl-C",
+    },
+    CommandHelp {
+        name: "verify-deposit",
+        usage: "verify-deposit <signature> <validator_or_alias>",
+        summary: "Check that a transaction paid the deposit PDA of a validator and is finalized",
+        summary_ru: "Проверить, что транзакция оплатила PDA депозита валидатора и финализирована",
+    },
+    CommandHelp {
+        name: "verify-receipt",
+        usage: "verify-receipt <receipt.json>",
+        summary: "Check the payer signature of a deposit receipt written with pda-fund-address --receipt",
+        summary_ru: "Проверить подпись плательщика в квитанции депозита, записанной через pda-fund-address --receipt",
+    },
+    CommandHelp {
+        name: "sign-message",
+        usage: "sign-message --keypair <keypair> <text> | sign-message --keypair <keypair> --file <message>",
+        summary: "Sign a message off-chain (ed25519) to prove control of a key such as the validator identity",
+        summary_ru: "Подписать сообщение вне сети (ed25519), чтобы доказать владение ключом, например identity валидатора",
+    },
+    CommandHelp {
+        name: "verify-message",
+        usage: "verify-message <pubkey> <signature> <text> | verify-message <pubkey> <signature> --file <message>",
+        summary: "Check an off-chain message signature made with sign-message",
+        summary_ru: "Проверить подпись сообщения вне сети, сделанную через sign-message",
+    },
+    CommandHelp {
+        name: "rpc-check",
+        usage: "rpc-check [url...] [--reference <url>] [--max-slot-lag <slots>]",
+        summary: "Probe RPC endpoints for latency, slot lag, version and getClusterNodes with PASS/FAIL each",
+        summary_ru: "Проверить RPC-эндпоинты: задержка, отставание по слотам, версия и getClusterNodes с PASS/FAIL для каждого",
+    },
+    CommandHelp {
+        name: "keygen",
+        usage: "keygen new --outfile <path> [--force] | keygen grind --starts-with <prefix:count> [--ends-with <suffix:count>] [--ignore-case] [--pda] [--threads <n>] | keygen encrypt <in.json> <out.enc> [--force]",
+        summary: "Create a funding wallet, grind a vanity address, or encrypt a keypair file with a passphrase",
+        summary_ru: "Создать кошелёк пополнения, подобрать красивый адрес или зашифровать файл ключа паролем",
+    },
+    CommandHelp {
+        name: "grind",
+        usage: "grind --starts-with <prefix:count> [--ends-with <suffix:count>] [--ignore-case] [--pda] [--threads <n>]",
+        summary: "Grind a vanity wallet address, or with --pda a validator identity whose deposit PDA matches (same as keygen grind)",
+        summary_ru: "Подобрать красивый адрес кошелька или, с --pda, identity валидатора с подходящим PDA депозита (то же, что keygen grind)",
+    },
+    CommandHelp {
+        name: "fees",
+        usage: "fees report [--month YYYY-MM]",
+        summary: "Transaction fees spent in a month (from the audit log) with a forecast",
+        summary_ru: "Комиссии транзакций за месяц (из журнала аудита) с прогнозом",
+    },
+    CommandHelp {
+        name: "audit",
+        usage: "audit stats [--window 24h|7d|all] | audit show [validator_or_alias] [--window 24h|7d|all] [--event <event>] [--status success|failed] [--limit <n>]",
+        summary: "Fundings per validator, SOL moved, failure rate, confirmation latency and fees, or the matching entries (from the audit log)",
+        summary_ru: "Пополнения по валидаторам, переведённые SOL, доля неудач, время подтверждения и комиссии или подходящие записи (из журнала аудита)",
+    },
+    CommandHelp {
+        name: "alias",
+        usage: "alias add <name> <validator_pubkey> [--force] | alias remove <name> | alias list",
+        summary: "Name validators so every command accepts the alias instead of the pubkey",
+        summary_ru: "Дать валидаторам имена, чтобы любая команда принимала алиас вместо pubkey",
+    },
+    CommandHelp {
+        name: "state",
+        usage: "state export --out <bundle.tar.zst> | state import <bundle.tar.zst> [--force]",
+        summary: "Back up or migrate config, aliases, state and audit log (secrets excluded)",
+        summary_ru: "Сохранить или перенести конфигурацию, алиасы, состояние и журнал аудита (без секретов)",
+    },
+    CommandHelp {
+        name: "plan",
+        usage: "plan create <validator_or_alias> <amount> [keypair_or_pubkey] [--out plan.json] | plan create --pairs <validator:amount,...> | --file <transfers.csv> [keypair_or_pubkey] [--out plan.yaml] | plan execute <plan.yaml> [keypair_path] --approve <hash>",
+        summary: "Write a funding plan for approval by a second operator, or run a reviewed YAML batch plan",
+        summary_ru: "Записать план пополнения для одобрения вторым оператором или выполнить проверенный пакетный план YAML",
+    },
+    CommandHelp {
+        name: "approve",
+        usage: "approve <plan.json> --approver <keypair> [--approval-ttl <seconds>] [--out approval.json]",
+        summary: "Countersign a funding plan and write an approval token",
+        summary_ru: "Заверить план пополнения второй подписью и записать токен одобрения",
+    },
+    CommandHelp {
+        name: "apply",
+        usage: "apply <plan.json> [keypair_path] --require-approval-file <approval.json> [--yes]",
+        summary: "Fund as planned after checking the approval",
+        summary_ru: "Пополнить по плану после проверки одобрения",
+    },
+    CommandHelp {
+        name: "run",
+        usage: "run <script.yaml> [--yes]",
+        summary: "Run a playbook of status checks, balances, fundings and notifications with conditions on earlier steps",
+        summary_ru: "Выполнить сценарий из проверок статуса, балансов, пополнений и уведомлений с условиями на предыдущие шаги",
+    },
+    CommandHelp { name: "examples", usage: "examples [operation]", summary: "Print example invocations", summary_ru: "Вывести примеры вызовов" },
+];
+
+/// Example invocations; the first one of each operation is also shown in the usage text
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        command: "pda-address",
+        description: "Derive the deposit PDA of a validator",
+        description_ru: "Вывести PDA депозита валидатора",
+        args: "pda-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL",
+    },
+    Example {
+        command: "pda-address",
+        description: "Derive the PDA of a validator that is not in gossip yet",
+        description_ru: "Вывести PDA валидатора, которого ещё нет в gossip",
+        args: "pda-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --skip-gossip-check",
+    },
+    Example {
+        command: "pda-address",
+        description: "Derive the PDA for a staging deployment of the program",
+        description_ru: "Вывести PDA для тестового развёртывания программы",
+        args: "pda-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --skip-gossip-check --seed-prefix solana_validator_deposit --program-id 11111111111111111111111111111112",
+    },
+    Example {
+        command: "pda-address",
+        description: "Print a Solscan link and a deposit QR code for a mobile wallet",
+        description_ru: "Вывести ссылку Solscan и QR-код депозита для мобильного кошелька",
+        args: "pda-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --explorer solscan --qr",
+    },
+    Example {
+        command: "derive",
+        description: "Derive the deposit PDA from its seeds, as a template for other PDA types",
+        description_ru: "Вывести PDA депозита из его сидов как шаблон для других типов PDA",
+        args: "derive --seed-spec prefix:solana_validator_deposit,pubkey:FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL",
+    },
+    Example {
+        command: "pda-address",
+        description: "Print the messages in Russian (or set DZ_LANG=ru)",
+        description_ru: "Выводить сообщения на русском (или задать DZ_LANG=ru)",
+        args: "pda-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --lang ru",
+    },
+    Example {
+        command: "pda-balance",
+        description: "Show the PDA balance",
+        description_ru: "Показать баланс PDA",
+        args: "pda-balance FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL",
+    },
+    Example {
+        command: "pda-balance",
+        description: "Push the balance to a Prometheus Pushgateway from cron",
+        description_ru: "Отправлять баланс в Prometheus Pushgateway из cron",
+        args: "pda-balance FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --quiet --push-metrics http://localhost:9091",
+    },
+    Example {
+        command: "pda-balance",
+        description: "On the validator host, take the validator from its identity keypair file",
+        description_ru: "На хосте валидатора взять валидатора из файла его identity-ключа",
+        args: "pda-balance --from-identity-file /home/sol/validator-keypair.json",
+    },
+    Example {
+        command: "pda-balance",
+        description: "Balances of the validators listed by another tool, without a temp file",
+        description_ru: "Балансы валидаторов из списка другой программы, без временного файла",
+        args: "pda-balance --stdin < validators.txt",
+    },
+    Example {
+        command: "pda-balance-history",
+        description: "Export 90 days of deposits and claims as CSV for plotting",
+        description_ru: "Выгрузить 90 дней депозитов и выводов в CSV для графика",
+        args: "pda-balance-history FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --days 90 --output csv --out history.csv",
+    },
+    Example {
+        command: "revenue-report",
+        description: "Check that 5% of the inflation rewards of epochs 640 to 650 was deposited",
+        description_ru: "Проверить, что внесено 5% инфляционных наград эпох с 640 по 650",
+        args: "revenue-report FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --epochs 640..650 --share 5",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Fund the PDA with 1.5 SOL",
+        description_ru: "Пополнить PDA на 1.5 SOL",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL /path/to/keypair.json 1.5",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Fund from the wallet of a config profile, requiring a vote account",
+        description_ru: "Пополнить с кошелька профиля конфигурации, требуя голосующий аккаунт",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL 2sol --profile treasury --require-vote-account",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Sign offline with a Ledger (on the air-gapped machine)",
+        description_ru: "Подписать офлайн через Ledger (на изолированной машине)",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL usb://ledger 1.5 --sign-only --blockhash <blockhash>",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Sign offline against a durable nonce, so the transaction does not expire",
+        description_ru: "Подписать офлайн с durable nonce, чтобы транзакция не истекала",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL usb://ledger 1.5 --sign-only --nonce-account <nonce_pubkey> --blockhash <nonce_value>",
+    },
+    Example {
+        command: "pda-watch",
+        description: "Poll every 60 seconds and alert when more than 0.5 SOL/hour flows out",
+        description_ru: "Опрашивать каждые 60 секунд и предупреждать, когда уходит больше 0.5 SOL в час",
+        args: "pda-watch FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL 60 0.5",
+    },
+    Example {
+        command: "pda-watch",
+        description: "Watch with a Prometheus endpoint for scraping",
+        description_ru: "Следить с эндпоинтом Prometheus для сбора метрик",
+        args: "pda-watch FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL 60 0.5 --metrics-port 9184",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Fund from CI, stopping on any warning with exit code 8",
+        description_ru: "Пополнить из CI, останавливаясь на любом предупреждении с кодом выхода 8",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL /path/to/keypair.json 1.5 --strict --yes --output json",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Fund with a priority fee of 5000 micro-lamports per compute unit",
+        description_ru: "Пополнить с приоритетной комиссией 5000 микролампортов за вычислительную единицу",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL /path/to/keypair.json 1.5 --priority-fee 5000",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Fund with a v0 transaction resolving accounts from an address lookup table",
+        description_ru: "Пополнить транзакцией v0, беря аккаунты из таблицы поиска адресов",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL /path/to/keypair.json 1.5 --alt 4syr5pBaboZy4cZyF6sys82uGD7jEvoAP2ZMaoich4fZ",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Fund the validator of an identity keypair, refusing any other validator",
+        description_ru: "Пополнить валидатора identity-ключа, отказываясь пополнять любого другого",
+        args: "pda-fund-address /path/to/keypair.json 1.5 --identity-keypair /path/to/validator-identity.json",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Land the deposit at the start of the next epoch, confirming now",
+        description_ru: "Провести депозит в начале следующей эпохи, подтвердив сейчас",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL /path/to/keypair.json 1.5 --at-epoch-boundary",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Propose the funding from a Squads multisig vault for members to approve",
+        description_ru: "Предложить пополнение из хранилища мультисига Squads на одобрение участникам",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL /path/to/member.json 1.5 --squads <multisig_pubkey>",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Attach a memo to the deposit; it is shown in explorers and kept in the audit log",
+        description_ru: "Приложить к депозиту memo; оно видно в обозревателях и сохраняется в журнале аудита",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL /path/to/keypair.json 1.5 --memo \"epoch 650 top-up\"",
+    },
+    Example {
+        command: "pda-fund-address",
+        description: "Make a scripted top-up safe to retry: a second run with the same key is refused",
+        description_ru: "Сделать скриптовое пополнение безопасным для повтора: второй запуск с тем же ключом отклоняется",
+        args: "pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL /path/to/keypair.json 1.5 --idempotency-key topup-epoch-650 --yes",
+    },
+    Example {
+        command: "pda-fund-many",
+        description: "Top up two validators in one transaction",
+        description_ru: "Пополнить двух валидаторов одной транзакцией",
+        args: "pda-fund-many /path/to/keypair.json --pairs FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL:1.5,main:2.0",
+    },
+    Example {
+        command: "pda-fund-many",
+        description: "Fund every validator,amount line of a CSV file without prompting",
+        description_ru: "Пополнить по каждой строке validator,amount файла CSV без подтверждения",
+        args: "pda-fund-many --file transfers.csv --yes --output json",
+    },
+    Example {
+        command: "pda-fund-many",
+        description: "Finish a run started with --state run.json that died halfway, skipping confirmed transfers",
+        description_ru: "Завершить прерванный запуск, начатый с --state run.json, пропуская подтверждённые переводы",
+        args: "pda-fund-many --resume run.json --yes",
+    },
+    Example {
+        command: "pda-fund-token",
+        description: "Deposit 2.5 USDC, creating the PDA's token account if it is missing",
+        description_ru: "Внести 2.5 USDC, создав токен-аккаунт PDA, если его нет",
+        args: "pda-fund-token FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL /path/to/keypair.json --mint EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v --amount 2.5",
+    },
+    Example {
+        command: "validator-status",
+        description: "Require a vote within the last 150 slots",
+        description_ru: "Требовать голос за последние 150 слотов",
+        args: "validator-status FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --require-recent-votes 150",
+    },
+    Example {
+        command: "validator-status",
+        description: "Check the stake, commission and delinquency rules a funding would apply",
+        description_ru: "Проверить правила стейка, комиссии и делинквентности, которые применит пополнение",
+        args: "validator-status FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --min-activated-stake 5000 --max-commission 10 --reject-delinquent",
+    },
+    Example {
+        command: "validator-status",
+        description: "On the validator host, check the validator answering on localhost:8899",
+        description_ru: "На хосте валидатора проверить валидатора, отвечающего на localhost:8899",
+        args: "validator-status --from-local-rpc",
+    },
+    Example {
+        command: "broadcast",
+        description: "Submit an offline-signed transaction",
+        description_ru: "Отправить транзакцию, подписанную офлайн",
+        args: "broadcast tx.b64",
+    },
+    Example {
+        command: "broadcast",
+        description: "Combine signatures of the funder and the fee payer and submit",
+        description_ru: "Объединить подписи отправителя и плательщика комиссии и отправить",
+        args: "broadcast funder.b64 fee-payer.b64",
+    },
+    Example {
+        command: "pda-batch",
+        description: "Balances of all validators in a file, largest first",
+        description_ru: "Балансы всех валидаторов из файла, начиная с наибольшего",
+        args: "pda-batch pda-balance --file validators.txt --sort balance",
+    },
+    Example {
+        command: "pda-batch",
+        description: "Export balances of the configured aliases to CSV",
+        description_ru: "Выгрузить балансы настроенных алиасов в CSV",
+        args: "pda-batch pda-balance --output csv --out balances.csv",
+    },
+    Example {
+        command: "pda-batch",
+        description: "Print one tab-separated line per validator for a script",
+        description_ru: "Вывести по одной строке с табуляциями на валидатора для скрипта",
+        args: "pda-batch pda-balance --file validators.txt --format '{{validator}}\\t{{pda}}\\t{{balance_sol}}'",
+    },
+    Example {
+        command: "pda-batch",
+        description: "Balance report where every row reflects the same slot or later",
+        description_ru: "Отчёт о балансах, где каждая строка соответствует одному слоту или позже",
+        args: "pda-batch pda-balance --file validators.txt --consistent-snapshot --output json",
+    },
+    Example {
+        command: "pda-balance",
+        description: "Reuse the gossip node list of earlier runs for 5 minutes",
+        description_ru: "Переиспользовать список узлов gossip прошлых запусков в течение 5 минут",
+        args: "pda-balance FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --gossip-cache-file /tmp/gossip.json --gossip-cache-ttl 300",
+    },
+    Example {
+        command: "pda-balance",
+        description: "Balance with its value in US dollars",
+        description_ru: "Баланс со стоимостью в долларах США",
+        args: "pda-balance FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --show-fiat usd",
+    },
+    Example {
+        command: "pda-balance",
+        description: "Query a provider that needs an auth header, through the datacenter proxy",
+        description_ru: "Запрос к провайдеру, требующему заголовок авторизации, через прокси датацентра",
+        args: "pda-balance FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --url https://rpc.example.com --rpc-header 'Authorization: Bearer <token>' --rpc-proxy http://proxy.internal:3128",
+    },
+    Example {
+        command: "pda-batch",
+        description: "Balance report paced for a public endpoint",
+        description_ru: "Отчёт о балансах в темпе, подходящем для публичного эндпоинта",
+        args: "pda-batch pda-balance --file validators.txt --rate-limit 5 --rate-burst 10",
+    },
+    Example {
+        command: "pda-batch",
+        description: "Give up on slow requests after 10 seconds and on the whole report after 2 minutes",
+        description_ru: "Бросать медленные запросы через 10 секунд, а весь отчёт через 2 минуты",
+        args: "pda-batch pda-balance --file validators.txt --rpc-timeout 10 --deadline 120",
+    },
+    Example {
+        command: "pda-batch",
+        description: "Check a large validator list 32 at a time",
+        description_ru: "Проверять большой список валидаторов по 32 за раз",
+        args: "pda-batch pda-balance --file validators.txt --concurrency 32",
+    },
+    Example {
+        command: "allocate",
+        description: "Split 500 SOL by activated stake over the configured validators and write the fundings as a plan",
+        description_ru: "Разделить 500 SOL по активированному стейку между настроенными валидаторами и записать пополнения как план",
+        args: "allocate --budget 500 --weights stake --out plan.yaml",
+    },
+    Example {
+        command: "allocate",
+        description: "Split 500 SOL by the validator,weight lines of a CSV file",
+        description_ru: "Разделить 500 SOL по строкам validator,weight файла CSV",
+        args: "allocate --budget 500 --weights weights.csv",
+    },
+    Example {
+        command: "dashboard",
+        description: "Monitor the configured validators; f tops up the selected PDA with 1 SOL",
+        description_ru: "Следить за настроенными валидаторами; f пополняет выбранный PDA на 1 SOL",
+        args: "dashboard --amount 1",
+    },
+    Example { command: "completion", description: "Install bash completion", description_ru: "Установить автодополнение для bash", args: "completion bash" },
+    Example {
+        command: "wallet",
+        description: "Move funds to a new funding wallet",
+        description_ru: "Перевести средства на новый кошелёк пополнения",
+        args: "wallet rotate --new /path/to/new-keypair.json",
+    },
+    Example {
+        command: "airdrop",
+        description: "Fund a test wallet on devnet",
+        description_ru: "Пополнить тестовый кошелёк в devnet",
+        args: "airdrop /path/to/keypair.json 2 --url https://api.devnet.solana.com",
+    },
+    Example {
+        command: "gossip-snapshot",
+        description: "Save today's node list",
+        description_ru: "Сохранить сегодняшний список узлов",
+        args: "gossip-snapshot --out nodes.json",
+    },
+    Example {
+        command: "gossip-diff",
+        description: "Which validators left gossip since the snapshot",
+        description_ru: "Какие валидаторы пропали из gossip после снимка",
+        args: "gossip-diff nodes.json",
+    },
+    Example {
+        command: "gossip-monitor",
+        description: "Notify when a validator of the list is out of gossip for 10 minutes",
+        description_ru: "Уведомлять, когда валидатор из списка отсутствует в gossip 10 минут",
+        args: "gossip-monitor --file validators.txt --interval 60 --grace 10m",
+    },
+    Example {
+        command: "verify-deposit",
+        description: "Check that a treasury payment reached the validator's PDA",
+        description_ru: "Проверить, что платёж казначейства дошёл до PDA валидатора",
+        args: "verify-deposit 5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL",
+    },
+    Example {
+        command: "verify-receipt",
+        description: "Check a deposit receipt received from a funder",
+        description_ru: "Проверить квитанцию депозита, полученную от отправителя",
+        args: "verify-receipt receipt.json",
+    },
+    Example {
+        command: "sign-message",
+        description: "Prove control of the validator identity for revenue distribution registration",
+        description_ru: "Доказать владение identity валидатора для регистрации в распределении дохода",
+        args: "sign-message --keypair /path/to/validator-keypair.json \"register FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\"",
+    },
+    Example {
+        command: "verify-message",
+        description: "Check a registration signature sent by a validator operator",
+        description_ru: "Проверить подпись регистрации, присланную оператором валидатора",
+        args: "verify-message FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL <signature> \"register FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\"",
+    },
+    Example {
+        command: "rpc-check",
+        description: "Check the configured endpoints before a batch run",
+        description_ru: "Проверить настроенные эндпоинты перед пакетным запуском",
+        args: "rpc-check",
+    },
+    Example {
+        command: "rpc-check",
+        description: "Check a provider endpoint against the public mainnet endpoint",
+        description_ru: "Сравнить эндпоинт провайдера с публичным эндпоинтом mainnet",
+        args: "rpc-check https://rpc.example.com --reference https://api.mainnet-beta.solana.com --max-slot-lag 50",
+    },
+    Example {
+        command: "keygen",
+        description: "Create a funding wallet",
+        description_ru: "Создать кошелёк пополнения",
+        args: "keygen new --outfile payer.json",
+    },
+    Example {
+        command: "keygen",
+        description: "Find a wallet whose address starts with dz (written as <pubkey>.json)",
+        description_ru: "Найти кошелёк с адресом, начинающимся на dz (записывается как <pubkey>.json)",
+        args: "keygen grind --starts-with dz:1",
+    },
+    Example {
+        command: "keygen",
+        description: "Encrypt the funding wallet at rest",
+        description_ru: "Зашифровать кошелёк пополнения на диске",
+        args: "keygen encrypt /path/to/keypair.json /path/to/keypair.enc",
+    },
+    Example {
+        command: "grind",
+        description: "Find a validator identity whose deposit PDA starts with dz",
+        description_ru: "Найти identity валидатора, чей PDA депозита начинается на dz",
+        args: "grind --starts-with dz:1 --pda",
+    },
+    Example { command: "fees", description: "Fees spent in January 2025", description_ru: "Комиссии за январь 2025", args: "fees report --month 2025-01" },
+    Example {
+        command: "audit",
+        description: "Operational overview of the last 7 days",
+        description_ru: "Операционная сводка за последние 7 дней",
+        args: "audit stats --window 7d",
+    },
+    Example {
+        command: "audit",
+        description: "Failed fundings of a validator in the last 24 hours",
+        description_ru: "Неудачные пополнения валидатора за последние 24 часа",
+        args: "audit show FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL --status failed --window 24h",
+    },
+    Example {
+        command: "alias",
+        description: "Name a validator once and use the name everywhere",
+        description_ru: "Один раз назвать валидатора и использовать имя везде",
+        args: "alias add mainnet-01 FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL",
+    },
+    Example {
+        command: "state",
+        description: "Back up the local state before moving to a new host",
+        description_ru: "Сохранить локальное состояние перед переездом на новый хост",
+        args: "state export --out bundle.tar.zst",
+    },
+    Example {
+        command: "state",
+        description: "Restore it on the new host",
+        description_ru: "Восстановить его на новом хосте",
+        args: "state import bundle.tar.zst",
+    },
+    Example {
+        command: "plan",
+        description: "Prepare funding of 250 SOL for approval",
+        description_ru: "Подготовить пополнение на 250 SOL для одобрения",
+        args: "plan create mainnet-01 250 --out plan.json",
+    },
+    Example {
+        command: "plan",
+        description: "Write a reviewable YAML plan for several validators and print its hash",
+        description_ru: "Записать проверяемый план YAML для нескольких валидаторов и вывести его хэш",
+        args: "plan create --file transfers.csv --out plan.yaml",
+    },
+    Example {
+        command: "plan",
+        description: "Run the plan once the reviewer approved its hash",
+        description_ru: "Выполнить план после того, как проверяющий одобрил его хэш",
+        args: "plan execute plan.yaml /path/to/keypair.json --approve <sha256>",
+    },
+    Example {
+        command: "approve",
+        description: "Countersign a plan with a second key",
+        description_ru: "Заверить план вторым ключом",
+        args: "approve plan.json --approver /path/to/approver.json --out approval.json",
+    },
+    Example {
+        command: "apply",
+        description: "Fund as planned once approved",
+        description_ru: "Пополнить по плану после одобрения",
+        args: "apply plan.json --require-approval-file approval.json",
+    },
+    Example {
+        command: "run",
+        description: "Run a reviewed top-up playbook",
+        description_ru: "Выполнить проверенный сценарий пополнения",
+        args: "run weekly-topup.yaml --yes",
+    },
+    Example {
+        command: "examples",
+        description: "Examples for offline signing",
+        description_ru: "Примеры для офлайн-подписи",
+        args: "examples pda-fund-address",
+    },
+];
+
+/// Looks up the help of an operation
+///
+/// # Arguments
+/// * `name` - Operation name
+///
+/// # Returns
+/// * `Option<&CommandHelp>` - Help, or None for an unknown operation
+pub fn command_help(name: &str) -> Option<&'static CommandHelp> {
+    COMMANDS.iter().find(|command| command.name == name)
+}
+
+/// Returns the examples of one operation, or all examples
+///
+/// # Arguments
+/// * `command` - Operation name, or None for all operations
+///
+/// # Returns
+/// * `Result<Vec<&Example>, Error>` - Examples or `Error::InvalidInput` for an unknown operation
+pub fn examples_for(command: Option<&str>) -> Result<Vec<&'static Example>, Error> {
+    match command {
+        None => Ok(EXAMPLES.iter().collect()),
+        Some(name) if command_help(name).is_some() => Ok(EXAMPLES.iter().filter(|example| example.command == name).collect()),
+        Some(name) => Err(Error::InvalidInput(format!("Unknown operation '{}'", name))),
+    }
+}
+
+/// Formats examples as commented shell lines
+///
+/// # Arguments
+/// * `program` - Program name
+/// * `examples` - Examples to print
+///
+/// # Returns
+/// * `String` - One `# description` line and one invocation per example
+pub fn format_examples(program: &str, examples: &[&Example]) -> String {
+    examples
+        .iter()
+        .map(|example| format!("# {}\n{} {}\n", example.description_text(), program, example.args))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats `<operation> --help`: usage, summary, the `--format` fields and the examples of the operation
+///
+/// # Arguments
+/// * `program` - Program name
+/// * `command` - Operation help
+///
+/// # Returns
+/// * `String` - Help text
+pub fn format_command_help(program: &str, command: &CommandHelp) -> String {
+    let mut help = format!("{}: {} {}\n\n{}\n", i18n::text("usage"), program, command.usage, command.summary_text());
+    if let Some(fields) = template_fields(command.name) {
+        help.push_str(&format!("\n{}\n", i18n::text("help-format-fields")));
+        for field in fields {
+            help.push_str(&format!("  {:<18} {}\n", field.name, field.description_text()));
+        }
+    }
+    let examples = EXAMPLES.iter().filter(|example| example.command == command.name).collect::<Vec<_>>();
+    if !examples.is_empty() {
+        help.push_str(&format!("\n{}:\n", i18n::text("examples")));
+        help.push_str(&format_examples(program, &examples));
+    }
+    help
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::{ParsedArgs, SWITCH_FLAGS, VALUE_FLAGS};
+    use crate::completion::OPERATIONS;
+
+    #[test]
+    fn test_examples_parse() {
+        for example in EXAMPLES {
+            let args = example.args.split_whitespace().map(String::from).collect::<Vec<_>>();
+            let parsed = ParsedArgs::parse(&args, VALUE_FLAGS, SWITCH_FLAGS)
+                .unwrap_or_else(|e| panic!("Example '{}' does not parse: {}", example.args, e));
+            assert_eq!(parsed.positionals.first().map(String::as_str), Some(example.command), "{}", example.args);
+            assert!(command_help(example.command).is_some(), "{}", example.args);
+        }
+    }
+
+    #[test]
+    fn test_every_operation_is_documented() {
+        for operation in OPERATIONS {
+            let help = command_help(operation).unwrap_or_else(|| panic!("No help for {}", operation));
+            assert!(help.usage.starts_with(operation));
+            assert!(!help.summary_ru.is_empty(), "No Russian summary for {}", operation);
+            assert!(!examples_for(Some(operation)).unwrap().is_empty(), "No examples for {}", operation);
+        }
+        assert!(EXAMPLES.iter().all(|example| !example.description_ru.is_empty()));
+        assert!(examples_for(Some("pda-list")).unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_format_command_help() {
+        let help = format_command_help("dz_validator_pda", command_help("broadcast").unwrap());
+        assert!(help.starts_with("Usage: dz_validator_pda broadcast <tx.b64>"));
+        assert!(help.contains("Examples:\n# Submit an offline-signed transaction\ndz_validator_pda broadcast tx.b64\n"));
+        assert!(!help.contains("--format"));
+        let help = format_command_help("dz_validator_pda", command_help("pda-balance").unwrap());
+        assert!(help.contains("Fields for --format '{{field}}':\n  validator "));
+        assert!(help.contains("  balance_sol        PDA balance in SOL\n"));
+    }
+}
@@ -0,0 +1,242 @@
+use crate::amount::format_sol;
+use crate::audit::format_timestamp;
+use crate::report::csv_row;
+use crate::rpc::SolanaRpc;
+use crate::{generate_deposit_pda, Error};
+use serde::Serialize;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+/// Days of history of `pda-balance-history` when `--days` is not given
+pub const DEFAULT_HISTORY_DAYS: u64 = 30;
+
+/// Signatures requested per `getSignaturesForAddress` page (the most the method returns)
+pub const SIGNATURE_PAGE_LIMIT: usize = 1000;
+
+/// Columns of the `pda-balance-history --output csv` document
+pub const HISTORY_CSV_COLUMNS: &[&str] =
+    &["time", "unix_time", "slot", "signature", "kind", "change_lamports", "balance_lamports", "balance_sol"];
+
+/// Balance of the deposit PDA after one transaction that changed it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BalancePoint {
+    /// Slot the transaction landed in
+    pub slot: u64,
+    /// Unix timestamp of its block, if the node knows it
+    pub block_time: Option<i64>,
+    /// Transaction signature
+    pub signature: String,
+    /// `deposit` when the balance went up, `withdrawal` when it went down
+    pub kind: String,
+    /// Balance change in lamports
+    pub change_lamports: i64,
+    /// Balance after the transaction in lamports
+    pub balance_lamports: u64,
+}
+
+/// Balance time series of a deposit PDA (`pda-balance-history`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BalanceHistory {
+    /// Validator identity
+    pub validator: String,
+    /// Deposit PDA
+    pub pda: String,
+    /// Unix timestamp of the start of the window
+    pub since: u64,
+    /// Points oldest first
+    pub points: Vec<BalancePoint>,
+    /// Lamports deposited in the window
+    pub deposited_lamports: u64,
+    /// Lamports withdrawn in the window
+    pub withdrawn_lamports: u64,
+    /// Transactions that touched the PDA without changing its balance or that the node no longer serves
+    pub skipped: usize,
+}
+
+impl BalanceHistory {
+    /// Renders the points as CSV with a `HISTORY_CSV_COLUMNS` header, oldest first
+    pub fn to_csv(&self) -> String {
+        let mut csv = csv_row(HISTORY_CSV_COLUMNS);
+        for point in &self.points {
+            let unix_time = point.block_time.and_then(|time| u64::try_from(time).ok());
+            csv.push_str(&csv_row(&[
+                unix_time.map(format_timestamp).unwrap_or_default(),
+                unix_time.map(|time| time.to_string()).unwrap_or_default(),
+                point.slot.to_string(),
+                point.signature.clone(),
+                point.kind.clone(),
+                point.change_lamports.to_string(),
+                point.balance_lamports.to_string(),
+                format_sol(point.balance_lamports),
+            ]));
+        }
+        csv
+    }
+}
+
+/// Reconstructs the balance of a deposit PDA over a time window from its transaction history
+///
+/// Pages through `getSignaturesForAddress` of the PDA, newest first, until a block older than the
+/// window, then fetches every successful transaction and reads the PDA balance before and after it.
+/// Transactions that left the balance unchanged are counted in `skipped`, as are transactions the
+/// node no longer serves. A node with a short history (most public endpoints) returns a shorter series.
+///
+/// # Arguments
+/// * `validator_id` - Validator identity
+/// * `since` - Unix timestamp of the start of the window
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<BalanceHistory, Error>` - Time series oldest first, or error if the history cannot be read
+pub async fn balance_history(validator_id: &Pubkey, since: u64, rpc: &dyn SolanaRpc) -> Result<BalanceHistory, Error> {
+    let pda = generate_deposit_pda(validator_id);
+    let (points, skipped) = balance_points(&pda, |_, block_time| block_time.is_some_and(|time| time < since as i64), rpc).await?;
+    let deposited_lamports = points.iter().filter(|point| point.change_lamports > 0).map(|point| point.change_lamports.unsigned_abs()).sum();
+    let withdrawn_lamports = points.iter().filter(|point| point.change_lamports < 0).map(|point| point.change_lamports.unsigned_abs()).sum();
+    Ok(BalanceHistory {
+        validator: validator_id.to_string(),
+        pda: pda.to_string(),
+        since,
+        points,
+        deposited_lamports,
+        withdrawn_lamports,
+        skipped,
+    })
+}
+
+/// Returns the balance changes of a deposit PDA from a slot on, oldest first
+///
+/// Same as `balance_history`, with the window starting at a slot instead of a time.
+///
+/// # Arguments
+/// * `validator_id` - Validator identity
+/// * `first_slot` - First slot of the window
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Vec<BalancePoint>, Error>` - Points oldest first, or error if the history cannot be read
+pub async fn balance_changes_since_slot(validator_id: &Pubkey, first_slot: u64, rpc: &dyn SolanaRpc) -> Result<Vec<BalancePoint>, Error> {
+    let pda = generate_deposit_pda(validator_id);
+    let (points, _) = balance_points(&pda, |slot, _| slot < first_slot, rpc).await?;
+    Ok(points)
+}
+
+/// Reads the PDA balance changes newer than the first transaction `before_window` returns true for
+///
+/// # Returns
+/// * `Result<(Vec<BalancePoint>, usize), Error>` - Points oldest first and the number of skipped transactions
+async fn balance_points(
+    pda: &Pubkey,
+    before_window: impl Fn(u64, Option<i64>) -> bool,
+    rpc: &dyn SolanaRpc,
+) -> Result<(Vec<BalancePoint>, usize), Error> {
+    let mut signatures = Vec::new();
+    let mut before = None;
+    'pages: loop {
+        let page = rpc
+            .get_signatures_for_address(pda, before, SIGNATURE_PAGE_LIMIT)
+            .await
+            .map_err(|e| Error::from_client_error("Failed to get transactions of the deposit PDA", &e))?;
+        for status in &page {
+            if before_window(status.slot, status.block_time) {
+                break 'pages;
+            }
+            let signature = status
+                .signature
+                .parse::<Signature>()
+                .map_err(|e| Error::Rpc(format!("Invalid signature {} returned by the node: {}", status.signature, e)))?;
+            before = Some(signature);
+            // A failed transaction only charges its fee payer, never the PDA
+            if status.err.is_none() {
+                signatures.push(signature);
+            }
+        }
+        if page.len() < SIGNATURE_PAGE_LIMIT {
+            break;
+        }
+    }
+
+    let mut points = Vec::new();
+    let mut skipped = 0;
+    for signature in signatures.iter().rev() {
+        let fetched = rpc
+            .get_transaction(signature, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| Error::from_client_error("Failed to get transaction", &e))?;
+        let balances = fetched.as_ref().and_then(|fetched| {
+            let index = fetched.account_keys().iter().position(|key| key == pda)?;
+            Some((fetched, *fetched.pre_balances.get(index)?, *fetched.post_balances.get(index)?))
+        });
+        let Some((fetched, pre, post)) = balances.filter(|(_, pre, post)| pre != post) else {
+            skipped += 1;
+            continue;
+        };
+        let change_lamports = post as i64 - pre as i64;
+        points.push(BalancePoint {
+            slot: fetched.slot,
+            block_time: fetched.block_time,
+            signature: signature.to_string(),
+            kind: if change_lamports > 0 { "deposit" } else { "withdrawal" }.to_string(),
+            change_lamports,
+            balance_lamports: post,
+        });
+    }
+    Ok((points, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+    use crate::rpc::FetchedTransaction;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::{Transaction, TransactionError};
+    use solana_system_interface::instruction::transfer;
+
+    fn landed(from: &Keypair, to: &Pubkey, balances: (u64, u64), slot: u64, block_time: i64) -> FetchedTransaction {
+        let instruction = transfer(&from.pubkey(), to, balances.1.abs_diff(balances.0));
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&from.pubkey()), &[from], Hash::new_unique());
+        FetchedTransaction {
+            slot,
+            block_time: Some(block_time),
+            transaction: transaction.into(),
+            error: None,
+            loaded_addresses: Vec::new(),
+            pre_balances: vec![10_000_000_000, balances.0, 1],
+            post_balances: vec![10_000_000_000, balances.1, 1],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_balance_history() {
+        let (payer, validator_id) = (Keypair::new(), Pubkey::new_unique());
+        let pda = generate_deposit_pda(&validator_id);
+        let since = 1_700_000_000;
+        let mut failed = landed(&payer, &pda, (3_000_000_000, 4_000_000_000), 130, since + 300);
+        failed.error = Some(TransactionError::InsufficientFundsForFee);
+        let rpc = MockRpc::new()
+            .with_landed_transaction(landed(&payer, &pda, (0, 1_000_000_000), 90, since - 100), true)
+            .with_landed_transaction(landed(&payer, &pda, (1_000_000_000, 3_000_000_000), 110, since + 100), true)
+            .with_landed_transaction(landed(&payer, &pda, (3_000_000_000, 3_000_000_000), 120, since + 200), true)
+            .with_landed_transaction(failed, true)
+            .with_landed_transaction(landed(&payer, &pda, (3_000_000_000, 2_500_000_000), 140, since + 400), false)
+            .with_landed_transaction(landed(&payer, &Pubkey::new_unique(), (0, 5), 150, since + 500), true);
+
+        let history = balance_history(&validator_id, since as u64, &rpc).await.unwrap();
+        // Oldest first; the transfer before the window, the failed one and the other account are left out
+        let series: Vec<(u64, &str, i64, u64)> = history
+            .points
+            .iter()
+            .map(|point| (point.slot, point.kind.as_str(), point.change_lamports, point.balance_lamports))
+            .collect();
+        assert_eq!(series, vec![(110, "deposit", 2_000_000_000, 3_000_000_000), (140, "withdrawal", -500_000_000, 2_500_000_000)]);
+        assert_eq!((history.deposited_lamports, history.withdrawn_lamports, history.skipped), (2_000_000_000, 500_000_000, 1));
+
+        let csv = history.to_csv();
+        assert!(csv.starts_with("time,unix_time,slot,signature,kind,change_lamports,balance_lamports,balance_sol\r\n"));
+        assert!(csv.contains(&format!("2023-11-14 22:15:00,1700000100,110,{},deposit,2000000000,3000000000,3\r\n", history.points[0].signature)));
+        assert_eq!(csv.lines().count(), 3);
+    }
+}
@@ -0,0 +1,2861 @@
+use crate::precedence::{first_set, SettingSource};
+use crate::report::Warning;
+use crate::Error;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Environment variable with the language of user-facing messages, below `--lang`
+pub const LANG_ENV_VAR: &str = "DZ_LANG";
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Language of user-facing messages
+///
+/// Notification texts, log events, JSON/CSV/YAML output and the audit log stay in English so scripts can parse them;
+/// errors and warnings keep an English `Display` for them and are translated on the terminal only (see `error_text`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lang {
+    /// English (default)
+    #[default]
+    En,
+    /// Russian
+    Ru,
+}
+
+impl Lang {
+    /// Parses a `--lang` value
+    ///
+    /// # Arguments
+    /// * `value` - `en` or `ru`, in any case
+    ///
+    /// # Returns
+    /// * `Result<Lang, Error>` - Language or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "en" => Ok(Lang::En),
+            "ru" => Ok(Lang::Ru),
+            _ => Err(Error::InvalidInput(format!("Invalid language '{}': expected en or ru", value.trim()))),
+        }
+    }
+
+    /// Picks the language by precedence: `--lang`, then `DZ_LANG`, then English
+    ///
+    /// # Arguments
+    /// * `flag` - Value of `--lang`
+    /// * `env` - Value of `DZ_LANG`
+    ///
+    /// # Returns
+    /// * `Result<Lang, Error>` - Language, or `Error::InvalidInput` naming the layer of an invalid value
+    pub fn resolve(flag: Option<&str>, env: Option<&str>) -> Result<Self, Error> {
+        match first_set(flag, env, None) {
+            Some((value, source)) => Lang::parse(value).map_err(|e| match source {
+                SettingSource::Env => Error::InvalidInput(format!("{} (from {})", e.message(), LANG_ENV_VAR)),
+                _ => e,
+            }),
+            None => Ok(Lang::default()),
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Lang::En => "en",
+            Lang::Ru => "ru",
+        })
+    }
+}
+
+/// Sets the language of this process
+///
+/// Must be called before the first message is printed; later calls are rejected.
+///
+/// # Arguments
+/// * `lang` - Language (see `Lang::resolve`)
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if the language was set
+pub fn set_lang(lang: Lang) -> Result<(), Error> {
+    LANG.set(lang).map_err(|_| Error::InvalidInput("Language is already set".to_string()))
+}
+
+/// Returns the language of this process (English if `set_lang` was not called)
+pub fn lang() -> Lang {
+    LANG.get().copied().unwrap_or_default()
+}
+
+/// A user-facing message in every supported language; `{name}` marks a placeholder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Message {
+    /// Key the CLI looks the message up by
+    pub key: &'static str,
+    /// English text
+    pub en: &'static str,
+    /// Russian text
+    pub ru: &'static str,
+}
+
+impl Message {
+    /// Returns the text in a language
+    pub fn text(&self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::En => self.en,
+            Lang::Ru => self.ru,
+        }
+    }
+}
+
+/// Message catalog of the CLI
+pub const MESSAGES: &[Message] = &[
+    Message { key: "error", en: "Error", ru: "Ошибка" },
+    Message { key: "usage", en: "Usage", ru: "Использование" },
+    Message { key: "operations", en: "Operations", ru: "Операции" },
+    Message { key: "example", en: "Example", ru: "Пример" },
+    Message { key: "options", en: "Options", ru: "Параметры" },
+    Message {
+        key: "usage-details",
+        en: "Run '{program} <operation> --help' for details or '{program} examples [operation]' for more examples",
+        ru: "Подробнее: '{program} <operation> --help', больше примеров: '{program} examples [operation]'",
+    },
+    Message {
+        key: "amount-note",
+        en: "Note: Amount is in SOL (e.g., 1.5 or 1.5sol), in lamports (e.g., 1500000000lamports) or ALL",
+        ru: "Примечание: сумма указывается в SOL (например, 1.5 или 1.5sol), в лампортах (например, 1500000000lamports) или ALL",
+    },
+    Message { key: "validator-pubkey", en: "Validator pubkey {validator}", ru: "Pubkey валидатора {validator}" },
+    Message { key: "validator-pubkey-label", en: "Validator pubkey: {validator}", ru: "Pubkey валидатора: {validator}" },
+    Message { key: "pda-address", en: "PDA Address: {pda}", ru: "Адрес PDA: {pda}" },
+    Message { key: "bump-seed", en: "Bump Seed: {bump}", ru: "Bump seed: {bump}" },
+    Message { key: "pda-balance", en: "PDA Balance: {lamports} lamports ({sol} SOL)", ru: "Баланс PDA: {lamports} лампортов ({sol} SOL)" },
+    Message {
+        key: "in-gossip",
+        en: "✓ Validator {validator} is present in Solana gossip network",
+        ru: "✓ Валидатор {validator} присутствует в gossip-сети Solana",
+    },
+    Message {
+        key: "not-in-gossip",
+        en: "✗ Validator {validator} is NOT found in Solana gossip network",
+        ru: "✗ Валидатор {validator} НЕ найден в gossip-сети Solana",
+    },
+    Message {
+        key: "not-in-gossip-hint",
+        en: "This validator may not be active or properly configured.",
+        ru: "Возможно, валидатор не активен или неправильно настроен.",
+    },
+    Message {
+        key: "not-in-gossip-funding",
+        en: "Warning: Funding this PDA may not be effective if the validator is not active.",
+        ru: "Предупреждение: пополнение этого PDA может быть бесполезным, если валидатор не активен.",
+    },
+    Message {
+        key: "not-in-gossip-pda",
+        en: "Warning: This PDA may not be effective if the validator is not active.",
+        ru: "Предупреждение: этот PDA может быть бесполезным, если валидатор не активен.",
+    },
+    Message { key: "gossip-check-failed", en: "✗ Error checking gossip network: {error}", ru: "✗ Ошибка проверки gossip-сети: {error}" },
+    Message {
+        key: "gossip-check-failed-hint",
+        en: "Warning: Unable to verify validator status - proceed with caution.",
+        ru: "Предупреждение: не удалось проверить статус валидатора - действуйте осторожно.",
+    },
+    Message { key: "balance-failed", en: "Error getting balance: {error}", ru: "Ошибка получения баланса: {error}" },
+    Message {
+        key: "funding-all",
+        en: "Funding PDA with all available balance (minus fee and rent-exempt reserve) from keypair: {keypair}",
+        ru: "Пополнение PDA всем доступным балансом (за вычетом комиссии и резерва на аренду) с ключа: {keypair}",
+    },
+    Message { key: "funding-amount", en: "Funding PDA with {amount} from keypair: {keypair}", ru: "Пополнение PDA на {amount} с ключа: {keypair}" },
+    Message { key: "fee-payer", en: "Fee payer: {payer}", ru: "Плательщик комиссии: {payer}" },
+    Message { key: "durable-nonce", en: "Using durable nonce account: {account}", ru: "Используется durable nonce-аккаунт: {account}" },
+    Message { key: "status-pass", en: "✓ Validator {validator} status PASS", ru: "✓ Статус валидатора {validator}: PASS" },
+    Message {
+        key: "status-warn-funded",
+        en: "⚠ Validator {validator} status WARN - funded anyway",
+        ru: "⚠ Статус валидатора {validator}: WARN - пополнение выполнено",
+    },
+    Message { key: "transaction-successful", en: "Transaction successful!", ru: "Транзакция выполнена!" },
+    Message { key: "transaction-signature", en: "Transaction signature: {signature}", ru: "Подпись транзакции: {signature}" },
+    Message {
+        key: "transferred",
+        en: "Transferred {amount} to PDA (fee {fee} lamports)",
+        ru: "Переведено {amount} на PDA (комиссия {fee} лампортов)",
+    },
+    Message { key: "memo", en: "Memo: {memo}", ru: "Memo: {memo}" },
+    Message { key: "funding-failed", en: "Error funding PDA: {error}", ru: "Ошибка пополнения PDA: {error}" },
+    Message {
+        key: "funding-retry-hint",
+        en: "The RPC endpoint may be temporarily unavailable. Check the PDA balance before retrying to avoid funding twice.",
+        ru: "RPC-узел может быть временно недоступен. Перед повтором проверьте баланс PDA, чтобы не пополнить его дважды.",
+    },
+    Message { key: "status-failed", en: "Error getting validator status: {error}", ru: "Ошибка получения статуса валидатора: {error}" },
+    Message { key: "gossip-present", en: "Gossip: present", ru: "Gossip: присутствует" },
+    Message { key: "gossip-not-found", en: "Gossip: NOT found", ru: "Gossip: НЕ найден" },
+    Message { key: "gossip-not-checked", en: "Gossip: not checked", ru: "Gossip: не проверялся" },
+    Message { key: "vote-account", en: "Vote account: {account}", ru: "Аккаунт голосования: {account}" },
+    Message { key: "vote-account-none", en: "Vote account: none", ru: "Аккаунт голосования: нет" },
+    Message { key: "delinquent-yes", en: "Delinquent: yes", ru: "Delinquent: да" },
+    Message { key: "delinquent-no", en: "Delinquent: no", ru: "Delinquent: нет" },
+    Message { key: "activated-stake", en: "Activated stake: {stake}", ru: "Активированный стейк: {stake}" },
+    Message { key: "commission", en: "Commission: {commission}%", ru: "Комиссия: {commission}%" },
+    Message { key: "last-vote", en: "Last vote slot: {slot} ({ago} slots ago)", ru: "Слот последнего голоса: {slot} ({ago} слотов назад)" },
+    Message { key: "epoch", en: "Epoch: {epoch} (slot {slot})", ru: "Эпоха: {epoch} (слот {slot})" },
+    Message { key: "verdict", en: "Verdict: {verdict}", ru: "Итог: {verdict}" },
+    Message { key: "confirm-suffix", en: "[y/N]", ru: "[y/N, д/Н]" },
+    Message { key: "json-not-supported", en: "--output json is not supported by {operation}", ru: "--output json не поддерживается операцией {operation}" },
+    Message {
+        key: "csv-not-supported",
+        en: "--output csv is only supported by pda-batch and pda-balance-history",
+        ru: "--output csv поддерживается только операциями pda-batch и pda-balance-history",
+    },
+    Message {
+        key: "qr-not-supported",
+        en: "--qr is only supported by pda-address with text output",
+        ru: "--qr поддерживается только операцией pda-address с текстовым выводом",
+    },
+    Message { key: "not-with-nice", en: "{operation} is not available with --nice", ru: "{operation} недоступна с --nice" },
+    Message { key: "rate-burst-needs-limit", en: "--rate-burst requires --rate-limit", ru: "--rate-burst требует --rate-limit" },
+    Message {
+        key: "gossip-cache-file-needs-ttl",
+        en: "--gossip-cache-file needs a gossip cache TTL above 0",
+        ru: "--gossip-cache-file требует TTL кэша gossip больше 0",
+    },
+    Message { key: "geyser-needs-endpoint", en: "--backend geyser requires --geyser-endpoint <url>", ru: "--backend geyser требует --geyser-endpoint <url>" },
+    Message {
+        key: "geyser-endpoint-needs-backend",
+        en: "--geyser-endpoint is only used with --backend geyser",
+        ru: "--geyser-endpoint используется только с --backend geyser",
+    },
+    Message { key: "deadline-not-with-dashboard", en: "--deadline is not supported by dashboard", ru: "--deadline не поддерживается операцией dashboard" },
+    Message { key: "completion-needs-shell", en: "completion requires a shell name", ru: "completion требует имя оболочки" },
+    Message { key: "only-supported-by", en: "{flag} is only supported by {operation}", ru: "{flag}: поддерживается только операцией {operation}" },
+    Message { key: "only-used-with", en: "{flag} is only used with {other}", ru: "{flag}: используется только вместе с {other}" },
+    Message { key: "cannot-be-used-with", en: "{flag} cannot be used with {other}", ru: "{flag}: нельзя использовать вместе с {other}" },
+    Message { key: "invalid-flag", en: "Invalid {flag}: {error}", ru: "Недопустимое значение {flag}: {error}" },
+    Message {
+        key: "missing-operation-and-validator",
+        en: "Please provide operation name and validator address as parameters",
+        ru: "Укажите в параметрах операцию и адрес валидатора",
+    },
+    Message { key: "empty-operation", en: "Operation parameter cannot be empty", ru: "Параметр операции не может быть пустым" },
+    Message { key: "empty-validator", en: "Validator address parameter cannot be empty", ru: "Адрес валидатора не может быть пустым" },
+    Message { key: "invalid-validator-format", en: "Invalid validator address format: {error}", ru: "Неверный формат адреса валидатора: {error}" },
+    Message {
+        key: "validator-must-be-base58",
+        en: "Validator address must be a valid base58 encoded string",
+        ru: "Адрес валидатора должен быть корректной строкой в base58",
+    },
+    Message {
+        key: "unknown-operation",
+        en: "Unknown operation '{operation}'. Supported operations: {operations}",
+        ru: "Неизвестная операция '{operation}'. Поддерживаемые операции: {operations}",
+    },
+    Message {
+        key: "fund-needs-amount",
+        en: "pda-fund-address requires keypair path and amount parameters",
+        ru: "pda-fund-address требует путь к ключу и сумму",
+    },
+    Message {
+        key: "config-wallet-note",
+        en: "Note: Without keypair_path the funding wallet from the config file is used",
+        ru: "Примечание: без keypair_path используется кошелёк пополнения из файла конфигурации",
+    },
+    Message { key: "no-funding-wallet", en: "No funding wallet configured in {path}", ru: "В {path} не настроен кошелёк пополнения" },
+    Message {
+        key: "amount-hint",
+        en: "Amount must be a valid number (e.g., 1.5 for 1.5 SOL, 1500000000lamports or ALL)",
+        ru: "Сумма должна быть числом (например, 1.5 для 1.5 SOL, 1500000000lamports или ALL)",
+    },
+    Message {
+        key: "receipt-needs-keypair",
+        en: "--receipt needs the funding wallet as a keypair file or env:// keypair, not {signer}: it is loaded again to sign the receipt",
+        ru: "--receipt требует кошелёк пополнения в виде файла ключа или ключа env://, а не {signer}: он загружается повторно для подписи квитанции",
+    },
+    Message { key: "receipt-written", en: "Receipt for slot {slot} written to {path}", ru: "Квитанция за слот {slot} записана в {path}" },
+    Message { key: "receipt-not-written", en: "Receipt not written to {path}: {error}", ru: "Квитанция не записана в {path}: {error}" },
+    Message {
+        key: "delta-verified",
+        en: "✓ PDA balance changed by exactly {amount} ({pre} → {post} lamports)",
+        ru: "✓ Баланс PDA изменился ровно на {amount} ({pre} → {post} лампортов)",
+    },
+    Message { key: "invalid-interval", en: "Invalid interval: {value}", ru: "Недопустимый интервал: {value}" },
+    Message { key: "interval-hint", en: "Interval must be a positive number of seconds", ru: "Интервал должен быть положительным числом секунд" },
+    Message { key: "invalid-outflow-threshold", en: "Invalid outflow threshold: {value}", ru: "Недопустимый порог оттока: {value}" },
+    Message {
+        key: "outflow-threshold-hint",
+        en: "Threshold must be a non-negative number of SOL per hour (e.g., 0.5)",
+        ru: "Порог должен быть неотрицательным числом SOL в час (например, 0.5)",
+    },
+    Message { key: "must-be-seconds", en: "must be a number of seconds", ru: "нужно число секунд" },
+    Message {
+        key: "watch-balance-rate",
+        en: "PDA Balance: {lamports} lamports ({sol} SOL), change {delta} lamports, net {net} SOL/hour, outflow {outflow} SOL/hour",
+        ru: "Баланс PDA: {lamports} лампортов ({sol} SOL), изменение {delta} лампортов, итого {net} SOL/час, отток {outflow} SOL/час",
+    },
+    Message { key: "alert", en: "ALERT", ru: "ТРЕВОГА" },
+    Message {
+        key: "creation-not-confirmed",
+        en: "Deposit account creation could not be confirmed: {error}",
+        ru: "Не удалось подтвердить создание депозитного аккаунта: {error}",
+    },
+    Message {
+        key: "deposit-not-initialized",
+        en: "The deposit account is not initialized by the program yet; run pda-watch to be alerted if it stays a bare system account for more than {seconds} seconds",
+        ru: "Программа ещё не инициализировала депозитный аккаунт; запустите pda-watch, чтобы получить оповещение, если он останется простым системным аккаунтом дольше {seconds} секунд",
+    },
+    Message { key: "deposit-account-state", en: "Deposit account state: {state}", ru: "Состояние депозитного аккаунта: {state}" },
+    Message { key: "payer", en: "Payer: {payer}", ru: "Плательщик: {payer}" },
+    Message { key: "amount", en: "Amount: {amount}", ru: "Сумма: {amount}" },
+    Message {
+        key: "signature-pending-fee-payer",
+        en: "Transaction signature: pending until the fee payer signs",
+        ru: "Подпись транзакции: появится после подписи плательщика комиссии",
+    },
+    Message { key: "partially-signed", en: "Partially signed; still needs signatures from: {signers}", ru: "Подписана частично; ещё нужны подписи: {signers}" },
+    Message {
+        key: "partially-signed-hint",
+        en: "Each remaining signer runs the same command with --sign-only; pass all outputs to `broadcast` to combine them",
+        ru: "Каждый оставшийся подписант запускает ту же команду с --sign-only; передайте все результаты в `broadcast`, чтобы объединить их",
+    },
+    Message {
+        key: "offline-nonce-hint",
+        en: "Validator status was not checked; the transaction stays valid until nonce account {account} is advanced",
+        ru: "Статус валидатора не проверялся; транзакция действительна, пока nonce-аккаунт {account} не продвинут",
+    },
+    Message {
+        key: "offline-blockhash-hint",
+        en: "Validator status was not checked; submit the transaction with `broadcast` before the blockhash expires",
+        ru: "Статус валидатора не проверялся; отправьте транзакцию через `broadcast`, пока blockhash не истёк",
+    },
+    Message { key: "signing-failed", en: "Error signing transaction: {error}", ru: "Ошибка подписи транзакции: {error}" },
+    Message {
+        key: "proposal-created",
+        en: "Proposal created; nothing is transferred until {threshold} members approve and execute it",
+        ru: "Предложение создано; ничего не будет переведено, пока {threshold} участников не одобрят и не исполнят его",
+    },
+    Message { key: "proposal", en: "Proposal: {proposal}", ru: "Предложение: {proposal}" },
+    Message { key: "transaction-index", en: "Transaction index: {index}", ru: "Индекс транзакции: {index}" },
+    Message { key: "vault", en: "Vault: {vault}", ru: "Хранилище: {vault}" },
+    Message { key: "proposal-failed", en: "Error creating Squads proposal: {error}", ru: "Ошибка создания предложения Squads: {error}" },
+    Message { key: "proposal-summary", en: "Proposal summary:", ru: "Сводка предложения:" },
+    Message {
+        key: "summary-multisig",
+        en: "  Multisig:            {multisig} (threshold {threshold})",
+        ru: "  Мультиподпись:       {multisig} (порог {threshold})",
+    },
+    Message { key: "summary-vault", en: "  Vault:               {vault}", ru: "  Хранилище:           {vault}" },
+    Message { key: "summary-vault-balance", en: "  Vault balance:       {amount}", ru: "  Баланс хранилища:    {amount}" },
+    Message { key: "summary-amount", en: "  Amount:              {amount}", ru: "  Сумма:               {amount}" },
+    Message {
+        key: "summary-proposal-fee",
+        en: "  Fee:                 {fee} lamports (plus rent of the proposal accounts)",
+        ru: "  Комиссия:            {fee} лампортов (плюс аренда аккаунтов предложения)",
+    },
+    Message { key: "summary-transaction-index", en: "  Transaction index:   {index}", ru: "  Индекс транзакции:   {index}" },
+    Message {
+        key: "confirm-proposal",
+        en: "About to propose a transfer of {amount} SOL from vault {vault} to PDA {pda} - proceed?",
+        ru: "Будет предложен перевод {amount} SOL из хранилища {vault} на PDA {pda} - продолжить?",
+    },
+    Message { key: "broadcast-needs-path", en: "broadcast requires the path of a signed transaction", ru: "broadcast требует путь к подписанной транзакции" },
+    Message { key: "broadcast-failed", en: "Error broadcasting transaction: {error}", ru: "Ошибка отправки транзакции: {error}" },
+    Message { key: "airdrop-needs-arguments", en: "airdrop requires a recipient and an amount", ru: "airdrop требует получателя и сумму" },
+    Message { key: "airdropped", en: "Airdropped {amount} to {recipient}", ru: "Получено из крана {amount} на {recipient}" },
+    Message { key: "balance", en: "Balance: {amount}", ru: "Баланс: {amount}" },
+    Message { key: "derive-program", en: "Program: {program}", ru: "Программа: {program}" },
+    Message { key: "derive-seed", en: "Seed:    {seed}", ru: "Seed:      {seed}" },
+    Message { key: "explorer", en: "Explorer: {url}", ru: "Обозреватель: {url}" },
+    Message {
+        key: "verify-deposit-needs-arguments",
+        en: "verify-deposit requires a transaction signature and a validator",
+        ru: "verify-deposit требует подпись транзакции и валидатора",
+    },
+    Message { key: "verify-receipt-needs-file", en: "verify-receipt requires a receipt file", ru: "verify-receipt требует файл квитанции" },
+    Message { key: "field-transaction", en: "Transaction:  {value}", ru: "Транзакция:       {value}" },
+    Message { key: "field-validator", en: "Validator:    {value}", ru: "Валидатор:        {value}" },
+    Message { key: "field-deposit-pda", en: "Deposit PDA:  {value}", ru: "PDA депозита:     {value}" },
+    Message { key: "field-slot", en: "Slot:         {value}", ru: "Слот:             {value}" },
+    Message {
+        key: "field-slot-depth",
+        en: "Slot:         {slot} ({finality}, {depth} slots deep)",
+        ru: "Слот:             {slot} ({finality}, глубина {depth} слотов)",
+    },
+    Message { key: "field-pda-credited", en: "PDA credited: {value}", ru: "Зачислено на PDA: {value}" },
+    Message { key: "field-paid-from", en: "Paid from:    {value}", ru: "Оплачено с:       {value}" },
+    Message { key: "field-error", en: "Error:        {value}", ru: "Ошибка:           {value}" },
+    Message { key: "field-verdict", en: "Verdict:      {verdict} - {reason}", ru: "Итог:             {verdict} - {reason}" },
+    Message { key: "field-block-time", en: "Block time:   {value} UTC", ru: "Время блока:      {value} UTC" },
+    Message { key: "field-amount", en: "Amount:       {value}", ru: "Сумма:            {value}" },
+    Message { key: "field-payer", en: "Payer:        {value}", ru: "Плательщик:       {value}" },
+    Message { key: "finalized", en: "finalized", ru: "финализирована" },
+    Message { key: "confirmed-not-finalized", en: "confirmed, not finalized", ru: "подтверждена, не финализирована" },
+    Message {
+        key: "deposit-verified",
+        en: "the transaction is finalized and credited the deposit PDA",
+        ru: "транзакция финализирована и зачислила средства на PDA депозита",
+    },
+    Message {
+        key: "deposit-not-finalized",
+        en: "the transaction credited the deposit PDA but is not finalized yet; check again later",
+        ru: "транзакция зачислила средства на PDA депозита, но ещё не финализирована; проверьте позже",
+    },
+    Message {
+        key: "deposit-wrong-recipient",
+        en: "the transaction did not credit the deposit PDA of this validator",
+        ru: "транзакция не зачисляла средства на PDA депозита этого валидатора",
+    },
+    Message {
+        key: "deposit-failed",
+        en: "the transaction failed on chain and transferred nothing",
+        ru: "транзакция завершилась ошибкой в сети и ничего не перевела",
+    },
+    Message {
+        key: "deposit-not-found",
+        en: "the node does not know this transaction (never landed, or older than its history)",
+        ru: "узлу неизвестна эта транзакция (не попала в сеть или старше его истории)",
+    },
+    Message { key: "receipt-valid", en: "Receipt signature is valid for payer {payer}", ru: "Подпись квитанции действительна для плательщика {payer}" },
+    Message { key: "warning", en: "Warning", ru: "Предупреждение" },
+    Message { key: "signature-valid", en: "Signature is valid for {pubkey}", ru: "Подпись действительна для {pubkey}" },
+    Message { key: "milliseconds", en: "{ms} ms", ru: "{ms} мс" },
+    Message { key: "slot-behind", en: "{slot} ({lag} behind)", ru: "{slot} (отставание {lag})" },
+    Message { key: "rpc-check-latency", en: "  Latency:       {value}", ru: "  Задержка:      {value}" },
+    Message { key: "rpc-check-slot", en: "  Slot:          {value}", ru: "  Слот:          {value}" },
+    Message { key: "rpc-check-version", en: "  Version:       {value}", ru: "  Версия:        {value}" },
+    Message { key: "rpc-check-nodes", en: "  Cluster nodes: {value}", ru: "  Узлов:         {value}" },
+    Message { key: "reference-slot", en: "Reference slot: {slot}", ru: "Эталонный слот: {slot}" },
+    Message { key: "gossip-snapshot-saved", en: "Saved {nodes} gossip nodes from {url} to {path}", ru: "Сохранено узлов gossip: {nodes}, из {url} в {path}" },
+    Message { key: "live-list", en: "live list", ru: "текущий список" },
+    Message {
+        key: "gossip-diff-summary",
+        en: "Gossip changes from {old} ({old_time}) to {new} ({new_time}): {appeared} appeared, {disappeared} disappeared, {unchanged} unchanged",
+        ru: "Изменения gossip с {old} ({old_time}) по {new} ({new_time}): появилось {appeared}, исчезло {disappeared}, без изменений {unchanged}",
+    },
+    Message {
+        key: "gossip-diff-endpoints",
+        en: "Note: The lists come from different endpoints ({old} and {new})",
+        ru: "Примечание: списки получены с разных узлов ({old} и {new})",
+    },
+    Message { key: "disappeared", en: "Disappeared", ru: "Исчезли" },
+    Message { key: "appeared", en: "Appeared", ru: "Появились" },
+    Message { key: "validators-in-gossip", en: "{present} of {total} validators in gossip", ru: "В gossip {present} из {total} валидаторов" },
+    Message { key: "no-fiat-value", en: "No {currency} value: {error}", ru: "Нет стоимости в {currency}: {error}" },
+    Message { key: "scan-to-deposit", en: "Scan to deposit:", ru: "Отсканируйте для пополнения:" },
+    Message { key: "policy-check", en: "Policy {rule}: {detail}", ru: "Правило {rule}: {detail}" },
+    Message { key: "trace-id", en: "Trace id: {trace_id}", ru: "Trace id: {trace_id}" },
+    Message { key: "interrupted", en: "Interrupted", ru: "Прервано" },
+    Message { key: "deadline-exceeded", en: "Operation deadline of {seconds} seconds exceeded", ru: "Превышен срок выполнения операции ({seconds} секунд)" },
+    Message {
+        key: "may-have-sent",
+        en: "A transaction may already have been sent; check the balances before retrying to avoid funding twice.",
+        ru: "Транзакция могла уже быть отправлена; перед повтором проверьте балансы, чтобы не пополнить дважды.",
+    },
+    Message { key: "written-to", en: "Written to {path}", ru: "Записано в {path}" },
+    Message { key: "validator", en: "Validator: {validator}", ru: "Валидатор: {validator}" },
+    Message { key: "funding-wallet", en: "Funding wallet: {wallet}", ru: "Кошелёк пополнения: {wallet}" },
+    Message { key: "plan-digest", en: "Plan digest: {digest}", ru: "Дайджест плана: {digest}" },
+    Message { key: "plan-hash", en: "Plan hash: {hash}", ru: "Хэш плана: {hash}" },
+    Message { key: "rpc-endpoint", en: "RPC endpoint: {url}", ru: "RPC-узел: {url}" },
+    Message { key: "batch-total", en: "Total: {amount} to {count} PDAs", ru: "Итого: {amount} на {count} PDA" },
+    Message {
+        key: "estimated-fee",
+        en: "Estimated fee: {fee} lamports in {transactions} transaction(s)",
+        ru: "Оценка комиссии: {fee} лампортов, транзакций: {transactions}",
+    },
+    Message { key: "plan-hash-matches", en: "Approval: plan hash matches", ru: "Одобрение: хэш плана совпадает" },
+    Message { key: "approved-by", en: "Approved by {approver} until {expires}", ru: "Одобрено {approver} до {expires}" },
+    Message { key: "funding-summary", en: "Funding summary:", ru: "Сводка пополнения:" },
+    Message { key: "summary-fee", en: "  Fee:                 {fee} lamports", ru: "  Комиссия:            {fee} лампортов" },
+    Message { key: "summary-payer-after", en: "  Payer balance after: {amount}", ru: "  Баланс плательщика:  {amount}" },
+    Message { key: "summary-pda-after", en: "  PDA balance after:   {amount}", ru: "  Баланс PDA после:    {amount}" },
+    Message { key: "summary-memo", en: "  Memo:                {memo}", ru: "  Memo:                {memo}" },
+    Message {
+        key: "summary-creates-pda",
+        en: "  The PDA does not exist yet and will be created by this transfer",
+        ru: "  PDA ещё не существует и будет создан этим переводом",
+    },
+    Message {
+        key: "confirm-transfer",
+        en: "About to transfer {amount} SOL from {payer} to PDA {pda} - proceed?",
+        ru: "Будет переведено {amount} SOL с {payer} на PDA {pda} - продолжить?",
+    },
+    Message { key: "approval-valid", en: "Approval: valid", ru: "Одобрение: действительно" },
+    Message { key: "notification-sent", en: "notification sent", ru: "уведомление отправлено" },
+    Message { key: "step-transferred", en: "transferred {amount} SOL, signature {signature}", ru: "переведено {amount} SOL, подпись {signature}" },
+    Message { key: "running-script", en: "Running script {name} ({steps} steps)", ru: "Запуск сценария {name} (шагов: {steps})" },
+    Message { key: "skipped", en: "skipped", ru: "пропущен" },
+    Message { key: "failed", en: "failed", ru: "ошибка" },
+    Message { key: "script-finished", en: "Script {name} finished", ru: "Сценарий {name} завершён" },
+    Message {
+        key: "script-stopped",
+        en: "Script {name} stopped after {done} of {steps} steps: {error}",
+        ru: "Сценарий {name} остановлен после шага {done} из {steps}: {error}",
+    },
+    Message { key: "opt-skip-gossip-check", en: "Do not require the validator to be in gossip", ru: "Не требовать присутствия валидатора в gossip" },
+    Message {
+        key: "opt-require-vote-account",
+        en: "Require the validator to have a vote account (pda-fund-address, validator-status)",
+        ru: "Требовать у валидатора аккаунт голосования (pda-fund-address, validator-status)",
+    },
+    Message {
+        key: "opt-require-recent-votes",
+        en: "Require a vote within the last <slots> slots (pda-fund-address, validator-status)",
+        ru: "Требовать голос за последние <slots> слотов (pda-fund-address, validator-status)",
+    },
+    Message {
+        key: "opt-min-activated-stake",
+        en: "Require at least this much activated stake (funding operations, validator-status)",
+        ru: "Требовать как минимум столько активированного стейка (операции пополнения, validator-status)",
+    },
+    Message {
+        key: "opt-max-commission",
+        en: "Require a commission of at most <pct> percent (funding operations, validator-status)",
+        ru: "Требовать комиссию не выше <pct> процентов (операции пополнения, validator-status)",
+    },
+    Message {
+        key: "opt-require-uptime",
+        en: "Require at least <pct> percent uptime from vote credits and block production (funding operations, validator-status)",
+        ru: "Требовать не менее <pct> процентов аптайма по кредитам голосования и производству блоков (операции пополнения, validator-status)",
+    },
+    Message {
+        key: "opt-lookback-epochs",
+        en: "Completed epochs --require-uptime looks back over (default 10, at most 64)",
+        ru: "Число завершённых эпох, за которые считается --require-uptime (по умолчанию 10, не больше 64)",
+    },
+    Message {
+        key: "opt-reject-delinquent",
+        en: "Refuse a delinquent vote account instead of warning (funding operations, validator-status)",
+        ru: "Отклонять delinquent-аккаунт голосования вместо предупреждения (операции пополнения, validator-status)",
+    },
+    Message { key: "opt-config", en: "Config file (default: {value})", ru: "Файл конфигурации (по умолчанию: {value})" },
+    Message { key: "opt-profile", en: "Use the funding wallet of a config profile", ru: "Использовать кошелёк пополнения профиля конфигурации" },
+    Message {
+        key: "opt-push-metrics",
+        en: "Push run results to a Prometheus Pushgateway (pda-balance, pda-fund-address)",
+        ru: "Отправлять результаты запуска в Prometheus Pushgateway (pda-balance, pda-fund-address)",
+    },
+    Message { key: "opt-metrics-job", en: "Job label for pushed metrics (default: {value})", ru: "Метка job отправляемых метрик (по умолчанию: {value})" },
+    Message {
+        key: "opt-output",
+        en: "Output format (json: single document with separate warnings and error; csv: pda-batch rows and pda-balance-history points)",
+        ru: "Формат вывода (json: один документ с отдельными предупреждениями и ошибкой; csv: строки pda-batch и точки pda-balance-history)",
+    },
+    Message {
+        key: "opt-format",
+        en: "Print pda-address, pda-balance and pda-batch results through a template such as '{{validator}} {{pda}} {{balance_sol}}' (fields: <operation> --help)",
+        ru: "Выводить результаты pda-address, pda-balance и pda-batch по шаблону, например '{{validator}} {{pda}} {{balance_sol}}' (поля: <operation> --help)",
+    },
+    Message {
+        key: "opt-sign-only",
+        en: "Sign pda-fund-address offline and print the base64 transaction (needs --blockhash)",
+        ru: "Подписать pda-fund-address офлайн и вывести транзакцию в base64 (нужен --blockhash)",
+    },
+    Message {
+        key: "opt-blockhash",
+        en: "Recent blockhash for --sign-only (the nonce value with --nonce-account)",
+        ru: "Недавний blockhash для --sign-only (значение nonce при --nonce-account)",
+    },
+    Message {
+        key: "opt-priority-fee",
+        en: "Priority fee of pda-fund-address in micro-lamports per compute unit; outliers against recent fees need --force",
+        ru: "Приоритетная комиссия pda-fund-address в микролампортах за вычислительную единицу; выбросы относительно недавних комиссий требуют --force",
+    },
+    Message {
+        key: "opt-tx-version",
+        en: "Send fundings as legacy (default) or v0 versioned transactions",
+        ru: "Отправлять пополнения как legacy (по умолчанию) или версионированные транзакции v0",
+    },
+    Message {
+        key: "opt-alt",
+        en: "Resolve accounts of v0 fundings from this address lookup table (implies --tx-version 0)",
+        ru: "Брать аккаунты пополнений v0 из этой адресной таблицы поиска (подразумевает --tx-version 0)",
+    },
+    Message {
+        key: "opt-explorer",
+        en: "Print solscan, solanafm or xray links for the PDA and transaction signatures",
+        ru: "Выводить ссылки solscan, solanafm или xray на PDA и подписи транзакций",
+    },
+    Message { key: "opt-qr", en: "Print the PDA as a deposit QR code (pda-address)", ru: "Вывести PDA как QR-код для пополнения (pda-address)" },
+    Message {
+        key: "opt-window",
+        en: "Time window of audit stats (default: {value}) and audit show (default: all)",
+        ru: "Окно времени audit stats (по умолчанию: {value}) и audit show (по умолчанию: all)",
+    },
+    Message {
+        key: "opt-days",
+        en: "Days of history of pda-balance-history (default: {value})",
+        ru: "Число дней истории pda-balance-history (по умолчанию: {value})",
+    },
+    Message { key: "opt-epochs", en: "Epochs of revenue-report, both included (at most 64)", ru: "Эпохи revenue-report, включая обе границы (не больше 64)" },
+    Message {
+        key: "opt-share",
+        en: "Share of the inflation rewards revenue-report expects to be deposited (e.g., 5 or 2.5)",
+        ru: "Доля инфляционных наград, которую revenue-report ожидает в депозитах (например, 5 или 2.5)",
+    },
+    Message {
+        key: "opt-budget",
+        en: "Total SOL allocate splits between the validators (e.g., 500)",
+        ru: "Общая сумма SOL, которую allocate делит между валидаторами (например, 500)",
+    },
+    Message {
+        key: "opt-weights",
+        en: "Split of allocate: by activated stake, equally, or by the validator,weight lines of a CSV file",
+        ru: "Способ деления allocate: по активированному стейку, поровну или по строкам validator,weight CSV-файла",
+    },
+    Message {
+        key: "opt-payer",
+        en: "Funding wallet allocate checks and plans for (default: the configured funding wallet)",
+        ru: "Кошелёк пополнения, который проверяет и планирует allocate (по умолчанию: настроенный кошелёк пополнения)",
+    },
+    Message {
+        key: "opt-event",
+        en: "Only audit show entries of this event (e.g. pda-fund, pda-fund-token, wallet-rotate)",
+        ru: "Показывать в audit show только записи этого события (например, pda-fund, pda-fund-token, wallet-rotate)",
+    },
+    Message { key: "opt-status", en: "Only audit show entries with this status", ru: "Показывать в audit show только записи с этим статусом" },
+    Message { key: "opt-limit", en: "Show at most <n> audit entries, newest first", ru: "Показать не больше <n> записей аудита, сначала новые" },
+    Message {
+        key: "opt-idempotency-key",
+        en: "Refuse to repeat a funding with the same key (recorded in the memo and audit log; pda-fund-address)",
+        ru: "Не повторять пополнение с тем же ключом (записывается в memo и журнал аудита; pda-fund-address)",
+    },
+    Message {
+        key: "opt-duplicate-window",
+        en: "Refuse a funding with the same payer and amount within this window (default: {value}; pda-fund-address)",
+        ru: "Не повторять пополнение с тем же плательщиком и суммой в этом окне (по умолчанию: {value}; pda-fund-address)",
+    },
+    Message {
+        key: "opt-show-fiat",
+        en: "Also show SOL amounts in fiat (pda-balance, pda-fund-address, apply, audit show)",
+        ru: "Показывать суммы SOL также в фиатной валюте (pda-balance, pda-fund-address, apply, audit show)",
+    },
+    Message {
+        key: "opt-rpc-timeout",
+        en: "Fail an RPC request that takes longer than this (default: {value})",
+        ru: "Прерывать RPC-запрос, который длится дольше (по умолчанию: {value})",
+    },
+    Message {
+        key: "opt-rpc-header",
+        en: "Extra HTTP header of every RPC request, e.g. an auth token (repeatable)",
+        ru: "Дополнительный HTTP-заголовок каждого RPC-запроса, например токен авторизации (можно повторять)",
+    },
+    Message {
+        key: "opt-rpc-proxy",
+        en: "Proxy of RPC requests (default: HTTPS_PROXY/HTTP_PROXY/ALL_PROXY)",
+        ru: "Прокси для RPC-запросов (по умолчанию: HTTPS_PROXY/HTTP_PROXY/ALL_PROXY)",
+    },
+    Message {
+        key: "opt-deadline",
+        en: "Stop the whole operation after this long (pda-batch reports the rows collected so far)",
+        ru: "Остановить всю операцию через это время (pda-batch выводит уже собранные строки)",
+    },
+    Message {
+        key: "opt-reference",
+        en: "Endpoint whose slot rpc-check compares against (default: the highest slot checked)",
+        ru: "Узел, с слотом которого сравнивает rpc-check (по умолчанию: наибольший проверенный слот)",
+    },
+    Message {
+        key: "opt-max-slot-lag",
+        en: "Slots an endpoint may be behind the reference in rpc-check (default: {value})",
+        ru: "Допустимое отставание узла от эталона в слотах в rpc-check (по умолчанию: {value})",
+    },
+    Message {
+        key: "opt-lang",
+        en: "Language of messages (default: {value}, then en; JSON and CSV output stay in English)",
+        ru: "Язык сообщений (по умолчанию: {value}, затем en; вывод JSON и CSV остаётся на английском)",
+    },
+    Message {
+        key: "opt-audit-log",
+        en: "Audit log of money-moving operations (default: config audit_log, then ~/.local/share/dz_validator_pda/audit.log)",
+        ru: "Журнал аудита операций с деньгами (по умолчанию: audit_log из конфигурации, затем ~/.local/share/dz_validator_pda/audit.log)",
+    },
+    Message {
+        key: "opt-nonce-account",
+        en: "Build pda-fund-address against a durable nonce instead of a recent blockhash",
+        ru: "Строить pda-fund-address на durable nonce вместо недавнего blockhash",
+    },
+    Message {
+        key: "opt-nonce-authority",
+        en: "Nonce authority signer (default: the funding wallet)",
+        ru: "Подписант полномочий nonce (по умолчанию: кошелёк пополнения)",
+    },
+    Message {
+        key: "opt-at-epoch-boundary",
+        en: "Wait until the next epoch begins, then send pda-fund-address (checked and confirmed up front)",
+        ru: "Дождаться начала следующей эпохи и отправить pda-fund-address (проверяется и подтверждается заранее)",
+    },
+    Message {
+        key: "opt-at-epoch",
+        en: "Wait until the given epoch begins, then send pda-fund-address",
+        ru: "Дождаться начала указанной эпохи и отправить pda-fund-address",
+    },
+    Message {
+        key: "opt-memo",
+        en: "Attach an SPL Memo to the pda-fund-address transaction (at most {value} bytes)",
+        ru: "Добавить SPL Memo к транзакции pda-fund-address (не больше {value} байт)",
+    },
+    Message {
+        key: "opt-receipt",
+        en: "Write a receipt of the confirmed pda-fund-address transfer, signed by the funding wallet (check with verify-receipt)",
+        ru: "Записать квитанцию подтверждённого перевода pda-fund-address, подписанную кошельком пополнения (проверка через verify-receipt)",
+    },
+    Message {
+        key: "opt-verify-delta",
+        en: "After a pda-fund-address transfer is confirmed, check that the PDA balance went up by exactly the requested amount (exit code 9 otherwise)",
+        ru: "После подтверждения перевода pda-fund-address проверить, что баланс PDA вырос ровно на запрошенную сумму (иначе код выхода 9)",
+    },
+    Message {
+        key: "opt-squads",
+        en: "Propose pda-fund-address as a Squads vault transaction; the funding wallet must be a member",
+        ru: "Предложить pda-fund-address как транзакцию хранилища Squads; кошелёк пополнения должен быть участником",
+    },
+    Message {
+        key: "opt-identity-keypair",
+        en: "Refuse pda-fund-address unless the validator matches this identity keypair (the validator may then be omitted)",
+        ru: "Отклонять pda-fund-address, если валидатор не совпадает с этим identity-ключом (валидатор тогда можно не указывать)",
+    },
+    Message {
+        key: "opt-from-identity-file",
+        en: "Take the validator from its identity keypair file, reading only the public half (the validator may then be omitted)",
+        ru: "Взять валидатора из файла его identity-ключа, читая только публичную часть (валидатор тогда можно не указывать)",
+    },
+    Message {
+        key: "opt-from-local-rpc",
+        en: "Take the validator from the identity of the node at {value} (the validator may then be omitted)",
+        ru: "Взять валидатора из identity узла по адресу {value} (валидатор тогда можно не указывать)",
+    },
+    Message {
+        key: "opt-fee-payer",
+        en: "Wallet paying the fee of pda-fund-address (default: the funding wallet)",
+        ru: "Кошелёк, оплачивающий комиссию pda-fund-address (по умолчанию: кошелёк пополнения)",
+    },
+    Message {
+        key: "opt-keypair",
+        en: "Additional signer for roles given as a pubkey (repeatable), or the signer of sign-message",
+        ru: "Дополнительный подписант для ролей, заданных pubkey (можно повторять), или подписант sign-message",
+    },
+    Message {
+        key: "opt-file",
+        en: "Read validator pubkeys or aliases from a file, one per line (pda-batch, dashboard, gossip-monitor), validator,amount lines (pda-fund-many, plan create), or the binary message of sign-message and verify-message",
+        ru: "Читать pubkey или алиасы валидаторов из файла, по одному в строке (pda-batch, dashboard, gossip-monitor), строки validator,amount (pda-fund-many, plan create) или двоичное сообщение sign-message и verify-message",
+    },
+    Message {
+        key: "opt-interval",
+        en: "Time between gossip-monitor checks (default: {value})",
+        ru: "Время между проверками gossip-monitor (по умолчанию: {value})",
+    },
+    Message {
+        key: "opt-grace",
+        en: "Time a validator may be missing from gossip before gossip-monitor notifies (default: {value})",
+        ru: "Сколько валидатор может отсутствовать в gossip до уведомления gossip-monitor (по умолчанию: {value})",
+    },
+    Message {
+        key: "opt-pairs",
+        en: "Validators and amounts of pda-fund-many or a YAML plan, packed into as few transactions as fit",
+        ru: "Валидаторы и суммы pda-fund-many или YAML-плана, упакованные в как можно меньшее число транзакций",
+    },
+    Message {
+        key: "opt-state",
+        en: "Record the status and signature of every pda-fund-many transfer in a new state file as it runs; last-seen file of gossip-monitor",
+        ru: "Записывать статус и подпись каждого перевода pda-fund-many в новый файл состояния по ходу запуска; файл последнего появления для gossip-monitor",
+    },
+    Message {
+        key: "opt-resume",
+        en: "Continue the pda-fund-many run of a state file, skipping transfers that were confirmed",
+        ru: "Продолжить запуск pda-fund-many из файла состояния, пропуская подтверждённые переводы",
+    },
+    Message { key: "opt-mint", en: "SPL token mint of pda-fund-token", ru: "Mint SPL-токена для pda-fund-token" },
+    Message {
+        key: "opt-amount",
+        en: "Token amount of pda-fund-token in whole tokens (e.g. 2.5), converted with the mint's decimals; top-up amount of dashboard in SOL",
+        ru: "Количество токенов pda-fund-token в целых токенах (например, 2.5), пересчитывается по decimals mint; сумма пополнения dashboard в SOL",
+    },
+    Message {
+        key: "opt-stdin",
+        en: "Read validator pubkeys or aliases from standard input, one per line (pda-address and pda-balance run as pda-batch)",
+        ru: "Читать pubkey или алиасы валидаторов со стандартного ввода, по одному в строке (pda-address и pda-balance выполняются как pda-batch)",
+    },
+    Message {
+        key: "opt-sort",
+        en: "Order of pda-batch rows (default: name, i.e. alias then pubkey)",
+        ru: "Порядок строк pda-batch (по умолчанию: name, то есть алиас, затем pubkey)",
+    },
+    Message {
+        key: "opt-consistent-snapshot",
+        en: "Read every pda-batch balance at the same slot or later",
+        ru: "Читать все балансы pda-batch в одном и том же или более позднем слоте",
+    },
+    Message {
+        key: "opt-concurrency",
+        en: "Validators pda-batch collects at the same time (default: {value})",
+        ru: "Сколько валидаторов pda-batch опрашивает одновременно (по умолчанию: {value})",
+    },
+    Message {
+        key: "opt-refresh",
+        en: "Seconds between two refreshes of the dashboard (default: {value})",
+        ru: "Секунд между обновлениями dashboard (по умолчанию: {value})",
+    },
+    Message {
+        key: "opt-init-timeout",
+        en: "Alert when a new PDA stays a bare system account this long (pda-watch, default: {value})",
+        ru: "Оповещать, если новый PDA остаётся простым системным аккаунтом дольше (pda-watch, по умолчанию: {value})",
+    },
+    Message {
+        key: "opt-metrics-port",
+        en: "Serve Prometheus metrics at http://0.0.0.0:<port>/metrics (pda-watch)",
+        ru: "Отдавать метрики Prometheus по адресу http://0.0.0.0:<port>/metrics (pda-watch)",
+    },
+    Message {
+        key: "opt-nice",
+        en: "Stay within public RPC limits: 1 request/s, gossip cached for 10 minutes, no pda-watch",
+        ru: "Соблюдать лимиты публичного RPC: 1 запрос/с, кэш gossip на 10 минут, без pda-watch",
+    },
+    Message {
+        key: "opt-rate-limit",
+        en: "Client-side RPC request rate shared by the whole run (overrides the --nice rate)",
+        ru: "Частота RPC-запросов на стороне клиента на весь запуск (заменяет частоту --nice)",
+    },
+    Message {
+        key: "opt-rate-burst",
+        en: "Requests sent back to back before --rate-limit pacing starts (default: the rate)",
+        ru: "Сколько запросов отправляется подряд до начала ограничения --rate-limit (по умолчанию: равно частоте)",
+    },
+    Message {
+        key: "opt-gossip-cache-ttl",
+        en: "Reuse the gossip node list this long; 0 fetches it for every check (default: 0, {value} for pda-batch and --nice)",
+        ru: "Сколько переиспользовать список узлов gossip; 0 запрашивает его при каждой проверке (по умолчанию: 0, {value} для pda-batch и --nice)",
+    },
+    Message {
+        key: "opt-gossip-cache-file",
+        en: "Keep the gossip node list in a file between runs, within the gossip cache TTL",
+        ru: "Хранить список узлов gossip в файле между запусками в пределах TTL кэша gossip",
+    },
+    Message {
+        key: "opt-backend",
+        en: "Source of gossip checks and pda-watch updates (geyser needs the geyser feature; default: rpc)",
+        ru: "Источник проверок gossip и обновлений pda-watch (geyser требует функцию geyser; по умолчанию: rpc)",
+    },
+    Message {
+        key: "opt-geyser-endpoint",
+        en: "Yellowstone gRPC endpoint of --backend geyser (token from {value})",
+        ru: "gRPC-узел Yellowstone для --backend geyser (токен из {value})",
+    },
+    Message {
+        key: "opt-url",
+        en: "RPC endpoint (default: DZ_RPC_URL, then rpc_url of the config file, then {value})",
+        ru: "RPC-узел (по умолчанию: DZ_RPC_URL, затем rpc_url из файла конфигурации, затем {value})",
+    },
+    Message {
+        key: "opt-commitment",
+        en: "Commitment of reads, preflight and confirmation: processed, confirmed or finalized (default: DZ_COMMITMENT, then the config file, then finalized)",
+        ru: "Уровень commitment для чтения, preflight и подтверждения: processed, confirmed или finalized (по умолчанию: DZ_COMMITMENT, затем файл конфигурации, затем finalized)",
+    },
+    Message {
+        key: "opt-out",
+        en: "Write the plan (JSON or YAML), approval or CSV to a file instead of stdout (plan create, allocate, approve, pda-batch and pda-balance-history --output csv); bundle path of state export; snapshot path of gossip-snapshot",
+        ru: "Записать план (JSON или YAML), одобрение или CSV в файл вместо stdout (plan create, allocate, approve, pda-batch и pda-balance-history --output csv); путь архива state export; путь снимка gossip-snapshot",
+    },
+    Message { key: "opt-approver", en: "Approver signer (approve)", ru: "Подписант одобрения (approve)" },
+    Message {
+        key: "opt-approve",
+        en: "SHA-256 of the reviewed plan file; plan execute refuses any other file",
+        ru: "SHA-256 проверенного файла плана; plan execute отклоняет любой другой файл",
+    },
+    Message {
+        key: "opt-approval-ttl",
+        en: "How long an approval stays valid (approve, default: {value})",
+        ru: "Сколько действует одобрение (approve, по умолчанию: {value})",
+    },
+    Message {
+        key: "opt-require-approval-file",
+        en: "Approval token that apply checks offline before funding",
+        ru: "Токен одобрения, который apply проверяет офлайн перед пополнением",
+    },
+    Message {
+        key: "opt-yes",
+        en: "Do not ask for confirmation before sending (pda-fund-address, pda-fund-many, pda-fund-token, apply, run; alias --assume-yes)",
+        ru: "Не спрашивать подтверждение перед отправкой (pda-fund-address, pda-fund-many, pda-fund-token, apply, run; синоним --assume-yes)",
+    },
+    Message {
+        key: "opt-seed-prefix",
+        en: "First PDA seed for forks or staging deployments (pda-address, default: {value})",
+        ru: "Первый seed PDA для форков и тестовых развёртываний (pda-address, по умолчанию: {value})",
+    },
+    Message {
+        key: "opt-program-id",
+        en: "Revenue distribution program to derive the PDA under (pda-address, derive)",
+        ru: "Программа распределения доходов, под которой выводится PDA (pda-address, derive)",
+    },
+    Message {
+        key: "opt-seed-spec",
+        en: "Seeds of derive in order: prefix:/str: text, pubkey: pubkey or alias, u64: number, hex: bytes",
+        ru: "Seed для derive по порядку: prefix:/str: текст, pubkey: pubkey или алиас, u64: число, hex: байты",
+    },
+    Message {
+        key: "opt-strict",
+        en: "Fail with exit code 8 on any warning (not in gossip, below rent exemption, stale RPC, ...)",
+        ru: "Завершаться с кодом выхода 8 при любом предупреждении (нет в gossip, ниже освобождения от аренды, отстающий RPC, ...)",
+    },
+    Message {
+        key: "opt-override-budget",
+        en: "Send a funding that exceeds a [budget] cap of the config, with a budget_overridden warning",
+        ru: "Отправить пополнение сверх лимита [budget] конфигурации, с предупреждением budget_overridden",
+    },
+    Message {
+        key: "opt-force",
+        en: "Fund even if the PDA would stay below rent exemption (pda-fund-address, pda-fund-many, apply) or the funding looks like a duplicate (pda-fund-address); overwrite the output of keygen new and keygen encrypt; point an existing alias at another validator (alias add)",
+        ru: "Пополнять, даже если PDA останется ниже освобождения от аренды (pda-fund-address, pda-fund-many, apply) или пополнение похоже на повтор (pda-fund-address); перезаписывать результат keygen new и keygen encrypt; перенаправлять существующий алиас на другого валидатора (alias add)",
+    },
+    Message { key: "opt-outfile", en: "Keypair file written by keygen new", ru: "Файл ключа, который записывает keygen new" },
+    Message {
+        key: "opt-starts-with",
+        en: "keygen grind: find count keypairs whose address starts with prefix (repeatable; also --ends-with <suffix:count> and --starts-and-ends-with <prefix:suffix:count>)",
+        ru: "keygen grind: найти count ключей, адрес которых начинается с prefix (можно повторять; также --ends-with <suffix:count> и --starts-and-ends-with <prefix:suffix:count>)",
+    },
+    Message { key: "opt-ignore-case", en: "keygen grind: match letters in either case", ru: "keygen grind: не учитывать регистр букв" },
+    Message { key: "opt-threads", en: "keygen grind worker threads (default: one per CPU)", ru: "Число потоков keygen grind (по умолчанию: по одному на CPU)" },
+    Message {
+        key: "opt-trace-id",
+        en: "Trace id of this run for logs, audit entries, notifications and RPC requests (default: random)",
+        ru: "Trace id запуска для логов, записей аудита, уведомлений и RPC-запросов (по умолчанию: случайный)",
+    },
+    Message {
+        key: "opt-timing",
+        en: "Print latency, errors and retries per RPC method at the end of the run (JSON: timing)",
+        ru: "Вывести в конце запуска задержки, ошибки и повторы по каждому RPC-методу (JSON: timing)",
+    },
+    Message {
+        key: "opt-verbose",
+        en: "Log debug (-v) or trace (-vv) events to stderr (default: info, or RUST_LOG)",
+        ru: "Писать в stderr события debug (-v) или trace (-vv) (по умолчанию: info или RUST_LOG)",
+    },
+    Message { key: "opt-quiet", en: "Log only errors to stderr", ru: "Писать в stderr только ошибки" },
+    Message {
+        key: "opt-help",
+        en: "Show this text, or the usage and examples of an operation",
+        ru: "Показать этот текст или использование и примеры операции",
+    },
+    Message {
+        key: "summary-transfers",
+        en: "  Transfers:           {transfers} in {transactions} transaction(s)",
+        ru: "  Переводов:           {transfers}, транзакций: {transactions}",
+    },
+    Message { key: "summary-total", en: "  Total amount:        {amount}", ru: "  Общая сумма:         {amount}" },
+    Message {
+        key: "confirm-batch",
+        en: "About to transfer {amount} SOL from {payer} to {count} PDAs - proceed?",
+        ru: "Будет переведено {amount} SOL с {payer} на {count} PDA - продолжить?",
+    },
+    Message { key: "column-validator", en: "VALIDATOR", ru: "ВАЛИДАТОР" },
+    Message { key: "column-amount-sol", en: "AMOUNT (SOL)", ru: "СУММА (SOL)" },
+    Message { key: "column-status", en: "STATUS", ru: "СТАТУС" },
+    Message { key: "column-signature", en: "SIGNATURE", ru: "ПОДПИСЬ" },
+    Message { key: "error-lower", en: "error", ru: "ошибка" },
+    Message {
+        key: "batch-confirmed",
+        en: "Confirmed {confirmed} of {total} transfers ({amount} SOL) in {transactions} transaction(s)",
+        ru: "Подтверждено {confirmed} из {total} переводов ({amount} SOL), транзакций: {transactions}",
+    },
+    Message {
+        key: "skipped-confirmed",
+        en: "Skipped {count} transfer(s) confirmed by an earlier run",
+        ru: "Пропущено переводов, подтверждённых прошлым запуском: {count}",
+    },
+    Message {
+        key: "skipped-unconfirmed",
+        en: "Skipped {count} unconfirmed transfer(s) of an earlier run; check their PDA balances",
+        ru: "Пропущено неподтверждённых переводов прошлого запуска: {count}; проверьте балансы их PDA",
+    },
+    Message { key: "retry-failed", en: "Retry the failed transfers with: {flags}", ru: "Повторить неудавшиеся переводы: {flags}" },
+    Message {
+        key: "unconfirmed-may-land",
+        en: "Unconfirmed transfers may still land; check their PDA balances before sending them again.",
+        ru: "Неподтверждённые переводы ещё могут попасть в сеть; проверьте балансы их PDA перед повторной отправкой.",
+    },
+    Message { key: "fund-many-failed", en: "Error funding PDAs: {error}", ru: "Ошибка пополнения PDA: {error}" },
+    Message {
+        key: "nothing-left",
+        en: "Nothing left to send in {path}: {confirmed} transfer(s) confirmed, {unconfirmed} unconfirmed",
+        ru: "В {path} больше нечего отправлять: подтверждено переводов {confirmed}, не подтверждено {unconfirmed}",
+    },
+    Message {
+        key: "batch-partial",
+        en: "collected {collected} of {requested} validators; the rows above are partial",
+        ru: "собрано {collected} из {requested} валидаторов; строки выше неполные",
+    },
+    Message {
+        key: "summary-tokens",
+        en: "  Amount:              {amount} tokens ({base_units} base units, {decimals} decimals)",
+        ru: "  Сумма:               {amount} токенов ({base_units} базовых единиц, decimals {decimals})",
+    },
+    Message { key: "summary-mint", en: "  Mint:                {mint}", ru: "  Mint:                {mint}" },
+    Message { key: "summary-token-account", en: "  PDA token account:   {account}", ru: "  Токен-аккаунт PDA:   {account}" },
+    Message { key: "summary-payer-tokens-after", en: "  Payer tokens after:  {amount}", ru: "  Токенов у плательщика после: {amount}" },
+    Message {
+        key: "summary-creates-token-account",
+        en: "  The PDA token account does not exist yet and will be created ({rent} lamports rent)",
+        ru: "  Токен-аккаунт PDA ещё не существует и будет создан (аренда {rent} лампортов)",
+    },
+    Message {
+        key: "confirm-token-transfer",
+        en: "About to transfer {amount} tokens from {payer} to PDA {pda} - proceed?",
+        ru: "Будет переведено {amount} токенов с {payer} на PDA {pda} - продолжить?",
+    },
+    Message { key: "token-account-created", en: "Created PDA token account: {account}", ru: "Создан токен-аккаунт PDA: {account}" },
+    Message {
+        key: "tokens-transferred",
+        en: "Transferred {amount} tokens to PDA token account {account} (fee {fee} lamports)",
+        ru: "Переведено {amount} токенов на токен-аккаунт PDA {account} (комиссия {fee} лампортов)",
+    },
+    Message { key: "token-funding-failed", en: "Error funding PDA token account: {error}", ru: "Ошибка пополнения токен-аккаунта PDA: {error}" },
+    Message {
+        key: "unknown-batch-operation",
+        en: "Unknown batch operation. Supported operations: pda-address, pda-balance",
+        ru: "Неизвестная пакетная операция. Поддерживаемые операции: pda-address, pda-balance",
+    },
+    Message {
+        key: "batch-aliases-note",
+        en: "Note: Without validators all aliases from the [validators] table of the config file are used",
+        ru: "Примечание: без валидаторов используются все алиасы из таблицы [validators] файла конфигурации",
+    },
+    Message {
+        key: "out-needs-csv",
+        en: "--out is only supported with --output csv for {operation}",
+        ru: "--out поддерживается только с --output csv для {operation}",
+    },
+    Message {
+        key: "consistent-snapshot-needs-balances",
+        en: "--consistent-snapshot applies to balances; use it with pda-batch pda-balance or --sort balance",
+        ru: "--consistent-snapshot относится к балансам; используйте его с pda-batch pda-balance или --sort balance",
+    },
+    Message {
+        key: "consistent-snapshot",
+        en: "Consistent snapshot: every balance reflects slot {slot} or later",
+        ru: "Согласованный снимок: каждый баланс соответствует слоту {slot} или позже",
+    },
+    Message { key: "needs-validator", en: "{operation} requires a validator", ru: "{operation} требует валидатора" },
+    Message { key: "history-validator", en: "Validator:   {value}", ru: "Валидатор:    {value}" },
+    Message { key: "history-deposit-pda", en: "Deposit PDA: {value}", ru: "PDA депозита: {value}" },
+    Message { key: "history-window", en: "Window:      last {days} days (since {since} UTC)", ru: "Окно:         последние {days} дн. (с {since} UTC)" },
+    Message { key: "history-no-changes", en: "No balance changes in the window", ru: "В этом окне баланс не менялся" },
+    Message { key: "heading-signature", en: "Signature", ru: "Подпись" },
+    Message { key: "heading-status", en: "Status", ru: "Статус" },
+    Message { key: "heading-validator", en: "Validator", ru: "Валидатор" },
+    Message { key: "plan-written-to", en: "Plan written to {path}", ru: "План записан в {path}" },
+    Message { key: "column-time", en: "Time (UTC)", ru: "Время (UTC)" },
+    Message { key: "column-slot", en: "Slot", ru: "Слот" },
+    Message { key: "column-kind", en: "Kind", ru: "Тип" },
+    Message { key: "column-change-sol", en: "Change (SOL)", ru: "Изменение (SOL)" },
+    Message { key: "column-balance-sol", en: "Balance (SOL)", ru: "Баланс (SOL)" },
+    Message { key: "history-deposited", en: "Deposited:   {value}", ru: "Внесено:      {value}" },
+    Message { key: "history-withdrawn", en: "Withdrawn:   {value}", ru: "Выведено:     {value}" },
+    Message {
+        key: "history-skipped",
+        en: "Skipped:     {count} transactions without a balance change or no longer served by the node",
+        ru: "Пропущено:    {count} транзакций без изменения баланса или уже не отдаваемых узлом",
+    },
+    Message { key: "revenue-report-needs-epochs", en: "revenue-report requires a validator and --epochs", ru: "revenue-report требует валидатора и --epochs" },
+    Message { key: "revenue-vote-account", en: "Vote account: {value}", ru: "Голосующий аккаунт: {value}" },
+    Message { key: "revenue-no-vote-account", en: "none (no inflation rewards)", ru: "нет (нет инфляционных наград)" },
+    Message { key: "revenue-share", en: "Share:        {share} of inflation rewards", ru: "Доля:             {share} инфляционных наград" },
+    Message { key: "column-epoch", en: "Epoch", ru: "Эпоха" },
+    Message { key: "column-opening-sol", en: "Opening (SOL)", ru: "Начало (SOL)" },
+    Message { key: "column-deposited", en: "Deposited", ru: "Внесено" },
+    Message { key: "column-claimed", en: "Claimed", ru: "Выведено" },
+    Message { key: "column-closing-sol", en: "Closing (SOL)", ru: "Конец (SOL)" },
+    Message { key: "column-rewards", en: "Rewards", ru: "Награды" },
+    Message { key: "column-expected", en: "Expected", ru: "Ожидалось" },
+    Message { key: "revenue-deposited", en: "Deposited:    {value}", ru: "Внесено:          {value}" },
+    Message { key: "revenue-claimed", en: "Claimed:      {value}", ru: "Выведено:         {value}" },
+    Message {
+        key: "revenue-rewards",
+        en: "Rewards:      {value} (inflation rewards of completed epochs; block rewards are not included)",
+        ru: "Награды:          {value} (инфляционные награды завершённых эпох; награды за блоки не учитываются)",
+    },
+    Message { key: "revenue-expected", en: "Expected:     {value} -> {status}", ru: "Ожидалось:        {value} -> {status}" },
+    Message { key: "allocate-needs-budget", en: "allocate requires --budget and --weights", ru: "allocate требует --budget и --weights" },
+    Message { key: "allocate-budget", en: "Budget:      {budget}, weights: {weights}", ru: "Бюджет:        {budget}, веса: {weights}" },
+    Message { key: "column-share", en: "Share", ru: "Доля" },
+    Message { key: "column-allocation-sol", en: "Allocation (SOL)", ru: "Выделено (SOL)" },
+    Message { key: "column-funding-sol", en: "Funding (SOL)", ru: "Пополнение (SOL)" },
+    Message { key: "allocate-funding", en: "Funding:     {value}", ru: "Пополнение:    {value}" },
+    Message { key: "allocate-unallocated", en: "Unallocated: {value} (already held by the PDAs)", ru: "Не выделено:   {value} (уже лежит на PDA)" },
+    Message { key: "allocate-wallet", en: "Funding wallet: {wallet}", ru: "Кошелёк пополнения: {wallet}" },
+    Message { key: "allocate-wallet-balance", en: "  Balance:       {value}", ru: "  Баланс:            {value}" },
+    Message {
+        key: "allocate-estimated-fee",
+        en: "  Estimated fee: {fee} lamports in {transactions} transaction(s)",
+        ru: "  Оценка комиссии:   {fee} лампортов в {transactions} транзакц.",
+    },
+    Message { key: "allocate-shortfall", en: "  Shortfall:     {value}", ru: "  Не хватает:        {value}" },
+    Message { key: "dashboard-press-enter", en: "Press Enter to return to the dashboard", ru: "Нажмите Enter, чтобы вернуться к панели" },
+    Message {
+        key: "unknown-command",
+        en: "Unknown {command} command. Supported commands: {commands}",
+        ru: "Неизвестная команда {command}. Поддерживаемые команды: {commands}",
+    },
+    Message { key: "keypair-encrypted", en: "Encrypted keypair {pubkey} written to {path}", ru: "Зашифрованный ключ {pubkey} записан в {path}" },
+    Message {
+        key: "keypair-encrypted-hint",
+        en: "Use it as {encrypted} anywhere a keypair path is accepted; remove {plain} once it is backed up",
+        ru: "Используйте {encrypted} везде, где принимается путь к ключу; удалите {plain}, когда сделаете резервную копию",
+    },
+    Message { key: "keypair-new-written", en: "Wrote new keypair {pubkey} to {path}", ru: "Новый ключ {pubkey} записан в {path}" },
+    Message {
+        key: "keypair-no-seed-phrase",
+        en: "There is no seed phrase: back up {path} (or encrypt it with keygen encrypt) before funding it",
+        ru: "Сид-фразы нет: сделайте резервную копию {path} (или зашифруйте его через keygen encrypt) до пополнения",
+    },
+    Message { key: "keypair-written", en: "Wrote keypair {pubkey} to {path}", ru: "Ключ {pubkey} записан в {path}" },
+    Message { key: "keypairs-searched", en: "Searched {count} keypairs", ru: "Перебрано ключей: {count}" },
+    Message {
+        key: "grind-searching",
+        en: "Searching on {threads} threads; Ctrl-C stops the search and keeps the keypairs written so far",
+        ru: "Поиск в {threads} потоков; Ctrl-C останавливает поиск и сохраняет уже записанные ключи",
+    },
+    Message { key: "state-exported", en: "Exported {bundle} ({files})", ru: "Экспортировано в {bundle} ({files})" },
+    Message { key: "state-imported", en: "Imported {bundle} ({files})", ru: "Импортировано из {bundle} ({files})" },
+    Message { key: "state-no-files", en: "no files", ru: "нет файлов" },
+    Message {
+        key: "state-secrets-excluded",
+        en: "Secrets not included, set them again in the config: {secrets}",
+        ru: "Секреты не включены, задайте их снова в конфигурации: {secrets}",
+    },
+    Message { key: "no-aliases", en: "No aliases configured in {path}", ru: "В {path} не настроены алиасы" },
+    Message { key: "alias-removed", en: "Removed alias {alias} ({validator})", ru: "Алиас {alias} удалён ({validator})" },
+    Message {
+        key: "alias-replaced",
+        en: "Alias {alias} now points at {validator} (was {previous})",
+        ru: "Алиас {alias} теперь указывает на {validator} (был {previous})",
+    },
+    Message { key: "alias-added", en: "Added alias {alias} for {validator}", ru: "Добавлен алиас {alias} для {validator}" },
+    Message { key: "fees-month", en: "Month: {month}", ru: "Месяц: {month}" },
+    Message { key: "fees-transactions", en: "Transactions: {count}", ru: "Транзакций: {count}" },
+    Message { key: "fees-spent", en: "Fees spent: {value}", ru: "Потрачено на комиссии: {value}" },
+    Message { key: "fees-cadence", en: "Cadence: {rate} lamports/day over the last {days} days", ru: "Темп: {rate} лампортов/день за последние {days} дн." },
+    Message { key: "fees-forecast", en: "Forecast for the month: {value}", ru: "Прогноз на месяц: {value}" },
+    Message { key: "fees-forecast-month-over", en: "Forecast for the month: - (month is over)", ru: "Прогноз на месяц: - (месяц закончился)" },
+    Message { key: "audit-no-entries", en: "No matching entries in {path}", ru: "В {path} нет подходящих записей" },
+    Message { key: "stats-all-entries", en: "all entries", ru: "все записи" },
+    Message { key: "stats-last", en: "last {window}", ru: "последние {window}" },
+    Message { key: "stats-window", en: "Window: {window}", ru: "Окно: {window}" },
+    Message {
+        key: "stats-fundings",
+        en: "Fundings: {fundings} ({failed} failed, {rate}% failure rate)",
+        ru: "Пополнений: {fundings} (неудачных {failed}, доля неудач {rate}%)",
+    },
+    Message { key: "stats-moved", en: "SOL moved: {value}", ru: "Переведено SOL: {value}" },
+    Message { key: "stats-fees", en: "Fees: {value}", ru: "Комиссии: {value}" },
+    Message {
+        key: "stats-latency",
+        en: "Mean confirmation latency: {seconds}s over {count} confirmations",
+        ru: "Среднее время подтверждения: {seconds} с по {count} подтверждениям",
+    },
+    Message {
+        key: "stats-no-latency",
+        en: "Mean confirmation latency: - (no confirmations recorded)",
+        ru: "Среднее время подтверждения: - (подтверждений не записано)",
+    },
+    Message {
+        key: "stats-validator",
+        en: "  {validator}: {fundings} fundings, {failed} failed, {moved} moved, fees {fee} lamports",
+        ru: "  {validator}: пополнений {fundings}, неудачных {failed}, переведено {moved}, комиссии {fee} лампортов",
+    },
+    Message { key: "wallet-rotation-failed", en: "Error rotating wallet: {error}", ru: "Ошибка ротации кошелька: {error}" },
+    Message { key: "wallet-old", en: "Old funding wallet: {pubkey} ({path})", ru: "Старый кошелёк пополнения: {pubkey} ({path})" },
+    Message { key: "wallet-new", en: "New funding wallet: {pubkey} ({path})", ru: "Новый кошелёк пополнения: {pubkey} ({path})" },
+    Message {
+        key: "wallet-swept",
+        en: "Swept {amount} to the new wallet (fee {fee} lamports)",
+        ru: "На новый кошелёк переведено {amount} (комиссия {fee} лампортов)",
+    },
+    Message {
+        key: "wallet-nothing-to-sweep",
+        en: "Old wallet balance does not cover the transfer fee - nothing to sweep",
+        ru: "Баланс старого кошелька не покрывает комиссию перевода - переводить нечего",
+    },
+    Message { key: "config-updated", en: "Config updated: {path}", ru: "Конфигурация обновлена: {path}" },
+    Message { key: "wallet-rotation-recorded", en: "Rotation recorded in audit log: {path}", ru: "Ротация записана в журнал аудита: {path}" },
+    Message {
+        key: "wallet-first-funding-verified",
+        en: "The first funding from the new wallet will be verified on-chain",
+        ru: "Первое пополнение с нового кошелька будет проверено в сети",
+    },
+    Message { key: "wallet-funding-confirmed", en: "Funding from the new wallet confirmed", ru: "Пополнение с нового кошелька подтверждено" },
+    Message {
+        key: "wallet-funding-unverified",
+        en: "Funding from the new wallet could not be verified: {error}",
+        ru: "Не удалось проверить пополнение с нового кошелька: {error}",
+    },
+    Message { key: "audit-entry-validator", en: "  validator {validator}", ru: "  валидатор {validator}" },
+    Message { key: "audit-entry-payer", en: "  payer {payer}", ru: "  плательщик {payer}" },
+    Message { key: "age-seconds", en: "{seconds}s ago", ru: "{seconds} с назад" },
+    Message { key: "age-minutes", en: "{minutes}m ago", ru: "{minutes} мин назад" },
+    Message { key: "age-hours", en: "{hours}h {minutes}m ago", ru: "{hours} ч {minutes} мин назад" },
+    Message { key: "age-days", en: "{days}d {hours}h ago", ru: "{days} д {hours} ч назад" },
+    Message { key: "dashboard-payer", en: "Payer {payer}: {balance} SOL", ru: "Плательщик {payer}: {balance} SOL" },
+    Message { key: "dashboard-payer-unavailable", en: "Payer {payer}: balance unavailable", ru: "Плательщик {payer}: баланс недоступен" },
+    Message { key: "dashboard-no-wallet", en: "No funding wallet configured", ru: "Кошелёк пополнения не настроен" },
+    Message { key: "dashboard-refreshing", en: "refreshing...", ru: "обновление..." },
+    Message { key: "dashboard-refreshed", en: "refreshed {age}", ru: "обновлено {age}" },
+    Message {
+        key: "dashboard-keys",
+        en: "Up/Down select | f top up | h history | r refresh | q quit",
+        ru: "Вверх/Вниз выбор | f пополнить | h история | r обновить | q выход",
+    },
+    Message { key: "column-name", en: "NAME", ru: "ИМЯ" },
+    Message { key: "column-gossip", en: "GOSSIP", ru: "GOSSIP" },
+    Message { key: "column-vote", en: "VOTE", ru: "ГОЛОС" },
+    Message { key: "column-pda-balance-sol", en: "PDA BALANCE (SOL)", ru: "БАЛАНС PDA (SOL)" },
+    Message { key: "column-last-deposit", en: "LAST DEPOSIT", ru: "ПОСЛЕДНИЙ ДЕПОЗИТ" },
+    Message { key: "gossip-yes", en: "yes", ru: "да" },
+    Message { key: "gossip-no", en: "NO", ru: "НЕТ" },
+    Message { key: "dashboard-never", en: "never", ru: "никогда" },
+    Message { key: "dashboard-validators", en: "Validators", ru: "Валидаторы" },
+    Message { key: "dashboard-history-of", en: "History of {validator}", ru: "История {validator}" },
+    Message { key: "column-when", en: "WHEN", ru: "КОГДА" },
+    Message { key: "column-signature-or-error", en: "SIGNATURE / ERROR", ru: "ПОДПИСЬ / ОШИБКА" },
+    Message { key: "dashboard-refresh-failed", en: "Refresh failed: {error}", ru: "Обновление не удалось: {error}" },
+    Message {
+        key: "dashboard-top-up-disabled",
+        en: "Start the dashboard with --amount <SOL> to enable top-ups",
+        ru: "Запустите панель с --amount <SOL>, чтобы включить пополнения",
+    },
+    Message { key: "dashboard-top-up-failed", en: "Top-up failed: {error}", ru: "Пополнение не удалось: {error}" },
+    Message {
+        key: "dashboard-topped-up",
+        en: "Transferred {amount} SOL to {pda}, signature {signature}",
+        ru: "Переведено {amount} SOL на {pda}, подпись {signature}",
+    },
+    Message { key: "prompt-seed-phrase", en: "Seed phrase: ", ru: "Сид-фраза: " },
+    Message { key: "prompt-bip39-passphrase", en: "BIP39 passphrase (empty for none): ", ru: "Пароль BIP39 (пусто, если нет): " },
+    Message { key: "recovered-wallet", en: "Recovered wallet: {pubkey}", ru: "Восстановлен кошелёк: {pubkey}" },
+    Message { key: "prompt-passphrase-for", en: "Passphrase for {path}: ", ru: "Пароль для {path}: " },
+    Message { key: "prompt-new-passphrase", en: "New passphrase: ", ru: "Новый пароль: " },
+    Message { key: "prompt-confirm-passphrase", en: "Confirm passphrase: ", ru: "Подтвердите пароль: " },
+    Message { key: "help-format-fields", en: "Fields for --format '{{field}}':", ru: "Поля для --format '{{field}}':" },
+    Message { key: "examples", en: "Examples", ru: "Примеры" },
+    Message { key: "column-balance-lamports", en: "BALANCE (lamports)", ru: "БАЛАНС (лампорты)" },
+    Message { key: "column-stake-lamports", en: "STAKE (lamports)", ru: "СТЕЙК (лампорты)" },
+    Message { key: "progress-collecting-validators", en: "Collecting validators", ru: "Сбор данных валидаторов" },
+    Message { key: "progress-downloading-gossip", en: "Downloading gossip node list", ru: "Загрузка списка узлов gossip" },
+    Message { key: "progress-waiting-confirmation", en: "Waiting for confirmation of {signature}", ru: "Ожидание подтверждения {signature}" },
+    Message { key: "progress-fetching-gossip", en: "Fetching gossip node list", ru: "Получение списка узлов gossip" },
+    Message { key: "progress-sending-transactions", en: "Sending transactions", ru: "Отправка транзакций" },
+    Message { key: "error-kind-invalid-input", en: "Invalid input: {message}", ru: "Неверные входные данные: {message}" },
+    Message { key: "error-kind-keypair", en: "Keypair error: {message}", ru: "Ошибка ключевой пары: {message}" },
+    Message { key: "error-kind-insufficient-funds", en: "Insufficient funds: {message}", ru: "Недостаточно средств: {message}" },
+    Message { key: "error-kind-funding-cancelled", en: "Funding cancelled: {message}", ru: "Пополнение отменено: {message}" },
+    Message { key: "error-kind-not-in-gossip", en: "Validator is not in gossip: {message}", ru: "Валидатор не найден в gossip: {message}" },
+    Message { key: "error-kind-strict-warning", en: "Strict mode: {message}", ru: "Строгий режим: {message}" },
+    Message { key: "error-kind-rpc-unavailable", en: "RPC node is unavailable: {message}", ru: "RPC-узел недоступен: {message}" },
+    Message { key: "error-kind-rpc", en: "RPC error: {message}", ru: "Ошибка RPC: {message}" },
+    Message { key: "error-kind-transaction-failed", en: "Transaction failed: {message}", ru: "Транзакция не выполнена: {message}" },
+    Message { key: "error-kind-confirmation-timeout", en: "Confirmation timed out: {message}", ru: "Истекло время подтверждения: {message}" },
+    Message { key: "error-kind-balance-mismatch", en: "Balance mismatch: {message}", ru: "Расхождение баланса: {message}" },
+    Message { key: "error-kind-io", en: "I/O error: {message}", ru: "Ошибка ввода-вывода: {message}" },
+    Message { key: "error-kind-http", en: "HTTP error: {message}", ru: "Ошибка HTTP: {message}" },
+    Message {
+        key: "warning-validator-not-in-gossip",
+        en: "Validator {validator} is NOT found in Solana gossip network",
+        ru: "Валидатор {validator} НЕ найден в сети gossip Solana",
+    },
+    Message { key: "warning-validator-not-in-gossip-unnamed", en: "Validator is not in Solana gossip network", ru: "Валидатор не найден в сети gossip Solana" },
+    Message { key: "warning-gossip-check-failed", en: "Error checking gossip network: {error}", ru: "Ошибка проверки сети gossip: {error}" },
+    Message { key: "warning-gossip-check-skipped", en: "Gossip check skipped", ru: "Проверка gossip пропущена" },
+    Message { key: "warning-no-vote-account", en: "Validator has no vote account", ru: "У валидатора нет аккаунта голосования" },
+    Message {
+        key: "warning-vote-account-delinquent",
+        en: "Vote account {vote_pubkey} is delinquent",
+        ru: "Аккаунт голосования {vote_pubkey} отстаёт (delinquent)",
+    },
+    Message {
+        key: "warning-no-activated-stake",
+        en: "Vote account {vote_pubkey} has no activated stake",
+        ru: "У аккаунта голосования {vote_pubkey} нет активированного стейка",
+    },
+    Message { key: "warning-vote-policy", en: "Vote account {vote_pubkey} has never voted", ru: "Аккаунт голосования {vote_pubkey} ни разу не голосовал" },
+    Message {
+        key: "warning-vote-policy-last-voted",
+        en: "Vote account {vote_pubkey} last voted {slots} slots ago (slot {slot}), more than the required {max_slots} slots",
+        ru: "Аккаунт голосования {vote_pubkey} последний раз голосовал {slots} слотов назад (слот {slot}), больше требуемых {max_slots} слотов",
+    },
+    Message {
+        key: "warning-vote-policy-stake",
+        en: "Vote account {vote_pubkey} has {stake} activated stake, less than the required {min_stake}",
+        ru: "У аккаунта голосования {vote_pubkey} активировано {stake} стейка, меньше требуемых {min_stake}",
+    },
+    Message {
+        key: "warning-vote-policy-commission",
+        en: "Vote account {vote_pubkey} charges {commission}% commission, more than the allowed {max_commission}%",
+        ru: "Аккаунт голосования {vote_pubkey} берёт комиссию {commission}%, больше допустимых {max_commission}%",
+    },
+    Message {
+        key: "warning-vote-policy-uptime-not-checked",
+        en: "Uptime of vote account {vote_pubkey} was not checked",
+        ru: "Аптайм аккаунта голосования {vote_pubkey} не проверен",
+    },
+    Message {
+        key: "warning-vote-policy-no-credits",
+        en: "Vote account {vote_pubkey} has no vote credits or leader slots in the last {epochs} epochs",
+        ru: "У аккаунта голосования {vote_pubkey} нет кредитов голосования и слотов лидера за последние {epochs} эпох",
+    },
+    Message {
+        key: "warning-vote-policy-uptime",
+        en: "Vote account {vote_pubkey} had {uptime} uptime, less than the required {required}",
+        ru: "Аптайм аккаунта голосования {vote_pubkey} составил {uptime}, меньше требуемых {required}",
+    },
+    Message {
+        key: "warning-balance-below-rent-exemption",
+        en: "PDA balance after the deposit ({balance} lamports) would be below the rent-exempt minimum of {minimum} lamports",
+        ru: "Баланс PDA после пополнения ({balance} лампортов) будет ниже минимума для освобождения от аренды в {minimum} лампортов",
+    },
+    Message {
+        key: "warning-balance-below-rent-exemption-current",
+        en: "Balance {balance} lamports is below the rent-exempt minimum of {minimum} lamports",
+        ru: "Баланс {balance} лампортов ниже минимума для освобождения от аренды в {minimum} лампортов",
+    },
+    Message {
+        key: "warning-unexpected-pda-owner",
+        en: "Deposit account {deposit_key} is owned by unexpected program {owner}",
+        ru: "Аккаунт депозита {deposit_key} принадлежит неожиданной программе {owner}",
+    },
+    Message {
+        key: "warning-amount-trimmed-to-reserve",
+        en: "Amount trimmed from {requested} to {trimmed} SOL to keep the wallet reserve of {reserve} SOL",
+        ru: "Сумма уменьшена с {requested} до {trimmed} SOL, чтобы сохранить резерв кошелька {reserve} SOL",
+    },
+    Message { key: "warning-stale-rpc", en: "RPC node {url} may be stale: {error}", ru: "RPC-узел {url} может отставать: {error}" },
+    Message {
+        key: "warning-rotated-wallet-unverified",
+        en: "Funding from rotated wallet {payer} could not be verified: {error}",
+        ru: "Пополнение со сменённого кошелька {payer} не удалось проверить: {error}",
+    },
+    Message {
+        key: "warning-validator-status-not-checked",
+        en: "Validator status was not checked: the transaction was signed offline",
+        ru: "Статус валидатора не проверен: транзакция подписана офлайн",
+    },
+    Message {
+        key: "warning-priority-fee-outlier",
+        en: "Priority fee {fee} micro-lamports/CU ({lamports} lamports) is more than {multiple}x the recent p99 of {p99} micro-lamports/CU; was it entered in lamports?",
+        ru: "Приоритетная комиссия {fee} микролампортов/CU ({lamports} лампортов) более чем в {multiple} раз выше недавнего p99 {p99} микролампортов/CU; не указана ли она в лампортах?",
+    },
+    Message {
+        key: "warning-priority-fee-outlier-low",
+        en: "Priority fee {fee} micro-lamports/CU is effectively zero while the network is congested (recent median {median} micro-lamports/CU); the transaction may not land",
+        ru: "Приоритетная комиссия {fee} микролампортов/CU фактически нулевая, а сеть перегружена (недавняя медиана {median} микролампортов/CU); транзакция может не пройти",
+    },
+    Message {
+        key: "warning-vault-underfunded",
+        en: "Vault {vault} holds {vault_balance} lamports, less than the {amount_lamports} lamports proposed",
+        ru: "В хранилище {vault} {vault_balance} лампортов, меньше предложенных {amount_lamports} лампортов",
+    },
+    Message { key: "warning-receipt-not-written", en: "Receipt not written to {path}: {error}", ru: "Квитанция не записана в {path}: {error}" },
+    Message {
+        key: "warning-budget-overridden",
+        en: "Budget exceeded (--override-budget): {violation}",
+        ru: "Бюджет превышен (--override-budget): {violation}",
+    },
+    Message {
+        key: "warning-pending-funding",
+        en: "Transaction {signature} from {payer} already sent {amount_lamports} lamports to PDA {pda} and is not finalized yet (slot {slot})",
+        ru: "Транзакция {signature} от {payer} уже отправила {amount_lamports} лампортов на PDA {pda} и ещё не финализирована (слот {slot})",
+    },
+    Message {
+        key: "warning-wallet-shortfall",
+        en: "The funding wallet holds {balance} but the plan needs {needed} including fees; it is {shortfall} short",
+        ru: "На кошельке пополнения {balance}, а плану нужно {needed} с учётом комиссий; не хватает {shortfall}",
+    },
+    Message {
+        key: "warning-rpc-fallback",
+        en: "RPC node does not support {method} ({reason}), needed by {used_by}; {fallback}",
+        ru: "RPC-узел не поддерживает {method} ({reason}), нужный для {used_by}; {fallback}",
+    },
+    Message {
+        key: "warning-rpc-unsupported",
+        en: "RPC node does not support {method} ({reason}), needed by {used_by}; the run will probably fail, use an up-to-date endpoint",
+        ru: "RPC-узел не поддерживает {method} ({reason}), нужный для {used_by}; запуск, скорее всего, завершится ошибкой, используйте актуальный узел",
+    },
+    Message { key: "err-validator-message", en: "Validator {validator}: {message}", ru: "Валидатор {validator}: {message}" },
+    Message { key: "err-os-not-found", en: "No such file or directory (os error {code})", ru: "Нет такого файла или каталога (ошибка ОС {code})" },
+    Message { key: "err-os-permission-denied", en: "Permission denied (os error {code})", ru: "Доступ запрещён (ошибка ОС {code})" },
+    Message { key: "err-os-is-a-directory", en: "Is a directory (os error {code})", ru: "Это каталог (ошибка ОС {code})" },
+    Message { key: "err-pubkey-wrong-size", en: "String is the wrong size", ru: "Строка неверной длины" },
+    Message { key: "err-pubkey-invalid-base58", en: "Invalid Base58 string", ru: "Неверная строка Base58" },
+    Message { key: "err-usage", en: "Usage: {usage}", ru: "Использование: {usage}" },
+    Message { key: "err-usage-after-message", en: "{message}. Usage: {usage}", ru: "{message}. Использование: {usage}" },
+    Message {
+        key: "err-seed-prefix-too-long",
+        en: "Seed prefix '{seed_prefix}' is longer than {max_seed_len} bytes",
+        ru: "Префикс сида '{seed_prefix}' длиннее {max_seed_len} байт",
+    },
+    Message {
+        key: "err-no-bump-seed-for-prefix",
+        en: "No valid bump seed for seed prefix '{seed_prefix}'",
+        ru: "Нет допустимого bump-сида для префикса '{seed_prefix}'",
+    },
+    Message { key: "err-address-empty", en: "Address cannot be empty", ru: "Адрес не может быть пустым" },
+    Message {
+        key: "err-invalid-base58-character",
+        en: "Invalid base58 character '{character}' found in address",
+        ru: "В адресе найден недопустимый символ base58 '{character}'",
+    },
+    Message { key: "err-invalid-base58-encoding", en: "Invalid base58 encoding: {error}", ru: "Неверная кодировка base58: {error}" },
+    Message { key: "err-invalid-pubkey-format", en: "Invalid pubkey format: {error}", ru: "Неверный формат публичного ключа: {error}" },
+    Message { key: "err-amount-not-positive", en: "Amount must be greater than 0", ru: "Сумма должна быть больше 0" },
+    Message {
+        key: "err-invalid-amount-decimals",
+        en: "Invalid amount: {amount} has more than {decimals} decimal places",
+        ru: "Неверная сумма: в {amount} больше {decimals} знаков после запятой",
+    },
+    Message { key: "err-invalid-amount-too-large", en: "Invalid amount: {amount} is too large", ru: "Неверная сумма: {amount} слишком велика" },
+    Message { key: "err-invalid-amount", en: "Invalid amount: {amount}", ru: "Неверная сумма: {amount}" },
+    Message {
+        key: "err-neither-pubkey-nor-alias",
+        en: "'{name}' is neither a validator pubkey nor a configured alias",
+        ru: "'{name}' не является ни публичным ключом валидатора, ни настроенным алиасом",
+    },
+    Message {
+        key: "err-alt-needs-v0",
+        en: "--alt needs a v0 transaction, not --tx-version legacy",
+        ru: "--alt требует транзакцию v0, а не --tx-version legacy",
+    },
+    Message {
+        key: "err-at-epoch-boundary-and-at-epoch",
+        en: "--at-epoch-boundary and --at-epoch cannot be combined",
+        ru: "--at-epoch-boundary и --at-epoch нельзя использовать вместе",
+    },
+    Message {
+        key: "err-at-epoch-with-sign-only",
+        en: "--at-epoch-boundary and --at-epoch cannot be used with --sign-only",
+        ru: "--at-epoch-boundary и --at-epoch нельзя использовать с --sign-only",
+    },
+    Message {
+        key: "err-backend-geyser-needs-feature",
+        en: "--backend geyser needs a build with the geyser feature (cargo build --features geyser)",
+        ru: "--backend geyser требует сборки с функцией geyser (cargo build --features geyser)",
+    },
+    Message {
+        key: "err-format-with-output",
+        en: "--format cannot be combined with --output json or csv",
+        ru: "--format нельзя использовать вместе с --output json или csv",
+    },
+    Message { key: "err-format-with-qr", en: "--format cannot be combined with --qr", ru: "--format нельзя использовать вместе с --qr" },
+    Message {
+        key: "err-format-only-supported-by",
+        en: "--format is only supported by pda-address, pda-balance and pda-batch",
+        ru: "--format поддерживается только командами pda-address, pda-balance и pda-batch",
+    },
+    Message {
+        key: "err-identity-keypair-only-supported-by",
+        en: "--identity-keypair is only supported by pda-fund-address",
+        ru: "--identity-keypair поддерживается только командой pda-fund-address",
+    },
+    Message {
+        key: "err-identity-sources-combined",
+        en: "--identity-keypair, --from-identity-file and --from-local-rpc cannot be combined",
+        ru: "--identity-keypair, --from-identity-file и --from-local-rpc нельзя использовать вместе",
+    },
+    Message {
+        key: "err-lookback-epochs-needs-require-uptime",
+        en: "--lookback-epochs needs --require-uptime",
+        ru: "--lookback-epochs требует --require-uptime",
+    },
+    Message {
+        key: "err-nonce-authority-needs-nonce-account",
+        en: "--nonce-authority requires --nonce-account",
+        ru: "--nonce-authority требует --nonce-account",
+    },
+    Message { key: "err-sign-only-needs-blockhash", en: "--sign-only requires --blockhash <blockhash>", ru: "--sign-only требует --blockhash <blockhash>" },
+    Message {
+        key: "err-squads-needs-exact-amount",
+        en: "--squads needs an exact amount; ALL is not supported",
+        ru: "--squads требует точную сумму; ALL не поддерживается",
+    },
+    Message {
+        key: "err-stdin-expects-validators",
+        en: "--stdin expects validators piped on standard input, one per line",
+        ru: "--stdin ожидает валидаторы на стандартном вводе, по одному в строке",
+    },
+    Message {
+        key: "err-stdin-only-supported-by",
+        en: "--stdin is only supported by pda-address, pda-balance and pda-batch",
+        ru: "--stdin поддерживается только командами pda-address, pda-balance и pda-batch",
+    },
+    Message {
+        key: "err-flag-with-resume",
+        en: "--{flag} cannot be combined with --resume; the transfers and progress are kept in {path}",
+        ru: "--{flag} нельзя использовать вместе с --resume; переводы и прогресс хранятся в {path}",
+    },
+    Message {
+        key: "err-flag-not-supported-by-fund-many",
+        en: "--{flag} is not supported by pda-fund-many",
+        ru: "--{flag} не поддерживается командой pda-fund-many",
+    },
+    Message {
+        key: "err-flag-not-supported-by-fund-token",
+        en: "--{flag} is not supported by pda-fund-token",
+        ru: "--{flag} не поддерживается командой pda-fund-token",
+    },
+    Message {
+        key: "err-flag-not-supported-by-plan-execute",
+        en: "--{flag} is not supported by plan execute",
+        ru: "--{flag} не поддерживается командой plan execute",
+    },
+    Message {
+        key: "err-flag-only-supported-by",
+        en: "--{flag} is only supported by pda-address, pda-balance, pda-fund-address, pda-fund-token, pda-watch, validator-status and plan create",
+        ru: "--{flag} поддерживается только командами pda-address, pda-balance, pda-fund-address, pda-fund-token, pda-watch, validator-status и plan create",
+    },
+    Message { key: "err-plan-needs-exact-amount", en: "A funding plan needs an exact amount, not ALL", ru: "План пополнения требует точную сумму, а не ALL" },
+    Message {
+        key: "err-scheduled-funding-needs-keypair-files",
+        en: "A funding scheduled for an epoch needs keypair files or env:// keypairs, not {signer}: the signers are loaded again when the epoch begins",
+        ru: "Пополнение, запланированное на эпоху, требует файлов ключевых пар или ключей env://, а не {signer}: подписанты загружаются заново в начале эпохи",
+    },
+    Message {
+        key: "err-not-token-account",
+        en: "Account {address} is not an SPL token account (owner {owner})",
+        ru: "Аккаунт {address} не является токен-аккаунтом SPL (владелец {owner})",
+    },
+    Message {
+        key: "err-not-lookup-table",
+        en: "Account {address} is not an address lookup table (owner {owner})",
+        ru: "Аккаунт {address} не является таблицей поиска адресов (владелец {owner})",
+    },
+    Message {
+        key: "err-not-token-mint",
+        en: "Account {mint} is not an SPL Token mint (owner {owner})",
+        ru: "Аккаунт {mint} не является минтом SPL Token (владелец {owner})",
+    },
+    Message {
+        key: "err-not-nonce-account",
+        en: "Account {nonce_account} is not an initialized nonce account: {error}",
+        ru: "Аккаунт {nonce_account} не является инициализированным nonce-аккаунтом: {error}",
+    },
+    Message { key: "err-lookup-table-missing", en: "Address lookup table {address} does not exist", ru: "Таблица поиска адресов {address} не существует" },
+    Message {
+        key: "err-lookup-table-deactivated",
+        en: "Address lookup table {address} was deactivated at slot {slot}",
+        ru: "Таблица поиска адресов {address} деактивирована в слоте {slot}",
+    },
+    Message { key: "err-airdrop-amount-zero", en: "Airdrop amount must be greater than 0", ru: "Сумма airdrop должна быть больше 0" },
+    Message { key: "err-airdrop-failed", en: "Airdrop request failed: {error}", ru: "Запрос airdrop не выполнен: {error}" },
+    Message {
+        key: "err-alias-taken",
+        en: "Alias '{name}' already points at {existing}; use --force to replace it",
+        ru: "Алиас '{name}' уже указывает на {existing}; используйте --force, чтобы заменить его",
+    },
+    Message {
+        key: "err-amount-all-per-validator",
+        en: "Amount ALL is not supported for {validator}: give an exact amount per validator",
+        ru: "Сумма ALL не поддерживается для {validator}: укажите точную сумму для каждого валидатора",
+    },
+    Message {
+        key: "err-amount-all-with-sign-only",
+        en: "Amount ALL needs the payer balance and cannot be used with --sign-only",
+        ru: "Сумма ALL требует баланса плательщика и не может использоваться с --sign-only",
+    },
+    Message {
+        key: "err-amount-for-validator-zero",
+        en: "Amount for {validator} must be greater than zero",
+        ru: "Сумма для {validator} должна быть больше нуля",
+    },
+    Message { key: "err-airdrop-needs-exact-amount", en: "An airdrop needs an exact amount, not ALL", ru: "Airdrop требует точную сумму, а не ALL" },
+    Message { key: "err-approval-rejected", en: "Approval rejected: {reason}", ru: "Одобрение отклонено: {reason}" },
+    Message {
+        key: "err-too-many-seeds",
+        en: "At most {max_seeds} seeds are allowed, got {seeds}",
+        ru: "Допускается не более {max_seeds} сидов, получено {seeds}",
+    },
+    Message {
+        key: "err-balance-read-before-snapshot",
+        en: "Balance of {address} was read at slot {slot}, before the snapshot slot {min_slot}",
+        ru: "Баланс {address} прочитан в слоте {slot}, до слота снимка {min_slot}",
+    },
+    Message {
+        key: "err-balances-read-before-snapshot",
+        en: "Balances were read at slot {slot}, before the snapshot slot {min_slot}",
+        ru: "Балансы прочитаны в слоте {slot}, до слота снимка {min_slot}",
+    },
+    Message {
+        key: "err-bundle-too-new",
+        en: "Bundle format version {format_version} (written by version {tool_version}) is newer than the supported version {supported}; upgrade this tool first",
+        ru: "Версия формата архива {format_version} (записан версией {tool_version}) новее поддерживаемой версии {supported}; сначала обновите эту утилиту",
+    },
+    Message { key: "err-cannot-render-qr", en: "Cannot render QR code: {error}", ru: "Не удалось отрисовать QR-код: {error}" },
+    Message {
+        key: "err-no-ledger-support",
+        en: "Cannot use {url}: hardware wallet support is not enabled in this build (rebuild with --features ledger)",
+        ru: "Нельзя использовать {url}: поддержка аппаратных кошельков не включена в этой сборке (пересоберите с --features ledger)",
+    },
+    Message {
+        key: "err-env-variable-not-set",
+        en: "Environment variable {variable} of {source} is not set",
+        ru: "Переменная окружения {variable} из {source} не задана",
+    },
+    Message {
+        key: "err-epoch-ended",
+        en: "Epoch {epoch} has already ended; the cluster is in epoch {current_epoch}",
+        ru: "Эпоха {epoch} уже завершилась; кластер находится в эпохе {current_epoch}",
+    },
+    Message {
+        key: "err-epoch-not-started",
+        en: "Epoch {epoch} has not started yet (current epoch is {current_epoch})",
+        ru: "Эпоха {epoch} ещё не началась (текущая эпоха {current_epoch})",
+    },
+    Message { key: "err-all-weights-zero", en: "Every validator has a weight of 0; nothing to allocate", ru: "У всех валидаторов вес 0; нечего распределять" },
+    Message {
+        key: "err-hardware-wallet-access",
+        en: "Failed to access hardware wallet: {error}",
+        ru: "Не удалось получить доступ к аппаратному кошельку: {error}",
+    },
+    Message { key: "err-broadcast-transaction", en: "Failed to broadcast transaction: {error}", ru: "Не удалось разослать транзакцию: {error}" },
+    Message { key: "err-build-token-transfer", en: "Failed to build token transfer: {error}", ru: "Не удалось собрать перевод токенов: {error}" },
+    Message { key: "err-compile-v0-message", en: "Failed to compile v0 message: {error}", ru: "Не удалось скомпилировать сообщение v0: {error}" },
+    Message { key: "err-compress-bundle", en: "Failed to compress bundle: {error}", ru: "Не удалось сжать архив: {error}" },
+    Message {
+        key: "err-connect-geyser",
+        en: "Failed to connect to Geyser endpoint {endpoint}: {error}",
+        ru: "Не удалось подключиться к Geyser-узлу {endpoint}: {error}",
+    },
+    Message { key: "err-create-directory", en: "Failed to create directory {path}: {error}", ru: "Не удалось создать каталог {path}: {error}" },
+    Message {
+        key: "err-decode-lookup-table",
+        en: "Failed to decode address lookup table {address}: {error}",
+        ru: "Не удалось декодировать таблицу поиска адресов {address}: {error}",
+    },
+    Message { key: "err-decode-mint", en: "Failed to decode mint {mint}: {error}", ru: "Не удалось декодировать минт {mint}: {error}" },
+    Message {
+        key: "err-decode-token-account",
+        en: "Failed to decode token account {address}: {error}",
+        ru: "Не удалось декодировать токен-аккаунт {address}: {error}",
+    },
+    Message {
+        key: "err-decrypt-wrong-passphrase",
+        en: "Failed to decrypt {source}: wrong passphrase",
+        ru: "Не удалось расшифровать {source}: неверная парольная фраза",
+    },
+    Message { key: "err-decrypt", en: "Failed to decrypt {source}: {error}", ru: "Не удалось расшифровать {source}: {error}" },
+    Message {
+        key: "err-derive-from-seed-phrase",
+        en: "Failed to derive keypair from seed phrase: {error}",
+        ru: "Не удалось вывести ключевую пару из сид-фразы: {error}",
+    },
+    Message { key: "err-encrypt-keypair", en: "Failed to encrypt keypair: {error}", ru: "Не удалось зашифровать ключевую пару: {error}" },
+    Message { key: "err-estimate-fee", en: "Failed to estimate transaction fee: {error}", ru: "Не удалось оценить комиссию транзакции: {error}" },
+    Message { key: "err-get-pda-token-account", en: "Failed to get PDA token account: {error}", ru: "Не удалось получить токен-аккаунт PDA: {error}" },
+    Message { key: "err-get-account", en: "Failed to get account: {error}", ru: "Не удалось получить аккаунт: {error}" },
+    Message { key: "err-get-lookup-table", en: "Failed to get address lookup table: {error}", ru: "Не удалось получить таблицу поиска адресов: {error}" },
+    Message { key: "err-get-balance", en: "Failed to get balance: {error}", ru: "Не удалось получить баланс: {error}" },
+    Message {
+        key: "err-get-balances-count",
+        en: "Failed to get balances: {requested} accounts requested, {returned} returned",
+        ru: "Не удалось получить балансы: запрошено {requested} аккаунтов, получено {returned}",
+    },
+    Message { key: "err-get-balances", en: "Failed to get balances: {error}", ru: "Не удалось получить балансы: {error}" },
+    Message { key: "err-get-block-production", en: "Failed to get block production: {error}", ru: "Не удалось получить производство блоков: {error}" },
+    Message { key: "err-get-cluster-nodes", en: "Failed to get cluster nodes: {error}", ru: "Не удалось получить узлы кластера: {error}" },
+    Message { key: "err-get-current-slot", en: "Failed to get current slot: {error}", ru: "Не удалось получить текущий слот: {error}" },
+    Message { key: "err-get-deposit-account", en: "Failed to get deposit account: {error}", ru: "Не удалось получить аккаунт депозита: {error}" },
+    Message { key: "err-get-epoch-info", en: "Failed to get epoch info: {error}", ru: "Не удалось получить информацию об эпохе: {error}" },
+    Message { key: "err-get-feature-gate", en: "Failed to get feature gate account: {error}", ru: "Не удалось получить аккаунт feature gate: {error}" },
+    Message {
+        key: "err-get-fee-payer-balance",
+        en: "Failed to get fee payer balance: {error}",
+        ru: "Не удалось получить баланс плательщика комиссии: {error}",
+    },
+    Message { key: "err-get-genesis-hash", en: "Failed to get genesis hash: {error}", ru: "Не удалось получить хеш генезиса: {error}" },
+    Message {
+        key: "err-get-inflation-reward",
+        en: "Failed to get inflation reward for epoch {epoch}: {error}",
+        ru: "Не удалось получить инфляционную награду за эпоху {epoch}: {error}",
+    },
+    Message {
+        key: "err-get-inflation-reward-unwrapped",
+        en: "Failed to get inflation reward for epoch {epoch}",
+        ru: "Не удалось получить инфляционную награду за эпоху {epoch}",
+    },
+    Message { key: "err-get-mint-account", en: "Failed to get mint account: {error}", ru: "Не удалось получить аккаунт минта: {error}" },
+    Message { key: "err-get-multisig-account", en: "Failed to get multisig account: {error}", ru: "Не удалось получить аккаунт мультиподписи: {error}" },
+    Message { key: "err-get-node-identity", en: "Failed to get node identity: {error}", ru: "Не удалось получить идентификатор узла: {error}" },
+    Message { key: "err-get-node-version", en: "Failed to get node version: {error}", ru: "Не удалось получить версию узла: {error}" },
+    Message { key: "err-get-nonce-account", en: "Failed to get nonce account: {error}", ru: "Не удалось получить nonce-аккаунт: {error}" },
+    Message { key: "err-get-payer-account", en: "Failed to get payer account: {error}", ru: "Не удалось получить аккаунт плательщика: {error}" },
+    Message {
+        key: "err-get-payer-token-account",
+        en: "Failed to get payer token account: {error}",
+        ru: "Не удалось получить токен-аккаунт плательщика: {error}",
+    },
+    Message { key: "err-get-recent-blockhash", en: "Failed to get recent blockhash: {error}", ru: "Не удалось получить последний blockhash: {error}" },
+    Message {
+        key: "err-get-prioritization-fees",
+        en: "Failed to get recent prioritization fees: {error}",
+        ru: "Не удалось получить последние приоритетные комиссии: {error}",
+    },
+    Message {
+        key: "err-get-funding-wallet-transactions",
+        en: "Failed to get recent transactions of the funding wallet: {error}",
+        ru: "Не удалось получить последние транзакции кошелька пополнения: {error}",
+    },
+    Message {
+        key: "err-get-rent-exempt-minimum",
+        en: "Failed to get rent-exempt minimum: {error}",
+        ru: "Не удалось получить минимум для освобождения от аренды: {error}",
+    },
+    Message { key: "err-get-snapshot-slot", en: "Failed to get snapshot slot: {error}", ru: "Не удалось получить слот снимка: {error}" },
+    Message { key: "err-get-sol-price", en: "Failed to get the SOL price from {url}: {error}", ru: "Не удалось получить курс SOL с {url}: {error}" },
+    Message {
+        key: "err-get-reference-slot",
+        en: "Failed to get the slot of the reference endpoint: {error}",
+        ru: "Не удалось получить слот эталонного узла: {error}",
+    },
+    Message { key: "err-get-transaction-status", en: "Failed to get transaction status: {error}", ru: "Не удалось получить статус транзакции: {error}" },
+    Message { key: "err-get-transaction-statuses", en: "Failed to get transaction statuses: {error}", ru: "Не удалось получить статусы транзакций: {error}" },
+    Message { key: "err-get-transaction", en: "Failed to get transaction: {error}", ru: "Не удалось получить транзакцию: {error}" },
+    Message {
+        key: "err-get-pda-transactions",
+        en: "Failed to get transactions of the deposit PDA: {error}",
+        ru: "Не удалось получить транзакции PDA депозита: {error}",
+    },
+    Message { key: "err-get-vault-balance", en: "Failed to get vault balance: {error}", ru: "Не удалось получить баланс хранилища: {error}" },
+    Message { key: "err-get-vote-account", en: "Failed to get vote account: {error}", ru: "Не удалось получить аккаунт голосования: {error}" },
+    Message { key: "err-get-vote-accounts", en: "Failed to get vote accounts: {error}", ru: "Не удалось получить аккаунты голосования: {error}" },
+    Message { key: "err-get-wallet-balance", en: "Failed to get wallet balance: {error}", ru: "Не удалось получить баланс кошелька: {error}" },
+    Message { key: "err-listen-metrics-port", en: "Failed to listen on metrics port {port}: {error}", ru: "Не удалось открыть порт метрик {port}: {error}" },
+    Message { key: "err-load-signer", en: "Failed to load signer {url}: {error}", ru: "Не удалось загрузить подписанта {url}: {error}" },
+    Message { key: "err-open-audit-log", en: "Failed to open audit log {path}: {error}", ru: "Не удалось открыть журнал аудита {path}: {error}" },
+    Message { key: "err-parse-config", en: "Failed to parse config {path}: {error}", ru: "Не удалось разобрать конфигурацию {path}: {error}" },
+    Message {
+        key: "err-parse-funding-state",
+        en: "Failed to parse funding state {path}: {error}",
+        ru: "Не удалось разобрать состояние пополнения {path}: {error}",
+    },
+    Message { key: "err-parse-gossip-cache", en: "Failed to parse gossip cache {path}: {error}", ru: "Не удалось разобрать кеш gossip {path}: {error}" },
+    Message {
+        key: "err-parse-gossip-monitor-state",
+        en: "Failed to parse gossip monitor state {path}: {error}",
+        ru: "Не удалось разобрать состояние монитора gossip {path}: {error}",
+    },
+    Message { key: "err-parse-price-cache", en: "Failed to parse price cache {path}: {error}", ru: "Не удалось разобрать кеш курсов {path}: {error}" },
+    Message { key: "err-parse-state", en: "Failed to parse state {path}: {error}", ru: "Не удалось разобрать состояние {path}: {error}" },
+    Message { key: "err-parse-file", en: "Failed to parse {path}: {error}", ru: "Не удалось разобрать {path}: {error}" },
+    Message { key: "err-push-metrics", en: "Failed to push metrics to {url}: {error}", ru: "Не удалось отправить метрики в {url}: {error}" },
+    Message { key: "err-read-audit-log", en: "Failed to read audit log {path}: {error}", ru: "Не удалось прочитать журнал аудита {path}: {error}" },
+    Message { key: "err-read-bundle", en: "Failed to read bundle {path}: {error}", ru: "Не удалось прочитать архив {path}: {error}" },
+    Message { key: "err-read-config", en: "Failed to read config {path}: {error}", ru: "Не удалось прочитать конфигурацию {path}: {error}" },
+    Message { key: "err-read-funding-list", en: "Failed to read funding list {path}: {error}", ru: "Не удалось прочитать список пополнений {path}: {error}" },
+    Message {
+        key: "err-read-funding-state",
+        en: "Failed to read funding state {path}: {error}",
+        ru: "Не удалось прочитать состояние пополнения {path}: {error}",
+    },
+    Message { key: "err-read-gossip-cache", en: "Failed to read gossip cache {path}: {error}", ru: "Не удалось прочитать кеш gossip {path}: {error}" },
+    Message {
+        key: "err-read-gossip-monitor-state",
+        en: "Failed to read gossip monitor state {path}: {error}",
+        ru: "Не удалось прочитать состояние монитора gossip {path}: {error}",
+    },
+    Message { key: "err-read-keypair", en: "Failed to read keypair from {source}: {error}", ru: "Не удалось прочитать ключевую пару из {source}: {error}" },
+    Message { key: "err-read-message-file", en: "Failed to read message file {path}: {error}", ru: "Не удалось прочитать файл сообщения {path}: {error}" },
+    Message {
+        key: "err-read-metrics-address",
+        en: "Failed to read metrics listener address: {error}",
+        ru: "Не удалось получить адрес сервера метрик: {error}",
+    },
+    Message { key: "err-read-passphrase", en: "Failed to read passphrase: {error}", ru: "Не удалось прочитать парольную фразу: {error}" },
+    Message {
+        key: "err-read-passphrase-unattended",
+        en: "Failed to read passphrase: {error} (set {variable} for unattended runs)",
+        ru: "Не удалось прочитать парольную фразу: {error} (задайте {variable} для запусков без участия человека)",
+    },
+    Message { key: "err-read-plan", en: "Failed to read plan {path}: {error}", ru: "Не удалось прочитать план {path}: {error}" },
+    Message { key: "err-read-price-cache", en: "Failed to read price cache {path}: {error}", ru: "Не удалось прочитать кеш курсов {path}: {error}" },
+    Message { key: "err-read-script", en: "Failed to read script {path}: {error}", ru: "Не удалось прочитать сценарий {path}: {error}" },
+    Message { key: "err-read-seed-phrase", en: "Failed to read seed phrase: {error}", ru: "Не удалось прочитать сид-фразу: {error}" },
+    Message { key: "err-read-stdin", en: "Failed to read standard input: {error}", ru: "Не удалось прочитать стандартный ввод: {error}" },
+    Message { key: "err-read-state", en: "Failed to read state {path}: {error}", ru: "Не удалось прочитать состояние {path}: {error}" },
+    Message { key: "err-read-transaction", en: "Failed to read transaction {path}: {error}", ru: "Не удалось прочитать транзакцию {path}: {error}" },
+    Message {
+        key: "err-read-validator-list",
+        en: "Failed to read validator list {path}: {error}",
+        ru: "Не удалось прочитать список валидаторов {path}: {error}",
+    },
+    Message { key: "err-read-weights-file", en: "Failed to read weights file {path}: {error}", ru: "Не удалось прочитать файл весов {path}: {error}" },
+    Message { key: "err-read-file", en: "Failed to read {path}: {error}", ru: "Не удалось прочитать {path}: {error}" },
+    Message { key: "err-replace-file", en: "Failed to replace {path}: {error}", ru: "Не удалось заменить {path}: {error}" },
+    Message {
+        key: "err-send-notification",
+        en: "Failed to send notification to {target}: {error}",
+        ru: "Не удалось отправить уведомление в {target}: {error}",
+    },
+    Message { key: "err-send-proposal", en: "Failed to send proposal transaction: {error}", ru: "Не удалось отправить транзакцию предложения: {error}" },
+    Message { key: "err-send-transaction", en: "Failed to send transaction: {error}", ru: "Не удалось отправить транзакцию: {error}" },
+    Message { key: "err-serialize-audit-entry", en: "Failed to serialize audit entry: {error}", ru: "Не удалось сериализовать запись аудита: {error}" },
+    Message {
+        key: "err-serialize-bundle-manifest",
+        en: "Failed to serialize bundle manifest: {error}",
+        ru: "Не удалось сериализовать манифест архива: {error}",
+    },
+    Message { key: "err-serialize-config", en: "Failed to serialize config: {error}", ru: "Не удалось сериализовать конфигурацию: {error}" },
+    Message { key: "err-serialize-funding-plan", en: "Failed to serialize funding plan: {error}", ru: "Не удалось сериализовать план пополнения: {error}" },
+    Message {
+        key: "err-serialize-funding-state",
+        en: "Failed to serialize funding state: {error}",
+        ru: "Не удалось сериализовать состояние пополнения: {error}",
+    },
+    Message { key: "err-serialize-gossip-cache", en: "Failed to serialize gossip cache: {error}", ru: "Не удалось сериализовать кеш gossip: {error}" },
+    Message {
+        key: "err-serialize-gossip-monitor-state",
+        en: "Failed to serialize gossip monitor state: {error}",
+        ru: "Не удалось сериализовать состояние монитора gossip: {error}",
+    },
+    Message { key: "err-serialize-keypair", en: "Failed to serialize keypair: {error}", ru: "Не удалось сериализовать ключевую пару: {error}" },
+    Message { key: "err-serialize-price-cache", en: "Failed to serialize price cache: {error}", ru: "Не удалось сериализовать кеш курсов: {error}" },
+    Message { key: "err-serialize-state", en: "Failed to serialize state: {error}", ru: "Не удалось сериализовать состояние: {error}" },
+    Message { key: "err-serialize-transaction", en: "Failed to serialize transaction: {error}", ru: "Не удалось сериализовать транзакцию: {error}" },
+    Message { key: "err-serialize-file", en: "Failed to serialize {path}: {error}", ru: "Не удалось сериализовать {path}: {error}" },
+    Message { key: "err-sign-approval", en: "Failed to sign approval: {error}", ru: "Не удалось подписать одобрение: {error}" },
+    Message { key: "err-sign-message", en: "Failed to sign message: {error}", ru: "Не удалось подписать сообщение: {error}" },
+    Message { key: "err-sign-receipt", en: "Failed to sign receipt: {error}", ru: "Не удалось подписать квитанцию: {error}" },
+    Message { key: "err-sign-transaction", en: "Failed to sign transaction: {error}", ru: "Не удалось подписать транзакцию: {error}" },
+    Message {
+        key: "err-subscribe-geyser-gossip",
+        en: "Failed to subscribe to Geyser gossip: {error}",
+        ru: "Не удалось подписаться на gossip через Geyser: {error}",
+    },
+    Message { key: "err-subscribe-account", en: "Failed to subscribe to account {pubkey}: {error}", ru: "Не удалось подписаться на аккаунт {pubkey}: {error}" },
+    Message { key: "err-sweep-wallet", en: "Failed to sweep wallet: {error}", ru: "Не удалось вывести средства с кошелька: {error}" },
+    Message { key: "err-write-audit-log", en: "Failed to write audit log {path}: {error}", ru: "Не удалось записать журнал аудита {path}: {error}" },
+    Message { key: "err-write-bundle-entry", en: "Failed to write bundle entry {name}: {error}", ru: "Не удалось записать элемент архива {name}: {error}" },
+    Message { key: "err-write-bundle", en: "Failed to write bundle: {error}", ru: "Не удалось записать архив: {error}" },
+    Message { key: "err-write-file", en: "Failed to write {path}: {error}", ru: "Не удалось записать {path}: {error}" },
+    Message {
+        key: "err-cancelled-status-unknown",
+        en: "Funding cancelled: Unable to check validator status: {error}",
+        ru: "Пополнение отменено: не удалось проверить статус валидатора: {error}",
+    },
+    Message {
+        key: "err-cancelled-budget",
+        en: "Funding cancelled: budget exceeded ({violations}). Pass --override-budget to send it anyway",
+        ru: "Пополнение отменено: бюджет превышен ({violations}). Передайте --override-budget, чтобы всё равно отправить его",
+    },
+    Message {
+        key: "err-cancelled-duplicate",
+        en: "Funding cancelled: possible duplicate, {reason}. Check the PDA balance (audit show) and pass --force to send it anyway",
+        ru: "Пополнение отменено: возможный дубликат, {reason}. Проверьте баланс PDA (audit show) и передайте --force, чтобы всё равно отправить его",
+    },
+    Message {
+        key: "err-cancelled-use-force",
+        en: "Funding cancelled: {reason}; use --force to send anyway",
+        ru: "Пополнение отменено: {reason}; используйте --force, чтобы всё равно отправить",
+    },
+    Message {
+        key: "err-funding-not-recorded",
+        en: "Funding not sent: it could not be recorded in the audit log first: {error}",
+        ru: "Пополнение не отправлено: не удалось заранее записать его в журнал аудита: {error}",
+    },
+    Message {
+        key: "err-funding-skipped-reserve",
+        en: "Funding skipped: payer balance {balance} lamports does not exceed fee {fee} + wallet reserve {reserve} lamports",
+        ru: "Пополнение пропущено: баланс плательщика {balance} лампортов не превышает комиссию {fee} + резерв кошелька {reserve} лампортов",
+    },
+    Message { key: "err-geyser-account-stream", en: "Geyser account stream failed: {error}", ru: "Поток аккаунтов Geyser прерван: {error}" },
+    Message {
+        key: "err-geyser-gossip-stream-ended",
+        en: "Geyser gossip stream ended before the node snapshot",
+        ru: "Поток gossip Geyser завершился до снимка узлов",
+    },
+    Message { key: "err-geyser-gossip-stream", en: "Geyser gossip stream failed: {error}", ru: "Поток gossip Geyser прерван: {error}" },
+    Message {
+        key: "err-message-text-and-file",
+        en: "Give the message either as text or with --file, not both",
+        ru: "Передайте сообщение текстом или через --file, но не обоими способами",
+    },
+    Message { key: "err-gossip-snapshot-not-found", en: "Gossip snapshot {path} not found", ru: "Снимок gossip {path} не найден" },
+    Message {
+        key: "err-instruction-too-large",
+        en: "Instruction {index} does not fit in a transaction ({size} bytes and {accounts} accounts signed; limits are {max_size} bytes and {max_accounts} accounts)",
+        ru: "Инструкция {index} не помещается в транзакцию ({size} байт и {accounts} аккаунтов с подписью; пределы {max_size} байт и {max_accounts} аккаунтов)",
+    },
+    Message {
+        key: "err-insufficient-payer-balance",
+        en: "Insufficient payer balance: need {required} lamports (amount {amount} + fee {fee} + rent-exempt reserve {reserve}), have {balance} lamports, shortfall {shortfall} lamports ({shortfall_sol} SOL)",
+        ru: "Недостаточно средств у плательщика: нужно {required} лампортов (сумма {amount} + комиссия {fee} + резерв для освобождения от аренды {reserve}), есть {balance} лампортов, не хватает {shortfall} лампортов ({shortfall_sol} SOL)",
+    },
+    Message {
+        key: "err-insufficient-token-balance",
+        en: "Insufficient token balance: need {amount} base units, have {balance} in {account}",
+        ru: "Недостаточно токенов: нужно {amount} базовых единиц, есть {balance} на {account}",
+    },
+    Message { key: "err-invalid-alt", en: "Invalid --alt address '{alt}'", ru: "Неверный адрес --alt '{alt}'" },
+    Message {
+        key: "err-invalid-at-epoch",
+        en: "Invalid --at-epoch '{value}': expected an epoch number",
+        ru: "Неверный --at-epoch '{value}': ожидается номер эпохи",
+    },
+    Message {
+        key: "err-invalid-budget",
+        en: "Invalid --budget '{value}': expected an amount of SOL like 500",
+        ru: "Неверный --budget '{value}': ожидается сумма в SOL, например 500",
+    },
+    Message {
+        key: "err-invalid-days",
+        en: "Invalid --days '{value}': expected a number of days from 1 to 3650",
+        ru: "Неверный --days '{value}': ожидается число дней от 1 до 3650",
+    },
+    Message { key: "err-invalid-epochs", en: "Invalid --epochs '{value}': {reason}", ru: "Неверный --epochs '{value}': {reason}" },
+    Message {
+        key: "err-format-unclosed-placeholder",
+        en: "Invalid --format: unclosed placeholder in '{template}'",
+        ru: "Неверный --format: незакрытый заполнитель в '{template}'",
+    },
+    Message {
+        key: "err-format-unknown-field",
+        en: "Invalid --format: unknown field '{name}' (available: {available})",
+        ru: "Неверный --format: неизвестное поле '{name}' (доступны: {available})",
+    },
+    Message {
+        key: "err-invalid-geyser-endpoint",
+        en: "Invalid --geyser-endpoint '{endpoint}': {error}",
+        ru: "Неверный --geyser-endpoint '{endpoint}': {error}",
+    },
+    Message {
+        key: "err-invalid-grace",
+        en: "Invalid --grace '{value}': expected minutes (5m), hours (1h) or days (1d)",
+        ru: "Неверный --grace '{value}': ожидаются минуты (5m), часы (1h) или дни (1d)",
+    },
+    Message {
+        key: "err-invalid-interval",
+        en: "Invalid --interval: must be a positive number of seconds",
+        ru: "Неверный --interval: должно быть положительное число секунд",
+    },
+    Message {
+        key: "err-invalid-lookback-epochs",
+        en: "Invalid --lookback-epochs '{value}': expected a number of epochs from 1 to {max}",
+        ru: "Неверный --lookback-epochs '{value}': ожидается число эпох от 1 до {max}",
+    },
+    Message {
+        key: "err-invalid-max-commission",
+        en: "Invalid --max-commission '{value}': expected a percentage from 0 to 100",
+        ru: "Неверный --max-commission '{value}': ожидается процент от 0 до 100",
+    },
+    Message {
+        key: "err-invalid-min-activated-stake",
+        en: "Invalid --min-activated-stake '{value}': expected an amount of SOL like 5000",
+        ru: "Неверный --min-activated-stake '{value}': ожидается сумма в SOL, например 5000",
+    },
+    Message { key: "err-invalid-program-id", en: "Invalid --program-id: {error}", ru: "Неверный --program-id: {error}" },
+    Message {
+        key: "err-invalid-refresh",
+        en: "Invalid --refresh: must be a positive number of seconds",
+        ru: "Неверный --refresh: должно быть положительное число секунд",
+    },
+    Message { key: "err-invalid-rpc-header-name", en: "Invalid --rpc-header name '{name}'", ru: "Неверное имя --rpc-header '{name}'" },
+    Message {
+        key: "err-invalid-rpc-header-value",
+        en: "Invalid --rpc-header value for '{name}': it contains characters not allowed in HTTP headers",
+        ru: "Неверное значение --rpc-header для '{name}': оно содержит символы, недопустимые в заголовках HTTP",
+    },
+    Message {
+        key: "err-invalid-rpc-header",
+        en: "Invalid --rpc-header: expected 'Name: value', e.g. 'Authorization: Bearer <token>'",
+        ru: "Неверный --rpc-header: ожидается 'Name: value', например 'Authorization: Bearer <token>'",
+    },
+    Message {
+        key: "err-invalid-rpc-proxy",
+        en: "Invalid --rpc-proxy: expected a proxy URL such as http://proxy.internal:3128",
+        ru: "Неверный --rpc-proxy: ожидается URL прокси, например http://proxy.internal:3128",
+    },
+    Message {
+        key: "err-invalid-squads-multisig",
+        en: "Invalid --squads multisig address '{multisig}'",
+        ru: "Неверный адрес мультиподписи --squads '{multisig}'",
+    },
+    Message {
+        key: "err-invalid-threads",
+        en: "Invalid --threads '{value}': expected a positive number",
+        ru: "Неверный --threads '{value}': ожидается положительное число",
+    },
+    Message {
+        key: "err-grind-character-not-base58",
+        en: "Invalid --{flag} '{value}': '{character}' never appears in an address (base58 has no 0, O, I or l)",
+        ru: "Неверный --{flag} '{value}': '{character}' не встречается в адресах (в base58 нет 0, O, I и l)",
+    },
+    Message {
+        key: "err-invalid-flag-percentage",
+        en: "Invalid --{flag} '{value}': expected a percentage like 95 or 99.5",
+        ru: "Неверный --{flag} '{value}': ожидается процент, например 95 или 99.5",
+    },
+    Message {
+        key: "err-invalid-flag-seconds",
+        en: "Invalid --{flag} '{value}': expected a positive number of seconds",
+        ru: "Неверный --{flag} '{value}': ожидается положительное число секунд",
+    },
+    Message {
+        key: "err-invalid-flag-expected",
+        en: "Invalid --{flag} '{value}': expected {expected}",
+        ru: "Неверный --{flag} '{value}': ожидается {expected}",
+    },
+    Message {
+        key: "err-grind-longer-than-address",
+        en: "Invalid --{flag} '{value}': longer than an address",
+        ru: "Неверный --{flag} '{value}': длиннее адреса",
+    },
+    Message {
+        key: "err-grind-nothing-to-match",
+        en: "Invalid --{flag} '{value}': no characters to match",
+        ru: "Неверный --{flag} '{value}': нет символов для поиска",
+    },
+    Message {
+        key: "err-grind-count-not-positive",
+        en: "Invalid --{flag} '{value}': the count must be a positive number",
+        ru: "Неверный --{flag} '{value}': количество должно быть положительным числом",
+    },
+    Message {
+        key: "err-invalid-pushgateway-url",
+        en: "Invalid Pushgateway URL '{url}': must start with http:// or https://",
+        ru: "Неверный URL Pushgateway '{url}': должен начинаться с http:// или https://",
+    },
+    Message {
+        key: "err-invalid-rpc-url",
+        en: "Invalid RPC URL '{url}': expected an http:// or https:// URL",
+        ru: "Неверный URL RPC '{url}': ожидается URL http:// или https://",
+    },
+    Message {
+        key: "err-alias-is-pubkey",
+        en: "Invalid alias '{name}': an alias cannot be a pubkey",
+        ru: "Неверный алиас '{name}': алиас не может быть публичным ключом",
+    },
+    Message {
+        key: "err-invalid-alias",
+        en: "Invalid alias '{name}': it must not be empty or contain whitespace, ',' or ':'",
+        ru: "Неверный алиас '{name}': он не может быть пустым или содержать пробелы, ',' или ':'",
+    },
+    Message { key: "err-invalid-approver", en: "Invalid approver '{approver}': {error}", ru: "Неверный одобряющий '{approver}': {error}" },
+    Message {
+        key: "err-invalid-audit-entry",
+        en: "Invalid audit log entry at {path}:{line}: {error}",
+        ru: "Неверная запись журнала аудита в {path}:{line}: {error}",
+    },
+    Message { key: "err-invalid-backend", en: "Invalid backend '{value}': expected rpc or geyser", ru: "Неверный бэкенд '{value}': ожидается rpc или geyser" },
+    Message { key: "err-invalid-base64-transaction", en: "Invalid base64 transaction: {error}", ru: "Неверная транзакция base64: {error}" },
+    Message { key: "err-invalid-blockhash", en: "Invalid blockhash '{value}': {error}", ru: "Неверный blockhash '{value}': {error}" },
+    Message { key: "err-invalid-bundle-manifest", en: "Invalid bundle manifest: {error}", ru: "Неверный манифест архива: {error}" },
+    Message { key: "err-bundle-without-manifest", en: "Invalid bundle {path}: no {manifest}", ru: "Неверный архив {path}: нет {manifest}" },
+    Message { key: "err-invalid-bundle", en: "Invalid bundle {path}: {error}", ru: "Неверный архив {path}: {error}" },
+    Message {
+        key: "err-bundle-entry-missing",
+        en: "Invalid bundle: {name} is listed in the manifest but missing",
+        ru: "Неверный архив: {name} указан в манифесте, но отсутствует",
+    },
+    Message { key: "err-bundle-entry-not-utf8", en: "Invalid bundle: {name} is not UTF-8", ru: "Неверный архив: {name} не в UTF-8" },
+    Message {
+        key: "err-invalid-commitment",
+        en: "Invalid commitment '{value}': expected processed, confirmed or finalized",
+        ru: "Неверный уровень подтверждения '{value}': ожидается processed, confirmed или finalized",
+    },
+    Message {
+        key: "err-invalid-concurrency",
+        en: "Invalid concurrency '{value}': expected a number greater than 0",
+        ru: "Неверный параллелизм '{value}': ожидается число больше 0",
+    },
+    Message {
+        key: "err-invalid-condition",
+        en: "Invalid condition '{value}': expected <step>.<field>, !<step>.<field> or <step>.<field> <op> <value>",
+        ru: "Неверное условие '{value}': ожидается <step>.<field>, !<step>.<field> или <step>.<field> <op> <value>",
+    },
+    Message { key: "err-invalid-bundle-config", en: "Invalid config in bundle: {error}", ru: "Неверная конфигурация в архиве: {error}" },
+    Message { key: "err-invalid-derivation-path", en: "Invalid derivation path in {url}: {error}", ru: "Неверный путь деривации в {url}: {error}" },
+    Message {
+        key: "err-invalid-explorer",
+        en: "Invalid explorer '{value}': expected solscan, solanafm or xray",
+        ru: "Неверный обозреватель '{value}': ожидается solscan, solanafm или xray",
+    },
+    Message { key: "err-invalid-funding-plan", en: "Invalid funding plan: {error}", ru: "Неверный план пополнения: {error}" },
+    Message {
+        key: "err-invalid-gossip-cache-ttl",
+        en: "Invalid gossip cache TTL '{value}': expected a number of seconds",
+        ru: "Неверный TTL кеша gossip '{value}': ожидается число секунд",
+    },
+    Message {
+        key: "err-invalid-hardware-wallet-url",
+        en: "Invalid hardware wallet URL {url}: {error}",
+        ru: "Неверный URL аппаратного кошелька {url}: {error}",
+    },
+    Message { key: "err-invalid-hex-seed", en: "Invalid hex seed '{value}'", ru: "Неверный hex-сид '{value}'" },
+    Message {
+        key: "err-invalid-idempotency-key",
+        en: "Invalid idempotency key '{value}': expected 1-{max} letters, digits, '-', '_', '.' or ':'",
+        ru: "Неверный ключ идемпотентности '{value}': ожидается 1-{max} букв, цифр, '-', '_', '.' или ':'",
+    },
+    Message { key: "err-invalid-identity-file", en: "Invalid identity file {path}: {reason}", ru: "Неверный файл идентификатора {path}: {reason}" },
+    Message { key: "err-invalid-env-keypair", en: "Invalid keypair in {source}: {reason}", ru: "Неверная ключевая пара в {source}: {reason}" },
+    Message { key: "err-invalid-language", en: "Invalid language '{value}': expected en or ru", ru: "Неверный язык '{value}': ожидается en или ru" },
+    Message {
+        key: "err-invalid-limit",
+        en: "Invalid limit '{value}': expected a number greater than 0",
+        ru: "Неверный лимит '{value}': ожидается число больше 0",
+    },
+    Message {
+        key: "err-invalid-log-level",
+        en: "Invalid log level '{value}': expected off, error, warn, info, debug or trace",
+        ru: "Неверный уровень журнала '{value}': ожидается off, error, warn, info, debug или trace",
+    },
+    Message {
+        key: "err-invalid-metrics-label",
+        en: "Invalid metrics label '{value}': only letters, digits, '_', '-', '.' and ':' are allowed",
+        ru: "Неверная метка метрик '{value}': допустимы только буквы, цифры, '_', '-', '.' и ':'",
+    },
+    Message { key: "err-invalid-mint", en: "Invalid mint: {error}", ru: "Неверный минт: {error}" },
+    Message {
+        key: "err-invalid-month",
+        en: "Invalid month '{value}': expected YYYY-MM (e.g. 2024-11)",
+        ru: "Неверный месяц '{value}': ожидается YYYY-MM (например, 2024-11)",
+    },
+    Message { key: "err-invalid-nonce-account", en: "Invalid nonce account {account}: {error}", ru: "Неверный nonce-аккаунт {account}: {error}" },
+    Message {
+        key: "err-invalid-low-balance",
+        en: "Invalid notify.low_balance '{value}': expected an amount like 0.5 or 500000000lamports",
+        ru: "Неверный notify.low_balance '{value}': ожидается сумма, например 0.5 или 500000000lamports",
+    },
+    Message {
+        key: "err-invalid-notify-url",
+        en: "Invalid notify.url '{url}': must start with http:// or https://",
+        ru: "Неверный notify.url '{url}': должен начинаться с http:// или https://",
+    },
+    Message {
+        key: "err-invalid-output-format",
+        en: "Invalid output format '{value}': expected text, json or csv",
+        ru: "Неверный формат вывода '{value}': ожидается text, json или csv",
+    },
+    Message { key: "err-invalid-pair", en: "Invalid pair '{pair}': expected validator:amount", ru: "Неверная пара '{pair}': ожидается validator:amount" },
+    Message { key: "err-invalid-price-response", en: "Invalid price API response: {error}", ru: "Неверный ответ API курсов: {error}" },
+    Message {
+        key: "err-invalid-priority-fee",
+        en: "Invalid priority fee '{value}': expected micro-lamports per compute unit, e.g. 5000",
+        ru: "Неверная приоритетная комиссия '{value}': ожидаются микролампорты за единицу вычислений, например 5000",
+    },
+    Message { key: "err-invalid-alias-pubkey", en: "Invalid pubkey for alias '{name}': {error}", ru: "Неверный публичный ключ для алиаса '{name}': {error}" },
+    Message { key: "err-invalid-label-pubkey", en: "Invalid pubkey for label '{label}': {error}", ru: "Неверный публичный ключ для метки '{label}': {error}" },
+    Message { key: "err-invalid-pubkey", en: "Invalid pubkey: {error}", ru: "Неверный публичный ключ: {error}" },
+    Message { key: "err-invalid-receipt", en: "Invalid receipt: {reason}", ru: "Неверная квитанция: {reason}" },
+    Message {
+        key: "err-invalid-reserve-sol",
+        en: "Invalid reserve_sol '{value}': expected an amount like 0.05 or 50000000lamports",
+        ru: "Неверный reserve_sol '{value}': ожидается сумма, например 0.05 или 50000000lamports",
+    },
+    Message { key: "err-invalid-script-step", en: "Invalid script step {index} ({op}): {message}", ru: "Неверный шаг сценария {index} ({op}): {message}" },
+    Message { key: "err-script-without-steps", en: "Invalid script: no steps", ru: "Неверный сценарий: нет шагов" },
+    Message { key: "err-invalid-script", en: "Invalid script: {error}", ru: "Неверный сценарий: {error}" },
+    Message {
+        key: "err-invalid-seed",
+        en: "Invalid seed '{part}': expected kind:value (prefix, str, pubkey, u64 or hex)",
+        ru: "Неверный сид '{part}': ожидается kind:value (prefix, str, pubkey, u64 или hex)",
+    },
+    Message {
+        key: "err-invalid-seed-phrase-length",
+        en: "Invalid seed phrase: expected 12, 15, 18, 21 or 24 words, got {words}",
+        ru: "Неверная сид-фраза: ожидается 12, 15, 18, 21 или 24 слова, получено {words}",
+    },
+    Message { key: "err-invalid-signature-value", en: "Invalid signature '{signature}': {error}", ru: "Неверная подпись '{signature}': {error}" },
+    Message {
+        key: "err-node-returned-invalid-signature",
+        en: "Invalid signature {signature} returned by the node: {error}",
+        ru: "Узел вернул неверную подпись {signature}: {error}",
+    },
+    Message { key: "err-invalid-signer-url", en: "Invalid signer URL {url}: {error}", ru: "Неверный URL подписанта {url}: {error}" },
+    Message {
+        key: "err-invalid-sort-key",
+        en: "Invalid sort key '{value}': expected name, balance or stake",
+        ru: "Неверный ключ сортировки '{value}': ожидается name, balance или stake",
+    },
+    Message { key: "err-invalid-bundle-state", en: "Invalid state in bundle: {error}", ru: "Неверное состояние в архиве: {error}" },
+    Message { key: "err-invalid-status", en: "Invalid status '{value}': expected {statuses}", ru: "Неверный статус '{value}': ожидается {statuses}" },
+    Message {
+        key: "err-invalid-trace-id",
+        en: "Invalid trace id '{value}': expected 1-{max} letters, digits, '-' or '_'",
+        ru: "Неверный trace id '{value}': ожидается 1-{max} букв, цифр, '-' или '_'",
+    },
+    Message {
+        key: "err-invalid-transaction-signature",
+        en: "Invalid transaction signature {signature}: {error}",
+        ru: "Неверная подпись транзакции {signature}: {error}",
+    },
+    Message {
+        key: "err-invalid-transaction-version",
+        en: "Invalid transaction version '{value}': expected legacy or 0",
+        ru: "Неверная версия транзакции '{value}': ожидается legacy или 0",
+    },
+    Message { key: "err-invalid-transaction", en: "Invalid transaction: {error}", ru: "Неверная транзакция: {error}" },
+    Message { key: "err-invalid-u64-seed", en: "Invalid u64 seed '{value}'", ru: "Неверный u64-сид '{value}'" },
+    Message { key: "err-invalid-validator", en: "Invalid validator '{validator}'", ru: "Неверный валидатор '{validator}'" },
+    Message { key: "err-invalid-validator-pubkey", en: "Invalid validator pubkey: {error}", ru: "Неверный публичный ключ валидатора: {error}" },
+    Message { key: "err-invalid-option-value", en: "Invalid value for --{name}: {value}", ru: "Неверное значение --{name}: {value}" },
+    Message {
+        key: "err-node-returned-invalid-vote-account",
+        en: "Invalid vote account {vote_pubkey} returned by the node: {error}",
+        ru: "Узел вернул неверный аккаунт голосования {vote_pubkey}: {error}",
+    },
+    Message {
+        key: "err-invalid-weight",
+        en: "Invalid weight '{value}': expected a non-negative number with at most 6 decimals",
+        ru: "Неверный вес '{value}': ожидается неотрицательное число не более чем с 6 знаками после запятой",
+    },
+    Message {
+        key: "err-invalid-window",
+        en: "Invalid window '{value}': expected minutes (30m), hours (24h), days (7d) or all",
+        ru: "Неверное окно '{value}': ожидаются минуты (30m), часы (24h), дни (7d) или all",
+    },
+    Message { key: "err-invalid-plan-field", en: "Invalid {field} '{value}' in funding plan", ru: "Неверное поле {field} '{value}' в плане пополнения" },
+    Message {
+        key: "err-invalid-whole-number",
+        en: "Invalid {flag} '{value}': expected a positive whole number",
+        ru: "Неверный {flag} '{value}': ожидается целое положительное число",
+    },
+    Message { key: "err-invalid-env-value", en: "Invalid {variable}: {error}", ru: "Неверная {variable}: {error}" },
+    Message {
+        key: "err-invalid-budget-amount",
+        en: "Invalid {scope}.{name} '{value}': expected an amount like 5 or 500000000lamports",
+        ru: "Неверный {scope}.{name} '{value}': ожидается сумма, например 5 или 500000000lamports",
+    },
+    Message { key: "err-language-already-set", en: "Language is already set", ru: "Язык уже задан" },
+    Message { key: "err-memo-empty", en: "Memo cannot be empty", ru: "Memo не может быть пустым" },
+    Message {
+        key: "err-memo-too-long",
+        en: "Memo is {length} bytes long; at most {max} bytes fit in a funding transaction",
+        ru: "Длина memo {length} байт; в транзакцию пополнения помещается не более {max} байт",
+    },
+    Message { key: "err-mint-missing", en: "Mint {mint} does not exist", ru: "Минт {mint} не существует" },
+    Message {
+        key: "err-missing-signers",
+        en: "Missing signer for {signers}: pass its keypair with --keypair, or use --sign-only to sign partially",
+        ru: "Нет подписанта для {signers}: передайте его ключевую пару через --keypair или используйте --sign-only для частичной подписи",
+    },
+    Message { key: "err-missing-signer", en: "Missing signer for {pubkey}", ru: "Нет подписанта для {pubkey}" },
+    Message { key: "err-missing-validator-before-amount", en: "Missing validator before amount '{amount}'", ru: "Не указан валидатор перед суммой '{amount}'" },
+    Message { key: "err-no-notify-section", en: "No [notify] section configured", ru: "Раздел [notify] не настроен" },
+    Message { key: "err-no-funding-wallet", en: "No funding wallet configured", ru: "Кошелёк пополнения не настроен" },
+    Message {
+        key: "err-no-funding-wallet-pass-old",
+        en: "No funding wallet configured in {path}; pass --old <keypair_path>",
+        ru: "В {path} не настроен кошелёк пополнения; передайте --old <keypair_path>",
+    },
+    Message {
+        key: "err-no-funding-wallet-given",
+        en: "No funding wallet given or configured in {path}",
+        ru: "Кошелёк пополнения не передан и не настроен в {path}",
+    },
+    Message {
+        key: "err-no-hardware-wallet",
+        en: "No hardware wallet found for {url}; connect and unlock the device",
+        ru: "Аппаратный кошелёк для {url} не найден; подключите и разблокируйте устройство",
+    },
+    Message {
+        key: "err-no-message",
+        en: "No message given: pass it as text or with --file",
+        ru: "Сообщение не передано: укажите его текстом или через --file",
+    },
+    Message { key: "err-no-seeds", en: "No seeds given", ru: "Сиды не указаны" },
+    Message { key: "err-no-transactions-to-combine", en: "No transactions to combine", ru: "Нет транзакций для объединения" },
+    Message { key: "err-no-transfers", en: "No transfers to send", ru: "Нет переводов для отправки" },
+    Message { key: "err-no-bump-seed", en: "No valid bump seed for these seeds", ru: "Нет допустимого bump-сида для этих сидов" },
+    Message {
+        key: "err-no-validators-given",
+        en: "No validators given; pass pubkeys or aliases, --file <path>, or add a [validators] table to {path}",
+        ru: "Валидаторы не указаны; передайте публичные ключи или алиасы, --file <path> или добавьте таблицу [validators] в {path}",
+    },
+    Message { key: "err-no-validators-on-stdin", en: "No validators on standard input", ru: "На стандартном вводе нет валидаторов" },
+    Message { key: "err-no-validators-to-allocate", en: "No validators to allocate to", ru: "Нет валидаторов для распределения" },
+    Message { key: "err-nonce-account-missing", en: "Nonce account {nonce_account} does not exist", ru: "Nonce-аккаунт {nonce_account} не существует" },
+    Message {
+        key: "err-nonce-authority-mismatch",
+        en: "Nonce account {nonce_account} is controlled by {authority}, not by {signer}; pass --nonce-authority",
+        ru: "Nonce-аккаунт {nonce_account} контролируется {authority}, а не {signer}; передайте --nonce-authority",
+    },
+    Message {
+        key: "err-not-confirmed",
+        en: "Not confirmed; nothing was sent (use --yes in scripts)",
+        ru: "Не подтверждено; ничего не отправлено (используйте --yes в скриптах)",
+    },
+    Message {
+        key: "err-nothing-to-plan",
+        en: "Nothing to plan: every PDA already holds its allocation",
+        ru: "Нечего планировать: на каждом PDA уже есть его доля",
+    },
+    Message {
+        key: "err-nothing-to-transfer",
+        en: "Nothing to transfer: payer balance {balance} lamports does not exceed fee {fee} + rent-exempt reserve {reserve} lamports",
+        ru: "Нечего переводить: баланс плательщика {balance} лампортов не превышает комиссию {fee} + резерв для освобождения от аренды {reserve} лампортов",
+    },
+    Message {
+        key: "err-notification-rejected",
+        en: "Notification endpoint {target} rejected the message: {status} {body}",
+        ru: "Адрес уведомлений {target} отклонил сообщение: {status} {body}",
+    },
+    Message { key: "err-same-wallet", en: "Old and new keypairs are the same wallet", ru: "Старая и новая ключевые пары - один и тот же кошелёк" },
+    Message { key: "err-option-takes-no-value", en: "Option --{name} does not take a value", ru: "Параметр --{name} не принимает значение" },
+    Message { key: "err-option-needs-value", en: "Option --{name} requires a value", ru: "Параметру --{name} требуется значение" },
+    Message { key: "err-passphrase-empty", en: "Passphrase cannot be empty", ru: "Парольная фраза не может быть пустой" },
+    Message { key: "err-passphrases-differ", en: "Passphrases do not match", ru: "Парольные фразы не совпадают" },
+    Message {
+        key: "err-payer-without-token-account",
+        en: "Payer {payer} has no token account for mint {mint}",
+        ru: "У плательщика {payer} нет токен-аккаунта для минта {mint}",
+    },
+    Message {
+        key: "err-price-missing-for-day",
+        en: "Price API has no SOL price in {currency} for that day",
+        ru: "В API курсов нет курса SOL в {currency} за этот день",
+    },
+    Message { key: "err-price-missing", en: "Price API response has no SOL price in {currency}", ru: "В ответе API курсов нет курса SOL в {currency}" },
+    Message { key: "err-price-api-status", en: "Price API {url} answered {status}", ru: "API курсов {url} ответил {status}" },
+    Message {
+        key: "err-pushgateway-rejected",
+        en: "Pushgateway {url} rejected metrics: {status} {body}",
+        ru: "Pushgateway {url} отклонил метрики: {status} {body}",
+    },
+    Message { key: "err-rpc-settings-already-configured", en: "RPC settings are already configured", ru: "Настройки RPC уже заданы" },
+    Message {
+        key: "err-refusing-overwrite",
+        en: "Refusing to overwrite {paths}; use --force to replace them",
+        ru: "Отказ перезаписать {paths}; используйте --force, чтобы заменить их",
+    },
+    Message {
+        key: "err-script-funds-without-wallet",
+        en: "Script funds a validator without a keypair and no funding wallet is configured in {path}",
+        ru: "Сценарий пополняет валидатора без ключевой пары, а кошелёк пополнения в {path} не настроен",
+    },
+    Message {
+        key: "err-script-notifies-without-notify",
+        en: "Script sends a notification but {path} has no [notify] section",
+        ru: "Сценарий отправляет уведомление, но в {path} нет раздела [notify]",
+    },
+    Message { key: "err-seed-too-long", en: "Seed '{part}' is longer than {max} bytes", ru: "Сид '{part}' длиннее {max} байт" },
+    Message { key: "err-squads-multisig-missing", en: "Squads multisig {address} does not exist", ru: "Мультиподпись Squads {address} не существует" },
+    Message {
+        key: "err-state-file-exists",
+        en: "State file {path} already exists; continue its run with --resume {resume_path}",
+        ru: "Файл состояния {path} уже существует; продолжите его запуск с --resume {resume_path}",
+    },
+    Message {
+        key: "err-status-no-longer-available",
+        en: "Status of transaction {signature} is no longer available",
+        ru: "Статус транзакции {signature} больше недоступен",
+    },
+    Message { key: "err-strict-mode", en: "Strict mode: {warnings}", ru: "Строгий режим: {warnings}" },
+    Message {
+        key: "err-telegram-needs-token",
+        en: "Telegram notifications need notify.bot_token and notify.chat_id",
+        ru: "Уведомлениям Telegram нужны notify.bot_token и notify.chat_id",
+    },
+    Message { key: "err-terminal", en: "Terminal error: {error}", ru: "Ошибка терминала: {error}" },
+    Message {
+        key: "err-geyser-needs-feature",
+        en: "The Geyser backend needs a build with the geyser feature (cargo build --features geyser)",
+        ru: "Бэкенду Geyser нужна сборка с функцией geyser (cargo build --features geyser)",
+    },
+    Message {
+        key: "err-amounts-overflow",
+        en: "The amounts of the transfers add up to more lamports than can be sent",
+        ru: "Суммы переводов в сумме дают больше лампортов, чем можно отправить",
+    },
+    Message { key: "err-plan-without-transfers", en: "The funding plan has no transfers", ru: "В плане пополнения нет переводов" },
+    Message {
+        key: "err-plan-total-mismatch",
+        en: "The funding plan total of {total} lamports is not the sum of its transfers ({sum} lamports)",
+        ru: "Итог плана пополнения {total} лампортов не равен сумме его переводов ({sum} лампортов)",
+    },
+    Message {
+        key: "err-payer-cannot-approve",
+        en: "The funding wallet of a plan cannot approve it: use a different approver key",
+        ru: "Кошелёк пополнения плана не может одобрить его: используйте другой ключ одобряющего",
+    },
+    Message {
+        key: "err-plan-hash-mismatch",
+        en: "The plan does not match the approved hash: it was changed after approval or another plan was approved",
+        ru: "План не совпадает с одобренным хешем: его изменили после одобрения или одобрен другой план",
+    },
+    Message {
+        key: "err-plan-payer-mismatch",
+        en: "The plan funds from {payer}, but the given keypair is {keypair}",
+        ru: "План пополняется с {payer}, а переданная ключевая пара - {keypair}",
+    },
+    Message {
+        key: "err-signature-mismatch",
+        en: "The signature does not match the message and pubkey {pubkey}",
+        ru: "Подпись не соответствует сообщению и публичному ключу {pubkey}",
+    },
+    Message {
+        key: "err-state-payer-mismatch",
+        en: "The state file was written for funding wallet {payer}, not {keypair}; resume it with the same wallet",
+        ru: "Файл состояния записан для кошелька пополнения {payer}, а не {keypair}; продолжайте с тем же кошельком",
+    },
+    Message {
+        key: "err-token-account-mismatch",
+        en: "Token account {address} holds mint {mint} for {owner}, expected mint {expected_mint} for {expected_owner}",
+        ru: "Токен-аккаунт {address} хранит минт {mint} для {owner}, ожидался минт {expected_mint} для {expected_owner}",
+    },
+    Message { key: "err-token-account-frozen", en: "Token account {address} is frozen", ru: "Токен-аккаунт {address} заморожен" },
+    Message { key: "err-trace-id-already-set", en: "Trace id is already set", ru: "Trace id уже задан" },
+    Message {
+        key: "err-missing-signatures",
+        en: "Transaction is missing signatures from {signers}; combine it with their --sign-only output",
+        ru: "В транзакции не хватает подписей {signers}; объедините её с их выводом --sign-only",
+    },
+    Message { key: "err-signatures-do-not-verify", en: "Transaction signatures do not verify", ru: "Подписи транзакции не проходят проверку" },
+    Message {
+        key: "err-balance-delta-mismatch",
+        en: "Transaction {signature} changed the balance of PDA {pda} by {delta} lamports instead of {expected} (pre {pre}, post {post})",
+        ru: "Транзакция {signature} изменила баланс PDA {pda} на {delta} лампортов вместо {expected} (до {pre}, после {post})",
+    },
+    Message {
+        key: "err-pda-not-credited",
+        en: "Transaction {signature} did not credit PDA {pda}",
+        ru: "Транзакция {signature} не зачислила средства на PDA {pda}",
+    },
+    Message { key: "err-pda-not-touched", en: "Transaction {signature} does not touch PDA {pda}", ru: "Транзакция {signature} не затрагивает PDA {pda}" },
+    Message { key: "err-transaction-failed", en: "Transaction {signature} failed: {error}", ru: "Транзакция {signature} не выполнена: {error}" },
+    Message {
+        key: "err-not-confirmed-in-time",
+        en: "Transaction {signature} was not confirmed within {seconds} seconds",
+        ru: "Транзакция {signature} не подтверждена за {seconds} секунд",
+    },
+    Message { key: "err-transaction-not-found", en: "Transaction {signature} was not found by the node", ru: "Узел не нашёл транзакцию {signature}" },
+    Message {
+        key: "err-transactions-differ",
+        en: "Transactions cannot be combined: they were signed for different messages (check amount, fee payer and blockhash)",
+        ru: "Транзакции нельзя объединить: они подписаны для разных сообщений (проверьте сумму, плательщика комиссии и blockhash)",
+    },
+    Message {
+        key: "err-plan-transfer-without-amount",
+        en: "Transfer to validator {validator} in funding plan has no amount",
+        ru: "У перевода валидатору {validator} в плане пополнения нет суммы",
+    },
+    Message { key: "err-unknown-alias", en: "Unknown alias '{name}'", ru: "Неизвестный алиас '{name}'" },
+    Message { key: "err-unknown-operation", en: "Unknown operation '{name}'", ru: "Неизвестная операция '{name}'" },
+    Message { key: "err-unknown-option", en: "Unknown option '--{name}'", ru: "Неизвестный параметр '--{name}'" },
+    Message { key: "err-unknown-profile", en: "Unknown profile '{name}'", ru: "Неизвестный профиль '{name}'" },
+    Message {
+        key: "err-unknown-seed-kind",
+        en: "Unknown seed kind '{kind}': expected prefix, str, pubkey, u64 or hex",
+        ru: "Неизвестный тип сида '{kind}': ожидается prefix, str, pubkey, u64 или hex",
+    },
+    Message {
+        key: "err-unsupported-currency",
+        en: "Unsupported currency '{value}': expected usd or eur",
+        ru: "Неподдерживаемая валюта '{value}': ожидается usd или eur",
+    },
+    Message { key: "err-unsupported-plan-version", en: "Unsupported funding plan version {version}", ru: "Неподдерживаемая версия плана пополнения {version}" },
+    Message {
+        key: "err-unsupported-shell",
+        en: "Unsupported shell '{value}': expected bash, zsh or fish",
+        ru: "Неподдерживаемая оболочка '{value}': ожидается bash, zsh или fish",
+    },
+    Message { key: "err-validator-listed-twice", en: "Validator {validator} is listed more than once", ru: "Валидатор {validator} указан более одного раза" },
+    Message {
+        key: "err-plan-validator-listed-twice",
+        en: "Validator {validator} is listed more than once in funding plan",
+        ru: "Валидатор {validator} указан в плане пополнения более одного раза",
+    },
+    Message {
+        key: "err-alias-already-assigned",
+        en: "Validator {validator} already has the alias '{alias}'",
+        ru: "У валидатора {validator} уже есть алиас '{alias}'",
+    },
+    Message {
+        key: "err-identity-mismatch",
+        en: "Validator {validator} does not match {source} ({identity})",
+        ru: "Валидатор {validator} не совпадает с {source} ({identity})",
+    },
+    Message {
+        key: "err-vault-transaction-too-large",
+        en: "Vault transaction has too many accounts or instructions",
+        ru: "В транзакции хранилища слишком много аккаунтов или инструкций",
+    },
+    Message {
+        key: "err-not-vote-state",
+        en: "Vote account {vote_pubkey} has data that is not a vote state",
+        ru: "Данные аккаунта голосования {vote_pubkey} не являются состоянием голосования",
+    },
+    Message { key: "err-vote-account-not-found", en: "Vote account {vote_pubkey} not found", ru: "Аккаунт голосования {vote_pubkey} не найден" },
+    Message {
+        key: "err-weights-and-validators",
+        en: "With a weights file the validators are taken from it; do not list them again",
+        ru: "При файле весов валидаторы берутся из него; не перечисляйте их повторно",
+    },
+    Message { key: "err-alias-add-arguments", en: "alias add requires a name and a validator pubkey", ru: "alias add требует имя и публичный ключ валидатора" },
+    Message { key: "err-alias-remove-arguments", en: "alias remove requires a name", ru: "alias remove требует имя" },
+    Message {
+        key: "err-allocate-out-needs-payer",
+        en: "allocate --out needs the funding wallet of the plan: pass --payer or configure one in {path}",
+        ru: "allocate --out требует кошелёк пополнения плана: передайте --payer или настройте его в {path}",
+    },
+    Message {
+        key: "err-apply-needs-approval",
+        en: "apply requires --require-approval-file <approval.json> from a second operator",
+        ru: "apply требует --require-approval-file <approval.json> от второго оператора",
+    },
+    Message {
+        key: "err-dashboard-needs-terminal",
+        en: "dashboard needs an interactive terminal; use pda-batch pda-balance in scripts",
+        ru: "dashboard требует интерактивный терминал; в скриптах используйте pda-batch pda-balance",
+    },
+    Message { key: "err-derive-needs-seed-spec", en: "derive requires --seed-spec", ru: "derive требует --seed-spec" },
+    Message { key: "err-gossip-diff-needs-snapshot", en: "gossip-diff requires a snapshot path", ru: "gossip-diff требует путь к снимку" },
+    Message { key: "err-gossip-snapshot-needs-out", en: "gossip-snapshot requires --out <path>", ru: "gossip-snapshot требует --out <path>" },
+    Message {
+        key: "err-grind-needs-pattern",
+        en: "keygen grind requires --starts-with <prefix:count>, --ends-with <suffix:count> or --starts-and-ends-with <prefix:suffix:count>",
+        ru: "keygen grind требует --starts-with <prefix:count>, --ends-with <suffix:count> или --starts-and-ends-with <prefix:suffix:count>",
+    },
+    Message { key: "err-keygen-new-needs-outfile", en: "keygen new requires --outfile <path>", ru: "keygen new требует --outfile <path>" },
+    Message {
+        key: "err-fund-many-needs-keypair",
+        en: "pda-fund-many requires a keypair path; no funding wallet configured in {path}",
+        ru: "pda-fund-many требует путь к ключевой паре; в {path} не настроен кошелёк пополнения",
+    },
+    Message {
+        key: "err-fund-token-needs-keypair",
+        en: "pda-fund-token requires a keypair path; no funding wallet configured in {path}",
+        ru: "pda-fund-token требует путь к ключевой паре; в {path} не настроен кошелёк пополнения",
+    },
+    Message {
+        key: "err-plan-execute-needs-approve",
+        en: "plan execute requires --approve <hash> from the reviewer of the plan",
+        ru: "plan execute требует --approve <hash> от проверяющего план",
+    },
+    Message {
+        key: "err-plan-execute-needs-keypair",
+        en: "plan execute requires a keypair path; no funding wallet configured in {path}",
+        ru: "plan execute требует путь к ключевой паре; в {path} не настроен кошелёк пополнения",
+    },
+    Message { key: "err-sign-message-needs-keypair", en: "sign-message requires --keypair <keypair>", ru: "sign-message требует --keypair <keypair>" },
+    Message { key: "err-state-export-needs-out", en: "state export requires --out <bundle.tar.zst>", ru: "state export требует --out <bundle.tar.zst>" },
+    Message { key: "err-state-import-needs-bundle", en: "state import requires a bundle path", ru: "state import требует путь к архиву" },
+    Message {
+        key: "err-verify-message-arguments",
+        en: "verify-message requires a pubkey and a signature",
+        ru: "verify-message требует публичный ключ и подпись",
+    },
+    Message { key: "err-wallet-rotate-needs-new", en: "wallet rotate requires --new <keypair_path>", ru: "wallet rotate требует --new <keypair_path>" },
+    Message { key: "err-not-squads-multisig", en: "{address} is not a Squads multisig", ru: "{address} не является мультиподписью Squads" },
+    Message {
+        key: "err-env-scheme-needs-variable",
+        en: "{scheme} needs a variable name, e.g. {example}DZ_FUNDING_KEYPAIR",
+        ru: "{scheme} требует имя переменной, например {example}DZ_FUNDING_KEYPAIR",
+    },
+    Message { key: "err-from-layer", en: "{error} (from {layer})", ru: "{error} (из {layer})" },
+    Message {
+        key: "err-not-multisig-member",
+        en: "{payer} is not a member of multisig {multisig} with the permission to create proposals",
+        ru: "{payer} не является участником мультиподписи {multisig} с правом создавать предложения",
+    },
+    Message { key: "err-already-encrypted", en: "{path} is already encrypted", ru: "{path} уже зашифрован" },
+    Message { key: "err-notifications-need-url", en: "{kind} notifications need notify.url", ru: "Уведомлениям {kind} нужен notify.url" },
+    Message { key: "err-transfers-not-confirmed", en: "{failed} of {total} transfers were not confirmed", ru: "{failed} из {total} переводов не подтверждены" },
+    Message {
+        key: "err-file-exists",
+        en: "{path} already exists (use --force to overwrite)",
+        ru: "{path} уже существует (используйте --force для перезаписи)",
+    },
+    Message { key: "err-line-expected-amount", en: "{path} line {line}: expected validator,amount", ru: "{path}, строка {line}: ожидается validator,amount" },
+    Message { key: "err-line-expected-weight", en: "{path} line {line}: expected validator,weight", ru: "{path}, строка {line}: ожидается validator,weight" },
+    Message { key: "err-line", en: "{path} line {line}: {error}", ru: "{path}, строка {line}: {error}" },
+    Message {
+        key: "err-airdrop-on-mainnet",
+        en: "{url} is a mainnet-beta endpoint; airdrops are only available on devnet, testnet and local test validators",
+        ru: "{url} - узел mainnet-beta; airdrop доступен только в devnet, testnet и на локальных тестовых валидаторах",
+    },
+    Message {
+        key: "warning-rpc-fallback-priority-fee",
+        en: "the priority fee is sent without checking it against recent network fees",
+        ru: "приоритетная комиссия отправляется без сверки с недавними комиссиями сети",
+    },
+    Message { key: "warning-rpc-fallback-legacy", en: "the transfer is sent as a legacy transaction", ru: "перевод отправляется как legacy-транзакция" },
+    Message {
+        key: "warning-rpc-unsupported-node-version",
+        en: "node version {version} is older than {major}.{minor}.{patch}",
+        ru: "версия узла {version} старше {major}.{minor}.{patch}",
+    },
+    Message {
+        key: "warning-rpc-unsupported-feature-gate",
+        en: "feature gate {feature} is not active on the cluster",
+        ru: "feature gate {feature} не активирован в кластере",
+    },
+    Message { key: "err-rpc-check-call-failed", en: "{method} failed: {error}", ru: "Вызов {method} не выполнен: {error}" },
+    Message { key: "err-rpc-check-no-nodes", en: "getClusterNodes returned no nodes", ru: "getClusterNodes не вернул ни одного узла" },
+    Message {
+        key: "err-rpc-check-slot-lag",
+        en: "{lag} slots behind the reference (at most {max} allowed)",
+        ru: "Отставание от эталона {lag} слотов (допускается не более {max})",
+    },
+    Message { key: "err-no-transfers-given", en: "No transfers given", ru: "Переводы не указаны" },
+    Message {
+        key: "opt-pda",
+        en: "keygen grind: match the deposit PDA of the keypair as a validator identity, not its own address",
+        ru: "keygen grind: сравнивать с образцом PDA депозита ключа как identity валидатора, а не его собственный адрес",
+    },
+    Message {
+        key: "keypair-pda-written",
+        en: "Wrote validator identity {pubkey} (deposit PDA {pda}) to {path}",
+        ru: "Identity валидатора {pubkey} (PDA депозита {pda}) записан в {path}",
+    },
+];
+
+/// Looks up a message in a language
+///
+/// # Arguments
+/// * `key` - Message key from `MESSAGES`
+/// * `lang` - Language
+///
+/// # Returns
+/// * `&'static str` - Text with its placeholders, or the key itself if it is not in the catalog
+pub fn text_in(key: &'static str, lang: Lang) -> &'static str {
+    MESSAGES.iter().find(|message| message.key == key).map(|message| message.text(lang)).unwrap_or(key)
+}
+
+/// Looks up a message in the language of this process
+pub fn text(key: &'static str) -> &'static str {
+    text_in(key, lang())
+}
+
+/// Looks up a message in the language of this process and fills in its placeholders
+///
+/// # Arguments
+/// * `key` - Message key from `MESSAGES`
+/// * `args` - Placeholder names (without braces) and values
+///
+/// # Returns
+/// * `String` - Message text
+pub fn message(key: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    fill(text(key), args)
+}
+
+/// Replaces `{name}` placeholders in a message text
+fn fill(text: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    args.iter().fold(text.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), &value.to_string()))
+}
+
+/// Returns true if a confirmation answer means yes (`y`/`yes`, and `д`/`да` in Russian)
+pub fn is_yes(answer: &str) -> bool {
+    let answer = answer.trim().to_lowercase();
+    matches!(answer.as_str(), "y" | "yes") || (lang() == Lang::Ru && matches!(answer.as_str(), "д" | "да"))
+}
+
+/// Placeholders of error and warning templates whose values are messages themselves and are translated too
+const NESTED_PLACEHOLDERS: &[&str] = &["error", "message", "reason", "fallback"];
+
+/// Renders an error for the terminal in the language of this process
+///
+/// `Display` of `Error` stays English for JSON, CSV and the audit log. The message is translated with the `err-*`
+/// and `warning-*` templates of the catalog; a message without a template keeps its English text behind the
+/// `error-kind-*` message of the error variant.
+///
+/// # Arguments
+/// * `error` - Error to print
+///
+/// # Returns
+/// * `String` - Error text (without the "Error:" prefix)
+pub fn error_text(error: &Error) -> String {
+    error_text_in(error, lang())
+}
+
+/// Renders an error for the terminal in a language (see `error_text`)
+pub fn error_text_in(error: &Error, lang: Lang) -> String {
+    if lang == Lang::En {
+        return error.to_string();
+    }
+    translate(error.message(), lang).unwrap_or_else(|| {
+        let key = format!("error-kind-{}", error.kind().replace('_', "-"));
+        let text = MESSAGES.iter().find(|message| message.key == key).map_or("{message}", |message| message.text(lang));
+        fill(text, &[("message", &error.message())])
+    })
+}
+
+/// Renders a warning for the terminal in the language of this process
+///
+/// The `code` and English `message` of the warning stay unchanged for JSON output; a message without a template is
+/// printed in English.
+pub fn warning_text(warning: &Warning) -> String {
+    localize(&warning.message)
+}
+
+/// Translates an English error or warning message into the language of this process
+///
+/// # Arguments
+/// * `text` - English message, e.g. the error of one row of a batch
+///
+/// # Returns
+/// * `String` - Translated message, or the message itself if no template matches it
+pub fn localize(text: &str) -> String {
+    translate(text, lang()).unwrap_or_else(|| text.to_string())
+}
+
+/// Finds the most specific error or warning template matching an English message and fills in its translation
+fn translate(text: &str, lang: Lang) -> Option<String> {
+    if lang == Lang::En {
+        return None;
+    }
+    let (message, values) = MESSAGES
+        .iter()
+        .filter(|message| message.key.starts_with("err-") || message.key.starts_with("warning-"))
+        .filter_map(|message| capture(message.en, text).map(|values| (message, values)))
+        .max_by_key(|(message, _)| literal_len(message.en))?;
+    let values: Vec<(&str, String)> = values
+        .into_iter()
+        .map(|(name, value)| {
+            let nested = match name {
+                // "Strict mode: {warnings}" joins the warning messages with "; "
+                "warnings" => Some(value.split("; ").map(|warning| translate(warning, lang).unwrap_or_else(|| warning.to_string())).collect::<Vec<_>>().join("; ")),
+                name if NESTED_PLACEHOLDERS.contains(&name) => translate(value, lang),
+                _ => None,
+            };
+            (name, nested.unwrap_or_else(|| value.to_string()))
+        })
+        .collect();
+    let args: Vec<(&str, &dyn fmt::Display)> = values.iter().map(|(name, value)| (*name, value as &dyn fmt::Display)).collect();
+    Some(fill(message.text(lang), &args))
+}
+
+/// Length of the literal text of a template, so the most specific of several matching templates wins
+fn literal_len(template: &str) -> usize {
+    template.split('{').map(|piece| piece.split_once('}').map_or(piece, |(_, literal)| literal).len()).sum()
+}
+
+/// Matches a message against an English template and returns the values of its placeholders
+///
+/// Literal parts between placeholders are matched at their first occurrence, so the last placeholder takes the rest
+/// of a nested message like "Failed to get balance: {error}".
+fn capture<'a>(template: &'a str, text: &'a str) -> Option<Vec<(&'a str, &'a str)>> {
+    let mut pieces = template.split('{');
+    let mut rest = text.strip_prefix(pieces.next()?)?;
+    let mut pieces = pieces.peekable();
+    let mut values = Vec::new();
+    while let Some(piece) = pieces.next() {
+        let (name, literal) = piece.split_once('}')?;
+        let end = match pieces.peek() {
+            None => rest.strip_suffix(literal)?.len(),
+            Some(_) if literal.is_empty() => return None,
+            Some(_) => rest.find(literal)?,
+        };
+        values.push((name, &rest[..end]));
+        rest = &rest[end + literal.len()..];
+    }
+    rest.is_empty().then_some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Placeholder names of a message text
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text.split('{').skip(1).filter_map(|rest| rest.split_once('}').map(|(name, _)| name)).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_parse_and_resolve_lang() {
+        assert_eq!(Lang::parse("RU").unwrap(), Lang::Ru);
+        assert_eq!(Lang::parse("en").unwrap(), Lang::En);
+        assert!(Lang::parse("de").unwrap_err().is_user_error());
+
+        assert_eq!(Lang::resolve(None, None).unwrap(), Lang::En);
+        assert_eq!(Lang::resolve(None, Some("ru")).unwrap(), Lang::Ru);
+        assert_eq!(Lang::resolve(Some("en"), Some("ru")).unwrap(), Lang::En);
+        assert_eq!(Lang::resolve(None, Some(" ")).unwrap(), Lang::En);
+        let error = Lang::resolve(None, Some("fr")).unwrap_err();
+        assert!(error.to_string().contains("(from DZ_LANG)"), "{}", error);
+    }
+
+    #[test]
+    fn test_catalog_is_complete() {
+        let mut keys = HashSet::new();
+        for message in MESSAGES {
+            assert!(keys.insert(message.key), "Duplicate key {}", message.key);
+            assert!(!message.en.is_empty() && !message.ru.is_empty(), "{}", message.key);
+            assert_eq!(placeholders(message.en), placeholders(message.ru), "{}", message.key);
+        }
+    }
+
+    #[test]
+    fn test_used_keys_are_in_catalog() {
+        // A missing key would print the key itself, so every literal key of the CLI must be in the catalog
+        let sources = [
+            include_str!("main.rs"),
+            include_str!("dashboard.rs"),
+            include_str!("help.rs"),
+            include_str!("signer.rs"),
+            include_str!("keystore.rs"),
+            include_str!("batch.rs"),
+            include_str!("fund_many.rs"),
+        ];
+        let keys: HashSet<&str> = MESSAGES.iter().map(|message| message.key).collect();
+        for source in sources {
+            for call in ["i18n::text(", "i18n::message("].into_iter().flat_map(|function| source.split(function).skip(1)) {
+                let Some(rest) = call.trim_start().strip_prefix('"') else { continue };
+                let key = rest.split('"').next().unwrap_or_default();
+                assert!(keys.contains(key), "Key {} is not in the catalog", key);
+            }
+        }
+
+        // Every error variant and warning code has a message for the terminal (see `error_text`)
+        let kinds = include_str!("error.rs")
+            .lines()
+            .filter_map(|line| line.trim().strip_suffix("\",")?.split_once("(_) => \"").map(|(_, kind)| format!("error-kind-{}", kind)));
+        let codes = include_str!("report.rs")
+            .lines()
+            .filter_map(|line| line.strip_prefix("pub const WARN_")?.split_once("= \"").map(|(_, code)| format!("warning-{}", code.trim_end_matches("\";"))));
+        let required: Vec<String> = kinds.chain(codes).map(|key| key.replace('_', "-")).collect();
+        assert!(required.iter().filter(|key| key.starts_with("error-kind-")).count() >= 13, "{:?}", required);
+        assert!(required.iter().filter(|key| key.starts_with("warning-")).count() >= 21, "{:?}", required);
+        for key in required {
+            let message = MESSAGES.iter().find(|message| message.key == key);
+            assert!(message.is_some_and(|message| !message.ru.is_empty()), "Key {} is not in the catalog", key);
+        }
+    }
+
+    #[test]
+    fn test_errors_and_warnings_are_translated() {
+        let error = Error::InvalidInput("Invalid pubkey format: String is the wrong size".to_string());
+        assert_eq!(error_text_in(&error, Lang::En), "Invalid pubkey format: String is the wrong size");
+        assert_eq!(error_text_in(&error, Lang::Ru), "Неверный формат публичного ключа: Строка неверной длины");
+
+        // The most specific template wins and nested messages are translated too
+        let error = Error::FundingCancelled(
+            "Funding cancelled: PDA balance after the deposit (5 lamports) would be below the rent-exempt minimum of 890880 lamports; use --force to send anyway"
+                .to_string(),
+        );
+        assert_eq!(
+            error_text_in(&error, Lang::Ru),
+            "Пополнение отменено: Баланс PDA после пополнения (5 лампортов) будет ниже минимума для освобождения от аренды в 890880 лампортов; используйте --force, чтобы всё равно отправить"
+        );
+        let error = Error::RpcUnavailable("Failed to get balance: Permission denied (os error 13)".to_string());
+        assert_eq!(error_text_in(&error, Lang::Ru), "Не удалось получить баланс: Доступ запрещён (ошибка ОС 13)");
+        let error = Error::StrictWarning("Strict mode: Gossip check skipped; Validator has no vote account".to_string());
+        assert_eq!(error_text_in(&error, Lang::Ru), "Строгий режим: Проверка gossip пропущена; У валидатора нет аккаунта голосования");
+
+        // A message without a template keeps its English text behind the error variant
+        let error = Error::Rpc("Node is behind".to_string());
+        assert_eq!(error_text_in(&error, Lang::Ru), "Ошибка RPC: Node is behind");
+
+        assert_eq!(translate("Validator 7x59 is NOT found in Solana gossip network", Lang::Ru).unwrap(), "Валидатор 7x59 НЕ найден в сети gossip Solana");
+        assert_eq!(translate("Validator 7x59 is NOT found in Solana gossip network", Lang::En), None);
+        assert_eq!(translate("Unexpected response", Lang::Ru), None);
+        let warning = Warning::new(crate::report::WARN_GOSSIP_CHECK_SKIPPED, "Gossip check skipped");
+        assert_eq!(warning_text(&warning), "Gossip check skipped");
+    }
+
+    #[test]
+    fn test_message_fills_placeholders() {
+        assert_eq!(text_in("pda-address", Lang::Ru), "Адрес PDA: {pda}");
+        assert_eq!(fill(text_in("pda-address", Lang::En), &[("pda", &"7x59")]), "PDA Address: 7x59");
+        assert_eq!(fill(text_in("transferred", Lang::Ru), &[("amount", &"1.5 SOL"), ("fee", &5000)]), "Переведено 1.5 SOL на PDA (комиссия 5000 лампортов)");
+        assert_eq!(text_in("no-such-key", Lang::Ru), "no-such-key");
+        assert!(is_yes("Yes\n"));
+        assert!(!is_yes(""));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::generate_deposit_pda;
+use crate::token::{create_associated_token_account_idempotent, get_associated_token_address, TOKEN_PROGRAM_ID};
+use crate::Error;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+pub use dz_validator_pda_core::{build_fund_instruction, build_fund_instructions};
+
+/// Returns the instructions of an SPL token deposit to the deposit PDA of a validator
+///
+/// # Arguments
+/// * `payer` - Token owner, which also pays the rent of the PDA's token account if it is created
+/// * `validator_id` - The validator's public key
+/// * `mint` - Token mint
+/// * `amount` - Amount in base units of the mint
+/// * `decimals` - Decimal places of the mint, checked by the token program
+///
+/// # Returns
+/// * `Result<Vec<Instruction>, Error>` - Idempotent create of the PDA's token account, then the checked transfer
+pub fn build_fund_token_instructions(
+    payer: &Pubkey,
+    validator_id: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Result<Vec<Instruction>, Error> {
+    let deposit_key = generate_deposit_pda(validator_id);
+    let transfer = spl_token_interface::instruction::transfer_checked(
+        &TOKEN_PROGRAM_ID,
+        &get_associated_token_address(payer, mint),
+        mint,
+        &get_associated_token_address(&deposit_key, mint),
+        payer,
+        &[],
+        amount,
+        decimals,
+    )
+    .map_err(|e| Error::InvalidInput(format!("Failed to build token transfer: {}", e)))?;
+    Ok(vec![create_associated_token_account_idempotent(payer, &deposit_key, mint), transfer])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_system_interface::instruction::SystemInstruction;
+
+    #[test]
+    fn test_build_fund_instructions() {
+        let (payer, validator_id, mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let deposit_key = generate_deposit_pda(&validator_id);
+
+        let instruction = build_fund_instruction(&payer, &validator_id, 42);
+        assert_eq!(instruction.program_id, solana_system_interface::program::ID);
+        assert_eq!(instruction.accounts.iter().map(|meta| meta.pubkey).collect::<Vec<_>>(), vec![payer, deposit_key]);
+        assert!(instruction.accounts[0].is_signer && !instruction.accounts[1].is_signer);
+        assert_eq!(bincode::deserialize::<SystemInstruction>(&instruction.data).unwrap(), SystemInstruction::Transfer { lamports: 42 });
+
+        let other = Pubkey::new_unique();
+        let instructions = build_fund_instructions(&payer, &[(validator_id, 42), (other, 7)]);
+        assert_eq!(instructions, vec![instruction, build_fund_instruction(&payer, &other, 7)]);
+
+        let instructions = build_fund_token_instructions(&payer, &validator_id, &mint, 1_500_000, 6).unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[1].program_id, TOKEN_PROGRAM_ID);
+        assert_eq!(instructions[1].accounts[2].pubkey, get_associated_token_address(&deposit_key, &mint));
+        assert_eq!(instructions[1].accounts[3].pubkey, payer);
+    }
+}
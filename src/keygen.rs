@@ -0,0 +1,295 @@
+use crate::{generate_deposit_pda, Error};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{write_keypair_file, Keypair, Signer};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Characters of a base58 address
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Longest base58 form of a pubkey
+const MAX_PUBKEY_CHARS: usize = 44;
+
+/// Keypairs generated by a grind thread between two checks of the stop flag
+const GRIND_CHECK_INTERVAL: u64 = 256;
+
+/// Writes a new random keypair to a JSON file (`keygen new`)
+///
+/// The file has the `solana-keygen` format (array of 64 bytes) and is only readable by its owner.
+/// There is no seed phrase, so the file itself is the only copy of the key.
+///
+/// # Arguments
+/// * `outfile` - Keypair path
+/// * `force` - Overwrite an existing file
+///
+/// # Returns
+/// * `Result<Pubkey, Error>` - Pubkey of the new keypair or error
+pub fn new_keypair_file(outfile: &Path, force: bool) -> Result<Pubkey, Error> {
+    if outfile.exists() && !force {
+        return Err(Error::InvalidInput(format!("{} already exists (use --force to overwrite)", outfile.display())));
+    }
+    let keypair = Keypair::new();
+    write_keypair_file(&keypair, outfile).map_err(|e| Error::Io(format!("Failed to write {}: {}", outfile.display(), e)))?;
+    Ok(keypair.pubkey())
+}
+
+/// Vanity pattern of `keygen grind`: a base58 prefix and suffix and how many keypairs to find
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrindPattern {
+    /// Start of the address (empty for any)
+    pub starts_with: String,
+    /// End of the address (empty for any)
+    pub ends_with: String,
+    /// Keypairs to find
+    pub count: u64,
+}
+
+impl GrindPattern {
+    /// Parses `--starts-with <prefix:count>`, `--ends-with <suffix:count>` or `--starts-and-ends-with <prefix:suffix:count>`
+    ///
+    /// # Arguments
+    /// * `flag` - Flag the value was given with (`starts-with`, `ends-with` or `starts-and-ends-with`)
+    /// * `value` - Flag value
+    /// * `ignore_case` - Whether letters match in either case (`--ignore-case`)
+    ///
+    /// # Returns
+    /// * `Result<GrindPattern, Error>` - Pattern or `Error::InvalidInput` for a malformed value or a character no address contains
+    pub fn parse(flag: &str, value: &str, ignore_case: bool) -> Result<Self, Error> {
+        let parts: Vec<&str> = value.split(':').collect();
+        let (starts_with, ends_with, count) = match (flag, parts.as_slice()) {
+            ("starts-with", [prefix, count]) => (*prefix, "", *count),
+            ("ends-with", [suffix, count]) => ("", *suffix, *count),
+            ("starts-and-ends-with", [prefix, suffix, count]) => (*prefix, *suffix, *count),
+            _ => {
+                let expected = if flag == "starts-and-ends-with" { "<prefix:suffix:count>" } else { "<chars:count>" };
+                return Err(Error::InvalidInput(format!("Invalid --{} '{}': expected {}", flag, value, expected)));
+            }
+        };
+        let count = count
+            .parse::<u64>()
+            .ok()
+            .filter(|count| *count > 0)
+            .ok_or_else(|| Error::InvalidInput(format!("Invalid --{} '{}': the count must be a positive number", flag, value)))?;
+        if starts_with.is_empty() && ends_with.is_empty() {
+            return Err(Error::InvalidInput(format!("Invalid --{} '{}': no characters to match", flag, value)));
+        }
+        if starts_with.len() + ends_with.len() > MAX_PUBKEY_CHARS {
+            return Err(Error::InvalidInput(format!("Invalid --{} '{}': longer than an address", flag, value)));
+        }
+        let invalid = starts_with.chars().chain(ends_with.chars()).find(|c| {
+            let candidates = if ignore_case { vec![c.to_ascii_lowercase(), c.to_ascii_uppercase()] } else { vec![*c] };
+            !candidates.iter().any(|candidate| BASE58_ALPHABET.contains(*candidate))
+        });
+        if let Some(c) = invalid {
+            return Err(Error::InvalidInput(format!(
+                "Invalid --{} '{}': '{}' never appears in an address (base58 has no 0, O, I or l)",
+                flag, value, c
+            )));
+        }
+
+        let normalize = |chars: &str| if ignore_case { chars.to_ascii_lowercase() } else { chars.to_string() };
+        Ok(GrindPattern { starts_with: normalize(starts_with), ends_with: normalize(ends_with), count })
+    }
+
+    /// Returns true if a base58 address matches, lowercased beforehand for `--ignore-case`
+    pub fn matches(&self, address: &str) -> bool {
+        address.starts_with(&self.starts_with) && address.ends_with(&self.ends_with)
+    }
+}
+
+/// Address `keygen grind` matches its patterns against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GrindTarget {
+    /// Pubkey of the keypair, for a funding wallet
+    #[default]
+    Wallet,
+    /// Deposit PDA of the keypair used as a validator identity (`--pda`)
+    DepositPda,
+}
+
+impl GrindTarget {
+    /// Returns the address a keypair is matched by
+    pub fn address(self, pubkey: &Pubkey) -> Pubkey {
+        match self {
+            GrindTarget::Wallet => *pubkey,
+            GrindTarget::DepositPda => generate_deposit_pda(pubkey),
+        }
+    }
+}
+
+/// Outcome of `keygen grind`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrindOutcome {
+    /// Keypair files written, in the order they were found
+    pub files: Vec<PathBuf>,
+    /// Keypairs generated
+    pub attempts: u64,
+}
+
+/// Generates keypairs on several threads until every pattern has found its count (`keygen grind`)
+///
+/// Each match is written right away as `<pubkey>.json` in `outdir`, so a search interrupted with
+/// `stop` keeps the keypairs found so far. A keypair counts for the first pattern it matches that
+/// still needs keypairs. With `GrindTarget::DepositPda` the file is still named after the keypair,
+/// but the patterns match the deposit PDA it derives as a validator identity.
+///
+/// # Arguments
+/// * `patterns` - Patterns to find
+/// * `target` - Address the patterns match: the keypair pubkey or its deposit PDA
+/// * `ignore_case` - Match letters in either case; the patterns must have been parsed the same way
+/// * `threads` - Worker threads
+/// * `outdir` - Directory of the keypair files
+/// * `stop` - Set to end the search early
+///
+/// # Returns
+/// * `Result<GrindOutcome, Error>` - Files written and attempts, or the first error writing a file
+pub fn grind(
+    patterns: &[GrindPattern],
+    target: GrindTarget,
+    ignore_case: bool,
+    threads: usize,
+    outdir: &Path,
+    stop: &AtomicBool,
+) -> Result<GrindOutcome, Error> {
+    let remaining = Mutex::new(patterns.iter().map(|pattern| pattern.count).collect::<Vec<u64>>());
+    let files = Mutex::new(Vec::new());
+    let error = Mutex::new(None);
+    let attempts = AtomicU64::new(0);
+    let done = AtomicBool::new(patterns.is_empty());
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| {
+                let mut generated = 0;
+                while !done.load(Ordering::Relaxed) && !stop.load(Ordering::Relaxed) {
+                    for _ in 0..GRIND_CHECK_INTERVAL {
+                        let keypair = Keypair::new();
+                        generated += 1;
+                        let address = target.address(&keypair.pubkey()).to_string();
+                        let address = if ignore_case { address.to_ascii_lowercase() } else { address };
+                        if !patterns.iter().any(|pattern| pattern.matches(&address)) {
+                            continue;
+                        }
+
+                        let mut remaining = remaining.lock().unwrap_or_else(|e| e.into_inner());
+                        let Some(index) = (0..patterns.len()).find(|index| remaining[*index] > 0 && patterns[*index].matches(&address)) else {
+                            continue;
+                        };
+                        let path = outdir.join(format!("{}.json", keypair.pubkey()));
+                        if let Err(e) = write_keypair_file(&keypair, &path) {
+                            *error.lock().unwrap_or_else(|e| e.into_inner()) = Some(Error::Io(format!("Failed to write {}: {}", path.display(), e)));
+                            done.store(true, Ordering::Relaxed);
+                            break;
+                        }
+                        remaining[index] -= 1;
+                        files.lock().unwrap_or_else(|e| e.into_inner()).push(path);
+                        if remaining.iter().all(|count| *count == 0) {
+                            done.store(true, Ordering::Relaxed);
+                            break;
+                        }
+                    }
+                }
+                attempts.fetch_add(generated, Ordering::Relaxed);
+            });
+        }
+    });
+
+    if let Some(e) = error.into_inner().unwrap_or_else(|e| e.into_inner()) {
+        return Err(e);
+    }
+    Ok(GrindOutcome { files: files.into_inner().unwrap_or_else(|e| e.into_inner()), attempts: attempts.into_inner() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::read_keypair_file;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dz_validator_pda_keygen_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_new_keypair_file() {
+        let dir = temp_dir("new");
+        let path = dir.join("payer.json");
+        let pubkey = new_keypair_file(&path, false).unwrap();
+        assert_eq!(read_keypair_file(&path).unwrap().pubkey(), pubkey);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        // An existing wallet is only replaced with --force
+        assert!(new_keypair_file(&path, false).unwrap_err().is_user_error());
+        assert_ne!(new_keypair_file(&path, true).unwrap(), pubkey);
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_parse_grind_pattern() {
+        let pattern = GrindPattern::parse("starts-with", "dz:2", false).unwrap();
+        assert_eq!(pattern, GrindPattern { starts_with: "dz".to_string(), ends_with: String::new(), count: 2 });
+        assert!(pattern.matches("dzXYZ") && !pattern.matches("DzXYZ"));
+
+        let pattern = GrindPattern::parse("starts-and-ends-with", "DZ:Pda:1", true).unwrap();
+        assert!(pattern.matches(&"DzAbcPDA".to_ascii_lowercase()));
+        // 'l' is not base58 but 'L' is
+        assert!(GrindPattern::parse("ends-with", "sol:1", true).is_ok());
+
+        for (flag, value) in [
+            ("starts-with", "dz"),
+            ("starts-with", "dz:0"),
+            ("starts-with", ":1"),
+            ("ends-with", "sol:1"),
+            ("starts-with", "0x:1"),
+            ("starts-and-ends-with", "dz:1"),
+        ] {
+            let error = GrindPattern::parse(flag, value, false).unwrap_err();
+            assert!(error.is_user_error(), "{} {}", flag, value);
+        }
+    }
+
+    #[test]
+    fn test_grind() {
+        let dir = temp_dir("grind");
+        let patterns = vec![
+            GrindPattern::parse("starts-with", "A:2", true).unwrap(),
+            GrindPattern::parse("ends-with", "b:1", true).unwrap(),
+        ];
+        let outcome = grind(&patterns, GrindTarget::Wallet, true, 2, &dir, &AtomicBool::new(false)).unwrap();
+        assert_eq!(outcome.files.len(), 3);
+        assert!(outcome.attempts >= 3);
+        for path in &outcome.files {
+            let pubkey = read_keypair_file(path).unwrap().pubkey().to_string();
+            assert_eq!(path.file_name().unwrap().to_str().unwrap(), format!("{}.json", pubkey));
+            let address = pubkey.to_ascii_lowercase();
+            assert!(address.starts_with('a') || address.ends_with('b'));
+        }
+
+        // A stopped search returns what it found so far
+        let impossible = vec![GrindPattern::parse("starts-with", "zzzzzzzzzz:1", false).unwrap()];
+        let outcome = grind(&impossible, GrindTarget::Wallet, false, 1, &dir, &AtomicBool::new(true)).unwrap();
+        assert!(outcome.files.is_empty());
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_grind_deposit_pda() {
+        let dir = temp_dir("grind_pda");
+        let patterns = vec![GrindPattern::parse("starts-with", "D:2", true).unwrap()];
+        let outcome = grind(&patterns, GrindTarget::DepositPda, true, 2, &dir, &AtomicBool::new(false)).unwrap();
+        assert_eq!(outcome.files.len(), 2);
+        for path in &outcome.files {
+            // The file holds the identity keypair; its deposit PDA is what matches
+            let identity = read_keypair_file(path).unwrap().pubkey();
+            assert_eq!(path.file_name().unwrap().to_str().unwrap(), format!("{}.json", identity));
+            assert!(generate_deposit_pda(&identity).to_string().to_ascii_lowercase().starts_with('d'));
+        }
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,187 @@
+use crate::config::write_atomic;
+use crate::i18n;
+use crate::{load_keypair, Error};
+use age::secrecy::{ExposeSecret, SecretString};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{EncodableKey, Keypair, Signer};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Environment variable holding the passphrase of encrypted keypairs, for unattended runs
+pub const PASSPHRASE_ENV_VAR: &str = "DZ_KEYPAIR_PASSPHRASE";
+
+/// scrypt work factor (log2 of N) of newly encrypted keypairs, about a second to unlock
+pub const DEFAULT_WORK_FACTOR: u8 = 18;
+
+/// Highest scrypt work factor accepted when unlocking, instead of age's estimate for the current machine
+pub const MAX_WORK_FACTOR: u8 = 22;
+
+/// Start of every age-encrypted file
+const AGE_MAGIC: &[u8] = b"age-encryption.org/";
+
+/// Last passphrase that unlocked a keypair, tried first so a run prompts only once
+static LAST_PASSPHRASE: Mutex<Option<SecretString>> = Mutex::new(None);
+
+/// Returns true if the data is an age-encrypted file
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(AGE_MAGIC)
+}
+
+/// Returns true if the file exists and is age-encrypted
+///
+/// # Arguments
+/// * `path` - Keypair path
+pub fn is_encrypted_file(path: &Path) -> bool {
+    let mut header = [0u8; AGE_MAGIC.len()];
+    fs::File::open(path).and_then(|mut file| file.read_exact(&mut header)).is_ok() && is_encrypted(&header)
+}
+
+/// Encrypts a keypair with a passphrase (age, scrypt recipient)
+///
+/// The plaintext is the usual JSON keypair, so decrypting the file with `age -d` gives a regular keypair file.
+///
+/// # Arguments
+/// * `keypair` - Keypair to encrypt
+/// * `passphrase` - Passphrase
+/// * `work_factor` - scrypt work factor (see `DEFAULT_WORK_FACTOR`)
+///
+/// # Returns
+/// * `Result<Vec<u8>, Error>` - Encrypted file contents or `Error::Keypair`
+pub fn encrypt_keypair(keypair: &Keypair, passphrase: &SecretString, work_factor: u8) -> Result<Vec<u8>, Error> {
+    let json = serde_json::to_string(&keypair.to_bytes().to_vec())
+        .map_err(|e| Error::Keypair(format!("Failed to serialize keypair: {}", e)))?;
+    let mut recipient = age::scrypt::Recipient::new(passphrase.clone());
+    recipient.set_work_factor(work_factor);
+    age::encrypt(&recipient, json.as_bytes()).map_err(|e| Error::Keypair(format!("Failed to encrypt keypair: {}", e)))
+}
+
+/// Decrypts a keypair encrypted by `encrypt_keypair`
+///
+/// # Arguments
+/// * `data` - Encrypted file contents
+/// * `passphrase` - Passphrase
+/// * `source` - Path shown in errors
+///
+/// # Returns
+/// * `Result<Keypair, Error>` - Keypair, or `Error::Keypair` for a wrong passphrase or a damaged file
+pub fn decrypt_keypair(data: &[u8], passphrase: &SecretString, source: &str) -> Result<Keypair, Error> {
+    let mut identity = age::scrypt::Identity::new(passphrase.clone());
+    identity.set_max_work_factor(MAX_WORK_FACTOR);
+    let plaintext = age::decrypt(&identity, data).map_err(|e| match e {
+        age::DecryptError::DecryptionFailed | age::DecryptError::NoMatchingKeys => {
+            Error::Keypair(format!("Failed to decrypt {}: wrong passphrase", source))
+        }
+        e => Error::Keypair(format!("Failed to decrypt {}: {}", source, e)),
+    })?;
+    Keypair::read(&mut plaintext.as_slice())
+        .map_err(|e| Error::Keypair(format!("Failed to read keypair from {}: {}", source, e)))
+}
+
+/// Reads the passphrase from `DZ_KEYPAIR_PASSPHRASE`, or asks for it without echoing it
+///
+/// # Arguments
+/// * `prompt` - Prompt shown on the terminal
+///
+/// # Returns
+/// * `Result<SecretString, Error>` - Non-empty passphrase or `Error::Keypair`
+pub fn read_passphrase(prompt: &str) -> Result<SecretString, Error> {
+    let passphrase = match std::env::var(PASSPHRASE_ENV_VAR) {
+        Ok(passphrase) => passphrase,
+        Err(_) => rpassword::prompt_password(prompt).map_err(|e| {
+            Error::Keypair(format!("Failed to read passphrase: {} (set {} for unattended runs)", e, PASSPHRASE_ENV_VAR))
+        })?,
+    };
+    if passphrase.is_empty() {
+        return Err(Error::Keypair("Passphrase cannot be empty".to_string()));
+    }
+    Ok(SecretString::from(passphrase))
+}
+
+/// Loads an encrypted keypair file, asking for its passphrase unless the last one used unlocks it
+///
+/// # Arguments
+/// * `path` - Encrypted keypair path
+///
+/// # Returns
+/// * `Result<Keypair, Error>` - Keypair or `Error::Keypair`
+pub fn load_encrypted_keypair(path: &str) -> Result<Keypair, Error> {
+    let data = fs::read(path).map_err(|e| Error::Keypair(format!("Failed to read keypair from {}: {}", path, e)))?;
+    let mut last = LAST_PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(keypair) = last.as_ref().and_then(|passphrase| decrypt_keypair(&data, passphrase, path).ok()) {
+        return Ok(keypair);
+    }
+    let passphrase = read_passphrase(&i18n::message("prompt-passphrase-for", &[("path", &path)]))?;
+    let keypair = decrypt_keypair(&data, &passphrase, path)?;
+    *last = Some(passphrase);
+    Ok(keypair)
+}
+
+/// Encrypts a plain JSON keypair file into a new file (`keygen encrypt`)
+///
+/// The passphrase is read from `DZ_KEYPAIR_PASSPHRASE`, or asked for twice. The plain file is left in place.
+///
+/// # Arguments
+/// * `input` - Plain JSON keypair path
+/// * `output` - Encrypted keypair path
+/// * `force` - Overwrite an existing output file
+///
+/// # Returns
+/// * `Result<Pubkey, Error>` - Pubkey of the encrypted keypair or error
+pub fn encrypt_keypair_file(input: &str, output: &Path, force: bool) -> Result<Pubkey, Error> {
+    if is_encrypted_file(Path::new(input)) {
+        return Err(Error::InvalidInput(format!("{} is already encrypted", input)));
+    }
+    if output.exists() && !force {
+        return Err(Error::InvalidInput(format!("{} already exists (use --force to overwrite)", output.display())));
+    }
+    let keypair = load_keypair(input)?;
+    let passphrase = read_passphrase(i18n::text("prompt-new-passphrase"))?;
+    if std::env::var(PASSPHRASE_ENV_VAR).is_err()
+        && read_passphrase(i18n::text("prompt-confirm-passphrase"))?.expose_secret() != passphrase.expose_secret()
+    {
+        return Err(Error::Keypair("Passphrases do not match".to_string()));
+    }
+    write_atomic(output, &encrypt_keypair(&keypair, &passphrase, DEFAULT_WORK_FACTOR)?)?;
+    Ok(keypair.pubkey())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Low work factor so tests do not spend a second per key derivation
+    const TEST_WORK_FACTOR: u8 = 10;
+
+    #[test]
+    fn test_encrypt_keypair_roundtrip() {
+        let keypair = Keypair::new();
+        let passphrase = SecretString::from("correct horse".to_string());
+        let encrypted = encrypt_keypair(&keypair, &passphrase, TEST_WORK_FACTOR).unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!is_encrypted(b"[1,2,3]"));
+
+        let decrypted = decrypt_keypair(&encrypted, &passphrase, "wallet.enc").unwrap();
+        assert_eq!(decrypted.pubkey(), keypair.pubkey());
+
+        let wrong = SecretString::from("battery staple".to_string());
+        let error = decrypt_keypair(&encrypted, &wrong, "wallet.enc").unwrap_err();
+        assert!(error.to_string().contains("wallet.enc: wrong passphrase"), "{}", error);
+    }
+
+    #[test]
+    fn test_load_keypair_detects_encrypted_files() {
+        let keypair = Keypair::new();
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_keystore_{}.enc", std::process::id()));
+        let passphrase = SecretString::from("correct horse".to_string());
+        fs::write(&path, encrypt_keypair(&keypair, &passphrase, TEST_WORK_FACTOR).unwrap()).unwrap();
+        assert!(is_encrypted_file(&path));
+        assert!(!is_encrypted_file(&path.with_extension("missing")));
+
+        // The passphrase of an earlier unlock is reused without prompting
+        *LAST_PASSPHRASE.lock().unwrap() = Some(passphrase);
+        assert_eq!(load_keypair(path.to_str().unwrap()).unwrap().pubkey(), keypair.pubkey());
+        fs::remove_file(path).ok();
+    }
+}
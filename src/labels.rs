@@ -0,0 +1,139 @@
+use crate::config::Config;
+use crate::{parse_pubkey, Error, REVENUE_DISTRIBUTION_PROGRAM_ID};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+
+/// Well-known addresses shown by name in text output
+pub const BUILTIN_LABELS: &[(Pubkey, &str)] = &[
+    (solana_system_interface::program::ID, "System Program"),
+    (solana_sdk::pubkey!("Vote111111111111111111111111111111111111111"), "Vote Program"),
+    (solana_sdk::pubkey!("Stake11111111111111111111111111111111111111"), "Stake Program"),
+    (crate::priority::COMPUTE_BUDGET_PROGRAM_ID, "Compute Budget Program"),
+    (REVENUE_DISTRIBUTION_PROGRAM_ID, "DoubleZero Revenue Distribution"),
+];
+
+/// Returns the built-in label of a well-known address
+///
+/// # Arguments
+/// * `pubkey` - Address to look up
+///
+/// # Returns
+/// * `Option<&str>` - Label, or None for an address without a built-in label
+pub fn builtin_label(pubkey: &Pubkey) -> Option<&'static str> {
+    BUILTIN_LABELS.iter().find(|(address, _)| address == pubkey).map(|(_, label)| *label)
+}
+
+/// Formats an address with its built-in label, e.g. `Stake Program (Stake111...)`
+pub fn format_builtin(pubkey: &Pubkey) -> String {
+    match builtin_label(pubkey) {
+        Some(label) => format!("{} ({})", label, pubkey),
+        None => pubkey.to_string(),
+    }
+}
+
+/// Shortens an address to its first and last four characters, e.g. `FjYE…SLQL`
+///
+/// Only used next to a label, which already tells the addresses apart.
+pub fn abbreviate(pubkey: &Pubkey) -> String {
+    let address = pubkey.to_string();
+    format!("{}…{}", &address[..4], &address[address.len() - 4..])
+}
+
+/// Address labels: built-ins, validator aliases and the `[labels]` table of the config file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Labels {
+    labels: BTreeMap<Pubkey, String>,
+}
+
+impl Labels {
+    /// Returns the built-in labels only
+    pub fn builtin() -> Self {
+        Labels {
+            labels: BUILTIN_LABELS.iter().map(|(address, label)| (*address, label.to_string())).collect(),
+        }
+    }
+
+    /// Merges the built-in labels with validator aliases and the `[labels]` table of a config
+    ///
+    /// Aliases override built-ins and `[labels]` entries override both.
+    ///
+    /// # Arguments
+    /// * `config` - Loaded configuration
+    ///
+    /// # Returns
+    /// * `Result<Labels, Error>` - Labels or `Error::InvalidInput` for an invalid pubkey
+    pub fn from_config(config: &Config) -> Result<Self, Error> {
+        let mut labels = Labels::builtin();
+        let aliases = config.validators.iter().map(|(alias, pubkey)| (pubkey, alias));
+        let configured = config.labels.iter();
+        for (pubkey, label) in aliases.chain(configured) {
+            let address = parse_pubkey(pubkey)
+                .map_err(|e| Error::InvalidInput(format!("Invalid pubkey for label '{}': {}", label, e)))?;
+            labels.insert(address, label.clone());
+        }
+        Ok(labels)
+    }
+
+    /// Adds or replaces a label
+    pub fn insert(&mut self, pubkey: Pubkey, label: String) {
+        self.labels.insert(pubkey, label);
+    }
+
+    /// Returns the label of an address, if any
+    pub fn get(&self, pubkey: &Pubkey) -> Option<&str> {
+        self.labels.get(pubkey).map(String::as_str)
+    }
+
+    /// Formats an address for text output
+    ///
+    /// # Arguments
+    /// * `pubkey` - Address to format
+    ///
+    /// # Returns
+    /// * `String` - `label (FjYE…SLQL)` for a labeled address, otherwise the full base58 address
+    pub fn format(&self, pubkey: &Pubkey) -> String {
+        match self.get(pubkey) {
+            Some(label) => format!("{} ({})", label, abbreviate(pubkey)),
+            None => pubkey.to_string(),
+        }
+    }
+
+    /// Formats a base58 address for text output, leaving anything that is not a pubkey unchanged
+    pub fn format_str(&self, address: &str) -> String {
+        match parse_pubkey(address) {
+            Ok(pubkey) => self.format(&pubkey),
+            Err(_) => address.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_labels() {
+        assert_eq!(builtin_label(&solana_system_interface::program::ID), Some("System Program"));
+        assert_eq!(format_builtin(&REVENUE_DISTRIBUTION_PROGRAM_ID), format!("DoubleZero Revenue Distribution ({})", REVENUE_DISTRIBUTION_PROGRAM_ID));
+        let unknown = Pubkey::new_unique();
+        assert_eq!(format_builtin(&unknown), unknown.to_string());
+    }
+
+    #[test]
+    fn test_labels_from_config() {
+        let validator = "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL";
+        let mut config = Config::default();
+        config.validators.insert("mainnet-01".to_string(), validator.to_string());
+        config.labels.insert("11111111111111111111111111111111".to_string(), "System".to_string());
+
+        let labels = Labels::from_config(&config).unwrap();
+        assert_eq!(labels.format_str(validator), "mainnet-01 (FjYE…SLQL)");
+        // Configured labels override built-ins
+        assert_eq!(labels.get(&solana_system_interface::program::ID), Some("System"));
+        assert_eq!(labels.get(&REVENUE_DISTRIBUTION_PROGRAM_ID), Some("DoubleZero Revenue Distribution"));
+        assert_eq!(labels.format_str("not-a-pubkey"), "not-a-pubkey");
+
+        config.labels.insert("invalid".to_string(), "Broken".to_string());
+        assert!(Labels::from_config(&config).unwrap_err().is_user_error());
+    }
+}
@@ -0,0 +1,213 @@
+use crate::trace::trace_id;
+use crate::Error;
+use std::fmt::{self, Write as _};
+use std::io::Write as _;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Environment variable with log directives (e.g. `debug` or `dz_validator_pda::rpc=trace,warn`)
+pub const LOG_ENV_VAR: &str = "RUST_LOG";
+
+/// Which log events are written, by target and level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Most verbose level for targets without a directive (None = off)
+    default: Option<Level>,
+    /// Per-target levels, matched by target prefix
+    directives: Vec<(String, Option<Level>)>,
+}
+
+impl LogFilter {
+    /// Filter for `--quiet`, the default, `-v` and `-vv`
+    ///
+    /// # Arguments
+    /// * `verbose` - Number of `-v` flags
+    /// * `quiet` - `--quiet` was given (errors only)
+    pub fn from_verbosity(verbose: usize, quiet: bool) -> Self {
+        let level = match (quiet, verbose) {
+            (true, _) => Level::ERROR,
+            (false, 0) => Level::INFO,
+            (false, 1) => Level::DEBUG,
+            (false, _) => Level::TRACE,
+        };
+        LogFilter { default: Some(level), directives: Vec::new() }
+    }
+
+    /// Parses comma-separated `RUST_LOG` directives: `level`, `target=level` or `target`
+    ///
+    /// # Arguments
+    /// * `spec` - Directives, e.g. `info,dz_validator_pda::rpc=trace`
+    ///
+    /// # Returns
+    /// * `Result<LogFilter, Error>` - Filter (default `info`) or `Error::InvalidInput`
+    pub fn parse(spec: &str) -> Result<Self, Error> {
+        let mut filter = LogFilter { default: Some(Level::INFO), directives: Vec::new() };
+        for directive in spec.split(',').map(str::trim).filter(|directive| !directive.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => filter.directives.push((target.trim().to_string(), parse_level(level.trim())?)),
+                None => match parse_level(directive) {
+                    Ok(level) => filter.default = level,
+                    // A bare target enables everything from it
+                    Err(_) => filter.directives.push((directive.to_string(), Some(Level::TRACE))),
+                },
+            }
+        }
+        // The longest matching target wins
+        filter.directives.sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+        Ok(filter)
+    }
+
+    /// Returns true if an event of `level` from `target` is written
+    pub fn enabled(&self, target: &str, level: &Level) -> bool {
+        let max_level = self
+            .directives
+            .iter()
+            .find(|(prefix, _)| target == prefix || target.starts_with(&format!("{}::", prefix)))
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default);
+        max_level.is_some_and(|max_level| *level <= max_level)
+    }
+}
+
+/// Parses a level name (`off`, `error`, `warn`, `info`, `debug`, `trace`)
+fn parse_level(value: &str) -> Result<Option<Level>, Error> {
+    if value.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    value
+        .parse::<Level>()
+        .map(Some)
+        .map_err(|_| Error::InvalidInput(format!("Invalid log level '{}': expected off, error, warn, info, debug or trace", value)))
+}
+
+/// Chooses the log filter: `-v`/`--quiet` flags win over `RUST_LOG`, which wins over the default (`info`)
+///
+/// # Arguments
+/// * `verbose` - Number of `-v` flags
+/// * `quiet` - `--quiet` was given
+/// * `rust_log` - Value of `RUST_LOG`, if set
+///
+/// # Returns
+/// * `Result<LogFilter, Error>` - Filter or `Error::InvalidInput` for an invalid `RUST_LOG`
+pub fn log_filter(verbose: usize, quiet: bool, rust_log: Option<&str>) -> Result<LogFilter, Error> {
+    match rust_log {
+        Some(spec) if verbose == 0 && !quiet => LogFilter::parse(spec),
+        _ => Ok(LogFilter::from_verbosity(verbose, quiet)),
+    }
+}
+
+/// Collects the message and fields of an event
+#[derive(Default)]
+struct EventVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for EventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Formats an event as one line: level, target, message and `key=value` fields
+fn format_event(event: &Event<'_>) -> String {
+    let mut visitor = EventVisitor::default();
+    event.record(&mut visitor);
+    if let Some(trace_id) = trace_id() {
+        let _ = write!(visitor.fields, " trace_id={}", trace_id);
+    }
+    let metadata = event.metadata();
+    format!("{:>5} {}: {}{}", metadata.level(), metadata.target(), visitor.message, visitor.fields)
+}
+
+/// Writes log events to stderr, keeping stdout for results
+struct StderrLogger {
+    filter: LogFilter,
+}
+
+impl Subscriber for StderrLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.filter.enabled(metadata.target(), metadata.level())
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        // Spans are not tracked; only events are written
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let line = format_event(event);
+        crate::progress::suspend(|| {
+            let _ = writeln!(std::io::stderr().lock(), "{}", line);
+        });
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+/// Installs the stderr logger as the global `tracing` subscriber (once per process)
+///
+/// # Arguments
+/// * `filter` - Events to write
+pub fn init_logging(filter: LogFilter) {
+    let _ = tracing::subscriber::set_global_default(StderrLogger { filter });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_levels() {
+        let quiet = LogFilter::from_verbosity(2, true);
+        assert!(quiet.enabled("dz_validator_pda", &Level::ERROR));
+        assert!(!quiet.enabled("dz_validator_pda", &Level::WARN));
+
+        let default = LogFilter::from_verbosity(0, false);
+        assert!(default.enabled("dz_validator_pda", &Level::INFO));
+        assert!(!default.enabled("dz_validator_pda", &Level::DEBUG));
+
+        assert!(LogFilter::from_verbosity(1, false).enabled("dz_validator_pda", &Level::DEBUG));
+        assert!(LogFilter::from_verbosity(2, false).enabled("dz_validator_pda", &Level::TRACE));
+    }
+
+    #[test]
+    fn test_rust_log_directives() {
+        let filter = LogFilter::parse("warn,dz_validator_pda::rpc=trace,dz_validator_pda=off").unwrap();
+        assert!(filter.enabled("dz_validator_pda::rpc", &Level::TRACE));
+        assert!(!filter.enabled("dz_validator_pda::rpc_other", &Level::ERROR));
+        assert!(!filter.enabled("dz_validator_pda::audit", &Level::ERROR));
+        assert!(filter.enabled("reqwest", &Level::WARN));
+        assert!(!filter.enabled("reqwest", &Level::INFO));
+
+        assert!(LogFilter::parse("dz_validator_pda").unwrap().enabled("dz_validator_pda::rpc", &Level::TRACE));
+        assert!(LogFilter::parse("verbose=loud").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_flags_win_over_rust_log() {
+        assert_eq!(log_filter(0, false, Some("debug")).unwrap(), LogFilter::parse("debug").unwrap());
+        assert_eq!(log_filter(1, false, Some("off")).unwrap(), LogFilter::from_verbosity(1, false));
+        assert_eq!(log_filter(0, true, Some("trace")).unwrap(), LogFilter::from_verbosity(0, true));
+        assert_eq!(log_filter(0, false, None).unwrap(), LogFilter::from_verbosity(0, false));
+    }
+}
@@ -1,228 +1,586 @@
+use dz_validator_pda::{
+    check_rpc_health, generate_deposit_pda, get_account_balance, get_rent_exempt_minimum, get_validator_status,
+    is_validator_in_gossip, load_keypair, parse_pubkey, parse_amount, prepare_pda_funding, send_signed_funding, sign_pda_funding, retry_transient, unix_timestamp,
+    validate_base58, Amount, Error, FundingAttempt, FundingOutcome, FundingSigners, NonceConfig, OutputFormat, PreparedFunding, SignedFunding, TransactionVersion, Verdict, Warning, DEPOSIT_PDA_SEED_PREFIX,
+    REVENUE_DISTRIBUTION_PROGRAM_ID,
+};
+use dz_validator_pda::approval::{
+    approve_plan, read_json_file, verify_approval, write_json_file, Approval, FundingPlan, DEFAULT_APPROVAL_TTL_SECS,
+};
+use dz_validator_pda::airdrop::request_airdrop;
+use dz_validator_pda::allocate::{allocate, read_weight_file, stake_weights, AllocationReport, WeightSource};
+use dz_validator_pda::amount::{format_sol, format_token_amount, parse_percent, LAMPORTS_PER_SOL};
+use dz_validator_pda::template::{template_fields, OutputTemplate};
+use dz_validator_pda::args::{ParsedArgs, SWITCH_FLAGS, VALUE_FLAGS};
+use dz_validator_pda::audit::{self, AuditEntry, DEFAULT_DUPLICATE_WINDOW};
+use dz_validator_pda::compat;
+use dz_validator_pda::completion::{completion_script, validator_candidates, Shell, COMPLETE_OPERATION};
+use dz_validator_pda::batch::{
+    attribute_outcomes, collect_entries_until, entries_to_csv, parse_concurrency, parse_validator_list, read_validator_file, retry_transfers, snapshot_slot,
+    sort_entries, BatchEntry, BatchOptions, SentTransaction, SortKey, TransferOutcome, TransferStatus, DEFAULT_BATCH_CONCURRENCY,
+};
+use dz_validator_pda::config::{default_config_path, write_atomic, Config};
+use dz_validator_pda::dashboard::{collect_snapshot, Action, Dashboard, DEFAULT_REFRESH_SECS};
+use dz_validator_pda::help::{command_help, examples_for, format_command_help, format_examples, Example, COMMANDS, EXAMPLES};
+use dz_validator_pda::fees::{fee_report, Month, FORECAST_WINDOW_DAYS};
+use dz_validator_pda::fiat::{self, default_price_cache_path, CachedPriceSource, CoinGecko, Currency, FiatQuote, DEFAULT_PRICE_TTL};
+use dz_validator_pda::gossip::{
+    default_monitor_state_path, diff_nodes, parse_grace, take_snapshot, GossipMonitorState, DEFAULT_GOSSIP_GRACE, DEFAULT_MONITOR_INTERVAL_SECS,
+};
+use dz_validator_pda::plan::{check_plan_hash, plan_hash, BatchPlan};
+use dz_validator_pda::pda::{derive, parse_seed_spec, DepositPda, Seed};
+use dz_validator_pda::pending::{find_onchain_funding, settle_funding_attempts};
+use dz_validator_pda::verify::{verify_balance_delta, verify_deposit, BalanceDelta, DepositVerdict, DepositVerification};
+use dz_validator_pda::fund_many::{
+    format_funding_pairs, parse_funding_pairs, prepare_funding_batch, read_funding_file, resolve_unconfirmed, send_funding_batch,
+    send_funding_batch_with_state, FundingRunState, PreparedFundingBatch,
+};
+use dz_validator_pda::token::{prepare_token_funding, send_token_funding, PreparedTokenFunding, TokenFundingOutcome};
+use dz_validator_pda::bundle::{export_bundle, import_bundle, StatePaths};
+use dz_validator_pda::deposit::{
+    confirmed_slot, get_deposit_account, DepositAccount, DepositAccountState, DepositEvent, DepositLifecycle,
+    DEFAULT_INITIALIZATION_TIMEOUT_SECS,
+};
+use dz_validator_pda::epoch::{self, wait_for_epoch, EpochTarget, MAX_EPOCH_POLL_INTERVAL};
+use dz_validator_pda::geyser::{self, AccountUpdates, Backend};
+use dz_validator_pda::explorer::{deposit_uri, render_qr, Explorer};
+use dz_validator_pda::i18n::{self, set_lang, Lang, LANG_ENV_VAR};
+use dz_validator_pda::keygen;
+use dz_validator_pda::keystore;
+use dz_validator_pda::labels::Labels;
+use dz_validator_pda::message::{read_message, sign_message, verify_message};
+use dz_validator_pda::memo::{idempotency_memo, parse_idempotency_key, parse_memo, MAX_MEMO_LEN};
+use dz_validator_pda::logging::{init_logging, log_filter, LogFilter, LOG_ENV_VAR};
+use dz_validator_pda::notify::{send_notification, BalanceThreshold, NotifyConfig, NotifyEvent};
+use dz_validator_pda::signer::{fill_validator, load_signer, read_identity_pubkey, SignerPool, SignerSource};
+use dz_validator_pda::offline::{
+    broadcast_transaction, check_fully_signed, combine_signatures, decode_partial_transaction, parse_blockhash,
+    sign_funding_offline,
+};
+use dz_validator_pda::precedence::{parse_rpc_url, ConnectionSettings};
+use dz_validator_pda::priority::parse_priority_fee;
+use dz_validator_pda::metrics::{
+    balance_metrics, funding_metrics, push_metrics, start_metrics_server, Metric, MetricsRegistry, DEFAULT_METRICS_JOB,
+    RPC_ERRORS_TOTAL,
+};
+use dz_validator_pda::report::{
+    rent_exemption_warning, strict_check, JsonReport, WARN_GOSSIP_CHECK_FAILED, WARN_GOSSIP_CHECK_SKIPPED, WARN_NOT_IN_GOSSIP,
+    WARN_RECEIPT_NOT_WRITTEN, WARN_ROTATED_WALLET_UNVERIFIED, WARN_STATUS_NOT_CHECKED, WARN_WALLET_SHORTFALL,
+};
+use dz_validator_pda::progress;
+use dz_validator_pda::receipt::{create_receipt, verify_receipt, DepositReceipt};
+use dz_validator_pda::rpc::gossip_cache::GossipCacheFile;
+use dz_validator_pda::rpc::{self, rpc_client, RateLimit, RpcSettings, SolanaRpc, LOCAL_RPC_URL};
+use dz_validator_pda::rpc_check::{evaluate_endpoints, probe_endpoint, EndpointCheck, DEFAULT_MAX_SLOT_LAG};
+use dz_validator_pda::script::{Script, Step, StepOp};
+use dz_validator_pda::timing::{self, format_timing_table};
+use dz_validator_pda::trace::{new_trace_id, set_trace_id, trace_id};
+use dz_validator_pda::state::{default_state_path, State};
+use dz_validator_pda::squads::{prepare_squads_proposal, send_squads_proposal, PreparedSquadsProposal};
+use dz_validator_pda::stats::{audit_stats, StatsWindow, DEFAULT_STATS_WINDOW};
+use dz_validator_pda::wallet::{sweep_wallet, wait_for_confirmation, DEFAULT_CONFIRMATION_TIMEOUT_SECS};
+use dz_validator_pda::watch::{BalanceRateTracker, DEFAULT_RATE_WINDOW_SECS};
+use dz_validator_pda::history::{balance_history, BalanceHistory, DEFAULT_HISTORY_DAYS};
+use dz_validator_pda::revenue::{revenue_report, EpochRange, Reconciliation, RevenueReport};
+use dz_validator_pda::health::{cancellation, get_policy_status, parse_max_commission, parse_min_activated_stake};
+use dz_validator_pda::uptime::{format_basis_points, parse_lookback_epochs, parse_required_uptime, UptimeRequirement, DEFAULT_LOOKBACK_EPOCHS};
+use dz_validator_pda::{FundingPolicy, PolicyCheck};
+use dz_validator_pda::error::{EXIT_CONFIRMATION_TIMEOUT, EXIT_FAILURE, EXIT_INVALID_INPUT, EXIT_STRICT_WARNING, EXIT_TRANSACTION_FAILED};
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::{Keypair, Signer, EncodableKey};
-use solana_sdk::transaction::Transaction;
-use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_sdk::signature::{Signature, Signer};
+use std::collections::HashMap;
 use std::env;
-use anyhow::Result;
-use bs58;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 
-pub const REVENUE_DISTRIBUTION_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("dzrevZC94tBLwuHw1dyynZxaXTWyp7yocsinyEVPtt4");
+/// Number of attempts for read-only RPC calls failing with transient errors
+const RPC_RETRY_ATTEMPTS: u32 = 3;
 
-/// Generates a Program Derived Address (PDA) for validator deposit
-/// 
-/// # Arguments
-/// * `validator_id` - The validator's public key
-/// 
-/// # Returns
-/// * `Pubkey` - The generated PDA for the deposit
-pub fn generate_deposit_pda(validator_id: &Pubkey) -> Pubkey {
-    let (deposit_key, _) = Pubkey::find_program_address(
-        &[b"solana_validator_deposit", validator_id.as_ref()],
-        &REVENUE_DISTRIBUTION_PROGRAM_ID
-    );
-    deposit_key
+/// Print the `--timing` table at exit (text output)
+static TIMING_TABLE: AtomicBool = AtomicBool::new(false);
+
+/// Exit code of a run stopped by Ctrl-C (128 + SIGINT, as shells report it)
+const EXIT_INTERRUPTED: i32 = 130;
+
+/// Operations that may send a transaction, so a stopped run may still have moved funds
+const FUNDING_OPERATIONS: &[&str] = &["pda-fund-address", "pda-fund-many", "pda-fund-token", "apply", "plan", "run", "wallet", "airdrop"];
+
+#[tokio::main]
+async fn main() {
+    run().await;
+    print_timing();
 }
 
-/// Validates if a string is a valid base58 encoded string
-/// 
-/// # Arguments
-/// * `address_str` - String to validate
-/// 
-/// # Returns
-/// * `Result<(), String>` - Validation result
-pub fn validate_base58(address_str: &str) -> Result<(), String> {
-    if address_str.trim().is_empty() {
-        return Err("Address cannot be empty".to_string());
+/// Exits with `code`, printing the `--timing` table first
+fn exit(code: i32) -> ! {
+    print_timing();
+    std::process::exit(code)
+}
+
+/// Prints the `--timing` table on stderr; with `--output json` it is part of the report instead
+fn print_timing() {
+    if let Some(timings) = timing::report().filter(|_| TIMING_TABLE.load(Ordering::Relaxed)) {
+        eprint!("{}", format_timing_table(&timings));
     }
+}
+
+/// Parses the arguments and runs the operation
+async fn run() {
+    let raw_args: Vec<String> = env::args().collect();
     
-    // Check if the string contains only valid base58 characters
-    let valid_chars = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
-    for ch in address_str.chars() {
-        if !valid_chars.contains(ch) {
-            return Err(format!("Invalid base58 character '{}' found in address", ch));
+    let parsed = match ParsedArgs::parse(&raw_args[1..], VALUE_FLAGS, SWITCH_FLAGS) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
         }
+    };
+    
+    // One trace id per run ties together logs, audit entries, notifications and RPC requests
+    let trace_id = parsed.value("trace-id").map(str::to_string).unwrap_or_else(new_trace_id);
+    if let Err(e) = set_trace_id(trace_id) {
+        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+        exit(exit_code(&e));
     }
     
-    // Try to decode the base58 string to verify it's valid
-    bs58::decode(address_str)
-        .into_vec()
-        .map_err(|e| format!("Invalid base58 encoding: {}", e))?;
+    // Messages are printed in the language of --lang, then DZ_LANG, then English
+    if let Err(e) = Lang::resolve(parsed.value("lang"), env::var(LANG_ENV_VAR).ok().as_deref()).and_then(set_lang) {
+        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+        exit(exit_code(&e));
+    }
     
-    Ok(())
-}
-
-/// Parses a string into a Pubkey
-/// 
-/// # Arguments
-/// * `address_str` - String containing the address
-/// 
-/// # Returns
-/// * `Result<Pubkey, String>` - Parsing result
-pub fn parse_pubkey(address_str: &str) -> Result<Pubkey, String> {
-    address_str.parse::<Pubkey>()
-        .map_err(|e| format!("Invalid pubkey format: {}", e))
-}
-
-/// Gets the balance of a given account
-/// 
-/// # Arguments
-/// * `address` - The account address to check balance for
-/// * `rpc_url` - The RPC endpoint URL (optional, defaults to mainnet)
-/// 
-/// # Returns
-/// * `Result<u64, String>` - Balance in lamports or error message
-pub async fn get_account_balance(address: &Pubkey, rpc_url: Option<&str>) -> Result<u64, String> {
-    let url = rpc_url.unwrap_or("https://api.mainnet-beta.solana.com");
-    let client = RpcClient::new(url.to_string());
+    // Every money-moving operation is recorded in one audit log: --audit-log, then the config file, then the default
+    if let Some(path) = audit_log_override(&parsed) {
+        audit::set_audit_log_path(path);
+    }
     
-    client.get_balance(address).await
-        .map_err(|e| format!("Failed to get balance: {}", e))
-}
-
-/// Cancels PDA funding if validator is not in gossip network
-/// 
-/// # Arguments
-/// * `validator_id` - The validator's public key
-/// * `rpc_url` - The RPC endpoint URL (optional, defaults to mainnet)
-/// 
-/// # Returns
-/// * `Result<bool, String>` - True if funding should be cancelled, false if should proceed, or error message
-pub async fn should_cancel_pda_funding(validator_id: &Pubkey, rpc_url: Option<&str>) -> Result<bool, String> {
-    match is_validator_in_gossip(validator_id, rpc_url).await {
-        Ok(true) => {
-            println!("✓ Validator {} is present in Solana gossip network - proceeding with funding", validator_id);
-            Ok(false) // Don't cancel
+    // Diagnostics go to stderr as log events; stdout is reserved for results
+    match log_filter(parsed.count("verbose"), parsed.has("quiet"), env::var(LOG_ENV_VAR).ok().as_deref()) {
+        Ok(filter) => init_logging(filter),
+        Err(e) => {
+            init_logging(LogFilter::from_verbosity(0, false));
+            warn!("Ignoring {}: {}", LOG_ENV_VAR, e);
+        }
+    }
+    
+    // Program name followed by positional parameters
+    let args: Vec<String> = raw_args[..1].iter().chain(parsed.positionals.iter()).cloned().collect();
+    
+    // Help is printed before any other argument is checked
+    if parsed.has("help") {
+        match args.get(1).and_then(|operation| command_help(operation)) {
+            Some(command) => print!("{}", format_command_help(&args[0], command)),
+            None => print_usage(&args[0]),
+        }
+        return;
+    }
+    
+    // Examples are generated from the same list the usage text and the tests use
+    if args.get(1).map(String::as_str) == Some("examples") {
+        match examples_for(args.get(2).map(String::as_str)) {
+            Ok(examples) => print!("{}", format_examples(&args[0], &examples)),
+            Err(e) => {
+                eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                exit(exit_code(&e));
+            }
         }
-        Ok(false) => {
-            println!("✗ Validator {} is NOT found in Solana gossip network - cancelling funding", validator_id);
-            println!("This validator may not be active or properly configured.");
-            Ok(true) // Cancel funding
+        return;
+    }
+    
+    // Validators piped on standard input turn pda-address and pda-balance into a batch over them
+    let args = match stdin_batch_arguments(args, &parsed) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
         }
+    };
+    
+    let output = match parsed.value("output").map(OutputFormat::parse).transpose() {
+        Ok(output) => output.unwrap_or_default(),
         Err(e) => {
-            println!("✗ Error checking gossip network: {} - cancelling funding for safety", e);
-            Ok(true) // Cancel funding on error
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
         }
+    };
+    
+    // JSON output is a single document, so streaming and interactive operations keep text output
+    if let Some(operation) = args.get(1).filter(|operation| ["wallet", "pda-watch", "gossip-monitor", "dashboard", "completion"].contains(&operation.as_str()))
+        && output == OutputFormat::Json
+    {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("json-not-supported", &[("operation", operation)]));
+        exit(EXIT_INVALID_INPUT);
     }
-}
-
-/// Funds a validator PDA account from a selected keypair
-/// 
-/// # Arguments
-/// * `validator_id` - The validator's public key
-/// * `keypair_path` - Path to the keypair file
-/// * `amount_sol` - Amount to transfer in SOL
-/// * `rpc_url` - The RPC endpoint URL (optional, defaults to mainnet)
-/// 
-/// # Returns
-/// * `Result<String, String>` - Transaction signature or error message
-pub async fn pda_fund_address(
-    validator_id: &Pubkey,
-    keypair_path: &str,
-    amount_sol: f64,
-    rpc_url: Option<&str>
-) -> Result<String, String> {
-    // Check if funding should be cancelled due to validator not being in gossip
-    match should_cancel_pda_funding(validator_id, rpc_url).await {
-        Ok(true) => {
-            return Err("Funding cancelled: Validator is not in Solana gossip network".to_string());
+    
+    // CSV is tabular, so only batch rows and time series produce it
+    if output == OutputFormat::Csv && !matches!(args.get(1).map(String::as_str), Some("pda-batch" | "pda-balance-history")) {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("csv-not-supported"));
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    // Progress bars share stderr with the log, so they are only drawn for a person watching a terminal
+    let full_screen = args.get(1).map(String::as_str) == Some("dashboard");
+    progress::set_enabled(output == OutputFormat::Text && !parsed.has("quiet") && !full_screen && std::io::stderr().is_terminal());
+    
+    // RPC calls are timed per method for the end-of-run report
+    timing::set_enabled(parsed.has("timing"));
+    TIMING_TABLE.store(output != OutputFormat::Json, Ordering::Relaxed);
+    
+    if let Err(e) = parsed.value("explorer").map(Explorer::parse).transpose() {
+        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+        exit(exit_code(&e));
+    }
+    
+    if let Err(e) = parsed.value("show-fiat").map(Currency::parse).transpose() {
+        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+        exit(exit_code(&e));
+    }
+    
+    // Templates replace the text output of the lookups, so they are checked before any RPC request
+    let template = match output_template(&args, &parsed, output) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    // The QR code is printed with the PDA address, so JSON output would no longer be a single document
+    if parsed.has("qr") && (args.get(1).map(String::as_str) != Some("pda-address") || output != OutputFormat::Text) {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("qr-not-supported"));
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    // Public RPC preset: paced requests and a long gossip cache; watch mode would poll forever
+    if let Some(operation @ ("pda-watch" | "gossip-monitor")) = args.get(1).map(String::as_str)
+        && parsed.has("nice")
+    {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("not-with-nice", &[("operation", &operation)]));
+        exit(EXIT_INVALID_INPUT);
+    }
+    // Endpoint and commitment: flags > environment > config file > defaults
+    let connection = match Config::load(&config_path(&parsed))
+        .and_then(|config| ConnectionSettings::resolve(&parsed, |name| env::var(name).ok(), &config))
+    {
+        Ok(connection) => connection,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    debug!(rpc_url = connection.rpc_url.value.as_str(), source = %connection.rpc_url.source, "RPC endpoint");
+    if parsed.has("rate-burst") && !parsed.has("rate-limit") {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("rate-burst-needs-limit"));
+        exit(EXIT_INVALID_INPUT);
+    }
+    let rate_limit = match parsed.value("rate-limit").map(|rate| RateLimit::parse(rate, parsed.value("rate-burst"))).transpose() {
+        Ok(rate_limit) => rate_limit,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    let gossip_cache_ttl = match parsed.value("gossip-cache-ttl").map(rpc::parse_gossip_cache_ttl).transpose() {
+        Ok(ttl) => ttl,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    let gossip_cache_file = parsed.value("gossip-cache-file").map(PathBuf::from);
+    if gossip_cache_file.is_some() && gossip_cache_ttl == Some(None) {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("gossip-cache-file-needs-ttl"));
+        exit(EXIT_INVALID_INPUT);
+    }
+    // A hung endpoint fails each request after --rpc-timeout instead of freezing the run
+    let rpc_timeout = match parsed.value("rpc-timeout").map(|value| rpc::parse_timeout(value, "rpc-timeout")).transpose() {
+        Ok(timeout) => timeout,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    let deadline = match parsed.value("deadline").map(|value| rpc::parse_timeout(value, "deadline")).transpose() {
+        Ok(deadline) => deadline,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
         }
-        Ok(false) => {
-            // Validator is in gossip, proceed with funding
+    };
+    // Provider auth headers and an explicit egress proxy apply to every RPC request
+    let rpc_headers = match parsed.values("rpc-header").into_iter().map(rpc::parse_rpc_header).collect::<Result<Vec<_>, _>>() {
+        Ok(headers) => headers,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
         }
+    };
+    let rpc_proxy = match parsed.value("rpc-proxy").map(rpc::parse_proxy).transpose() {
+        Ok(proxy) => proxy,
         Err(e) => {
-            return Err(format!("Failed to check gossip status: {}", e));
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    // Gossip presence checks and pda-watch can use a Yellowstone gRPC stream instead of JSON-RPC
+    let geyser_endpoint = match (parsed.value("backend").map(Backend::parse).transpose(), parsed.value("geyser-endpoint")) {
+        (Err(e), _) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+        (Ok(Some(Backend::Geyser)), Some(endpoint)) => Some(endpoint.to_string()),
+        (Ok(Some(Backend::Geyser)), None) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::text("geyser-needs-endpoint"));
+            exit(EXIT_INVALID_INPUT);
         }
+        (Ok(_), Some(_)) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::text("geyser-endpoint-needs-backend"));
+            exit(EXIT_INVALID_INPUT);
+        }
+        (Ok(_), None) => None,
+    };
+    // The dashboard owns the terminal and must restore it before exiting
+    if deadline.is_some() && args.get(1).map(String::as_str) == Some("dashboard") {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("deadline-not-with-dashboard"));
+        exit(EXIT_INVALID_INPUT);
     }
+    // One gossip node list serves a whole batch run
+    let batch = args.get(1).map(String::as_str) == Some("pda-batch");
+    let mut settings = if parsed.has("nice") { RpcSettings::nice() } else { RpcSettings::default() };
+    settings.url = Some(connection.rpc_url.value);
+    settings.commitment = connection.commitment.map(|commitment| commitment.value).unwrap_or(settings.commitment);
+    settings.rate_limit = rate_limit.or(settings.rate_limit);
+    if batch || gossip_cache_file.is_some() {
+        settings.gossip_cache_ttl = settings.gossip_cache_ttl.or(Some(rpc::DEFAULT_GOSSIP_CACHE_TTL));
+    }
+    settings.gossip_cache_ttl = gossip_cache_ttl.unwrap_or(settings.gossip_cache_ttl);
+    settings.gossip_cache_file = gossip_cache_file;
+    settings.timeout = rpc_timeout;
+    settings.headers = rpc_headers;
+    settings.proxy = rpc_proxy;
+    settings.geyser_endpoint = geyser_endpoint;
+    if let Err(e) = rpc::configure(settings) {
+        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+        exit(exit_code(&e));
+    }
+    spawn_run_guard(args.get(1).map(String::as_str), deadline);
+    
+    // The validator can be checked against, or taken from, its identity keypair, identity file or local node
+    let args = match identity_arguments(args, &parsed).await {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
     
-    let url = rpc_url.unwrap_or("https://api.mainnet-beta.solana.com");
-    let client = RpcClient::new(url.to_string());
+    // Wallet management does not take a validator address
+    if args.get(1).map(String::as_str) == Some("wallet") {
+        run_wallet_command(&args, &parsed).await;
+        return;
+    }
     
-    // Convert SOL to lamports
-    let amount_lamports = (amount_sol * 1_000_000_000.0) as u64;
+    // Fee accounting reads the audit log and does not take a validator address
+    if args.get(1).map(String::as_str) == Some("fees") {
+        run_fees_command(&args, &parsed, output);
+        return;
+    }
     
-    // Load keypair from file
-    let keypair = Keypair::read_from_file(keypair_path)
-        .map_err(|e| format!("Failed to read keypair from {}: {}", keypair_path, e))?;
+    // Operational overview of the audit log
+    if args.get(1).map(String::as_str) == Some("audit") {
+        run_audit_command(&args, &parsed, output).await;
+        return;
+    }
     
-    // Generate PDA for the validator
-    let pda_address = generate_deposit_pda(validator_id);
+    // Keypair encryption works on files, not on a validator address
+    if args.get(1).map(String::as_str) == Some("keygen") {
+        run_keygen_command(&args, &parsed, output);
+        return;
+    }
     
-    // Get recent blockhash
-    let recent_blockhash = client.get_latest_blockhash().await
-        .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
+    // `grind` is short for `keygen grind`
+    if args.get(1).map(String::as_str) == Some("grind") {
+        let keygen_args: Vec<String> = [args[0].clone(), "keygen".to_string()].into_iter().chain(args[1..].iter().cloned()).collect();
+        run_keygen_command(&keygen_args, &parsed, output);
+        return;
+    }
     
-    // Create transfer instruction
-    let transfer_instruction = solana_system_interface::instruction::transfer(
-        &keypair.pubkey(),
-        &pda_address,
-        amount_lamports,
-    );
+    // The alias registry edits the [validators] table of the config file
+    if args.get(1).map(String::as_str) == Some("alias") {
+        run_alias_command(&args, &parsed, output);
+        return;
+    }
     
-    // Create and sign transaction
-    let transaction = Transaction::new_signed_with_payer(
-        &[transfer_instruction],
-        Some(&keypair.pubkey()),
-        &[&keypair],
-        recent_blockhash,
-    );
+    // Backup and migration of the local files
+    if args.get(1).map(String::as_str) == Some("state") {
+        run_state_command(&args, &parsed, output);
+        return;
+    }
     
-    // Send transaction
-    let config = RpcSendTransactionConfig {
-        skip_preflight: false,
-        preflight_commitment: None,
-        encoding: None,
-        max_retries: Some(3),
-        min_context_slot: None,
-    };
+    // Playbooks run several operations from a script file
+    if args.get(1).map(String::as_str) == Some("run") {
+        run_script_command(&args, &parsed, output).await;
+        return;
+    }
     
-    let signature = client.send_transaction_with_config(&transaction, config).await
-        .map_err(|e| format!("Failed to send transaction: {}", e))?;
+    // Two-person funding works on plan and approval files instead of a validator address
+    if let Some(command @ ("plan" | "approve" | "apply")) = args.get(1).map(String::as_str) {
+        let result = match command {
+            "plan" if args.get(2).map(String::as_str) == Some("execute") => {
+                execute_batch_plan(&args, &parsed, output).await;
+                return;
+            }
+            "plan" if parsed.has("pairs") || parsed.has("file") => create_batch_plan(&args, &parsed).await,
+            "plan" => create_plan(&args, &parsed),
+            "approve" => approve_plan_file(&args, &parsed),
+            _ => {
+                apply_plan(&args, &parsed, output).await;
+                return;
+            }
+        };
+        if let Err(e) = result {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+        return;
+    }
     
-    Ok(signature.to_string())
-}
-
-/// Checks if a validator ID is present in the Solana gossip network
-/// 
-/// # Arguments
-/// * `validator_id` - The validator's public key to check
-/// * `rpc_url` - The RPC endpoint URL (optional, defaults to mainnet)
-/// 
-/// # Returns
-/// * `Result<bool, String>` - True if validator is in gossip, false otherwise, or error message
-pub async fn is_validator_in_gossip(validator_id: &Pubkey, rpc_url: Option<&str>) -> Result<bool, String> {
-    let url = rpc_url.unwrap_or("https://api.mainnet-beta.solana.com");
-    let client = RpcClient::new(url.to_string());
-    
-    // Get the cluster info to check if validator is in gossip
-    let cluster_nodes = client.get_cluster_nodes().await
-        .map_err(|e| format!("Failed to get cluster nodes: {}", e))?;
-    
-    // Check if the validator ID is in the cluster nodes
-    let validator_string = validator_id.to_string();
-    let is_in_gossip = cluster_nodes.iter().any(|node| {
-        node.pubkey.to_string() == validator_string
-    });
+    // Shell completion does not take a validator address
+    if args.get(1).map(String::as_str) == Some("completion") {
+        match args.get(2).map(|shell| Shell::parse(shell)) {
+            Some(Ok(shell)) => print!("{}", completion_script(shell, env!("CARGO_PKG_NAME"), &[VALUE_FLAGS, SWITCH_FLAGS].concat())),
+            Some(Err(e)) => {
+                eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                exit(exit_code(&e));
+            }
+            None => {
+                eprintln!("{}: {}", i18n::text("error"), i18n::text("completion-needs-shell"));
+                eprintln!("{}: {} completion <bash|zsh|fish>", i18n::text("usage"), args[0]);
+                exit(EXIT_INVALID_INPUT);
+            }
+        }
+        return;
+    }
     
-    Ok(is_in_gossip)
-}
-
-#[tokio::main]
-async fn main() {
-    let args: Vec<_> = env::args().collect();
+    // Dynamic candidates for the completion scripts; failures just produce no candidates
+    if args.get(1).map(String::as_str) == Some(COMPLETE_OPERATION) {
+        let config = Config::load(&config_path(&parsed)).unwrap_or_default();
+        let state = State::load(&default_state_path()).unwrap_or_default();
+        let include_aliases = args.get(2).map(String::as_str) == Some("validators");
+        for candidate in validator_candidates(&config, &state, include_aliases) {
+            println!("{}", candidate);
+        }
+        return;
+    }
+    
+    // Broadcasting takes a signed transaction instead of a validator address
+    if args.get(1).map(String::as_str) == Some("broadcast") {
+        broadcast_signed_transaction(&args, &parsed, output).await;
+        return;
+    }
+    
+    // Test wallets are funded from the faucet of devnet or testnet
+    if args.get(1).map(String::as_str) == Some("airdrop") {
+        run_airdrop_command(&args, &parsed, output).await;
+        return;
+    }
+    
+    // Node list snapshots show which validators joined or left gossip between two points in time
+    if let Some(operation @ ("gossip-snapshot" | "gossip-diff")) = args.get(1).map(String::as_str) {
+        run_gossip_command(operation, &args, &parsed, output).await;
+        return;
+    }
+    
+    // Gossip presence is monitored for a list of validators until Ctrl-C
+    if args.get(1).map(String::as_str) == Some("gossip-monitor") {
+        run_gossip_monitor(&args, &parsed).await;
+        return;
+    }
+    
+    // Endpoint health checks take RPC URLs instead of a validator address
+    if args.get(1).map(String::as_str) == Some("rpc-check") {
+        run_rpc_check_command(&args, &parsed, output).await;
+        return;
+    }
+    
+    // Arbitrary PDAs are derived from a seed list instead of a validator address
+    if args.get(1).map(String::as_str) == Some("derive") {
+        run_derive_command(&args, &parsed, output);
+        return;
+    }
+    
+    // Auditing a payment starts from its signature
+    if args.get(1).map(String::as_str) == Some("verify-deposit") {
+        run_verify_deposit_command(&args, &parsed, output).await;
+        return;
+    }
+    
+    // Receipts are checked offline from the file alone
+    if args.get(1).map(String::as_str) == Some("verify-receipt") {
+        run_verify_receipt_command(&args, output);
+        return;
+    }
+    
+    // Off-chain message signatures need a keypair or a pubkey, not a validator address
+    if let Some(operation @ ("sign-message" | "verify-message")) = args.get(1).map(String::as_str) {
+        run_message_command(operation, &args, &parsed, output);
+        return;
+    }
+    
+    // Packed funding takes validator:amount pairs instead of a single address
+    if args.get(1).map(String::as_str) == Some("pda-fund-many") {
+        run_fund_many_command(&args, &parsed, output).await;
+        return;
+    }
+    
+    // Token deposits take the mint and amount as flags and accept aliases
+    if args.get(1).map(String::as_str) == Some("pda-fund-token") {
+        run_fund_token_command(&args, &parsed, output).await;
+        return;
+    }
+    
+    // The balance history is read from the transactions of the deposit PDA
+    if args.get(1).map(String::as_str) == Some("pda-balance-history") {
+        run_balance_history_command(&args, &parsed, output).await;
+        return;
+    }
+    
+    // The revenue report reads the PDA history and the rewards of a range of epochs
+    if args.get(1).map(String::as_str) == Some("revenue-report") {
+        run_revenue_report_command(&args, &parsed, output).await;
+        return;
+    }
+    
+    // Treasury allocation splits a budget over a list of validators and can write it as a batch plan
+    if args.get(1).map(String::as_str) == Some("allocate") {
+        run_allocate_command(&args, &parsed, output).await;
+        return;
+    }
+    
+    // Batch operations take a list of validators instead of a single address
+    if args.get(1).map(String::as_str) == Some("pda-batch") {
+        run_batch_command(&args, &parsed, output, template.as_ref()).await;
+        return;
+    }
+    
+    // The dashboard monitors the configured validators until it is closed
+    if args.get(1).map(String::as_str) == Some("dashboard") {
+        if let Err(e) = run_dashboard(&args, &parsed).await {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+        return;
+    }
     
     if args.len() < 3 {
-        eprintln!("Error: Please provide operation name and validator address as parameters");
-        eprintln!("Usage: {} <operation> <validator_address> [additional_params]", args[0]);
-        eprintln!("Operations:");
-        eprintln!("  pda-address     - Generate PDA address for validator");
-        eprintln!("  pda-balance     - Show balance of PDA address for validator");
-        eprintln!("  pda-fund-address - Fund validator PDA from keypair");
-        eprintln!("Example: {} pda-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", args[0]);
-        eprintln!("Example: {} pda-balance FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", args[0]);
-        eprintln!("Example: {} pda-fund-address FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL /path/to/keypair.json 1.5", args[0]);
-        eprintln!("Note: Amount is in SOL (e.g., 1.5 for 1.5 SOL)");
-        std::process::exit(1);
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("missing-operation-and-validator"));
+        print_usage(&args[0]);
+        exit(EXIT_INVALID_INPUT);
     }
     
     let operation = args[1].as_str();
@@ -230,408 +588,4797 @@ async fn main() {
     
     // Проверка на заполненность параметров
     if operation.trim().is_empty() {
-        eprintln!("Error: Operation parameter cannot be empty");
-        std::process::exit(1);
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("empty-operation"));
+        exit(EXIT_INVALID_INPUT);
     }
     
     if address.trim().is_empty() {
-        eprintln!("Error: Validator address parameter cannot be empty");
-        std::process::exit(1);
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("empty-validator"));
+        exit(EXIT_INVALID_INPUT);
     }
     
+    let address = match resolve_alias_argument(&parsed, address) {
+        Ok(address) => address,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    let address = address.as_str();
+    
     // Validate base58 format for validator address
     if let Err(e) = validate_base58(address) {
-        eprintln!("Error: Invalid validator address format: {}", e);
-        eprintln!("Validator address must be a valid base58 encoded string");
-        std::process::exit(1);
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("invalid-validator-format", &[("error", &i18n::localize(&e))]));
+        eprintln!("{}", i18n::text("validator-must-be-base58"));
+        exit(EXIT_INVALID_INPUT);
     }
     
     // Проверка операции
-    if operation != "pda-address" && operation != "pda-balance" && operation != "pda-fund-address" {
-        eprintln!("Error: Unknown operation '{}'. Supported operations: pda-address, pda-balance, pda-fund-address", operation);
-        std::process::exit(1);
+    if operation != "pda-address" && operation != "pda-balance" && operation != "pda-fund-address" && operation != "pda-watch" && operation != "validator-status" {
+        eprintln!(
+            "{}: {}",
+            i18n::text("error"),
+            i18n::message(
+                "unknown-operation",
+                &[
+                    ("operation", &operation),
+                    ("operations", &"pda-address, derive, pda-balance, pda-balance-history, revenue-report, pda-fund-address, pda-fund-many, pda-fund-token, pda-watch, validator-status, pda-batch, allocate, dashboard, broadcast, wallet, airdrop, gossip-snapshot, gossip-diff, gossip-monitor, verify-deposit, verify-receipt, sign-message, verify-message, rpc-check, keygen, grind, fees, audit, alias, state, plan, approve, apply, run, completion"),
+                ],
+            )
+        );
+        exit(EXIT_INVALID_INPUT);
     }
     
     // Additional validation for pda-fund-address operation
-    if operation == "pda-fund-address" {
-        if args.len() < 5 {
-            eprintln!("Error: pda-fund-address requires keypair path and amount parameters");
-            eprintln!("Usage: {} pda-fund-address <validator_address> <keypair_path> <amount_sol>", args[0]);
-            eprintln!("Note: Amount is in SOL (e.g., 1.5 for 1.5 SOL)");
-            std::process::exit(1);
+    if operation == "pda-fund-address" && args.len() < 4 {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("fund-needs-amount"));
+        eprintln!("{}: {} pda-fund-address <validator_address> [keypair_path] <amount>", i18n::text("usage"), args[0]);
+        eprintln!("{}", i18n::text("amount-note"));
+        eprintln!("{}", i18n::text("config-wallet-note"));
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    if parsed.has("blockhash") && !parsed.has("sign-only") {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("only-used-with", &[("flag", &"--blockhash"), ("other", &"--sign-only")]));
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    // Offline signing cannot check the priority fee against recent network fees
+    if parsed.has("priority-fee") && parsed.has("sign-only") {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("cannot-be-used-with", &[("flag", &"--priority-fee"), ("other", &"--sign-only")]));
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    // Offline signing and broadcast only handle legacy transactions
+    if (parsed.has("tx-version") || parsed.has("alt")) && parsed.has("sign-only") {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("cannot-be-used-with", &[("flag", &"--tx-version and --alt"), ("other", &"--sign-only")]));
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    if parsed.has("memo") {
+        if operation != "pda-fund-address" {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("only-supported-by", &[("flag", &"--memo"), ("operation", &"pda-fund-address")]));
+            exit(EXIT_INVALID_INPUT);
+        }
+        // The signed transaction is built by offline.rs, which does not add a memo
+        if parsed.has("sign-only") {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("cannot-be-used-with", &[("flag", &"--memo"), ("other", &"--sign-only")]));
+            exit(EXIT_INVALID_INPUT);
+        }
+    }
+    
+    if (parsed.has("idempotency-key") || parsed.has("duplicate-window")) && operation != "pda-fund-address" {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("only-supported-by", &[("flag", &"--idempotency-key and --duplicate-window"), ("operation", &"pda-fund-address")]));
+        exit(EXIT_INVALID_INPUT);
+    }
+    // Offline-signed transactions carry no memo and are not recorded until they are broadcast
+    if parsed.has("idempotency-key") && parsed.has("sign-only") {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("cannot-be-used-with", &[("flag", &"--idempotency-key"), ("other", &"--sign-only")]));
+        exit(EXIT_INVALID_INPUT);
+    }
+    if let Err(e) = duplicate_window(&parsed) {
+        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+        exit(exit_code(&e));
+    }
+    
+    let epoch_target = match epoch_target(&parsed) {
+        Ok(Some(_)) if operation != "pda-fund-address" => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("only-supported-by", &[("flag", &"--at-epoch-boundary and --at-epoch"), ("operation", &"pda-fund-address")]));
+            exit(EXIT_INVALID_INPUT);
+        }
+        Ok(epoch_target) => epoch_target,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    if parsed.has("receipt") {
+        if operation != "pda-fund-address" {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("only-supported-by", &[("flag", &"--receipt"), ("operation", &"pda-fund-address")]));
+            exit(EXIT_INVALID_INPUT);
+        }
+        // Offline-signed transactions and proposals have not transferred anything yet
+        if parsed.has("sign-only") || parsed.has("squads") {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("cannot-be-used-with", &[("flag", &"--receipt"), ("other", &"--sign-only or --squads")]));
+            exit(EXIT_INVALID_INPUT);
+        }
+    }
+    
+    if parsed.has("verify-delta") {
+        if operation != "pda-fund-address" {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("only-supported-by", &[("flag", &"--verify-delta"), ("operation", &"pda-fund-address")]));
+            exit(EXIT_INVALID_INPUT);
         }
+        if parsed.has("sign-only") || parsed.has("squads") {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("cannot-be-used-with", &[("flag", &"--verify-delta"), ("other", &"--sign-only or --squads")]));
+            exit(EXIT_INVALID_INPUT);
+        }
+    }
+    
+    if parsed.has("squads") {
+        if operation != "pda-fund-address" {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("only-supported-by", &[("flag", &"--squads"), ("operation", &"pda-fund-address")]));
+            exit(EXIT_INVALID_INPUT);
+        }
+        // The proposal is sent right away; the vault transfers once members approve and execute it
+        let conflicting = ["sign-only", "nonce-account", "priority-fee", "tx-version", "alt", "idempotency-key"];
+        if let Some(flag) = conflicting.iter().find(|flag| parsed.has(flag)) {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("cannot-be-used-with", &[("flag", &format!("--{}", flag)), ("other", &"--squads")]));
+            exit(EXIT_INVALID_INPUT);
+        }
+        if epoch_target.is_some() {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("cannot-be-used-with", &[("flag", &"--at-epoch-boundary and --at-epoch"), ("other", &"--squads")]));
+            exit(EXIT_INVALID_INPUT);
+        }
+    }
+    
+    // Other deployments are only derived; balances and funding always use the mainnet program
+    if operation != "pda-address" && (parsed.has("seed-prefix") || parsed.has("program-id")) {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("only-supported-by", &[("flag", &"--seed-prefix and --program-id"), ("operation", &"pda-address")]));
+        exit(EXIT_INVALID_INPUT);
     }
     
+    // Old provider nodes are caught before the run instead of failing halfway through it
+    preflight_compatibility(operation, &parsed, output).await;
+    
+    let policy = match funding_policy(&parsed) {
+        Ok(policy) => policy,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    let program_id = match parsed.value("program-id").map(parse_pubkey).transpose() {
+        Ok(program_id) => program_id.unwrap_or(REVENUE_DISTRIBUTION_PROGRAM_ID),
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("invalid-flag", &[("flag", &"--program-id"), ("error", &i18n::localize(&e))]));
+            exit(EXIT_INVALID_INPUT);
+        }
+    };
+    let seed_prefix = parsed.value("seed-prefix").unwrap_or(DEPOSIT_PDA_SEED_PREFIX);
+    
     match parse_pubkey(address) {
         Ok(validator_id) => {
-            let deposit_key = generate_deposit_pda(&validator_id);
+            let (deposit_key, bump) = match DepositPda::with_program(&validator_id, seed_prefix, &program_id) {
+                Ok(deposit) => (deposit.address, deposit.bump),
+                Err(e) => {
+                    eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                    exit(exit_code(&e));
+                }
+            };
+            remember_validator(&validator_id);
+            let validator_label = address_labels(&parsed).format(&validator_id);
+            let rpc = rpc_client(None);
             
-            if operation == "pda-address" {
-                println!("Validator pubkey {}", address);
-                println!("Checking if validator is in gossip network...");
+            if operation == "pda-address" && (output == OutputFormat::Json || template.is_some()) {
+                let warnings = gossip_warnings(&validator_id, policy.check_gossip).await;
+                let result = serde_json::json!({
+                    "validator": address,
+                    "pda": deposit_key.to_string(),
+                    "bump": bump,
+                    "program_id": program_id.to_string(),
+                    "seed_prefix": seed_prefix,
+                });
+                let mut result = result;
+                if let Some(explorer) = explorer(&parsed) {
+                    result["pda_url"] = explorer.account_url(&deposit_key.to_string()).into();
+                }
+                print_result(operation, result, warnings, policy.strict, template.as_ref());
+            } else if operation == "pda-address" {
+                println!("{}", i18n::message("validator-pubkey", &[("validator", &validator_label)]));
+                if !policy.check_gossip {
+                    println!("{}", i18n::message("pda-address", &[("pda", &deposit_key)]));
+                    println!("{}", i18n::message("bump-seed", &[("bump", &bump)]));
+                    print_pda_links(&parsed, &deposit_key);
+                } else {
+                    debug!("Checking if validator is in gossip network...");
+                
+                    let warning = match retry_transient(RPC_RETRY_ATTEMPTS, || is_validator_in_gossip(&validator_id, &rpc)).await {
+                        Ok(true) => {
+                            println!("{}", i18n::message("in-gossip", &[("validator", &validator_id)]));
+                            println!("{}", i18n::message("pda-address", &[("pda", &deposit_key)]));
+                            println!("{}", i18n::message("bump-seed", &[("bump", &bump)]));
+                            None
+                        }
+                        Ok(false) => {
+                            println!("{}", i18n::message("not-in-gossip", &[("validator", &validator_id)]));
+                            println!("{}", i18n::text("not-in-gossip-hint"));
+                            println!("{}", i18n::message("pda-address", &[("pda", &deposit_key)]));
+                            println!("{}", i18n::message("bump-seed", &[("bump", &bump)]));
+                            println!("{}", i18n::text("not-in-gossip-funding"));
+                            Some(Warning::new(WARN_NOT_IN_GOSSIP, format!("Validator {} is NOT found in Solana gossip network", validator_id)))
+                        }
+                        Err(e) => {
+                            println!("{}", i18n::message("gossip-check-failed", &[("error", &i18n::error_text(&e))]));
+                            println!("{}", i18n::message("pda-address", &[("pda", &deposit_key)]));
+                            println!("{}", i18n::message("bump-seed", &[("bump", &bump)]));
+                            println!("{}", i18n::text("gossip-check-failed-hint"));
+                            Some(Warning::new(WARN_GOSSIP_CHECK_FAILED, format!("Error checking gossip network: {}", e)))
+                        }
+                    };
+                    print_pda_links(&parsed, &deposit_key);
+                    exit_if_strict(policy.strict, warning.as_slice());
+                }
+            } else if operation == "pda-balance" && (output == OutputFormat::Json || template.is_some()) {
+                let mut warnings = gossip_warnings(&validator_id, policy.check_gossip).await;
+                warnings.extend(check_rpc_health(&rpc).await);
                 
-                match is_validator_in_gossip(&validator_id, None).await {
-                    Ok(true) => {
-                        println!("✓ Validator {} is present in Solana gossip network", validator_id);
-                        println!("PDA Address: {}", deposit_key);
+                let result = retry_transient(RPC_RETRY_ATTEMPTS, || get_account_balance(&deposit_key, &rpc)).await;
+                push_run_metrics(&parsed, address, &balance_metrics(&deposit_key, result.as_ref().ok().copied(), unix_timestamp())).await;
+                
+                let mut fields = serde_json::json!({ "validator": address, "pda": deposit_key.to_string() });
+                if let Some(explorer) = explorer(&parsed) {
+                    fields["pda_url"] = explorer.account_url(&deposit_key.to_string()).into();
+                }
+                match result {
+                    Ok(balance) => {
+                        warnings.extend(rent_warning(&deposit_key, balance).await);
+                        fields["balance_lamports"] = balance.into();
+                        if let Some(quote) = fiat_quote(&parsed, balance, None).await {
+                            fields["fiat"] = serde_json::json!(quote);
+                        }
+                        // The JSON report keeps lamports only; templates also get the exact SOL amount
+                        if template.is_some() {
+                            fields["balance_sol"] = format_sol(balance).into();
+                        }
+                        print_result(operation, fields, warnings, policy.strict, template.as_ref());
                     }
-                    Ok(false) => {
-                        println!("✗ Validator {} is NOT found in Solana gossip network", validator_id);
-                        println!("This validator may not be active or properly configured.");
-                        println!("PDA Address: {}", deposit_key);
-                        println!("Warning: Funding this PDA may not be effective if the validator is not active.");
+                    Err(e) if template.is_some() => {
+                        eprintln!("{}", i18n::message("balance-failed", &[("error", &i18n::error_text(&e))]));
+                        exit(exit_code(&e));
                     }
                     Err(e) => {
-                        println!("✗ Error checking gossip network: {}", e);
-                        println!("PDA Address: {}", deposit_key);
-                        println!("Warning: Unable to verify validator status - proceed with caution.");
+                        println!("{}", JsonReport::failure(operation, fields, warnings, &e).to_json());
+                        exit(exit_code(&e));
                     }
                 }
             } else if operation == "pda-balance" {
-                println!("Validator pubkey {}", address);
-                println!("Checking if validator is in gossip network...");
+                println!("{}", i18n::message("validator-pubkey", &[("validator", &validator_label)]));
+                let mut warnings = Vec::new();
+                if policy.check_gossip {
+                    debug!("Checking if validator is in gossip network...");
                 
-                match is_validator_in_gossip(&validator_id, None).await {
-                    Ok(true) => {
-                        println!("✓ Validator {} is present in Solana gossip network", validator_id);
-                    }
-                    Ok(false) => {
-                        println!("✗ Validator {} is NOT found in Solana gossip network", validator_id);
-                        println!("This validator may not be active or properly configured.");
-                        println!("Warning: This PDA may not be effective if the validator is not active.");
-                    }
-                    Err(e) => {
-                        println!("✗ Error checking gossip network: {}", e);
-                        println!("Warning: Unable to verify validator status - proceed with caution.");
+                    match retry_transient(RPC_RETRY_ATTEMPTS, || is_validator_in_gossip(&validator_id, &rpc)).await {
+                        Ok(true) => {
+                            println!("{}", i18n::message("in-gossip", &[("validator", &validator_id)]));
+                        }
+                        Ok(false) => {
+                            println!("{}", i18n::message("not-in-gossip", &[("validator", &validator_id)]));
+                            println!("{}", i18n::text("not-in-gossip-hint"));
+                            println!("{}", i18n::text("not-in-gossip-pda"));
+                            warnings.push(Warning::new(WARN_NOT_IN_GOSSIP, format!("Validator {} is NOT found in Solana gossip network", validator_id)));
+                        }
+                        Err(e) => {
+                            println!("{}", i18n::message("gossip-check-failed", &[("error", &i18n::error_text(&e))]));
+                            println!("{}", i18n::text("gossip-check-failed-hint"));
+                            warnings.push(Warning::new(WARN_GOSSIP_CHECK_FAILED, format!("Error checking gossip network: {}", e)));
+                        }
                     }
                 }
                 
-                match get_account_balance(&deposit_key, None).await {
+                let result = retry_transient(RPC_RETRY_ATTEMPTS, || get_account_balance(&deposit_key, &rpc)).await;
+                push_run_metrics(&parsed, address, &balance_metrics(&deposit_key, result.as_ref().ok().copied(), unix_timestamp())).await;
+                
+                match result {
                     Ok(balance) => {
                         let sol_balance = balance as f64 / 1_000_000_000.0; // Convert lamports to SOL
-                        println!("PDA Address: {}", deposit_key);
-                        println!("PDA Balance: {} lamports ({} SOL)", balance, sol_balance);
+                        println!("{}", i18n::message("pda-address", &[("pda", &deposit_key)]));
+                        println!("{}", i18n::message("pda-balance", &[("lamports", &balance), ("sol", &sol_balance)]));
+                        if let Some(quote) = fiat_quote(&parsed, balance, None).await {
+                            println!("{}", quote);
+                        }
+                        print_pda_links(&parsed, &deposit_key);
+                        
+                        let balance_warnings = check_rpc_health(&rpc).await.into_iter().chain(rent_warning(&deposit_key, balance).await);
+                        for warning in balance_warnings {
+                            println!("⚠ {}", i18n::warning_text(&warning));
+                            warnings.push(warning);
+                        }
+                        exit_if_strict(policy.strict, &warnings);
                     }
                     Err(e) => {
-                        eprintln!("Error getting balance: {}", e);
-                        std::process::exit(1);
+                        eprintln!("{}", i18n::message("balance-failed", &[("error", &i18n::error_text(&e))]));
+                        exit(exit_code(&e));
                     }
                 }
             } else if operation == "pda-fund-address" {
-                let keypair_path = &args[3];
-                let amount_str = &args[4];
-                
-                let amount_sol = match amount_str.parse::<f64>() {
-                    Ok(amount) => {
-                        if amount <= 0.0 {
-                            eprintln!("Error: Amount must be greater than 0");
-                            std::process::exit(1);
+                // Without an explicit keypair path the configured funding wallet is used
+                let (keypair_path, amount_str) = if args.len() >= 5 {
+                    (args[3].clone(), &args[4])
+                } else {
+                    match configured_keypair(&parsed) {
+                        Ok(Some(keypair_path)) => (keypair_path, &args[3]),
+                        Ok(None) => {
+                            eprintln!("{}: {}", i18n::text("error"), i18n::text("fund-needs-amount"));
+                            eprintln!("{}", i18n::message("no-funding-wallet", &[("path", &config_path(&parsed).display())]));
+                            exit(EXIT_INVALID_INPUT);
+                        }
+                        Err(e) => {
+                            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                            exit(exit_code(&e));
                         }
-                        amount
-                    },
-                    Err(_) => {
-                        eprintln!("Error: Invalid amount: {}", amount_str);
-                        eprintln!("Amount must be a valid number (e.g., 1.5 for 1.5 SOL)");
-                        std::process::exit(1);
                     }
                 };
+                let keypair_path = keypair_path.as_str();
+                let signers = FundingSigners {
+                    funder: keypair_path.to_string(),
+                    fee_payer: parsed.value("fee-payer").map(str::to_string),
+                    keypairs: parsed.values("keypair").into_iter().map(str::to_string).collect(),
+                };
                 
-                let amount_lamports = (amount_sol * 1_000_000_000.0) as u64;
-                println!("Validator pubkey: {}", address);
-                println!("PDA Address: {}", deposit_key);
-                println!("Funding PDA with {} SOL ({} lamports) from keypair: {}", amount_sol, amount_lamports, keypair_path);
-                println!("Checking validator gossip status before funding...");
-                
-                match pda_fund_address(&validator_id, keypair_path, amount_sol, None).await {
-                    Ok(signature) => {
-                        println!("Transaction successful!");
-                        println!("Transaction signature: {}", signature);
-                        println!("Transferred {} SOL ({} lamports) to PDA", amount_sol, amount_lamports);
+                let amount = match parse_amount(amount_str) {
+                    Ok(amount) => amount,
+                    Err(e) => {
+                        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                        eprintln!("{}", i18n::text("amount-hint"));
+                        exit(exit_code(&e));
                     }
+                };
+                
+                let nonce = match nonce_config(&parsed) {
+                    Ok(nonce) => nonce,
                     Err(e) => {
-                        eprintln!("Error funding PDA: {}", e);
-                        std::process::exit(1);
+                        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                        exit(exit_code(&e));
                     }
+                };
+                
+                // Air-gapped signing: nothing is read from or sent to RPC
+                if parsed.has("sign-only") {
+                    sign_funding_only(&parsed, &validator_id, &deposit_key, &signers, amount, nonce.as_ref(), output);
+                    return;
                 }
-            }
-        }
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::str::FromStr;
-
-    #[test]
-    fn test_generate_deposit_pda() {
-        // Test validator ID
-        let validator_id = Pubkey::from_str("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
-            .expect("Failed to parse test validator ID");
-        
-        let deposit_pda = generate_deposit_pda(&validator_id);
-        
-        // Check that PDA is not equal to the default key
-        assert_ne!(deposit_pda, Pubkey::default());
-        
-        // Check that PDA is deterministic (same result for same input)
-        let deposit_pda2 = generate_deposit_pda(&validator_id);
-        assert_eq!(deposit_pda, deposit_pda2);
-    }
-
-    #[test]
-    fn test_generate_deposit_pda_different_validators() {
-        let validator1 = Pubkey::from_str("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
-            .expect("Failed to parse validator1");
-        let validator2 = Pubkey::from_str("11111111111111111111111111111112")
-            .expect("Failed to parse validator2");
-        
-        let deposit_pda1 = generate_deposit_pda(&validator1);
-        let deposit_pda2 = generate_deposit_pda(&validator2);
-        
-        // Different validators should generate different PDAs
-        assert_ne!(deposit_pda1, deposit_pda2);
-    }
+                
+                // The funding wallet is loaded again to sign the receipt once the transfer is confirmed
+                if parsed.has("receipt") && !SignerSource::parse(&signers.funder).is_unattended() {
+                    eprintln!("{}: {}", i18n::text("error"), i18n::message("receipt-needs-keypair", &[("signer", &signers.funder)]));
+                    exit(EXIT_INVALID_INPUT);
+                }
+                
+                if let Some(multisig) = parsed.value("squads") {
+                    propose_squads_funding(&parsed, &validator_id, multisig, &signers, amount, &policy, output).await;
+                    return;
+                }
+                
+                if output == OutputFormat::Text {
+                    println!("{}", i18n::message("validator-pubkey-label", &[("validator", &validator_label)]));
+                    println!("{}", i18n::message("pda-address", &[("pda", &deposit_key)]));
+                    print_pda_links(&parsed, &deposit_key);
+                    match amount {
+                        Amount::All => println!("{}", i18n::message("funding-all", &[("keypair", &keypair_path)])),
+                        Amount::Lamports(_) => println!("{}", i18n::message("funding-amount", &[("amount", &amount), ("keypair", &keypair_path)])),
+                    }
+                    if let Some(fee_payer) = &signers.fee_payer {
+                        println!("{}", i18n::message("fee-payer", &[("payer", fee_payer)]));
+                    }
+                    if let Some(nonce) = &nonce {
+                        println!("{}", i18n::message("durable-nonce", &[("account", &nonce.account)]));
+                    }
+                    info!("Checking validator status before funding...");
+                }
+                
+                let idempotency_key = parsed.value("idempotency-key");
+                let result = match epoch_target {
+                    Some(target) => fund_at_epoch(&parsed, &validator_id, &signers, amount, &policy, nonce.as_ref(), target).await,
+                    None => fund_with_confirmation(&parsed, &validator_id, &signers, amount, &policy, nonce.as_ref(), idempotency_key).await,
+                };
+                push_run_metrics(&parsed, address, &funding_metrics(&deposit_key, result.as_ref().ok(), unix_timestamp())).await;
+                
+                let event = match &result {
+                    Ok(outcome) => NotifyEvent::FundingLanded {
+                        validator: address.to_string(),
+                        pda: deposit_key.to_string(),
+                        amount_lamports: outcome.amount_lamports,
+                        signature: outcome.signature.clone(),
+                    },
+                    Err(e) => NotifyEvent::FundingFailed {
+                        validator: address.to_string(),
+                        pda: deposit_key.to_string(),
+                        error: e.to_string(),
+                    },
+                };
+                notify(notify_config(&parsed).as_ref(), &event).await;
+                
+                let mut fields = serde_json::json!({ "validator": address, "pda": deposit_key.to_string() });
+                if let Some(explorer) = explorer(&parsed) {
+                    fields["pda_url"] = explorer.account_url(&deposit_key.to_string()).into();
+                }
+                match result {
+                    Ok(outcome) if output == OutputFormat::Json => {
+                        let mut warnings = outcome.warnings.clone();
+                        warnings.extend(verify_rotated_wallet(&outcome.payer, &outcome.signature, output).await);
+                        let mut fields = fields;
+                        fields["payer"] = outcome.payer.clone().into();
+                        fields["signature"] = outcome.signature.clone().into();
+                        add_signature_url(&parsed, &mut fields, &outcome.signature);
+                        fields["amount_lamports"] = outcome.amount_lamports.into();
+                        fields["fee_lamports"] = outcome.fee_lamports.into();
+                        fields["account_created"] = outcome.creates_account.into();
+                        if let Some(quote) = fiat_quote(&parsed, outcome.amount_lamports, None).await {
+                            fields["fiat"] = serde_json::json!(quote);
+                        }
+                        if !outcome.policy_checks.is_empty() {
+                            fields["policy"] = serde_json::json!(outcome.policy_checks);
+                        }
+                        if let Some(memo) = &outcome.memo {
+                            fields["memo"] = memo.clone().into();
+                        }
+                        if outcome.creates_account {
+                            fields["creation_slot"] = serde_json::json!(announce_deposit_creation(&deposit_key, &outcome, output).await);
+                        }
+                        if let Some(path) = parsed.value("receipt") {
+                            match write_receipt(path, &validator_id, &signers, &outcome).await {
+                                Ok(_) => fields["receipt"] = path.into(),
+                                Err(e) => warnings.push(Warning::new(WARN_RECEIPT_NOT_WRITTEN, format!("Receipt not written to {}: {}", path, e))),
+                            }
+                        }
+                        if parsed.has("verify-delta") {
+                            let checked = verify_funding_delta(&validator_id, &outcome).await.and_then(|delta| {
+                                fields["balance_delta"] = serde_json::json!(delta);
+                                delta.check()
+                            });
+                            if let Err(e) = checked {
+                                println!("{}", JsonReport::failure(operation, fields, warnings, &e).to_json());
+                                exit(exit_code(&e));
+                            }
+                        }
+                        println!("{}", JsonReport::success(operation, fields, warnings).to_json());
+                    }
+                    Ok(outcome) => {
+                        print_policy_checks(&outcome.policy_checks);
+                        if outcome.warnings.is_empty() {
+                            println!("{}", i18n::message("status-pass", &[("validator", &validator_id)]));
+                        } else {
+                            for warning in &outcome.warnings {
+                                println!("⚠ {}", i18n::warning_text(warning));
+                            }
+                            println!("{}", i18n::message("status-warn-funded", &[("validator", &validator_id)]));
+                        }
+                        println!("{}", i18n::text("transaction-successful"));
+                        println!("{}", i18n::message("transaction-signature", &[("signature", &outcome.signature)]));
+                        print_signature_link(&parsed, &outcome.signature);
+                        println!("{}", i18n::message("transferred", &[("amount", &Amount::Lamports(outcome.amount_lamports)), ("fee", &outcome.fee_lamports)]));
+                        if let Some(quote) = fiat_quote(&parsed, outcome.amount_lamports, None).await {
+                            println!("{}", quote);
+                        }
+                        if let Some(memo) = &outcome.memo {
+                            println!("{}", i18n::message("memo", &[("memo", memo)]));
+                        }
+                        if outcome.creates_account {
+                            announce_deposit_creation(&deposit_key, &outcome, output).await;
+                        }
+                        if let Some(path) = parsed.value("receipt") {
+                            match write_receipt(path, &validator_id, &signers, &outcome).await {
+                                Ok(receipt) => println!("{}", i18n::message("receipt-written", &[("slot", &receipt.slot), ("path", &path)])),
+                                Err(e) => println!("⚠ {}", i18n::message("receipt-not-written", &[("path", &path), ("error", &i18n::error_text(&e))])),
+                            }
+                        }
+                        verify_rotated_wallet(&outcome.payer, &outcome.signature, output).await;
+                        if parsed.has("verify-delta") {
+                            match verify_funding_delta(&validator_id, &outcome).await.and_then(|delta| delta.check().map(|_| delta)) {
+                                Ok(delta) => println!(
+                                    "{}",
+                                    i18n::message(
+                                        "delta-verified",
+                                        &[("amount", &Amount::Lamports(outcome.amount_lamports)), ("pre", &delta.pre_balance), ("post", &delta.post_balance)],
+                                    )
+                                ),
+                                Err(e) => {
+                                    eprintln!("✗ {}", i18n::error_text(&e));
+                                    exit(exit_code(&e));
+                                }
+                            }
+                        }
+                    }
+                    Err(e) if output == OutputFormat::Json => {
+                        println!("{}", JsonReport::failure(operation, fields, Vec::new(), &e).to_json());
+                        exit(exit_code(&e));
+                    }
+                    Err(e) => {
+                        eprintln!("{}", i18n::message("funding-failed", &[("error", &i18n::error_text(&e))]));
+                        // Sending is not idempotent, so transient failures are reported rather than retried
+                        if e.is_retryable() {
+                            eprintln!("{}", i18n::text("funding-retry-hint"));
+                        }
+                        print_trace_id();
+                        exit(exit_code(&e));
+                    }
+                }
+            } else if operation == "pda-watch" {
+                let interval_secs = match args.get(3).map(|value| value.parse::<u64>()) {
+                    None => 60,
+                    Some(Ok(interval)) if interval > 0 => interval,
+                    Some(_) => {
+                        eprintln!("{}: {}", i18n::text("error"), i18n::message("invalid-interval", &[("value", &args[3])]));
+                        eprintln!("{}", i18n::text("interval-hint"));
+                        exit(EXIT_INVALID_INPUT);
+                    }
+                };
+                
+                let max_outflow_sol_per_hour = match args.get(4).map(|value| value.parse::<f64>()) {
+                    None => None,
+                    Some(Ok(threshold)) if threshold >= 0.0 => Some(threshold),
+                    Some(_) => {
+                        eprintln!("{}: {}", i18n::text("error"), i18n::message("invalid-outflow-threshold", &[("value", &args[4])]));
+                        eprintln!("{}", i18n::text("outflow-threshold-hint"));
+                        exit(EXIT_INVALID_INPUT);
+                    }
+                };
+                
+                let initialization_timeout_secs = match parsed.value("init-timeout").map(str::parse::<u64>) {
+                    None => DEFAULT_INITIALIZATION_TIMEOUT_SECS,
+                    Some(Ok(timeout)) => timeout,
+                    Some(Err(_)) => {
+                        eprintln!("{}: {}", i18n::text("error"), i18n::message("invalid-flag", &[("flag", &"--init-timeout"), ("error", &i18n::text("must-be-seconds"))]));
+                        exit(EXIT_INVALID_INPUT);
+                    }
+                };
+                
+                let metrics_port = match parsed.parsed_value::<u16>("metrics-port") {
+                    Ok(port) => port,
+                    Err(e) => {
+                        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                        exit(exit_code(&e));
+                    }
+                };
+                
+                let notify_config = notify_config(&parsed);
+                let low_balance_lamports = match notify_config.as_ref().map(NotifyConfig::low_balance_lamports).transpose() {
+                    Ok(threshold) => threshold.flatten(),
+                    Err(e) => {
+                        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                        exit(exit_code(&e));
+                    }
+                };
+                
+                println!("{}", i18n::message("validator-pubkey-label", &[("validator", &validator_label)]));
+                println!("{}", i18n::message("pda-address", &[("pda", &deposit_key)]));
+                info!("Watching PDA balance every {} seconds (Ctrl-C to stop)", interval_secs);
+                if let Some(threshold) = max_outflow_sol_per_hour {
+                    info!("Alert threshold: {} SOL/hour outflow", threshold);
+                }
+                if let Some(threshold) = low_balance_lamports {
+                    info!("Notifying when the balance drops below {}", Amount::Lamports(threshold));
+                }
+                
+                let exporter = match metrics_port {
+                    Some(port) => {
+                        let registry = Arc::new(MetricsRegistry::new());
+                        registry.register_counters();
+                        match start_metrics_server(port, Arc::clone(&registry)).await {
+                            Ok(address) => info!("Serving Prometheus metrics at http://{}/metrics", address),
+                            Err(e) => {
+                                eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                                exit(exit_code(&e));
+                            }
+                        }
+                        Some(WatchExporter { registry, validator: address.to_string(), payer: configured_payer(&parsed) })
+                    }
+                    None => None,
+                };
+                
+                watch_pda_balance(
+                    &deposit_key,
+                    interval_secs,
+                    max_outflow_sol_per_hour,
+                    initialization_timeout_secs,
+                    exporter.as_ref(),
+                    notify_config.as_ref(),
+                ).await;
+            } else if operation == "validator-status" && output == OutputFormat::Json {
+                let fields = serde_json::json!({ "validator": address });
+                let status = match retry_transient(RPC_RETRY_ATTEMPTS, || get_policy_status(&validator_id, &policy, &rpc)).await {
+                    Ok(status) => status,
+                    Err(e) => {
+                        println!("{}", JsonReport::failure(operation, fields, Vec::new(), &e).to_json());
+                        exit(exit_code(&e));
+                    }
+                };
+                
+                // WARN reasons are soft issues; FAIL reasons are part of the verdict
+                let (verdict, reasons) = status.evaluate(&policy);
+                let mut warnings = check_rpc_health(&rpc).await.into_iter().collect::<Vec<_>>();
+                let mut fields = fields;
+                fields["in_gossip"] = serde_json::json!(status.in_gossip);
+                fields["vote_account"] = serde_json::json!(status.vote_account);
+                fields["epoch"] = status.epoch.into();
+                fields["current_slot"] = status.current_slot.into();
+                if status.uptime.is_some() {
+                    fields["uptime"] = serde_json::json!(status.uptime);
+                }
+                fields["verdict"] = verdict.to_string().into();
+                let checks = status.policy_checks(&policy);
+                if !checks.is_empty() {
+                    fields["policy"] = serde_json::json!(checks);
+                }
+                // Not in gossip exits with its own code, other FAIL verdicts with 1
+                let fail_exit_code = cancellation(&reasons, "Validator status FAIL").exit_code();
+                if verdict == Verdict::Fail {
+                    fields["reasons"] = serde_json::json!(reasons);
+                } else {
+                    warnings.extend(reasons);
+                }
+                if verdict == Verdict::Fail {
+                    println!("{}", JsonReport::success(operation, fields, warnings).to_json());
+                    exit(fail_exit_code);
+                }
+                print_json_success(operation, fields, warnings, policy.strict);
+            } else if operation == "validator-status" {
+                let labels = address_labels(&parsed);
+                println!("{}", i18n::message("validator-pubkey-label", &[("validator", &validator_label)]));
+                
+                let status = match retry_transient(RPC_RETRY_ATTEMPTS, || get_policy_status(&validator_id, &policy, &rpc)).await {
+                    Ok(status) => status,
+                    Err(e) => {
+                        eprintln!("{}", i18n::message("status-failed", &[("error", &i18n::error_text(&e))]));
+                        exit(exit_code(&e));
+                    }
+                };
+                
+                match status.in_gossip {
+                    Some(true) => println!("{}", i18n::text("gossip-present")),
+                    Some(false) => println!("{}", i18n::text("gossip-not-found")),
+                    None => println!("{}", i18n::text("gossip-not-checked")),
+                }
+                match &status.vote_account {
+                    Some(vote_account) => {
+                        println!("{}", i18n::message("vote-account", &[("account", &labels.format_str(&vote_account.vote_pubkey))]));
+                        println!("{}", i18n::text(if vote_account.delinquent { "delinquent-yes" } else { "delinquent-no" }));
+                        println!("{}", i18n::message("activated-stake", &[("stake", &Amount::Lamports(vote_account.activated_stake))]));
+                        println!("{}", i18n::message("commission", &[("commission", &vote_account.commission)]));
+                        let ago = status.current_slot.saturating_sub(vote_account.last_vote);
+                        println!("{}", i18n::message("last-vote", &[("slot", &vote_account.last_vote), ("ago", &ago)]));
+                    }
+                    None => println!("{}", i18n::text("vote-account-none")),
+                }
+                println!("{}", i18n::message("epoch", &[("epoch", &status.epoch), ("slot", &status.current_slot)]));
+                print_policy_checks(&status.policy_checks(&policy));
+                
+                let (verdict, mut reasons) = status.evaluate(&policy);
+                println!("{}", i18n::message("verdict", &[("verdict", &verdict)]));
+                for reason in &reasons {
+                    println!("  - {}", i18n::warning_text(reason));
+                }
+                if verdict == Verdict::Fail {
+                    exit(cancellation(&reasons, "Validator status FAIL").exit_code());
+                }
+                if policy.strict {
+                    reasons.extend(check_rpc_health(&rpc).await);
+                    exit_if_strict(policy.strict, &reasons);
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::localize(&e));
+            exit(EXIT_INVALID_INPUT);
+        }
+    }
+}
 
-    #[test]
-    fn test_parse_pubkey_valid() {
-        let valid_address = "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL";
-        let result = parse_pubkey(valid_address);
+/// Polls the PDA balance until Ctrl-C, printing the rate of change and outflow alerts
+async fn watch_pda_balance(
+    deposit_key: &Pubkey,
+    interval_secs: u64,
+    max_outflow_sol_per_hour: Option<f64>,
+    initialization_timeout_secs: u64,
+    exporter: Option<&WatchExporter>,
+    notify_config: Option<&NotifyConfig>,
+) {
+    let rpc = rpc_client(None);
+    let mut tracker = BalanceRateTracker::new(DEFAULT_RATE_WINDOW_SECS);
+    let mut lifecycle = DepositLifecycle::new(initialization_timeout_secs);
+    let mut low_balance = notify_config
+        .and_then(|config| config.low_balance_lamports().ok().flatten())
+        .map(BalanceThreshold::new);
+    // With --backend geyser changes arrive as they happen; the interval only re-reads the balance in between
+    let mut updates = match rpc::settings().geyser_endpoint.as_deref() {
+        Some(endpoint) => match geyser::subscribe_account(endpoint, deposit_key, rpc::settings().commitment).await {
+            Ok(updates) => Some(updates),
+            Err(e) => {
+                warn!(error = %e, "Unable to stream PDA account changes, polling instead");
+                None
+            }
+        },
+        None => None,
+    };
+    let mut streamed = None;
+    
+    loop {
+        let timestamp = unix_timestamp();
+        if let Some(exporter) = exporter {
+            exporter.update(deposit_key).await;
+        }
+        
+        let account = match streamed.take() {
+            Some(account) => Ok(account),
+            None => retry_transient(RPC_RETRY_ATTEMPTS, || get_deposit_account(deposit_key, &rpc)).await,
+        };
+        match account {
+            Ok(account) => {
+                let balance = account.lamports;
+                tracker.record(timestamp, balance);
+                if let Some(exporter) = exporter {
+                    exporter.registry.set(
+                        Metric::gauge("dz_validator_pda_balance_lamports", "PDA balance in lamports", balance as f64)
+                            .with_label("pda", &deposit_key.to_string())
+                            .with_label("validator", &exporter.validator),
+                    );
+                }
+                let sol_balance = balance as f64 / 1_000_000_000.0;
+                
+                match (tracker.last_delta(), tracker.net_sol_per_hour(), tracker.outflow_sol_per_hour()) {
+                    (Some(delta), Some(net_rate), Some(outflow_rate)) => {
+                        println!(
+                            "[{}] {}",
+                            timestamp,
+                            i18n::message(
+                                "watch-balance-rate",
+                                &[
+                                    ("lamports", &balance),
+                                    ("sol", &sol_balance),
+                                    ("delta", &format!("{:+}", delta)),
+                                    ("net", &format!("{:+.6}", net_rate)),
+                                    ("outflow", &format!("{:.6}", outflow_rate)),
+                                ],
+                            )
+                        );
+                    }
+                    _ => {
+                        println!("[{}] {}", timestamp, i18n::message("pda-balance", &[("lamports", &balance), ("sol", &sol_balance)]));
+                    }
+                }
+                
+                if let Some(alert) = max_outflow_sol_per_hour.and_then(|threshold| tracker.outflow_alert(threshold)) {
+                    println!("⚠ {}: {}", i18n::text("alert"), alert);
+                }
+                
+                if let Some(event) = low_balance.as_mut().and_then(|threshold| threshold.observe(&deposit_key.to_string(), balance)) {
+                    println!("⚠ {}", event);
+                    notify(notify_config, &event).await;
+                }
+                
+                for event in lifecycle.observe(&account, timestamp) {
+                    match event {
+                        DepositEvent::Created { .. } => println!("🎉 {}", event),
+                        DepositEvent::Initialized { .. } => println!("✓ {}", event),
+                        DepositEvent::NotInitialized { .. } => println!("⚠ {}: {}", i18n::text("alert"), event),
+                    }
+                }
+            }
+            Err(e) => {
+                warn!(timestamp, error = %e, "Error getting PDA balance");
+                if let Some(exporter) = exporter {
+                    exporter.registry.increment(RPC_ERRORS_TOTAL, "RPC requests that failed after retries");
+                }
+            }
+        }
         
-        assert!(result.is_ok());
-        let pubkey = result.unwrap();
-        assert_eq!(pubkey.to_string(), valid_address);
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(interval_secs)) => {}
+            update = next_account_update(&mut updates) => match update {
+                Some(Ok(account)) => streamed = Some(account),
+                Some(Err(e)) => {
+                    warn!(error = %e, "PDA account stream failed, polling instead");
+                    updates = None;
+                }
+                None => {
+                    warn!("PDA account stream ended, polling instead");
+                    updates = None;
+                }
+            },
+            _ = tokio::signal::ctrl_c() => {
+                info!("Stopped watching PDA balance");
+                return;
+            }
+        }
     }
+}
 
-    #[test]
-    fn test_parse_pubkey_invalid() {
-        let invalid_address = "invalid_address";
-        let result = parse_pubkey(invalid_address);
-        
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert!(error.contains("Invalid pubkey format"));
+/// Waits for the next streamed account change, or forever without a stream
+async fn next_account_update(updates: &mut Option<AccountUpdates>) -> Option<Result<DepositAccount, Error>> {
+    match updates {
+        Some(updates) => updates.next().await,
+        None => std::future::pending().await,
     }
+}
+
+/// Metrics served by `pda-watch --metrics-port`
+struct WatchExporter {
+    registry: Arc<MetricsRegistry>,
+    validator: String,
+    payer: Option<Pubkey>,
+}
 
-    #[test]
-    fn test_parse_pubkey_empty() {
-        let empty_address = "";
-        let result = parse_pubkey(empty_address);
+impl WatchExporter {
+    /// Refreshes the payer balance and the last funding time (from the audit log)
+    async fn update(&self, deposit_key: &Pubkey) {
+        let rpc = rpc_client(None);
+        if let Some(payer) = &self.payer {
+            match retry_transient(RPC_RETRY_ATTEMPTS, || get_account_balance(payer, &rpc)).await {
+                Ok(balance) => self.registry.set(
+                    Metric::gauge("dz_validator_pda_payer_balance_lamports", "Funding wallet balance in lamports", balance as f64)
+                        .with_label("payer", &payer.to_string()),
+                ),
+                Err(_) => self.registry.increment(RPC_ERRORS_TOTAL, "RPC requests that failed after retries"),
+            }
+        }
         
-        assert!(result.is_err());
+        let pda = format!("PDA {}", deposit_key);
+        let last_funding = audit::read_entries(&audit::audit_log_path())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| entry.event == audit::EVENT_PDA_FUND && entry.message.as_deref() == Some(pda.as_str()))
+            .map(|entry| entry.timestamp)
+            .max();
+        if let Some(timestamp) = last_funding {
+            self.registry.set(
+                Metric::gauge("dz_validator_pda_last_funding_timestamp_seconds", "Unix time of the last funding of the PDA", timestamp as f64)
+                    .with_label("pda", &deposit_key.to_string()),
+            );
+        }
     }
+}
 
-    #[test]
-    fn test_parse_pubkey_whitespace_only() {
-        let whitespace_address = "   ";
-        let result = parse_pubkey(whitespace_address);
-        
-        assert!(result.is_err());
+/// Returns the configured funding wallet if it can be read without prompting (plain keypair file or `env://`)
+fn configured_payer(parsed: &ParsedArgs) -> Option<Pubkey> {
+    let source = configured_keypair(parsed).ok()??;
+    match SignerSource::parse(&source) {
+        SignerSource::File(path) if keystore::is_encrypted_file(Path::new(&path)) => None,
+        SignerSource::File(path) => load_keypair(&path).ok().map(|keypair| keypair.pubkey()),
+        SignerSource::Env(_) => load_signer(&source).ok().map(|signer| signer.pubkey()),
+        _ => None,
     }
+}
 
-    #[test]
-    fn test_revenue_distribution_program_id() {
-        // Check that the program constant is correctly defined
-        let expected_program_id = "dzrevZC94tBLwuHw1dyynZxaXTWyp7yocsinyEVPtt4";
-        assert_eq!(REVENUE_DISTRIBUTION_PROGRAM_ID.to_string(), expected_program_id);
+/// Records a sent funding and its fee, or a failed funding attempt, in the audit log
+///
+/// Cancelled fundings (declined confirmation, failed pre-flight checks) sent nothing and are not recorded.
+/// The idempotency key of the run is kept with the entry, so a retry with the same key is refused.
+fn record_funding(
+    validator_id: &Pubkey,
+    deposit_key: &Pubkey,
+    result: &Result<FundingOutcome, Error>,
+    attempt: Option<&FundingAttempt>,
+    idempotency_key: Option<&str>,
+) {
+    let Some(mut entry) = audit::funding_entry(validator_id, deposit_key, result, attempt) else {
+        return;
+    };
+    entry.idempotency_key = idempotency_key.map(str::to_string);
+    if let Err(e) = audit::append_entry(&audit::audit_log_path(), &entry) {
+        warn!("Unable to write audit log: {}", e);
     }
+}
 
-    #[test]
-    fn test_deposit_pda_seed() {
-        let validator_id = Pubkey::from_str("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
-            .expect("Failed to parse test validator ID");
-        
-        let deposit_pda = generate_deposit_pda(&validator_id);
-        
-        // Check that PDA is actually created with correct seeds
-        let (expected_pda, bump_seed) = Pubkey::find_program_address(
-            &[b"solana_validator_deposit", validator_id.as_ref()],
-            &REVENUE_DISTRIBUTION_PROGRAM_ID
-        );
-        
-        assert_eq!(deposit_pda, expected_pda);
-        assert!(bump_seed > 0); // bump seed should be greater than 0
+/// Confirms a funding that created the deposit PDA, records the creation in the audit log and reports its slot
+///
+/// Returns the creation slot, or None if the transaction could not be confirmed.
+async fn announce_deposit_creation(deposit_key: &Pubkey, outcome: &FundingOutcome, output: OutputFormat) -> Option<u64> {
+    let rpc = rpc_client(None);
+    if output == OutputFormat::Text {
+        info!("PDA did not exist before this transfer - waiting for confirmation...");
+    }
+    let started = Instant::now();
+    let slot = match confirmed_slot(&outcome.signature, Duration::from_secs(DEFAULT_CONFIRMATION_TIMEOUT_SECS), &rpc).await {
+        Ok(slot) => slot,
+        Err(e) => {
+            if output == OutputFormat::Text {
+                println!("⚠ {}", i18n::message("creation-not-confirmed", &[("error", &i18n::error_text(&e))]));
+            }
+            return None;
+        }
+    };
+    
+    let event = DepositEvent::Created { slot, lamports: outcome.amount_lamports };
+    let entry = AuditEntry {
+        payer: Some(outcome.payer.clone()),
+        amount_lamports: Some(outcome.amount_lamports),
+        fee_lamports: Some(outcome.fee_lamports),
+        signature: Some(outcome.signature.clone()),
+        confirmation_ms: Some(started.elapsed().as_millis() as u64),
+        message: Some(format!("{}: {}", deposit_key, event)),
+        ..AuditEntry::new(audit::EVENT_DEPOSIT_CREATED, audit::STATUS_SUCCESS)
+    };
+    if let Err(e) = audit::append_entry(&audit::audit_log_path(), &entry) {
+        warn!("Unable to write audit log: {}", e);
     }
+    
+    if output == OutputFormat::Text {
+        println!("🎉 {}", event);
+        // The revenue distribution program takes ownership later; pda-watch alerts if it never does
+        if let Ok(account) = get_deposit_account(deposit_key, &rpc).await {
+            if account.state == DepositAccountState::SystemOwned {
+                println!("{}", i18n::message("deposit-not-initialized", &[("seconds", &DEFAULT_INITIALIZATION_TIMEOUT_SECS)]));
+            } else {
+                println!("{}", i18n::message("deposit-account-state", &[("state", &account.state)]));
+            }
+        }
+    }
+    Some(slot)
+}
 
+/// Signs a funding transaction without RPC access and prints it as base64 (`--sign-only`)
+///
+/// In text mode only the transaction goes to stdout, so it can be redirected to a file for `broadcast`.
+fn sign_funding_only(
+    parsed: &ParsedArgs,
+    validator_id: &Pubkey,
+    deposit_key: &Pubkey,
+    signers: &FundingSigners,
+    amount: Amount,
+    nonce: Option<&NonceConfig>,
+    output: OutputFormat,
+) {
+    let result = parsed
+        .value("blockhash")
+        .ok_or_else(|| Error::InvalidInput("--sign-only requires --blockhash <blockhash>".to_string()))
+        .and_then(parse_blockhash)
+        .and_then(|blockhash| {
+            if signers.is_interactive() {
+                info!("Approve the transaction on your hardware wallet when prompted");
+            }
+            sign_funding_offline(validator_id, signers, amount, blockhash, nonce)
+        });
+    
+    let mut fields = serde_json::json!({ "validator": validator_id.to_string(), "pda": deposit_key.to_string() });
+    match result {
+        Ok(signed) if output == OutputFormat::Json => {
+            fields["payer"] = signed.payer.into();
+            fields["signature"] = signed.signature.into();
+            fields["amount_lamports"] = signed.amount_lamports.into();
+            fields["transaction"] = signed.transaction.into();
+            fields["missing_signers"] = signed.missing_signers.into();
+            let warning = Warning::new(WARN_STATUS_NOT_CHECKED, "Validator status was not checked: the transaction was signed offline");
+            println!("{}", JsonReport::success("pda-fund-address", fields, vec![warning]).to_json());
+        }
+        Ok(signed) => {
+            eprintln!("{}", i18n::message("validator-pubkey-label", &[("validator", &validator_id)]));
+            eprintln!("{}", i18n::message("pda-address", &[("pda", &deposit_key)]));
+            eprintln!("{}", i18n::message("payer", &[("payer", &address_labels(parsed).format_str(&signed.payer))]));
+            eprintln!("{}", i18n::message("amount", &[("amount", &Amount::Lamports(signed.amount_lamports))]));
+            match &signed.signature {
+                Some(signature) => eprintln!("{}", i18n::message("transaction-signature", &[("signature", signature)])),
+                None => eprintln!("{}", i18n::text("signature-pending-fee-payer")),
+            }
+            if !signed.missing_signers.is_empty() {
+                eprintln!("{}", i18n::message("partially-signed", &[("signers", &signed.missing_signers.join(", "))]));
+                eprintln!("{}", i18n::text("partially-signed-hint"));
+            }
+            match nonce {
+                Some(nonce) => eprintln!("{}", i18n::message("offline-nonce-hint", &[("account", &nonce.account)])),
+                None => eprintln!("{}", i18n::text("offline-blockhash-hint")),
+            }
+            println!("{}", signed.transaction);
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("pda-fund-address", fields, Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}", i18n::message("signing-failed", &[("error", &i18n::error_text(&e))]));
+            exit(exit_code(&e));
+        }
+    }
+}
 
-    #[tokio::test]
-    async fn test_get_account_balance_with_custom_rpc() {
-        let test_address = Pubkey::from_str("11111111111111111111111111111112")
-            .expect("Failed to parse test address");
-        
-        // Test with a custom RPC URL (this might fail if the URL is invalid, but we're testing the function)
-        let result = get_account_balance(&test_address, Some("https://api.mainnet-beta.solana.com")).await;
-        assert!(result.is_ok());
+/// Creates a Squads proposal that funds the PDA from the multisig vault (`--squads`) and prints its address
+async fn propose_squads_funding(
+    parsed: &ParsedArgs,
+    validator_id: &Pubkey,
+    multisig: &str,
+    signers: &FundingSigners,
+    amount: Amount,
+    policy: &FundingPolicy,
+    output: OutputFormat,
+) {
+    let result = async {
+        let multisig = multisig.parse::<Pubkey>().map_err(|_| Error::InvalidInput(format!("Invalid --squads multisig address '{}'", multisig)))?;
+        let Amount::Lamports(amount_lamports) = amount else {
+            return Err(Error::InvalidInput("--squads needs an exact amount; ALL is not supported".to_string()));
+        };
+        let rpc = rpc_client(None);
+        let prepared = prepare_squads_proposal(validator_id, &multisig, amount_lamports, signers, policy, &rpc).await?;
+        confirm_squads_proposal(parsed, &prepared)?;
+        if signers.is_interactive() {
+            info!("Approve the transaction on your hardware wallet when prompted");
+        }
+        send_squads_proposal(prepared, &rpc).await
+    }
+    .await;
+    
+    let mut fields = serde_json::json!({ "validator": validator_id.to_string(), "pda": generate_deposit_pda(validator_id).to_string() });
+    match result {
+        Ok(proposal) if output == OutputFormat::Json => {
+            fields["multisig"] = proposal.multisig.into();
+            fields["vault"] = proposal.vault.into();
+            fields["creator"] = proposal.creator.into();
+            fields["transaction_index"] = proposal.transaction_index.into();
+            fields["transaction"] = proposal.transaction.into();
+            fields["proposal"] = proposal.proposal.into();
+            fields["threshold"] = proposal.threshold.into();
+            fields["amount_lamports"] = proposal.amount_lamports.into();
+            fields["fee_lamports"] = proposal.fee_lamports.into();
+            fields["signature"] = proposal.signature.clone().into();
+            add_signature_url(parsed, &mut fields, &proposal.signature);
+            println!("{}", JsonReport::success("pda-fund-address", fields, proposal.warnings).to_json());
+        }
+        Ok(proposal) => {
+            for warning in &proposal.warnings {
+                println!("⚠ {}", i18n::warning_text(warning));
+            }
+            println!("{}", i18n::message("proposal-created", &[("threshold", &proposal.threshold)]));
+            println!("{}", i18n::message("proposal", &[("proposal", &proposal.proposal)]));
+            println!("{}", i18n::message("transaction-index", &[("index", &proposal.transaction_index)]));
+            println!("{}", i18n::message("vault", &[("vault", &proposal.vault)]));
+            println!("{}", i18n::message("transaction-signature", &[("signature", &proposal.signature)]));
+            print_signature_link(parsed, &proposal.signature);
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("pda-fund-address", fields, Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}", i18n::message("proposal-failed", &[("error", &i18n::error_text(&e))]));
+            print_trace_id();
+            exit(exit_code(&e));
+        }
     }
+}
 
-    #[test]
-    fn test_pda_fund_address_parameters() {
-        let validator_id = Pubkey::from_str("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
-            .expect("Failed to parse test validator ID");
-        
-        // Test that the function signature is correct
-        // This is a compile-time test to ensure the function exists with correct parameters
-        let _validator_id = &validator_id;
-        let _keypair_path = "test_keypair.json";
-        let _amount_sol = 1.0f64;
-        let _rpc_url = Some("https://api.mainnet-beta.solana.com");
-        
-        // The function signature should be:
-        // pda_fund_address(validator_id, keypair_path, amount_sol, rpc_url)
-        // This test ensures the function can be called with the expected parameters
-        assert!(true); // Placeholder assertion
+/// Prints what a Squads proposal will do and asks for confirmation unless `--yes` is given
+fn confirm_squads_proposal(parsed: &ParsedArgs, prepared: &PreparedSquadsProposal) -> Result<(), Error> {
+    eprintln!("{}", i18n::text("proposal-summary"));
+    eprintln!("{}", i18n::message("summary-multisig", &[("multisig", &prepared.multisig), ("threshold", &prepared.threshold)]));
+    eprintln!("{}", i18n::message("summary-vault", &[("vault", &prepared.vault)]));
+    eprintln!("{}", i18n::message("summary-vault-balance", &[("amount", &Amount::Lamports(prepared.vault_balance))]));
+    eprintln!("{}", i18n::message("summary-amount", &[("amount", &Amount::Lamports(prepared.amount_lamports))]));
+    eprintln!("{}", i18n::message("summary-proposal-fee", &[("fee", &prepared.fee_lamports)]));
+    eprintln!("{}", i18n::message("summary-transaction-index", &[("index", &prepared.transaction_index)]));
+    if assume_yes(parsed) {
+        return Ok(());
     }
+    let prompt = i18n::message(
+        "confirm-proposal",
+        &[("amount", &format_sol(prepared.amount_lamports)), ("vault", &prepared.vault), ("pda", &prepared.pda)],
+    );
+    if !confirm(&prompt) {
+        return Err(Error::FundingCancelled("Not confirmed; nothing was sent (use --yes in scripts)".to_string()));
+    }
+    Ok(())
+}
 
-    #[test]
-    fn test_pda_fund_address_generates_correct_pda() {
-        let validator_id = Pubkey::from_str("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
-            .expect("Failed to parse test validator ID");
-        
-        // Test that the funding function uses the same PDA generation as the existing function
-        let expected_pda = generate_deposit_pda(&validator_id);
-        
-        // The pda_fund_address function should generate the same PDA
-        // This test ensures consistency between PDA generation functions
-        assert_ne!(expected_pda, Pubkey::default());
+/// Returns the durable nonce selected with `--nonce-account` and `--nonce-authority`
+fn nonce_config(parsed: &ParsedArgs) -> Result<Option<NonceConfig>, Error> {
+    let authority = parsed.value("nonce-authority").map(str::to_string);
+    match parsed.value("nonce-account") {
+        Some(account) => {
+            let account = parse_pubkey(account)
+                .map_err(|e| Error::InvalidInput(format!("Invalid nonce account {}: {}", account, e)))?;
+            Ok(Some(NonceConfig { account, authority }))
+        }
+        None if authority.is_some() => Err(Error::InvalidInput("--nonce-authority requires --nonce-account".to_string())),
+        None => Ok(None),
     }
+}
 
-    #[tokio::test]
-    async fn test_is_validator_in_gossip_function_signature() {
-        let validator_id = Pubkey::from_str("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
-            .expect("Failed to parse test validator ID");
-        
-        // Test that the function can be called with the expected parameters
-        // This is a compile-time test to ensure the function exists with correct parameters
-        let _validator_id = &validator_id;
-        let _rpc_url = Some("https://api.mainnet-beta.solana.com");
-        
-        // The function signature should be:
-        // is_validator_in_gossip(validator_id, rpc_url)
-        // This test ensures the function can be called with the expected parameters
-        assert!(true); // Placeholder assertion
-    }
-
-    #[test]
-    fn test_gossip_validation_integration() {
-        // Test that the gossip validation function is properly integrated
-        // This test ensures the function exists and can be called
-        let validator_id = Pubkey::from_str("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
-            .expect("Failed to parse test validator ID");
-        
-        // Test that the function signature is correct
-        // This is a compile-time test to ensure the function exists
-        let _validator_id = &validator_id;
-        let _rpc_url = Some("https://api.mainnet-beta.solana.com");
-        
-        // The function should exist and be callable
-        assert!(true); // Placeholder assertion
-    }
-
-    #[test]
-    fn test_validate_base58_valid_addresses() {
-        // Test valid base58 addresses
-        let valid_addresses = vec![
-            "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL",
-            "11111111111111111111111111111112",
-            "So11111111111111111111111111111111111111112",
-            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
-        ];
-        
-        for address in valid_addresses {
-            let result = validate_base58(address);
-            assert!(result.is_ok(), "Address {} should be valid base58", address);
-        }
-    }
-
-    #[test]
-    fn test_validate_base58_invalid_addresses() {
-        // Test invalid base58 addresses
-        let invalid_addresses = vec![
-            "", // empty string
-            "   ", // whitespace only
-            "invalid_address", // contains invalid characters
-            "0OIl", // contains 0, O, I, l which are not in base58
-            "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQ0", // contains 0
-            "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQO", // contains O
-            "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQI", // contains I
-            "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQl", // contains l
-        ];
-        
-        for address in invalid_addresses {
-            let result = validate_base58(address);
-            assert!(result.is_err(), "Address '{}' should be invalid base58", address);
-        }
-    }
-
-    #[test]
-    fn test_validate_base58_edge_cases() {
-        // Test edge cases
-        let edge_cases = vec![
-            ("", "Address cannot be empty"),
-            ("   ", "Address cannot be empty"),
-            ("0", "Invalid base58 character '0' found in address"),
-            ("O", "Invalid base58 character 'O' found in address"),
-            ("I", "Invalid base58 character 'I' found in address"),
-            ("l", "Invalid base58 character 'l' found in address"),
-        ];
-        
-        for (address, expected_error) in edge_cases {
-            let result = validate_base58(address);
-            assert!(result.is_err(), "Address '{}' should be invalid", address);
-            let error = result.unwrap_err();
-            assert!(error.contains(expected_error), "Expected error containing '{}', got '{}'", expected_error, error);
+/// Handles `broadcast <tx.b64> [<tx.b64> ...]`
+///
+/// Several files are partially signed copies of one transaction whose signatures are combined.
+async fn broadcast_signed_transaction(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let paths = &args[2.min(args.len())..];
+    if paths.is_empty() {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("broadcast-needs-path"));
+        eprintln!("{}: {} broadcast <tx.b64> [<tx.b64> ...]", i18n::text("usage"), args[0]);
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    let transaction = paths
+        .iter()
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .map_err(|e| Error::InvalidInput(format!("Failed to read transaction {}: {}", path, e)))
+                .and_then(|encoded| decode_partial_transaction(&encoded))
+        })
+        .collect::<Result<Vec<_>, Error>>()
+        .and_then(combine_signatures)
+        .and_then(|transaction| check_fully_signed(&transaction).map(|()| transaction));
+    let rpc = rpc_client(None);
+    let result = match transaction {
+        Ok(transaction) => broadcast_transaction(&transaction, &rpc).await,
+        Err(e) => Err(e),
+    };
+    
+    match result {
+        Ok(signature) if output == OutputFormat::Json => {
+            let mut fields = serde_json::json!({ "signature": signature });
+            add_signature_url(parsed, &mut fields, &signature);
+            println!("{}", JsonReport::success("broadcast", fields, Vec::new()).to_json());
+        }
+        Ok(signature) => {
+            println!("{}", i18n::text("transaction-successful"));
+            println!("{}", i18n::message("transaction-signature", &[("signature", &signature)]));
+            print_signature_link(parsed, &signature);
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("broadcast", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}", i18n::message("broadcast-failed", &[("error", &i18n::error_text(&e))]));
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Handles `airdrop <keypair_or_pubkey> <amount>`
+async fn run_airdrop_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let (Some(recipient), Some(amount)) = (args.get(2), args.get(3)) else {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("airdrop-needs-arguments"));
+        eprintln!("{}: {} airdrop <keypair_or_pubkey> <amount> [--url <devnet_or_testnet_url>]", i18n::text("usage"), args[0]);
+        exit(EXIT_INVALID_INPUT);
+    };
+    
+    // Only the pubkey is needed; a keypair path is accepted for convenience
+    let request = SignerPool::default().resolve(recipient).and_then(|recipient| match parse_amount(amount)? {
+        Amount::Lamports(lamports) => Ok((recipient, lamports)),
+        Amount::All => Err(Error::InvalidInput("An airdrop needs an exact amount, not ALL".to_string())),
+    });
+    let rpc = rpc_client(None);
+    let result = match request {
+        Ok((recipient, lamports)) => request_airdrop(&recipient, lamports, Duration::from_secs(DEFAULT_CONFIRMATION_TIMEOUT_SECS), &rpc)
+            .await
+            .map(|outcome| (recipient, outcome)),
+        Err(e) => Err(e),
+    };
+    
+    match result {
+        Ok((recipient, outcome)) if output == OutputFormat::Json => {
+            let mut fields = serde_json::json!({
+                "recipient": recipient.to_string(),
+                "signature": outcome.signature,
+                "amount_lamports": outcome.amount_lamports,
+                "balance_lamports": outcome.balance_lamports,
+            });
+            add_signature_url(parsed, &mut fields, &outcome.signature);
+            println!("{}", JsonReport::success("airdrop", fields, Vec::new()).to_json());
+        }
+        Ok((recipient, outcome)) => {
+            println!("{}", i18n::message("airdropped", &[("amount", &Amount::Lamports(outcome.amount_lamports)), ("recipient", &recipient)]));
+            println!("{}", i18n::message("transaction-signature", &[("signature", &outcome.signature)]));
+            print_signature_link(parsed, &outcome.signature);
+            println!("{}", i18n::message("balance", &[("amount", &Amount::Lamports(outcome.balance_lamports))]));
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("airdrop", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
         }
     }
+}
 
-    #[tokio::test]
-    async fn test_should_cancel_pda_funding_function_signature() {
-        let validator_id = Pubkey::from_str("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
-            .expect("Failed to parse test validator ID");
-        
-        // Test that the function can be called with the expected parameters
-        // This is a compile-time test to ensure the function exists with correct parameters
-        let _validator_id = &validator_id;
-        let _rpc_url = Some("https://api.mainnet-beta.solana.com");
-        
-        // The function signature should be:
-        // should_cancel_pda_funding(validator_id, rpc_url)
-        // This test ensures the function can be called with the expected parameters
-        assert!(true); // Placeholder assertion
-    }
-
-    #[test]
-    fn test_cancel_functionality_integration() {
-        // Test that the cancel functionality is properly integrated
-        // This test ensures the function exists and can be called
-        let validator_id = Pubkey::from_str("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
-            .expect("Failed to parse test validator ID");
-        
-        // Test that the function signature is correct
-        // This is a compile-time test to ensure the function exists
-        let _validator_id = &validator_id;
-        let _rpc_url = Some("https://api.mainnet-beta.solana.com");
-        
-        // The function should exist and be callable
-        assert!(true); // Placeholder assertion
+/// Handles `derive --seed-spec <kind:value,...> [--program-id <pubkey>]`
+fn run_derive_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let result = derive_from_spec(parsed);
+    let (seeds, program_id, address, bump) = match result {
+        Ok(derived) => derived,
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("derive", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            eprintln!("{}: {} derive --seed-spec <kind:value,...> [--program-id <pubkey>]", i18n::text("usage"), args[0]);
+            exit(exit_code(&e));
+        }
+    };
+    
+    if output == OutputFormat::Json {
+        let mut result = serde_json::json!({
+            "program_id": program_id.to_string(),
+            "seeds": seeds.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            "pda": address.to_string(),
+            "bump": bump,
+        });
+        if let Some(explorer) = explorer(parsed) {
+            result["pda_url"] = explorer.account_url(&address.to_string()).into();
+        }
+        println!("{}", JsonReport::success("derive", result, Vec::new()).to_json());
+    } else {
+        println!("{}", i18n::message("derive-program", &[("program", &program_id)]));
+        for seed in &seeds {
+            println!("{}", i18n::message("derive-seed", &[("seed", seed)]));
+        }
+        println!("{}", i18n::message("pda-address", &[("pda", &address)]));
+        println!("{}", i18n::message("bump-seed", &[("bump", &bump)]));
+        if let Some(explorer) = explorer(parsed) {
+            println!("{}", i18n::message("explorer", &[("url", &explorer.account_url(&address.to_string()))]));
+        }
     }
+}
 
-    #[test]
-    fn test_pda_fund_address_with_gossip_check() {
-        let validator_id = Pubkey::from_str("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
-            .expect("Failed to parse test validator ID");
-        
-        // Test that the funding function now includes gossip checking
-        // This test ensures the function signature is correct and includes the new functionality
-        let _validator_id = &validator_id;
-        let _keypair_path = "test_keypair.json";
-        let _amount_sol = 1.0f64;
-        let _rpc_url = Some("https://api.mainnet-beta.solana.com");
-        
-        // The function should exist and be callable with gossip checking
-        assert!(true); // Placeholder assertion
+/// Parses the seeds and program of `derive` and derives the address
+fn derive_from_spec(parsed: &ParsedArgs) -> Result<(Vec<Seed>, Pubkey, Pubkey, u8), Error> {
+    let spec = parsed.value("seed-spec").ok_or_else(|| Error::InvalidInput("derive requires --seed-spec".to_string()))?;
+    let program_id = match parsed.value("program-id") {
+        Some(program_id) => parse_pubkey(program_id).map_err(|e| Error::InvalidInput(format!("Invalid --program-id: {}", e)))?,
+        None => REVENUE_DISTRIBUTION_PROGRAM_ID,
+    };
+    // Validator aliases are accepted wherever a pubkey seed is
+    let config = Config::load(&config_path(parsed))?;
+    let seeds = parse_seed_spec(spec, |value| Ok(config.resolve_validator(value)?.1))?;
+    let (address, bump) = derive(&seeds, &program_id)?;
+    Ok((seeds, program_id, address, bump))
+}
+
+/// Handles `verify-deposit <signature> <validator_or_alias>`
+///
+/// Exits with 0 only for a verified payment: 7 while it is not finalized, 6 if the transaction failed
+/// and 1 if it did not pay the PDA or was not found.
+async fn run_verify_deposit_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let (Some(signature), Some(validator)) = (args.get(2), args.get(3)) else {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("verify-deposit-needs-arguments"));
+        eprintln!("{}: {} verify-deposit <signature> <validator_or_alias>", i18n::text("usage"), args[0]);
+        exit(EXIT_INVALID_INPUT);
+    };
+    
+    let request = signature
+        .parse::<Signature>()
+        .map_err(|e| Error::InvalidInput(format!("Invalid transaction signature {}: {}", signature, e)))
+        .and_then(|signature| Ok((signature, Config::load(&config_path(parsed))?.resolve_validator(validator)?.1)));
+    let result = match request {
+        Ok((signature, validator_id)) => {
+            preflight_compatibility("verify-deposit", parsed, output).await;
+            verify_deposit(&signature, &validator_id, &rpc_client(None)).await
+        }
+        Err(e) => Err(e),
+    };
+    let verification = match result {
+        Ok(verification) => verification,
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("verify-deposit", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    if output == OutputFormat::Json {
+        let mut fields = serde_json::json!(verification);
+        add_signature_url(parsed, &mut fields, &verification.signature);
+        println!("{}", JsonReport::success("verify-deposit", fields, Vec::new()).to_json());
+    } else {
+        print_deposit_verification(parsed, &verification);
+    }
+    let code = match verification.verdict {
+        DepositVerdict::Verified => return,
+        DepositVerdict::NotFinalized => EXIT_CONFIRMATION_TIMEOUT,
+        DepositVerdict::Failed => EXIT_TRANSACTION_FAILED,
+        DepositVerdict::WrongRecipient | DepositVerdict::NotFound => EXIT_FAILURE,
+    };
+    exit(code);
+}
+
+/// Prints the text report of `verify-deposit`
+fn print_deposit_verification(parsed: &ParsedArgs, verification: &DepositVerification) {
+    let labels = address_labels(parsed);
+    let format = |address: &str| address.parse::<Pubkey>().map(|pubkey| labels.format(&pubkey)).unwrap_or_else(|_| address.to_string());
+    println!("{}", i18n::message("field-transaction", &[("value", &verification.signature)]));
+    print_signature_link(parsed, &verification.signature);
+    println!("{}", i18n::message("field-validator", &[("value", &format(&verification.validator))]));
+    println!("{}", i18n::message("field-deposit-pda", &[("value", &verification.pda)]));
+    if let Some(slot) = verification.slot {
+        let finality = i18n::text(if verification.finalized { "finalized" } else { "confirmed-not-finalized" });
+        println!("{}", i18n::message("field-slot-depth", &[("slot", &slot), ("finality", &finality), ("depth", &verification.depth.unwrap_or_default())]));
+        println!("{}", i18n::message("field-pda-credited", &[("value", &Amount::Lamports(verification.amount_lamports))]));
+        if !verification.funders.is_empty() {
+            let funders: Vec<String> = verification.funders.iter().map(|funder| format(funder)).collect();
+            println!("{}", i18n::message("field-paid-from", &[("value", &funders.join(", "))]));
+        }
+    }
+    if let Some(error) = &verification.error {
+        println!("{}", i18n::message("field-error", &[("value", error)]));
+    }
+    let reason = i18n::text(match verification.verdict {
+        DepositVerdict::Verified => "deposit-verified",
+        DepositVerdict::NotFinalized => "deposit-not-finalized",
+        DepositVerdict::WrongRecipient => "deposit-wrong-recipient",
+        DepositVerdict::Failed => "deposit-failed",
+        DepositVerdict::NotFound => "deposit-not-found",
+    });
+    println!("{}", i18n::message("field-verdict", &[("verdict", &verification.verdict), ("reason", &reason)]));
+}
+
+/// Waits for a funding to be confirmed, then writes its receipt signed by the funding wallet to `--receipt`
+async fn write_receipt(path: &str, validator_id: &Pubkey, signers: &FundingSigners, outcome: &FundingOutcome) -> Result<DepositReceipt, Error> {
+    let rpc = rpc_client(None);
+    let payer = load_signer(&signers.funder)?;
+    wait_for_confirmation(&outcome.signature, Duration::from_secs(DEFAULT_CONFIRMATION_TIMEOUT_SECS), &rpc).await?;
+    let signature = outcome
+        .signature
+        .parse::<Signature>()
+        .map_err(|e| Error::InvalidInput(format!("Invalid transaction signature {}: {}", outcome.signature, e)))?;
+    let receipt = create_receipt(&signature, validator_id, payer.as_ref(), &rpc).await?;
+    write_json_file(Path::new(path), &receipt)?;
+    Ok(receipt)
+}
+
+/// Waits for a funding to be confirmed, then reads how it changed the PDA balance (`--verify-delta`)
+async fn verify_funding_delta(validator_id: &Pubkey, outcome: &FundingOutcome) -> Result<BalanceDelta, Error> {
+    let rpc = rpc_client(None);
+    wait_for_confirmation(&outcome.signature, Duration::from_secs(DEFAULT_CONFIRMATION_TIMEOUT_SECS), &rpc).await?;
+    let signature = outcome
+        .signature
+        .parse::<Signature>()
+        .map_err(|e| Error::InvalidInput(format!("Invalid transaction signature {}: {}", outcome.signature, e)))?;
+    verify_balance_delta(&signature, validator_id, outcome.amount_lamports, &rpc).await
+}
+
+/// Handles `verify-receipt <receipt.json>`
+fn run_verify_receipt_command(args: &[String], output: OutputFormat) {
+    let Some(path) = args.get(2) else {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("verify-receipt-needs-file"));
+        eprintln!("{}: {} verify-receipt <receipt.json>", i18n::text("usage"), args[0]);
+        exit(EXIT_INVALID_INPUT);
+    };
+    
+    let result = read_json_file::<DepositReceipt>(Path::new(path)).and_then(|receipt| verify_receipt(&receipt).map(|_| receipt));
+    match result {
+        Ok(receipt) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::success("verify-receipt", serde_json::json!(receipt), Vec::new()).to_json());
+        }
+        Ok(receipt) => {
+            println!("{}", i18n::message("field-transaction", &[("value", &receipt.signature)]));
+            println!("{}", i18n::message("field-slot", &[("value", &receipt.slot)]));
+            if let Some(block_time) = receipt.block_time {
+                println!("{}", i18n::message("field-block-time", &[("value", &audit::format_timestamp(block_time.max(0) as u64))]));
+            }
+            println!("{}", i18n::message("field-validator", &[("value", &receipt.validator)]));
+            println!("{}", i18n::message("field-deposit-pda", &[("value", &receipt.pda)]));
+            println!("{}", i18n::message("field-amount", &[("value", &Amount::Lamports(receipt.amount_lamports))]));
+            println!("{}", i18n::message("field-payer", &[("value", &receipt.payer)]));
+            println!("{}", i18n::message("receipt-valid", &[("payer", &receipt.payer)]));
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("verify-receipt", serde_json::json!({ "file": path }), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Handles `sign-message --keypair <keypair> <text>` and `verify-message <pubkey|alias> <signature> <text>`
+///
+/// Instead of the text, `--file` gives a binary message.
+fn run_message_command(operation: &str, args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let result = match operation {
+        "sign-message" => parsed
+            .value("keypair")
+            .ok_or_else(|| Error::InvalidInput("sign-message requires --keypair <keypair>".to_string()))
+            .and_then(|source| {
+                let message = read_message(args.get(2).map(String::as_str), parsed.value("file").map(Path::new))?;
+                let signer = load_signer(source)?;
+                let signature = sign_message(signer.as_ref(), &message)?;
+                Ok((signer.pubkey(), signature.to_string()))
+            }),
+        _ => match (args.get(2), args.get(3)) {
+            (Some(pubkey), Some(signature)) => read_message(args.get(4).map(String::as_str), parsed.value("file").map(Path::new))
+                .and_then(|message| verify_message(&resolve_alias_argument(parsed, pubkey)?, signature, &message))
+                .map(|pubkey| (pubkey, signature.clone())),
+            _ => Err(Error::InvalidInput("verify-message requires a pubkey and a signature".to_string())),
+        },
+    };
+    
+    match result {
+        Ok((pubkey, signature)) if output == OutputFormat::Json => {
+            let fields = serde_json::json!({ "pubkey": pubkey.to_string(), "signature": signature, "valid": true });
+            println!("{}", JsonReport::success(operation, fields, Vec::new()).to_json());
+        }
+        Ok((_, signature)) if operation == "sign-message" => println!("{}", signature),
+        Ok((pubkey, _)) => println!("{}", i18n::message("signature-valid", &[("pubkey", &pubkey)])),
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure(operation, serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            if operation == "sign-message" {
+                eprintln!("{}: {} sign-message --keypair <keypair> <text> (--file <message>)", i18n::text("usage"), args[0]);
+            } else {
+                eprintln!("{}: {} verify-message <pubkey> <signature> <text> (--file <message>)", i18n::text("usage"), args[0]);
+            }
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Handles `rpc-check [url...] [--reference <url>] [--max-slot-lag <slots>]`
+///
+/// Without URLs the selected endpoint and the `rpc_endpoints` of the config file are checked.
+async fn run_rpc_check_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let result = rpc_check(args, parsed).await;
+    let (checks, reference_slot) = match result {
+        Ok(result) => result,
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("rpc-check", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    if output == OutputFormat::Json {
+        let fields = serde_json::json!({ "reference_slot": reference_slot, "endpoints": checks });
+        println!("{}", JsonReport::success("rpc-check", fields, Vec::new()).to_json());
+    } else {
+        for check in &checks {
+            println!("{} {}", check.verdict(), check.url);
+            let latency = check.latency_ms.map_or("-".to_string(), |latency| i18n::message("milliseconds", &[("ms", &latency)]));
+            let slot = match (check.slot, check.slot_lag) {
+                (Some(slot), Some(lag)) => i18n::message("slot-behind", &[("slot", &slot), ("lag", &lag)]),
+                (Some(slot), None) => slot.to_string(),
+                (None, _) => "-".to_string(),
+            };
+            println!("{}", i18n::message("rpc-check-latency", &[("value", &latency)]));
+            println!("{}", i18n::message("rpc-check-slot", &[("value", &slot)]));
+            println!("{}", i18n::message("rpc-check-version", &[("value", &check.version.as_deref().unwrap_or("-"))]));
+            println!("{}", i18n::message("rpc-check-nodes", &[("value", &check.cluster_nodes.map_or("-".to_string(), |nodes| nodes.to_string()))]));
+            for failure in &check.failures {
+                println!("  - {}", i18n::localize(failure));
+            }
+        }
+        if let Some(slot) = reference_slot {
+            println!("{}", i18n::message("reference-slot", &[("slot", &slot)]));
+        }
+    }
+    if checks.iter().any(|check| !check.passed) {
+        exit(EXIT_FAILURE);
+    }
+}
+
+/// Probes the endpoints of `rpc-check` and compares them with the reference
+async fn rpc_check(args: &[String], parsed: &ParsedArgs) -> Result<(Vec<EndpointCheck>, Option<u64>), Error> {
+    let mut urls = args[2..].to_vec();
+    if urls.is_empty() {
+        urls.push(rpc::settings().url.clone().unwrap_or_else(|| rpc::DEFAULT_RPC_URL.to_string()));
+        urls.extend(Config::load(&config_path(parsed))?.rpc_endpoints);
+    }
+    let mut endpoints = Vec::new();
+    for url in &urls {
+        let url = parse_rpc_url(url)?;
+        if !endpoints.contains(&url) {
+            endpoints.push(url);
+        }
+    }
+    let max_slot_lag = parsed.parsed_value::<u64>("max-slot-lag")?.unwrap_or(DEFAULT_MAX_SLOT_LAG);
+    
+    let reference_slot = match parsed.value("reference") {
+        Some(url) => {
+            let reference = rpc_client(Some(&parse_rpc_url(url)?));
+            let slot = reference
+                .get_slot()
+                .await
+                .map_err(|e| Error::from_client_error("Failed to get the slot of the reference endpoint", &e))?;
+            Some(slot)
+        }
+        None => None,
+    };
+    let mut checks = Vec::new();
+    for url in &endpoints {
+        checks.push(probe_endpoint(&rpc_client(Some(url))).await);
+    }
+    let reference_slot = evaluate_endpoints(&mut checks, reference_slot, max_slot_lag);
+    Ok((checks, reference_slot))
+}
+
+/// Handles `gossip-snapshot --out <path>` and `gossip-diff <old.json> [new.json]`
+async fn run_gossip_command(operation: &str, args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let rpc = rpc_client(None);
+    let result = if operation == "gossip-snapshot" {
+        gossip_snapshot(parsed, &rpc).await
+    } else {
+        match args.get(2) {
+            Some(old) => gossip_diff(parsed, old, args.get(3).map(String::as_str), &rpc, output).await,
+            None => Err(Error::InvalidInput("gossip-diff requires a snapshot path".to_string())),
+        }
+    };
+    
+    match result {
+        Ok(fields) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::success(operation, fields, Vec::new()).to_json());
+        }
+        Ok(fields) if operation == "gossip-snapshot" => {
+            println!(
+                "{}",
+                i18n::message(
+                    "gossip-snapshot-saved",
+                    &[("nodes", &fields["nodes"]), ("url", &fields["rpc_url"].as_str().unwrap_or_default()), ("path", &fields["out"].as_str().unwrap_or_default())],
+                )
+            );
+        }
+        Ok(_) => {}
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure(operation, serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            if e.is_user_error() {
+                eprintln!("{}: {} gossip-snapshot --out <path> | gossip-diff <old.json> [new.json]", i18n::text("usage"), args[0]);
+            }
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Saves the live node list to `--out`, returning the report fields
+async fn gossip_snapshot(parsed: &ParsedArgs, rpc: &dyn SolanaRpc) -> Result<serde_json::Value, Error> {
+    let out = parsed.value("out").ok_or_else(|| Error::InvalidInput("gossip-snapshot requires --out <path>".to_string()))?;
+    let snapshot = take_snapshot(rpc, unix_timestamp()).await?;
+    snapshot.save(Path::new(out))?;
+    Ok(serde_json::json!({ "out": out, "rpc_url": snapshot.rpc_url, "fetched_at": snapshot.fetched_at, "nodes": snapshot.nodes.len() }))
+}
+
+/// Compares a snapshot with a later snapshot, or with the live node list, printing the text report
+async fn gossip_diff(parsed: &ParsedArgs, old: &str, new: Option<&str>, rpc: &dyn SolanaRpc, output: OutputFormat) -> Result<serde_json::Value, Error> {
+    let load = |path: &str| {
+        GossipCacheFile::load(Path::new(path))?.ok_or_else(|| Error::InvalidInput(format!("Gossip snapshot {} not found", path)))
+    };
+    let old_snapshot = load(old)?;
+    let new_snapshot = match new {
+        Some(path) => load(path)?,
+        None => take_snapshot(rpc, unix_timestamp()).await?,
+    };
+    let diff = diff_nodes(&old_snapshot.node_set(), &new_snapshot.node_set());
+    
+    if output != OutputFormat::Json {
+        println!(
+            "{}",
+            i18n::message(
+                "gossip-diff-summary",
+                &[
+                    ("old", &old),
+                    ("old_time", &audit::format_timestamp(old_snapshot.fetched_at)),
+                    ("new", &new.unwrap_or(i18n::text("live-list"))),
+                    ("new_time", &audit::format_timestamp(new_snapshot.fetched_at)),
+                    ("appeared", &diff.appeared.len()),
+                    ("disappeared", &diff.disappeared.len()),
+                    ("unchanged", &diff.unchanged),
+                ],
+            )
+        );
+        if old_snapshot.rpc_url != new_snapshot.rpc_url {
+            println!("{}", i18n::message("gossip-diff-endpoints", &[("old", &old_snapshot.rpc_url), ("new", &new_snapshot.rpc_url)]));
+        }
+        // Aliases and labels make the validators of the config stand out
+        let labels = address_labels(parsed);
+        for (heading, nodes) in [("disappeared", &diff.disappeared), ("appeared", &diff.appeared)] {
+            if !nodes.is_empty() {
+                println!("{}:", i18n::text(heading));
+                for node in nodes {
+                    println!("  {}", labels.format_str(node));
+                }
+            }
+        }
+    }
+    
+    let side = |file: Option<&str>, snapshot: &GossipCacheFile| {
+        serde_json::json!({ "file": file, "rpc_url": snapshot.rpc_url, "fetched_at": snapshot.fetched_at, "nodes": snapshot.nodes.len() })
+    };
+    Ok(serde_json::json!({
+        "old": side(Some(old), &old_snapshot),
+        "new": side(new, &new_snapshot),
+        "appeared": diff.appeared,
+        "disappeared": diff.disappeared,
+        "unchanged": diff.unchanged,
+    }))
+}
+
+/// Handles `gossip-monitor [validator_or_alias ...]`: notifies when a validator stays out of gossip, until Ctrl-C
+async fn run_gossip_monitor(args: &[String], parsed: &ParsedArgs) {
+    if let Err(e) = gossip_monitor(args, parsed).await {
+        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+        if e.is_user_error() {
+            eprintln!(
+                "{}: {} gossip-monitor [validator_or_alias ...] [--file <path>] [--interval <seconds>] [--grace <5m>] [--state <path>]",
+                i18n::text("usage"),
+                args[0]
+            );
+        }
+        exit(exit_code(&e));
+    }
+}
+
+/// Checks the gossip presence of the validators every `--interval` seconds, keeping last-seen times in `--state`
+async fn gossip_monitor(args: &[String], parsed: &ParsedArgs) -> Result<(), Error> {
+    let interval_secs = match parsed.value("interval").map(str::parse::<u64>) {
+        None => DEFAULT_MONITOR_INTERVAL_SECS,
+        Some(Ok(secs)) if secs > 0 => secs,
+        Some(_) => return Err(Error::InvalidInput("Invalid --interval: must be a positive number of seconds".to_string())),
+    };
+    let grace_secs = parse_grace(parsed.value("grace").unwrap_or(DEFAULT_GOSSIP_GRACE))?;
+    let state_path = parsed.value("state").map(PathBuf::from).unwrap_or_else(default_monitor_state_path);
+    let validators: Vec<Pubkey> = batch_validators(&args[2..], parsed)?.into_iter().map(|(_, validator_id)| validator_id).collect();
+    let mut state = GossipMonitorState::load(&state_path)?;
+    let notify_config = notify_config(parsed);
+    if notify_config.is_none() {
+        warn!("No [notify] section configured; validators missing from gossip are only printed");
+    }
+    
+    info!(
+        "Monitoring gossip presence of {} validators every {} seconds, notifying after {} seconds missing (Ctrl-C to stop)",
+        validators.len(), interval_secs, grace_secs
+    );
+    let rpc = rpc_client(None);
+    loop {
+        let timestamp = unix_timestamp();
+        match retry_transient(RPC_RETRY_ATTEMPTS, || rpc::gossip_nodes(&rpc)).await {
+            Ok(nodes) => {
+                let present = validators.iter().filter(|validator_id| nodes.contains(validator_id)).count();
+                println!("[{}] {}", timestamp, i18n::message("validators-in-gossip", &[("present", &present), ("total", &validators.len())]));
+                for event in state.observe(&validators, &nodes, timestamp, grace_secs) {
+                    let marker = if matches!(event, NotifyEvent::GossipAbsent { .. }) { "⚠" } else { "✓" };
+                    println!("{} {}", marker, event);
+                    notify(notify_config.as_ref(), &event).await;
+                }
+                if let Err(e) = state.save(&state_path) {
+                    warn!("Gossip monitor state not saved: {}", e);
+                }
+            }
+            // A failed check says nothing about the validators, so their absence clocks are left as they are
+            Err(e) => warn!(timestamp, error = %e, "Error getting gossip nodes"),
+        }
+        tokio::time::sleep(Duration::from_secs(interval_secs)).await;
+    }
+}
+
+/// Price source of `--show-fiat`, shared by all amounts of the run
+static PRICES: OnceLock<CachedPriceSource<CoinGecko>> = OnceLock::new();
+
+/// Values an amount in the `--show-fiat` currency (validated at startup)
+///
+/// A missing price is a warning, not an error: the amount itself is still shown.
+async fn fiat_quote(parsed: &ParsedArgs, lamports: u64, at: Option<u64>) -> Option<FiatQuote> {
+    let currency = Currency::parse(parsed.value("show-fiat")?).ok()?;
+    let prices = PRICES.get_or_init(|| CachedPriceSource::new(CoinGecko::new(), Some(default_price_cache_path()), DEFAULT_PRICE_TTL));
+    match fiat::quote(prices, currency, lamports, at).await {
+        Ok(quote) => Some(quote),
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("warning"), i18n::message("no-fiat-value", &[("currency", &currency), ("error", &i18n::error_text(&e))]));
+            None
+        }
+    }
+}
+
+/// Returns the block explorer selected with `--explorer` (validated at startup)
+fn explorer(parsed: &ParsedArgs) -> Option<Explorer> {
+    parsed.value("explorer").and_then(|name| Explorer::parse(name).ok())
+}
+
+/// Prints the explorer link of a PDA and, with `--qr`, its deposit QR code
+fn print_pda_links(parsed: &ParsedArgs, deposit_key: &Pubkey) {
+    if let Some(explorer) = explorer(parsed) {
+        println!("{}", i18n::message("explorer", &[("url", &explorer.account_url(&deposit_key.to_string()))]));
+    }
+    if parsed.has("qr") {
+        match render_qr(&deposit_uri(&deposit_key.to_string())) {
+            Ok(qr) => println!("{}\n{}", i18n::text("scan-to-deposit"), qr),
+            Err(e) => eprintln!("{}: {}", i18n::text("warning"), e),
+        }
+    }
+}
+
+/// Prints the explorer link of a transaction
+fn print_signature_link(parsed: &ParsedArgs, signature: &str) {
+    if let Some(explorer) = explorer(parsed) {
+        println!("{}", i18n::message("explorer", &[("url", &explorer.transaction_url(signature))]));
+    }
+}
+
+/// Adds the explorer link of a transaction to a JSON report as `signature_url`
+fn add_signature_url(parsed: &ParsedArgs, fields: &mut serde_json::Value, signature: &str) {
+    if let Some(explorer) = explorer(parsed) {
+        fields["signature_url"] = explorer.transaction_url(signature).into();
+    }
+}
+
+/// Returns the validator health policy selected by the options
+fn funding_policy(parsed: &ParsedArgs) -> Result<FundingPolicy, Error> {
+    let lookup_table = parsed
+        .value("alt")
+        .map(|alt| alt.parse::<Pubkey>().map_err(|_| Error::InvalidInput(format!("Invalid --alt address '{}'", alt))))
+        .transpose()?;
+    // A lookup table is only usable from a v0 message, so --alt implies --tx-version 0
+    let transaction_version = match parsed.value("tx-version").map(TransactionVersion::parse).transpose()? {
+        Some(TransactionVersion::Legacy) if lookup_table.is_some() => {
+            return Err(Error::InvalidInput("--alt needs a v0 transaction, not --tx-version legacy".to_string()));
+        }
+        // A cluster without v0 messages still takes the same transfer as a legacy transaction
+        Some(TransactionVersion::V0) if lookup_table.is_none() && !compat::is_supported(&compat::V0_MESSAGES) => TransactionVersion::Legacy,
+        Some(version) => version,
+        None if lookup_table.is_some() => TransactionVersion::V0,
+        None => TransactionVersion::Legacy,
+    };
+    let config = Config::load(&config_path(parsed))?;
+    Ok(FundingPolicy {
+        check_gossip: !parsed.has("skip-gossip-check"),
+        require_vote_account: parsed.has("require-vote-account"),
+        require_recent_votes: parsed.parsed_value::<u64>("require-recent-votes")?,
+        min_activated_stake: parsed.value("min-activated-stake").map(parse_min_activated_stake).transpose()?,
+        max_commission: parsed.value("max-commission").map(parse_max_commission).transpose()?,
+        reject_delinquent: parsed.has("reject-delinquent"),
+        require_uptime: match (parsed.value("require-uptime"), parsed.value("lookback-epochs")) {
+            (Some(uptime), lookback) => Some(UptimeRequirement {
+                min_basis_points: parse_required_uptime(uptime)?,
+                lookback_epochs: lookback.map(parse_lookback_epochs).transpose()?.unwrap_or(DEFAULT_LOOKBACK_EPOCHS),
+            }),
+            (None, Some(_)) => return Err(Error::InvalidInput("--lookback-epochs needs --require-uptime".to_string())),
+            (None, None) => None,
+        },
+        strict: parsed.has("strict"),
+        force: parsed.has("force"),
+        reserve_lamports: config.reserve_lamports()?,
+        priority_fee: parsed.value("priority-fee").map(parse_priority_fee).transpose()?,
+        transaction_version,
+        lookup_table,
+        memo: match parsed.value("idempotency-key").map(parse_idempotency_key).transpose()? {
+            Some(key) => Some(idempotency_memo(parsed.value("memo"), &key)?),
+            None => parsed.value("memo").map(parse_memo).transpose()?,
+        },
+        budget: config.budget(&audit::audit_log_path())?,
+        override_budget: parsed.has("override-budget"),
+    })
+}
+
+/// Prints the result of each vote account rule of the funding policy
+fn print_policy_checks(checks: &[PolicyCheck]) {
+    for check in checks {
+        println!("{} {}", if check.passed { "✓" } else { "✗" }, i18n::message("policy-check", &[("rule", &check.rule), ("detail", &check.detail)]));
+    }
+}
+
+/// Prints the trace id of the run after a failed funding, for correlating it with logs and notifications
+fn print_trace_id() {
+    if let Some(trace_id) = trace_id() {
+        eprintln!("{}", i18n::message("trace-id", &[("trace_id", &trace_id)]));
+    }
+}
+
+/// Returns the exit code for a failed run (see `Error::exit_code`)
+fn exit_code(e: &Error) -> i32 {
+    e.exit_code()
+}
+
+/// Why a run was stopped before it finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stop {
+    /// Ctrl-C
+    Interrupted,
+    /// `--deadline` passed
+    Deadline(Duration),
+}
+
+impl Stop {
+    fn exit_code(self) -> i32 {
+        match self {
+            Stop::Interrupted => EXIT_INTERRUPTED,
+            Stop::Deadline(_) => EXIT_FAILURE,
+        }
+    }
+}
+
+impl std::fmt::Display for Stop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stop::Interrupted => write!(f, "Interrupted"),
+            Stop::Deadline(deadline) => write!(f, "Operation deadline of {} seconds exceeded", deadline.as_secs()),
+        }
+    }
+}
+
+impl Stop {
+    /// Returns the reason in the language of the run (`Display` stays English for JSON output)
+    fn message(self) -> String {
+        match self {
+            Stop::Interrupted => i18n::text("interrupted").to_string(),
+            Stop::Deadline(deadline) => i18n::message("deadline-exceeded", &[("seconds", &deadline.as_secs())]),
+        }
+    }
+}
+
+/// Completes on Ctrl-C (if `ctrl_c` is set) or when the deadline passes, whichever comes first
+async fn run_stop(deadline: Option<Duration>, ctrl_c: bool) -> Stop {
+    let interrupted = async {
+        if !ctrl_c || tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+    let expired = async {
+        match deadline {
+            Some(deadline) => tokio::time::sleep(deadline).await,
+            None => std::future::pending::<()>().await,
+        }
+    };
+    tokio::select! {
+        _ = interrupted => Stop::Interrupted,
+        _ = expired => Stop::Deadline(deadline.unwrap_or_default()),
+    }
+}
+
+/// Ends the run on Ctrl-C or at `--deadline`, cancelling whatever RPC request is in flight
+///
+/// pda-batch stops itself to report partial results, pda-watch and gossip-monitor handle Ctrl-C as their
+/// normal exit and the dashboard reads Ctrl-C as a key, so only their deadline (if any) is watched here.
+fn spawn_run_guard(operation: Option<&str>, deadline: Option<Duration>) {
+    let operation = operation.unwrap_or_default().to_string();
+    let ctrl_c = !["pda-batch", "pda-watch", "gossip-monitor", "dashboard"].contains(&operation.as_str());
+    if operation == "pda-batch" || (!ctrl_c && deadline.is_none()) {
+        return;
+    }
+    tokio::spawn(async move {
+        let stop = run_stop(deadline, ctrl_c).await;
+        eprintln!("{}: {}", i18n::text("error"), stop.message());
+        if FUNDING_OPERATIONS.contains(&operation.as_str()) {
+            eprintln!("{}", i18n::text("may-have-sent"));
+        }
+        print_trace_id();
+        exit(stop.exit_code());
+    });
+}
+
+/// Checks the RPC node against the capabilities the run needs before anything is read or sent
+///
+/// Missing capabilities with a fallback are degraded to it for the rest of the run; those without
+/// one are reported up front. Both are printed to stderr, and `--strict` stops the run on either.
+/// A node that cannot be asked is left to the operation, which reports its own RPC errors.
+async fn preflight_compatibility(operation: &str, parsed: &ParsedArgs, output: OutputFormat) {
+    let required = compat::required_capabilities(operation, parsed);
+    if required.is_empty() {
+        return;
+    }
+    let rpc = rpc_client(None);
+    let compatibility = match compat::check_compatibility(&required, &rpc).await {
+        Ok(compatibility) => compatibility,
+        Err(e) => {
+            debug!(error = %e, "Skipping RPC compatibility check");
+            return;
+        }
+    };
+    debug!(version = compatibility.version.as_str(), missing = compatibility.missing.len(), "RPC compatibility check");
+    let warnings = compatibility.warnings();
+    compat::set_compatibility(compatibility);
+    for warning in &warnings {
+        eprintln!("⚠ {}", i18n::warning_text(warning));
+    }
+    if parsed.has("strict") && let Err(e) = strict_check(&warnings) {
+        if output == OutputFormat::Json {
+            println!("{}", JsonReport::failure(operation, serde_json::json!({}), warnings, &e).to_json());
+        } else {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+        }
+        exit(EXIT_STRICT_WARNING);
+    }
+}
+
+/// Stops a `--strict` run that raised warnings (text output)
+fn exit_if_strict(strict: bool, warnings: &[Warning]) {
+    if strict && let Err(e) = strict_check(warnings) {
+        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+        exit(EXIT_STRICT_WARNING);
+    }
+}
+
+/// Prints a successful JSON report, or a failure if `--strict` rejects its warnings
+fn print_json_success(operation: &str, result: serde_json::Value, warnings: Vec<Warning>, strict: bool) {
+    if strict && let Err(e) = strict_check(&warnings) {
+        println!("{}", JsonReport::failure(operation, result, warnings, &e).to_json());
+        exit(EXIT_STRICT_WARNING);
+    }
+    println!("{}", JsonReport::success(operation, result, warnings).to_json());
+}
+
+/// Prints a single result through the `--format` template, or as a JSON report without one
+///
+/// With a template, warnings go to stderr so stdout holds only the rendered line.
+fn print_result(operation: &str, result: serde_json::Value, warnings: Vec<Warning>, strict: bool, template: Option<&OutputTemplate>) {
+    let Some(template) = template else {
+        return print_json_success(operation, result, warnings, strict);
+    };
+    println!("{}", template.render(&result));
+    for warning in &warnings {
+        eprintln!("⚠ {}", i18n::warning_text(warning));
+    }
+    exit_if_strict(strict, &warnings);
+}
+
+/// Parses `--format` against the fields of the operation
+///
+/// # Returns
+/// * `Result<Option<OutputTemplate>, Error>` - Template, None without `--format`, or `Error::InvalidInput`
+///   for an operation or output format it does not apply to
+fn output_template(args: &[String], parsed: &ParsedArgs, output: OutputFormat) -> Result<Option<OutputTemplate>, Error> {
+    let Some(format) = parsed.value("format") else {
+        return Ok(None);
+    };
+    let fields = args
+        .get(1)
+        .and_then(|operation| template_fields(operation))
+        .ok_or_else(|| Error::InvalidInput("--format is only supported by pda-address, pda-balance and pda-batch".to_string()))?;
+    if output != OutputFormat::Text {
+        return Err(Error::InvalidInput("--format cannot be combined with --output json or csv".to_string()));
+    }
+    if parsed.has("qr") {
+        return Err(Error::InvalidInput("--format cannot be combined with --qr".to_string()));
+    }
+    OutputTemplate::parse(format, fields).map(Some)
+}
+
+/// Writes a plan or approval to `--out`, or prints it to stdout
+fn emit_document<T: serde::Serialize>(parsed: &ParsedArgs, document: &T) -> Result<(), Error> {
+    match parsed.value("out") {
+        Some(path) => {
+            write_json_file(Path::new(path), document)?;
+            eprintln!("{}", i18n::message("written-to", &[("path", &path)]));
+        }
+        None => println!("{}", serde_json::to_string_pretty(document).map_err(|e| Error::Io(e.to_string()))?),
+    }
+    Ok(())
+}
+
+/// Prints what a plan will do (stderr, so stdout stays the document)
+fn print_plan_summary(parsed: &ParsedArgs, plan: &FundingPlan) {
+    let labels = address_labels(parsed);
+    eprintln!("{}", i18n::message("validator", &[("validator", &labels.format_str(&plan.validator))]));
+    eprintln!("{}", i18n::message("pda-address", &[("pda", &plan.pda)]));
+    eprintln!("{}", i18n::message("amount", &[("amount", &Amount::Lamports(plan.amount_lamports))]));
+    eprintln!("{}", i18n::message("funding-wallet", &[("wallet", &labels.format_str(&plan.funder))]));
+    eprintln!("{}", i18n::message("plan-digest", &[("digest", &plan.digest())]));
+}
+
+/// Handles `plan create <validator_or_alias> <amount> [keypair_or_pubkey] [--out plan.json]`
+fn create_plan(args: &[String], parsed: &ParsedArgs) -> Result<(), Error> {
+    if args.get(2).map(String::as_str) != Some("create") || args.len() < 5 {
+        return Err(Error::InvalidInput(format!(
+            "Usage: {} plan create <validator_or_alias> <amount> [keypair_or_pubkey] [--out plan.json]",
+            args[0]
+        )));
+    }
+    
+    let (_, validator_id) = Config::load(&config_path(parsed))?.resolve_validator(&args[3])?;
+    let amount_lamports = match parse_amount(&args[4])? {
+        Amount::Lamports(lamports) => lamports,
+        Amount::All => return Err(Error::InvalidInput("A funding plan needs an exact amount, not ALL".to_string())),
+    };
+    // The author only needs the funding wallet pubkey, not its key
+    let funder_source = match args.get(5) {
+        Some(source) => source.clone(),
+        None => configured_keypair(parsed)?
+            .ok_or_else(|| Error::InvalidInput(format!("No funding wallet given or configured in {}", config_path(parsed).display())))?,
+    };
+    let funder = SignerPool::default().resolve(&funder_source)?;
+    
+    let plan = FundingPlan::new(&validator_id, &funder, amount_lamports, unix_timestamp());
+    print_plan_summary(parsed, &plan);
+    emit_document(parsed, &plan)
+}
+
+/// Handles `plan create --pairs <validator:amount,...> | --file <transfers.csv> [keypair_or_pubkey] [--out plan.yaml]`
+///
+/// Only the fees are estimated; the health checks run when the plan is executed.
+async fn create_batch_plan(args: &[String], parsed: &ParsedArgs) -> Result<(), Error> {
+    if args.get(2).map(String::as_str) != Some("create") {
+        return Err(Error::InvalidInput(format!(
+            "Usage: {} plan create --pairs <validator:amount,...> | --file <transfers.csv> [keypair_or_pubkey] [--out plan.yaml]",
+            args[0]
+        )));
+    }
+    let transfers = funding_pairs(parsed)?;
+    let funder_source = match args.get(3) {
+        Some(source) => source.clone(),
+        None => configured_keypair(parsed)?
+            .ok_or_else(|| Error::InvalidInput(format!("No funding wallet given or configured in {}", config_path(parsed).display())))?,
+    };
+    let payer = SignerPool::default().resolve(&funder_source)?;
+    let priority_fee = parsed.value("priority-fee").map(parse_priority_fee).transpose()?;
+    
+    let plan = BatchPlan::create(&payer, &transfers, priority_fee, unix_timestamp(), &rpc_client(None)).await?;
+    let yaml = plan.to_yaml()?;
+    print_batch_plan_summary(parsed, &plan);
+    match parsed.value("out") {
+        Some(path) => {
+            write_atomic(Path::new(path), yaml.as_bytes())?;
+            eprintln!("{}", i18n::message("written-to", &[("path", &path)]));
+        }
+        None => print!("{}", yaml),
+    }
+    eprintln!("{}", i18n::message("plan-hash", &[("hash", &plan_hash(yaml.as_bytes()))]));
+    Ok(())
+}
+
+/// Prints what a batch plan will do (stderr, so stdout stays the document)
+fn print_batch_plan_summary(parsed: &ParsedArgs, plan: &BatchPlan) {
+    let labels = address_labels(parsed);
+    eprintln!("{}", i18n::message("funding-wallet", &[("wallet", &labels.format_str(&plan.payer))]));
+    eprintln!("{}", i18n::message("rpc-endpoint", &[("url", &plan.rpc)]));
+    for transfer in &plan.transfers {
+        eprintln!("  {} -> {}", labels.format_str(&transfer.validator), Amount::Lamports(transfer.amount_lamports));
+    }
+    eprintln!("{}", i18n::message("batch-total", &[("amount", &Amount::Lamports(plan.total_lamports)), ("count", &plan.transfers.len())]));
+    eprintln!("{}", i18n::message("estimated-fee", &[("fee", &plan.estimated_fee_lamports), ("transactions", &plan.transaction_count)]));
+}
+
+/// Reads a batch plan, checks it against `--approve` and prepares its transfers from the funding wallet of the plan
+async fn prepare_batch_plan(args: &[String], parsed: &ParsedArgs) -> Result<(BatchPlan, PreparedFundingBatch), Error> {
+    let Some(plan_path) = args.get(3) else {
+        return Err(Error::InvalidInput(format!("Usage: {} plan execute <plan.yaml> [keypair_path] --approve <hash>", args[0])));
+    };
+    let approved = parsed
+        .value("approve")
+        .ok_or_else(|| Error::InvalidInput("plan execute requires --approve <hash> from the reviewer of the plan".to_string()))?;
+    let contents = std::fs::read(plan_path).map_err(|e| Error::Io(format!("Failed to read plan {}: {}", plan_path, e)))?;
+    check_plan_hash(&contents, approved)?;
+    let plan = BatchPlan::parse(&String::from_utf8_lossy(&contents))?;
+    let (payer, transfers) = plan.accounts()?;
+    for flag in ["pairs", "file", "sign-only", "nonce-account", "tx-version", "alt"] {
+        if parsed.has(flag) {
+            return Err(Error::InvalidInput(format!("--{} is not supported by plan execute", flag)));
+        }
+    }
+    print_batch_plan_summary(parsed, &plan);
+    eprintln!("{}", i18n::text("plan-hash-matches"));
+    
+    let keypair_path = match args.get(4) {
+        Some(keypair_path) => keypair_path.clone(),
+        None => configured_keypair(parsed)?.ok_or_else(|| {
+            Error::InvalidInput(format!("plan execute requires a keypair path; no funding wallet configured in {}", config_path(parsed).display()))
+        })?,
+    };
+    let signers = FundingSigners {
+        funder: keypair_path,
+        fee_payer: parsed.value("fee-payer").map(str::to_string),
+        keypairs: parsed.values("keypair").into_iter().map(str::to_string).collect(),
+    };
+    // The plan was reviewed for its endpoint, so it runs there
+    let rpc = rpc_client(Some(&plan.rpc));
+    let prepared = prepare_funding_batch(&transfers, &signers, &funding_policy(parsed)?, &rpc).await?;
+    if prepared.payer != payer {
+        return Err(Error::InvalidInput(format!("The plan funds from {}, but the given keypair is {}", payer, prepared.payer)));
+    }
+    confirm_funding_batch(parsed, &prepared)?;
+    if signers.is_interactive() {
+        info!("Approve each transaction on your hardware wallet when prompted");
+    }
+    Ok((plan, prepared))
+}
+
+/// Handles `plan execute <plan.yaml> [keypair_path] --approve <hash> [--yes]`
+async fn execute_batch_plan(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let fail = |e: &Error| -> ! {
+        if output == OutputFormat::Json {
+            println!("{}", JsonReport::failure("plan-execute", serde_json::json!({}), Vec::new(), e).to_json());
+        } else {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(e));
+            print_trace_id();
+        }
+        exit(exit_code(e));
+    };
+    let (plan, prepared) = match prepare_batch_plan(args, parsed).await {
+        Ok(prepared) => prepared,
+        Err(e) => fail(&e),
+    };
+    let sent = match send_funding_batch(&prepared, Duration::from_secs(DEFAULT_CONFIRMATION_TIMEOUT_SECS), &rpc_client(Some(&plan.rpc))).await {
+        Ok(sent) => sent,
+        Err(e) => fail(&e),
+    };
+    report_fund_many("plan-execute", parsed, output, prepared, sent, None, (0, 0)).await;
+}
+
+/// Handles `approve <plan.json> --approver <keypair> [--approval-ttl <seconds>] [--out approval.json]`
+fn approve_plan_file(args: &[String], parsed: &ParsedArgs) -> Result<(), Error> {
+    let (Some(plan_path), Some(approver_source)) = (args.get(2), parsed.value("approver")) else {
+        return Err(Error::InvalidInput(format!(
+            "Usage: {} approve <plan.json> --approver <keypair> [--approval-ttl <seconds>] [--out approval.json]",
+            args[0]
+        )));
+    };
+    let ttl_secs = parsed.parsed_value::<u64>("approval-ttl")?.unwrap_or(DEFAULT_APPROVAL_TTL_SECS);
+    
+    let plan: FundingPlan = read_json_file(Path::new(plan_path))?;
+    plan.accounts()?;
+    print_plan_summary(parsed, &plan);
+    
+    let approver = load_signer(approver_source)?;
+    let approval = approve_plan(&plan, approver.as_ref(), ttl_secs, unix_timestamp())?;
+    eprintln!("{}", i18n::message("approved-by", &[("approver", &approval.approver), ("expires", &approval.expires_at)]));
+    emit_document(parsed, &approval)
+}
+
+/// Asks for confirmation on the terminal; input that is not a terminal never confirms
+fn confirm(prompt: &str) -> bool {
+    if !std::io::stdin().is_terminal() {
+        return false;
+    }
+    eprint!("{} {} ", prompt, i18n::text("confirm-suffix"));
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    if std::io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    i18n::is_yes(&answer)
+}
+
+/// Returns true if confirmation prompts are skipped (`--yes`, `-y` or `--assume-yes`)
+fn assume_yes(parsed: &ParsedArgs) -> bool {
+    parsed.has("yes") || parsed.has("assume-yes")
+}
+
+/// Prints what a prepared funding will do and asks for confirmation unless `--yes` is given
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok to send, or `Error::FundingCancelled` if not confirmed (nothing was signed or sent)
+fn confirm_funding(parsed: &ParsedArgs, prepared: &PreparedFunding) -> Result<(), Error> {
+    let labels = address_labels(parsed);
+    eprintln!("{}", i18n::text("funding-summary"));
+    eprintln!("{}", i18n::message("summary-amount", &[("amount", &Amount::Lamports(prepared.amount_lamports))]));
+    eprintln!("{}", i18n::message("summary-fee", &[("fee", &prepared.fee_lamports)]));
+    eprintln!("{}", i18n::message("summary-payer-after", &[("amount", &Amount::Lamports(prepared.payer_balance_after))]));
+    eprintln!("{}", i18n::message("summary-pda-after", &[("amount", &Amount::Lamports(prepared.pda_balance_after))]));
+    if let Some(memo) = &prepared.memo {
+        eprintln!("{}", i18n::message("summary-memo", &[("memo", memo)]));
+    }
+    if prepared.creates_account {
+        eprintln!("{}", i18n::text("summary-creates-pda"));
+    }
+    if assume_yes(parsed) {
+        return Ok(());
+    }
+    let prompt = i18n::message(
+        "confirm-transfer",
+        &[("amount", &format_sol(prepared.amount_lamports)), ("payer", &labels.format(&prepared.payer)), ("pda", &prepared.pda)],
+    );
+    if !confirm(&prompt) {
+        return Err(Error::FundingCancelled("Not confirmed; nothing was sent (use --yes in scripts)".to_string()));
+    }
+    Ok(())
+}
+
+/// Checks and builds a funding, asks for confirmation, then signs, records and sends it
+async fn fund_with_confirmation(
+    parsed: &ParsedArgs,
+    validator_id: &Pubkey,
+    signers: &FundingSigners,
+    amount: Amount,
+    policy: &FundingPolicy,
+    nonce: Option<&NonceConfig>,
+    idempotency_key: Option<&str>,
+) -> Result<FundingOutcome, Error> {
+    let rpc = rpc_client(None);
+    let signed = async {
+        let prepared = prepare_pda_funding(validator_id, signers, amount, policy, nonce, &rpc).await?;
+        check_duplicate_funding(parsed, validator_id, &prepared, policy.force, &rpc).await?;
+        confirm_funding(parsed, &prepared)?;
+        if signers.is_interactive() {
+            info!("Approve the transaction on your hardware wallet when prompted");
+        }
+        sign_pda_funding(prepared)
+    }
+    .await;
+    send_recorded_funding(validator_id, signed, idempotency_key, &rpc).await
+}
+
+/// Records a signed funding as pending in the audit log, sends it, then records its outcome
+///
+/// The pending entry is written before the transaction leaves, so a run that loses the outcome (a timeout,
+/// a crash) still leaves the payer, amount and signature for the duplicate check and the budget.
+/// Errors before signing are recorded like any failed funding.
+async fn send_recorded_funding(
+    validator_id: &Pubkey,
+    signed: Result<SignedFunding, Error>,
+    idempotency_key: Option<&str>,
+    rpc: &dyn SolanaRpc,
+) -> Result<FundingOutcome, Error> {
+    let deposit_key = generate_deposit_pda(validator_id);
+    let (attempt, result) = match signed {
+        Ok(signed) => {
+            let attempt = signed.attempt();
+            let mut entry = audit::pending_funding_entry(validator_id, &deposit_key, &attempt);
+            entry.idempotency_key = idempotency_key.map(str::to_string);
+            if let Err(e) = audit::append_entry(&audit::audit_log_path(), &entry) {
+                return Err(Error::Io(format!("Funding not sent: it could not be recorded in the audit log first: {}", e)));
+            }
+            (Some(attempt), send_signed_funding(signed, rpc).await)
+        }
+        Err(e) => (None, Err(e)),
+    };
+    record_funding(validator_id, &deposit_key, &result, attempt.as_ref(), idempotency_key);
+    result
+}
+
+/// Reads `--duplicate-window` (default `DEFAULT_DUPLICATE_WINDOW`)
+fn duplicate_window(parsed: &ParsedArgs) -> Result<StatsWindow, Error> {
+    StatsWindow::parse(parsed.value("duplicate-window").unwrap_or(DEFAULT_DUPLICATE_WINDOW))
+}
+
+/// Refuses a funding that repeats an earlier one unless `--force` is given
+///
+/// A repeat has the same `--idempotency-key`, or without a key the same validator, payer and amount
+/// within `--duplicate-window`. Earlier attempts whose outcome was lost are looked up in the recent
+/// transactions of the PDA first: one that landed or may still land is a repeat, one that failed or
+/// expired is not. This keeps a script that retries after a timeout from funding twice.
+///
+/// When the audit log has no repeat, for instance because the funding was sent from another machine,
+/// the history of the PDA within the window is searched as well (`pending::find_onchain_funding`).
+/// That search is best effort: a node that does not serve it only logs a warning.
+async fn check_duplicate_funding(parsed: &ParsedArgs, validator_id: &Pubkey, prepared: &PreparedFunding, force: bool, rpc: &dyn SolanaRpc) -> Result<(), Error> {
+    let now = unix_timestamp();
+    let since = duplicate_window(parsed)?.start(now);
+    let entries = settle_funding_attempts(&audit::audit_log_path(), now, rpc).await?;
+    let key = parsed.value("idempotency-key");
+    let (attempt, when) = match audit::find_duplicate_funding(&entries, &validator_id.to_string(), &prepared.payer.to_string(), prepared.amount_lamports, key, since) {
+        Some(earlier) => {
+            let attempt = match (&earlier.signature, earlier.status.as_str()) {
+                (Some(signature), audit::STATUS_PENDING | audit::STATUS_UNCONFIRMED) => format!("transaction {} that may still land", signature),
+                (Some(signature), _) => format!("transaction {}", signature),
+                (None, status) => format!("a {} attempt", status),
+            };
+            (attempt, format!("at {} UTC", audit::format_timestamp(earlier.timestamp)))
+        }
+        None => match find_onchain_funding(&generate_deposit_pda(validator_id), &prepared.payer, prepared.amount_lamports, key, since, rpc).await {
+            Ok(Some(earlier)) => {
+                let when = match earlier.block_time {
+                    Some(block_time) => format!("at {} UTC", audit::format_timestamp(block_time.max(0) as u64)),
+                    None => format!("in slot {}", earlier.slot),
+                };
+                (format!("transaction {} found on chain", earlier.signature), when)
+            }
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                warn!("On-chain duplicate check skipped: {}", e);
+                return Ok(());
+            }
+        },
+    };
+    let reason = match key {
+        Some(key) => format!("idempotency key '{}' was already used by {} {}", key, attempt, when),
+        None => format!("the same amount was sent from {} by {} {}", prepared.payer, attempt, when),
+    };
+    if force {
+        warn!("Possible duplicate funding sent anyway (--force): {}", reason);
+        return Ok(());
+    }
+    Err(Error::FundingCancelled(format!(
+        "Funding cancelled: possible duplicate, {}. Check the PDA balance (audit show) and pass --force to send it anyway",
+        reason
+    )))
+}
+
+/// Reads `--at-epoch-boundary` / `--at-epoch N`
+fn epoch_target(parsed: &ParsedArgs) -> Result<Option<EpochTarget>, Error> {
+    let target = match (parsed.has("at-epoch-boundary"), parsed.value("at-epoch")) {
+        (true, Some(_)) => return Err(Error::InvalidInput("--at-epoch-boundary and --at-epoch cannot be combined".to_string())),
+        (true, None) => Some(EpochTarget::NextBoundary),
+        (false, Some(value)) => Some(EpochTarget::parse(value)?),
+        (false, None) => None,
+    };
+    if target.is_some() && parsed.has("sign-only") {
+        return Err(Error::InvalidInput("--at-epoch-boundary and --at-epoch cannot be used with --sign-only".to_string()));
+    }
+    Ok(target)
+}
+
+/// Checks and confirms a funding now, waits until the target epoch begins, then checks it again and sends it
+async fn fund_at_epoch(
+    parsed: &ParsedArgs,
+    validator_id: &Pubkey,
+    signers: &FundingSigners,
+    amount: Amount,
+    policy: &FundingPolicy,
+    nonce: Option<&NonceConfig>,
+    target: EpochTarget,
+) -> Result<FundingOutcome, Error> {
+    // Signers are loaded again at the boundary, which only works unattended for keypair files and env:// keypairs
+    let sources = std::iter::once(&signers.funder).chain(&signers.fee_payer).chain(&signers.keypairs);
+    if let Some(source) = sources.into_iter().find(|source| !SignerSource::parse(source).is_unattended()) {
+        return Err(Error::InvalidInput(format!(
+            "A funding scheduled for an epoch needs keypair files or env:// keypairs, not {}: the signers are loaded again when the epoch begins",
+            source
+        )));
+    }
+    
+    let rpc = rpc_client(None);
+    let target_epoch = target.resolve(epoch::get_epoch_info(&rpc).await?.epoch)?;
+    
+    // Everything is checked and confirmed up front, so a mistake does not surface hours later
+    let prepared = prepare_pda_funding(validator_id, signers, amount, policy, nonce, &rpc).await?;
+    check_duplicate_funding(parsed, validator_id, &prepared, policy.force, &rpc).await?;
+    confirm_funding(parsed, &prepared)?;
+    drop(prepared);
+    
+    wait_for_epoch(target_epoch, MAX_EPOCH_POLL_INTERVAL, &rpc, |progress| {
+        if progress.reached() {
+            info!("Epoch {} has begun - sending the funding", progress.target_epoch);
+        } else {
+            info!("Waiting for the epoch boundary: {}", progress);
+        }
+    })
+    .await?;
+    
+    // The blockhash, balances and validator health are read again at the boundary
+    let signed = prepare_pda_funding(validator_id, signers, amount, policy, nonce, &rpc).await.and_then(sign_pda_funding);
+    send_recorded_funding(validator_id, signed, parsed.value("idempotency-key"), &rpc).await
+}
+
+/// Checks a plan and its approval offline and returns the plan, its validator and the signers
+fn prepare_plan(args: &[String], parsed: &ParsedArgs) -> Result<(FundingPlan, Pubkey, FundingSigners), Error> {
+    let Some(plan_path) = args.get(2) else {
+        return Err(Error::InvalidInput(format!(
+            "Usage: {} apply <plan.json> [keypair_path] --require-approval-file <approval.json> [--yes]",
+            args[0]
+        )));
+    };
+    let approval_path = parsed.value("require-approval-file").ok_or_else(|| {
+        Error::InvalidInput("apply requires --require-approval-file <approval.json> from a second operator".to_string())
+    })?;
+    
+    let plan: FundingPlan = read_json_file(Path::new(plan_path))?;
+    let (validator_id, _) = plan.accounts()?;
+    let approval: Approval = read_json_file(Path::new(approval_path))?;
+    let approvers = Config::load(&config_path(parsed))?.approver_pubkeys()?;
+    verify_approval(&plan, &approval, &approvers, unix_timestamp())?;
+    
+    // The plan fixes the funding wallet; the given keypair must sign for it
+    let keypair = match args.get(3) {
+        Some(keypair) => Some(keypair.clone()),
+        None => configured_keypair(parsed)?,
+    };
+    let signers = FundingSigners {
+        funder: plan.funder.clone(),
+        fee_payer: parsed.value("fee-payer").map(str::to_string),
+        keypairs: keypair.into_iter().chain(parsed.values("keypair").into_iter().map(str::to_string)).collect(),
+    };
+    Ok((plan, validator_id, signers))
+}
+
+/// Handles `apply <plan.json> [keypair_path] --require-approval-file <approval.json> [--yes]`
+async fn apply_plan(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    preflight_compatibility("apply", parsed, output).await;
+    let prepared = funding_policy(parsed).and_then(|policy| Ok((prepare_plan(args, parsed)?, policy)));
+    let ((plan, validator_id, signers), policy) = match prepared {
+        Ok(prepared) => prepared,
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("apply", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    print_plan_summary(parsed, &plan);
+    eprintln!("{}", i18n::text("approval-valid"));
+    
+    let result = fund_with_confirmation(parsed, &validator_id, &signers, Amount::Lamports(plan.amount_lamports), &policy, None, None).await;
+    let event = match &result {
+        Ok(outcome) => NotifyEvent::FundingLanded {
+            validator: plan.validator.clone(),
+            pda: plan.pda.clone(),
+            amount_lamports: outcome.amount_lamports,
+            signature: outcome.signature.clone(),
+        },
+        Err(e) => NotifyEvent::FundingFailed { validator: plan.validator.clone(), pda: plan.pda.clone(), error: e.to_string() },
+    };
+    notify(notify_config(parsed).as_ref(), &event).await;
+    
+    let fields = serde_json::json!({ "validator": plan.validator, "pda": plan.pda, "plan_digest": plan.digest() });
+    match result {
+        Ok(outcome) if output == OutputFormat::Json => {
+            let mut fields = fields;
+            add_signature_url(parsed, &mut fields, &outcome.signature);
+            fields["signature"] = outcome.signature.into();
+            fields["amount_lamports"] = outcome.amount_lamports.into();
+            fields["fee_lamports"] = outcome.fee_lamports.into();
+            if let Some(quote) = fiat_quote(parsed, outcome.amount_lamports, None).await {
+                fields["fiat"] = serde_json::json!(quote);
+            }
+            println!("{}", JsonReport::success("apply", fields, outcome.warnings).to_json());
+        }
+        Ok(outcome) => {
+            for warning in &outcome.warnings {
+                println!("⚠ {}", i18n::warning_text(warning));
+            }
+            println!("{}", i18n::text("transaction-successful"));
+            println!("{}", i18n::message("transaction-signature", &[("signature", &outcome.signature)]));
+            print_signature_link(parsed, &outcome.signature);
+            println!("{}", i18n::message("transferred", &[("amount", &Amount::Lamports(outcome.amount_lamports)), ("fee", &outcome.fee_lamports)]));
+            if let Some(quote) = fiat_quote(parsed, outcome.amount_lamports, None).await {
+                println!("{}", quote);
+            }
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("apply", fields, Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}", i18n::message("funding-failed", &[("error", &i18n::error_text(&e))]));
+            print_trace_id();
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Reads a script and resolves its validators and funding wallets before any step runs
+fn load_script(args: &[String], parsed: &ParsedArgs) -> Result<(Script, Vec<Option<Pubkey>>), Error> {
+    let Some(script_path) = args.get(2) else {
+        return Err(Error::InvalidInput(format!("Usage: {} run <script.yaml> [--yes]", args[0])));
+    };
+    let yaml = std::fs::read_to_string(script_path)
+        .map_err(|e| Error::Io(format!("Failed to read script {}: {}", script_path, e)))?;
+    let script = Script::parse(&yaml)?;
+    
+    let config = Config::load(&config_path(parsed))?;
+    let mut validators = Vec::with_capacity(script.steps.len());
+    for step in &script.steps {
+        validators.push(step.validator.as_deref().map(|validator| config.resolve_validator(validator)).transpose()?.map(|(_, id)| id));
+        if step.op == StepOp::PdaFundAddress && step.keypair.is_none() && configured_keypair(parsed)?.is_none() {
+            return Err(Error::InvalidInput(format!(
+                "Script funds a validator without a keypair and no funding wallet is configured in {}",
+                config_path(parsed).display()
+            )));
+        }
+        if step.op == StepOp::Notify && config.notify.is_none() {
+            return Err(Error::InvalidInput(format!("Script sends a notification but {} has no [notify] section", config_path(parsed).display())));
+        }
+    }
+    Ok((script, validators))
+}
+
+/// Runs one script step and returns its result fields and a one-line summary
+async fn run_script_step(
+    parsed: &ParsedArgs,
+    step: &Step,
+    validator_id: Option<&Pubkey>,
+    policy: &FundingPolicy,
+    rpc: &dyn SolanaRpc,
+) -> Result<(serde_json::Value, String), Error> {
+    let Some(validator_id) = validator_id else {
+        // Only notify steps have no validator
+        let config = notify_config(parsed).ok_or_else(|| Error::InvalidInput("No [notify] section configured".to_string()))?;
+        send_notification(&config, &NotifyEvent::Message { text: step.message.clone().unwrap_or_default() }).await?;
+        return Ok((serde_json::json!({}), i18n::text("notification-sent").to_string()));
+    };
+    let deposit_key = generate_deposit_pda(validator_id);
+    
+    match step.op {
+        StepOp::ValidatorStatus => {
+            let status = retry_transient(RPC_RETRY_ATTEMPTS, || get_validator_status(validator_id, policy.check_gossip, rpc)).await?;
+            let (verdict, reasons) = status.evaluate(policy);
+            let messages: Vec<&str> = reasons.iter().map(|reason| reason.message.as_str()).collect();
+            if verdict == Verdict::Fail {
+                return Err(cancellation(&reasons, &format!("Validator {} status FAIL", validator_id)));
+            }
+            if policy.strict {
+                strict_check(&reasons)?;
+            }
+            let fields = serde_json::json!({
+                "validator": validator_id.to_string(),
+                "verdict": verdict.to_string(),
+                "in_gossip": status.in_gossip,
+                "delinquent": status.vote_account.as_ref().map(|vote_account| vote_account.delinquent),
+                "activated_stake": status.vote_account.as_ref().map(|vote_account| vote_account.activated_stake),
+                "reasons": messages,
+            });
+            Ok((fields, verdict.to_string()))
+        }
+        StepOp::PdaBalance => {
+            let balance = retry_transient(RPC_RETRY_ATTEMPTS, || get_account_balance(&deposit_key, rpc)).await?;
+            let fields = serde_json::json!({
+                "validator": validator_id.to_string(),
+                "pda": deposit_key.to_string(),
+                "balance_lamports": balance,
+                "balance_sol": balance as f64 / LAMPORTS_PER_SOL as f64,
+            });
+            Ok((fields, format!("{} SOL", format_sol(balance))))
+        }
+        StepOp::PdaFundAddress => {
+            let amount = parse_amount(step.amount.as_deref().unwrap_or_default())?;
+            let funder = match &step.keypair {
+                Some(keypair) => keypair.clone(),
+                None => configured_keypair(parsed)?.ok_or_else(|| Error::InvalidInput("No funding wallet configured".to_string()))?,
+            };
+            let signers = FundingSigners {
+                funder,
+                fee_payer: parsed.value("fee-payer").map(str::to_string),
+                keypairs: parsed.values("keypair").into_iter().map(str::to_string).collect(),
+            };
+            
+            let result = fund_with_confirmation(parsed, validator_id, &signers, amount, policy, None, None).await;
+            let event = match &result {
+                Ok(outcome) => NotifyEvent::FundingLanded {
+                    validator: validator_id.to_string(),
+                    pda: deposit_key.to_string(),
+                    amount_lamports: outcome.amount_lamports,
+                    signature: outcome.signature.clone(),
+                },
+                Err(e) => NotifyEvent::FundingFailed { validator: validator_id.to_string(), pda: deposit_key.to_string(), error: e.to_string() },
+            };
+            notify(notify_config(parsed).as_ref(), &event).await;
+            
+            let outcome = result?;
+            let mut fields = serde_json::json!({
+                "validator": validator_id.to_string(),
+                "pda": deposit_key.to_string(),
+                "signature": outcome.signature,
+                "amount_lamports": outcome.amount_lamports,
+                "fee_lamports": outcome.fee_lamports,
+            });
+            add_signature_url(parsed, &mut fields, &outcome.signature);
+            Ok((fields, i18n::message("step-transferred", &[("amount", &format_sol(outcome.amount_lamports)), ("signature", &outcome.signature)])))
+        }
+        StepOp::Notify => unreachable!("notify steps have no validator"),
+    }
+}
+
+/// Handles `run <script.yaml> [--yes]`
+///
+/// Steps run in order; a step whose conditions do not hold is skipped, and the first failing step
+/// without `continue_on_error` stops the script.
+async fn run_script_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    preflight_compatibility("run", parsed, output).await;
+    let prepared = load_script(args, parsed).and_then(|script| Ok((script, funding_policy(parsed)?)));
+    let ((script, validators), policy) = match prepared {
+        Ok(prepared) => prepared,
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("run", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    let name = script.name.clone().unwrap_or_else(|| args[2].clone());
+    let total = script.steps.len();
+    if output == OutputFormat::Text {
+        println!("{}", i18n::message("running-script", &[("name", &name), ("steps", &total)]));
+    }
+    
+    let rpc = rpc_client(None);
+    let mut results: HashMap<String, serde_json::Value> = HashMap::new();
+    let mut reports = Vec::with_capacity(total);
+    let mut failure = None;
+    for (index, (step, validator_id)) in script.steps.iter().zip(&validators).enumerate() {
+        let label = format!("[{}/{}] {}", index + 1, total, step.id.as_deref().map(str::to_string).unwrap_or_else(|| step.op.to_string()));
+        let (status, result) = if !step.should_run(&results) {
+            if output == OutputFormat::Text {
+                println!("{}: {}", label, i18n::text("skipped"));
+            }
+            ("skipped", serde_json::json!({ "ok": false, "skipped": true }))
+        } else {
+            match run_script_step(parsed, step, validator_id.as_ref(), &policy, &rpc).await {
+                Ok((mut fields, summary)) => {
+                    if output == OutputFormat::Text {
+                        println!("{}: {}", label, summary);
+                    }
+                    fields["ok"] = true.into();
+                    ("ok", fields)
+                }
+                Err(e) => {
+                    if output == OutputFormat::Text {
+                        println!("{}: {}: {}", label, i18n::text("failed"), i18n::error_text(&e));
+                    }
+                    let fields = serde_json::json!({ "ok": false, "error": e.to_string() });
+                    if !step.continue_on_error {
+                        failure = Some(e);
+                    }
+                    ("failed", fields)
+                }
+            }
+        };
+        if let Some(id) = &step.id {
+            results.insert(id.clone(), result.clone());
+        }
+        reports.push(serde_json::json!({ "id": step.id, "op": step.op.to_string(), "status": status, "result": result }));
+        if failure.is_some() {
+            break;
+        }
+    }
+    
+    let fields = serde_json::json!({ "script": name, "steps": reports });
+    match failure {
+        None if output == OutputFormat::Json => println!("{}", JsonReport::success("run", fields, Vec::new()).to_json()),
+        None => println!("{}", i18n::message("script-finished", &[("name", &name)])),
+        Some(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("run", fields, Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Some(e) => {
+            eprintln!(
+                "{}: {}",
+                i18n::text("error"),
+                i18n::message("script-stopped", &[("name", &name), ("done", &reports.len()), ("steps", &total), ("error", &i18n::error_text(&e))])
+            );
+            print_trace_id();
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Records a validator as recently used for shell completion
+///
+/// The state file is a convenience, so failures to update it are ignored.
+fn remember_validator(validator_id: &Pubkey) {
+    let path = default_state_path();
+    if let Ok(mut state) = State::load(&path) {
+        state.record_validator(&validator_id.to_string());
+        let _ = state.save(&path);
+    }
+}
+
+/// Checks gossip presence and returns the result as warnings (JSON output)
+async fn gossip_warnings(validator_id: &Pubkey, check_gossip: bool) -> Vec<Warning> {
+    if !check_gossip {
+        return vec![Warning::new(WARN_GOSSIP_CHECK_SKIPPED, "Gossip check skipped")];
+    }
+    
+    let rpc = rpc_client(None);
+    match retry_transient(RPC_RETRY_ATTEMPTS, || is_validator_in_gossip(validator_id, &rpc)).await {
+        Ok(true) => Vec::new(),
+        Ok(false) => vec![Warning::new(
+            WARN_NOT_IN_GOSSIP,
+            format!("Validator {} is NOT found in Solana gossip network", validator_id),
+        )],
+        Err(e) => vec![Warning::new(WARN_GOSSIP_CHECK_FAILED, format!("Error checking gossip network: {}", e))],
+    }
+}
+
+/// Returns a warning if the PDA balance is below its rent-exempt minimum
+async fn rent_warning(deposit_key: &Pubkey, balance: u64) -> Option<Warning> {
+    let rpc = rpc_client(None);
+    let rent_exempt_minimum = retry_transient(RPC_RETRY_ATTEMPTS, || get_rent_exempt_minimum(deposit_key, &rpc)).await.ok()?;
+    rent_exemption_warning(balance, rent_exempt_minimum)
+}
+
+/// Returns the config file path from `--config` or the default location
+fn config_path(parsed: &ParsedArgs) -> PathBuf {
+    parsed.value("config").map(PathBuf::from).unwrap_or_else(default_config_path)
+}
+
+/// Returns the audit log path from `--audit-log` or config `audit_log`, or None for the default
+///
+/// An unreadable config file is reported by the operation that needs it.
+fn audit_log_override(parsed: &ParsedArgs) -> Option<PathBuf> {
+    match parsed.value("audit-log") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => Config::load(&config_path(parsed)).ok()?.audit_log.map(PathBuf::from),
+    }
+}
+
+/// Returns the funding wallet keypair path configured for the selected profile
+fn configured_keypair(parsed: &ParsedArgs) -> Result<Option<String>, Error> {
+    let config = Config::load(&config_path(parsed))?;
+    Ok(config.keypair(parsed.value("profile"))?.map(str::to_string))
+}
+
+/// Resolves a validator argument that is a configured alias to its pubkey
+///
+/// A pubkey is returned as given without reading the config, and anything that is neither is left
+/// for the caller's own validation.
+///
+/// # Arguments
+/// * `parsed` - Parsed arguments (for `--config`)
+/// * `argument` - Alias or pubkey from the command line
+///
+/// # Returns
+/// * `Result<String, Error>` - Pubkey or the unchanged argument, or `Error::InvalidInput` for an unreadable config
+fn resolve_alias_argument(parsed: &ParsedArgs, argument: &str) -> Result<String, Error> {
+    if parse_pubkey(argument).is_ok() {
+        return Ok(argument.to_string());
+    }
+    let config = Config::load(&config_path(parsed))?;
+    Ok(config.validators.get(argument).cloned().unwrap_or_else(|| argument.to_string()))
+}
+
+/// Returns the address labels for text output; an unreadable config falls back to the built-in labels
+fn address_labels(parsed: &ParsedArgs) -> Labels {
+    match Config::load(&config_path(parsed)).and_then(|config| Labels::from_config(&config)) {
+        Ok(labels) => labels,
+        Err(e) => {
+            warn!("Using built-in address labels only: {}", e);
+            Labels::builtin()
+        }
+    }
+}
+
+/// Returns the `[notify]` settings of the config file, if any
+fn notify_config(parsed: &ParsedArgs) -> Option<NotifyConfig> {
+    Config::load(&config_path(parsed)).ok()?.notify
+}
+
+/// Delivers a notification when `[notify]` is configured
+///
+/// Delivery failures are reported as warnings and do not change the exit code.
+async fn notify(config: Option<&NotifyConfig>, event: &NotifyEvent) {
+    let Some(config) = config else {
+        return;
+    };
+    if let Err(e) = send_notification(config, event).await {
+        warn!("{}", e);
+    }
+}
+
+/// Pushes the metrics of this run when `--push-metrics` is given
+///
+/// Push failures are reported as warnings and do not change the exit code.
+async fn push_run_metrics(parsed: &ParsedArgs, validator: &str, metrics: &[Metric]) {
+    let Some(gateway_url) = parsed.value("push-metrics") else {
+        return;
+    };
+    let job = parsed.value("metrics-job").unwrap_or(DEFAULT_METRICS_JOB);
+    
+    if let Err(e) = push_metrics(gateway_url, job, &[("validator", validator)], metrics).await {
+        warn!("{}", e);
+    }
+}
+
+/// Prints the usage text to stderr
+fn print_usage(program: &str) {
+    eprintln!("{}: {} <operation> <validator_address> [additional_params]", i18n::text("usage"), program);
+    eprintln!("{}:", i18n::text("operations"));
+    for command in COMMANDS {
+        eprintln!("  {:<16} - {}", command.name, command.summary_text());
+    }
+    for example in first_examples() {
+        eprintln!("{}: {} {}", i18n::text("example"), program, example.args);
+    }
+    eprintln!("{}", i18n::message("usage-details", &[("program", &program)]));
+    eprintln!("{}", i18n::text("amount-note"));
+    eprintln!("{}:", i18n::text("options"));
+    eprintln!("  --skip-gossip-check            - {}", i18n::text("opt-skip-gossip-check"));
+    eprintln!("  --require-vote-account         - {}", i18n::text("opt-require-vote-account"));
+    eprintln!("  --require-recent-votes <slots> - {}", i18n::text("opt-require-recent-votes"));
+    eprintln!("  --min-activated-stake <sol>    - {}", i18n::text("opt-min-activated-stake"));
+    eprintln!("  --max-commission <pct>         - {}", i18n::text("opt-max-commission"));
+    eprintln!("  --require-uptime <pct>         - {}", i18n::text("opt-require-uptime"));
+    eprintln!("  --lookback-epochs <n>          - {}", i18n::text("opt-lookback-epochs"));
+    eprintln!("  --reject-delinquent            - {}", i18n::text("opt-reject-delinquent"));
+    eprintln!("  --config <path>                - {}", i18n::message("opt-config", &[("value", &default_config_path().display())]));
+    eprintln!("  --profile <name>               - {}", i18n::text("opt-profile"));
+    eprintln!("  --push-metrics <url>           - {}", i18n::text("opt-push-metrics"));
+    eprintln!("  --metrics-job <name>           - {}", i18n::message("opt-metrics-job", &[("value", &DEFAULT_METRICS_JOB)]));
+    eprintln!("  --output <text|json|csv>       - {}", i18n::text("opt-output"));
+    eprintln!("  --format <template>            - {}", i18n::text("opt-format"));
+    eprintln!("  --sign-only                    - {}", i18n::text("opt-sign-only"));
+    eprintln!("  --blockhash <blockhash>        - {}", i18n::text("opt-blockhash"));
+    eprintln!("  --priority-fee <fee>           - {}", i18n::text("opt-priority-fee"));
+    eprintln!("  --tx-version <legacy|0>        - {}", i18n::text("opt-tx-version"));
+    eprintln!("  --alt <table>                  - {}", i18n::text("opt-alt"));
+    eprintln!("  --explorer <name>              - {}", i18n::text("opt-explorer"));
+    eprintln!("  --qr                           - {}", i18n::text("opt-qr"));
+    eprintln!("  --window <24h|7d|all>          - {}", i18n::message("opt-window", &[("value", &DEFAULT_STATS_WINDOW)]));
+    eprintln!("  --days <n>                     - {}", i18n::message("opt-days", &[("value", &DEFAULT_HISTORY_DAYS)]));
+    eprintln!("  --epochs <first..last>         - {}", i18n::text("opt-epochs"));
+    eprintln!("  --share <percent>              - {}", i18n::text("opt-share"));
+    eprintln!("  --budget <amount>              - {}", i18n::text("opt-budget"));
+    eprintln!("  --weights <stake|equal|path>   - {}", i18n::text("opt-weights"));
+    eprintln!("  --payer <keypair_or_pubkey>    - {}", i18n::text("opt-payer"));
+    eprintln!("  --event <event>                - {}", i18n::text("opt-event"));
+    eprintln!("  --status <success|failed>      - {}", i18n::text("opt-status"));
+    eprintln!("  --limit <n>                    - {}", i18n::text("opt-limit"));
+    eprintln!("  --idempotency-key <key>        - {}", i18n::text("opt-idempotency-key"));
+    eprintln!("  --duplicate-window <window>    - {}", i18n::message("opt-duplicate-window", &[("value", &DEFAULT_DUPLICATE_WINDOW)]));
+    eprintln!("  --show-fiat <usd|eur>          - {}", i18n::text("opt-show-fiat"));
+    eprintln!("  --rpc-timeout <seconds>        - {}", i18n::message("opt-rpc-timeout", &[("value", &rpc::DEFAULT_RPC_TIMEOUT.as_secs())]));
+    eprintln!("  --rpc-header <'Name: value'>   - {}", i18n::text("opt-rpc-header"));
+    eprintln!("  --rpc-proxy <url>              - {}", i18n::text("opt-rpc-proxy"));
+    eprintln!("  --deadline <seconds>           - {}", i18n::text("opt-deadline"));
+    eprintln!("  --reference <url>              - {}", i18n::text("opt-reference"));
+    eprintln!("  --max-slot-lag <slots>         - {}", i18n::message("opt-max-slot-lag", &[("value", &DEFAULT_MAX_SLOT_LAG)]));
+    eprintln!("  --lang <en|ru>                 - {}", i18n::message("opt-lang", &[("value", &LANG_ENV_VAR)]));
+    eprintln!("  --audit-log <path>             - {}", i18n::text("opt-audit-log"));
+    eprintln!("  --nonce-account <pubkey>       - {}", i18n::text("opt-nonce-account"));
+    eprintln!("  --nonce-authority <keypair>    - {}", i18n::text("opt-nonce-authority"));
+    eprintln!("  --at-epoch-boundary            - {}", i18n::text("opt-at-epoch-boundary"));
+    eprintln!("  --at-epoch <epoch>             - {}", i18n::text("opt-at-epoch"));
+    eprintln!("  --memo <text>                  - {}", i18n::message("opt-memo", &[("value", &MAX_MEMO_LEN)]));
+    eprintln!("  --receipt <path>               - {}", i18n::text("opt-receipt"));
+    eprintln!("  --verify-delta                 - {}", i18n::text("opt-verify-delta"));
+    eprintln!("  --squads <multisig>            - {}", i18n::text("opt-squads"));
+    eprintln!("  --identity-keypair <keypair>   - {}", i18n::text("opt-identity-keypair"));
+    eprintln!("  --from-identity-file <path>    - {}", i18n::text("opt-from-identity-file"));
+    eprintln!("  --from-local-rpc               - {}", i18n::message("opt-from-local-rpc", &[("value", &LOCAL_RPC_URL)]));
+    eprintln!("  --fee-payer <keypair|pubkey>   - {}", i18n::text("opt-fee-payer"));
+    eprintln!("  --keypair <keypair>            - {}", i18n::text("opt-keypair"));
+    eprintln!("  --file <path>                  - {}", i18n::text("opt-file"));
+    eprintln!("  --interval <seconds>           - {}", i18n::message("opt-interval", &[("value", &DEFAULT_MONITOR_INTERVAL_SECS)]));
+    eprintln!("  --grace <5m|1h>                - {}", i18n::message("opt-grace", &[("value", &DEFAULT_GOSSIP_GRACE)]));
+    eprintln!("  --pairs <validator:amount,...> - {}", i18n::text("opt-pairs"));
+    eprintln!("  --state <path>                 - {}", i18n::text("opt-state"));
+    eprintln!("  --resume <path>                - {}", i18n::text("opt-resume"));
+    eprintln!("  --mint <pubkey>                - {}", i18n::text("opt-mint"));
+    eprintln!("  --amount <tokens>              - {}", i18n::text("opt-amount"));
+    eprintln!("  --stdin                        - {}", i18n::text("opt-stdin"));
+    eprintln!("  --sort <name|balance|stake>    - {}", i18n::text("opt-sort"));
+    eprintln!("  --consistent-snapshot          - {}", i18n::text("opt-consistent-snapshot"));
+    eprintln!("  --concurrency <n>              - {}", i18n::message("opt-concurrency", &[("value", &DEFAULT_BATCH_CONCURRENCY)]));
+    eprintln!("  --refresh <seconds>            - {}", i18n::message("opt-refresh", &[("value", &DEFAULT_REFRESH_SECS)]));
+    eprintln!("  --init-timeout <seconds>       - {}", i18n::message("opt-init-timeout", &[("value", &DEFAULT_INITIALIZATION_TIMEOUT_SECS)]));
+    eprintln!("  --metrics-port <port>          - {}", i18n::text("opt-metrics-port"));
+    eprintln!("  --nice                         - {}", i18n::text("opt-nice"));
+    eprintln!("  --rate-limit <req/s>           - {}", i18n::text("opt-rate-limit"));
+    eprintln!("  --rate-burst <n>               - {}", i18n::text("opt-rate-burst"));
+    eprintln!("  --gossip-cache-ttl <seconds>   - {}", i18n::message("opt-gossip-cache-ttl", &[("value", &rpc::DEFAULT_GOSSIP_CACHE_TTL.as_secs())]));
+    eprintln!("  --gossip-cache-file <path>     - {}", i18n::text("opt-gossip-cache-file"));
+    eprintln!("  --backend rpc|geyser           - {}", i18n::text("opt-backend"));
+    eprintln!("  --geyser-endpoint <url>        - {}", i18n::message("opt-geyser-endpoint", &[("value", &geyser::GEYSER_TOKEN_ENV)]));
+    eprintln!("  --url <rpc_url>                - {}", i18n::message("opt-url", &[("value", &rpc::DEFAULT_RPC_URL)]));
+    eprintln!("  --commitment <level>           - {}", i18n::text("opt-commitment"));
+    eprintln!("  --out <path>                   - {}", i18n::text("opt-out"));
+    eprintln!("  --approver <keypair>           - {}", i18n::text("opt-approver"));
+    eprintln!("  --approve <hash>               - {}", i18n::text("opt-approve"));
+    eprintln!("  --approval-ttl <seconds>       - {}", i18n::message("opt-approval-ttl", &[("value", &DEFAULT_APPROVAL_TTL_SECS)]));
+    eprintln!("  --require-approval-file <path> - {}", i18n::text("opt-require-approval-file"));
+    eprintln!("  -y, --yes                      - {}", i18n::text("opt-yes"));
+    eprintln!("  --seed-prefix <seed>           - {}", i18n::message("opt-seed-prefix", &[("value", &DEPOSIT_PDA_SEED_PREFIX)]));
+    eprintln!("  --program-id <pubkey>          - {}", i18n::text("opt-program-id"));
+    eprintln!("  --seed-spec <kind:value,...>   - {}", i18n::text("opt-seed-spec"));
+    eprintln!("  --strict                       - {}", i18n::text("opt-strict"));
+    eprintln!("  --override-budget              - {}", i18n::text("opt-override-budget"));
+    eprintln!("  --force                        - {}", i18n::text("opt-force"));
+    eprintln!("  --outfile <path>               - {}", i18n::text("opt-outfile"));
+    eprintln!("  --starts-with <prefix:count>   - {}", i18n::text("opt-starts-with"));
+    eprintln!("  --ignore-case                  - {}", i18n::text("opt-ignore-case"));
+    eprintln!("  --pda                          - {}", i18n::text("opt-pda"));
+    eprintln!("  --threads <n>                  - {}", i18n::text("opt-threads"));
+    eprintln!("  --trace-id <id>                - {}", i18n::text("opt-trace-id"));
+    eprintln!("  --timing                       - {}", i18n::text("opt-timing"));
+    eprintln!("  -v, -vv, --verbose             - {}", i18n::text("opt-verbose"));
+    eprintln!("  -q, --quiet                    - {}", i18n::text("opt-quiet"));
+    eprintln!("  -h, --help                     - {}", i18n::text("opt-help"));
+}
+
+/// First example of each operation, in the order of `EXAMPLES`
+fn first_examples() -> Vec<&'static Example> {
+    let mut examples: Vec<&Example> = Vec::new();
+    for example in EXAMPLES {
+        if !examples.iter().any(|shown| shown.command == example.command) {
+            examples.push(example);
+        }
+    }
+    examples
+}
+
+/// Checks the pda-fund-many flags and loads the state file of `--resume`
+///
+/// Transactions a previous run left unconfirmed are looked up and the file is updated with what was found.
+async fn load_fund_many_state(parsed: &ParsedArgs, rpc: &dyn SolanaRpc) -> Result<Option<(FundingRunState, PathBuf)>, Error> {
+    // Packed transactions are plain legacy transactions signed online with a recent blockhash
+    for flag in ["sign-only", "nonce-account", "tx-version", "alt"] {
+        if parsed.has(flag) {
+            return Err(Error::InvalidInput(format!("--{} is not supported by pda-fund-many", flag)));
+        }
+    }
+    
+    let Some(path) = parsed.value("resume").map(PathBuf::from) else {
+        if let Some(path) = parsed.value("state").filter(|path| Path::new(path).exists()) {
+            return Err(Error::InvalidInput(format!("State file {} already exists; continue its run with --resume {}", path, path)));
+        }
+        return Ok(None);
+    };
+    for flag in ["pairs", "file", "state"] {
+        if parsed.has(flag) {
+            return Err(Error::InvalidInput(format!("--{} cannot be combined with --resume; the transfers and progress are kept in {}", flag, path.display())));
+        }
+    }
+    let mut state = FundingRunState::load(&path)?;
+    resolve_unconfirmed(&mut state, rpc).await?;
+    state.save(&path)?;
+    Ok(Some((state, path)))
+}
+
+/// Error message of `funding_pairs` without `--pairs` or `--file`
+const NO_TRANSFERS: &str = "No transfers given";
+
+/// Reads the transfers of `--pairs` and `--file` and resolves their validator aliases
+fn funding_pairs(parsed: &ParsedArgs) -> Result<Vec<(Pubkey, u64)>, Error> {
+    let mut pairs = parsed.value("pairs").map(parse_funding_pairs).transpose()?.unwrap_or_default();
+    if let Some(path) = parsed.value("file") {
+        pairs.extend(read_funding_file(Path::new(path))?);
+    }
+    if pairs.is_empty() {
+        return Err(Error::InvalidInput(NO_TRANSFERS.to_string()));
+    }
+    let config = Config::load(&config_path(parsed))?;
+    pairs.iter().map(|(name, lamports)| Ok((config.resolve_validator(name)?.1, *lamports))).collect()
+}
+
+/// Reads, checks and packs the transfers of `pda-fund-many`, then asks for confirmation
+///
+/// With `--resume` the pending and failed transfers of the state file are sent, from the same funding wallet.
+async fn prepare_fund_many(args: &[String], parsed: &ParsedArgs, resume: Option<&FundingRunState>, rpc: &dyn SolanaRpc) -> Result<PreparedFundingBatch, Error> {
+    let transfers = match resume {
+        Some(state) => state.remaining(),
+        None => funding_pairs(parsed).map_err(|e| match e {
+            Error::InvalidInput(message) if message == NO_TRANSFERS => Error::InvalidInput(format!(
+                "{}. Usage: {} pda-fund-many [keypair_path] --pairs <validator:amount,...> | --file <transfers.csv> | --resume <run.json>",
+                message, args[0]
+            )),
+            e => e,
+        })?,
+    };
+    
+    let keypair_path = match args.get(2) {
+        Some(keypair_path) => keypair_path.clone(),
+        None => configured_keypair(parsed)?.ok_or_else(|| {
+            Error::InvalidInput(format!("pda-fund-many requires a keypair path; no funding wallet configured in {}", config_path(parsed).display()))
+        })?,
+    };
+    let signers = FundingSigners {
+        funder: keypair_path,
+        fee_payer: parsed.value("fee-payer").map(str::to_string),
+        keypairs: parsed.values("keypair").into_iter().map(str::to_string).collect(),
+    };
+    
+    let prepared = prepare_funding_batch(&transfers, &signers, &funding_policy(parsed)?, rpc).await?;
+    if let Some(state) = resume.filter(|state| state.payer != prepared.payer.to_string()) {
+        return Err(Error::InvalidInput(format!(
+            "The state file was written for funding wallet {}, not {}; resume it with the same wallet",
+            state.payer, prepared.payer
+        )));
+    }
+    confirm_funding_batch(parsed, &prepared)?;
+    if signers.is_interactive() {
+        info!("Approve each transaction on your hardware wallet when prompted");
+    }
+    Ok(prepared)
+}
+
+/// Prints what a packed funding will do and asks for confirmation unless `--yes` is given
+fn confirm_funding_batch(parsed: &ParsedArgs, prepared: &PreparedFundingBatch) -> Result<(), Error> {
+    eprintln!("{}", i18n::text("funding-summary"));
+    eprintln!("{}", i18n::message("summary-transfers", &[("transfers", &prepared.transfers.len()), ("transactions", &prepared.transaction_count)]));
+    eprintln!("{}", i18n::message("summary-total", &[("amount", &Amount::Lamports(prepared.total_lamports))]));
+    eprintln!("{}", i18n::message("summary-fee", &[("fee", &prepared.fee_lamports)]));
+    eprintln!("{}", i18n::message("summary-payer-after", &[("amount", &Amount::Lamports(prepared.payer_balance_after))]));
+    if assume_yes(parsed) {
+        return Ok(());
+    }
+    let prompt = i18n::message(
+        "confirm-batch",
+        &[
+            ("amount", &format_sol(prepared.total_lamports)),
+            ("payer", &address_labels(parsed).format(&prepared.payer)),
+            ("count", &prepared.transfers.len()),
+        ],
+    );
+    if !confirm(&prompt) {
+        return Err(Error::FundingCancelled("Not confirmed; nothing was sent (use --yes in scripts)".to_string()));
+    }
+    Ok(())
+}
+
+/// Handles `pda-fund-many [keypair_path] --pairs <validator:amount,...> | --file <transfers.csv> | --resume <run.json>`
+async fn run_fund_many_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    preflight_compatibility("pda-fund-many", parsed, output).await;
+    let rpc = rpc_client(None);
+    let timeout = Duration::from_secs(DEFAULT_CONFIRMATION_TIMEOUT_SECS);
+    let mut state = match load_fund_many_state(parsed, &rpc).await {
+        Ok(state) => state,
+        Err(e) => fund_many_failed(output, &e),
+    };
+    // Transfers of an earlier run that are not sent again
+    let (skipped_confirmed, skipped_unconfirmed) = state
+        .as_ref()
+        .map(|(state, _)| (state.count(TransferStatus::Confirmed), state.count(TransferStatus::Unconfirmed)))
+        .unwrap_or_default();
+    if let Some((resumed, path)) = state.as_ref().filter(|(resumed, _)| resumed.remaining().is_empty()) {
+        finish_resumed_fund_many(output, resumed, path);
+        return;
+    }
+    
+    let result = match prepare_fund_many(args, parsed, state.as_ref().map(|(state, _)| state), &rpc).await {
+        Ok(prepared) => {
+            let sent = match (state.as_mut(), parsed.value("state")) {
+                (Some((state, path)), _) => send_funding_batch_with_state(&prepared, timeout, state, path, &rpc).await,
+                (None, Some(path)) => {
+                    let mut new_state = FundingRunState::new(&prepared.payer, &prepared.transfers);
+                    let sent = send_funding_batch_with_state(&prepared, timeout, &mut new_state, Path::new(path), &rpc).await;
+                    state = Some((new_state, PathBuf::from(path)));
+                    sent
+                }
+                (None, None) => send_funding_batch(&prepared, timeout, &rpc).await,
+            };
+            sent.map(|sent| (prepared, sent))
+        }
+        Err(e) => Err(e),
+    };
+    let (prepared, sent) = match result {
+        Ok(result) => result,
+        Err(e) => fund_many_failed(output, &e),
+    };
+    report_fund_many("pda-fund-many", parsed, output, prepared, sent, state.as_ref(), (skipped_confirmed, skipped_unconfirmed)).await;
+}
+
+/// Records, notifies and prints the outcome of a sent batch, then exits with its status
+///
+/// Shared by `pda-fund-many` and `plan execute`; `skipped` counts the confirmed and unconfirmed
+/// transfers of an earlier run that were not sent again.
+async fn report_fund_many(
+    operation: &str,
+    parsed: &ParsedArgs,
+    output: OutputFormat,
+    prepared: PreparedFundingBatch,
+    sent: Vec<SentTransaction>,
+    state: Option<&(FundingRunState, PathBuf)>,
+    (skipped_confirmed, skipped_unconfirmed): (usize, usize),
+) {
+    let validators: Vec<Pubkey> = prepared.transfers.iter().map(|(validator_id, _)| *validator_id).collect();
+    let outcomes = attribute_outcomes(&validators, &sent);
+    // Every packed transaction has the same signers, so they all pay the same fee
+    let transaction_fee = prepared.fee_lamports / prepared.transaction_count as u64;
+    
+    // The fee of each confirmed transaction is recorded once, with its first transfer
+    let mut charged = Vec::new();
+    for (validator_id, outcome) in validators.iter().zip(&outcomes) {
+        remember_validator(validator_id);
+        let deposit_key = generate_deposit_pda(validator_id);
+        let result = match outcome.status {
+            TransferStatus::Confirmed => {
+                let signature = outcome.signature.clone().unwrap_or_default();
+                let first = !charged.contains(&signature);
+                charged.push(signature.clone());
+                Ok(FundingOutcome {
+                    payer: prepared.payer.to_string(),
+                    signature,
+                    amount_lamports: outcome.amount_lamports,
+                    fee_lamports: if first { transaction_fee } else { 0 },
+                    warnings: Vec::new(),
+                    policy_checks: Vec::new(),
+                    creates_account: false,
+                    memo: None,
+                    epoch: prepared.epoch,
+                })
+            }
+            TransferStatus::Failed | TransferStatus::Pending => Err(Error::TransactionFailed(outcome.error.clone().unwrap_or_default())),
+            TransferStatus::Unconfirmed => Err(Error::ConfirmationTimeout(outcome.error.clone().unwrap_or_default())),
+        };
+        // An unconfirmed transfer may have landed, so it keeps counting for the budget and the duplicate check until settled
+        let attempt = outcome.signature.clone().filter(|_| outcome.status == TransferStatus::Unconfirmed).map(|signature| FundingAttempt {
+            payer: prepared.payer.to_string(),
+            signature,
+            amount_lamports: outcome.amount_lamports,
+            epoch: prepared.epoch,
+        });
+        record_funding(validator_id, &deposit_key, &result, attempt.as_ref(), None);
+        let event = match &result {
+            Ok(funded) => NotifyEvent::FundingLanded {
+                validator: outcome.validator.clone(),
+                pda: outcome.pda.clone(),
+                amount_lamports: funded.amount_lamports,
+                signature: funded.signature.clone(),
+            },
+            Err(e) => NotifyEvent::FundingFailed { validator: outcome.validator.clone(), pda: outcome.pda.clone(), error: e.to_string() },
+        };
+        notify(notify_config(parsed).as_ref(), &event).await;
+    }
+    
+    let confirmed: Vec<&TransferOutcome> = outcomes.iter().filter(|outcome| outcome.status == TransferStatus::Confirmed).collect();
+    let retry = retry_transfers(&outcomes);
+    if output == OutputFormat::Json {
+        let mut result = serde_json::json!({
+            "payer": prepared.payer.to_string(),
+            "transactions": sent.len(),
+            "confirmed_lamports": confirmed.iter().map(|outcome| outcome.amount_lamports).sum::<u64>(),
+            "transfers": outcomes,
+        });
+        if !retry.is_empty() {
+            result["retry_pairs"] = format_funding_pairs(&retry).into();
+        }
+        if let Some((_, path)) = state {
+            result["state"] = path.display().to_string().into();
+            result["skipped_confirmed"] = skipped_confirmed.into();
+            result["skipped_unconfirmed"] = skipped_unconfirmed.into();
+        }
+        if confirmed.len() == outcomes.len() {
+            println!("{}", JsonReport::success(operation, result, prepared.warnings).to_json());
+        } else {
+            let e = Error::TransactionFailed(format!("{} of {} transfers were not confirmed", outcomes.len() - confirmed.len(), outcomes.len()));
+            println!("{}", JsonReport::failure(operation, result, prepared.warnings, &e).to_json());
+        }
+    } else {
+        for warning in &prepared.warnings {
+            println!("⚠ {}", i18n::warning_text(warning));
+        }
+        println!("{:<44}  {:>16}  {:<11}  {}", i18n::text("column-validator"), i18n::text("column-amount-sol"), i18n::text("column-status"), i18n::text("column-signature"));
+        for outcome in &outcomes {
+            let mut row = format!(
+                "{:<44}  {:>16}  {:<11}  {}",
+                outcome.validator,
+                format_sol(outcome.amount_lamports),
+                outcome.status,
+                outcome.signature.as_deref().unwrap_or("-")
+            );
+            if let Some(error) = &outcome.error {
+                row.push_str(&format!("  {}: {}", i18n::text("error-lower"), i18n::localize(error)));
+            }
+            println!("{}", row);
+        }
+        println!(
+            "{}",
+            i18n::message(
+                "batch-confirmed",
+                &[
+                    ("confirmed", &confirmed.len()),
+                    ("total", &outcomes.len()),
+                    ("amount", &format_sol(confirmed.iter().map(|outcome| outcome.amount_lamports).sum())),
+                    ("transactions", &sent.len()),
+                ],
+            )
+        );
+        if skipped_confirmed > 0 {
+            println!("{}", i18n::message("skipped-confirmed", &[("count", &skipped_confirmed)]));
+        }
+        if skipped_unconfirmed > 0 {
+            println!("{}", i18n::message("skipped-unconfirmed", &[("count", &skipped_unconfirmed)]));
+        }
+        match state {
+            Some((_, path)) if !retry.is_empty() => println!("{}", i18n::message("retry-failed", &[("flags", &format!("--resume {}", path.display()))])),
+            None if !retry.is_empty() => println!("{}", i18n::message("retry-failed", &[("flags", &format!("--pairs {}", format_funding_pairs(&retry)))])),
+            _ => {}
+        }
+        if outcomes.iter().any(|outcome| outcome.status == TransferStatus::Unconfirmed) {
+            println!("{}", i18n::text("unconfirmed-may-land"));
+        }
+    }
+    
+    if confirmed.len() != outcomes.len() || skipped_unconfirmed > 0 {
+        if output == OutputFormat::Text {
+            print_trace_id();
+        }
+        // Transfers that may still land take precedence, as resending them could fund twice
+        let unconfirmed = skipped_unconfirmed > 0 || outcomes.iter().any(|outcome| outcome.status == TransferStatus::Unconfirmed);
+        exit(if unconfirmed { EXIT_CONFIRMATION_TIMEOUT } else { EXIT_TRANSACTION_FAILED });
+    }
+}
+
+/// Reports a pda-fund-many run that failed before anything was sent and exits
+fn fund_many_failed(output: OutputFormat, e: &Error) -> ! {
+    if output == OutputFormat::Json {
+        println!("{}", JsonReport::failure("pda-fund-many", serde_json::json!({}), Vec::new(), e).to_json());
+    } else {
+        eprintln!("{}", i18n::message("fund-many-failed", &[("error", &i18n::error_text(e))]));
+        print_trace_id();
+    }
+    exit(exit_code(e));
+}
+
+/// Reports a resumed pda-fund-many run with nothing left to send
+///
+/// Exits with the confirmation timeout code while transfers of the earlier run are still unconfirmed.
+fn finish_resumed_fund_many(output: OutputFormat, state: &FundingRunState, path: &Path) {
+    let confirmed = state.count(TransferStatus::Confirmed);
+    let unconfirmed = state.count(TransferStatus::Unconfirmed);
+    if output == OutputFormat::Json {
+        let result = serde_json::json!({
+            "payer": state.payer,
+            "state": path.display().to_string(),
+            "transfers": state.transfers,
+        });
+        println!("{}", JsonReport::success("pda-fund-many", result, Vec::new()).to_json());
+    } else {
+        println!("{}", i18n::message("nothing-left", &[("path", &path.display()), ("confirmed", &confirmed), ("unconfirmed", &unconfirmed)]));
+        if unconfirmed > 0 {
+            println!("{}", i18n::text("unconfirmed-may-land"));
+        }
+    }
+    if unconfirmed > 0 {
+        exit(EXIT_CONFIRMATION_TIMEOUT);
+    }
+}
+
+/// Fills in or checks the validator parameter with the identity from `--identity-keypair`, `--from-identity-file` or `--from-local-rpc`
+async fn identity_arguments(args: Vec<String>, parsed: &ParsedArgs) -> Result<Vec<String>, Error> {
+    let flags: Vec<&str> = ["identity-keypair", "from-identity-file", "from-local-rpc"].into_iter().filter(|flag| parsed.has(flag)).collect();
+    let flag = match flags.as_slice() {
+        [] => return Ok(args),
+        [flag] => *flag,
+        _ => return Err(Error::InvalidInput("--identity-keypair, --from-identity-file and --from-local-rpc cannot be combined".to_string())),
+    };
+    let operation = args.get(1).map(String::as_str).unwrap_or_default();
+    // Position of the validator and the number of parameters from there on when all optional ones are given
+    let (position, arity) = match operation {
+        "pda-fund-address" => (2, 3),
+        _ if flag == "identity-keypair" => {
+            return Err(Error::InvalidInput("--identity-keypair is only supported by pda-fund-address".to_string()));
+        }
+        "pda-address" | "pda-balance" | "validator-status" => (2, 1),
+        "pda-watch" | "pda-fund-token" => (2, 2),
+        "plan" if args.get(2).map(String::as_str) == Some("create") => (3, 3),
+        _ => {
+            return Err(Error::InvalidInput(format!(
+                "--{} is only supported by pda-address, pda-balance, pda-fund-address, pda-fund-token, pda-watch, validator-status and plan create",
+                flag
+            )));
+        }
+    };
+    let value = parsed.value(flag).unwrap_or_default();
+    let (identity, source) = match flag {
+        // Only the pubkey is read; the identity keypair never signs the funding
+        "identity-keypair" => (load_signer(value)?.pubkey(), "the identity keypair"),
+        "from-identity-file" => (read_identity_pubkey(Path::new(value))?, "the identity file"),
+        _ => (rpc::node_identity(&rpc_client(Some(LOCAL_RPC_URL))).await?, "the identity of the local node"),
+    };
+    debug!(identity = %identity, flag, "Validator identity");
+    let parameters = fill_validator(&args[position..], arity, &identity, source)?;
+    Ok(args[..position].iter().cloned().chain(parameters).collect())
+}
+
+/// Reads and checks the arguments of `pda-fund-token`, prepares the deposit and asks for confirmation
+async fn prepare_fund_token(args: &[String], parsed: &ParsedArgs, rpc: &dyn SolanaRpc) -> Result<(Pubkey, PreparedTokenFunding), Error> {
+    // Token deposits are plain legacy transactions signed online with a recent blockhash
+    for flag in ["sign-only", "nonce-account", "tx-version", "alt"] {
+        if parsed.has(flag) {
+            return Err(Error::InvalidInput(format!("--{} is not supported by pda-fund-token", flag)));
+        }
+    }
+    
+    let usage = || {
+        Error::InvalidInput(format!(
+            "Usage: {} pda-fund-token <validator_or_alias> [keypair_path] --mint <mint> --amount <tokens>",
+            args[0]
+        ))
+    };
+    let validator = args.get(2).ok_or_else(usage)?;
+    let mint = parsed.value("mint").ok_or_else(usage)?;
+    let amount = parsed.value("amount").ok_or_else(usage)?;
+    let mint = parse_pubkey(mint).map_err(|e| Error::InvalidInput(format!("Invalid mint: {}", e)))?;
+    let validator_id = Config::load(&config_path(parsed))?.resolve_validator(validator)?.1;
+    
+    let keypair_path = match args.get(3) {
+        Some(keypair_path) => keypair_path.clone(),
+        None => configured_keypair(parsed)?.ok_or_else(|| {
+            Error::InvalidInput(format!("pda-fund-token requires a keypair path; no funding wallet configured in {}", config_path(parsed).display()))
+        })?,
+    };
+    let signers = FundingSigners {
+        funder: keypair_path,
+        fee_payer: parsed.value("fee-payer").map(str::to_string),
+        keypairs: parsed.values("keypair").into_iter().map(str::to_string).collect(),
+    };
+    
+    let prepared = prepare_token_funding(&validator_id, &mint, amount, &signers, &funding_policy(parsed)?, rpc).await?;
+    confirm_token_funding(parsed, &prepared)?;
+    if signers.is_interactive() {
+        info!("Approve the transaction on your hardware wallet when prompted");
+    }
+    Ok((validator_id, prepared))
+}
+
+/// Prints what a token deposit will do and asks for confirmation unless `--yes` is given
+fn confirm_token_funding(parsed: &ParsedArgs, prepared: &PreparedTokenFunding) -> Result<(), Error> {
+    let amount = format_token_amount(prepared.amount, prepared.decimals);
+    eprintln!("{}", i18n::text("funding-summary"));
+    eprintln!("{}", i18n::message("summary-tokens", &[("amount", &amount), ("base_units", &prepared.amount), ("decimals", &prepared.decimals)]));
+    eprintln!("{}", i18n::message("summary-mint", &[("mint", &prepared.mint)]));
+    eprintln!("{}", i18n::message("summary-token-account", &[("account", &prepared.token_account)]));
+    eprintln!("{}", i18n::message("summary-fee", &[("fee", &prepared.fee_lamports)]));
+    eprintln!("{}", i18n::message("summary-payer-tokens-after", &[("amount", &format_token_amount(prepared.payer_token_balance_after, prepared.decimals))]));
+    if prepared.creates_token_account {
+        eprintln!("{}", i18n::message("summary-creates-token-account", &[("rent", &prepared.rent_lamports)]));
+    }
+    if assume_yes(parsed) {
+        return Ok(());
+    }
+    let prompt = i18n::message(
+        "confirm-token-transfer",
+        &[("amount", &amount), ("payer", &address_labels(parsed).format(&prepared.payer)), ("pda", &prepared.pda)],
+    );
+    if !confirm(&prompt) {
+        return Err(Error::FundingCancelled("Not confirmed; nothing was sent (use --yes in scripts)".to_string()));
+    }
+    Ok(())
+}
+
+/// Records a sent token deposit, or a failed attempt, in the audit log
+fn record_token_funding(validator_id: &Pubkey, mint: &str, result: &Result<TokenFundingOutcome, Error>) {
+    let entry = match result {
+        Ok(outcome) => AuditEntry {
+            validator: Some(validator_id.to_string()),
+            payer: Some(outcome.payer.clone()),
+            recipient: Some(outcome.token_account.clone()),
+            fee_lamports: Some(outcome.fee_lamports),
+            signature: Some(outcome.signature.clone()),
+            message: Some(format!("{} tokens of mint {}", format_token_amount(outcome.amount, outcome.decimals), outcome.mint)),
+            ..AuditEntry::new(audit::EVENT_PDA_FUND_TOKEN, audit::STATUS_SUCCESS)
+        },
+        Err(Error::FundingCancelled(_) | Error::NotInGossip(_) | Error::StrictWarning(_)) => return,
+        Err(e) => AuditEntry {
+            validator: Some(validator_id.to_string()),
+            message: Some(format!("Mint {}: {}", mint, e)),
+            ..AuditEntry::new(audit::EVENT_PDA_FUND_TOKEN, audit::STATUS_FAILED)
+        },
+    };
+    if let Err(e) = audit::append_entry(&audit::audit_log_path(), &entry) {
+        warn!("Unable to write audit log: {}", e);
+    }
+}
+
+/// Handles `pda-fund-token <validator_or_alias> [keypair_path] --mint <mint> --amount <tokens>`
+async fn run_fund_token_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    preflight_compatibility("pda-fund-token", parsed, output).await;
+    let rpc = rpc_client(None);
+    let (validator_id, result) = match prepare_fund_token(args, parsed, &rpc).await {
+        Ok((validator_id, prepared)) => (Some(validator_id), send_token_funding(prepared, &rpc).await),
+        Err(e) => (None, Err(e)),
+    };
+    if let Some(validator_id) = &validator_id {
+        record_token_funding(validator_id, parsed.value("mint").unwrap_or_default(), &result);
+        remember_validator(validator_id);
+    }
+    
+    match result {
+        Ok(outcome) if output == OutputFormat::Json => {
+            let mut fields = serde_json::json!({
+                "payer": outcome.payer,
+                "signature": outcome.signature,
+                "mint": outcome.mint,
+                "token_account": outcome.token_account,
+                "amount": outcome.amount,
+                "decimals": outcome.decimals,
+                "fee_lamports": outcome.fee_lamports,
+                "creates_token_account": outcome.creates_token_account,
+            });
+            add_signature_url(parsed, &mut fields, &outcome.signature);
+            print_json_success("pda-fund-token", fields, outcome.warnings, parsed.has("strict"));
+        }
+        Ok(outcome) => {
+            for warning in &outcome.warnings {
+                println!("⚠ {}", i18n::warning_text(warning));
+            }
+            if outcome.creates_token_account {
+                println!("{}", i18n::message("token-account-created", &[("account", &outcome.token_account)]));
+            }
+            println!(
+                "{}",
+                i18n::message(
+                    "tokens-transferred",
+                    &[("amount", &format_token_amount(outcome.amount, outcome.decimals)), ("account", &outcome.token_account), ("fee", &outcome.fee_lamports)],
+                )
+            );
+            println!("{}", i18n::message("transaction-signature", &[("signature", &outcome.signature)]));
+            print_signature_link(parsed, &outcome.signature);
+            exit_if_strict(parsed.has("strict"), &outcome.warnings);
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("pda-fund-token", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}", i18n::message("token-funding-failed", &[("error", &i18n::error_text(&e))]));
+            print_trace_id();
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Handles `pda-batch <pda-address|pda-balance> [validator_or_alias ...]`
+async fn run_batch_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat, template: Option<&OutputTemplate>) {
+    let operation = match args.get(2).map(String::as_str) {
+        Some(operation @ ("pda-address" | "pda-balance")) => operation,
+        _ => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::text("unknown-batch-operation"));
+            eprintln!(
+                "{}: {} pda-batch <pda-address|pda-balance> [validator_or_alias ...] [--file <path>] [--stdin] [--sort name|balance|stake] [--consistent-snapshot] [--concurrency <n>]",
+                i18n::text("usage"),
+                args[0]
+            );
+            eprintln!("{}", i18n::text("batch-aliases-note"));
+            exit(EXIT_INVALID_INPUT);
+        }
+    };
+    
+    if parsed.has("out") && output != OutputFormat::Csv {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("out-needs-csv", &[("operation", &"pda-batch")]));
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    let sort = match parsed.value("sort").map(SortKey::parse).transpose() {
+        Ok(sort) => sort.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    let validators = match batch_validators(&args[3..], parsed) {
+        Ok(validators) => validators,
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    let mut options = BatchOptions {
+        check_gossip: !parsed.has("skip-gossip-check"),
+        fetch_balance: operation == "pda-balance" || sort == SortKey::Balance,
+        fetch_stake: sort == SortKey::Stake,
+        min_context_slot: None,
+    };
+    if parsed.has("consistent-snapshot") && !options.fetch_balance {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("consistent-snapshot-needs-balances"));
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    let concurrency = match parsed.value("concurrency").map(parse_concurrency).transpose() {
+        Ok(concurrency) => concurrency.unwrap_or(DEFAULT_BATCH_CONCURRENCY),
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    preflight_compatibility("pda-batch", parsed, output).await;
+    let rpc: Arc<dyn SolanaRpc> = Arc::new(rpc_client(None));
+    if parsed.has("consistent-snapshot") {
+        match snapshot_slot(rpc.as_ref()).await {
+            Ok(slot) => options.min_context_slot = Some(slot),
+            Err(e) => {
+                eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                exit(exit_code(&e));
+            }
+        }
+    }
+    for (_, validator_id) in &validators {
+        remember_validator(validator_id);
+    }
+    let requested = validators.len();
+    // Ctrl-C and --deadline cut the batch short; the rows collected so far are still reported
+    let deadline = parsed.value("deadline").and_then(|value| rpc::parse_timeout(value, "deadline").ok());
+    let (mut entries, stopped) = collect_entries_until(validators, options, concurrency, rpc, run_stop(deadline, true)).await;
+    if operation == "pda-balance" {
+        for entry in &entries {
+            let deposit_key: Pubkey = entry.pda.parse().expect("batch entries hold valid PDAs");
+            let metrics = balance_metrics(&deposit_key, entry.balance_lamports, unix_timestamp());
+            push_run_metrics(parsed, &entry.validator, &metrics).await;
+        }
+    }
+    sort_entries(&mut entries, sort);
+    
+    if output == OutputFormat::Csv {
+        let csv = entries_to_csv(&entries, unix_timestamp());
+        match parsed.value("out") {
+            Some(path) => {
+                if let Err(e) = write_atomic(Path::new(path), csv.as_bytes()) {
+                    eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                    exit(exit_code(&e));
+                }
+                eprintln!("{}", i18n::message("written-to", &[("path", &path)]));
+            }
+            None => print!("{}", csv),
+        }
+    } else if output == OutputFormat::Json {
+        let warnings = if options.check_gossip {
+            entries.iter().filter_map(gossip_warning).collect()
+        } else {
+            vec![Warning::new(WARN_GOSSIP_CHECK_SKIPPED, "Gossip check skipped")]
+        };
+        let mut result = match options.min_context_slot {
+            Some(slot) => serde_json::json!({ "entries": entries, "snapshot_slot": slot }),
+            None => serde_json::json!({ "entries": entries }),
+        };
+        if let Some(stop) = &stopped {
+            result["stopped"] = stop.to_string().into();
+            result["requested"] = requested.into();
+        }
+        // Failed rows exit with 1 below, which takes precedence over strict mode
+        let strict = parsed.has("strict") && entries.iter().all(|entry| entry.error.is_none());
+        print_json_success(&format!("pda-batch {}", operation), result, warnings, strict);
+    } else if let Some(template) = template {
+        for entry in &entries {
+            let mut values = serde_json::json!(entry);
+            values["balance_sol"] = entry.balance_lamports.map(format_sol).into();
+            println!("{}", template.render(&values));
+        }
+    } else {
+        print_batch_table(&entries, &options);
+    }
+    if let Some(slot) = options.min_context_slot
+        && output != OutputFormat::Json
+    {
+        eprintln!("{}", i18n::message("consistent-snapshot", &[("slot", &slot)]));
+    }
+    
+    if let Some(stop) = stopped {
+        eprintln!("{}: {}", stop.message(), i18n::message("batch-partial", &[("collected", &entries.len()), ("requested", &requested)]));
+        exit(stop.exit_code());
+    }
+    if entries.iter().any(|entry| entry.error.is_some()) {
+        exit(EXIT_FAILURE);
+    }
+    exit_if_strict(parsed.has("strict"), &entries.iter().filter_map(gossip_warning).collect::<Vec<_>>());
+}
+
+/// Handles `pda-balance-history <validator_or_alias> [--days <n>]`
+async fn run_balance_history_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let Some(validator) = args.get(2) else {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("needs-validator", &[("operation", &"pda-balance-history")]));
+        eprintln!("{}: {} pda-balance-history <validator_or_alias> [--days <n>] [--output text|json|csv] [--out <path>]", i18n::text("usage"), args[0]);
+        exit(EXIT_INVALID_INPUT);
+    };
+    if parsed.has("out") && output != OutputFormat::Csv {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("out-needs-csv", &[("operation", &"pda-balance-history")]));
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    let request = parse_history_days(parsed.value("days"))
+        .and_then(|days| Ok((days, Config::load(&config_path(parsed))?.resolve_validator(validator)?.1)));
+    let result = match request {
+        Ok((days, validator_id)) => {
+            preflight_compatibility("pda-balance-history", parsed, output).await;
+            let since = unix_timestamp().saturating_sub(days * 86_400);
+            balance_history(&validator_id, since, &rpc_client(None)).await.map(|history| (days, history))
+        }
+        Err(e) => Err(e),
+    };
+    let (days, history) = match result {
+        Ok(result) => result,
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("pda-balance-history", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    match output {
+        OutputFormat::Csv => {
+            let csv = history.to_csv();
+            match parsed.value("out") {
+                Some(path) => {
+                    if let Err(e) = write_atomic(Path::new(path), csv.as_bytes()) {
+                        eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+                        exit(exit_code(&e));
+                    }
+                    eprintln!("{}", i18n::message("written-to", &[("path", &path)]));
+                }
+                None => print!("{}", csv),
+            }
+        }
+        OutputFormat::Json => {
+            let mut fields = serde_json::json!(history);
+            fields["days"] = days.into();
+            println!("{}", JsonReport::success("pda-balance-history", fields, Vec::new()).to_json());
+        }
+        OutputFormat::Text => print_balance_history(parsed, &history, days),
+    }
+}
+
+/// Parses `--days` of `pda-balance-history` (default: `DEFAULT_HISTORY_DAYS`)
+fn parse_history_days(value: Option<&str>) -> Result<u64, Error> {
+    let Some(value) = value else {
+        return Ok(DEFAULT_HISTORY_DAYS);
+    };
+    match value.trim().parse::<u64>() {
+        Ok(days) if (1..=3_650).contains(&days) => Ok(days),
+        _ => Err(Error::InvalidInput(format!("Invalid --days '{}': expected a number of days from 1 to 3650", value))),
+    }
+}
+
+/// Prints the text report of `pda-balance-history`
+fn print_balance_history(parsed: &ParsedArgs, history: &BalanceHistory, days: u64) {
+    let labels = address_labels(parsed);
+    let validator = history.validator.parse::<Pubkey>().map(|pubkey| labels.format(&pubkey)).unwrap_or_else(|_| history.validator.clone());
+    println!("{}", i18n::message("history-validator", &[("value", &validator)]));
+    println!("{}", i18n::message("history-deposit-pda", &[("value", &history.pda)]));
+    println!("{}", i18n::message("history-window", &[("days", &days), ("since", &audit::format_timestamp(history.since))]));
+    if history.points.is_empty() {
+        println!("{}", i18n::text("history-no-changes"));
+    } else {
+        println!();
+        println!(
+            "{:<19}  {:>12}  {:<10}  {:>16}  {:>16}  {}",
+            i18n::text("column-time"),
+            i18n::text("column-slot"),
+            i18n::text("column-kind"),
+            i18n::text("column-change-sol"),
+            i18n::text("column-balance-sol"),
+            i18n::text("heading-signature")
+        );
+        for point in &history.points {
+            let time = point.block_time.and_then(|time| u64::try_from(time).ok()).map(audit::format_timestamp).unwrap_or_else(|| "-".to_string());
+            let sign = if point.change_lamports < 0 { "-" } else { "+" };
+            let change = format!("{}{}", sign, format_sol(point.change_lamports.unsigned_abs()));
+            println!(
+                "{:<19}  {:>12}  {:<10}  {:>16}  {:>16}  {}",
+                time,
+                point.slot,
+                point.kind,
+                change,
+                format_sol(point.balance_lamports),
+                point.signature
+            );
+        }
+    }
+    println!();
+    println!("{}", i18n::message("history-deposited", &[("value", &Amount::Lamports(history.deposited_lamports))]));
+    println!("{}", i18n::message("history-withdrawn", &[("value", &Amount::Lamports(history.withdrawn_lamports))]));
+    if history.skipped > 0 {
+        println!("{}", i18n::message("history-skipped", &[("count", &history.skipped)]));
+    }
+}
+
+/// Handles `revenue-report <validator_or_alias> --epochs <first..last> [--share <percent>]`
+async fn run_revenue_report_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let (Some(validator), Some(epochs)) = (args.get(2), parsed.value("epochs")) else {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("revenue-report-needs-epochs"));
+        eprintln!("{}: {} revenue-report <validator_or_alias> --epochs <first..last> [--share <percent>] [--output text|json]", i18n::text("usage"), args[0]);
+        exit(EXIT_INVALID_INPUT);
+    };
+    
+    let request = EpochRange::parse(epochs).and_then(|range| {
+        let share = parsed.value("share").map(|value| parse_percent(value, "share")).transpose()?;
+        Ok((range, share, Config::load(&config_path(parsed))?.resolve_validator(validator)?.1))
+    });
+    let result = match request {
+        Ok((range, share, validator_id)) => {
+            preflight_compatibility("revenue-report", parsed, output).await;
+            revenue_report(&validator_id, range, share, &rpc_client(None)).await
+        }
+        Err(e) => Err(e),
+    };
+    let report = match result {
+        Ok(report) => report,
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("revenue-report", serde_json::json!({ "validator": validator }), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    if output == OutputFormat::Json {
+        println!("{}", JsonReport::success("revenue-report", serde_json::json!(report), Vec::new()).to_json());
+    } else {
+        print_revenue_report(parsed, &report);
+    }
+    // Like a FAIL verdict of validator-status, a shortfall over the range is reported with status 1
+    if report.status == Some(Reconciliation::Short) {
+        exit(EXIT_FAILURE);
+    }
+}
+
+/// Prints the text report of `revenue-report`
+fn print_revenue_report(parsed: &ParsedArgs, report: &RevenueReport) {
+    let labels = address_labels(parsed);
+    let validator = report.validator.parse::<Pubkey>().map(|pubkey| labels.format(&pubkey)).unwrap_or_else(|_| report.validator.clone());
+    println!("{}", i18n::message("field-validator", &[("value", &validator)]));
+    println!("{}", i18n::message("field-deposit-pda", &[("value", &report.pda)]));
+    println!("{}", i18n::message("revenue-vote-account", &[("value", &report.vote_account.as_deref().unwrap_or(i18n::text("revenue-no-vote-account")))]));
+    if let Some(share) = report.share_basis_points {
+        println!("{}", i18n::message("revenue-share", &[("share", &format_basis_points(share))]));
+    }
+    println!();
+    println!(
+        "{:>6}  {:>14}  {:>14}  {:>14}  {:>14}  {:>14}  {:>14}  {}",
+        i18n::text("column-epoch"),
+        i18n::text("column-opening-sol"),
+        i18n::text("column-deposited"),
+        i18n::text("column-claimed"),
+        i18n::text("column-closing-sol"),
+        i18n::text("column-rewards"),
+        i18n::text("column-expected"),
+        i18n::text("heading-status")
+    );
+    let optional_sol = |lamports: Option<u64>| lamports.map(format_sol).unwrap_or_else(|| "-".to_string());
+    for epoch in &report.epochs {
+        println!(
+            "{:>6}  {:>14}  {:>14}  {:>14}  {:>14}  {:>14}  {:>14}  {}",
+            epoch.epoch,
+            format_sol(epoch.opening_balance_lamports),
+            format_sol(epoch.deposited_lamports),
+            format_sol(epoch.claimed_lamports),
+            format_sol(epoch.closing_balance_lamports),
+            optional_sol(epoch.reward_lamports),
+            optional_sol(epoch.expected_lamports),
+            epoch.status.map_or("-", |status| status.label())
+        );
+    }
+    println!();
+    println!("{}", i18n::message("revenue-deposited", &[("value", &Amount::Lamports(report.deposited_lamports))]));
+    println!("{}", i18n::message("revenue-claimed", &[("value", &Amount::Lamports(report.claimed_lamports))]));
+    println!("{}", i18n::message("revenue-rewards", &[("value", &Amount::Lamports(report.reward_lamports))]));
+    if let (Some(expected), Some(status)) = (report.expected_lamports, report.status) {
+        println!("{}", i18n::message("revenue-expected", &[("value", &Amount::Lamports(expected)), ("status", &status.label())]));
+    }
+}
+
+/// Handles `allocate --budget <amount> --weights stake|equal|<weights.csv> [validator_or_alias ...] [--payer <keypair_or_pubkey>] [--out plan.yaml]`
+///
+/// Nothing is sent. With a funding wallet the fundings are also priced as a batch plan and checked
+/// against the wallet balance, and `--out` writes that plan for `plan execute`.
+async fn run_allocate_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let (Some(budget), Some(weights)) = (parsed.value("budget"), parsed.value("weights")) else {
+        eprintln!("{}: {}", i18n::text("error"), i18n::text("allocate-needs-budget"));
+        eprintln!(
+            "{}: {} allocate --budget <amount> --weights stake|equal|<weights.csv> [validator_or_alias ...] [--file <path>] [--payer <keypair_or_pubkey>] [--out plan.yaml]",
+            i18n::text("usage"),
+            args[0]
+        );
+        exit(EXIT_INVALID_INPUT);
+    };
+    
+    let result = match allocation_request(args, parsed, budget, weights) {
+        Ok(request) => run_allocation(request, parsed).await,
+        Err(e) => Err(e),
+    };
+    let (report, funding) = match result {
+        Ok(result) => result,
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("allocate", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    
+    let mut warnings = Vec::new();
+    if let Some(funding) = funding.as_ref().filter(|funding| funding.shortfall_lamports > 0) {
+        warnings.push(Warning::new(
+            WARN_WALLET_SHORTFALL,
+            format!(
+                "The funding wallet holds {} but the plan needs {} including fees; it is {} short",
+                Amount::Lamports(funding.balance_lamports),
+                Amount::Lamports(funding.plan.total_lamports + funding.plan.estimated_fee_lamports),
+                Amount::Lamports(funding.shortfall_lamports)
+            ),
+        ));
+    }
+    
+    if output == OutputFormat::Json {
+        let mut result = serde_json::json!(report);
+        if let Some(funding) = &funding {
+            result["payer"] = funding.plan.payer.clone().into();
+            result["payer_balance_lamports"] = funding.balance_lamports.into();
+            result["estimated_fee_lamports"] = funding.plan.estimated_fee_lamports.into();
+            result["transaction_count"] = funding.plan.transaction_count.into();
+            result["wallet_shortfall_lamports"] = funding.shortfall_lamports.into();
+            result["plan_hash"] = funding.plan_hash.clone().into();
+            if let Some(path) = parsed.value("out") {
+                result["plan"] = path.into();
+            }
+        }
+        print_json_success("allocate", result, warnings, parsed.has("strict"));
+    } else {
+        print_allocation(parsed, &report, funding.as_ref());
+        for warning in &warnings {
+            println!("⚠ {}", i18n::warning_text(warning));
+        }
+        exit_if_strict(parsed.has("strict"), &warnings);
+    }
+}
+
+/// Budget, weighted validators and funding wallet of an `allocate` run, checked before any RPC request
+struct AllocationRequest {
+    budget_lamports: u64,
+    weights: WeightSource,
+    /// Alias, identity and weight of each validator; stake weights are read later
+    validators: Vec<(Option<String>, Pubkey, u64)>,
+    payer: Option<Pubkey>,
+    priority_fee: Option<u64>,
+}
+
+/// Funding wallet side of an allocation: the batch plan of the fundings and whether the wallet covers it
+struct AllocationFunding {
+    plan: BatchPlan,
+    plan_hash: String,
+    balance_lamports: u64,
+    shortfall_lamports: u64,
+}
+
+/// Parses the budget, weights, validators and funding wallet of `allocate`
+fn allocation_request(args: &[String], parsed: &ParsedArgs, budget: &str, weights: &str) -> Result<AllocationRequest, Error> {
+    let budget_lamports = match parse_amount(budget) {
+        Ok(Amount::Lamports(lamports)) if lamports > 0 => lamports,
+        _ => return Err(Error::InvalidInput(format!("Invalid --budget '{}': expected an amount of SOL like 500", budget))),
+    };
+    let weights = WeightSource::parse(weights);
+    let validators = match &weights {
+        // The weights file lists the validators itself
+        WeightSource::File(path) => {
+            if args.len() > 2 || parsed.has("file") || parsed.has("stdin") {
+                return Err(Error::InvalidInput("With a weights file the validators are taken from it; do not list them again".to_string()));
+            }
+            let config = Config::load(&config_path(parsed))?;
+            read_weight_file(path)?
+                .into_iter()
+                .map(|(name, weight)| config.resolve_validator(&name).map(|(alias, validator_id)| (alias, validator_id, weight)))
+                .collect::<Result<Vec<_>, Error>>()?
+        }
+        _ => batch_validators(&args[2..], parsed)?.into_iter().map(|(alias, validator_id)| (alias, validator_id, 1)).collect(),
+    };
+    if validators.is_empty() {
+        return Err(Error::InvalidInput("No validators to allocate to".to_string()));
+    }
+    
+    let payer = match parsed.value("payer") {
+        Some(source) => Some(source.to_string()),
+        None => configured_keypair(parsed)?,
+    };
+    let payer = payer.map(|source| SignerPool::default().resolve(&source)).transpose()?;
+    if parsed.has("out") && payer.is_none() {
+        return Err(Error::InvalidInput(format!(
+            "allocate --out needs the funding wallet of the plan: pass --payer or configure one in {}",
+            config_path(parsed).display()
+        )));
+    }
+    Ok(AllocationRequest {
+        budget_lamports,
+        weights,
+        validators,
+        payer,
+        priority_fee: parsed.value("priority-fee").map(parse_priority_fee).transpose()?,
+    })
+}
+
+/// Reads the weights and balances of an allocation, then prices its fundings from the funding wallet
+async fn run_allocation(request: AllocationRequest, parsed: &ParsedArgs) -> Result<(AllocationReport, Option<AllocationFunding>), Error> {
+    let rpc = rpc_client(None);
+    let mut validators = request.validators;
+    if request.weights == WeightSource::Stake {
+        let identities: Vec<Pubkey> = validators.iter().map(|(_, validator_id, _)| *validator_id).collect();
+        for ((_, _, weight), stake) in validators.iter_mut().zip(stake_weights(&identities, &rpc).await?) {
+            *weight = stake;
+        }
+    }
+    let report = allocate(request.budget_lamports, &request.weights.label(), &validators, &rpc).await?;
+    
+    let Some(payer) = request.payer else {
+        return Ok((report, None));
+    };
+    let transfers = report.transfers()?;
+    if transfers.is_empty() {
+        if parsed.has("out") {
+            return Err(Error::InvalidInput("Nothing to plan: every PDA already holds its allocation".to_string()));
+        }
+        return Ok((report, None));
+    }
+    let plan = BatchPlan::create(&payer, &transfers, request.priority_fee, unix_timestamp(), &rpc).await?;
+    let yaml = plan.to_yaml()?;
+    let balance_lamports = get_account_balance(&payer, &rpc).await?;
+    if let Some(path) = parsed.value("out") {
+        write_atomic(Path::new(path), yaml.as_bytes())?;
+    }
+    let shortfall_lamports = (plan.total_lamports + plan.estimated_fee_lamports).saturating_sub(balance_lamports);
+    Ok((report, Some(AllocationFunding { plan_hash: plan_hash(yaml.as_bytes()), plan, balance_lamports, shortfall_lamports })))
+}
+
+/// Prints the text report of `allocate`
+fn print_allocation(parsed: &ParsedArgs, report: &AllocationReport, funding: Option<&AllocationFunding>) {
+    let labels = address_labels(parsed);
+    println!("{}", i18n::message("allocate-budget", &[("budget", &Amount::Lamports(report.budget_lamports)), ("weights", &report.weights)]));
+    println!();
+    println!(
+        "{:>8}  {:>16}  {:>14}  {:>14}  {}",
+        i18n::text("column-share"),
+        i18n::text("column-allocation-sol"),
+        i18n::text("column-balance-sol"),
+        i18n::text("column-funding-sol"),
+        i18n::text("heading-validator")
+    );
+    for allocation in &report.allocations {
+        println!(
+            "{:>8}  {:>16}  {:>14}  {:>14}  {}",
+            format_basis_points(allocation.share_basis_points),
+            format_sol(allocation.allocation_lamports),
+            format_sol(allocation.balance_lamports),
+            format_sol(allocation.funding_lamports),
+            labels.format_str(&allocation.validator)
+        );
+    }
+    println!();
+    println!("{}", i18n::message("allocate-funding", &[("value", &Amount::Lamports(report.funding_lamports))]));
+    println!("{}", i18n::message("allocate-unallocated", &[("value", &Amount::Lamports(report.unallocated_lamports))]));
+    if let Some(funding) = funding {
+        println!();
+        println!("{}", i18n::message("allocate-wallet", &[("wallet", &labels.format_str(&funding.plan.payer))]));
+        println!("{}", i18n::message("allocate-wallet-balance", &[("value", &Amount::Lamports(funding.balance_lamports))]));
+        println!(
+            "{}",
+            i18n::message("allocate-estimated-fee", &[("fee", &funding.plan.estimated_fee_lamports), ("transactions", &funding.plan.transaction_count)])
+        );
+        println!("{}", i18n::message("allocate-shortfall", &[("value", &Amount::Lamports(funding.shortfall_lamports))]));
+        if let Some(path) = parsed.value("out") {
+            println!("{}", i18n::message("plan-written-to", &[("path", &path)]));
+        }
+        println!("{}", i18n::message("plan-hash", &[("hash", &funding.plan_hash)]));
+    }
+}
+
+/// Resolves the validators of a batch from arguments, `--file` or the configured aliases
+///
+/// Duplicates are dropped, so a validator given both by alias and by pubkey is reported once.
+fn batch_validators(names: &[String], parsed: &ParsedArgs) -> Result<Vec<(Option<String>, Pubkey)>, Error> {
+    let config = Config::load(&config_path(parsed))?;
+    
+    let mut names = names.to_vec();
+    if let Some(path) = parsed.value("file") {
+        names.extend(read_validator_file(Path::new(path))?);
+    }
+    if parsed.has("stdin") {
+        let piped = read_validator_stdin()?;
+        // An empty pipe is an upstream failure, not a request for every configured alias
+        if piped.is_empty() {
+            return Err(Error::InvalidInput("No validators on standard input".to_string()));
+        }
+        names.extend(piped);
+    }
+    if names.is_empty() {
+        names = config.validators.keys().cloned().collect();
+    }
+    if names.is_empty() {
+        return Err(Error::InvalidInput(format!(
+            "No validators given; pass pubkeys or aliases, --file <path>, or add a [validators] table to {}",
+            config_path(parsed).display()
+        )));
+    }
+    
+    let mut validators: Vec<(Option<String>, Pubkey)> = Vec::with_capacity(names.len());
+    for name in &names {
+        let (alias, validator_id) = config.resolve_validator(name)?;
+        if !validators.iter().any(|(_, known)| *known == validator_id) {
+            validators.push((alias, validator_id));
+        }
+    }
+    Ok(validators)
+}
+
+/// Rewrites `pda-address --stdin` and `pda-balance --stdin` as `pda-batch <operation> --stdin`
+///
+/// # Arguments
+/// * `args` - Program name followed by positional parameters
+/// * `parsed` - Parsed arguments
+///
+/// # Returns
+/// * `Result<Vec<String>, Error>` - Arguments to run, or `Error::InvalidInput` if the operation does not take `--stdin`
+fn stdin_batch_arguments(mut args: Vec<String>, parsed: &ParsedArgs) -> Result<Vec<String>, Error> {
+    if !parsed.has("stdin") {
+        return Ok(args);
+    }
+    match args.get(1).map(String::as_str) {
+        Some("pda-address" | "pda-balance") => {
+            args.insert(1, "pda-batch".to_string());
+            Ok(args)
+        }
+        Some("pda-batch") => Ok(args),
+        _ => Err(Error::InvalidInput("--stdin is only supported by pda-address, pda-balance and pda-batch".to_string())),
+    }
+}
+
+/// Reads validator pubkeys or aliases piped on standard input, one per line
+fn read_validator_stdin() -> Result<Vec<String>, Error> {
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Err(Error::InvalidInput("--stdin expects validators piped on standard input, one per line".to_string()));
+    }
+    let contents = std::io::read_to_string(stdin).map_err(|e| Error::InvalidInput(format!("Failed to read standard input: {}", e)))?;
+    Ok(parse_validator_list(&contents))
+}
+
+/// Returns a warning if a batch row is not in gossip (JSON output)
+fn gossip_warning(entry: &BatchEntry) -> Option<Warning> {
+    (entry.in_gossip == Some(false)).then(|| {
+        Warning::new(
+            WARN_NOT_IN_GOSSIP,
+            format!("Validator {} is NOT found in Solana gossip network", entry.validator),
+        )
+    })
+}
+
+/// Prints batch rows as aligned columns; columns that were not collected are left out
+fn print_batch_table(entries: &[BatchEntry], options: &BatchOptions) {
+    let name_width = entries
+        .iter()
+        .filter_map(|entry| entry.name.as_deref())
+        .chain([i18n::text("column-name")])
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or_default();
+    
+    let mut header = format!("{:<name_width$}  {:<44}  {:<44}", i18n::text("column-name"), i18n::text("column-validator"), "PDA");
+    if options.check_gossip {
+        header.push_str(&format!("  {:<6}", i18n::text("column-gossip")));
+    }
+    if options.fetch_balance {
+        header.push_str(&format!("  {:>20}", i18n::text("column-balance-lamports")));
+    }
+    if options.fetch_stake {
+        header.push_str(&format!("  {:>20}", i18n::text("column-stake-lamports")));
+    }
+    println!("{}", header.trim_end());
+    
+    let optional = |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_else(|| "-".to_string());
+    for entry in entries {
+        let mut row = format!("{:<name_width$}  {:<44}  {:<44}", entry.name.as_deref().unwrap_or("-"), entry.validator, entry.pda);
+        if options.check_gossip {
+            let gossip = match entry.in_gossip {
+                Some(true) => i18n::text("gossip-yes"),
+                Some(false) => i18n::text("gossip-no"),
+                None => "-",
+            };
+            row.push_str(&format!("  {:<6}", gossip));
+        }
+        if options.fetch_balance {
+            row.push_str(&format!("  {:>20}", optional(entry.balance_lamports)));
+        }
+        if options.fetch_stake {
+            row.push_str(&format!("  {:>20}", optional(entry.activated_stake)));
+        }
+        if let Some(error) = &entry.error {
+            row.push_str(&format!("  {}: {}", i18n::text("error-lower"), i18n::localize(error)));
+        }
+        println!("{}", row.trim_end());
+    }
+}
+
+/// Handles `dashboard [validator_or_alias ...]`: a live table of the validators until `q` is pressed
+async fn run_dashboard(args: &[String], parsed: &ParsedArgs) -> Result<(), Error> {
+    if !std::io::stdout().is_terminal() || !std::io::stdin().is_terminal() {
+        return Err(Error::InvalidInput("dashboard needs an interactive terminal; use pda-batch pda-balance in scripts".to_string()));
+    }
+    let refresh = match parsed.value("refresh").map(str::parse::<u64>) {
+        None => Duration::from_secs(DEFAULT_REFRESH_SECS),
+        Some(Ok(secs)) if secs > 0 => Duration::from_secs(secs),
+        Some(_) => return Err(Error::InvalidInput("Invalid --refresh: must be a positive number of seconds".to_string())),
+    };
+    let amount = parsed.value("amount").map(parse_amount).transpose()?;
+    let policy = funding_policy(parsed)?;
+    let validators = batch_validators(&args[2..], parsed)?;
+    for (_, validator_id) in &validators {
+        remember_validator(validator_id);
+    }
+    let payer = configured_payer(parsed);
+    let rpc: Arc<dyn SolanaRpc> = Arc::new(rpc_client(None));
+    let audit_path = audit::audit_log_path();
+    let mut audit_entries = audit::read_entries(&audit_path).unwrap_or_default();
+    
+    let refresh_task = |audit_entries: Vec<AuditEntry>| {
+        let (validators, rpc) = (validators.clone(), Arc::clone(&rpc));
+        tokio::spawn(async move { collect_snapshot(validators, payer, &audit_entries, unix_timestamp(), rpc).await })
+    };
+    let mut dashboard = Dashboard { refreshing: true, ..Dashboard::default() };
+    let mut task = Some(refresh_task(audit_entries.clone()));
+    let mut next_refresh = Instant::now() + refresh;
+    let terminal_error = |e: std::io::Error| Error::Io(format!("Terminal error: {}", e));
+    
+    let mut terminal = ratatui::init();
+    let result = loop {
+        if task.as_ref().is_some_and(|task| task.is_finished()) {
+            match task.take().expect("finished refresh task").await {
+                Ok(snapshot) => dashboard.update(snapshot),
+                Err(e) => {
+                    dashboard.refreshing = false;
+                    dashboard.status = Some(i18n::message("dashboard-refresh-failed", &[("error", &e)]));
+                }
+            }
+            next_refresh = Instant::now() + refresh;
+        }
+        if task.is_none() && (dashboard.refreshing || Instant::now() >= next_refresh) {
+            dashboard.refreshing = true;
+            task = Some(refresh_task(audit_entries.clone()));
+        }
+        
+        if let Err(e) = terminal.draw(|frame| dashboard.render(frame, &audit_entries, unix_timestamp())) {
+            break Err(terminal_error(e));
+        }
+        let key = match tokio::task::block_in_place(|| event::poll(Duration::from_millis(250))) {
+            Ok(true) => match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => key.code,
+                Ok(_) => continue,
+                Err(e) => break Err(terminal_error(e)),
+            },
+            Ok(false) => continue,
+            Err(e) => break Err(terminal_error(e)),
+        };
+        match dashboard.handle_key(key) {
+            Action::Quit => break Ok(()),
+            Action::Refresh | Action::None => {}
+            Action::TopUp(validator_id) => match amount {
+                None => dashboard.status = Some(i18n::text("dashboard-top-up-disabled").to_string()),
+                Some(amount) => {
+                    // The funding summary, confirmation and passphrase prompts need the regular terminal
+                    ratatui::restore();
+                    dashboard.status = Some(dashboard_top_up(parsed, &validator_id, amount, &policy).await);
+                    eprintln!("{}", i18n::text("dashboard-press-enter"));
+                    let _ = std::io::stdin().lock().read_line(&mut String::new());
+                    terminal = ratatui::init();
+                    audit_entries = audit::read_entries(&audit_path).unwrap_or_default();
+                    // A refresh started before the top-up would show the old balance
+                    if let Some(task) = task.take() {
+                        task.abort();
+                    }
+                    dashboard.refreshing = true;
+                }
+            },
+        }
+    };
+    ratatui::restore();
+    result
+}
+
+/// Tops up the PDA of a dashboard row with the usual checks and confirmation, returning the status line
+async fn dashboard_top_up(parsed: &ParsedArgs, validator_id: &Pubkey, amount: Amount, policy: &FundingPolicy) -> String {
+    let funder = match configured_keypair(parsed) {
+        Ok(Some(funder)) => funder,
+        Ok(None) => return i18n::message("no-funding-wallet", &[("path", &config_path(parsed).display())]),
+        Err(e) => return i18n::message("dashboard-top-up-failed", &[("error", &i18n::error_text(&e))]),
+    };
+    let signers = FundingSigners {
+        funder,
+        fee_payer: parsed.value("fee-payer").map(str::to_string),
+        keypairs: parsed.values("keypair").into_iter().map(str::to_string).collect(),
+    };
+    let deposit_key = generate_deposit_pda(validator_id);
+    
+    let result = fund_with_confirmation(parsed, validator_id, &signers, amount, policy, None, None).await;
+    let event = match &result {
+        Ok(outcome) => NotifyEvent::FundingLanded {
+            validator: validator_id.to_string(),
+            pda: deposit_key.to_string(),
+            amount_lamports: outcome.amount_lamports,
+            signature: outcome.signature.clone(),
+        },
+        Err(e) => NotifyEvent::FundingFailed { validator: validator_id.to_string(), pda: deposit_key.to_string(), error: e.to_string() },
+    };
+    notify(notify_config(parsed).as_ref(), &event).await;
+    
+    match result {
+        Ok(outcome) => i18n::message(
+            "dashboard-topped-up",
+            &[("amount", &format_sol(outcome.amount_lamports)), ("pda", &deposit_key), ("signature", &outcome.signature)],
+        ),
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            i18n::message("dashboard-top-up-failed", &[("error", &i18n::error_text(&e))])
+        }
+    }
+}
+
+/// Handles `keygen new --outfile <path>`, `keygen grind --starts-with <prefix:count> ...` and `keygen encrypt <in.json> <out.enc> [--force]`
+fn run_keygen_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let (operation, result) = match args.get(2).map(String::as_str) {
+        Some("encrypt") if args.len() >= 5 => {
+            let (input, out) = (&args[3], &args[4]);
+            let result = keystore::encrypt_keypair_file(input, Path::new(out), parsed.has("force"))
+                .map(|pubkey| serde_json::json!({ "pubkey": pubkey.to_string(), "file": out }));
+            ("keygen-encrypt", result)
+        }
+        Some("new") => {
+            let result = parsed
+                .value("outfile")
+                .ok_or_else(|| Error::InvalidInput("keygen new requires --outfile <path>".to_string()))
+                .and_then(|outfile| {
+                    keygen::new_keypair_file(Path::new(outfile), parsed.has("force"))
+                        .map(|pubkey| serde_json::json!({ "pubkey": pubkey.to_string(), "file": outfile }))
+                });
+            ("keygen-new", result)
+        }
+        Some("grind") => ("keygen-grind", grind_keypairs(parsed, output)),
+        _ => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("unknown-command", &[("command", &"keygen"), ("commands", &"new, grind, encrypt")]));
+            eprintln!("{}: {} keygen new --outfile <path> [--force]", i18n::text("usage"), args[0]);
+            eprintln!("       {} keygen grind --starts-with <prefix:count> | --ends-with <suffix:count> | --starts-and-ends-with <prefix:suffix:count> [--ignore-case] [--pda] [--threads <n>]", args[0]);
+            eprintln!("       {} keygen encrypt <in.json> <out.enc> [--force]", args[0]);
+            exit(EXIT_INVALID_INPUT);
+        }
+    };
+    
+    let fields = match result {
+        Ok(fields) => fields,
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure(operation, serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    };
+    if output == OutputFormat::Json {
+        println!("{}", JsonReport::success(operation, fields, Vec::new()).to_json());
+        return;
+    }
+    match operation {
+        "keygen-encrypt" => {
+            println!("{}", i18n::message("keypair-encrypted", &[("pubkey", &fields["pubkey"].as_str().unwrap_or_default()), ("path", &args[4])]));
+            println!("{}", i18n::message("keypair-encrypted-hint", &[("encrypted", &args[4]), ("plain", &args[3])]));
+        }
+        "keygen-new" => {
+            let file = fields["file"].as_str().unwrap_or_default();
+            println!("{}", i18n::message("keypair-new-written", &[("pubkey", &fields["pubkey"].as_str().unwrap_or_default()), ("path", &file)]));
+            println!("{}", i18n::message("keypair-no-seed-phrase", &[("path", &file)]));
+        }
+        _ => {
+            for keypair in fields["keypairs"].as_array().into_iter().flatten() {
+                let (pubkey, path) = (keypair["pubkey"].as_str().unwrap_or_default(), keypair["file"].as_str().unwrap_or_default());
+                match keypair["pda"].as_str() {
+                    Some(pda) => println!("{}", i18n::message("keypair-pda-written", &[("pubkey", &pubkey), ("pda", &pda), ("path", &path)])),
+                    None => println!("{}", i18n::message("keypair-written", &[("pubkey", &pubkey), ("path", &path)])),
+                }
+            }
+            println!("{}", i18n::message("keypairs-searched", &[("count", &fields["attempts"])]));
+        }
+    }
+}
+
+/// Runs `keygen grind` in the current directory and returns its JSON result
+///
+/// With `--pda` the patterns match the deposit PDA of each keypair instead of its pubkey, and every
+/// keypair in the result also carries its `pda`.
+fn grind_keypairs(parsed: &ParsedArgs, output: OutputFormat) -> Result<serde_json::Value, Error> {
+    let ignore_case = parsed.has("ignore-case");
+    let target = if parsed.has("pda") { keygen::GrindTarget::DepositPda } else { keygen::GrindTarget::Wallet };
+    let mut patterns = Vec::new();
+    for flag in ["starts-with", "ends-with", "starts-and-ends-with"] {
+        for value in parsed.values(flag) {
+            patterns.push(keygen::GrindPattern::parse(flag, value, ignore_case)?);
+        }
+    }
+    if patterns.is_empty() {
+        return Err(Error::InvalidInput(
+            "keygen grind requires --starts-with <prefix:count>, --ends-with <suffix:count> or --starts-and-ends-with <prefix:suffix:count>".to_string(),
+        ));
+    }
+    let threads = match parsed.value("threads") {
+        Some(value) => value
+            .parse::<usize>()
+            .ok()
+            .filter(|threads| *threads > 0)
+            .ok_or_else(|| Error::InvalidInput(format!("Invalid --threads '{}': expected a positive number", value)))?,
+        None => std::thread::available_parallelism().map(usize::from).unwrap_or(1),
+    };
+    
+    if output == OutputFormat::Text {
+        eprintln!("{}", i18n::message("grind-searching", &[("threads", &threads)]));
+    }
+    let outcome = keygen::grind(&patterns, target, ignore_case, threads, Path::new("."), &AtomicBool::new(false))?;
+    let keypairs: Vec<serde_json::Value> = outcome
+        .files
+        .iter()
+        .map(|file| {
+            let pubkey = file.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+            let mut keypair = serde_json::json!({ "pubkey": pubkey, "file": file.display().to_string() });
+            if target == keygen::GrindTarget::DepositPda && let Ok(identity) = pubkey.parse::<Pubkey>() {
+                keypair["pda"] = serde_json::json!(generate_deposit_pda(&identity).to_string());
+            }
+            keypair
+        })
+        .collect();
+    Ok(serde_json::json!({ "keypairs": keypairs, "attempts": outcome.attempts }))
+}
+
+/// Handles `state export --out <bundle.tar.zst>` and `state import <bundle.tar.zst> [--force]`
+fn run_state_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let paths = StatePaths::with_config(config_path(parsed));
+    let (operation, result) = match args.get(2).map(String::as_str) {
+        Some("export") => {
+            let result = parsed
+                .value("out")
+                .ok_or_else(|| Error::InvalidInput("state export requires --out <bundle.tar.zst>".to_string()))
+                .and_then(|out| export_bundle(&paths, Path::new(out), unix_timestamp()).map(|manifest| (out.to_string(), manifest)));
+            ("state-export", result)
+        }
+        Some("import") => {
+            let result = args
+                .get(3)
+                .ok_or_else(|| Error::InvalidInput("state import requires a bundle path".to_string()))
+                .and_then(|bundle| import_bundle(Path::new(bundle), &paths, parsed.has("force")).map(|manifest| (bundle.clone(), manifest)));
+            ("state-import", result)
+        }
+        _ => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("unknown-command", &[("command", &"state"), ("commands", &"export, import")]));
+            eprintln!("{}: {} state export --out <bundle.tar.zst> | state import <bundle.tar.zst> [--force]", i18n::text("usage"), args[0]);
+            exit(EXIT_INVALID_INPUT);
+        }
+    };
+    
+    match result {
+        Ok((bundle, manifest)) if output == OutputFormat::Json => {
+            let fields = serde_json::json!({ "bundle": bundle, "manifest": manifest });
+            println!("{}", JsonReport::success(operation, fields, Vec::new()).to_json());
+        }
+        Ok((bundle, manifest)) => {
+            let key = if operation == "state-export" { "state-exported" } else { "state-imported" };
+            let files = if manifest.files.is_empty() { i18n::text("state-no-files").to_string() } else { manifest.files.join(", ") };
+            println!("{}", i18n::message(key, &[("bundle", &bundle), ("files", &files)]));
+            if !manifest.excluded.is_empty() {
+                println!("{}", i18n::message("state-secrets-excluded", &[("secrets", &manifest.excluded.join(", "))]));
+            }
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure(operation, serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Handles `alias add <name> <pubkey> [--force]`, `alias remove <name>` and `alias list`
+///
+/// Aliases are stored in the `[validators]` table of the config file.
+fn run_alias_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let path = config_path(parsed);
+    let (operation, result) = match args.get(2).map(String::as_str) {
+        Some("add") => {
+            let result = match (args.get(3), args.get(4)) {
+                (Some(name), Some(validator)) => parse_pubkey(validator)
+                    .map_err(|e| Error::InvalidInput(format!("Invalid validator pubkey: {}", e)))
+                    .and_then(|validator_id| {
+                        let mut config = Config::load(&path)?;
+                        let previous = config.add_alias(name, &validator_id, parsed.has("force"))?;
+                        config.save(&path)?;
+                        Ok(serde_json::json!({ "alias": name, "validator": validator_id.to_string(), "replaced": previous }))
+                    }),
+                _ => Err(Error::InvalidInput("alias add requires a name and a validator pubkey".to_string())),
+            };
+            ("alias-add", result)
+        }
+        Some("remove") => {
+            let result = args
+                .get(3)
+                .ok_or_else(|| Error::InvalidInput("alias remove requires a name".to_string()))
+                .and_then(|name| {
+                    let mut config = Config::load(&path)?;
+                    let validator = config.remove_alias(name)?;
+                    config.save(&path)?;
+                    Ok(serde_json::json!({ "alias": name, "validator": validator }))
+                });
+            ("alias-remove", result)
+        }
+        Some("list") => {
+            let result = Config::load(&path).map(|config| {
+                let aliases: Vec<_> = config
+                    .validators
+                    .iter()
+                    .map(|(alias, validator)| serde_json::json!({ "alias": alias, "validator": validator }))
+                    .collect();
+                serde_json::json!({ "aliases": aliases })
+            });
+            ("alias-list", result)
+        }
+        _ => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("unknown-command", &[("command", &"alias"), ("commands", &"add, remove, list")]));
+            eprintln!("{}: {} alias add <name> <validator_pubkey> [--force] | alias remove <name> | alias list", i18n::text("usage"), args[0]);
+            exit(EXIT_INVALID_INPUT);
+        }
+    };
+    
+    match result {
+        Ok(fields) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::success(operation, fields, Vec::new()).to_json());
+        }
+        Ok(fields) if operation == "alias-list" => {
+            let aliases = fields["aliases"].as_array().cloned().unwrap_or_default();
+            if aliases.is_empty() {
+                println!("{}", i18n::message("no-aliases", &[("path", &path.display())]));
+            }
+            for alias in aliases {
+                println!("{:<20} {}", alias["alias"].as_str().unwrap_or_default(), alias["validator"].as_str().unwrap_or_default());
+            }
+        }
+        Ok(fields) => {
+            let (alias, validator) = (fields["alias"].as_str().unwrap_or_default(), fields["validator"].as_str().unwrap_or_default());
+            if operation == "alias-remove" {
+                println!("{}", i18n::message("alias-removed", &[("alias", &alias), ("validator", &validator)]));
+            } else if let Some(previous) = fields["replaced"].as_str().filter(|previous| *previous != validator) {
+                println!("{}", i18n::message("alias-replaced", &[("alias", &alias), ("validator", &validator), ("previous", &previous)]));
+            } else {
+                println!("{}", i18n::message("alias-added", &[("alias", &alias), ("validator", &validator)]));
+            }
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure(operation, serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Handles `fees report [--month YYYY-MM]`
+fn run_fees_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    if args.get(2).map(String::as_str) != Some("report") {
+        eprintln!("{}: {}", i18n::text("error"), i18n::message("unknown-command", &[("command", &"fees"), ("commands", &"report")]));
+        eprintln!("{}: {} fees report [--month YYYY-MM]", i18n::text("usage"), args[0]);
+        exit(EXIT_INVALID_INPUT);
+    }
+    
+    let now = unix_timestamp();
+    let result = parsed
+        .value("month")
+        .map(Month::parse)
+        .unwrap_or_else(|| Ok(Month::from_timestamp(now)))
+        .and_then(|month| Ok((month, audit::read_entries(&audit::audit_log_path())?)))
+        .map(|(month, entries)| fee_report(&entries, month, now));
+    
+    match result {
+        Ok(report) if output == OutputFormat::Json => {
+            let fields = serde_json::to_value(&report).unwrap_or_default();
+            println!("{}", JsonReport::success("fees-report", fields, Vec::new()).to_json());
+        }
+        Ok(report) => {
+            println!("{}", i18n::message("fees-month", &[("month", &report.month)]));
+            println!("{}", i18n::message("fees-transactions", &[("count", &report.transactions)]));
+            println!("{}", i18n::message("fees-spent", &[("value", &Amount::Lamports(report.fee_lamports))]));
+            println!(
+                "{}",
+                i18n::message("fees-cadence", &[("rate", &format!("{:.0}", report.daily_rate_lamports)), ("days", &FORECAST_WINDOW_DAYS)])
+            );
+            match report.forecast_lamports {
+                Some(forecast) => println!("{}", i18n::message("fees-forecast", &[("value", &Amount::Lamports(forecast))])),
+                None => println!("{}", i18n::text("fees-forecast-month-over")),
+            }
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("fees-report", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Handles `audit stats` and `audit show`
+async fn run_audit_command(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    match args.get(2).map(String::as_str) {
+        Some("stats") => run_audit_stats(parsed, output),
+        Some("show") => run_audit_show(args, parsed, output).await,
+        _ => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("unknown-command", &[("command", &"audit"), ("commands", &"stats, show")]));
+            eprintln!("{}: {} audit stats [--window 24h|7d|all]", i18n::text("usage"), args[0]);
+            eprintln!("       {} audit show [validator_or_alias] [--window 24h|7d|all] [--event <event>] [--status success|failed] [--limit <n>]", args[0]);
+            exit(EXIT_INVALID_INPUT);
+        }
+    }
+}
+
+/// Handles `audit show [validator_or_alias] [--window 24h|7d|all] [--event <event>] [--status success|failed] [--limit <n>]`
+async fn run_audit_show(args: &[String], parsed: &ParsedArgs, output: OutputFormat) {
+    let labels = Config::load(&config_path(parsed)).and_then(|config| Labels::from_config(&config)).unwrap_or_else(|_| Labels::builtin());
+    let query = audit_query(args, parsed, unix_timestamp());
+    let result = query.and_then(|query| Ok((query, audit::read_entries(&audit::audit_log_path())?)));
+    
+    match result {
+        Ok((query, entries)) if output == OutputFormat::Json => {
+            let matching = audit::query_entries(&entries, &query);
+            let mut json_entries = serde_json::to_value(&matching).unwrap_or_default();
+            for (entry, json_entry) in matching.iter().zip(json_entries.as_array_mut().into_iter().flatten()) {
+                if let Some(amount) = entry.amount_lamports
+                    && let Some(quote) = fiat_quote(parsed, amount, Some(entry.timestamp)).await
+                {
+                    json_entry["fiat"] = serde_json::json!(quote);
+                }
+            }
+            let fields = serde_json::json!({
+                "audit_log": audit::audit_log_path().display().to_string(),
+                "count": matching.len(),
+                "entries": json_entries,
+            });
+            println!("{}", JsonReport::success("audit-show", fields, Vec::new()).to_json());
+        }
+        Ok((query, entries)) => {
+            let matching = audit::query_entries(&entries, &query);
+            if matching.is_empty() {
+                println!("{}", i18n::message("audit-no-entries", &[("path", &audit::audit_log_path().display())]));
+            }
+            for entry in matching {
+                let mut line = format!("{}  {:<16} {:<8}", audit::format_timestamp(entry.timestamp), entry.event, entry.status);
+                if let Some(validator) = &entry.validator {
+                    line.push_str(&i18n::message("audit-entry-validator", &[("validator", &labels.format_str(validator))]));
+                }
+                if let Some(payer) = &entry.payer {
+                    line.push_str(&i18n::message("audit-entry-payer", &[("payer", &labels.format_str(payer))]));
+                }
+                if let Some(amount) = entry.amount_lamports {
+                    line.push_str(&format!("  {}", Amount::Lamports(amount)));
+                    // Valued at the price of the day of the funding
+                    if let Some(quote) = fiat_quote(parsed, amount, Some(entry.timestamp)).await {
+                        line.push_str(&format!(" ({})", quote));
+                    }
+                }
+                if let Some(signature) = &entry.signature {
+                    line.push_str(&format!("  {}", signature));
+                }
+                println!("{}", line);
+                if let Some(message) = &entry.message {
+                    println!("    {}", message);
+                }
+            }
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("audit-show", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Builds the filter of `audit show` from its arguments
+fn audit_query(args: &[String], parsed: &ParsedArgs, now: u64) -> Result<audit::AuditQuery, Error> {
+    let validator = args
+        .get(3)
+        .map(|name| Ok::<_, Error>(Config::load(&config_path(parsed))?.resolve_validator(name)?.1.to_string()))
+        .transpose()?;
+    let since = match parsed.value("window") {
+        Some(window) => StatsWindow::parse(window)?.start(now),
+        None => 0,
+    };
+    let limit = match parsed.value("limit") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(limit) if limit > 0 => Some(limit),
+            _ => return Err(Error::InvalidInput(format!("Invalid limit '{}': expected a number greater than 0", value))),
+        },
+        None => None,
+    };
+    Ok(audit::AuditQuery {
+        validator,
+        event: parsed.value("event").map(str::to_string),
+        status: parsed.value("status").map(audit::parse_status).transpose()?,
+        since,
+        limit,
+    })
+}
+
+/// Handles `audit stats [--window 24h|7d|all]`
+fn run_audit_stats(parsed: &ParsedArgs, output: OutputFormat) {
+    let now = unix_timestamp();
+    let window = parsed.value("window").unwrap_or(DEFAULT_STATS_WINDOW);
+    let result = StatsWindow::parse(window)
+        .and_then(|parsed_window| Ok((parsed_window, audit::read_entries(&audit::audit_log_path())?)))
+        .map(|(parsed_window, entries)| audit_stats(&entries, parsed_window, now));
+    
+    match result {
+        Ok(stats) if output == OutputFormat::Json => {
+            let mut fields = serde_json::to_value(&stats).unwrap_or_default();
+            fields["window"] = window.into();
+            println!("{}", JsonReport::success("audit-stats", fields, Vec::new()).to_json());
+        }
+        Ok(stats) => {
+            let labels = Config::load(&config_path(parsed)).and_then(|config| Labels::from_config(&config)).unwrap_or_else(|_| Labels::builtin());
+            let window = if stats.since == 0 { i18n::text("stats-all-entries").to_string() } else { i18n::message("stats-last", &[("window", &window)]) };
+            println!("{}", i18n::message("stats-window", &[("window", &window)]));
+            println!(
+                "{}",
+                i18n::message(
+                    "stats-fundings",
+                    &[("fundings", &stats.fundings), ("failed", &stats.failed), ("rate", &format!("{:.1}", stats.failure_rate * 100.0))],
+                )
+            );
+            println!("{}", i18n::message("stats-moved", &[("value", &Amount::Lamports(stats.moved_lamports))]));
+            println!("{}", i18n::message("stats-fees", &[("value", &Amount::Lamports(stats.fee_lamports))]));
+            match stats.mean_confirmation_ms {
+                Some(mean) => println!(
+                    "{}",
+                    i18n::message("stats-latency", &[("seconds", &format!("{:.1}", mean as f64 / 1000.0)), ("count", &stats.confirmations)])
+                ),
+                None => println!("{}", i18n::text("stats-no-latency")),
+            }
+            for validator in &stats.validators {
+                println!(
+                    "{}",
+                    i18n::message(
+                        "stats-validator",
+                        &[
+                            ("validator", &labels.format_str(&validator.validator)),
+                            ("fundings", &validator.fundings),
+                            ("failed", &validator.failed),
+                            ("moved", &Amount::Lamports(validator.amount_lamports)),
+                            ("fee", &validator.fee_lamports),
+                        ],
+                    )
+                );
+            }
+        }
+        Err(e) if output == OutputFormat::Json => {
+            println!("{}", JsonReport::failure("audit-stats", serde_json::json!({}), Vec::new(), &e).to_json());
+            exit(exit_code(&e));
+        }
+        Err(e) => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::error_text(&e));
+            exit(exit_code(&e));
+        }
+    }
+}
+
+/// Handles `wallet <subcommand>`
+async fn run_wallet_command(args: &[String], parsed: &ParsedArgs) {
+    match args.get(2).map(String::as_str) {
+        Some("rotate") => {
+            if let Err(e) = rotate_wallet(parsed).await {
+                eprintln!("{}", i18n::message("wallet-rotation-failed", &[("error", &i18n::error_text(&e))]));
+                exit(exit_code(&e));
+            }
+        }
+        _ => {
+            eprintln!("{}: {}", i18n::text("error"), i18n::message("unknown-command", &[("command", &"wallet"), ("commands", &"rotate")]));
+            eprintln!("{}: {} wallet rotate --new <keypair_path> [--old <keypair_path>] [--profile <name>]", i18n::text("usage"), args[0]);
+            exit(EXIT_INVALID_INPUT);
+        }
+    }
+}
+
+/// Sweeps the old funding wallet into the new one, updates the config and records the rotation
+async fn rotate_wallet(parsed: &ParsedArgs) -> Result<(), Error> {
+    let new_path = parsed
+        .value("new")
+        .ok_or_else(|| Error::InvalidInput("wallet rotate requires --new <keypair_path>".to_string()))?;
+
+    let config_path = config_path(parsed);
+    let profile = parsed.value("profile");
+    let mut config = Config::load(&config_path)?;
+    let old_path = match parsed.value("old") {
+        Some(old_path) => old_path.to_string(),
+        None => config.keypair(profile)?.map(str::to_string).ok_or_else(|| {
+            Error::InvalidInput(format!(
+                "No funding wallet configured in {}; pass --old <keypair_path>",
+                config_path.display()
+            ))
+        })?,
+    };
+
+    let old_keypair = load_signer(&old_path)?;
+    let new_keypair = load_signer(new_path)?;
+    if old_keypair.pubkey() == new_keypair.pubkey() {
+        return Err(Error::InvalidInput("Old and new keypairs are the same wallet".to_string()));
+    }
+
+    println!("{}", i18n::message("wallet-old", &[("pubkey", &old_keypair.pubkey()), ("path", &old_path)]));
+    println!("{}", i18n::message("wallet-new", &[("pubkey", &new_keypair.pubkey()), ("path", &new_path)]));
+
+    let audit_path = audit::audit_log_path();
+    let mut entry = AuditEntry {
+        payer: Some(old_keypair.pubkey().to_string()),
+        recipient: Some(new_keypair.pubkey().to_string()),
+        ..AuditEntry::new(audit::EVENT_WALLET_ROTATE, audit::STATUS_SUCCESS)
+    };
+
+    let rpc = rpc_client(None);
+    match sweep_wallet(old_keypair.as_ref(), &new_keypair.pubkey(), &rpc).await {
+        Ok(Some(outcome)) => {
+            println!("{}", i18n::message("wallet-swept", &[("amount", &Amount::Lamports(outcome.amount_lamports)), ("fee", &outcome.fee_lamports)]));
+            println!("{}", i18n::message("transaction-signature", &[("signature", &outcome.signature)]));
+            print_signature_link(parsed, &outcome.signature);
+            entry.amount_lamports = Some(outcome.amount_lamports);
+            entry.fee_lamports = Some(outcome.fee_lamports);
+            entry.signature = Some(outcome.signature);
+        }
+        Ok(None) => println!("{}", i18n::text("wallet-nothing-to-sweep")),
+        Err(e) => {
+            entry.status = audit::STATUS_FAILED.to_string();
+            entry.message = Some(e.to_string());
+            audit::append_entry(&audit_path, &entry)?;
+            return Err(e);
+        }
+    }
+
+    // Point every reference to the old wallet at the new one; set it for the selected profile if none matched
+    if config.replace_keypair(&old_path, new_path) == 0 {
+        match profile {
+            Some(name) => config.profiles.entry(name.to_string()).or_default().keypair = Some(new_path.to_string()),
+            None => config.keypair = Some(new_path.to_string()),
+        }
+    }
+    if let Err(e) = config.save(&config_path) {
+        entry.status = audit::STATUS_FAILED.to_string();
+        entry.message = Some(format!("Funds were swept but the config was not updated: {}", e));
+        audit::append_entry(&audit_path, &entry)?;
+        return Err(e);
+    }
+    println!("{}", i18n::message("config-updated", &[("path", &config_path.display())]));
+
+    audit::append_entry(&audit_path, &entry)?;
+    println!("{}", i18n::message("wallet-rotation-recorded", &[("path", &audit_path.display())]));
+    println!("{}", i18n::text("wallet-first-funding-verified"));
+
+    Ok(())
+}
+
+/// Verifies the first funding made from a freshly rotated wallet and records the result
+///
+/// Returns a warning if the funding could not be verified.
+async fn verify_rotated_wallet(payer: &str, signature: &str, output: OutputFormat) -> Option<Warning> {
+    let audit_path = audit::audit_log_path();
+    let entries = match audit::read_entries(&audit_path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("Unable to read audit log: {}", e);
+            return None;
+        }
+    };
+    if !audit::wallet_needs_verification(&entries, payer) {
+        return None;
+    }
+
+    if output == OutputFormat::Text {
+        info!("First funding from rotated wallet {} - waiting for confirmation...", payer);
+    }
+    let mut entry = AuditEntry {
+        payer: Some(payer.to_string()),
+        signature: Some(signature.to_string()),
+        ..AuditEntry::new(audit::EVENT_WALLET_VERIFY, audit::STATUS_SUCCESS)
+    };
+
+    let rpc = rpc_client(None);
+    let started = Instant::now();
+    let warning = match wait_for_confirmation(signature, Duration::from_secs(DEFAULT_CONFIRMATION_TIMEOUT_SECS), &rpc).await {
+        Ok(()) => {
+            if output == OutputFormat::Text {
+                println!("✓ {}", i18n::text("wallet-funding-confirmed"));
+            }
+            entry.confirmation_ms = Some(started.elapsed().as_millis() as u64);
+            None
+        }
+        Err(e) => {
+            if output == OutputFormat::Text {
+                println!("✗ {}", i18n::message("wallet-funding-unverified", &[("error", &i18n::error_text(&e))]));
+            }
+            entry.status = audit::STATUS_FAILED.to_string();
+            entry.message = Some(e.to_string());
+            Some(Warning::new(
+                WARN_ROTATED_WALLET_UNVERIFIED,
+                format!("Funding from rotated wallet {} could not be verified: {}", payer, e),
+            ))
+        }
+    };
+
+    if let Err(e) = audit::append_entry(&audit_path, &entry) {
+        warn!("Unable to write audit log: {}", e);
     }
+    warning
 }
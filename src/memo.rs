@@ -0,0 +1,115 @@
+use crate::Error;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+
+/// SPL Memo program (v2), which logs its UTF-8 instruction data and checks the listed signers
+pub const MEMO_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Longest memo accepted by `--memo`, in bytes, so the funding transaction still fits in one packet
+pub const MAX_MEMO_LEN: usize = 566;
+
+/// Checks the value of `--memo`
+///
+/// # Arguments
+/// * `value` - Memo text
+///
+/// # Returns
+/// * `Result<String, Error>` - Memo, or `Error::InvalidInput` if it is empty or longer than `MAX_MEMO_LEN` bytes
+pub fn parse_memo(value: &str) -> Result<String, Error> {
+    if value.trim().is_empty() {
+        return Err(Error::InvalidInput("Memo cannot be empty".to_string()));
+    }
+    if value.len() > MAX_MEMO_LEN {
+        return Err(Error::InvalidInput(format!(
+            "Memo is {} bytes long; at most {} bytes fit in a funding transaction",
+            value.len(),
+            MAX_MEMO_LEN
+        )));
+    }
+    Ok(value.to_string())
+}
+
+/// Longest idempotency key accepted by `--idempotency-key`
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 64;
+
+/// Checks the value of `--idempotency-key`
+///
+/// # Arguments
+/// * `value` - Key chosen by the caller, e.g. a job id
+///
+/// # Returns
+/// * `Result<String, Error>` - Key, or `Error::InvalidInput` unless it is 1-64 ASCII letters, digits, `-`, `_`, `.` or `:`
+pub fn parse_idempotency_key(value: &str) -> Result<String, Error> {
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':');
+    if value.is_empty() || value.len() > MAX_IDEMPOTENCY_KEY_LEN || !value.chars().all(valid_char) {
+        return Err(Error::InvalidInput(format!(
+            "Invalid idempotency key '{}': expected 1-{} letters, digits, '-', '_', '.' or ':'",
+            value, MAX_IDEMPOTENCY_KEY_LEN
+        )));
+    }
+    Ok(value.to_string())
+}
+
+/// Returns the memo of a funding with an idempotency key, so the key is also recorded on chain
+///
+/// # Arguments
+/// * `memo` - Memo given with `--memo`, if any
+/// * `idempotency_key` - Checked idempotency key
+///
+/// # Returns
+/// * `Result<String, Error>` - `[memo ]idempotency-key:<key>`, or `Error::InvalidInput` if it no longer fits
+pub fn idempotency_memo(memo: Option<&str>, idempotency_key: &str) -> Result<String, Error> {
+    let tag = format!("idempotency-key:{}", idempotency_key);
+    match memo {
+        Some(memo) => parse_memo(&format!("{} {}", memo, tag)),
+        None => Ok(tag),
+    }
+}
+
+/// Returns an SPL Memo instruction signed by `signer`
+///
+/// # Arguments
+/// * `memo` - Memo text
+/// * `signer` - Signer of the transaction the memo is attributed to (the funding wallet)
+pub fn memo_instruction(memo: &str, signer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: MEMO_PROGRAM_ID,
+        accounts: vec![AccountMeta::new_readonly(*signer, true)],
+        data: memo.as_bytes().to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memo() {
+        assert_eq!(parse_memo("epoch 650 top-up").unwrap(), "epoch 650 top-up");
+        assert!(parse_memo("  ").unwrap_err().is_user_error());
+        assert!(parse_memo(&"x".repeat(MAX_MEMO_LEN)).is_ok());
+        assert!(parse_memo(&"x".repeat(MAX_MEMO_LEN + 1)).unwrap_err().to_string().contains("at most 566 bytes"));
+    }
+
+    #[test]
+    fn test_idempotency_key() {
+        assert_eq!(parse_idempotency_key("job-42:2025.03").unwrap(), "job-42:2025.03");
+        assert!(parse_idempotency_key("").unwrap_err().is_user_error());
+        assert!(parse_idempotency_key("two words").is_err());
+        assert!(parse_idempotency_key(&"k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1)).is_err());
+
+        assert_eq!(idempotency_memo(None, "job-42").unwrap(), "idempotency-key:job-42");
+        assert_eq!(idempotency_memo(Some("top-up"), "job-42").unwrap(), "top-up idempotency-key:job-42");
+        assert!(idempotency_memo(Some(&"x".repeat(MAX_MEMO_LEN)), "job-42").is_err());
+    }
+
+    #[test]
+    fn test_memo_instruction() {
+        let signer = Pubkey::new_unique();
+        let instruction = memo_instruction("top-up ✓", &signer);
+        assert_eq!(instruction.program_id, MEMO_PROGRAM_ID);
+        assert_eq!(String::from_utf8(instruction.data).unwrap(), "top-up ✓");
+        assert!(instruction.accounts[0].is_signer);
+        assert_eq!(instruction.accounts[0].pubkey, signer);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{parse_pubkey, Error};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use std::path::Path;
+
+/// Returns the message of `sign-message` and `verify-message`: the text argument, or the raw bytes of `--file`
+///
+/// # Arguments
+/// * `text` - Message given on the command line
+/// * `file` - File whose bytes are the message
+///
+/// # Returns
+/// * `Result<Vec<u8>, Error>` - Message bytes, or `Error::InvalidInput` unless exactly one of them is given
+pub fn read_message(text: Option<&str>, file: Option<&Path>) -> Result<Vec<u8>, Error> {
+    match (text, file) {
+        (Some(text), None) => Ok(text.as_bytes().to_vec()),
+        (None, Some(path)) => std::fs::read(path).map_err(|e| Error::Io(format!("Failed to read message file {}: {}", path.display(), e))),
+        (Some(_), Some(_)) => Err(Error::InvalidInput("Give the message either as text or with --file, not both".to_string())),
+        (None, None) => Err(Error::InvalidInput("No message given: pass it as text or with --file".to_string())),
+    }
+}
+
+/// Signs a message off-chain with a signer (`sign-message`)
+///
+/// The signature is plain ed25519 over the message bytes, without the header of
+/// `solana sign-offchain-message`, so any ed25519 library can check it against the pubkey.
+///
+/// # Arguments
+/// * `signer` - Keypair, hardware wallet or other signer, usually the validator identity
+/// * `message` - Message bytes
+///
+/// # Returns
+/// * `Result<Signature, Error>` - Signature or `Error::Keypair`
+pub fn sign_message(signer: &dyn Signer, message: &[u8]) -> Result<Signature, Error> {
+    signer.try_sign_message(message).map_err(|e| Error::Keypair(format!("Failed to sign message: {}", e)))
+}
+
+/// Checks an off-chain signature made by `sign_message` (`verify-message`)
+///
+/// # Arguments
+/// * `pubkey` - Base58 pubkey of the signer
+/// * `signature` - Base58 signature
+/// * `message` - Message bytes
+///
+/// # Returns
+/// * `Result<Pubkey, Error>` - Signer pubkey, or `Error::InvalidInput` if an argument is malformed or the signature does not match
+pub fn verify_message(pubkey: &str, signature: &str, message: &[u8]) -> Result<Pubkey, Error> {
+    let pubkey = parse_pubkey(pubkey).map_err(|e| Error::InvalidInput(format!("Invalid pubkey: {}", e)))?;
+    let signature = signature
+        .parse::<Signature>()
+        .map_err(|e| Error::InvalidInput(format!("Invalid signature '{}': {}", signature, e)))?;
+    if !signature.verify(pubkey.as_ref(), message) {
+        return Err(Error::InvalidInput(format!("The signature does not match the message and pubkey {}", pubkey)));
+    }
+    Ok(pubkey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+
+    #[test]
+    fn test_sign_and_verify_message() {
+        let keypair = Keypair::new();
+        let message = b"register validator for revenue distribution\x00\xff";
+        let signature = sign_message(&keypair, message).unwrap().to_string();
+        let pubkey = keypair.pubkey().to_string();
+        assert_eq!(verify_message(&pubkey, &signature, message).unwrap(), keypair.pubkey());
+
+        // Another message, signer or a malformed argument is rejected
+        assert!(verify_message(&pubkey, &signature, b"register validator").unwrap_err().to_string().contains("does not match"));
+        assert!(verify_message(&Keypair::new().pubkey().to_string(), &signature, message).is_err());
+        assert!(verify_message(&pubkey, "not-a-signature", message).unwrap_err().to_string().contains("Invalid signature"));
+        assert!(verify_message("not-a-pubkey", &signature, message).is_err());
+    }
+
+    #[test]
+    fn test_read_message() {
+        assert_eq!(read_message(Some("hello"), None).unwrap(), b"hello");
+        assert!(read_message(None, None).is_err());
+        assert!(read_message(Some("hello"), Some(Path::new("message.bin"))).is_err());
+        let path = std::env::temp_dir().join(format!("dz-message-{}.bin", std::process::id()));
+        std::fs::write(&path, [0u8, 1, 2, 255]).unwrap();
+        assert_eq!(read_message(None, Some(&path)).unwrap(), vec![0u8, 1, 2, 255]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
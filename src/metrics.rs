@@ -0,0 +1,503 @@
+use crate::trace::with_trace_header;
+use crate::{Error, FundingOutcome};
+use solana_sdk::pubkey::Pubkey;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Default `job` label used when pushing metrics
+pub const DEFAULT_METRICS_JOB: &str = "dz_validator_pda";
+
+/// Timeout for a single push to the Pushgateway
+const PUSH_TIMEOUT_SECS: u64 = 10;
+
+/// Counter of funding transactions attempted by a long-running process
+pub const FUNDING_ATTEMPTS_TOTAL: &str = "dz_validator_pda_funding_attempts_total";
+
+/// Counter of funding transactions that failed
+pub const FUNDING_FAILURES_TOTAL: &str = "dz_validator_pda_funding_failures_total";
+
+/// Counter of RPC requests that failed after retries
+pub const RPC_ERRORS_TOTAL: &str = "dz_validator_pda_rpc_errors_total";
+
+/// Largest HTTP request head read by the metrics endpoint
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Prometheus metric type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MetricKind {
+    /// Value that can go up and down
+    #[default]
+    Gauge,
+    /// Monotonically increasing total
+    Counter,
+}
+
+/// A single sample in the Prometheus text exposition format
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    /// Metric name (e.g. `dz_validator_pda_balance_lamports`)
+    pub name: String,
+    /// Help text written in the `# HELP` line
+    pub help: String,
+    /// Metric type written in the `# TYPE` line
+    pub kind: MetricKind,
+    /// Label names and values
+    pub labels: Vec<(String, String)>,
+    /// Sample value
+    pub value: f64,
+}
+
+impl Metric {
+    /// Creates a gauge sample without labels
+    ///
+    /// # Arguments
+    /// * `name` - Metric name
+    /// * `help` - Help text
+    /// * `value` - Sample value
+    pub fn gauge(name: &str, help: &str, value: f64) -> Self {
+        Metric {
+            name: name.to_string(),
+            help: help.to_string(),
+            kind: MetricKind::Gauge,
+            labels: Vec::new(),
+            value,
+        }
+    }
+
+    /// Creates a counter sample without labels
+    ///
+    /// # Arguments
+    /// * `name` - Metric name (by convention ending in `_total`)
+    /// * `help` - Help text
+    /// * `value` - Current total
+    pub fn counter(name: &str, help: &str, value: f64) -> Self {
+        Metric {
+            kind: MetricKind::Counter,
+            ..Metric::gauge(name, help, value)
+        }
+    }
+
+    /// Adds a label to the sample
+    pub fn with_label(mut self, name: &str, value: &str) -> Self {
+        self.labels.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Renders metrics in the Prometheus text exposition format
+///
+/// `# HELP` and `# TYPE` lines are written once per metric name, before its first sample.
+///
+/// # Arguments
+/// * `metrics` - Samples to render
+///
+/// # Returns
+/// * `String` - Exposition text ending with a newline
+pub fn render_metrics(metrics: &[Metric]) -> String {
+    let mut output = String::new();
+    let mut described: Vec<&str> = Vec::new();
+
+    for metric in metrics {
+        if !described.contains(&metric.name.as_str()) {
+            output.push_str(&format!("# HELP {} {}\n", metric.name, metric.help));
+            let kind = match metric.kind {
+                MetricKind::Gauge => "gauge",
+                MetricKind::Counter => "counter",
+            };
+            output.push_str(&format!("# TYPE {} {}\n", metric.name, kind));
+            described.push(&metric.name);
+        }
+
+        output.push_str(&metric.name);
+        if !metric.labels.is_empty() {
+            let labels: Vec<String> = metric
+                .labels
+                .iter()
+                .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+                .collect();
+            output.push_str(&format!("{{{}}}", labels.join(",")));
+        }
+        output.push_str(&format!(" {}\n", metric.value));
+    }
+
+    output
+}
+
+/// Escapes a label value (backslash, double quote and newline)
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Builds the Pushgateway URL for a job and grouping labels
+///
+/// # Arguments
+/// * `gateway_url` - Pushgateway base URL (e.g. `http://localhost:9091`)
+/// * `job` - Value of the `job` label
+/// * `grouping` - Additional grouping label names and values
+///
+/// # Returns
+/// * `Result<String, Error>` - Push URL or `Error::InvalidInput` if a label cannot be used in a URL path
+pub fn pushgateway_url(gateway_url: &str, job: &str, grouping: &[(&str, &str)]) -> Result<String, Error> {
+    if !gateway_url.starts_with("http://") && !gateway_url.starts_with("https://") {
+        return Err(Error::InvalidInput(format!(
+            "Invalid Pushgateway URL '{}': must start with http:// or https://",
+            gateway_url
+        )));
+    }
+
+    let mut url = format!("{}/metrics/job/{}", gateway_url.trim_end_matches('/'), path_segment(job)?);
+    for (name, value) in grouping {
+        url.push_str(&format!("/{}/{}", path_segment(name)?, path_segment(value)?));
+    }
+    Ok(url)
+}
+
+/// Checks that a label can be used as a URL path segment without encoding
+fn path_segment(value: &str) -> Result<&str, Error> {
+    let valid = !value.is_empty()
+        && value.chars().all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.' | ':'));
+    if valid {
+        Ok(value)
+    } else {
+        Err(Error::InvalidInput(format!(
+            "Invalid metrics label '{}': only letters, digits, '_', '-', '.' and ':' are allowed",
+            value
+        )))
+    }
+}
+
+/// Pushes metrics to a Prometheus Pushgateway
+///
+/// Metrics are sent with POST, so only metrics with the same names in the group are replaced.
+///
+/// # Arguments
+/// * `gateway_url` - Pushgateway base URL
+/// * `job` - Value of the `job` label
+/// * `grouping` - Additional grouping label names and values
+/// * `metrics` - Samples to push
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if the Pushgateway accepted the metrics, or `Error::Http`
+pub async fn push_metrics(gateway_url: &str, job: &str, grouping: &[(&str, &str)], metrics: &[Metric]) -> Result<(), Error> {
+    let url = pushgateway_url(gateway_url, job, grouping)?;
+
+    let response = with_trace_header(reqwest::Client::new().post(&url))
+        .header(reqwest::header::CONTENT_TYPE, "text/plain; version=0.0.4")
+        .body(render_metrics(metrics))
+        .timeout(Duration::from_secs(PUSH_TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| Error::Http(format!("Failed to push metrics to {}: {}", url, e)))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::Http(format!(
+            "Pushgateway {} rejected metrics: {} {}",
+            url,
+            status,
+            body.trim()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Builds the metrics of a `pda-balance` run
+///
+/// # Arguments
+/// * `pda` - PDA address
+/// * `balance` - Balance in lamports, or None if it could not be read
+/// * `timestamp` - Unix timestamp of the run
+///
+/// # Returns
+/// * `Vec<Metric>` - Balance (only when known), result and timestamp gauges
+pub fn balance_metrics(pda: &Pubkey, balance: Option<u64>, timestamp: u64) -> Vec<Metric> {
+    let pda = pda.to_string();
+    let mut metrics = Vec::new();
+    if let Some(balance) = balance {
+        metrics.push(
+            Metric::gauge("dz_validator_pda_balance_lamports", "PDA balance in lamports", balance as f64)
+                .with_label("pda", &pda),
+        );
+    }
+    metrics.push(
+        Metric::gauge("dz_validator_pda_balance_check_success", "1 if the last balance check succeeded", balance.is_some() as u8 as f64)
+            .with_label("pda", &pda),
+    );
+    metrics.push(
+        Metric::gauge("dz_validator_pda_balance_check_timestamp_seconds", "Unix time of the last balance check", timestamp as f64)
+            .with_label("pda", &pda),
+    );
+    metrics
+}
+
+/// Builds the metrics of a `pda-fund-address` run
+///
+/// # Arguments
+/// * `pda` - PDA address
+/// * `outcome` - Funding outcome, or None if funding failed or was cancelled
+/// * `timestamp` - Unix timestamp of the run
+///
+/// # Returns
+/// * `Vec<Metric>` - Amount and fee (only on success), result and timestamp gauges
+pub fn funding_metrics(pda: &Pubkey, outcome: Option<&FundingOutcome>, timestamp: u64) -> Vec<Metric> {
+    let pda = pda.to_string();
+    let mut metrics = Vec::new();
+    if let Some(outcome) = outcome {
+        metrics.push(
+            Metric::gauge("dz_validator_pda_funding_amount_lamports", "Lamports transferred by the last funding", outcome.amount_lamports as f64)
+                .with_label("pda", &pda),
+        );
+        metrics.push(
+            Metric::gauge("dz_validator_pda_funding_fee_lamports", "Fee paid by the last funding", outcome.fee_lamports as f64)
+                .with_label("pda", &pda),
+        );
+    }
+    metrics.push(
+        Metric::gauge("dz_validator_pda_funding_success", "1 if the last funding succeeded", outcome.is_some() as u8 as f64)
+            .with_label("pda", &pda),
+    );
+    metrics.push(
+        Metric::gauge("dz_validator_pda_funding_timestamp_seconds", "Unix time of the last funding attempt", timestamp as f64)
+            .with_label("pda", &pda),
+    );
+    metrics
+}
+
+/// Current metrics of a long-running process, shared with the metrics endpoint
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    metrics: Mutex<Vec<Metric>>,
+}
+
+impl MetricsRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        MetricsRegistry::default()
+    }
+
+    /// Sets a sample, replacing the one with the same name and labels
+    ///
+    /// Samples of one metric are kept together, as the exposition format requires.
+    ///
+    /// # Arguments
+    /// * `metric` - Sample to store
+    pub fn set(&self, metric: Metric) {
+        let mut metrics = self.metrics.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(existing) = metrics.iter_mut().find(|existing| existing.name == metric.name && existing.labels == metric.labels) {
+            *existing = metric;
+        } else if let Some(position) = metrics.iter().rposition(|existing| existing.name == metric.name) {
+            metrics.insert(position + 1, metric);
+        } else {
+            metrics.push(metric);
+        }
+    }
+
+    /// Adds one to a counter, registering it at 1 if it does not exist yet
+    ///
+    /// # Arguments
+    /// * `name` - Counter name
+    /// * `help` - Help text
+    pub fn increment(&self, name: &str, help: &str) {
+        let value = self.value(name, &[]).unwrap_or(0.0) + 1.0;
+        self.set(Metric::counter(name, help, value));
+    }
+
+    /// Returns the value of a sample
+    ///
+    /// # Arguments
+    /// * `name` - Metric name
+    /// * `labels` - Label names and values of the sample
+    pub fn value(&self, name: &str, labels: &[(String, String)]) -> Option<f64> {
+        let metrics = self.metrics.lock().unwrap_or_else(|e| e.into_inner());
+        metrics.iter().find(|metric| metric.name == name && metric.labels == labels).map(|metric| metric.value)
+    }
+
+    /// Registers the funding and RPC error counters at 0, so they are exported before the first event
+    pub fn register_counters(&self) {
+        for (name, help) in [
+            (FUNDING_ATTEMPTS_TOTAL, "Funding transactions attempted"),
+            (FUNDING_FAILURES_TOTAL, "Funding transactions that failed"),
+            (RPC_ERRORS_TOTAL, "RPC requests that failed after retries"),
+        ] {
+            if self.value(name, &[]).is_none() {
+                self.set(Metric::counter(name, help, 0.0));
+            }
+        }
+    }
+
+    /// Renders all samples in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        render_metrics(&self.metrics.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Starts an HTTP endpoint serving the registry at `/metrics`
+///
+/// The listener is bound before returning, so a port that is in use is reported immediately;
+/// requests are then served in the background for the lifetime of the process.
+///
+/// # Arguments
+/// * `port` - TCP port to listen on (on all interfaces; 0 picks a free port)
+/// * `registry` - Metrics to serve
+///
+/// # Returns
+/// * `Result<SocketAddr, Error>` - Bound address or `Error::Io`
+pub async fn start_metrics_server(port: u16, registry: Arc<MetricsRegistry>) -> Result<SocketAddr, Error> {
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .await
+        .map_err(|e| Error::Io(format!("Failed to listen on metrics port {}: {}", port, e)))?;
+    let address = listener
+        .local_addr()
+        .map_err(|e| Error::Io(format!("Failed to read metrics listener address: {}", e)))?;
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+            let registry = Arc::clone(&registry);
+            // A misbehaving scraper only affects its own connection
+            tokio::spawn(async move {
+                let _ = serve_metrics_request(stream, &registry).await;
+            });
+        }
+    });
+
+    Ok(address)
+}
+
+/// Answers one HTTP request: `GET /metrics` gets the exposition text, anything else a 404
+async fn serve_metrics_request(mut stream: tokio::net::TcpStream, registry: &MetricsRegistry) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+
+    let request_line = String::from_utf8_lossy(&request).lines().next().unwrap_or_default().to_string();
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+
+    let (status, body) = if method == "GET" && (path == "/metrics" || path.starts_with("/metrics?")) {
+        ("200 OK", registry.render())
+    } else {
+        ("404 Not Found", "Not found; metrics are served at /metrics\n".to_string())
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_metrics() {
+        let metrics = vec![
+            Metric::gauge("dz_balance_lamports", "PDA balance", 1500.0).with_label("pda", "Pda1"),
+            Metric::gauge("dz_balance_lamports", "PDA balance", 42.0).with_label("pda", "Pda2"),
+            Metric::gauge("dz_success", "Run result", 1.0),
+        ];
+
+        assert_eq!(
+            render_metrics(&metrics),
+            "# HELP dz_balance_lamports PDA balance\n\
+             # TYPE dz_balance_lamports gauge\n\
+             dz_balance_lamports{pda=\"Pda1\"} 1500\n\
+             dz_balance_lamports{pda=\"Pda2\"} 42\n\
+             # HELP dz_success Run result\n\
+             # TYPE dz_success gauge\n\
+             dz_success 1\n"
+        );
+    }
+
+    #[test]
+    fn test_render_escapes_label_values() {
+        let metric = Metric::gauge("m", "h", 0.5).with_label("error", "say \"hi\"\\\nbye");
+        assert!(render_metrics(&[metric]).contains("m{error=\"say \\\"hi\\\"\\\\\\nbye\"} 0.5\n"));
+    }
+
+    #[test]
+    fn test_run_metrics() {
+        let pda = Pubkey::new_unique();
+
+        let metrics = balance_metrics(&pda, Some(2_000_000_000), 1_731_000_000);
+        let text = render_metrics(&metrics);
+        assert!(text.contains(&format!("dz_validator_pda_balance_lamports{{pda=\"{}\"}} 2000000000\n", pda)));
+        assert!(text.contains(&format!("dz_validator_pda_balance_check_success{{pda=\"{}\"}} 1\n", pda)));
+        assert!(text.contains("} 1731000000\n"));
+
+        let failed = balance_metrics(&pda, None, 1_731_000_000);
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0].value, 0.0);
+
+        let outcome = FundingOutcome { payer: "payer".to_string(), signature: "sig".to_string(), amount_lamports: 1_500_000_000, fee_lamports: 5_000, warnings: Vec::new(), policy_checks: Vec::new(), creates_account: false, memo: None, epoch: None };
+        assert_eq!(funding_metrics(&pda, Some(&outcome), 0).len(), 4);
+        let failed = funding_metrics(&pda, None, 0);
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0].name, "dz_validator_pda_funding_success");
+        assert_eq!(failed[0].value, 0.0);
+    }
+
+    #[test]
+    fn test_pushgateway_url() {
+        assert_eq!(
+            pushgateway_url("http://localhost:9091/", "dz_validator_pda", &[("validator", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")]).unwrap(),
+            "http://localhost:9091/metrics/job/dz_validator_pda/validator/FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL"
+        );
+        assert!(pushgateway_url("localhost:9091", "job", &[]).unwrap_err().is_user_error());
+        assert!(pushgateway_url("http://localhost:9091", "my/job", &[]).is_err());
+        assert!(pushgateway_url("http://localhost:9091", "", &[]).is_err());
+    }
+
+    #[test]
+    fn test_registry_keeps_samples_of_a_metric_together() {
+        let registry = MetricsRegistry::new();
+        registry.set(Metric::gauge("dz_balance_lamports", "PDA balance", 1.0).with_label("pda", "Pda1"));
+        registry.increment("dz_rpc_errors_total", "RPC errors");
+        registry.set(Metric::gauge("dz_balance_lamports", "PDA balance", 2.0).with_label("pda", "Pda2"));
+        registry.set(Metric::gauge("dz_balance_lamports", "PDA balance", 3.0).with_label("pda", "Pda1"));
+        registry.increment("dz_rpc_errors_total", "RPC errors");
+
+        assert_eq!(
+            registry.render(),
+            "# HELP dz_balance_lamports PDA balance\n\
+             # TYPE dz_balance_lamports gauge\n\
+             dz_balance_lamports{pda=\"Pda1\"} 3\n\
+             dz_balance_lamports{pda=\"Pda2\"} 2\n\
+             # HELP dz_rpc_errors_total RPC errors\n\
+             # TYPE dz_rpc_errors_total counter\n\
+             dz_rpc_errors_total 2\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let registry = Arc::new(MetricsRegistry::new());
+        registry.set(Metric::gauge("dz_validator_pda_balance_lamports", "PDA balance", 42.0));
+        let address = start_metrics_server(0, Arc::clone(&registry)).await.unwrap();
+
+        let base = format!("http://127.0.0.1:{}", address.port());
+        let response = reqwest::get(format!("{}/metrics", base)).await.unwrap();
+        assert!(response.status().is_success());
+        assert!(response.text().await.unwrap().contains("dz_validator_pda_balance_lamports 42\n"));
+
+        let response = reqwest::get(format!("{}/other", base)).await.unwrap();
+        assert_eq!(response.status().as_u16(), 404);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::Error;
+use crate::rpc::SolanaRpc;
+use solana_rpc_client_nonce_utils::nonblocking::data_from_account;
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+
+/// Durable nonce account used instead of a recent blockhash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceConfig {
+    /// Nonce account pubkey
+    pub account: Pubkey,
+    /// Keypair file or signer URL of the nonce authority (defaults to the funding wallet)
+    pub authority: Option<String>,
+}
+
+/// Reads the stored nonce value of a durable nonce account
+///
+/// # Arguments
+/// * `account` - Nonce account data
+/// * `nonce_account` - Nonce account pubkey (for error messages)
+/// * `authority` - Expected nonce authority
+///
+/// # Returns
+/// * `Result<Hash, Error>` - Nonce value to use as the transaction blockhash, or `Error::InvalidInput`
+pub fn nonce_blockhash(account: &Account, nonce_account: &Pubkey, authority: &Pubkey) -> Result<Hash, Error> {
+    let data = data_from_account(account)
+        .map_err(|e| Error::InvalidInput(format!("Account {} is not an initialized nonce account: {}", nonce_account, e)))?;
+
+    if data.authority != *authority {
+        return Err(Error::InvalidInput(format!(
+            "Nonce account {} is controlled by {}, not by {}; pass --nonce-authority",
+            nonce_account, data.authority, authority
+        )));
+    }
+    Ok(data.blockhash())
+}
+
+/// Fetches the current nonce value of a durable nonce account
+///
+/// # Arguments
+/// * `nonce_account` - Nonce account pubkey
+/// * `authority` - Expected nonce authority
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Hash, Error>` - Nonce value or error
+pub async fn get_nonce_blockhash(nonce_account: &Pubkey, authority: &Pubkey, rpc: &dyn SolanaRpc) -> Result<Hash, Error> {
+    let account = rpc.get_account_with_context(nonce_account)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get nonce account", &e))?
+        .value
+        .ok_or_else(|| Error::InvalidInput(format!("Nonce account {} does not exist", nonce_account)))?;
+
+    nonce_blockhash(&account, nonce_account, authority)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_nonce::state::{Data, DurableNonce, State};
+    use solana_nonce::versions::Versions;
+
+    fn nonce_account(authority: &Pubkey, durable_nonce: DurableNonce) -> Account {
+        let data = Data::new(*authority, durable_nonce, 5_000);
+        Account::new_data(1_447_680, &Versions::new(State::Initialized(data)), &solana_system_interface::program::ID).unwrap()
+    }
+
+    #[test]
+    fn test_nonce_blockhash() {
+        let nonce = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let durable_nonce = DurableNonce::from_blockhash(&Hash::new_unique());
+        let account = nonce_account(&authority, durable_nonce);
+
+        assert_eq!(nonce_blockhash(&account, &nonce, &authority).unwrap(), *durable_nonce.as_hash());
+
+        let error = nonce_blockhash(&account, &nonce, &Pubkey::new_unique()).unwrap_err();
+        assert!(error.to_string().contains("--nonce-authority"));
+    }
+
+    #[test]
+    fn test_nonce_blockhash_rejects_other_accounts() {
+        let wallet = Account::new(1_000_000, 0, &solana_system_interface::program::ID);
+        let error = nonce_blockhash(&wallet, &Pubkey::new_unique(), &Pubkey::new_unique()).unwrap_err();
+        assert!(error.is_user_error());
+    }
+}
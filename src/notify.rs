@@ -0,0 +1,371 @@
+use crate::amount::format_sol;
+use crate::audit::format_timestamp;
+use crate::trace::{trace_id, with_trace_header};
+use crate::{parse_amount, Amount, Error};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// Timeout for delivering one notification
+const NOTIFY_TIMEOUT_SECS: u64 = 10;
+
+/// Base URL of the Telegram Bot API
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+/// Format of the notification request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyKind {
+    /// Generic JSON document with the event name, message and details
+    #[default]
+    Webhook,
+    /// Slack incoming webhook (`{"text": ...}`)
+    Slack,
+    /// Telegram bot `sendMessage`
+    Telegram,
+}
+
+impl fmt::Display for NotifyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotifyKind::Webhook => write!(f, "webhook"),
+            NotifyKind::Slack => write!(f, "slack"),
+            NotifyKind::Telegram => write!(f, "telegram"),
+        }
+    }
+}
+
+/// `[notify]` table of the config file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotifyConfig {
+    /// Request format
+    #[serde(default)]
+    pub kind: NotifyKind,
+    /// Webhook URL (webhook and slack)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Bot token (telegram)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot_token: Option<String>,
+    /// Chat to post to (telegram)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    /// PDA balance that triggers a low-balance notification in `pda-watch` (e.g. "0.5" or "500000000lamports")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_balance: Option<String>,
+}
+
+/// Event worth telling the operator about
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotifyEvent {
+    /// A funding transaction was sent successfully
+    FundingLanded { validator: String, pda: String, amount_lamports: u64, signature: String },
+    /// A funding attempt failed
+    FundingFailed { validator: String, pda: String, error: String },
+    /// A watched PDA balance dropped below the threshold
+    BalanceLow { pda: String, balance_lamports: u64, threshold_lamports: u64 },
+    /// A watched PDA balance is back at or above the threshold
+    BalanceRecovered { pda: String, balance_lamports: u64, threshold_lamports: u64 },
+    /// A monitored validator has been missing from gossip for longer than the grace period
+    GossipAbsent { validator: String, absent_secs: u64, last_seen: Option<u64> },
+    /// A validator reported as missing is back in gossip
+    GossipReturned { validator: String, absent_secs: u64 },
+    /// Free-form message of a `run` script step
+    Message { text: String },
+}
+
+impl NotifyEvent {
+    /// Returns the event name used in generic webhook payloads
+    pub fn name(&self) -> &'static str {
+        match self {
+            NotifyEvent::FundingLanded { .. } => "funding-landed",
+            NotifyEvent::FundingFailed { .. } => "funding-failed",
+            NotifyEvent::BalanceLow { .. } => "balance-low",
+            NotifyEvent::BalanceRecovered { .. } => "balance-recovered",
+            NotifyEvent::GossipAbsent { .. } => "gossip-absent",
+            NotifyEvent::GossipReturned { .. } => "gossip-returned",
+            NotifyEvent::Message { .. } => "message",
+        }
+    }
+}
+
+impl fmt::Display for NotifyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotifyEvent::FundingLanded { validator, pda, amount_lamports, signature } => write!(
+                f,
+                "Funded PDA {} of validator {} with {} SOL (signature {})",
+                pda, validator, format_sol(*amount_lamports), signature
+            ),
+            NotifyEvent::FundingFailed { validator, pda, error } => {
+                write!(f, "Funding PDA {} of validator {} failed: {}", pda, validator, error)
+            }
+            NotifyEvent::BalanceLow { pda, balance_lamports, threshold_lamports } => write!(
+                f,
+                "PDA {} balance {} SOL is below {} SOL",
+                pda, format_sol(*balance_lamports), format_sol(*threshold_lamports)
+            ),
+            NotifyEvent::BalanceRecovered { pda, balance_lamports, threshold_lamports } => write!(
+                f,
+                "PDA {} balance {} SOL is back above {} SOL",
+                pda, format_sol(*balance_lamports), format_sol(*threshold_lamports)
+            ),
+            NotifyEvent::GossipAbsent { validator, absent_secs, last_seen } => {
+                write!(f, "Validator {} has been missing from gossip for {} seconds", validator, absent_secs)?;
+                match last_seen {
+                    Some(last_seen) => write!(f, " (last seen {} UTC)", format_timestamp(*last_seen)),
+                    None => write!(f, " (not seen since monitoring started)"),
+                }
+            }
+            NotifyEvent::GossipReturned { validator, absent_secs } => {
+                write!(f, "Validator {} is back in gossip after {} seconds", validator, absent_secs)
+            }
+            NotifyEvent::Message { text } => f.write_str(text),
+        }
+    }
+}
+
+impl NotifyConfig {
+    /// Returns the low-balance threshold in lamports, if configured
+    ///
+    /// # Returns
+    /// * `Result<Option<u64>, Error>` - Threshold or `Error::InvalidInput` (`ALL` is not a threshold)
+    pub fn low_balance_lamports(&self) -> Result<Option<u64>, Error> {
+        let Some(value) = &self.low_balance else {
+            return Ok(None);
+        };
+        match parse_amount(value) {
+            Ok(Amount::Lamports(lamports)) => Ok(Some(lamports)),
+            _ => Err(Error::InvalidInput(format!(
+                "Invalid notify.low_balance '{}': expected an amount like 0.5 or 500000000lamports",
+                value
+            ))),
+        }
+    }
+
+    /// Builds the request that delivers an event
+    ///
+    /// # Arguments
+    /// * `event` - Event to deliver
+    /// * `trace_id` - Trace id of the run, added to the body or message
+    ///
+    /// # Returns
+    /// * `Result<(String, serde_json::Value), Error>` - URL and JSON body, or `Error::InvalidInput`
+    ///   if the settings of the selected kind are missing
+    pub fn request(&self, event: &NotifyEvent, trace_id: Option<&str>) -> Result<(String, serde_json::Value), Error> {
+        let message = match trace_id {
+            Some(trace_id) if self.kind != NotifyKind::Webhook => format!("{} [trace {}]", event, trace_id),
+            _ => event.to_string(),
+        };
+        match self.kind {
+            NotifyKind::Webhook => {
+                let mut body = serde_json::json!({ "event": event.name(), "message": message });
+                if let Some(trace_id) = trace_id {
+                    body["trace_id"] = trace_id.into();
+                }
+                match event {
+                    NotifyEvent::FundingLanded { validator, pda, amount_lamports, signature } => {
+                        body["validator"] = validator.clone().into();
+                        body["pda"] = pda.clone().into();
+                        body["amount_lamports"] = (*amount_lamports).into();
+                        body["signature"] = signature.clone().into();
+                    }
+                    NotifyEvent::FundingFailed { validator, pda, error } => {
+                        body["validator"] = validator.clone().into();
+                        body["pda"] = pda.clone().into();
+                        body["error"] = error.clone().into();
+                    }
+                    NotifyEvent::BalanceLow { pda, balance_lamports, threshold_lamports }
+                    | NotifyEvent::BalanceRecovered { pda, balance_lamports, threshold_lamports } => {
+                        body["pda"] = pda.clone().into();
+                        body["balance_lamports"] = (*balance_lamports).into();
+                        body["threshold_lamports"] = (*threshold_lamports).into();
+                    }
+                    NotifyEvent::GossipAbsent { validator, absent_secs, last_seen } => {
+                        body["validator"] = validator.clone().into();
+                        body["absent_secs"] = (*absent_secs).into();
+                        body["last_seen"] = (*last_seen).into();
+                    }
+                    NotifyEvent::GossipReturned { validator, absent_secs } => {
+                        body["validator"] = validator.clone().into();
+                        body["absent_secs"] = (*absent_secs).into();
+                    }
+                    NotifyEvent::Message { .. } => {}
+                }
+                Ok((self.webhook_url()?, body))
+            }
+            NotifyKind::Slack => Ok((self.webhook_url()?, serde_json::json!({ "text": message }))),
+            NotifyKind::Telegram => {
+                let (Some(bot_token), Some(chat_id)) = (&self.bot_token, &self.chat_id) else {
+                    return Err(Error::InvalidInput("Telegram notifications need notify.bot_token and notify.chat_id".to_string()));
+                };
+                Ok((
+                    format!("{}/bot{}/sendMessage", TELEGRAM_API_URL, bot_token),
+                    serde_json::json!({ "chat_id": chat_id, "text": message }),
+                ))
+            }
+        }
+    }
+
+    /// Returns the configured webhook URL
+    fn webhook_url(&self) -> Result<String, Error> {
+        match &self.url {
+            Some(url) if url.starts_with("http://") || url.starts_with("https://") => Ok(url.clone()),
+            Some(url) => Err(Error::InvalidInput(format!(
+                "Invalid notify.url '{}': must start with http:// or https://",
+                url
+            ))),
+            None => Err(Error::InvalidInput(format!("{} notifications need notify.url", self.kind))),
+        }
+    }
+}
+
+/// Posts an event to the configured webhook
+///
+/// # Arguments
+/// * `config` - Notification settings
+/// * `event` - Event to deliver
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if the endpoint accepted it, `Error::InvalidInput` for incomplete
+///   settings or `Error::Http` if delivery failed
+pub async fn send_notification(config: &NotifyConfig, event: &NotifyEvent) -> Result<(), Error> {
+    let (url, body) = config.request(event, trace_id())?;
+    // The Telegram URL contains the bot token, so it is left out of error messages
+    let target = match config.kind {
+        NotifyKind::Telegram => TELEGRAM_API_URL.to_string(),
+        _ => url.clone(),
+    };
+
+    let response = with_trace_header(reqwest::Client::new().post(&url))
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+        .timeout(Duration::from_secs(NOTIFY_TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| Error::Http(format!("Failed to send notification to {}: {}", target, e.without_url())))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::Http(format!("Notification endpoint {} rejected the message: {} {}", target, status, body.trim())));
+    }
+
+    Ok(())
+}
+
+/// Detects a watched balance crossing the low-balance threshold
+#[derive(Debug, Clone)]
+pub struct BalanceThreshold {
+    threshold_lamports: u64,
+    below: Option<bool>,
+}
+
+impl BalanceThreshold {
+    /// Creates a detector
+    ///
+    /// # Arguments
+    /// * `threshold_lamports` - Balance below which the operator is notified
+    pub fn new(threshold_lamports: u64) -> Self {
+        BalanceThreshold { threshold_lamports, below: None }
+    }
+
+    /// Records a balance and returns an event when it crosses the threshold
+    ///
+    /// A balance that is already low at the first observation is reported; a recovery is only
+    /// reported after a low balance.
+    ///
+    /// # Arguments
+    /// * `pda` - PDA address
+    /// * `balance_lamports` - Current balance
+    ///
+    /// # Returns
+    /// * `Option<NotifyEvent>` - `BalanceLow` or `BalanceRecovered` on a crossing
+    pub fn observe(&mut self, pda: &str, balance_lamports: u64) -> Option<NotifyEvent> {
+        let below = balance_lamports < self.threshold_lamports;
+        let previous = self.below.replace(below);
+        let (pda, threshold_lamports) = (pda.to_string(), self.threshold_lamports);
+        match (previous, below) {
+            (None | Some(false), true) => Some(NotifyEvent::BalanceLow { pda, balance_lamports, threshold_lamports }),
+            (Some(true), false) => Some(NotifyEvent::BalanceRecovered { pda, balance_lamports, threshold_lamports }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn landed() -> NotifyEvent {
+        NotifyEvent::FundingLanded {
+            validator: "Validator1".to_string(),
+            pda: "Pda1".to_string(),
+            amount_lamports: 1_500_000_000,
+            signature: "Sig1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_request_formats() {
+        let webhook = NotifyConfig { url: Some("https://example.com/hook".to_string()), ..NotifyConfig::default() };
+        let (url, body) = webhook.request(&landed(), None).unwrap();
+        assert_eq!(url, "https://example.com/hook");
+        assert_eq!(body["event"], "funding-landed");
+        assert_eq!(body["amount_lamports"], 1_500_000_000u64);
+        assert_eq!(body["message"], "Funded PDA Pda1 of validator Validator1 with 1.5 SOL (signature Sig1)");
+        assert!(body.get("trace_id").is_none());
+        let (_, body) = webhook.request(&landed(), Some("3f2a")).unwrap();
+        assert_eq!(body["trace_id"], "3f2a");
+        let message = NotifyEvent::Message { text: "Weekly top-up done".to_string() };
+        let (_, body) = webhook.request(&message, None).unwrap();
+        assert_eq!(body, serde_json::json!({ "event": "message", "message": "Weekly top-up done" }));
+
+        let slack = NotifyConfig { kind: NotifyKind::Slack, ..webhook.clone() };
+        let (_, body) = slack.request(&landed(), None).unwrap();
+        assert_eq!(body, serde_json::json!({ "text": landed().to_string() }));
+        let (_, body) = slack.request(&landed(), Some("3f2a")).unwrap();
+        assert_eq!(body["text"], format!("{} [trace 3f2a]", landed()));
+
+        let telegram = NotifyConfig {
+            kind: NotifyKind::Telegram,
+            bot_token: Some("123:abc".to_string()),
+            chat_id: Some("-100".to_string()),
+            ..NotifyConfig::default()
+        };
+        let (url, body) = telegram.request(&landed(), None).unwrap();
+        assert_eq!(url, "https://api.telegram.org/bot123:abc/sendMessage");
+        assert_eq!(body["chat_id"], "-100");
+    }
+
+    #[test]
+    fn test_incomplete_settings_are_user_errors() {
+        let missing_url = NotifyConfig { kind: NotifyKind::Slack, ..NotifyConfig::default() };
+        assert!(missing_url.request(&landed(), None).unwrap_err().is_user_error());
+
+        let bad_url = NotifyConfig { url: Some("example.com".to_string()), ..NotifyConfig::default() };
+        assert!(bad_url.request(&landed(), None).is_err());
+
+        let telegram = NotifyConfig { kind: NotifyKind::Telegram, bot_token: Some("t".to_string()), ..NotifyConfig::default() };
+        assert!(telegram.request(&landed(), None).is_err());
+
+        let all = NotifyConfig { low_balance: Some("ALL".to_string()), ..NotifyConfig::default() };
+        assert!(all.low_balance_lamports().is_err());
+        let half = NotifyConfig { low_balance: Some("0.5".to_string()), ..NotifyConfig::default() };
+        assert_eq!(half.low_balance_lamports().unwrap(), Some(500_000_000));
+    }
+
+    #[test]
+    fn test_threshold_crossings() {
+        let mut threshold = BalanceThreshold::new(1_000);
+        assert_eq!(threshold.observe("Pda1", 5_000), None);
+        assert!(matches!(threshold.observe("Pda1", 999), Some(NotifyEvent::BalanceLow { balance_lamports: 999, .. })));
+        assert_eq!(threshold.observe("Pda1", 500), None);
+        assert!(matches!(threshold.observe("Pda1", 1_000), Some(NotifyEvent::BalanceRecovered { .. })));
+        assert_eq!(threshold.observe("Pda1", 2_000), None);
+
+        // Already low when watching starts
+        let mut threshold = BalanceThreshold::new(1_000);
+        assert!(matches!(threshold.observe("Pda1", 10), Some(NotifyEvent::BalanceLow { .. })));
+    }
+}
@@ -0,0 +1,315 @@
+use crate::{funding_message, Amount, Error, FundingSigners, NonceConfig};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use crate::rpc::SolanaRpc;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+
+/// Funding transaction signed without RPC access
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedFunding {
+    /// Funding wallet the lamports are transferred from
+    pub payer: String,
+    /// Transaction signature (known before broadcast), or None until the fee payer has signed
+    pub signature: Option<String>,
+    /// Amount to transfer in lamports
+    pub amount_lamports: u64,
+    /// Base64-encoded, possibly partially signed transaction
+    pub transaction: String,
+    /// Signers that still have to countersign before broadcast
+    pub missing_signers: Vec<String>,
+}
+
+/// Parses a base58 blockhash given with `--blockhash`
+///
+/// # Arguments
+/// * `value` - Blockhash string
+///
+/// # Returns
+/// * `Result<Hash, Error>` - Blockhash or `Error::InvalidInput`
+pub fn parse_blockhash(value: &str) -> Result<Hash, Error> {
+    value
+        .trim()
+        .parse::<Hash>()
+        .map_err(|e| Error::InvalidInput(format!("Invalid blockhash '{}': {}", value, e)))
+}
+
+/// Signs a PDA funding transaction offline
+///
+/// Nothing is read from RPC, so the validator status, the payer balance and the fee are not checked;
+/// they are checked by the cluster when the transaction is broadcast. Roles given as a bare pubkey
+/// are left unsigned, so another party can sign the same transaction and the signatures can be
+/// combined with `combine_signatures`.
+///
+/// # Arguments
+/// * `validator_id` - The validator's public key
+/// * `signers` - Funding wallet, fee payer and additional signers
+/// * `amount` - Exact amount to transfer (`Amount::All` needs the payer balance and is rejected)
+/// * `blockhash` - Recent blockhash obtained on an online machine, or the nonce value when `nonce` is given
+/// * `nonce` - Durable nonce account and authority (optional); the transaction then does not expire until the nonce advances
+///
+/// # Returns
+/// * `Result<SignedFunding, Error>` - Signed transaction or error
+pub fn sign_funding_offline(
+    validator_id: &Pubkey,
+    signers: &FundingSigners,
+    amount: Amount,
+    blockhash: Hash,
+    nonce: Option<&NonceConfig>,
+) -> Result<SignedFunding, Error> {
+    let Amount::Lamports(amount_lamports) = amount else {
+        return Err(Error::InvalidInput(
+            "Amount ALL needs the payer balance and cannot be used with --sign-only".to_string(),
+        ));
+    };
+
+    let (pool, accounts) = signers.load(nonce)?;
+    let mut transaction = Transaction::new_unsigned(funding_message(validator_id, &accounts, amount_lamports, None, None, &blockhash));
+    let missing = pool.sign_partial(&mut transaction)?;
+
+    Ok(SignedFunding {
+        payer: accounts.funder.to_string(),
+        signature: Some(transaction.signatures[0]).filter(|signature| *signature != Signature::default()).map(|signature| signature.to_string()),
+        amount_lamports,
+        transaction: encode_transaction(&transaction)?,
+        missing_signers: missing.iter().map(Pubkey::to_string).collect(),
+    })
+}
+
+/// Returns the required signers whose signature is still missing
+///
+/// # Arguments
+/// * `transaction` - Possibly partially signed transaction
+///
+/// # Returns
+/// * `Vec<Pubkey>` - Signers in the order of the transaction's signatures
+pub fn missing_signers(transaction: &Transaction) -> Vec<Pubkey> {
+    transaction
+        .signatures
+        .iter()
+        .zip(&transaction.message.account_keys)
+        .filter(|(signature, _)| **signature == Signature::default())
+        .map(|(_, pubkey)| *pubkey)
+        .collect()
+}
+
+/// Merges the signatures of partially signed copies of one transaction
+///
+/// # Arguments
+/// * `transactions` - Copies of the same message, each signed by some of its signers
+///
+/// # Returns
+/// * `Result<Transaction, Error>` - Transaction with every signature found, or `Error::InvalidInput` if the messages differ
+pub fn combine_signatures(transactions: Vec<Transaction>) -> Result<Transaction, Error> {
+    let mut transactions = transactions.into_iter();
+    let mut combined = transactions
+        .next()
+        .ok_or_else(|| Error::InvalidInput("No transactions to combine".to_string()))?;
+
+    for transaction in transactions {
+        if transaction.message != combined.message {
+            return Err(Error::InvalidInput(
+                "Transactions cannot be combined: they were signed for different messages (check amount, fee payer and blockhash)".to_string(),
+            ));
+        }
+        for (signature, other) in combined.signatures.iter_mut().zip(transaction.signatures) {
+            if *signature == Signature::default() {
+                *signature = other;
+            }
+        }
+    }
+    Ok(combined)
+}
+
+/// Encodes a transaction as base64 of its wire format
+///
+/// # Arguments
+/// * `transaction` - Signed transaction
+///
+/// # Returns
+/// * `Result<String, Error>` - Base64 string
+pub fn encode_transaction(transaction: &Transaction) -> Result<String, Error> {
+    let bytes = bincode::serialize(transaction)
+        .map_err(|e| Error::InvalidInput(format!("Failed to serialize transaction: {}", e)))?;
+    Ok(BASE64.encode(bytes))
+}
+
+/// Decodes a base64 transaction and checks that it is fully signed
+///
+/// # Arguments
+/// * `encoded` - Base64 string (surrounding whitespace is ignored)
+///
+/// # Returns
+/// * `Result<Transaction, Error>` - Transaction or `Error::InvalidInput` if it is malformed, unsigned or its signatures do not verify
+pub fn decode_transaction(encoded: &str) -> Result<Transaction, Error> {
+    let transaction = decode_partial_transaction(encoded)?;
+    check_fully_signed(&transaction)?;
+    Ok(transaction)
+}
+
+/// Decodes a base64 transaction that may still miss signatures
+///
+/// # Arguments
+/// * `encoded` - Base64 string (surrounding whitespace is ignored)
+///
+/// # Returns
+/// * `Result<Transaction, Error>` - Transaction or `Error::InvalidInput` if it is malformed or a present signature does not verify
+pub fn decode_partial_transaction(encoded: &str) -> Result<Transaction, Error> {
+    let bytes = BASE64
+        .decode(encoded.trim())
+        .map_err(|e| Error::InvalidInput(format!("Invalid base64 transaction: {}", e)))?;
+    let transaction: Transaction = bincode::deserialize(&bytes)
+        .map_err(|e| Error::InvalidInput(format!("Invalid transaction: {}", e)))?;
+
+    let verified = transaction
+        .signatures
+        .iter()
+        .zip(transaction.verify_with_results())
+        .all(|(signature, valid)| valid || *signature == Signature::default());
+    if !verified {
+        return Err(Error::InvalidInput("Transaction signatures do not verify".to_string()));
+    }
+    Ok(transaction)
+}
+
+/// Checks that every required signer has signed
+///
+/// # Arguments
+/// * `transaction` - Transaction whose present signatures were verified
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if fully signed, or `Error::InvalidInput` listing the missing signers
+pub fn check_fully_signed(transaction: &Transaction) -> Result<(), Error> {
+    let missing = missing_signers(transaction);
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let missing: Vec<String> = missing.iter().map(Pubkey::to_string).collect();
+    Err(Error::InvalidInput(format!(
+        "Transaction is missing signatures from {}; combine it with their --sign-only output",
+        missing.join(", ")
+    )))
+}
+
+/// Submits a previously signed transaction
+///
+/// # Arguments
+/// * `transaction` - Signed transaction
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<String, Error>` - Transaction signature or error (an expired blockhash is reported by preflight)
+pub async fn broadcast_transaction(transaction: &Transaction, rpc: &dyn SolanaRpc) -> Result<String, Error> {
+    let signature = rpc.send_transaction(transaction).await
+        .map_err(|e| Error::from_client_error("Failed to broadcast transaction", &e))?;
+    Ok(signature.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_funding_transaction;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let payer = Keypair::new();
+        let transaction = build_funding_transaction(&Pubkey::new_unique(), &payer, 1_500_000_000, Hash::new_unique(), None);
+
+        let encoded = encode_transaction(&transaction).unwrap();
+        let decoded = decode_transaction(&format!("{}\n", encoded)).unwrap();
+        assert_eq!(decoded, transaction);
+        assert_eq!(decoded.message.account_keys[0], payer.pubkey());
+    }
+
+    #[test]
+    fn test_decode_rejects_tampered_transaction() {
+        let payer = Keypair::new();
+        let mut transaction = build_funding_transaction(&Pubkey::new_unique(), &payer, 1_000, Hash::new_unique(), None);
+        transaction.message.recent_blockhash = Hash::new_unique();
+
+        let error = decode_transaction(&encode_transaction(&transaction).unwrap()).unwrap_err();
+        assert!(error.is_user_error());
+        assert!(decode_transaction("not base64!").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_nonce_transaction_advances_nonce_first() {
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        let nonce_account = Pubkey::new_unique();
+        let nonce_value = Hash::new_unique();
+
+        let transaction = build_funding_transaction(&Pubkey::new_unique(), &payer, 1_000, nonce_value, Some((nonce_account, &authority)));
+        assert!(transaction.is_signed());
+        assert_eq!(transaction.signatures.len(), 2);
+        assert_eq!(transaction.message.recent_blockhash, nonce_value);
+        assert_eq!(transaction.message.instructions.len(), 2);
+        assert_eq!(transaction.message.account_keys[transaction.message.instructions[0].accounts[0] as usize], nonce_account);
+
+        // The payer can also be the nonce authority
+        let transaction = build_funding_transaction(&Pubkey::new_unique(), &payer, 1_000, nonce_value, Some((nonce_account, &payer)));
+        assert_eq!(transaction.signatures.len(), 1);
+        assert!(decode_transaction(&encode_transaction(&transaction).unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_sign_only_rejects_all_and_bad_blockhash() {
+        let error = sign_funding_offline(&Pubkey::new_unique(), &FundingSigners::new("/nonexistent/keypair.json"), Amount::All, Hash::default(), None).unwrap_err();
+        assert!(error.to_string().contains("--sign-only"));
+
+        assert!(parse_blockhash("not-a-blockhash").unwrap_err().is_user_error());
+        let blockhash = Hash::new_unique();
+        assert_eq!(parse_blockhash(&blockhash.to_string()).unwrap(), blockhash);
+    }
+
+    #[test]
+    fn test_partial_signatures_are_combined() {
+        let dir = std::env::temp_dir().join(format!("dz_validator_pda_multisig_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let funder = Keypair::new();
+        let fee_payer = Keypair::new();
+        let funder_path = dir.join("funder.json");
+        let fee_payer_path = dir.join("fee-payer.json");
+        solana_sdk::signature::write_keypair_file(&funder, &funder_path).unwrap();
+        solana_sdk::signature::write_keypair_file(&fee_payer, &fee_payer_path).unwrap();
+
+        let validator_id = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let amount = Amount::Lamports(1_000);
+
+        // Each party signs only its own role
+        let first = FundingSigners {
+            funder: funder_path.to_string_lossy().into_owned(),
+            fee_payer: Some(fee_payer.pubkey().to_string()),
+            keypairs: Vec::new(),
+        };
+        let second = FundingSigners {
+            funder: funder.pubkey().to_string(),
+            fee_payer: Some(fee_payer.pubkey().to_string()),
+            keypairs: vec![fee_payer_path.to_string_lossy().into_owned()],
+        };
+        let first = sign_funding_offline(&validator_id, &first, amount, blockhash, None).unwrap();
+        let second = sign_funding_offline(&validator_id, &second, amount, blockhash, None).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first.missing_signers, vec![fee_payer.pubkey().to_string()]);
+        assert_eq!(second.missing_signers, vec![funder.pubkey().to_string()]);
+        assert!(decode_transaction(&first.transaction).unwrap_err().to_string().contains("missing signatures"));
+
+        let partial = vec![
+            decode_partial_transaction(&first.transaction).unwrap(),
+            decode_partial_transaction(&second.transaction).unwrap(),
+        ];
+        let combined = combine_signatures(partial).unwrap();
+        assert!(check_fully_signed(&combined).is_ok());
+        assert!(combined.verify().is_ok());
+        assert_eq!(combined.message.account_keys[0], fee_payer.pubkey());
+
+        // Different messages cannot be combined
+        let other = build_funding_transaction(&validator_id, &funder, 2_000, blockhash, None);
+        assert!(combine_signatures(vec![combined, other]).unwrap_err().is_user_error());
+    }
+}
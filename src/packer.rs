@@ -0,0 +1,166 @@
+use crate::Error;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+
+/// Largest serialized transaction that fits in one packet (`PACKET_DATA_SIZE`: the 1280-byte IPv6 MTU minus headers)
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Most accounts a transaction may reference (the account lock limit of the runtime)
+pub const MAX_TRANSACTION_ACCOUNTS: usize = 64;
+
+/// Limits every packed transaction must stay within
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackLimits {
+    /// Serialized size once signed, signatures included
+    pub max_size: usize,
+    /// Unique accounts, fee payer and programs included
+    pub max_accounts: usize,
+}
+
+impl Default for PackLimits {
+    /// Limits of the cluster: `MAX_TRANSACTION_SIZE` bytes and `MAX_TRANSACTION_ACCOUNTS` accounts
+    fn default() -> Self {
+        PackLimits { max_size: MAX_TRANSACTION_SIZE, max_accounts: MAX_TRANSACTION_ACCOUNTS }
+    }
+}
+
+impl PackLimits {
+    /// Returns true if a message stays within the limits once signed
+    pub fn fits(&self, message: &Message) -> bool {
+        message.account_keys.len() <= self.max_accounts && signed_size(message) <= self.max_size
+    }
+}
+
+/// Returns the serialized size of a message once signed
+///
+/// The transaction carries one 64-byte signature per required signer of the message, so signers
+/// added by the instructions are counted along with their account keys.
+pub fn signed_size(message: &Message) -> usize {
+    bincode::serialized_size(&Transaction::new_unsigned(message.clone())).map(|size| size as usize).unwrap_or(usize::MAX)
+}
+
+/// Packs instructions into as few transactions as the limits allow
+///
+/// Instructions keep their order and a transaction is closed as soon as the next instruction
+/// would break a limit. Accounts shared by the instructions (the funding wallet, the System
+/// Program) are stored once per transaction, so the size is measured on the compiled message
+/// rather than estimated per instruction.
+///
+/// # Arguments
+/// * `payer` - Fee payer of every transaction
+/// * `prefix` - Instructions repeated at the start of every transaction, e.g. compute budget instructions
+/// * `instructions` - Instructions to pack
+/// * `blockhash` - Recent blockhash
+/// * `limits` - Size and account limits (`PackLimits::default()` for the cluster limits)
+///
+/// # Returns
+/// * `Result<Vec<Message>, Error>` - Unsigned messages in instruction order, or `Error::InvalidInput`
+///   if an instruction does not fit in a transaction on its own
+pub fn pack_instructions(
+    payer: &Pubkey,
+    prefix: &[Instruction],
+    instructions: &[Instruction],
+    blockhash: &Hash,
+    limits: &PackLimits,
+) -> Result<Vec<Message>, Error> {
+    let message = |packed: &[Instruction]| {
+        let all: Vec<Instruction> = prefix.iter().chain(packed).cloned().collect();
+        Message::new_with_blockhash(&all, Some(payer), blockhash)
+    };
+
+    let mut messages = Vec::new();
+    let mut packed: Vec<Instruction> = Vec::new();
+    let mut current: Option<Message> = None;
+    for (index, instruction) in instructions.iter().enumerate() {
+        packed.push(instruction.clone());
+        let candidate = message(&packed);
+        if limits.fits(&candidate) {
+            current = Some(candidate);
+            continue;
+        }
+
+        // The next transaction starts with the instruction that did not fit
+        packed = vec![instruction.clone()];
+        let alone = message(&packed);
+        if !limits.fits(&alone) {
+            return Err(Error::InvalidInput(format!(
+                "Instruction {} does not fit in a transaction ({} bytes and {} accounts signed; limits are {} bytes and {} accounts)",
+                index + 1,
+                signed_size(&alone),
+                alone.account_keys.len(),
+                limits.max_size,
+                limits.max_accounts
+            )));
+        }
+        messages.extend(current.replace(alone));
+    }
+    messages.extend(current);
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+    use solana_system_interface::instruction::transfer;
+
+    #[test]
+    fn test_pack_transfers_fill_packets() {
+        let (payer, blockhash) = (Pubkey::new_unique(), Hash::new_unique());
+        let transfers: Vec<Instruction> = (0..100).map(|lamports| transfer(&payer, &Pubkey::new_unique(), lamports + 1)).collect();
+
+        let messages = pack_instructions(&payer, &[], &transfers, &blockhash, &PackLimits::default()).unwrap();
+        let mut offset = 0;
+        for message in &messages {
+            let count = message.instructions.len();
+            assert!(signed_size(message) <= MAX_TRANSACTION_SIZE);
+            assert_eq!(message.instructions[0].data, transfers[offset].data);
+            // Closed only when full: one more transfer would not fit in a packet
+            if offset + count < transfers.len() {
+                let next = Message::new_with_blockhash(&transfers[offset..offset + count + 1], Some(&payer), &blockhash);
+                assert!(signed_size(&next) > MAX_TRANSACTION_SIZE);
+            }
+            offset += count;
+        }
+        assert_eq!(offset, transfers.len());
+    }
+
+    #[test]
+    fn test_pack_respects_account_limit_and_signers() {
+        let (payer, blockhash) = (Pubkey::new_unique(), Hash::new_unique());
+        let transfers: Vec<Instruction> = (0..10).map(|_| transfer(&payer, &Pubkey::new_unique(), 1)).collect();
+
+        // Payer and System Program plus three recipients per transaction
+        let limits = PackLimits { max_accounts: 5, ..PackLimits::default() };
+        let messages = pack_instructions(&payer, &[], &transfers, &blockhash, &limits).unwrap();
+        assert_eq!(messages.iter().map(|message| message.instructions.len()).collect::<Vec<_>>(), vec![3, 3, 3, 1]);
+        assert!(messages.iter().all(|message| message.account_keys.len() <= 5));
+
+        // Every extra signer costs a 64-byte signature, so fewer transfers fit
+        let signed: Vec<Instruction> = (0..40).map(|_| transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1)).collect();
+        let messages = pack_instructions(&payer, &[], &signed, &blockhash, &PackLimits::default()).unwrap();
+        assert!(messages[0].instructions.len() < 10);
+        assert!(messages.iter().all(|message| signed_size(message) <= MAX_TRANSACTION_SIZE));
+
+        // The prefix is repeated in every transaction
+        let prefix = transfer(&payer, &payer, 0);
+        let messages = pack_instructions(&payer, std::slice::from_ref(&prefix), &transfers, &blockhash, &limits).unwrap();
+        assert!(messages.iter().all(|message| message.instructions.len() <= 4 && message.instructions[0].data == prefix.data));
+        assert!(pack_instructions(&payer, &[], &[], &blockhash, &limits).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pack_rejects_oversized_instruction() {
+        let payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let oversized = Instruction::new_with_bytes(program_id, &[0u8; MAX_TRANSACTION_SIZE], vec![AccountMeta::new(payer, true)]);
+        let instructions = vec![transfer(&payer, &Pubkey::new_unique(), 1), oversized];
+
+        let error = pack_instructions(&payer, &[], &instructions, &Hash::new_unique(), &PackLimits::default()).unwrap_err();
+        assert!(error.is_user_error());
+        assert!(error.to_string().contains("Instruction 2 does not fit"));
+    }
+}
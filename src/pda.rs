@@ -0,0 +1,186 @@
+use crate::Error;
+use serde::Serialize;
+use solana_sdk::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use std::fmt;
+
+pub use dz_validator_pda_core::{DEPOSIT_PDA_SEED_PREFIX, REVENUE_DISTRIBUTION_PROGRAM_ID};
+
+/// One seed of a program derived address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+pub enum Seed {
+    /// UTF-8 bytes of a string (`prefix:` or `str:`)
+    Str(String),
+    /// The 32 bytes of a pubkey (`pubkey:`)
+    Pubkey(Pubkey),
+    /// 8 little-endian bytes of a number (`u64:`)
+    U64(u64),
+    /// Raw bytes written as hex (`hex:`)
+    Hex(Vec<u8>),
+}
+
+impl Seed {
+    /// Returns the bytes the seed contributes to the derivation
+    pub fn bytes(&self) -> Vec<u8> {
+        match self {
+            Seed::Str(value) => value.as_bytes().to_vec(),
+            Seed::Pubkey(pubkey) => pubkey.to_bytes().to_vec(),
+            Seed::U64(value) => value.to_le_bytes().to_vec(),
+            Seed::Hex(bytes) => bytes.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Seed::Str(value) => write!(f, "str:{}", value),
+            Seed::Pubkey(pubkey) => write!(f, "pubkey:{}", pubkey),
+            Seed::U64(value) => write!(f, "u64:{}", value),
+            Seed::Hex(bytes) => write!(f, "hex:{}", bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()),
+        }
+    }
+}
+
+/// Parses a seed list (`--seed-spec "prefix:solana_validator_deposit,pubkey:<validator>"`)
+///
+/// Seeds are comma-separated `kind:value` pairs in derivation order: `prefix:` or `str:` for a string,
+/// `pubkey:` for a pubkey or validator alias, `u64:` for a little-endian number and `hex:` for raw bytes.
+///
+/// # Arguments
+/// * `value` - Seed list
+/// * `resolve_pubkey` - Resolves the value of a `pubkey:` seed, e.g. a validator alias
+///
+/// # Returns
+/// * `Result<Vec<Seed>, Error>` - Seeds, or `Error::InvalidInput` for an unknown kind, a seed over
+///   32 bytes or more than 16 seeds
+pub fn parse_seed_spec(value: &str, resolve_pubkey: impl Fn(&str) -> Result<Pubkey, Error>) -> Result<Vec<Seed>, Error> {
+    let seeds = value
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (kind, value) = part.split_once(':').ok_or_else(|| {
+                Error::InvalidInput(format!("Invalid seed '{}': expected kind:value (prefix, str, pubkey, u64 or hex)", part))
+            })?;
+            let seed = match kind.trim().to_ascii_lowercase().as_str() {
+                "prefix" | "str" => Seed::Str(value.to_string()),
+                "pubkey" => Seed::Pubkey(resolve_pubkey(value.trim())?),
+                "u64" => Seed::U64(value.trim().parse().map_err(|_| Error::InvalidInput(format!("Invalid u64 seed '{}'", value)))?),
+                "hex" => Seed::Hex(parse_hex(value.trim()).ok_or_else(|| Error::InvalidInput(format!("Invalid hex seed '{}'", value)))?),
+                _ => {
+                    return Err(Error::InvalidInput(format!(
+                        "Unknown seed kind '{}': expected prefix, str, pubkey, u64 or hex",
+                        kind
+                    )));
+                }
+            };
+            if seed.bytes().len() > MAX_SEED_LEN {
+                return Err(Error::InvalidInput(format!("Seed '{}' is longer than {} bytes", part, MAX_SEED_LEN)));
+            }
+            Ok(seed)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    if seeds.is_empty() {
+        return Err(Error::InvalidInput("No seeds given".to_string()));
+    }
+    // One seed slot is taken by the bump
+    if seeds.len() >= MAX_SEEDS {
+        return Err(Error::InvalidInput(format!("At most {} seeds are allowed, got {}", MAX_SEEDS - 1, seeds.len())));
+    }
+    Ok(seeds)
+}
+
+fn parse_hex(value: &str) -> Option<Vec<u8>> {
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len()).step_by(2).map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok()).collect()
+}
+
+/// Derives a program address from a seed list
+///
+/// # Arguments
+/// * `seeds` - Seeds in derivation order
+/// * `program_id` - Program owning the address
+///
+/// # Returns
+/// * `Result<(Pubkey, u8), Error>` - The PDA and its bump seed, or `Error::InvalidInput` if no bump gives an off-curve address
+pub fn derive(seeds: &[Seed], program_id: &Pubkey) -> Result<(Pubkey, u8), Error> {
+    let bytes: Vec<Vec<u8>> = seeds.iter().map(Seed::bytes).collect();
+    let slices: Vec<&[u8]> = bytes.iter().map(Vec::as_slice).collect();
+    Pubkey::try_find_program_address(&slices, program_id)
+        .ok_or_else(|| Error::InvalidInput("No valid bump seed for these seeds".to_string()))
+}
+
+/// Deposit PDA of a validator: `[seed prefix, validator]` under the revenue distribution program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepositPda {
+    /// Validator identity
+    pub validator: Pubkey,
+    /// Derived address
+    pub address: Pubkey,
+    /// Bump seed, which instructions of the program take to re-derive the address cheaply
+    pub bump: u8,
+}
+
+impl DepositPda {
+    /// Derives the deposit PDA of the mainnet program
+    pub fn new(validator: &Pubkey) -> Self {
+        let (address, bump) = dz_validator_pda_core::deposit_pda(validator);
+        DepositPda { validator: *validator, address, bump }
+    }
+
+    /// Derives the deposit PDA of a fork or staging deployment
+    ///
+    /// # Arguments
+    /// * `validator` - Validator identity
+    /// * `seed_prefix` - First seed (`DEPOSIT_PDA_SEED_PREFIX` for the mainnet program)
+    /// * `program_id` - Program owning the PDA
+    ///
+    /// # Returns
+    /// * `Result<DepositPda, Error>` - The PDA, or `Error::InvalidInput` for a prefix longer than 32 bytes
+    pub fn with_program(validator: &Pubkey, seed_prefix: &str, program_id: &Pubkey) -> Result<Self, Error> {
+        let (address, bump) =
+            dz_validator_pda_core::deposit_pda_with_program(validator, seed_prefix, program_id).map_err(Error::InvalidInput)?;
+        Ok(DepositPda { validator: *validator, address, bump })
+    }
+
+    /// Returns the seeds of the mainnet deposit PDA, as `derive` takes them
+    pub fn seeds(validator: &Pubkey) -> Vec<Seed> {
+        vec![Seed::Str(DEPOSIT_PDA_SEED_PREFIX.to_string()), Seed::Pubkey(*validator)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_seed_spec() {
+        let validator = Pubkey::new_unique();
+        let resolve = |value: &str| match value {
+            "main" => Ok(validator),
+            value => value.parse::<Pubkey>().map_err(|e| Error::InvalidInput(e.to_string())),
+        };
+        let seeds = parse_seed_spec(&format!("prefix:escrow, pubkey:{}, u64:7, hex:0x00ff", validator), resolve).unwrap();
+        assert_eq!(seeds, vec![Seed::Str("escrow".to_string()), Seed::Pubkey(validator), Seed::U64(7), Seed::Hex(vec![0, 255])]);
+        assert_eq!(seeds[2].bytes(), vec![7, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(parse_seed_spec("str:a,pubkey:main", resolve).unwrap()[1], Seed::Pubkey(validator));
+
+        for invalid in ["", "escrow", "blob:1", "u64:x", "hex:abc", &format!("str:{}", "x".repeat(33))] {
+            assert!(parse_seed_spec(invalid, resolve).unwrap_err().is_user_error(), "{}", invalid);
+        }
+        assert!(parse_seed_spec(&vec!["u64:1"; 16].join(","), resolve).is_err());
+    }
+
+    #[test]
+    fn test_deposit_pda_matches_seed_derivation() {
+        let validator = Pubkey::new_unique();
+        let deposit = DepositPda::new(&validator);
+        assert_eq!(derive(&DepositPda::seeds(&validator), &REVENUE_DISTRIBUTION_PROGRAM_ID).unwrap(), (deposit.address, deposit.bump));
+        assert_eq!(DepositPda::with_program(&validator, DEPOSIT_PDA_SEED_PREFIX, &REVENUE_DISTRIBUTION_PROGRAM_ID).unwrap(), deposit);
+    }
+}
@@ -0,0 +1,420 @@
+use crate::audit::{self, AuditEntry};
+use crate::memo::MEMO_PROGRAM_ID;
+use crate::report::{Warning, WARN_PENDING_FUNDING};
+use crate::rpc::{FetchedTransaction, SolanaRpc};
+use crate::verify::{balance_increase, transfers_to};
+use crate::{generate_deposit_pda, Error};
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::TransactionConfirmationStatus;
+use std::collections::hash_map::{Entry, HashMap};
+use std::path::Path;
+
+/// Recent transactions of the funding wallet looked at for transfers still in flight
+pub const PENDING_SIGNATURE_LIMIT: usize = 25;
+
+/// Recent transactions of a deposit PDA a funding attempt of unknown outcome is looked up in
+pub const ATTEMPT_SIGNATURE_LIMIT: usize = 100;
+
+/// Transactions of a deposit PDA read per page when its history is searched for an earlier funding
+pub const DUPLICATE_SIGNATURE_LIMIT: usize = 100;
+
+/// Seconds after which a funding attempt that is not on chain can no longer land (its blockhash is valid for 150 blocks)
+pub const ATTEMPT_EXPIRY_SECS: u64 = 120;
+
+/// Transfer from the funding wallet to a PDA that landed but is not finalized yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingFunding {
+    /// Signature of the transfer
+    pub signature: Signature,
+    /// Deposit PDA it credits
+    pub pda: Pubkey,
+    /// Lamports it added to the PDA
+    pub amount_lamports: u64,
+    /// Slot it landed in
+    pub slot: u64,
+}
+
+/// Earlier funding of a deposit PDA found in its on-chain history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnChainFunding {
+    /// Signature of the transaction
+    pub signature: Signature,
+    /// Slot it landed in
+    pub slot: u64,
+    /// Estimated production time of its block as a Unix timestamp, if known
+    pub block_time: Option<i64>,
+}
+
+/// Finds transfers from a funding wallet to the given PDAs that are not finalized yet
+///
+/// Looks at the most recent transactions of the wallet (`getSignaturesForAddress`) and keeps the
+/// successful ones below the finalized commitment that credited one of the PDAs. Such a transfer
+/// can still be rolled back or is about to finalize, so sending another one now would likely
+/// fund the PDA twice.
+///
+/// # Arguments
+/// * `payer` - Funding wallet
+/// * `pdas` - Deposit PDAs about to be funded
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Vec<PendingFunding>, Error>` - Pending transfers, newest first, or error if the history cannot be read
+pub async fn find_pending_fundings(payer: &Pubkey, pdas: &[Pubkey], rpc: &dyn SolanaRpc) -> Result<Vec<PendingFunding>, Error> {
+    let recent = rpc
+        .get_signatures_for_address(payer, None, PENDING_SIGNATURE_LIMIT)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get recent transactions of the funding wallet", &e))?;
+
+    let mut pending = Vec::new();
+    for status in recent {
+        let finalized = matches!(status.confirmation_status, Some(TransactionConfirmationStatus::Finalized));
+        if finalized || status.err.is_some() {
+            continue;
+        }
+        let Ok(signature) = status.signature.parse::<Signature>() else {
+            continue;
+        };
+        let fetched = rpc
+            .get_transaction(&signature, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| Error::from_client_error("Failed to get transaction", &e))?;
+        // A transaction still at processed is not served yet; it shows up on the next run
+        let Some(fetched) = fetched else { continue };
+        for pda in pdas {
+            let amount_lamports = balance_increase(&fetched, pda);
+            if amount_lamports > 0 {
+                pending.push(PendingFunding { signature, pda: *pda, amount_lamports, slot: fetched.slot });
+            }
+        }
+    }
+    Ok(pending)
+}
+
+/// Warns about transfers to the PDAs that are still in flight (see `find_pending_fundings`)
+///
+/// The check is best effort: a node that does not serve the wallet history only logs a warning,
+/// since most providers limit `getSignaturesForAddress` and the audit log duplicate check still applies.
+///
+/// # Arguments
+/// * `payer` - Funding wallet
+/// * `pdas` - Deposit PDAs about to be funded
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Vec<Warning>` - One `pending_funding` warning per pending transfer
+pub async fn pending_funding_warnings(payer: &Pubkey, pdas: &[Pubkey], rpc: &dyn SolanaRpc) -> Vec<Warning> {
+    match find_pending_fundings(payer, pdas, rpc).await {
+        Ok(pending) => pending
+            .iter()
+            .map(|funding| {
+                Warning::new(
+                    WARN_PENDING_FUNDING,
+                    format!(
+                        "Transaction {} from {} already sent {} lamports to PDA {} and is not finalized yet (slot {})",
+                        funding.signature, payer, funding.amount_lamports, funding.pda, funding.slot
+                    ),
+                )
+            })
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Pending funding check skipped: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Finds an earlier funding of a deposit PDA in its on-chain history, for the duplicate check of `pda-fund-address`
+///
+/// Complements `audit::find_duplicate_funding`, which only sees fundings sent from this machine. The transactions
+/// of the PDA since `since` (`getSignaturesForAddress`, page by page) are fetched until one that succeeded is a
+/// repeat: with an idempotency key, one whose memo carries `idempotency-key:<key>`; without one, a system
+/// transfer of `amount_lamports` from `payer` to the PDA. Transactions without a block time count as recent.
+///
+/// # Arguments
+/// * `pda` - Deposit PDA about to be funded
+/// * `payer` - Funding wallet
+/// * `amount_lamports` - Amount to transfer
+/// * `idempotency_key` - Key of the new funding, if any
+/// * `since` - Unix timestamp of the start of the duplicate window
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Option<OnChainFunding>, Error>` - Most recent repeat, None, or error if the history cannot be read
+pub async fn find_onchain_funding(
+    pda: &Pubkey,
+    payer: &Pubkey,
+    amount_lamports: u64,
+    idempotency_key: Option<&str>,
+    since: u64,
+    rpc: &dyn SolanaRpc,
+) -> Result<Option<OnChainFunding>, Error> {
+    let tag = idempotency_key.map(|key| format!("idempotency-key:{}", key));
+    let mut before = None;
+    loop {
+        let page = rpc
+            .get_signatures_for_address(pda, before, DUPLICATE_SIGNATURE_LIMIT)
+            .await
+            .map_err(|e| Error::from_client_error("Failed to get transactions of the deposit PDA", &e))?;
+        for status in &page {
+            if status.block_time.is_some_and(|block_time| block_time < since as i64) {
+                return Ok(None);
+            }
+            let signature = status
+                .signature
+                .parse::<Signature>()
+                .map_err(|e| Error::Rpc(format!("Invalid signature {} returned by the node: {}", status.signature, e)))?;
+            before = Some(signature);
+            // A failed transaction never moved any lamports
+            if status.err.is_some() {
+                continue;
+            }
+            let fetched = rpc
+                .get_transaction(&signature, CommitmentConfig::confirmed())
+                .await
+                .map_err(|e| Error::from_client_error("Failed to get transaction", &e))?;
+            let Some(fetched) = fetched else { continue };
+            let repeat = match &tag {
+                Some(tag) => memos(&fetched).iter().any(|memo| memo.split_whitespace().any(|word| word == tag)),
+                None => transfers_to(&fetched, pda).contains(&(*payer, amount_lamports)),
+            };
+            if repeat {
+                return Ok(Some(OnChainFunding { signature, slot: fetched.slot, block_time: fetched.block_time }));
+            }
+        }
+        if page.len() < DUPLICATE_SIGNATURE_LIMIT {
+            return Ok(None);
+        }
+    }
+}
+
+/// Returns the texts of the SPL Memo instructions of a transaction
+fn memos(fetched: &FetchedTransaction) -> Vec<String> {
+    let keys = fetched.account_keys();
+    fetched
+        .transaction
+        .message
+        .instructions()
+        .iter()
+        .filter(|instruction| keys.get(instruction.program_id_index as usize) == Some(&MEMO_PROGRAM_ID))
+        .filter_map(|instruction| String::from_utf8(instruction.data.clone()).ok())
+        .collect()
+}
+
+/// Settles the funding attempts of the audit log whose outcome is not known (see `audit::unresolved_fundings`)
+///
+/// Each attempt is looked up in the recent transactions of its deposit PDA (`getSignaturesForAddress`). One that
+/// landed is recorded as a success; one that failed, or that is not on chain `ATTEMPT_EXPIRY_SECS` after it was
+/// signed, is recorded as failed. An attempt that cannot be looked up stays unresolved, so it keeps counting as a
+/// possible duplicate and against the budget.
+///
+/// # Arguments
+/// * `ledger` - Audit log
+/// * `now` - Current Unix timestamp
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Vec<AuditEntry>, Error>` - Audit log entries including the settling ones, or `Error::Io` if the log cannot be read or written
+pub async fn settle_funding_attempts(ledger: &Path, now: u64, rpc: &dyn SolanaRpc) -> Result<Vec<AuditEntry>, Error> {
+    let mut entries = audit::read_entries(ledger)?;
+    let mut history: HashMap<Pubkey, Vec<RpcConfirmedTransactionStatusWithSignature>> = HashMap::new();
+    let mut settled = Vec::new();
+    for attempt in audit::unresolved_fundings(&entries) {
+        let (Some(validator_id), Some(signature)) = (attempt.validator.as_deref().and_then(|validator| validator.parse::<Pubkey>().ok()), &attempt.signature) else {
+            continue;
+        };
+        let pda = generate_deposit_pda(&validator_id);
+        let recent = match history.entry(pda) {
+            Entry::Occupied(recent) => recent.into_mut(),
+            Entry::Vacant(vacant) => match rpc.get_signatures_for_address(&pda, None, ATTEMPT_SIGNATURE_LIMIT).await {
+                Ok(recent) => vacant.insert(recent),
+                Err(e) => {
+                    tracing::warn!("Funding attempt {} not checked on chain: {}", signature, e);
+                    continue;
+                }
+            },
+        };
+        let entry = match recent.iter().find(|status| &status.signature == signature) {
+            Some(status) if status.err.is_none() => {
+                audit::settled_entry(attempt, true, &format!("PDA {}: transaction found on chain in slot {}", pda, status.slot))
+            }
+            Some(status) => audit::settled_entry(attempt, false, &format!("PDA {}: transaction failed on chain in slot {}", pda, status.slot)),
+            // A full page may not reach back to the attempt
+            None if recent.len() < ATTEMPT_SIGNATURE_LIMIT && now.saturating_sub(attempt.timestamp) > ATTEMPT_EXPIRY_SECS => {
+                audit::settled_entry(attempt, false, &format!("PDA {}: transaction not found on chain and its blockhash has expired", pda))
+            }
+            None => continue,
+        };
+        settled.push(entry);
+    }
+    
+    for entry in &settled {
+        audit::append_entry(ledger, entry)?;
+    }
+    entries.extend(settled);
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_deposit_pda;
+    use crate::instruction::build_fund_instruction;
+    use crate::memo::memo_instruction;
+    use crate::rpc::mock::MockRpc;
+    use crate::rpc::FetchedTransaction;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::{Transaction, TransactionError};
+
+    fn funding(payer: &Keypair, validator_id: &Pubkey, lamports: u64, slot: u64) -> FetchedTransaction {
+        let instruction = build_fund_instruction(&payer.pubkey(), validator_id, lamports);
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[payer], Hash::new_unique());
+        FetchedTransaction {
+            slot,
+            block_time: None,
+            transaction: transaction.into(),
+            error: None,
+            loaded_addresses: Vec::new(),
+            pre_balances: vec![10_000_000_000, 0, 1],
+            post_balances: vec![10_000_000_000 - lamports - 5_000, lamports, 1],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_pending_fundings() {
+        let (payer, validator_id, other) = (Keypair::new(), Pubkey::new_unique(), Pubkey::new_unique());
+        let in_flight = funding(&payer, &validator_id, 2_000_000_000, 990);
+        let signature = in_flight.transaction.signatures[0];
+        let mut failed = funding(&payer, &validator_id, 1_000_000_000, 991);
+        failed.error = Some(TransactionError::InsufficientFundsForFee);
+        let rpc = MockRpc::new()
+            .with_landed_transaction(in_flight, false)
+            .with_landed_transaction(failed, false)
+            .with_landed_transaction(funding(&payer, &validator_id, 3_000_000_000, 900), true)
+            .with_landed_transaction(funding(&payer, &other, 4_000_000_000, 992), false);
+
+        let pda = generate_deposit_pda(&validator_id);
+        let pending = find_pending_fundings(&payer.pubkey(), &[pda], &rpc).await.unwrap();
+        assert_eq!(pending, vec![PendingFunding { signature, pda, amount_lamports: 2_000_000_000, slot: 990 }]);
+
+        // Finalized, failed and unrelated transfers are no reason to wait
+        assert!(find_pending_fundings(&payer.pubkey(), &[generate_deposit_pda(&Pubkey::new_unique())], &rpc).await.unwrap().is_empty());
+        let warnings = pending_funding_warnings(&payer.pubkey(), &[pda, generate_deposit_pda(&other)], &rpc).await;
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|warning| warning.code == WARN_PENDING_FUNDING));
+
+        // A node without the wallet history skips the check
+        rpc.set_unavailable(true);
+        assert!(pending_funding_warnings(&payer.pubkey(), &[pda], &rpc).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_settle_funding_attempts() {
+        let (payer, validator_id) = (Keypair::new(), Pubkey::new_unique());
+        let landed = funding(&payer, &validator_id, 2_000_000_000, 990);
+        let mut failed = funding(&payer, &validator_id, 1_000_000_000, 991);
+        failed.error = Some(TransactionError::InsufficientFundsForFee);
+        let attempt = |timestamp: u64, signature: String| AuditEntry {
+            timestamp,
+            validator: Some(validator_id.to_string()),
+            payer: Some(payer.pubkey().to_string()),
+            amount_lamports: Some(1_000_000_000),
+            signature: Some(signature),
+            ..AuditEntry::new(audit::EVENT_PDA_FUND, audit::STATUS_PENDING)
+        };
+        let now = 1_700_000_000;
+        let ledger = std::env::temp_dir().join(format!("dz_validator_pda_settle_{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&ledger);
+        for entry in [
+            attempt(now - 60, landed.transaction.signatures[0].to_string()),
+            attempt(now - 60, failed.transaction.signatures[0].to_string()),
+            attempt(now - 600, "Expired".to_string()),
+            attempt(now - 30, "InFlight".to_string()),
+        ] {
+            audit::append_entry(&ledger, &entry).unwrap();
+        }
+        let rpc = MockRpc::new().with_landed_transaction(landed, false).with_landed_transaction(failed, true);
+
+        // A node that cannot be asked leaves every attempt unresolved
+        rpc.set_unavailable(true);
+        assert_eq!(settle_funding_attempts(&ledger, now, &rpc).await.unwrap().len(), 4);
+        rpc.set_unavailable(false);
+
+        let entries = settle_funding_attempts(&ledger, now, &rpc).await.unwrap();
+        let settled: Vec<&str> = entries[4..].iter().map(|entry| entry.status.as_str()).collect();
+        assert_eq!(settled, vec![audit::STATUS_SUCCESS, audit::STATUS_FAILED, audit::STATUS_FAILED]);
+        assert_eq!(entries, audit::read_entries(&ledger).unwrap());
+        // The transaction sent 30 seconds ago may still land
+        let unresolved = audit::unresolved_fundings(&entries);
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].signature.as_deref(), Some("InFlight"));
+        std::fs::remove_file(&ledger).ok();
+    }
+
+    /// Settles an audit log that does not exist, as on a machine that never funded the validator
+    async fn empty_audit_log(validator_id: &Pubkey, payer: &Pubkey, amount_lamports: u64, key: Option<&str>, rpc: &dyn SolanaRpc) {
+        let ledger = std::env::temp_dir().join(format!("dz_validator_pda_no_audit_{}.log", std::process::id()));
+        let entries = settle_funding_attempts(&ledger, 1_700_000_000, rpc).await.unwrap();
+        assert!(audit::find_duplicate_funding(&entries, &validator_id.to_string(), &payer.to_string(), amount_lamports, key, 0).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_find_onchain_funding_same_transfer() {
+        let (payer, validator_id) = (Keypair::new(), Pubkey::new_unique());
+        let now: u64 = 1_700_000_000;
+        let mut earlier = funding(&payer, &validator_id, 2_000_000_000, 990);
+        earlier.block_time = Some(now as i64 - 600);
+        let signature = earlier.transaction.signatures[0];
+        let mut old = funding(&payer, &validator_id, 1_000_000_000, 500);
+        old.block_time = Some(now as i64 - 7_200);
+        let rpc = MockRpc::new().with_landed_transaction(earlier, true).with_landed_transaction(old, true);
+        let pda = generate_deposit_pda(&validator_id);
+        empty_audit_log(&validator_id, &payer.pubkey(), 2_000_000_000, None, &rpc).await;
+
+        let found = find_onchain_funding(&pda, &payer.pubkey(), 2_000_000_000, None, now - 3_600, &rpc).await.unwrap();
+        assert_eq!(found, Some(OnChainFunding { signature, slot: 990, block_time: Some(now as i64 - 600) }));
+        // Another amount, another payer or a transfer before the window is no repeat
+        assert_eq!(find_onchain_funding(&pda, &payer.pubkey(), 3_000_000_000, None, now - 3_600, &rpc).await.unwrap(), None);
+        assert_eq!(find_onchain_funding(&pda, &Pubkey::new_unique(), 2_000_000_000, None, now - 3_600, &rpc).await.unwrap(), None);
+        assert_eq!(find_onchain_funding(&pda, &payer.pubkey(), 1_000_000_000, None, now - 3_600, &rpc).await.unwrap(), None);
+        assert!(find_onchain_funding(&pda, &payer.pubkey(), 1_000_000_000, None, now - 86_400, &rpc).await.unwrap().is_some());
+
+        rpc.set_unavailable(true);
+        assert!(find_onchain_funding(&pda, &payer.pubkey(), 2_000_000_000, None, now - 3_600, &rpc).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_find_onchain_funding_idempotency_key() {
+        let (payer, validator_id) = (Keypair::new(), Pubkey::new_unique());
+        let pda = generate_deposit_pda(&validator_id);
+        let instructions = [
+            build_fund_instruction(&payer.pubkey(), &validator_id, 1_500_000_000),
+            memo_instruction("January idempotency-key:job-42", &payer.pubkey()),
+        ];
+        let transaction = Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[&payer], Hash::new_unique());
+        let signature = transaction.signatures[0];
+        let keyed = FetchedTransaction { transaction: transaction.into(), ..funding(&payer, &validator_id, 1_500_000_000, 990) };
+        let instructions = [
+            build_fund_instruction(&payer.pubkey(), &validator_id, 1_500_000_000),
+            memo_instruction("idempotency-key:job-43", &payer.pubkey()),
+        ];
+        let transaction = Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[&payer], Hash::new_unique());
+        let failed = FetchedTransaction {
+            transaction: transaction.into(),
+            error: Some(TransactionError::InsufficientFundsForFee),
+            ..funding(&payer, &validator_id, 1_500_000_000, 991)
+        };
+        let rpc = MockRpc::new().with_landed_transaction(keyed, false).with_landed_transaction(failed, true);
+        empty_audit_log(&validator_id, &payer.pubkey(), 2_000_000_000, Some("job-42"), &rpc).await;
+
+        // The key matches whatever the amount, and even from another wallet
+        let found = find_onchain_funding(&pda, &Pubkey::new_unique(), 2_000_000_000, Some("job-42"), 0, &rpc).await.unwrap();
+        assert_eq!(found.map(|funding| funding.signature), Some(signature));
+        // Only the whole key matches, and a failed transaction does not count
+        assert_eq!(find_onchain_funding(&pda, &payer.pubkey(), 1_500_000_000, Some("job-4"), 0, &rpc).await.unwrap(), None);
+        assert_eq!(find_onchain_funding(&pda, &payer.pubkey(), 1_500_000_000, Some("job-43"), 0, &rpc).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::fund_many::pack_transfers;
+use crate::rpc::SolanaRpc;
+use crate::{generate_deposit_pda, Error, FundingAccounts};
+use serde::{Deserialize, Serialize};
+use solana_sdk::hash::hash;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+
+/// Version of the YAML batch plan format
+pub const BATCH_PLAN_VERSION: u32 = 1;
+
+/// One transfer of a batch plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedTransfer {
+    /// Validator identity pubkey
+    pub validator: String,
+    /// Deposit PDA of the validator
+    pub pda: String,
+    /// Exact amount to transfer in lamports
+    pub amount_lamports: u64,
+}
+
+/// Funding of several validators written for review before anyone runs it (`plan create --pairs`)
+///
+/// The plan is approved by the SHA-256 of its file (see `plan_hash`), so the reviewer signs off on
+/// exactly the text they read and `plan execute` refuses a file changed afterwards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchPlan {
+    /// Plan file format version
+    pub version: u32,
+    /// Unix timestamp of plan creation
+    pub created_at: u64,
+    /// Funding wallet pubkey; `plan execute` must sign as this wallet
+    pub payer: String,
+    /// RPC endpoint the fees were estimated on and the plan is executed against
+    pub rpc: String,
+    /// Transfers in the order they are packed
+    pub transfers: Vec<PlannedTransfer>,
+    /// Sum of the transferred amounts in lamports
+    pub total_lamports: u64,
+    /// Number of transactions the transfers are packed into
+    pub transaction_count: usize,
+    /// Transaction fees estimated at creation, in lamports
+    pub estimated_fee_lamports: u64,
+}
+
+/// Returns the hash that approves a plan file: the hex SHA-256 of its bytes, as `sha256sum` prints it
+///
+/// # Arguments
+/// * `contents` - Plan file contents
+pub fn plan_hash(contents: &[u8]) -> String {
+    hash(contents).to_bytes().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Checks the `--approve` hash against the plan file
+///
+/// # Arguments
+/// * `contents` - Plan file contents
+/// * `approved` - Hash given with `--approve`
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if they match, or `Error::InvalidInput`
+pub fn check_plan_hash(contents: &[u8], approved: &str) -> Result<(), Error> {
+    if !plan_hash(contents).eq_ignore_ascii_case(approved.trim()) {
+        return Err(Error::InvalidInput(
+            "The plan does not match the approved hash: it was changed after approval or another plan was approved".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl BatchPlan {
+    /// Estimates the fees of a batch funding and writes it down as a plan, without checking or sending anything
+    ///
+    /// # Arguments
+    /// * `payer` - Funding wallet pubkey
+    /// * `transfers` - Validators and amounts in lamports; a validator may appear only once
+    /// * `priority_fee` - Priority fee in micro-lamports per compute unit included in the estimate (optional)
+    /// * `created_at` - Current Unix timestamp
+    /// * `rpc` - RPC client
+    ///
+    /// # Returns
+    /// * `Result<BatchPlan, Error>` - Plan, or error if the transfers are invalid or the fees cannot be estimated
+    pub async fn create(
+        payer: &Pubkey,
+        transfers: &[(Pubkey, u64)],
+        priority_fee: Option<u64>,
+        created_at: u64,
+        rpc: &dyn SolanaRpc,
+    ) -> Result<Self, Error> {
+        let blockhash = rpc.get_latest_blockhash().await
+            .map_err(|e| Error::from_client_error("Failed to get recent blockhash", &e))?;
+        let accounts = FundingAccounts { funder: *payer, fee_payer: *payer, nonce: None };
+        let messages = pack_transfers(&accounts, transfers, priority_fee, &blockhash)?;
+        let mut estimated_fee_lamports = 0u64;
+        for message in &messages {
+            estimated_fee_lamports += rpc.get_fee_for_message(message).await
+                .map_err(|e| Error::from_client_error("Failed to estimate transaction fee", &e))?;
+        }
+
+        let plan = BatchPlan {
+            version: BATCH_PLAN_VERSION,
+            created_at,
+            payer: payer.to_string(),
+            rpc: rpc.url(),
+            transfers: transfers
+                .iter()
+                .map(|(validator_id, amount_lamports)| PlannedTransfer {
+                    validator: validator_id.to_string(),
+                    pda: generate_deposit_pda(validator_id).to_string(),
+                    amount_lamports: *amount_lamports,
+                })
+                .collect(),
+            total_lamports: transfers.iter().map(|(_, lamports)| lamports).sum(),
+            transaction_count: messages.len(),
+            estimated_fee_lamports,
+        };
+        plan.accounts()?;
+        Ok(plan)
+    }
+
+    /// Parses a plan file
+    ///
+    /// # Arguments
+    /// * `yaml` - Plan file contents
+    ///
+    /// # Returns
+    /// * `Result<BatchPlan, Error>` - Plan or `Error::InvalidInput`
+    pub fn parse(yaml: &str) -> Result<Self, Error> {
+        serde_yaml::from_str(yaml).map_err(|e| Error::InvalidInput(format!("Invalid funding plan: {}", e)))
+    }
+
+    /// Writes the plan as YAML
+    pub fn to_yaml(&self) -> Result<String, Error> {
+        serde_yaml::to_string(self).map_err(|e| Error::Io(format!("Failed to serialize funding plan: {}", e)))
+    }
+
+    /// Parses the funding wallet and transfers and checks that the plan is consistent
+    ///
+    /// Every PDA must belong to its validator, no validator may appear twice and the total must be
+    /// the sum of the transfers, so the reviewed figures are the ones sent.
+    ///
+    /// # Returns
+    /// * `Result<(Pubkey, Vec<(Pubkey, u64)>), Error>` - Funding wallet and transfers, or `Error::InvalidInput`
+    pub fn accounts(&self) -> Result<(Pubkey, Vec<(Pubkey, u64)>), Error> {
+        let invalid = |field: &str, value: &str| Error::InvalidInput(format!("Invalid {} '{}' in funding plan", field, value));
+        if self.version != BATCH_PLAN_VERSION {
+            return Err(Error::InvalidInput(format!("Unsupported funding plan version {}", self.version)));
+        }
+        let payer = self.payer.parse::<Pubkey>().map_err(|_| invalid("payer", &self.payer))?;
+        if self.transfers.is_empty() {
+            return Err(Error::InvalidInput("The funding plan has no transfers".to_string()));
+        }
+
+        let mut seen = HashSet::new();
+        let mut transfers = Vec::new();
+        for transfer in &self.transfers {
+            let validator_id = transfer.validator.parse::<Pubkey>().map_err(|_| invalid("validator", &transfer.validator))?;
+            if generate_deposit_pda(&validator_id).to_string() != transfer.pda {
+                return Err(invalid("pda", &transfer.pda));
+            }
+            if transfer.amount_lamports == 0 {
+                return Err(Error::InvalidInput(format!("Transfer to validator {} in funding plan has no amount", validator_id)));
+            }
+            if !seen.insert(validator_id) {
+                return Err(Error::InvalidInput(format!("Validator {} is listed more than once in funding plan", validator_id)));
+            }
+            transfers.push((validator_id, transfer.amount_lamports));
+        }
+        let total_lamports: u64 = transfers.iter().map(|(_, lamports)| lamports).sum();
+        if total_lamports != self.total_lamports {
+            return Err(Error::InvalidInput(format!(
+                "The funding plan total of {} lamports is not the sum of its transfers ({} lamports)",
+                self.total_lamports, total_lamports
+            )));
+        }
+        Ok((payer, transfers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::{MockRpc, MOCK_FEE_PER_SIGNATURE};
+
+    #[tokio::test]
+    async fn test_create_and_check_plan() {
+        let (payer, first, second) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let plan = BatchPlan::create(&payer, &[(first, 2_000_000_000), (second, 500_000_000)], None, 1_700_000_000, &MockRpc::new())
+            .await
+            .unwrap();
+        assert_eq!((plan.total_lamports, plan.transaction_count), (2_500_000_000, 1));
+        assert_eq!(plan.estimated_fee_lamports, MOCK_FEE_PER_SIGNATURE);
+        assert_eq!(plan.transfers[1].pda, generate_deposit_pda(&second).to_string());
+
+        let yaml = plan.to_yaml().unwrap();
+        let parsed = BatchPlan::parse(&yaml).unwrap();
+        assert_eq!(parsed, plan);
+        assert_eq!(parsed.accounts().unwrap(), (payer, vec![(first, 2_000_000_000), (second, 500_000_000)]));
+
+        // An edited amount no longer adds up to the reviewed total
+        let mut edited = plan.clone();
+        edited.transfers[0].amount_lamports = 20_000_000_000;
+        assert!(edited.accounts().unwrap_err().to_string().contains("not the sum"));
+        let mut edited = plan.clone();
+        edited.transfers[1].pda = generate_deposit_pda(&first).to_string();
+        assert!(edited.accounts().unwrap_err().is_user_error());
+        let mut edited = plan;
+        edited.transfers[1].validator = first.to_string();
+        edited.transfers[1].pda = generate_deposit_pda(&first).to_string();
+        assert!(edited.accounts().unwrap_err().to_string().contains("more than once"));
+        assert!(BatchPlan::parse("payer: [").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_plan_hash() {
+        // Same digest as `sha256sum`
+        assert_eq!(plan_hash(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert!(check_plan_hash(b"abc", " BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD\n").is_ok());
+        assert!(check_plan_hash(b"abd", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad").unwrap_err().is_user_error());
+    }
+}
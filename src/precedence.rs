@@ -0,0 +1,189 @@
+use crate::args::ParsedArgs;
+use crate::config::Config;
+use crate::rpc::{parse_commitment, DEFAULT_RPC_URL};
+use crate::Error;
+use solana_commitment_config::CommitmentConfig;
+use std::fmt;
+
+/// Environment variable with the RPC endpoint, below `--url` and above the config file
+pub const RPC_URL_ENV_VAR: &str = "DZ_RPC_URL";
+
+/// Environment variable with the commitment level, below `--commitment` and above the config file
+pub const COMMITMENT_ENV_VAR: &str = "DZ_COMMITMENT";
+
+/// Where a setting was taken from, highest precedence first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingSource {
+    /// Command line flag
+    Flag,
+    /// Environment variable
+    Env,
+    /// Config file
+    Config,
+    /// Built-in default
+    Default,
+}
+
+impl fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SettingSource::Flag => "flag",
+            SettingSource::Env => "environment",
+            SettingSource::Config => "config file",
+            SettingSource::Default => "default",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Setting value together with its source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layered<T> {
+    /// Effective value
+    pub value: T,
+    /// Layer it came from
+    pub source: SettingSource,
+}
+
+/// Picks a setting by precedence: flag, then environment variable, then config file
+///
+/// Empty values are treated as unset, so `DZ_RPC_URL=` does not hide the config file.
+///
+/// # Arguments
+/// * `flag` - Value of the command line flag
+/// * `env` - Value of the environment variable
+/// * `config` - Value from the config file
+///
+/// # Returns
+/// * `Option<(&str, SettingSource)>` - First value set and its layer, or None to use the default
+pub fn first_set<'a>(flag: Option<&'a str>, env: Option<&'a str>, config: Option<&'a str>) -> Option<(&'a str, SettingSource)> {
+    [(flag, SettingSource::Flag), (env, SettingSource::Env), (config, SettingSource::Config)]
+        .into_iter()
+        .find_map(|(value, source)| value.filter(|value| !value.trim().is_empty()).map(|value| (value, source)))
+}
+
+/// Connection settings after applying flags > environment > config file > defaults
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionSettings {
+    /// RPC endpoint (`--url`, `DZ_RPC_URL`, `rpc_url`)
+    pub rpc_url: Layered<String>,
+    /// Commitment level (`--commitment`, `DZ_COMMITMENT`, `commitment`), None for the default
+    pub commitment: Option<Layered<CommitmentConfig>>,
+}
+
+impl ConnectionSettings {
+    /// Resolves the connection settings from every layer
+    ///
+    /// # Arguments
+    /// * `parsed` - Command line arguments
+    /// * `env` - Environment lookup (`std::env::var` outside tests)
+    /// * `config` - Loaded config file
+    ///
+    /// # Returns
+    /// * `Result<ConnectionSettings, Error>` - Settings, or `Error::InvalidInput` naming the layer of an invalid value
+    pub fn resolve(parsed: &ParsedArgs, env: impl Fn(&str) -> Option<String>, config: &Config) -> Result<Self, Error> {
+        let env_url = env(RPC_URL_ENV_VAR);
+        let rpc_url = match first_set(parsed.value("url"), env_url.as_deref(), config.rpc_url.as_deref()) {
+            Some((url, source)) => Layered { value: parse_rpc_url(url).map_err(|e| in_layer(e, source, RPC_URL_ENV_VAR))?, source },
+            None => Layered { value: DEFAULT_RPC_URL.to_string(), source: SettingSource::Default },
+        };
+
+        let env_commitment = env(COMMITMENT_ENV_VAR);
+        let commitment = first_set(parsed.value("commitment"), env_commitment.as_deref(), config.commitment.as_deref())
+            .map(|(value, source)| {
+                let value = parse_commitment(value).map_err(|e| in_layer(e, source, COMMITMENT_ENV_VAR))?;
+                Ok::<_, Error>(Layered { value, source })
+            })
+            .transpose()?;
+
+        Ok(ConnectionSettings { rpc_url, commitment })
+    }
+}
+
+/// Checks an RPC endpoint URL
+///
+/// # Arguments
+/// * `value` - `http://` or `https://` URL
+///
+/// # Returns
+/// * `Result<String, Error>` - URL without surrounding whitespace, or `Error::InvalidInput`
+pub fn parse_rpc_url(value: &str) -> Result<String, Error> {
+    let url = value.trim();
+    if !(url.starts_with("http://") || url.starts_with("https://")) || url.len() <= "https://".len() {
+        return Err(Error::InvalidInput(format!("Invalid RPC URL '{}': expected an http:// or https:// URL", url)));
+    }
+    Ok(url.to_string())
+}
+
+/// Adds the layer an invalid value came from to its error
+fn in_layer(error: Error, source: SettingSource, env_var: &str) -> Error {
+    let layer = match source {
+        SettingSource::Env => env_var.to_string(),
+        source => source.to_string(),
+    };
+    Error::InvalidInput(format!("{} (from {})", error.message(), layer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::{SWITCH_FLAGS, VALUE_FLAGS};
+    use std::collections::HashMap;
+
+    fn args(flags: &[&str]) -> ParsedArgs {
+        let args: Vec<String> = ["dz_validator_pda", "pda-balance"].iter().chain(flags).map(|arg| arg.to_string()).collect();
+        ParsedArgs::parse(&args, VALUE_FLAGS, SWITCH_FLAGS).unwrap()
+    }
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_first_set() {
+        assert_eq!(first_set(Some("a"), Some("b"), Some("c")), Some(("a", SettingSource::Flag)));
+        assert_eq!(first_set(None, Some("b"), Some("c")), Some(("b", SettingSource::Env)));
+        assert_eq!(first_set(None, Some(" "), Some("c")), Some(("c", SettingSource::Config)));
+        assert_eq!(first_set(None, None, None), None);
+    }
+
+    #[test]
+    fn test_connection_settings_precedence() {
+        let config = Config {
+            rpc_url: Some("https://config.example".to_string()),
+            commitment: Some("processed".to_string()),
+            ..Config::default()
+        };
+        let vars = [(RPC_URL_ENV_VAR, "https://env.example"), (COMMITMENT_ENV_VAR, "confirmed")];
+
+        let defaults = ConnectionSettings::resolve(&args(&[]), env(&[]), &Config::default()).unwrap();
+        assert_eq!(defaults.rpc_url, Layered { value: DEFAULT_RPC_URL.to_string(), source: SettingSource::Default });
+        assert_eq!(defaults.commitment, None);
+
+        let from_config = ConnectionSettings::resolve(&args(&[]), env(&[]), &config).unwrap();
+        assert_eq!(from_config.rpc_url.source, SettingSource::Config);
+        assert_eq!(from_config.commitment.unwrap().value, CommitmentConfig::processed());
+
+        let from_env = ConnectionSettings::resolve(&args(&[]), env(&vars), &config).unwrap();
+        assert_eq!(from_env.rpc_url.value, "https://env.example");
+        assert_eq!(from_env.commitment.unwrap(), Layered { value: CommitmentConfig::confirmed(), source: SettingSource::Env });
+
+        let from_flags = ConnectionSettings::resolve(&args(&["--url", "http://localhost:8899", "--commitment", "finalized"]), env(&vars), &config).unwrap();
+        assert_eq!(from_flags.rpc_url, Layered { value: "http://localhost:8899".to_string(), source: SettingSource::Flag });
+        assert_eq!(from_flags.commitment.unwrap().value, CommitmentConfig::finalized());
+    }
+
+    #[test]
+    fn test_invalid_setting_names_its_layer() {
+        let error = ConnectionSettings::resolve(&args(&[]), env(&[(COMMITMENT_ENV_VAR, "recent")]), &Config::default()).unwrap_err();
+        assert!(error.is_user_error());
+        assert!(error.to_string().contains("Invalid commitment 'recent'"), "{}", error);
+        assert!(error.to_string().contains("(from DZ_COMMITMENT)"), "{}", error);
+
+        let config = Config { rpc_url: Some("api.mainnet-beta.solana.com".to_string()), ..Config::default() };
+        let error = ConnectionSettings::resolve(&args(&[]), env(&[]), &config).unwrap_err();
+        assert!(error.to_string().contains("(from config file)"), "{}", error);
+        assert!(parse_rpc_url("https://").is_err());
+    }
+}
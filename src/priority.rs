@@ -0,0 +1,201 @@
+use crate::report::{Warning, WARN_PRIORITY_FEE_OUTLIER};
+use crate::rpc::SolanaRpc;
+use crate::Error;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+/// Compute budget program
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Compute unit limit of a funding transaction with a priority fee
+///
+/// A transfer, a nonce advance and the two compute budget instructions use well under 1,000 units;
+/// the explicit limit keeps the priority fee from being charged for the default 200,000 units per instruction.
+pub const FUNDING_COMPUTE_UNIT_LIMIT: u32 = 10_000;
+
+/// A priority fee this many times above the recent p99 needs `--force`
+pub const MAX_PRIORITY_FEE_P99_MULTIPLE: u64 = 100;
+
+/// During congestion, a priority fee below the recent median divided by this needs `--force`
+pub const MIN_PRIORITY_FEE_MEDIAN_DIVISOR: u64 = 100;
+
+/// Recent prioritization fees paid for the accounts of a funding, in micro-lamports per compute unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriorityFeeStats {
+    /// Number of recent slots sampled
+    pub samples: usize,
+    /// Median
+    pub p50: u64,
+    /// 99th percentile
+    pub p99: u64,
+}
+
+impl PriorityFeeStats {
+    /// Computes the percentiles of recent prioritization fees
+    ///
+    /// # Arguments
+    /// * `fees` - Fee of each recent slot
+    ///
+    /// # Returns
+    /// * `Option<PriorityFeeStats>` - Percentiles, or None without samples
+    pub fn from_fees(fees: &[u64]) -> Option<Self> {
+        if fees.is_empty() {
+            return None;
+        }
+        let mut sorted = fees.to_vec();
+        sorted.sort_unstable();
+        // Nearest-rank percentile
+        let percentile = |p: usize| sorted[(sorted.len() * p).div_ceil(100).saturating_sub(1)];
+        Some(PriorityFeeStats { samples: sorted.len(), p50: percentile(50), p99: percentile(99) })
+    }
+
+    /// Returns true if most recent slots paid a priority fee
+    pub fn is_congested(&self) -> bool {
+        self.p50 > 0
+    }
+}
+
+/// Parses a priority fee (`--priority-fee`) in micro-lamports per compute unit
+///
+/// # Arguments
+/// * `value` - Whole number of micro-lamports
+///
+/// # Returns
+/// * `Result<u64, Error>` - Fee or `Error::InvalidInput`
+pub fn parse_priority_fee(value: &str) -> Result<u64, Error> {
+    value.trim().parse::<u64>().map_err(|_| {
+        Error::InvalidInput(format!("Invalid priority fee '{}': expected micro-lamports per compute unit, e.g. 5000", value))
+    })
+}
+
+/// Returns the lamports a priority fee adds to a funding transaction
+pub fn priority_fee_lamports(micro_lamports_per_cu: u64) -> u64 {
+    (micro_lamports_per_cu as u128 * FUNDING_COMPUTE_UNIT_LIMIT as u128).div_ceil(1_000_000) as u64
+}
+
+/// Returns the compute budget instructions setting the unit limit and price of a funding
+///
+/// # Arguments
+/// * `micro_lamports_per_cu` - Priority fee in micro-lamports per compute unit
+///
+/// # Returns
+/// * `Vec<Instruction>` - `SetComputeUnitLimit` and `SetComputeUnitPrice`
+pub fn compute_budget_instructions(micro_lamports_per_cu: u64) -> Vec<Instruction> {
+    let mut limit = vec![2u8];
+    limit.extend_from_slice(&FUNDING_COMPUTE_UNIT_LIMIT.to_le_bytes());
+    let mut price = vec![3u8];
+    price.extend_from_slice(&micro_lamports_per_cu.to_le_bytes());
+    vec![
+        Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &limit, Vec::new()),
+        Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &price, Vec::new()),
+    ]
+}
+
+/// Checks a user-specified priority fee against recent network fees
+///
+/// Catches typos such as lamports entered where micro-lamports were expected: a fee more than
+/// `MAX_PRIORITY_FEE_P99_MULTIPLE` times the recent p99, or effectively zero while the network is
+/// congested, cancels the funding unless forced.
+///
+/// # Arguments
+/// * `micro_lamports_per_cu` - Requested priority fee
+/// * `stats` - Recent fees (None if the node returned none, which skips the check)
+/// * `force` - Send anyway (`--force`)
+///
+/// # Returns
+/// * `Result<Option<Warning>, Error>` - Warning for a forced outlier, or `Error::FundingCancelled`
+pub fn check_priority_fee(micro_lamports_per_cu: u64, stats: Option<&PriorityFeeStats>, force: bool) -> Result<Option<Warning>, Error> {
+    let Some(stats) = stats else {
+        return Ok(None);
+    };
+    let too_high = stats.p99 > 0 && micro_lamports_per_cu / MAX_PRIORITY_FEE_P99_MULTIPLE > stats.p99;
+    let too_low = stats.is_congested() && micro_lamports_per_cu < stats.p50 / MIN_PRIORITY_FEE_MEDIAN_DIVISOR;
+    let reason = if too_high {
+        format!(
+            "Priority fee {} micro-lamports/CU ({} lamports) is more than {}x the recent p99 of {} micro-lamports/CU; was it entered in lamports?",
+            micro_lamports_per_cu,
+            priority_fee_lamports(micro_lamports_per_cu),
+            MAX_PRIORITY_FEE_P99_MULTIPLE,
+            stats.p99
+        )
+    } else if too_low {
+        format!(
+            "Priority fee {} micro-lamports/CU is effectively zero while the network is congested (recent median {} micro-lamports/CU); the transaction may not land",
+            micro_lamports_per_cu, stats.p50
+        )
+    } else {
+        return Ok(None);
+    };
+
+    if !force {
+        return Err(Error::FundingCancelled(format!("Funding cancelled: {}; use --force to send anyway", reason)));
+    }
+    Ok(Some(Warning::new(WARN_PRIORITY_FEE_OUTLIER, reason)))
+}
+
+/// Reads the recent prioritization fees paid for transactions writing the given accounts
+///
+/// # Arguments
+/// * `accounts` - Writable accounts of the funding (payer and PDA)
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Option<PriorityFeeStats>, Error>` - Percentiles, None without samples or on a node without the method, or error
+pub async fn recent_priority_fees(accounts: &[Pubkey], rpc: &dyn SolanaRpc) -> Result<Option<PriorityFeeStats>, Error> {
+    if !crate::compat::is_supported(&crate::compat::PRIORITY_FEE_SAMPLES) {
+        return Ok(None);
+    }
+    let fees = rpc.get_recent_prioritization_fees(accounts).await
+        .map_err(|e| Error::from_client_error("Failed to get recent prioritization fees", &e))?;
+    Ok(PriorityFeeStats::from_fees(&fees))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_percentiles() {
+        assert_eq!(PriorityFeeStats::from_fees(&[]), None);
+        let fees: Vec<u64> = (1..=100).collect();
+        assert_eq!(PriorityFeeStats::from_fees(&fees), Some(PriorityFeeStats { samples: 100, p50: 50, p99: 99 }));
+        assert_eq!(PriorityFeeStats::from_fees(&[0, 0, 7]), Some(PriorityFeeStats { samples: 3, p50: 0, p99: 7 }));
+        assert!(!PriorityFeeStats::from_fees(&[0, 0, 7]).unwrap().is_congested());
+    }
+
+    #[test]
+    fn test_check_priority_fee() {
+        let stats = PriorityFeeStats { samples: 150, p50: 20_000, p99: 50_000 };
+        assert_eq!(check_priority_fee(30_000, Some(&stats), false).unwrap(), None);
+        assert_eq!(check_priority_fee(5_000_000, Some(&stats), false).unwrap(), None);
+
+        // 1 lamport per CU typed as micro-lamports is far above p99
+        let error = check_priority_fee(1_000_000_000, Some(&stats), false).unwrap_err();
+        assert!(matches!(error, Error::FundingCancelled(_)));
+        assert!(error.to_string().contains("entered in lamports"), "{}", error);
+        let warning = check_priority_fee(1_000_000_000, Some(&stats), true).unwrap().unwrap();
+        assert_eq!(warning.code, WARN_PRIORITY_FEE_OUTLIER);
+
+        // Effectively zero during congestion
+        assert!(check_priority_fee(10, Some(&stats), false).unwrap_err().to_string().contains("effectively zero"));
+        assert_eq!(check_priority_fee(200, Some(&stats), false).unwrap(), None);
+
+        // Quiet network or no samples: anything goes
+        let quiet = PriorityFeeStats { samples: 150, p50: 0, p99: 0 };
+        assert_eq!(check_priority_fee(0, Some(&quiet), false).unwrap(), None);
+        assert_eq!(check_priority_fee(1_000_000_000, Some(&quiet), false).unwrap(), None);
+        assert_eq!(check_priority_fee(1_000_000_000, None, false).unwrap(), None);
+    }
+
+    #[test]
+    fn test_compute_budget_instructions() {
+        let instructions = compute_budget_instructions(5_000);
+        assert_eq!(instructions.len(), 2);
+        assert!(instructions.iter().all(|instruction| instruction.program_id == COMPUTE_BUDGET_PROGRAM_ID && instruction.accounts.is_empty()));
+        assert_eq!(instructions[0].data, [2, 0x10, 0x27, 0, 0]);
+        assert_eq!(instructions[1].data, [3, 0x88, 0x13, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(priority_fee_lamports(5_000), 50);
+        assert_eq!(priority_fee_lamports(1), 1);
+        assert!(parse_priority_fee("1.5").unwrap_err().is_user_error());
+    }
+}
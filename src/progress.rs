@@ -0,0 +1,95 @@
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Redraw interval of spinners
+const TICK_INTERVAL: Duration = Duration::from_millis(120);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Every visible bar is drawn through this, so log lines can be printed above them
+static BARS: OnceLock<MultiProgress> = OnceLock::new();
+
+/// Turns progress bars on or off for the process (off by default)
+///
+/// The CLI enables them for text output on a terminal; with `--output json`, `--quiet`, a redirected
+/// stderr, or in library use, every bar is hidden and costs nothing.
+///
+/// # Arguments
+/// * `enabled` - Draw progress bars and spinners on stderr
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns true if progress bars are drawn
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn bars() -> &'static MultiProgress {
+    BARS.get_or_init(MultiProgress::new)
+}
+
+/// Creates a bar for a known number of items, with completion count and ETA
+///
+/// # Arguments
+/// * `len` - Number of items
+/// * `message` - What is being done, e.g. "Collecting validators"
+///
+/// # Returns
+/// * `ProgressBar` - Visible bar, or a hidden one if progress bars are disabled
+pub fn bar(len: u64, message: &str) -> ProgressBar {
+    if !enabled() {
+        return ProgressBar::hidden();
+    }
+    let style = ProgressStyle::with_template("{spinner} {msg} [{bar:30}] {pos}/{len} ({elapsed}, ETA {eta})")
+        .unwrap_or_else(|_| ProgressStyle::default_bar())
+        .progress_chars("=> ");
+    let bar = bars().add(ProgressBar::new(len).with_style(style).with_message(message.to_string()));
+    bar.enable_steady_tick(TICK_INTERVAL);
+    bar
+}
+
+/// Creates a spinner for a wait of unknown length, with the time elapsed
+///
+/// # Arguments
+/// * `message` - What is awaited, e.g. "Fetching gossip nodes"
+///
+/// # Returns
+/// * `ProgressBar` - Visible spinner, or a hidden one if progress bars are disabled
+pub fn spinner(message: &str) -> ProgressBar {
+    if !enabled() {
+        return ProgressBar::hidden();
+    }
+    let style = ProgressStyle::with_template("{spinner} {msg} ({elapsed})").unwrap_or_else(|_| ProgressStyle::default_spinner());
+    let spinner = bars().add(ProgressBar::new_spinner().with_style(style).with_message(message.to_string()));
+    spinner.enable_steady_tick(TICK_INTERVAL);
+    spinner
+}
+
+/// Runs `f` with the bars cleared from the terminal, then draws them again
+///
+/// Used by the logger so log lines do not tear through a bar.
+pub fn suspend<R>(f: impl FnOnce() -> R) -> R {
+    match BARS.get() {
+        Some(bars) if enabled() => bars.suspend(f),
+        _ => f(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_progress_is_hidden() {
+        // Progress is off unless the CLI turns it on
+        let bar = bar(3, "Collecting validators");
+        bar.inc(3);
+        assert!(bar.is_hidden());
+        assert_eq!(bar.position(), 3);
+        assert!(spinner("Fetching gossip nodes").is_hidden());
+        assert_eq!(suspend(|| 7), 7);
+    }
+}
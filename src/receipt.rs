@@ -0,0 +1,178 @@
+use crate::rpc::SolanaRpc;
+use crate::verify::balance_increase;
+use crate::{generate_deposit_pda, Error};
+use serde::{Deserialize, Serialize};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+
+/// Version of the receipt file format
+pub const RECEIPT_VERSION: u32 = 1;
+
+/// Portable proof of a completed deposit, signed by the funding wallet (`--receipt`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositReceipt {
+    /// Receipt file format version
+    pub version: u32,
+    /// Signature of the funding transaction
+    pub signature: String,
+    /// Slot the transaction landed in
+    pub slot: u64,
+    /// Estimated time of its block as a Unix timestamp, if known
+    pub block_time: Option<i64>,
+    /// Funding wallet that paid and signed the receipt
+    pub payer: String,
+    /// Validator identity pubkey
+    pub validator: String,
+    /// Deposit PDA of the validator
+    pub pda: String,
+    /// Increase of the PDA balance in lamports
+    pub amount_lamports: u64,
+    /// Base58 signature of the payer over every other field
+    pub payer_signature: String,
+}
+
+impl DepositReceipt {
+    /// Returns the message signed by the payer
+    ///
+    /// Every field but the signature is covered, so any edit of the receipt file invalidates it.
+    pub fn signed_message(&self) -> Vec<u8> {
+        let block_time = self.block_time.map_or("-".to_string(), |block_time| block_time.to_string());
+        format!(
+            "dz_validator_pda deposit receipt\nversion: {}\nsignature: {}\nslot: {}\nblock_time: {}\npayer: {}\nvalidator: {}\npda: {}\namount_lamports: {}",
+            self.version, self.signature, self.slot, block_time, self.payer, self.validator, self.pda, self.amount_lamports
+        )
+        .into_bytes()
+    }
+}
+
+/// Builds and signs the receipt of a confirmed funding transaction
+///
+/// Slot, block time and amount are read from the landed transaction rather than taken from the
+/// caller, so the receipt states what the chain recorded.
+///
+/// # Arguments
+/// * `signature` - Signature of the confirmed funding transaction
+/// * `validator_id` - Funded validator
+/// * `payer` - Funding wallet, which signs the receipt
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<DepositReceipt, Error>` - Signed receipt, `Error::TransactionFailed` if the transaction failed
+///   or did not credit the PDA, or `Error::Rpc` if it cannot be fetched
+pub async fn create_receipt(signature: &Signature, validator_id: &Pubkey, payer: &dyn Signer, rpc: &dyn SolanaRpc) -> Result<DepositReceipt, Error> {
+    let fetched = rpc
+        .get_transaction(signature, CommitmentConfig::confirmed())
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get transaction", &e))?
+        .ok_or_else(|| Error::Rpc(format!("Transaction {} was not found by the node", signature)))?;
+    if let Some(error) = &fetched.error {
+        return Err(Error::TransactionFailed(format!("Transaction {} failed: {}", signature, error)));
+    }
+    let deposit_key = generate_deposit_pda(validator_id);
+    let amount_lamports = balance_increase(&fetched, &deposit_key);
+    if amount_lamports == 0 {
+        return Err(Error::TransactionFailed(format!("Transaction {} did not credit PDA {}", signature, deposit_key)));
+    }
+
+    let mut receipt = DepositReceipt {
+        version: RECEIPT_VERSION,
+        signature: signature.to_string(),
+        slot: fetched.slot,
+        block_time: fetched.block_time,
+        payer: payer.pubkey().to_string(),
+        validator: validator_id.to_string(),
+        pda: deposit_key.to_string(),
+        amount_lamports,
+        payer_signature: String::new(),
+    };
+    receipt.payer_signature = payer
+        .try_sign_message(&receipt.signed_message())
+        .map_err(|e| Error::Keypair(format!("Failed to sign receipt: {}", e)))?
+        .to_string();
+    Ok(receipt)
+}
+
+/// Checks a receipt without RPC access (`verify-receipt`)
+///
+/// # Arguments
+/// * `receipt` - Receipt to check
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if the PDA belongs to the validator and the payer signed the receipt,
+///   otherwise `Error::InvalidInput`
+pub fn verify_receipt(receipt: &DepositReceipt) -> Result<(), Error> {
+    let invalid = |reason: String| Error::InvalidInput(format!("Invalid receipt: {}", reason));
+    if receipt.version != RECEIPT_VERSION {
+        return Err(invalid(format!("unsupported version {}", receipt.version)));
+    }
+    let validator_id = receipt.validator.parse::<Pubkey>().map_err(|_| invalid(format!("invalid validator '{}'", receipt.validator)))?;
+    let payer = receipt.payer.parse::<Pubkey>().map_err(|_| invalid(format!("invalid payer '{}'", receipt.payer)))?;
+    if generate_deposit_pda(&validator_id).to_string() != receipt.pda {
+        return Err(invalid(format!("{} is not the deposit PDA of validator {}", receipt.pda, validator_id)));
+    }
+    let valid_signature = receipt
+        .payer_signature
+        .parse::<Signature>()
+        .is_ok_and(|signature| signature.verify(payer.as_ref(), &receipt.signed_message()));
+    if !valid_signature {
+        return Err(invalid(format!("the signature does not match payer {}", payer)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+    use crate::rpc::FetchedTransaction;
+    use crate::instruction::build_fund_instruction;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::transaction::{Transaction, TransactionError};
+
+    const SOL: u64 = 1_000_000_000;
+
+    fn funding(payer: &Keypair, validator_id: &Pubkey, lamports: u64) -> FetchedTransaction {
+        let instruction = build_fund_instruction(&payer.pubkey(), validator_id, lamports);
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[payer], Hash::new_unique());
+        FetchedTransaction {
+            slot: 900,
+            block_time: Some(1_700_000_000),
+            transaction: transaction.into(),
+            error: None,
+            loaded_addresses: Vec::new(),
+            pre_balances: vec![10 * SOL, 0, 1],
+            post_balances: vec![10 * SOL - lamports - 5_000, lamports, 1],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_verify_receipt() {
+        let (payer, validator_id) = (Keypair::new(), Pubkey::new_unique());
+        let paid = funding(&payer, &validator_id, 2 * SOL);
+        let signature = paid.transaction.signatures[0];
+        let mut failed = funding(&payer, &validator_id, SOL);
+        failed.error = Some(TransactionError::InsufficientFundsForFee);
+        let failed_signature = failed.transaction.signatures[0];
+        let rpc = MockRpc::new().with_landed_transaction(paid, false).with_landed_transaction(failed, true);
+
+        let receipt = create_receipt(&signature, &validator_id, &payer, &rpc).await.unwrap();
+        assert_eq!((receipt.slot, receipt.block_time, receipt.amount_lamports), (900, Some(1_700_000_000), 2 * SOL));
+        assert_eq!(receipt.pda, generate_deposit_pda(&validator_id).to_string());
+        verify_receipt(&receipt).unwrap();
+
+        // Any edited field, or a receipt signed by another key, is rejected
+        let edited = DepositReceipt { amount_lamports: 20 * SOL, ..receipt.clone() };
+        assert!(verify_receipt(&edited).unwrap_err().to_string().contains("does not match payer"));
+        let impostor = DepositReceipt { payer: Pubkey::new_unique().to_string(), ..receipt.clone() };
+        assert!(verify_receipt(&impostor).is_err());
+        let other_validator = DepositReceipt { validator: Pubkey::new_unique().to_string(), ..receipt };
+        assert!(verify_receipt(&other_validator).unwrap_err().to_string().contains("is not the deposit PDA"));
+
+        // Failed, unrelated and unknown transactions get no receipt
+        assert!(matches!(create_receipt(&failed_signature, &validator_id, &payer, &rpc).await, Err(Error::TransactionFailed(_))));
+        assert!(matches!(create_receipt(&signature, &Pubkey::new_unique(), &payer, &rpc).await, Err(Error::TransactionFailed(_))));
+        assert!(create_receipt(&Signature::new_unique(), &validator_id, &payer, &rpc).await.is_err());
+    }
+}
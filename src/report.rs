@@ -0,0 +1,324 @@
+use crate::trace::trace_id;
+use crate::timing::{self, MethodTiming};
+use crate::Error;
+use serde::Serialize;
+
+/// Validator is not present in gossip
+pub const WARN_NOT_IN_GOSSIP: &str = "validator_not_in_gossip";
+/// Gossip presence could not be checked
+pub const WARN_GOSSIP_CHECK_FAILED: &str = "gossip_check_failed";
+/// Gossip check was skipped with `--skip-gossip-check`
+pub const WARN_GOSSIP_CHECK_SKIPPED: &str = "gossip_check_skipped";
+/// Validator has no vote account
+pub const WARN_NO_VOTE_ACCOUNT: &str = "no_vote_account";
+/// Vote account is delinquent
+pub const WARN_DELINQUENT: &str = "vote_account_delinquent";
+/// Vote account has no activated stake
+pub const WARN_NO_STAKE: &str = "no_activated_stake";
+/// Vote account does not meet the health policy
+pub const WARN_VOTE_POLICY: &str = "vote_policy";
+/// Account balance is below the rent-exempt minimum
+pub const WARN_BELOW_RENT_EXEMPTION: &str = "balance_below_rent_exemption";
+/// Deposit PDA is owned by a program other than the revenue distribution program
+pub const WARN_UNEXPECTED_OWNER: &str = "unexpected_pda_owner";
+/// Transfer was reduced to keep the funding wallet reserve (config `reserve_sol`)
+pub const WARN_TRIMMED_TO_RESERVE: &str = "amount_trimmed_to_reserve";
+/// RPC node reports itself unhealthy or behind the cluster
+pub const WARN_STALE_RPC: &str = "stale_rpc";
+/// First funding from a rotated wallet could not be verified
+pub const WARN_ROTATED_WALLET_UNVERIFIED: &str = "rotated_wallet_unverified";
+/// Transaction was signed offline without checking the validator status
+pub const WARN_STATUS_NOT_CHECKED: &str = "validator_status_not_checked";
+/// Priority fee is far from recent network fees and was sent with `--force`
+pub const WARN_PRIORITY_FEE_OUTLIER: &str = "priority_fee_outlier";
+/// Squads vault holds less than the proposed funding
+pub const WARN_VAULT_UNDERFUNDED: &str = "vault_underfunded";
+/// Funding landed but its `--receipt` could not be created or written
+pub const WARN_RECEIPT_NOT_WRITTEN: &str = "receipt_not_written";
+/// Funding exceeds a configured budget cap but was sent with `--override-budget`
+pub const WARN_BUDGET_OVERRIDDEN: &str = "budget_overridden";
+/// An earlier transfer from the same wallet to the PDA is not finalized yet
+pub const WARN_PENDING_FUNDING: &str = "pending_funding";
+/// The funding wallet holds less than an `allocate` plan needs
+pub const WARN_WALLET_SHORTFALL: &str = "wallet_shortfall";
+/// RPC node lacks a capability the run asked for and a fallback was used instead
+pub const WARN_RPC_FALLBACK: &str = "rpc_fallback";
+/// RPC node lacks a capability the run needs and that has no fallback
+pub const WARN_RPC_UNSUPPORTED: &str = "rpc_unsupported";
+
+/// Output format selected with `--output`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable text (default)
+    #[default]
+    Text,
+    /// A single JSON document on stdout
+    Json,
+    /// Comma-separated values with a header row (batch operations)
+    Csv,
+}
+
+impl OutputFormat {
+    /// Parses `text`, `json` or `csv`
+    ///
+    /// # Arguments
+    /// * `value` - Value of `--output`
+    ///
+    /// # Returns
+    /// * `Result<OutputFormat, Error>` - Output format or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(Error::InvalidInput(format!("Invalid output format '{}': expected text, json or csv", value))),
+        }
+    }
+}
+
+/// Soft issue that does not stop the operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Warning {
+    /// Stable machine-readable code (one of the `WARN_*` constants)
+    pub code: String,
+    /// Human readable description
+    pub message: String,
+}
+
+impl Warning {
+    /// Creates a warning
+    ///
+    /// # Arguments
+    /// * `code` - Warning code
+    /// * `message` - Human readable description
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        Warning {
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Fatal error as reported in JSON output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorReport {
+    /// Error kind (see `Error::kind`)
+    pub kind: String,
+    /// Error message
+    pub message: String,
+    /// True if retrying later may succeed
+    pub retryable: bool,
+    /// Exit code of the process (see `Error::exit_code`)
+    pub exit_code: i32,
+}
+
+impl From<&Error> for ErrorReport {
+    fn from(error: &Error) -> Self {
+        ErrorReport {
+            kind: error.kind().to_string(),
+            message: error.to_string(),
+            retryable: error.is_retryable(),
+            exit_code: error.exit_code(),
+        }
+    }
+}
+
+/// JSON document printed by an operation in JSON mode
+///
+/// Soft issues go to `warnings` and never make `ok` false; only a fatal `error` does.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsonReport {
+    /// Operation name (e.g. `pda-balance`)
+    pub operation: String,
+    /// True if the operation succeeded
+    pub ok: bool,
+    /// Operation specific result fields
+    pub result: serde_json::Value,
+    /// Soft issues
+    pub warnings: Vec<Warning>,
+    /// Fatal error, if the operation failed
+    pub error: Option<ErrorReport>,
+    /// Trace id of the run, also found in logs, audit entries and notifications
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// Latency and retries per RPC method, with `--timing`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Vec<MethodTiming>>,
+}
+
+impl JsonReport {
+    /// Creates a report for a successful operation
+    ///
+    /// # Arguments
+    /// * `operation` - Operation name
+    /// * `result` - Result fields
+    /// * `warnings` - Soft issues
+    pub fn success(operation: &str, result: serde_json::Value, warnings: Vec<Warning>) -> Self {
+        JsonReport {
+            operation: operation.to_string(),
+            ok: true,
+            result,
+            warnings,
+            error: None,
+            trace_id: trace_id().map(str::to_string),
+            timing: timing::report(),
+        }
+    }
+
+    /// Creates a report for a failed operation
+    ///
+    /// # Arguments
+    /// * `operation` - Operation name
+    /// * `result` - Result fields known before the failure
+    /// * `warnings` - Soft issues collected before the failure
+    /// * `error` - Fatal error
+    pub fn failure(operation: &str, result: serde_json::Value, warnings: Vec<Warning>, error: &Error) -> Self {
+        JsonReport {
+            operation: operation.to_string(),
+            ok: false,
+            result,
+            warnings,
+            error: Some(ErrorReport::from(error)),
+            trace_id: trace_id().map(str::to_string),
+            timing: timing::report(),
+        }
+    }
+
+    /// Serializes the report as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|e| format!("{{\"ok\":false,\"error\":\"{}\"}}", e))
+    }
+}
+
+/// Returns a warning if a balance is below the rent-exempt minimum
+///
+/// # Arguments
+/// * `balance` - Account balance in lamports
+/// * `rent_exempt_minimum` - Rent-exempt minimum for the account in lamports
+///
+/// # Returns
+/// * `Option<Warning>` - Warning if the balance is below the minimum
+pub fn rent_exemption_warning(balance: u64, rent_exempt_minimum: u64) -> Option<Warning> {
+    (balance < rent_exempt_minimum).then(|| {
+        Warning::new(
+            WARN_BELOW_RENT_EXEMPTION,
+            format!(
+                "Balance {} lamports is below the rent-exempt minimum of {} lamports",
+                balance, rent_exempt_minimum
+            ),
+        )
+    })
+}
+
+/// Warnings caused by an explicit opt-out flag rather than an observed problem
+const OPT_OUT_WARNINGS: &[&str] = &[WARN_GOSSIP_CHECK_SKIPPED, WARN_STATUS_NOT_CHECKED];
+
+/// Turns warnings into a failure for `--strict`
+///
+/// Warnings from explicit opt-outs (`--skip-gossip-check`, `--sign-only`) do not fail.
+///
+/// # Arguments
+/// * `warnings` - Warnings raised so far
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if no warning is left, otherwise `Error::StrictWarning` with every message
+pub fn strict_check(warnings: &[Warning]) -> Result<(), Error> {
+    let messages: Vec<&str> = warnings
+        .iter()
+        .filter(|warning| !OPT_OUT_WARNINGS.contains(&warning.code.as_str()))
+        .map(|warning| warning.message.as_str())
+        .collect();
+    if messages.is_empty() {
+        return Ok(());
+    }
+    Err(Error::StrictWarning(format!("Strict mode: {}", messages.join("; "))))
+}
+
+/// Formats one CSV row (RFC 4180)
+///
+/// Fields containing a comma, quote or line break are quoted, with quotes doubled.
+///
+/// # Arguments
+/// * `fields` - Field values in column order
+///
+/// # Returns
+/// * `String` - Row terminated by CRLF
+pub fn csv_row<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut row = fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\r', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output_format() {
+        assert_eq!(OutputFormat::parse("json").unwrap(), OutputFormat::Json);
+        assert_eq!(OutputFormat::parse("TEXT").unwrap(), OutputFormat::Text);
+        assert_eq!(OutputFormat::parse("csv").unwrap(), OutputFormat::Csv);
+        assert!(OutputFormat::parse("yaml").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_csv_row_quoting() {
+        assert_eq!(csv_row(&["a", "", "1.5"]), "a,,1.5\r\n");
+        assert_eq!(csv_row(&["x,y", "say \"hi\"", "two\nlines"]), "\"x,y\",\"say \"\"hi\"\"\",\"two\nlines\"\r\n");
+    }
+
+    #[test]
+    fn test_json_report_keeps_warnings_separate_from_errors() {
+        let warning = Warning::new(WARN_NOT_IN_GOSSIP, "Validator is not in Solana gossip network");
+        let report = JsonReport::success("pda-address", serde_json::json!({ "pda": "Pda" }), vec![warning]);
+        let value: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+        assert_eq!(value["ok"], true);
+        assert_eq!(value["warnings"][0]["code"], WARN_NOT_IN_GOSSIP);
+        assert!(value["error"].is_null());
+
+        let error = Error::RpcUnavailable("timeout".to_string());
+        let report = JsonReport::failure("pda-balance", serde_json::json!({}), Vec::new(), &error);
+        let value: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+        assert_eq!(value["ok"], false);
+        assert_eq!(value["warnings"], serde_json::json!([]));
+        assert_eq!(value["error"]["kind"], "rpc_unavailable");
+        assert_eq!(value["error"]["retryable"], true);
+        assert_eq!(value["error"]["exit_code"], 3);
+    }
+
+    #[test]
+    fn test_strict_check() {
+        assert!(strict_check(&[]).is_ok());
+        assert!(strict_check(&[Warning::new(WARN_GOSSIP_CHECK_SKIPPED, "Gossip check skipped")]).is_ok());
+
+        let warnings = [
+            Warning::new(WARN_GOSSIP_CHECK_SKIPPED, "Gossip check skipped"),
+            Warning::new(WARN_STALE_RPC, "RPC node may be stale"),
+            Warning::new(WARN_BELOW_RENT_EXEMPTION, "Balance is below the rent-exempt minimum"),
+        ];
+        let error = strict_check(&warnings).unwrap_err();
+        assert_eq!(error.kind(), "strict_warning");
+        assert_eq!(error.message(), "Strict mode: RPC node may be stale; Balance is below the rent-exempt minimum");
+    }
+
+    #[test]
+    fn test_rent_exemption_warning() {
+        assert!(rent_exemption_warning(890_880, 890_880).is_none());
+        let warning = rent_exemption_warning(1_000, 890_880).unwrap();
+        assert_eq!(warning.code, WARN_BELOW_RENT_EXEMPTION);
+        assert!(warning.message.contains("1000 lamports"));
+    }
+}
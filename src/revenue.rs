@@ -0,0 +1,328 @@
+use crate::health::get_validator_vote_account;
+use crate::history::balance_changes_since_slot;
+use crate::rpc::SolanaRpc;
+use crate::{generate_deposit_pda, Error};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+/// Most epochs one `revenue-report` covers (one `getInflationReward` request per epoch)
+pub const MAX_REPORT_EPOCHS: u64 = 64;
+
+/// Inclusive range of epochs given with `--epochs`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochRange {
+    /// First epoch of the report
+    pub first: u64,
+    /// Last epoch of the report (included)
+    pub last: u64,
+}
+
+impl EpochRange {
+    /// Parses `640..650` (both ends included) or a single epoch like `645`
+    ///
+    /// # Arguments
+    /// * `value` - Value of `--epochs`
+    ///
+    /// # Returns
+    /// * `Result<EpochRange, Error>` - Range of at most `MAX_REPORT_EPOCHS` epochs, or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        let invalid = |reason: &str| Error::InvalidInput(format!("Invalid --epochs '{}': {}", value, reason));
+        let (first, last) = value.trim().split_once("..").unwrap_or((value.trim(), value.trim()));
+        let (Ok(first), Ok(last)) = (first.trim().parse::<u64>(), last.trim().parse::<u64>()) else {
+            return Err(invalid("expected an epoch range like 640..650"));
+        };
+        if first > last {
+            return Err(invalid("the first epoch is after the last"));
+        }
+        if last - first >= MAX_REPORT_EPOCHS {
+            return Err(invalid(&format!("at most {} epochs can be reported at once", MAX_REPORT_EPOCHS)));
+        }
+        Ok(EpochRange { first, last })
+    }
+}
+
+/// Whether the deposits cover the expected share of the rewards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reconciliation {
+    /// At least the expected share was deposited
+    Covered,
+    /// Less than the expected share was deposited
+    Short,
+    /// The epoch is not over, so its rewards are not known yet
+    Pending,
+}
+
+impl Reconciliation {
+    fn of(deposited_lamports: u64, expected_lamports: Option<u64>) -> Self {
+        match expected_lamports {
+            None => Reconciliation::Pending,
+            Some(expected) if deposited_lamports >= expected => Reconciliation::Covered,
+            Some(_) => Reconciliation::Short,
+        }
+    }
+
+    /// Label of the text report
+    pub fn label(&self) -> &'static str {
+        match self {
+            Reconciliation::Covered => "COVERED",
+            Reconciliation::Short => "SHORT",
+            Reconciliation::Pending => "PENDING",
+        }
+    }
+}
+
+/// Deposit PDA movements and validator rewards of one epoch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RevenueEpoch {
+    /// Epoch number
+    pub epoch: u64,
+    /// First slot of the epoch
+    pub first_slot: u64,
+    /// Last slot of the epoch
+    pub last_slot: u64,
+    /// PDA balance at the start of the epoch in lamports
+    pub opening_balance_lamports: u64,
+    /// Lamports deposited into the PDA during the epoch
+    pub deposited_lamports: u64,
+    /// Lamports claimed out of the PDA during the epoch
+    pub claimed_lamports: u64,
+    /// PDA balance at the end of the epoch (or now, for the current epoch) in lamports
+    pub closing_balance_lamports: u64,
+    /// Inflation reward of the vote account for the epoch (None while the epoch is not over)
+    pub reward_lamports: Option<u64>,
+    /// Commission the reward was paid at, if the node reports it
+    pub commission: Option<u8>,
+    /// `--share` of the reward that should have been deposited (None without `--share` or while the epoch is not over)
+    pub expected_lamports: Option<u64>,
+    /// Deposits against the expected share (None without `--share`)
+    pub status: Option<Reconciliation>,
+}
+
+/// Reconciliation of deposits, claims and rewards over a range of epochs (`revenue-report`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RevenueReport {
+    /// Validator identity
+    pub validator: String,
+    /// Deposit PDA
+    pub pda: String,
+    /// Vote account the rewards are read for (None if the validator has none)
+    pub vote_account: Option<String>,
+    /// Expected share of the rewards in basis points (`--share`)
+    pub share_basis_points: Option<u64>,
+    /// One row per epoch, oldest first
+    pub epochs: Vec<RevenueEpoch>,
+    /// Lamports deposited over the range
+    pub deposited_lamports: u64,
+    /// Lamports claimed over the range
+    pub claimed_lamports: u64,
+    /// Inflation rewards of the completed epochs
+    pub reward_lamports: u64,
+    /// Expected deposits of the completed epochs (None without `--share`)
+    pub expected_lamports: Option<u64>,
+    /// Deposits over the whole range against the expected share (None without `--share`)
+    pub status: Option<Reconciliation>,
+}
+
+/// Returns the share of a reward, rounded down
+fn share_of(reward_lamports: u64, share_basis_points: u64) -> u64 {
+    (reward_lamports as u128 * share_basis_points as u128 / 10_000) as u64
+}
+
+/// Correlates PDA deposits and claims with the inflation rewards of a validator per epoch
+///
+/// The PDA history is read back to the first slot of the range and every balance change is
+/// assigned to the epoch of its slot. Rewards come from `getInflationReward` for the current vote
+/// account of the validator; block (fee) rewards are not served by that method and not included.
+/// With `share_basis_points`, each completed epoch compares its deposits with that share of its
+/// reward. Deposits made for an epoch after it ended land in a later epoch, so the range total
+/// is what `status` reports.
+///
+/// # Arguments
+/// * `validator_id` - Validator identity
+/// * `range` - Epochs to report
+/// * `share_basis_points` - Share of the rewards expected to be deposited, if any
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<RevenueReport, Error>` - Report, `Error::InvalidInput` for epochs that have not started,
+///   or error if the node could not be queried
+pub async fn revenue_report(validator_id: &Pubkey, range: EpochRange, share_basis_points: Option<u64>, rpc: &dyn SolanaRpc) -> Result<RevenueReport, Error> {
+    let epoch_info = rpc.get_epoch_info().await
+        .map_err(|e| Error::from_client_error("Failed to get epoch info", &e))?;
+    if range.last > epoch_info.epoch {
+        return Err(Error::InvalidInput(format!("Epoch {} has not started yet (current epoch is {})", range.last, epoch_info.epoch)));
+    }
+    let current_epoch_start = epoch_info.absolute_slot.saturating_sub(epoch_info.slot_index);
+    let epoch_start = |epoch: u64| current_epoch_start.saturating_sub((epoch_info.epoch - epoch) * epoch_info.slots_in_epoch);
+
+    let vote_account = match get_validator_vote_account(validator_id, rpc).await? {
+        Some(vote_account) => Some(
+            vote_account
+                .vote_pubkey
+                .parse::<Pubkey>()
+                .map_err(|e| Error::Rpc(format!("Invalid vote account {} returned by the node: {}", vote_account.vote_pubkey, e)))?,
+        ),
+        None => None,
+    };
+    let pda = generate_deposit_pda(validator_id);
+    let points = balance_changes_since_slot(validator_id, epoch_start(range.first), rpc).await?;
+    let mut balance = match points.first() {
+        Some(point) => (point.balance_lamports as i64 - point.change_lamports) as u64,
+        None => rpc.get_balance(&pda).await.map_err(|e| Error::from_client_error("Failed to get balance", &e))?,
+    };
+
+    let mut epochs = Vec::new();
+    for epoch in range.first..=range.last {
+        let first_slot = epoch_start(epoch);
+        let last_slot = first_slot + epoch_info.slots_in_epoch - 1;
+        let opening_balance_lamports = balance;
+        let (mut deposited_lamports, mut claimed_lamports) = (0, 0);
+        for point in points.iter().filter(|point| (first_slot..=last_slot).contains(&point.slot)) {
+            if point.change_lamports > 0 {
+                deposited_lamports += point.change_lamports.unsigned_abs();
+            } else {
+                claimed_lamports += point.change_lamports.unsigned_abs();
+            }
+            balance = point.balance_lamports;
+        }
+
+        // Rewards of an epoch are paid at the start of the next one
+        let reward = match vote_account {
+            Some(vote_account) if epoch < epoch_info.epoch => rpc
+                .get_inflation_reward(&[vote_account], epoch)
+                .await
+                .map_err(|e| Error::from_client_error(&format!("Failed to get inflation reward for epoch {}", epoch), &e))?
+                .into_iter()
+                .next()
+                .flatten(),
+            _ => None,
+        };
+        let reward_lamports = (epoch < epoch_info.epoch).then(|| reward.as_ref().map_or(0, |reward| reward.amount));
+        let expected_lamports = share_basis_points.zip(reward_lamports).map(|(share, reward)| share_of(reward, share));
+        epochs.push(RevenueEpoch {
+            epoch,
+            first_slot,
+            last_slot,
+            opening_balance_lamports,
+            deposited_lamports,
+            claimed_lamports,
+            closing_balance_lamports: balance,
+            reward_lamports,
+            commission: reward.and_then(|reward| reward.commission),
+            expected_lamports,
+            status: share_basis_points.map(|_| Reconciliation::of(deposited_lamports, expected_lamports)),
+        });
+    }
+
+    let deposited_lamports = epochs.iter().map(|epoch| epoch.deposited_lamports).sum();
+    let reward_lamports = epochs.iter().filter_map(|epoch| epoch.reward_lamports).sum();
+    let completed = range.first < epoch_info.epoch;
+    let expected_lamports = share_basis_points
+        .map(|_| epochs.iter().filter_map(|epoch| epoch.expected_lamports).sum())
+        .filter(|_| completed);
+    Ok(RevenueReport {
+        validator: validator_id.to_string(),
+        pda: pda.to_string(),
+        vote_account: vote_account.map(|vote_account| vote_account.to_string()),
+        share_basis_points,
+        claimed_lamports: epochs.iter().map(|epoch| epoch.claimed_lamports).sum(),
+        deposited_lamports,
+        reward_lamports,
+        expected_lamports,
+        status: share_basis_points.map(|_| Reconciliation::of(deposited_lamports, expected_lamports)),
+        epochs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+    use crate::rpc::FetchedTransaction;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::Transaction;
+    use solana_system_interface::instruction::transfer;
+
+    const SLOTS: u64 = 432_000;
+    const SOL: u64 = 1_000_000_000;
+
+    fn landed(from: &Keypair, to: &Pubkey, balances: (u64, u64), slot: u64) -> FetchedTransaction {
+        let instruction = transfer(&from.pubkey(), to, balances.1.abs_diff(balances.0));
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&from.pubkey()), &[from], Hash::new_unique());
+        FetchedTransaction {
+            slot,
+            block_time: None,
+            transaction: transaction.into(),
+            error: None,
+            loaded_addresses: Vec::new(),
+            pre_balances: vec![100 * SOL, balances.0, 1],
+            post_balances: vec![100 * SOL, balances.1, 1],
+        }
+    }
+
+    #[test]
+    fn test_parse_epoch_range() {
+        assert_eq!(EpochRange::parse("640..650").unwrap(), EpochRange { first: 640, last: 650 });
+        assert_eq!(EpochRange::parse("645").unwrap(), EpochRange { first: 645, last: 645 });
+        for invalid in ["", "650..640", "a..b", "640..", "0..64"] {
+            assert!(EpochRange::parse(invalid).unwrap_err().is_user_error(), "{}", invalid);
+        }
+        assert!(EpochRange::parse("0..63").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_revenue_report() {
+        let (payer, validator_id) = (Keypair::new(), Pubkey::new_unique());
+        let pda = generate_deposit_pda(&validator_id);
+        // Epoch 100 is current and starts at slot 10 * SLOTS, so epoch 97 starts at 7 * SLOTS
+        let rpc = MockRpc::new().with_vote_account(validator_id, 0, 1_000 * SOL, false);
+        rpc.set_slot(10 * SLOTS);
+        rpc.set_epoch(100);
+        let vote_account = get_validator_vote_account(&validator_id, &rpc).await.unwrap().unwrap().vote_pubkey.parse::<Pubkey>().unwrap();
+        let rpc = rpc
+            .with_landed_transaction(landed(&payer, &pda, (0, SOL), 6 * SLOTS + 5), true)
+            .with_landed_transaction(landed(&payer, &pda, (SOL, 2 * SOL), 7 * SLOTS + 5), true)
+            .with_landed_transaction(landed(&payer, &pda, (2 * SOL, SOL / 2), 8 * SLOTS + 5), true)
+            .with_landed_transaction(landed(&payer, &pda, (SOL / 2, SOL), 9 * SLOTS + 5), true)
+            .with_landed_transaction(landed(&payer, &pda, (SOL, 3 * SOL), 10 * SLOTS + 5), false)
+            .with_inflation_reward(vote_account, 97, 10 * SOL)
+            .with_inflation_reward(vote_account, 98, 10 * SOL)
+            .with_inflation_reward(vote_account, 99, 20 * SOL);
+
+        let report = revenue_report(&validator_id, EpochRange { first: 97, last: 100 }, Some(1_000), &rpc).await.unwrap();
+        let rows: Vec<_> = report
+            .epochs
+            .iter()
+            .map(|epoch| {
+                (
+                    epoch.epoch,
+                    epoch.opening_balance_lamports,
+                    epoch.deposited_lamports,
+                    epoch.claimed_lamports,
+                    epoch.closing_balance_lamports,
+                    epoch.expected_lamports,
+                    epoch.status,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (97, SOL, SOL, 0, 2 * SOL, Some(SOL), Some(Reconciliation::Covered)),
+                (98, 2 * SOL, 0, 3 * SOL / 2, SOL / 2, Some(SOL), Some(Reconciliation::Short)),
+                (99, SOL / 2, SOL / 2, 0, SOL, Some(2 * SOL), Some(Reconciliation::Short)),
+                (100, SOL, 2 * SOL, 0, 3 * SOL, None, Some(Reconciliation::Pending)),
+            ]
+        );
+        assert_eq!(report.epochs[0].commission, Some(10));
+        assert_eq!((report.deposited_lamports, report.claimed_lamports, report.reward_lamports), (7 * SOL / 2, 3 * SOL / 2, 40 * SOL));
+        // 3.5 SOL deposited against 4 SOL expected from the completed epochs
+        assert_eq!((report.expected_lamports, report.status), (Some(4 * SOL), Some(Reconciliation::Short)));
+
+        let report = revenue_report(&validator_id, EpochRange { first: 99, last: 99 }, None, &rpc).await.unwrap();
+        assert_eq!((report.epochs[0].reward_lamports, report.epochs[0].status, report.status), (Some(20 * SOL), None, None));
+        assert!(revenue_report(&validator_id, EpochRange { first: 100, last: 101 }, None, &rpc).await.unwrap_err().is_user_error());
+    }
+}
@@ -0,0 +1,629 @@
+use crate::Error;
+use async_trait::async_trait;
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcBlockProductionConfig, RpcBlockProductionConfigRange, RpcContextConfig, RpcSendTransactionConfig,
+};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{RpcConfirmedTransactionStatusWithSignature, RpcInflationReward, RpcResult, RpcVoteAccountStatus};
+use solana_commitment_config::CommitmentConfig;
+use solana_rpc_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClientConfig};
+use solana_sdk::account::Account;
+use solana_sdk::epoch_info::EpochInfo;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{Transaction, TransactionError, VersionedTransaction};
+use solana_transaction_status_client_types::{EncodedConfirmedTransactionWithStatusMeta, TransactionStatus, UiLoadedAddresses};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+pub mod gossip_cache;
+pub mod mock;
+pub mod transport;
+
+pub use transport::{parse_proxy, parse_rpc_header, RateLimit};
+use gossip_cache::GossipCacheFile;
+use transport::RateLimitedSender;
+
+/// RPC endpoint used when none is given
+pub const DEFAULT_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+
+/// RPC service of a validator on its own host (`--from-local-rpc`)
+pub const LOCAL_RPC_URL: &str = "http://localhost:8899";
+
+/// Request timeout of the HTTP transport when `--rpc-timeout` is not given (the solana-client default)
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most accounts `getMultipleAccounts` returns per request
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Gossip cache TTL of `--nice`, `pda-batch` and `--gossip-cache-file` when `--gossip-cache-ttl` is not given
+pub const DEFAULT_GOSSIP_CACHE_TTL: Duration = Duration::from_secs(600);
+
+/// Process-wide RPC behaviour, set once at startup
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcSettings {
+    /// Endpoint of clients created without an explicit URL (None for `DEFAULT_RPC_URL`)
+    pub url: Option<String>,
+    /// Client-side request rate shared by all clients (None for no pacing)
+    pub rate_limit: Option<RateLimit>,
+    /// How long the gossip node list is reused (None to fetch it for every check)
+    pub gossip_cache_ttl: Option<Duration>,
+    /// File that keeps the gossip node list between runs within the TTL (None to cache in memory only)
+    pub gossip_cache_file: Option<PathBuf>,
+    /// Commitment of reads, preflight simulation and confirmation waits (finalized by default)
+    pub commitment: CommitmentConfig,
+    /// Time a single request may take before it fails (None for `DEFAULT_RPC_TIMEOUT`)
+    pub timeout: Option<Duration>,
+    /// Extra HTTP headers of every request, e.g. an `Authorization` header of the RPC provider
+    pub headers: Vec<(String, String)>,
+    /// Proxy of every request (None for the `HTTPS_PROXY`/`HTTP_PROXY`/`ALL_PROXY` environment variables)
+    pub proxy: Option<String>,
+    /// Yellowstone gRPC endpoint of `--backend geyser` (None for `getClusterNodes` and polling)
+    pub geyser_endpoint: Option<String>,
+}
+
+impl RpcSettings {
+    /// Conservative preset for public endpoints (`--nice`): 1 request per second and a 10 minute gossip cache
+    pub fn nice() -> Self {
+        RpcSettings {
+            rate_limit: Some(RateLimit { requests_per_second: 1, burst: 1 }),
+            gossip_cache_ttl: Some(DEFAULT_GOSSIP_CACHE_TTL),
+            ..RpcSettings::default()
+        }
+    }
+}
+
+/// Parses a commitment level (`--commitment`)
+///
+/// # Arguments
+/// * `value` - `processed`, `confirmed` or `finalized`
+///
+/// # Returns
+/// * `Result<CommitmentConfig, Error>` - Commitment or `Error::InvalidInput`
+pub fn parse_commitment(value: &str) -> Result<CommitmentConfig, Error> {
+    value.to_ascii_lowercase().parse::<CommitmentConfig>().map_err(|_| {
+        Error::InvalidInput(format!("Invalid commitment '{}': expected processed, confirmed or finalized", value))
+    })
+}
+
+/// Parses the gossip cache TTL (`--gossip-cache-ttl`)
+///
+/// # Arguments
+/// * `value` - TTL in seconds (0 to fetch the node list for every check)
+///
+/// # Returns
+/// * `Result<Option<Duration>, Error>` - TTL (None for no cache) or `Error::InvalidInput`
+pub fn parse_gossip_cache_ttl(value: &str) -> Result<Option<Duration>, Error> {
+    let secs = value.trim().parse::<u64>().map_err(|_| {
+        Error::InvalidInput(format!("Invalid gossip cache TTL '{}': expected a number of seconds", value))
+    })?;
+    Ok((secs > 0).then(|| Duration::from_secs(secs)))
+}
+
+/// Parses a timeout in seconds (`--rpc-timeout`, `--deadline`)
+///
+/// # Arguments
+/// * `value` - Positive number of seconds
+/// * `flag` - Flag name shown in errors, without dashes
+///
+/// # Returns
+/// * `Result<Duration, Error>` - Timeout or `Error::InvalidInput`
+pub fn parse_timeout(value: &str, flag: &str) -> Result<Duration, Error> {
+    match value.trim().parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+        _ => Err(Error::InvalidInput(format!("Invalid --{} '{}': expected a positive number of seconds", flag, value))),
+    }
+}
+
+/// Landed transaction returned by `getTransaction`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchedTransaction {
+    /// Slot the transaction landed in
+    pub slot: u64,
+    /// Estimated production time of its block as a Unix timestamp, if known
+    pub block_time: Option<i64>,
+    /// The transaction as signed
+    pub transaction: VersionedTransaction,
+    /// Error of a transaction that failed on chain
+    pub error: Option<TransactionError>,
+    /// Accounts loaded from address lookup tables, writable ones first, then read-only ones
+    pub loaded_addresses: Vec<Pubkey>,
+    /// Balances in lamports before the transaction, in the order of `account_keys`
+    pub pre_balances: Vec<u64>,
+    /// Balances in lamports after the transaction, in the order of `account_keys`
+    pub post_balances: Vec<u64>,
+}
+
+impl FetchedTransaction {
+    /// Returns every account key of the transaction, in the order instruction account indexes refer to
+    pub fn account_keys(&self) -> Vec<Pubkey> {
+        let mut keys = self.transaction.message.static_account_keys().to_vec();
+        keys.extend(&self.loaded_addresses);
+        keys
+    }
+}
+
+static SETTINGS: OnceLock<RpcSettings> = OnceLock::new();
+
+/// Gossip node list with the endpoint and time it was fetched
+type GossipCache = Option<(String, Instant, Arc<HashSet<Pubkey>>)>;
+
+static GOSSIP_CACHE: Mutex<GossipCache> = Mutex::new(None);
+
+/// RPC methods used by the tool
+///
+/// Implemented by `RpcClient` for real endpoints and by `mock::MockRpc` for tests. Errors are
+/// returned as client errors, so callers add context with `Error::from_client_error`.
+#[async_trait]
+pub trait SolanaRpc: Send + Sync {
+    /// Endpoint URL, used to key the gossip cache
+    fn url(&self) -> String;
+
+    /// Balance of an account in lamports (0 if it does not exist)
+    async fn get_balance(&self, pubkey: &Pubkey) -> ClientResult<u64>;
+
+    /// Balance of an account as seen at `min_context_slot` or later, with the slot it was read at
+    ///
+    /// Fails with `JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED` while the node is behind that slot.
+    async fn get_balance_with_min_context_slot(&self, pubkey: &Pubkey, min_context_slot: u64) -> RpcResult<u64>;
+
+    /// Current slot of the node
+    async fn get_slot(&self) -> ClientResult<u64>;
+
+    /// Account and the slot it was read at (None if it does not exist)
+    async fn get_account_with_context(&self, pubkey: &Pubkey) -> RpcResult<Option<Account>>;
+
+    /// Up to `MAX_MULTIPLE_ACCOUNTS` accounts in one request, in the order of `pubkeys` (None for a missing account)
+    ///
+    /// The account data is left out (a zero-length data slice), so only lamports, owner and flags are
+    /// returned. With `min_context_slot` the read fails like `get_balance_with_min_context_slot` while
+    /// the node is behind that slot.
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey], min_context_slot: Option<u64>) -> RpcResult<Vec<Option<Account>>>;
+
+    /// Rent-exempt minimum for an account with `data_len` bytes of data
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64>;
+
+    /// Identity pubkeys of all nodes in gossip
+    async fn get_cluster_nodes(&self) -> ClientResult<Vec<Pubkey>>;
+
+    /// Identity pubkey of the node serving the requests
+    async fn get_identity(&self) -> ClientResult<Pubkey>;
+
+    /// Genesis hash of the cluster, which tells mainnet, testnet and devnet apart
+    async fn get_genesis_hash(&self) -> ClientResult<Hash>;
+
+    /// Software version of the node (`getVersion`)
+    async fn get_version(&self) -> ClientResult<String>;
+
+    /// Asks the cluster faucet for lamports (devnet, testnet and local test validators only)
+    async fn request_airdrop(&self, pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature>;
+
+    /// Current and delinquent vote accounts
+    async fn get_vote_accounts(&self) -> ClientResult<RpcVoteAccountStatus>;
+
+    /// Current epoch and slot
+    async fn get_epoch_info(&self) -> ClientResult<EpochInfo>;
+
+    /// Leader slots and blocks produced by a validator identity in a slot range (`getBlockProduction`)
+    ///
+    /// Nodes only serve the current and the previous epoch.
+    async fn get_block_production(&self, identity: &Pubkey, first_slot: u64, last_slot: u64) -> ClientResult<(u64, u64)>;
+
+    /// Inflation rewards credited to accounts for a completed epoch, in the order of `addresses` (`getInflationReward`)
+    ///
+    /// None for an account that earned nothing in that epoch.
+    async fn get_inflation_reward(&self, addresses: &[Pubkey], epoch: u64) -> ClientResult<Vec<Option<RpcInflationReward>>>;
+
+    /// Ok if the node is healthy and caught up with the cluster
+    async fn get_health(&self) -> ClientResult<()>;
+
+    /// Most recent blockhash
+    async fn get_latest_blockhash(&self) -> ClientResult<Hash>;
+
+    /// Fee of a message in lamports
+    async fn get_fee_for_message(&self, message: &Message) -> ClientResult<u64>;
+
+    /// Prioritization fees of recent slots for transactions writing the accounts, in micro-lamports per compute unit
+    async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ClientResult<Vec<u64>>;
+
+    /// Sends a signed transaction with preflight checks and up to 3 node retries
+    async fn send_transaction(&self, transaction: &Transaction) -> ClientResult<Signature>;
+
+    /// Sends a signed versioned transaction with the same preflight and retry settings as `send_transaction`
+    async fn send_versioned_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature>;
+
+    /// Sends a signed transaction and waits until it is confirmed
+    async fn send_and_confirm_transaction(&self, transaction: &Transaction) -> ClientResult<Signature>;
+
+    /// Status of a transaction at the client commitment (None if not seen or not yet at that commitment)
+    async fn get_signature_status(&self, signature: &Signature) -> ClientResult<Option<Result<(), TransactionError>>>;
+
+    /// Statuses of transactions, whatever their commitment
+    async fn get_signature_statuses(&self, signatures: &[Signature]) -> RpcResult<Vec<Option<TransactionStatus>>>;
+
+    /// Landed transaction at a commitment of `confirmed` or `finalized` (None if not found at that commitment)
+    async fn get_transaction(&self, signature: &Signature, commitment: CommitmentConfig) -> ClientResult<Option<FetchedTransaction>>;
+
+    /// Most recent transactions involving an address at the confirmed commitment, newest first
+    ///
+    /// With `before`, the page starts after that signature, so older history is read by passing
+    /// the oldest signature of the previous page.
+    async fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<Signature>,
+        limit: usize,
+    ) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>>;
+}
+
+#[async_trait]
+impl SolanaRpc for RpcClient {
+    fn url(&self) -> String {
+        RpcClient::url(self)
+    }
+
+    async fn get_balance(&self, pubkey: &Pubkey) -> ClientResult<u64> {
+        RpcClient::get_balance(self, pubkey).await
+    }
+
+    async fn get_balance_with_min_context_slot(&self, pubkey: &Pubkey, min_context_slot: u64) -> RpcResult<u64> {
+        let config = RpcContextConfig { commitment: Some(self.commitment()), min_context_slot: Some(min_context_slot) };
+        self.send(RpcRequest::GetBalance, serde_json::json!([pubkey.to_string(), config])).await
+    }
+
+    async fn get_slot(&self) -> ClientResult<u64> {
+        RpcClient::get_slot(self).await
+    }
+
+    async fn get_account_with_context(&self, pubkey: &Pubkey) -> RpcResult<Option<Account>> {
+        self.get_account_with_commitment(pubkey, self.commitment()).await
+    }
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey], min_context_slot: Option<u64>) -> RpcResult<Vec<Option<Account>>> {
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(UiDataSliceConfig { offset: 0, length: 0 }),
+            commitment: Some(self.commitment()),
+            min_context_slot,
+        };
+        self.get_multiple_accounts_with_config(pubkeys, config).await
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        RpcClient::get_minimum_balance_for_rent_exemption(self, data_len).await
+    }
+
+    async fn get_cluster_nodes(&self) -> ClientResult<Vec<Pubkey>> {
+        let nodes = RpcClient::get_cluster_nodes(self).await?;
+        Ok(nodes.iter().filter_map(|node| node.pubkey.parse().ok()).collect())
+    }
+
+    async fn get_identity(&self) -> ClientResult<Pubkey> {
+        RpcClient::get_identity(self).await
+    }
+
+    async fn get_genesis_hash(&self) -> ClientResult<Hash> {
+        RpcClient::get_genesis_hash(self).await
+    }
+
+    async fn get_version(&self) -> ClientResult<String> {
+        Ok(RpcClient::get_version(self).await?.solana_core)
+    }
+
+    async fn request_airdrop(&self, pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        RpcClient::request_airdrop(self, pubkey, lamports).await
+    }
+
+    async fn get_vote_accounts(&self) -> ClientResult<RpcVoteAccountStatus> {
+        RpcClient::get_vote_accounts(self).await
+    }
+
+    async fn get_epoch_info(&self) -> ClientResult<EpochInfo> {
+        RpcClient::get_epoch_info(self).await
+    }
+
+    async fn get_block_production(&self, identity: &Pubkey, first_slot: u64, last_slot: u64) -> ClientResult<(u64, u64)> {
+        let config = RpcBlockProductionConfig {
+            identity: Some(identity.to_string()),
+            range: Some(RpcBlockProductionConfigRange { first_slot, last_slot: Some(last_slot) }),
+            commitment: Some(self.commitment()),
+        };
+        let production = self.get_block_production_with_config(config).await?.value;
+        Ok(production
+            .by_identity
+            .get(&identity.to_string())
+            .map(|(leader_slots, blocks_produced)| (*leader_slots as u64, *blocks_produced as u64))
+            .unwrap_or_default())
+    }
+
+    async fn get_inflation_reward(&self, addresses: &[Pubkey], epoch: u64) -> ClientResult<Vec<Option<RpcInflationReward>>> {
+        RpcClient::get_inflation_reward(self, addresses, Some(epoch)).await
+    }
+
+    async fn get_health(&self) -> ClientResult<()> {
+        RpcClient::get_health(self).await
+    }
+
+    async fn get_latest_blockhash(&self) -> ClientResult<Hash> {
+        RpcClient::get_latest_blockhash(self).await
+    }
+
+    async fn get_fee_for_message(&self, message: &Message) -> ClientResult<u64> {
+        RpcClient::get_fee_for_message(self, message).await
+    }
+
+    async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ClientResult<Vec<u64>> {
+        let fees = RpcClient::get_recent_prioritization_fees(self, accounts).await?;
+        Ok(fees.iter().map(|fee| fee.prioritization_fee).collect())
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> ClientResult<Signature> {
+        let config = RpcSendTransactionConfig {
+            skip_preflight: false,
+            preflight_commitment: Some(self.commitment().commitment),
+            max_retries: Some(3),
+            ..RpcSendTransactionConfig::default()
+        };
+        self.send_transaction_with_config(transaction, config).await
+    }
+
+    async fn send_versioned_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        let config = RpcSendTransactionConfig {
+            skip_preflight: false,
+            preflight_commitment: Some(self.commitment().commitment),
+            max_retries: Some(3),
+            ..RpcSendTransactionConfig::default()
+        };
+        self.send_transaction_with_config(transaction, config).await
+    }
+
+    async fn send_and_confirm_transaction(&self, transaction: &Transaction) -> ClientResult<Signature> {
+        RpcClient::send_and_confirm_transaction(self, transaction).await
+    }
+
+    async fn get_signature_status(&self, signature: &Signature) -> ClientResult<Option<Result<(), TransactionError>>> {
+        RpcClient::get_signature_status(self, signature).await
+    }
+
+    async fn get_signature_statuses(&self, signatures: &[Signature]) -> RpcResult<Vec<Option<TransactionStatus>>> {
+        RpcClient::get_signature_statuses(self, signatures).await
+    }
+
+    async fn get_transaction(&self, signature: &Signature, commitment: CommitmentConfig) -> ClientResult<Option<FetchedTransaction>> {
+        let config = serde_json::json!({
+            "encoding": "base64",
+            "commitment": commitment.commitment,
+            "maxSupportedTransactionVersion": 0,
+        });
+        let fetched: Option<EncodedConfirmedTransactionWithStatusMeta> =
+            self.send(RpcRequest::GetTransaction, serde_json::json!([signature.to_string(), config])).await?;
+        let Some(fetched) = fetched else {
+            return Ok(None);
+        };
+        let transaction = fetched.transaction.transaction.decode().ok_or_else(|| {
+            ClientError::from(ClientErrorKind::Custom(format!("Transaction {} could not be decoded", signature)))
+        })?;
+        let meta = fetched.transaction.meta;
+        let loaded: Option<UiLoadedAddresses> = meta.as_ref().and_then(|meta| meta.loaded_addresses.clone().into());
+        let loaded_addresses = loaded
+            .map(|loaded| loaded.writable.into_iter().chain(loaded.readonly).filter_map(|key| key.parse().ok()).collect())
+            .unwrap_or_default();
+        let (pre_balances, post_balances) = meta
+            .as_ref()
+            .map(|meta| (meta.pre_balances.clone(), meta.post_balances.clone()))
+            .unwrap_or_default();
+        Ok(Some(FetchedTransaction {
+            slot: fetched.slot,
+            block_time: fetched.block_time,
+            transaction,
+            error: meta.and_then(|meta| meta.err).map(TransactionError::from),
+            loaded_addresses,
+            pre_balances,
+            post_balances,
+        }))
+    }
+
+    async fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<Signature>,
+        limit: usize,
+    ) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        // The method does not serve the processed commitment, whatever the client commitment is
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            limit: Some(limit),
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..GetConfirmedSignaturesForAddress2Config::default()
+        };
+        RpcClient::get_signatures_for_address_with_config(self, address, config).await
+    }
+}
+
+/// Sets the process-wide RPC settings
+///
+/// Must be called before the first RPC client is created; later calls are rejected.
+///
+/// # Arguments
+/// * `settings` - Settings to apply
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if the settings were applied
+pub fn configure(settings: RpcSettings) -> Result<(), Error> {
+    SETTINGS
+        .set(settings)
+        .map_err(|_| Error::InvalidInput("RPC settings are already configured".to_string()))
+}
+
+/// Returns the process-wide RPC settings (defaults if `configure` was not called)
+pub fn settings() -> &'static RpcSettings {
+    SETTINGS.get_or_init(RpcSettings::default)
+}
+
+/// Creates an RPC client that follows the process-wide settings
+///
+/// # Arguments
+/// * `rpc_url` - The RPC endpoint URL (optional, defaults to the configured endpoint or mainnet)
+///
+/// # Returns
+/// * `RpcClient` - Client for the endpoint
+pub fn rpc_client(rpc_url: Option<&str>) -> RpcClient {
+    let url = rpc_url.or(settings().url.as_deref()).unwrap_or(DEFAULT_RPC_URL);
+    rpc_client_with_commitment(url, settings().commitment)
+}
+
+/// Creates an RPC client for an endpoint and commitment, paced by the process-wide rate limit
+///
+/// # Arguments
+/// * `rpc_url` - The RPC endpoint URL
+/// * `commitment` - Commitment of reads, preflight simulation and confirmation waits
+///
+/// # Returns
+/// * `RpcClient` - Client for the endpoint
+pub fn rpc_client_with_commitment(rpc_url: &str, commitment: CommitmentConfig) -> RpcClient {
+    let config = RpcClientConfig::with_commitment(commitment);
+    let settings = settings();
+    let timeout = settings.timeout.unwrap_or(DEFAULT_RPC_TIMEOUT);
+    let sender = RateLimitedSender::new(rpc_url, settings.rate_limit, timeout, &settings.headers, settings.proxy.as_deref());
+    RpcClient::new_sender(sender, config)
+}
+
+/// Returns the identity pubkeys of all nodes in gossip, reusing a cached list within the gossip cache TTL
+///
+/// The list is looked up in memory first, then in the gossip cache file if one is configured.
+/// A freshly fetched list is written back to the file; failing to read or write it only logs a warning.
+/// With a Geyser endpoint configured the list comes from its gossip stream instead of `getClusterNodes`.
+///
+/// # Arguments
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Arc<HashSet<Pubkey>>, Error>` - Node identities or error
+pub async fn gossip_nodes(rpc: &dyn SolanaRpc) -> Result<Arc<HashSet<Pubkey>>, Error> {
+    let geyser_endpoint = settings().geyser_endpoint.as_deref();
+    let url = geyser_endpoint.map_or_else(|| rpc.url(), str::to_string);
+    let url = url.as_str();
+    let ttl = settings().gossip_cache_ttl;
+    let cache_file = settings().gossip_cache_file.as_deref();
+
+    if let Some(ttl) = ttl {
+        let mut cache = GOSSIP_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((cached_url, fetched_at, nodes)) = cache.as_ref()
+            && cached_url == url
+            && fetched_at.elapsed() < ttl
+        {
+            tracing::debug!(nodes = nodes.len(), "Using cached gossip node list");
+            return Ok(Arc::clone(nodes));
+        }
+
+        if let Some(path) = cache_file {
+            match GossipCacheFile::load(path) {
+                Ok(Some(file)) => {
+                    if let Some(age) = file.fresh_age(url, ttl, crate::unix_timestamp()) {
+                        let nodes = Arc::new(file.node_set());
+                        tracing::debug!(nodes = nodes.len(), age_secs = age.as_secs(), "Using gossip node list from cache file");
+                        let fetched_at = Instant::now().checked_sub(age).unwrap_or_else(Instant::now);
+                        *cache = Some((url.to_string(), fetched_at, Arc::clone(&nodes)));
+                        return Ok(nodes);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(error = %e, "Ignoring gossip cache file"),
+            }
+        }
+    }
+
+    tracing::debug!(rpc_url = url, "Fetching gossip node list");
+
+    let spinner = crate::progress::spinner(crate::i18n::text("progress-fetching-gossip"));
+    let cluster_nodes = match geyser_endpoint {
+        Some(endpoint) => crate::geyser::gossip_nodes(endpoint).await,
+        None => rpc.get_cluster_nodes().await.map_err(|e| Error::from_client_error("Failed to get cluster nodes", &e)),
+    };
+    spinner.finish_and_clear();
+    let cluster_nodes = cluster_nodes?;
+    let nodes: Arc<HashSet<Pubkey>> = Arc::new(cluster_nodes.into_iter().collect());
+
+    if ttl.is_some() {
+        *GOSSIP_CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some((url.to_string(), Instant::now(), Arc::clone(&nodes)));
+        if let Some(path) = cache_file
+            && let Err(e) = GossipCacheFile::new(url, crate::unix_timestamp(), &nodes).save(path)
+        {
+            tracing::warn!(error = %e, "Failed to write gossip cache file");
+        }
+    }
+    Ok(nodes)
+}
+
+/// Returns the identity pubkey of the node behind an RPC endpoint (`getIdentity`)
+///
+/// # Arguments
+/// * `rpc` - RPC client, usually for `LOCAL_RPC_URL` on the validator host
+///
+/// # Returns
+/// * `Result<Pubkey, Error>` - Node identity or error
+pub async fn node_identity(rpc: &dyn SolanaRpc) -> Result<Pubkey, Error> {
+    rpc.get_identity().await.map_err(|e| Error::from_client_error("Failed to get node identity", &e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nice_preset() {
+        let nice = RpcSettings::nice();
+        assert_eq!(nice.rate_limit, Some(RateLimit { requests_per_second: 1, burst: 1 }));
+        assert_eq!(nice.gossip_cache_ttl, Some(Duration::from_secs(600)));
+        assert_eq!(RpcSettings::default().rate_limit, None);
+        assert_eq!(nice.commitment, CommitmentConfig::finalized());
+    }
+
+    #[test]
+    fn test_parse_gossip_cache_ttl() {
+        assert_eq!(parse_gossip_cache_ttl("300").unwrap(), Some(Duration::from_secs(300)));
+        assert_eq!(parse_gossip_cache_ttl("0").unwrap(), None);
+        assert!(parse_gossip_cache_ttl("10m").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("5", "rpc-timeout").unwrap(), Duration::from_secs(5));
+        assert!(parse_timeout("0", "rpc-timeout").is_err());
+        let error = parse_timeout("30s", "deadline").unwrap_err();
+        assert!(error.is_user_error());
+        assert!(error.to_string().contains("Invalid --deadline '30s'"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_commitment() {
+        assert_eq!(parse_commitment("processed").unwrap(), CommitmentConfig::processed());
+        assert_eq!(parse_commitment("Confirmed").unwrap(), CommitmentConfig::confirmed());
+        assert_eq!(parse_commitment("finalized").unwrap(), CommitmentConfig::finalized());
+        assert!(parse_commitment("recent").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_rpc_client_uses_configured_commitment() {
+        // Settings are process-wide, so only the default is checked here
+        let client = rpc_client(Some("http://localhost:8899"));
+        assert_eq!(client.commitment(), settings().commitment);
+    }
+
+    #[tokio::test]
+    async fn test_node_identity() {
+        let identity = Pubkey::new_unique();
+        let rpc = mock::MockRpc::new().with_identity(identity);
+        assert_eq!(node_identity(&rpc).await.unwrap(), identity);
+
+        rpc.set_unavailable(true);
+        assert!(matches!(node_identity(&rpc).await, Err(Error::RpcUnavailable(_))));
+    }
+}
@@ -0,0 +1,118 @@
+use crate::config::write_atomic;
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Gossip node list saved between runs (`--gossip-cache-file`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GossipCacheFile {
+    /// Endpoint the list was fetched from
+    pub rpc_url: String,
+    /// Unix timestamp of the fetch
+    pub fetched_at: u64,
+    /// Identity pubkeys of the nodes in gossip
+    pub nodes: Vec<String>,
+}
+
+impl GossipCacheFile {
+    /// Creates a cache entry for a freshly fetched node list
+    ///
+    /// # Arguments
+    /// * `rpc_url` - Endpoint the list was fetched from
+    /// * `fetched_at` - Unix timestamp of the fetch
+    /// * `nodes` - Node identities
+    pub fn new(rpc_url: &str, fetched_at: u64, nodes: &HashSet<Pubkey>) -> Self {
+        let mut nodes: Vec<String> = nodes.iter().map(Pubkey::to_string).collect();
+        nodes.sort();
+        GossipCacheFile { rpc_url: rpc_url.to_string(), fetched_at, nodes }
+    }
+
+    /// Loads the cache file
+    ///
+    /// # Arguments
+    /// * `path` - Path to the cache file
+    ///
+    /// # Returns
+    /// * `Result<Option<GossipCacheFile>, Error>` - Cached list (None if the file does not exist) or `Error::Io`
+    pub fn load(path: &Path) -> Result<Option<Self>, Error> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::Io(format!("Failed to read gossip cache {}: {}", path.display(), e)))?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| Error::Io(format!("Failed to parse gossip cache {}: {}", path.display(), e)))
+    }
+
+    /// Saves the cache file atomically
+    ///
+    /// # Arguments
+    /// * `path` - Path to the cache file
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Ok if the file was written
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let contents = serde_json::to_string(self)
+            .map_err(|e| Error::Io(format!("Failed to serialize gossip cache: {}", e)))?;
+        write_atomic(path, contents.as_bytes())
+    }
+
+    /// Returns the age of the list if it was fetched from `rpc_url` less than `ttl` ago
+    ///
+    /// # Arguments
+    /// * `rpc_url` - Endpoint of the current run
+    /// * `ttl` - How long a list is reused
+    /// * `now` - Current Unix timestamp in seconds
+    ///
+    /// # Returns
+    /// * `Option<Duration>` - Age of a usable list, None if it is stale or from another endpoint
+    pub fn fresh_age(&self, rpc_url: &str, ttl: Duration, now: u64) -> Option<Duration> {
+        let age = Duration::from_secs(now.checked_sub(self.fetched_at)?);
+        (self.rpc_url == rpc_url && age < ttl).then_some(age)
+    }
+
+    /// Returns the node identities, skipping entries that are not valid pubkeys
+    pub fn node_set(&self) -> HashSet<Pubkey> {
+        self.nodes.iter().filter_map(|node| node.parse().ok()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://api.mainnet-beta.solana.com";
+
+    #[test]
+    fn test_fresh_age() {
+        let cache = GossipCacheFile { rpc_url: URL.to_string(), fetched_at: 1_000, nodes: Vec::new() };
+        let ttl = Duration::from_secs(600);
+
+        assert_eq!(cache.fresh_age(URL, ttl, 1_100), Some(Duration::from_secs(100)));
+        assert_eq!(cache.fresh_age(URL, ttl, 1_600), None);
+        assert_eq!(cache.fresh_age("http://localhost:8899", ttl, 1_100), None);
+        // A list from the future (clock moved back) is not trusted
+        assert_eq!(cache.fresh_age(URL, ttl, 900), None);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_gossip_{}.json", std::process::id()));
+        assert_eq!(GossipCacheFile::load(&path).unwrap(), None);
+
+        let nodes: HashSet<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let cache = GossipCacheFile::new(URL, 1_000, &nodes);
+        cache.save(&path).unwrap();
+        let loaded = GossipCacheFile::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.as_ref(), Some(&cache));
+        assert_eq!(loaded.unwrap().node_set(), nodes);
+    }
+}
@@ -0,0 +1,513 @@
+use super::{FetchedTransaction, SolanaRpc, MAX_MULTIPLE_ACCOUNTS};
+use async_trait::async_trait;
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_client::rpc_custom_error::JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED;
+use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
+use solana_client::rpc_response::{
+    Response, RpcConfirmedTransactionStatusWithSignature, RpcInflationReward, RpcResponseContext, RpcResult, RpcVoteAccountInfo, RpcVoteAccountStatus,
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::account::Account;
+use solana_sdk::epoch_info::EpochInfo;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{Transaction, TransactionError, VersionedTransaction};
+use solana_system_interface::instruction::SystemInstruction;
+use solana_transaction_status_client_types::{TransactionConfirmationStatus, TransactionStatus};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// Fee the mock charges per signature, as on mainnet
+pub const MOCK_FEE_PER_SIGNATURE: u64 = 5_000;
+
+/// State of the mock cluster
+#[derive(Debug, Default)]
+struct MockState {
+    accounts: HashMap<Pubkey, Account>,
+    cluster_nodes: Vec<Pubkey>,
+    identity: Pubkey,
+    genesis_hash: Hash,
+    version: String,
+    airdrops: u64,
+    multiple_accounts_requests: u64,
+    current_votes: Vec<RpcVoteAccountInfo>,
+    delinquent_votes: Vec<RpcVoteAccountInfo>,
+    block_production: HashMap<Pubkey, (u64, u64)>,
+    inflation_rewards: HashMap<(Pubkey, u64), RpcInflationReward>,
+    slot: u64,
+    epoch: u64,
+    blockhash: Hash,
+    prioritization_fees: Vec<u64>,
+    unhealthy: bool,
+    unavailable: bool,
+    sent: Vec<Transaction>,
+    sent_versioned: Vec<VersionedTransaction>,
+    statuses: HashMap<Signature, (u64, Result<(), TransactionError>)>,
+    landed: HashMap<Signature, FetchedTransaction>,
+    unfinalized: HashSet<Signature>,
+}
+
+/// In-memory `SolanaRpc` for tests: accounts, gossip and vote accounts are set up front,
+/// and sent transactions are recorded (and reported as landed) instead of executed
+#[derive(Debug, Default)]
+pub struct MockRpc {
+    state: Mutex<MockState>,
+}
+
+impl MockRpc {
+    /// Creates an empty healthy cluster at slot 1000 of epoch 500
+    pub fn new() -> Self {
+        let rpc = MockRpc::default();
+        {
+            let mut state = rpc.state();
+            state.slot = 1_000;
+            state.epoch = 500;
+            state.blockhash = Hash::new_unique();
+            state.identity = Pubkey::new_unique();
+            state.version = "3.0.0".to_string();
+        }
+        rpc
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Adds or replaces an account
+    pub fn with_account(self, pubkey: Pubkey, account: Account) -> Self {
+        self.state().accounts.insert(pubkey, account);
+        self
+    }
+
+    /// Adds a system-owned wallet with a balance
+    pub fn with_wallet(self, pubkey: Pubkey, lamports: u64) -> Self {
+        self.with_account(pubkey, Account::new(lamports, 0, &solana_system_interface::program::ID))
+    }
+
+    /// Adds a node identity to gossip
+    pub fn with_gossip_node(self, identity: Pubkey) -> Self {
+        self.state().cluster_nodes.push(identity);
+        self
+    }
+
+    /// Sets the identity of the node answering `getIdentity`
+    pub fn with_identity(self, identity: Pubkey) -> Self {
+        self.state().identity = identity;
+        self
+    }
+
+    /// Sets the genesis hash of the cluster (a devnet-like default hash otherwise)
+    pub fn with_genesis_hash(self, genesis_hash: Hash) -> Self {
+        self.state().genesis_hash = genesis_hash;
+        self
+    }
+
+    /// Sets the software version reported by `getVersion` (3.0.0 by default)
+    pub fn with_version(self, version: &str) -> Self {
+        self.state().version = version.to_string();
+        self
+    }
+
+    /// Adds a vote account for a validator identity
+    ///
+    /// # Arguments
+    /// * `identity` - Validator identity
+    /// * `last_vote` - Most recent slot voted on
+    /// * `activated_stake` - Activated stake in lamports
+    /// * `delinquent` - List it as delinquent
+    pub fn with_vote_account(self, identity: Pubkey, last_vote: u64, activated_stake: u64, delinquent: bool) -> Self {
+        {
+            let mut state = self.state();
+            let info = RpcVoteAccountInfo {
+                vote_pubkey: Pubkey::new_unique().to_string(),
+                node_pubkey: identity.to_string(),
+                activated_stake,
+                commission: 10,
+                epoch_vote_account: true,
+                epoch_credits: Vec::new(),
+                last_vote,
+                root_slot: last_vote.saturating_sub(32),
+            };
+            if delinquent {
+                state.delinquent_votes.push(info);
+            } else {
+                state.current_votes.push(info);
+            }
+        }
+        self
+    }
+
+    /// Sets the leader slots and blocks produced by a validator identity, whatever the slot range
+    pub fn with_block_production(self, identity: Pubkey, leader_slots: u64, blocks_produced: u64) -> Self {
+        self.state().block_production.insert(identity, (leader_slots, blocks_produced));
+        self
+    }
+
+    /// Sets the inflation reward credited to an account for an epoch, with a 10% commission
+    pub fn with_inflation_reward(self, address: Pubkey, epoch: u64, amount: u64) -> Self {
+        let reward = RpcInflationReward { epoch, effective_slot: (epoch + 1) * 432_000, amount, post_balance: amount, commission: Some(10) };
+        self.state().inflation_rewards.insert((address, epoch), reward);
+        self
+    }
+
+    /// Sets the prioritization fees of recent slots, in micro-lamports per compute unit
+    pub fn with_prioritization_fees(self, fees: Vec<u64>) -> Self {
+        self.state().prioritization_fees = fees;
+        self
+    }
+
+    /// Moves the node to another slot
+    pub fn set_slot(&self, slot: u64) {
+        self.state().slot = slot;
+    }
+
+    /// Moves the cluster to another epoch
+    pub fn set_epoch(&self, epoch: u64) {
+        self.state().epoch = epoch;
+    }
+
+    /// Makes `getHealth` report the node as behind
+    pub fn unhealthy(self) -> Self {
+        self.state().unhealthy = true;
+        self
+    }
+
+    /// Makes every request fail as if the endpoint were unreachable
+    pub fn set_unavailable(&self, unavailable: bool) {
+        self.state().unavailable = unavailable;
+    }
+
+    /// Adds a transaction that landed earlier, as returned by `getTransaction`
+    ///
+    /// # Arguments
+    /// * `transaction` - Landed transaction
+    /// * `finalized` - Whether it is found at the finalized commitment (only at confirmed otherwise)
+    pub fn with_landed_transaction(self, transaction: FetchedTransaction, finalized: bool) -> Self {
+        {
+            let mut state = self.state();
+            let signature = transaction.transaction.signatures[0];
+            let result = transaction.error.clone().map_or(Ok(()), Err);
+            state.statuses.insert(signature, (transaction.slot, result));
+            if !finalized {
+                state.unfinalized.insert(signature);
+            }
+            state.landed.insert(signature, transaction);
+        }
+        self
+    }
+
+    /// Returns the transactions sent so far
+    pub fn sent_transactions(&self) -> Vec<Transaction> {
+        self.state().sent.clone()
+    }
+
+    /// Returns the versioned transactions sent so far
+    pub fn sent_versioned_transactions(&self) -> Vec<VersionedTransaction> {
+        self.state().sent_versioned.clone()
+    }
+
+    /// Returns the number of `getMultipleAccounts` requests answered so far
+    pub fn multiple_accounts_requests(&self) -> u64 {
+        self.state().multiple_accounts_requests
+    }
+
+    fn check_available(&self) -> ClientResult<()> {
+        if self.state().unavailable {
+            return Err(ClientError::from(ClientErrorKind::Io(std::io::Error::new(
+                std::io::ErrorKind::ConnectionRefused,
+                "mock RPC is unavailable",
+            ))));
+        }
+        Ok(())
+    }
+
+    fn check_min_context_slot(&self, min_context_slot: Option<u64>) -> ClientResult<()> {
+        if min_context_slot.is_some_and(|min_context_slot| self.state().slot < min_context_slot) {
+            return Err(ClientError::from(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                code: JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED,
+                message: "Minimum context slot has not been reached".to_string(),
+                data: RpcResponseErrorData::Empty,
+            })));
+        }
+        Ok(())
+    }
+
+    fn context(&self) -> RpcResponseContext {
+        RpcResponseContext { slot: self.state().slot, api_version: None }
+    }
+}
+
+#[async_trait]
+impl SolanaRpc for MockRpc {
+    fn url(&self) -> String {
+        "mock://".to_string()
+    }
+
+    async fn get_balance(&self, pubkey: &Pubkey) -> ClientResult<u64> {
+        self.check_available()?;
+        Ok(self.state().accounts.get(pubkey).map(|account| account.lamports).unwrap_or(0))
+    }
+
+    async fn get_balance_with_min_context_slot(&self, pubkey: &Pubkey, min_context_slot: u64) -> RpcResult<u64> {
+        self.check_available()?;
+        self.check_min_context_slot(Some(min_context_slot))?;
+        let value = self.get_balance(pubkey).await?;
+        Ok(Response { context: self.context(), value })
+    }
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey], min_context_slot: Option<u64>) -> RpcResult<Vec<Option<Account>>> {
+        self.check_available()?;
+        if pubkeys.len() > MAX_MULTIPLE_ACCOUNTS {
+            return Err(ClientError::from(ClientErrorKind::Custom(format!(
+                "Too many inputs provided; max {}",
+                MAX_MULTIPLE_ACCOUNTS
+            ))));
+        }
+        self.check_min_context_slot(min_context_slot)?;
+        let mut state = self.state();
+        state.multiple_accounts_requests += 1;
+        let value = pubkeys
+            .iter()
+            .map(|pubkey| state.accounts.get(pubkey).map(|account| Account { data: Vec::new(), ..account.clone() }))
+            .collect();
+        drop(state);
+        Ok(Response { context: self.context(), value })
+    }
+
+    async fn get_slot(&self) -> ClientResult<u64> {
+        self.check_available()?;
+        Ok(self.state().slot)
+    }
+
+    async fn get_account_with_context(&self, pubkey: &Pubkey) -> RpcResult<Option<Account>> {
+        self.check_available()?;
+        let value = self.state().accounts.get(pubkey).cloned();
+        Ok(Response { context: self.context(), value })
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        self.check_available()?;
+        Ok(Rent::default().minimum_balance(data_len))
+    }
+
+    async fn get_cluster_nodes(&self) -> ClientResult<Vec<Pubkey>> {
+        self.check_available()?;
+        Ok(self.state().cluster_nodes.clone())
+    }
+
+    async fn get_identity(&self) -> ClientResult<Pubkey> {
+        self.check_available()?;
+        Ok(self.state().identity)
+    }
+
+    async fn get_genesis_hash(&self) -> ClientResult<Hash> {
+        self.check_available()?;
+        Ok(self.state().genesis_hash)
+    }
+
+    async fn get_version(&self) -> ClientResult<String> {
+        self.check_available()?;
+        Ok(self.state().version.clone())
+    }
+
+    async fn request_airdrop(&self, pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        self.check_available()?;
+        let mut state = self.state();
+        // Each airdrop gets its own signature, derived from a counter
+        state.airdrops += 1;
+        let mut bytes = [0u8; 64];
+        bytes[..8].copy_from_slice(&state.airdrops.to_le_bytes());
+        let signature = Signature::from(bytes);
+        let slot = state.slot;
+        state
+            .accounts
+            .entry(*pubkey)
+            .or_insert_with(|| Account::new(0, 0, &solana_system_interface::program::ID))
+            .lamports += lamports;
+        state.statuses.insert(signature, (slot, Ok(())));
+        Ok(signature)
+    }
+
+    async fn get_vote_accounts(&self) -> ClientResult<RpcVoteAccountStatus> {
+        self.check_available()?;
+        let state = self.state();
+        Ok(RpcVoteAccountStatus { current: state.current_votes.clone(), delinquent: state.delinquent_votes.clone() })
+    }
+
+    async fn get_epoch_info(&self) -> ClientResult<EpochInfo> {
+        self.check_available()?;
+        let state = self.state();
+        Ok(EpochInfo {
+            epoch: state.epoch,
+            slot_index: 0,
+            slots_in_epoch: 432_000,
+            absolute_slot: state.slot,
+            block_height: state.slot,
+            transaction_count: None,
+        })
+    }
+
+    async fn get_block_production(&self, identity: &Pubkey, _first_slot: u64, _last_slot: u64) -> ClientResult<(u64, u64)> {
+        self.check_available()?;
+        Ok(self.state().block_production.get(identity).copied().unwrap_or_default())
+    }
+
+    async fn get_inflation_reward(&self, addresses: &[Pubkey], epoch: u64) -> ClientResult<Vec<Option<RpcInflationReward>>> {
+        self.check_available()?;
+        let state = self.state();
+        Ok(addresses.iter().map(|address| state.inflation_rewards.get(&(*address, epoch)).cloned()).collect())
+    }
+
+    async fn get_health(&self) -> ClientResult<()> {
+        self.check_available()?;
+        if self.state().unhealthy {
+            return Err(ClientError::from(ClientErrorKind::Custom("Node is behind by 150 slots".to_string())));
+        }
+        Ok(())
+    }
+
+    async fn get_latest_blockhash(&self) -> ClientResult<Hash> {
+        self.check_available()?;
+        Ok(self.state().blockhash)
+    }
+
+    async fn get_fee_for_message(&self, message: &Message) -> ClientResult<u64> {
+        self.check_available()?;
+        Ok(MOCK_FEE_PER_SIGNATURE * message.header.num_required_signatures as u64)
+    }
+
+    async fn get_recent_prioritization_fees(&self, _accounts: &[Pubkey]) -> ClientResult<Vec<u64>> {
+        self.check_available()?;
+        Ok(self.state().prioritization_fees.clone())
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> ClientResult<Signature> {
+        self.check_available()?;
+        transaction
+            .verify()
+            .map_err(|e| ClientError::from(ClientErrorKind::TransactionError(e)))?;
+        let signature = transaction.signatures[0];
+        let mut state = self.state();
+        let slot = state.slot;
+        state.sent.push(transaction.clone());
+        state.statuses.insert(signature, (slot, Ok(())));
+        let landed = landed(&state, transaction.clone().into(), slot);
+        state.landed.insert(signature, landed);
+        Ok(signature)
+    }
+
+    async fn send_versioned_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        self.check_available()?;
+        if !transaction.verify_with_results().iter().all(|verified| *verified) {
+            return Err(ClientError::from(ClientErrorKind::TransactionError(TransactionError::SignatureFailure)));
+        }
+        let signature = transaction.signatures[0];
+        let mut state = self.state();
+        let slot = state.slot;
+        state.sent_versioned.push(transaction.clone());
+        state.statuses.insert(signature, (slot, Ok(())));
+        let landed = landed(&state, transaction.clone(), slot);
+        state.landed.insert(signature, landed);
+        Ok(signature)
+    }
+
+    async fn send_and_confirm_transaction(&self, transaction: &Transaction) -> ClientResult<Signature> {
+        self.send_transaction(transaction).await
+    }
+
+    async fn get_signature_status(&self, signature: &Signature) -> ClientResult<Option<Result<(), TransactionError>>> {
+        self.check_available()?;
+        Ok(self.state().statuses.get(signature).map(|(_, result)| result.clone()))
+    }
+
+    async fn get_signature_statuses(&self, signatures: &[Signature]) -> RpcResult<Vec<Option<TransactionStatus>>> {
+        self.check_available()?;
+        let value = {
+            let state = self.state();
+            signatures
+                .iter()
+                .map(|signature| {
+                    state.statuses.get(signature).map(|(slot, result)| TransactionStatus {
+                        slot: *slot,
+                        confirmations: None,
+                        status: result.clone(),
+                        err: result.clone().err(),
+                        confirmation_status: None,
+                    })
+                })
+                .collect()
+        };
+        Ok(Response { context: self.context(), value })
+    }
+
+    async fn get_transaction(&self, signature: &Signature, commitment: CommitmentConfig) -> ClientResult<Option<FetchedTransaction>> {
+        self.check_available()?;
+        let state = self.state();
+        if commitment.is_finalized() && state.unfinalized.contains(signature) {
+            return Ok(None);
+        }
+        Ok(state.landed.get(signature).cloned())
+    }
+
+    async fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<Signature>,
+        limit: usize,
+    ) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        self.check_available()?;
+        let state = self.state();
+        let mut found: Vec<RpcConfirmedTransactionStatusWithSignature> = state
+            .landed
+            .iter()
+            .filter(|(_, landed)| landed.account_keys().contains(address))
+            .map(|(signature, landed)| RpcConfirmedTransactionStatusWithSignature {
+                signature: signature.to_string(),
+                slot: landed.slot,
+                err: landed.error.clone().map(Into::into),
+                memo: None,
+                block_time: landed.block_time,
+                confirmation_status: Some(if state.unfinalized.contains(signature) {
+                    TransactionConfirmationStatus::Confirmed
+                } else {
+                    TransactionConfirmationStatus::Finalized
+                }),
+            })
+            .collect();
+        found.sort_by(|a, b| b.slot.cmp(&a.slot).then_with(|| a.signature.cmp(&b.signature)));
+        if let Some(before) = before {
+            let before = before.to_string();
+            let start = found.iter().position(|status| status.signature == before).map_or(found.len(), |index| index + 1);
+            found.drain(..start);
+        }
+        found.truncate(limit);
+        Ok(found)
+    }
+}
+
+/// Successful transaction landed at a slot, without lookup table accounts
+///
+/// The mock does not execute transactions, so the balances after it only apply its system transfers.
+fn landed(state: &MockState, transaction: VersionedTransaction, slot: u64) -> FetchedTransaction {
+    let keys = transaction.message.static_account_keys().to_vec();
+    let pre_balances: Vec<u64> = keys.iter().map(|key| state.accounts.get(key).map_or(0, |account| account.lamports)).collect();
+    let mut post_balances = pre_balances.clone();
+    for instruction in transaction.message.instructions() {
+        if keys.get(instruction.program_id_index as usize) != Some(&solana_system_interface::program::ID) {
+            continue;
+        }
+        // Accounts loaded from lookup tables are past the static keys and keep no balance here
+        if let (Ok(SystemInstruction::Transfer { lamports }), [from, to, ..]) =
+            (bincode::deserialize(&instruction.data), instruction.accounts.as_slice())
+            && usize::from(*from) < keys.len()
+            && usize::from(*to) < keys.len()
+        {
+            post_balances[*from as usize] = post_balances[*from as usize].saturating_sub(lamports);
+            post_balances[*to as usize] += lamports;
+        }
+    }
+    FetchedTransaction { slot, block_time: None, transaction, error: None, loaded_addresses: Vec::new(), pre_balances, post_balances }
+}
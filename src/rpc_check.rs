@@ -0,0 +1,151 @@
+use crate::rpc::SolanaRpc;
+use crate::Verdict;
+use serde::Serialize;
+use std::time::Instant;
+
+/// Slots an endpoint may be behind the reference before it fails `rpc-check` (about a minute)
+pub const DEFAULT_MAX_SLOT_LAG: u64 = 150;
+
+/// Result of probing one RPC endpoint (`rpc-check`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EndpointCheck {
+    /// Endpoint URL
+    pub url: String,
+    /// Round trip of `getSlot` in milliseconds (None if it failed)
+    pub latency_ms: Option<u64>,
+    /// Slot reported by the node
+    pub slot: Option<u64>,
+    /// Software version of the node
+    pub version: Option<String>,
+    /// Slots behind the reference endpoint (None until `evaluate_endpoints` knows a reference slot)
+    pub slot_lag: Option<u64>,
+    /// Nodes returned by `getClusterNodes`
+    pub cluster_nodes: Option<usize>,
+    /// Whether every probe succeeded and the endpoint is within the allowed lag
+    pub passed: bool,
+    /// Reasons the endpoint failed
+    pub failures: Vec<String>,
+}
+
+impl EndpointCheck {
+    /// Returns PASS or FAIL
+    pub fn verdict(&self) -> Verdict {
+        if self.passed { Verdict::Pass } else { Verdict::Fail }
+    }
+}
+
+/// Probes an endpoint for latency, slot, version and a working `getClusterNodes`
+///
+/// Failed probes are recorded in `failures` rather than returned as errors, so one dead endpoint
+/// does not stop the others from being checked. The lag is filled in by `evaluate_endpoints`.
+///
+/// # Arguments
+/// * `rpc` - Client of the endpoint
+///
+/// # Returns
+/// * `EndpointCheck` - Probe results, passed if no probe failed
+pub async fn probe_endpoint(rpc: &dyn SolanaRpc) -> EndpointCheck {
+    let mut failures = Vec::new();
+    let started = Instant::now();
+    let slot = match rpc.get_slot().await {
+        Ok(slot) => Some(slot),
+        Err(e) => {
+            failures.push(format!("getSlot failed: {}", e));
+            None
+        }
+    };
+    let latency_ms = slot.map(|_| started.elapsed().as_millis() as u64);
+    let version = match rpc.get_version().await {
+        Ok(version) => Some(version),
+        Err(e) => {
+            failures.push(format!("getVersion failed: {}", e));
+            None
+        }
+    };
+    let cluster_nodes = match rpc.get_cluster_nodes().await {
+        Ok(nodes) if nodes.is_empty() => {
+            failures.push("getClusterNodes returned no nodes".to_string());
+            Some(0)
+        }
+        Ok(nodes) => Some(nodes.len()),
+        Err(e) => {
+            failures.push(format!("getClusterNodes failed: {}", e));
+            None
+        }
+    };
+    EndpointCheck {
+        url: rpc.url(),
+        latency_ms,
+        slot,
+        version,
+        slot_lag: None,
+        cluster_nodes,
+        passed: failures.is_empty(),
+        failures,
+    }
+}
+
+/// Compares the probed endpoints with a reference slot and fails those too far behind
+///
+/// # Arguments
+/// * `checks` - Probed endpoints
+/// * `reference_slot` - Slot of the reference endpoint (None for the highest slot among `checks`)
+/// * `max_slot_lag` - Slots an endpoint may be behind the reference
+///
+/// # Returns
+/// * `Option<u64>` - Reference slot used, or None if no endpoint reported a slot
+pub fn evaluate_endpoints(checks: &mut [EndpointCheck], reference_slot: Option<u64>, max_slot_lag: u64) -> Option<u64> {
+    let reference_slot = reference_slot.or_else(|| checks.iter().filter_map(|check| check.slot).max())?;
+    for check in checks.iter_mut() {
+        let Some(slot) = check.slot else { continue };
+        let lag = reference_slot.saturating_sub(slot);
+        check.slot_lag = Some(lag);
+        if lag > max_slot_lag {
+            check.failures.push(format!("{} slots behind the reference (at most {} allowed)", lag, max_slot_lag));
+            check.passed = false;
+        }
+    }
+    Some(reference_slot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[tokio::test]
+    async fn test_probe_and_evaluate_endpoints() {
+        let healthy = MockRpc::new().with_gossip_node(Pubkey::new_unique()).with_version("2.3.1");
+        let behind = MockRpc::new().with_gossip_node(Pubkey::new_unique());
+        behind.set_slot(700);
+        let no_gossip = MockRpc::new();
+        let down = MockRpc::new();
+        down.set_unavailable(true);
+
+        let mut checks = vec![
+            probe_endpoint(&healthy).await,
+            probe_endpoint(&behind).await,
+            probe_endpoint(&no_gossip).await,
+            probe_endpoint(&down).await,
+        ];
+        assert_eq!((checks[0].slot, checks[0].version.as_deref(), checks[0].cluster_nodes), (Some(1_000), Some("2.3.1"), Some(1)));
+        assert!(checks[0].latency_ms.is_some());
+        assert!(!checks[2].passed);
+        assert_eq!(checks[3].failures.len(), 3);
+        assert_eq!((checks[3].slot, checks[3].latency_ms), (None, None));
+
+        // Without a reference endpoint the highest slot is the reference
+        assert_eq!(evaluate_endpoints(&mut checks, None, DEFAULT_MAX_SLOT_LAG), Some(1_000));
+        assert_eq!(checks[0].verdict(), Verdict::Pass);
+        assert_eq!((checks[1].slot_lag, checks[1].verdict()), (Some(300), Verdict::Fail));
+        assert_eq!(checks[3].slot_lag, None);
+
+        // A reference ahead of every endpoint fails them all once the lag is too large
+        let mut checks = vec![probe_endpoint(&healthy).await];
+        evaluate_endpoints(&mut checks, Some(1_100), 100);
+        assert!(checks[0].passed);
+        evaluate_endpoints(&mut checks, Some(1_101), 100);
+        assert!(!checks[0].passed);
+    }
+}
@@ -0,0 +1,388 @@
+use crate::amount::parse_amount;
+use crate::Error;
+use serde::de::{self, Deserializer};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Operation of a script step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StepOp {
+    /// Gossip, vote account and health verdict of a validator; a FAIL verdict fails the step
+    ValidatorStatus,
+    /// PDA balance of a validator
+    PdaBalance,
+    /// Funding of a validator PDA
+    PdaFundAddress,
+    /// Message to the configured `[notify]` channel
+    Notify,
+}
+
+impl fmt::Display for StepOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StepOp::ValidatorStatus => "validator-status",
+            StepOp::PdaBalance => "pda-balance",
+            StepOp::PdaFundAddress => "pda-fund-address",
+            StepOp::Notify => "notify",
+        })
+    }
+}
+
+/// Comparison of a step result field with a literal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Condition on the result of an earlier step, e.g. `balance.balance_sol < 5` or `!fund.ok`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    /// Id of the step whose result is tested
+    pub step: String,
+    /// Field of the step result
+    pub field: String,
+    /// True for `!step.field`
+    pub negated: bool,
+    /// Comparison and literal; None tests the field for truthiness
+    pub comparison: Option<(Comparison, String)>,
+}
+
+impl Condition {
+    /// Parses `<step>.<field>`, `!<step>.<field>` or `<step>.<field> <op> <literal>` with op `== != < <= > >=`
+    ///
+    /// # Arguments
+    /// * `value` - Condition text
+    ///
+    /// # Returns
+    /// * `Result<Condition, Error>` - Condition or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        let invalid = || {
+            Error::InvalidInput(format!(
+                "Invalid condition '{}': expected <step>.<field>, !<step>.<field> or <step>.<field> <op> <value>",
+                value
+            ))
+        };
+        let tokens: Vec<&str> = value.split_whitespace().collect();
+        let (reference, negated, comparison) = match tokens.as_slice() {
+            [reference] => match reference.strip_prefix('!') {
+                Some(reference) => (reference, true, None),
+                None => (*reference, false, None),
+            },
+            [reference, op, literal] => {
+                let op = match *op {
+                    "==" => Comparison::Eq,
+                    "!=" => Comparison::Ne,
+                    "<" => Comparison::Lt,
+                    "<=" => Comparison::Le,
+                    ">" => Comparison::Gt,
+                    ">=" => Comparison::Ge,
+                    _ => return Err(invalid()),
+                };
+                (*reference, false, Some((op, literal.trim_matches(|c| c == '"' || c == '\'').to_string())))
+            }
+            _ => return Err(invalid()),
+        };
+        let (step, field) = reference.split_once('.').ok_or_else(invalid)?;
+        if !is_identifier(step) || !is_identifier(field) {
+            return Err(invalid());
+        }
+        Ok(Condition { step: step.to_string(), field: field.to_string(), negated, comparison })
+    }
+
+    /// Evaluates the condition against the results of the steps run so far
+    ///
+    /// A missing step or field makes the condition false (and its negation true). Numbers are
+    /// compared numerically; other values compare as case-insensitive text with `==` and `!=` only.
+    ///
+    /// # Arguments
+    /// * `results` - Result objects by step id
+    ///
+    /// # Returns
+    /// * `bool` - True if the condition holds
+    pub fn holds(&self, results: &HashMap<String, serde_json::Value>) -> bool {
+        let value = results.get(&self.step).and_then(|result| result.get(&self.field));
+        let Some((op, literal)) = &self.comparison else {
+            return value.is_some_and(is_truthy) != self.negated;
+        };
+        let Some(value) = value.filter(|value| !value.is_null()) else {
+            return false;
+        };
+
+        let ordering = match (value.as_f64(), literal.parse::<f64>()) {
+            (Some(value), Ok(literal)) => value.partial_cmp(&literal),
+            _ => {
+                let text = match value {
+                    serde_json::Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                let equal = text.eq_ignore_ascii_case(literal);
+                return match op {
+                    Comparison::Eq => equal,
+                    Comparison::Ne => !equal,
+                    _ => false,
+                };
+            }
+        };
+        let Some(ordering) = ordering else {
+            return false;
+        };
+        match op {
+            Comparison::Eq => ordering.is_eq(),
+            Comparison::Ne => ordering.is_ne(),
+            Comparison::Lt => ordering.is_lt(),
+            Comparison::Le => ordering.is_le(),
+            Comparison::Gt => ordering.is_gt(),
+            Comparison::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let negation = if self.negated { "!" } else { "" };
+        write!(f, "{}{}.{}", negation, self.step, self.field)?;
+        if let Some((op, literal)) = &self.comparison {
+            let op = match op {
+                Comparison::Eq => "==",
+                Comparison::Ne => "!=",
+                Comparison::Lt => "<",
+                Comparison::Le => "<=",
+                Comparison::Gt => ">",
+                Comparison::Ge => ">=",
+            };
+            write!(f, " {} {}", op, literal)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns true for `true`, non-zero numbers and non-empty text
+fn is_truthy(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Bool(value) => *value,
+        serde_json::Value::Number(number) => number.as_f64().is_some_and(|number| number != 0.0),
+        serde_json::Value::String(text) => !text.is_empty(),
+        serde_json::Value::Null => false,
+        _ => true,
+    }
+}
+
+/// Returns true for names of letters, digits, `_` and `-`
+fn is_identifier(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// One step of a script
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    /// Name later conditions refer to
+    pub id: Option<String>,
+    /// Operation to run
+    pub op: StepOp,
+    /// Validator pubkey or alias (all operations except `notify`)
+    pub validator: Option<String>,
+    /// Amount to transfer (`pda-fund-address`), as for the command line
+    #[serde(default, deserialize_with = "scalar_text")]
+    pub amount: Option<String>,
+    /// Funding wallet (`pda-fund-address`, default: the configured wallet)
+    pub keypair: Option<String>,
+    /// Text to send (`notify`)
+    pub message: Option<String>,
+    /// Conditions that must all hold for the step to run
+    #[serde(default, deserialize_with = "conditions")]
+    pub when: Vec<Condition>,
+    /// Go on with the next step if this one fails
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+impl Step {
+    /// Returns true if every condition holds for the results so far
+    pub fn should_run(&self, results: &HashMap<String, serde_json::Value>) -> bool {
+        self.when.iter().all(|condition| condition.holds(results))
+    }
+}
+
+/// Sequence of operations run by `run <script.yaml>`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Script {
+    /// Name shown in the output and the JSON report
+    pub name: Option<String>,
+    /// Steps in execution order
+    pub steps: Vec<Step>,
+}
+
+impl Script {
+    /// Parses and checks a script
+    ///
+    /// Every step must have the fields of its operation and no others, ids must be unique and
+    /// conditions may only refer to earlier steps, so mistakes surface before anything runs.
+    ///
+    /// # Arguments
+    /// * `yaml` - Script text
+    ///
+    /// # Returns
+    /// * `Result<Script, Error>` - Script or `Error::InvalidInput`
+    pub fn parse(yaml: &str) -> Result<Self, Error> {
+        let script: Script = serde_yaml::from_str(yaml).map_err(|e| Error::InvalidInput(format!("Invalid script: {}", e)))?;
+        if script.steps.is_empty() {
+            return Err(Error::InvalidInput("Invalid script: no steps".to_string()));
+        }
+
+        let mut ids = HashSet::new();
+        for (index, step) in script.steps.iter().enumerate() {
+            let invalid = |message: String| Error::InvalidInput(format!("Invalid script step {} ({}): {}", index + 1, step.op, message));
+            for condition in &step.when {
+                if !ids.contains(&condition.step) {
+                    return Err(invalid(format!("condition '{}' does not refer to an earlier step", condition)));
+                }
+            }
+            if let Some(id) = &step.id {
+                if !is_identifier(id) {
+                    return Err(invalid(format!("id '{}' may only contain letters, digits, '_' and '-'", id)));
+                }
+                if !ids.insert(id.clone()) {
+                    return Err(invalid(format!("duplicate id '{}'", id)));
+                }
+            }
+
+            let funding = step.op == StepOp::PdaFundAddress;
+            let notify = step.op == StepOp::Notify;
+            // Field, whether it is given, and whether the operation requires and accepts it
+            for (field, present, required, accepted) in [
+                ("validator", step.validator.is_some(), !notify, !notify),
+                ("amount", step.amount.is_some(), funding, funding),
+                ("keypair", step.keypair.is_some(), false, funding),
+                ("message", step.message.is_some(), notify, notify),
+            ] {
+                if required && !present {
+                    return Err(invalid(format!("missing {}", field)));
+                }
+                if present && !accepted {
+                    return Err(invalid(format!("{} is not used by {}", field, step.op)));
+                }
+            }
+            if let Some(amount) = &step.amount {
+                parse_amount(amount).map_err(|e| invalid(e.to_string()))?;
+            }
+        }
+        Ok(script)
+    }
+}
+
+/// Reads a YAML scalar (text or number) as text
+fn scalar_text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Scalar {
+        Text(String),
+        Integer(u64),
+        Number(f64),
+    }
+    Ok(Option::<Scalar>::deserialize(deserializer)?.map(|scalar| match scalar {
+        Scalar::Text(text) => text,
+        Scalar::Integer(value) => value.to_string(),
+        Scalar::Number(value) => value.to_string(),
+    }))
+}
+
+/// Reads one condition or a list of conditions
+fn conditions<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Condition>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    let texts = match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(text) => vec![text],
+        OneOrMany::Many(texts) => texts,
+    };
+    texts.iter().map(|text| Condition::parse(text).map_err(de::Error::custom)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYBOOK: &str = r#"
+name: Weekly top-up
+steps:
+  - id: status
+    op: validator-status
+    validator: mainnet-1
+  - id: balance
+    op: pda-balance
+    validator: mainnet-1
+  - id: fund
+    op: pda-fund-address
+    validator: mainnet-1
+    amount: 1.5
+    when: [status.verdict != fail, balance.balance_sol < 5]
+  - op: notify
+    message: Weekly top-up done
+    when: fund.ok
+"#;
+
+    #[test]
+    fn test_parse_script() {
+        let script = Script::parse(PLAYBOOK).unwrap();
+        assert_eq!(script.name.as_deref(), Some("Weekly top-up"));
+        assert_eq!(script.steps.len(), 4);
+        assert_eq!(script.steps[2].op, StepOp::PdaFundAddress);
+        assert_eq!(script.steps[2].amount.as_deref(), Some("1.5"));
+        assert_eq!(script.steps[2].when.len(), 2);
+        assert_eq!(script.steps[3].when[0].to_string(), "fund.ok");
+
+        let invalid = [
+            "steps: []",
+            "steps:\n  - op: pda-balance",
+            "steps:\n  - op: pda-fund-address\n    validator: A",
+            "steps:\n  - op: pda-balance\n    validator: A\n    amount: 1",
+            "steps:\n  - op: pda-fund-address\n    validator: A\n    amount: lots",
+            "steps:\n  - op: notify\n    message: hi\n    when: later.ok\n  - id: later\n    op: pda-balance\n    validator: A",
+            "steps:\n  - id: a\n    op: pda-balance\n    validator: A\n  - id: a\n    op: pda-balance\n    validator: A",
+            "steps:\n  - op: pda-sweep\n    validator: A",
+            "steps:\n  - op: pda-balance\n    validator: A\n    retries: 3",
+        ];
+        for yaml in invalid {
+            assert!(Script::parse(yaml).unwrap_err().is_user_error(), "{}", yaml);
+        }
+    }
+
+    #[test]
+    fn test_conditions() {
+        let mut results = HashMap::new();
+        results.insert("status".to_string(), serde_json::json!({ "ok": true, "verdict": "WARN" }));
+        results.insert("balance".to_string(), serde_json::json!({ "ok": true, "balance_sol": 4.2 }));
+        results.insert("fund".to_string(), serde_json::json!({ "ok": false, "skipped": true }));
+        let holds = |text: &str| Condition::parse(text).unwrap().holds(&results);
+
+        assert!(holds("status.ok"));
+        assert!(holds("status.verdict != fail"));
+        assert!(holds("status.verdict == warn"));
+        assert!(holds("balance.balance_sol < 5"));
+        assert!(!holds("balance.balance_sol >= 5"));
+        assert!(!holds("fund.ok"));
+        assert!(holds("!fund.ok"));
+        assert!(holds("fund.skipped"));
+        // Missing steps and fields never satisfy a comparison
+        assert!(!holds("fund.signature != x"));
+        assert!(!holds("verify.ok"));
+        // Text only supports equality
+        assert!(!holds("status.verdict < z"));
+
+        for invalid in ["ok", "status.", "status.ok ~ 1", "status.ok == 1 2", "st atus.ok"] {
+            assert!(Condition::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,579 @@
+use crate::i18n;
+use crate::offline::missing_signers;
+use crate::{load_keypair, parse_pubkey, Error, FundingAccounts, NonceConfig};
+use solana_derivation_path::DerivationPath;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{
+    generate_seed_from_seed_phrase_and_passphrase, keypair_from_seed, keypair_from_seed_and_derivation_path, EncodableKey,
+    Keypair, Signer,
+};
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+use std::io::Read;
+use std::path::Path;
+use uriparse::URIReference;
+
+/// URL scheme of hardware wallet signers (e.g. `usb://ledger?key=0`)
+pub const USB_SCHEME: &str = "usb://";
+
+/// URL scheme of seed phrase signers entered interactively (e.g. `prompt://?key=0`)
+pub const PROMPT_SCHEME: &str = "prompt://";
+
+/// URL of a JSON keypair read from standard input
+pub const STDIN_SCHEME: &str = "stdin://";
+
+/// URL scheme of keypairs held in an environment variable (e.g. `env://DZ_FUNDING_KEYPAIR`)
+pub const ENV_SCHEME: &str = "env://";
+
+/// Accepted BIP39 seed phrase lengths
+const SEED_PHRASE_WORD_COUNTS: &[usize] = &[12, 15, 18, 21, 24];
+
+/// Where a signer is loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerSource {
+    /// JSON keypair file
+    File(String),
+    /// Hardware wallet URL such as `usb://ledger` or `usb://ledger?key=0/1`
+    Usb(String),
+    /// Seed phrase prompt URL such as `prompt://` or `prompt://?key=0`
+    Prompt(String),
+    /// JSON keypair on standard input
+    Stdin,
+    /// Base58 or JSON keypair in the named environment variable
+    Env(String),
+}
+
+impl SignerSource {
+    /// Parses a keypair path or signer URL
+    ///
+    /// # Arguments
+    /// * `source` - Keypair file path, `usb://`, `prompt://` or `env://` URL, or `stdin://`
+    ///
+    /// # Returns
+    /// * `SignerSource` - Parsed signer source
+    pub fn parse(source: &str) -> Self {
+        let lowercase = source.to_ascii_lowercase();
+        if lowercase.starts_with(USB_SCHEME) {
+            SignerSource::Usb(source.to_string())
+        } else if lowercase.starts_with(PROMPT_SCHEME) {
+            SignerSource::Prompt(source.to_string())
+        } else if lowercase == STDIN_SCHEME {
+            SignerSource::Stdin
+        } else if lowercase.starts_with(ENV_SCHEME) {
+            SignerSource::Env(source[ENV_SCHEME.len()..].to_string())
+        } else {
+            SignerSource::File(source.to_string())
+        }
+    }
+
+    /// Returns true if signing requires confirmation on a device
+    pub fn is_interactive(&self) -> bool {
+        matches!(self, SignerSource::Usb(_))
+    }
+
+    /// Returns true if the signer can be loaded again without user input (keypair files and environment variables)
+    pub fn is_unattended(&self) -> bool {
+        matches!(self, SignerSource::File(_) | SignerSource::Env(_))
+    }
+}
+
+/// Loads a signer from any supported source
+///
+/// This is the single entry point for every signing path, so all of them accept the same sources.
+///
+/// # Arguments
+/// * `source` - Keypair file path, `usb://ledger?key=<account>[/<change>]`, `prompt://[?key=<account>[/<change>]]`, `stdin://`
+///   or `env://<VARIABLE>`
+///
+/// # Returns
+/// * `Result<Box<dyn Signer>, Error>` - Signer or `Error::Keypair`
+pub fn load_signer(source: &str) -> Result<Box<dyn Signer>, Error> {
+    match SignerSource::parse(source) {
+        SignerSource::File(path) => Ok(Box::new(load_keypair(&path)?)),
+        SignerSource::Usb(url) => load_usb_signer(&url),
+        SignerSource::Prompt(url) => Ok(Box::new(load_prompt_keypair(&url)?)),
+        SignerSource::Stdin => Ok(Box::new(read_keypair(&mut std::io::stdin().lock())?)),
+        SignerSource::Env(variable) => Ok(Box::new(load_env_keypair(&variable)?)),
+    }
+}
+
+/// Cross-checks the parameters of `pda-fund-address` against the validator's identity keypair (`--identity-keypair`)
+///
+/// The validator address may be left out, in which case it is taken from the identity: with two
+/// parameters the first one is the validator only if it is a valid pubkey, otherwise it is the keypair path.
+///
+/// # Arguments
+/// * `parameters` - Parameters after the operation: `[validator_address] [keypair_path] <amount>`
+/// * `identity` - Pubkey of the identity keypair
+///
+/// # Returns
+/// * `Result<Vec<String>, Error>` - Parameters with the validator address filled in, or `Error::InvalidInput`
+///   if the given validator is not the identity
+pub fn validator_from_identity(parameters: &[String], identity: &Pubkey) -> Result<Vec<String>, Error> {
+    fill_validator(parameters, 3, identity, "the identity keypair")
+}
+
+/// Cross-checks or fills in the leading validator parameter of an operation with a known identity
+///
+/// With `arity` parameters the first one is the validator; with one less it is the validator only if
+/// it is a valid pubkey (the optional parameter after it was given instead); with fewer it is missing.
+///
+/// # Arguments
+/// * `parameters` - Parameters starting at the validator position
+/// * `arity` - Number of parameters when all optional ones are given, the validator included
+/// * `identity` - Validator identity pubkey
+/// * `source` - Where the identity came from, for the error message (e.g. "the identity file")
+///
+/// # Returns
+/// * `Result<Vec<String>, Error>` - Parameters with the validator filled in, or `Error::InvalidInput`
+///   if the given validator is not the identity
+pub fn fill_validator(parameters: &[String], arity: usize, identity: &Pubkey, source: &str) -> Result<Vec<String>, Error> {
+    let given = if parameters.len() >= arity {
+        parameters.first()
+    } else if parameters.len() + 1 == arity {
+        parameters.first().filter(|first| parse_pubkey(first).is_ok())
+    } else {
+        None
+    };
+    match given {
+        Some(validator) if parse_pubkey(validator).ok() != Some(*identity) => Err(Error::InvalidInput(format!(
+            "Validator {} does not match {} ({})",
+            validator, source, identity
+        ))),
+        Some(_) => Ok(parameters.to_vec()),
+        None => Ok(std::iter::once(identity.to_string()).chain(parameters.iter().cloned()).collect()),
+    }
+}
+
+/// Reads the pubkey of a validator identity keypair file without loading the secret key
+///
+/// The file is the usual JSON array of 64 bytes; the last 32 bytes are the public half.
+///
+/// # Arguments
+/// * `path` - Identity keypair file, e.g. `validator-keypair.json`
+///
+/// # Returns
+/// * `Result<Pubkey, Error>` - Identity pubkey or `Error::Keypair`
+pub fn read_identity_pubkey(path: &Path) -> Result<Pubkey, Error> {
+    let invalid = |reason: String| Error::Keypair(format!("Invalid identity file {}: {}", path.display(), reason));
+    let contents = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+    let bytes: Vec<u8> = serde_json::from_str(contents.trim()).map_err(|_| invalid("expected a JSON array of 64 bytes".to_string()))?;
+    if bytes.len() != 64 {
+        return Err(invalid(format!("expected 64 bytes, got {}", bytes.len())));
+    }
+    Pubkey::try_from(&bytes[32..]).map_err(|_| invalid("invalid public key".to_string()))
+}
+
+/// Sources of the signers of a funding transaction
+///
+/// Each role is a signer source (see `load_signer`) or the pubkey of a wallet that is signed for
+/// by one of `keypairs` or, when signing offline, by another party.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FundingSigners {
+    /// Wallet the lamports are transferred from
+    pub funder: String,
+    /// Wallet paying the transaction fee (defaults to the funding wallet)
+    pub fee_payer: Option<String>,
+    /// Additional signers (`--keypair`)
+    pub keypairs: Vec<String>,
+}
+
+impl FundingSigners {
+    /// Creates signer sources for a single funding wallet that also pays the fee
+    ///
+    /// # Arguments
+    /// * `funder` - Signer source of the funding wallet
+    pub fn new(funder: &str) -> Self {
+        FundingSigners {
+            funder: funder.to_string(),
+            ..FundingSigners::default()
+        }
+    }
+
+    /// Returns true if any signer needs confirmation on a device
+    pub fn is_interactive(&self) -> bool {
+        std::iter::once(&self.funder)
+            .chain(&self.fee_payer)
+            .chain(&self.keypairs)
+            .any(|source| SignerSource::parse(source).is_interactive())
+    }
+
+    /// Loads the signers and resolves the accounts of the transaction
+    ///
+    /// # Arguments
+    /// * `nonce` - Durable nonce account and authority (optional; the authority defaults to the funding wallet)
+    ///
+    /// # Returns
+    /// * `Result<(SignerPool, FundingAccounts), Error>` - Loaded signers and transaction accounts, or `Error::Keypair`
+    pub fn load(&self, nonce: Option<&NonceConfig>) -> Result<(SignerPool, FundingAccounts), Error> {
+        let mut pool = SignerPool::default();
+        for source in &self.keypairs {
+            pool.add(load_signer(source)?);
+        }
+
+        let funder = pool.resolve(&self.funder)?;
+        let fee_payer = self.fee_payer.as_deref().map(|source| pool.resolve(source)).transpose()?.unwrap_or(funder);
+        let nonce = match nonce {
+            Some(nonce) => {
+                let authority = nonce.authority.as_deref().map(|source| pool.resolve(source)).transpose()?.unwrap_or(funder);
+                Some((nonce.account, authority))
+            }
+            None => None,
+        };
+
+        Ok((pool, FundingAccounts { funder, fee_payer, nonce }))
+    }
+}
+
+/// Signers loaded for one transaction, without duplicates
+#[derive(Default)]
+pub struct SignerPool {
+    signers: Vec<Box<dyn Signer>>,
+}
+
+impl SignerPool {
+    /// Adds a signer unless one with the same pubkey is already present
+    ///
+    /// # Returns
+    /// * `Pubkey` - Pubkey of the signer
+    pub fn add(&mut self, signer: Box<dyn Signer>) -> Pubkey {
+        let pubkey = signer.pubkey();
+        if !self.contains(&pubkey) {
+            self.signers.push(signer);
+        }
+        pubkey
+    }
+
+    /// Resolves a role to a pubkey
+    ///
+    /// A bare pubkey (that is not an existing file) is used as is and must be signed for by another
+    /// signer; any other source is loaded and added to the pool.
+    ///
+    /// # Arguments
+    /// * `source` - Signer source or pubkey
+    ///
+    /// # Returns
+    /// * `Result<Pubkey, Error>` - Pubkey of the role or `Error::Keypair`
+    pub fn resolve(&mut self, source: &str) -> Result<Pubkey, Error> {
+        if !Path::new(source).exists()
+            && let Ok(pubkey) = source.parse::<Pubkey>()
+        {
+            return Ok(pubkey);
+        }
+        Ok(self.add(load_signer(source)?))
+    }
+
+    /// Returns true if the pool has a signer for the pubkey
+    pub fn contains(&self, pubkey: &Pubkey) -> bool {
+        self.signers.iter().any(|signer| signer.pubkey() == *pubkey)
+    }
+
+    /// Signs a transaction with every pooled signer it requires; other signers are ignored
+    ///
+    /// # Arguments
+    /// * `transaction` - Transaction to sign; existing signatures of other signers are kept
+    ///
+    /// # Returns
+    /// * `Result<Vec<Pubkey>, Error>` - Required signers whose signature is still missing, or `Error::Keypair`
+    pub fn sign_partial(&self, transaction: &mut Transaction) -> Result<Vec<Pubkey>, Error> {
+        let required = &transaction.message.account_keys[..transaction.message.header.num_required_signatures as usize];
+        let signers: Vec<&dyn Signer> = self
+            .signers
+            .iter()
+            .map(|signer| signer.as_ref())
+            .filter(|signer| required.contains(&signer.pubkey()))
+            .collect();
+
+        let blockhash = transaction.message.recent_blockhash;
+        transaction
+            .try_partial_sign(&signers, blockhash)
+            .map_err(|e| Error::Keypair(format!("Failed to sign transaction: {}", e)))?;
+        Ok(missing_signers(transaction))
+    }
+
+    /// Signs a versioned message with its required signers
+    ///
+    /// # Arguments
+    /// * `message` - Message to sign; every required signer must be in the pool
+    ///
+    /// # Returns
+    /// * `Result<VersionedTransaction, Error>` - Fully signed transaction or `Error::Keypair`
+    pub fn sign_versioned(&self, message: VersionedMessage) -> Result<VersionedTransaction, Error> {
+        let required = &message.static_account_keys()[..message.header().num_required_signatures as usize];
+        let signers: Vec<&dyn Signer> = required
+            .iter()
+            .map(|pubkey| {
+                self.signers
+                    .iter()
+                    .find(|signer| signer.pubkey() == *pubkey)
+                    .map(|signer| signer.as_ref())
+                    .ok_or_else(|| Error::Keypair(format!("Missing signer for {}", pubkey)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        VersionedTransaction::try_new(message, &signers)
+            .map_err(|e| Error::Keypair(format!("Failed to sign transaction: {}", e)))
+    }
+}
+
+/// Derives a keypair from a BIP39 seed phrase
+///
+/// Without a derivation path the keypair is made from the seed itself, as `solana-keygen recover` does;
+/// with a path (e.g. `m/44'/501'/0'`) it is derived with BIP32.
+///
+/// # Arguments
+/// * `seed_phrase` - 12 to 24 word seed phrase
+/// * `passphrase` - BIP39 passphrase (empty for none)
+/// * `derivation_path` - Derivation path, if any
+///
+/// # Returns
+/// * `Result<Keypair, Error>` - Keypair or `Error::Keypair` (the phrase is never included in the message)
+pub fn keypair_from_seed_phrase(seed_phrase: &str, passphrase: &str, derivation_path: Option<DerivationPath>) -> Result<Keypair, Error> {
+    let words: Vec<&str> = seed_phrase.split_whitespace().collect();
+    if !SEED_PHRASE_WORD_COUNTS.contains(&words.len()) {
+        return Err(Error::Keypair(format!(
+            "Invalid seed phrase: expected 12, 15, 18, 21 or 24 words, got {}",
+            words.len()
+        )));
+    }
+
+    let seed = generate_seed_from_seed_phrase_and_passphrase(&words.join(" "), passphrase);
+    let keypair = match derivation_path {
+        Some(derivation_path) => keypair_from_seed_and_derivation_path(&seed, Some(derivation_path)),
+        None => keypair_from_seed(&seed),
+    };
+    keypair.map_err(|e| Error::Keypair(format!("Failed to derive keypair from seed phrase: {}", e)))
+}
+
+/// Reads a JSON keypair (array of 64 bytes, as written by `solana-keygen`)
+///
+/// # Arguments
+/// * `reader` - Source of the keypair, e.g. standard input
+///
+/// # Returns
+/// * `Result<Keypair, Error>` - Keypair or `Error::Keypair`
+pub fn read_keypair<R: Read>(reader: &mut R) -> Result<Keypair, Error> {
+    Keypair::read(reader).map_err(|e| Error::Keypair(format!("Failed to read keypair from {}: {}", STDIN_SCHEME, e)))
+}
+
+/// Parses a keypair held in an environment variable: a JSON array of 64 bytes or a base58 secret key
+///
+/// # Arguments
+/// * `value` - Value of the variable
+/// * `variable` - Variable name shown in errors (the value never is)
+///
+/// # Returns
+/// * `Result<Keypair, Error>` - Keypair or `Error::Keypair`
+pub fn parse_env_keypair(value: &str, variable: &str) -> Result<Keypair, Error> {
+    let value = value.trim();
+    let invalid = |reason: String| Error::Keypair(format!("Invalid keypair in {}{}: {}", ENV_SCHEME, variable, reason));
+    if value.starts_with('[') {
+        return Keypair::read(&mut value.as_bytes()).map_err(|e| invalid(e.to_string()));
+    }
+    let bytes = bs58::decode(value).into_vec().map_err(|_| invalid("expected a JSON array or a base58 secret key".to_string()))?;
+    Keypair::try_from(bytes.as_slice()).map_err(|_| invalid(format!("expected 64 bytes, got {}", bytes.len())))
+}
+
+/// Loads the keypair of an `env://` signer
+fn load_env_keypair(variable: &str) -> Result<Keypair, Error> {
+    if variable.is_empty() {
+        return Err(Error::Keypair(format!("{} needs a variable name, e.g. {}DZ_FUNDING_KEYPAIR", ENV_SCHEME, ENV_SCHEME)));
+    }
+    let value = std::env::var(variable)
+        .map_err(|_| Error::Keypair(format!("Environment variable {} of {}{} is not set", variable, ENV_SCHEME, variable)))?;
+    parse_env_keypair(&value, variable)
+}
+
+/// Returns the derivation path selected by the `key` or `full-path` query of a signer URL
+fn derivation_path_from_url(url: &str) -> Result<Option<DerivationPath>, Error> {
+    let uri = URIReference::try_from(url)
+        .map_err(|e| Error::Keypair(format!("Invalid signer URL {}: {}", url, e)))?;
+    DerivationPath::from_uri_any_query(&uri)
+        .map_err(|e| Error::Keypair(format!("Invalid derivation path in {}: {}", url, e)))
+}
+
+/// Asks for a seed phrase and passphrase without echoing them and derives the keypair
+fn load_prompt_keypair(url: &str) -> Result<Keypair, Error> {
+    let derivation_path = derivation_path_from_url(url)?;
+
+    let seed_phrase = rpassword::prompt_password(i18n::text("prompt-seed-phrase"))
+        .map_err(|e| Error::Keypair(format!("Failed to read seed phrase: {}", e)))?;
+    let passphrase = rpassword::prompt_password(i18n::text("prompt-bip39-passphrase"))
+        .map_err(|e| Error::Keypair(format!("Failed to read passphrase: {}", e)))?;
+
+    let keypair = keypair_from_seed_phrase(&seed_phrase, &passphrase, derivation_path)?;
+    // A mistyped phrase still gives a valid keypair, so show which wallet was recovered
+    eprintln!("{}", i18n::message("recovered-wallet", &[("pubkey", &keypair.pubkey())]));
+    Ok(keypair)
+}
+
+/// Connects to a Ledger and derives the keypair selected by the URL
+fn load_usb_signer(url: &str) -> Result<Box<dyn Signer>, Error> {
+    use solana_remote_wallet::locator::Locator;
+    use solana_remote_wallet::remote_keypair::generate_remote_keypair;
+    use solana_remote_wallet::remote_wallet::maybe_wallet_manager;
+
+    if !cfg!(feature = "ledger") {
+        return Err(Error::Keypair(format!(
+            "Cannot use {}: hardware wallet support is not enabled in this build (rebuild with --features ledger)",
+            url
+        )));
+    }
+
+    let uri = URIReference::try_from(url)
+        .map_err(|e| Error::Keypair(format!("Invalid hardware wallet URL {}: {}", url, e)))?;
+    let locator = Locator::new_from_uri(&uri)
+        .map_err(|e| Error::Keypair(format!("Invalid hardware wallet URL {}: {}", url, e)))?;
+    let derivation_path = DerivationPath::from_uri_key_query(&uri)
+        .map_err(|e| Error::Keypair(format!("Invalid derivation path in {}: {}", url, e)))?
+        .unwrap_or_default();
+
+    let wallet_manager = maybe_wallet_manager()
+        .map_err(|e| Error::Keypair(format!("Failed to access hardware wallet: {}", e)))?
+        .ok_or_else(|| Error::Keypair(format!("No hardware wallet found for {}; connect and unlock the device", url)))?;
+
+    let keypair = generate_remote_keypair(locator, derivation_path, &wallet_manager, false, "funding wallet")
+        .map_err(|e| Error::Keypair(format!("Failed to load signer {}: {}", url, e)))?;
+
+    Ok(Box::new(keypair))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validator_from_identity() {
+        let identity = Pubkey::new_unique();
+        let params = |values: &[&str]| values.iter().map(|value| value.to_string()).collect::<Vec<String>>();
+
+        // A matching validator is kept, with or without a keypair path
+        let given = params(&[&identity.to_string(), "funding.json", "1.5"]);
+        assert_eq!(validator_from_identity(&given, &identity).unwrap(), given);
+        let given = params(&[&identity.to_string(), "1.5"]);
+        assert_eq!(validator_from_identity(&given, &identity).unwrap(), given);
+
+        // Without a validator it is derived from the identity
+        let expected = params(&[&identity.to_string(), "funding.json", "1.5"]);
+        assert_eq!(validator_from_identity(&params(&["funding.json", "1.5"]), &identity).unwrap(), expected);
+        assert_eq!(validator_from_identity(&params(&["1.5"]), &identity).unwrap(), params(&[&identity.to_string(), "1.5"]));
+
+        // Any other validator is refused
+        let other = Pubkey::new_unique().to_string();
+        for given in [params(&[&other, "funding.json", "1.5"]), params(&[&other, "1.5"]), params(&["typo", "funding.json", "1.5"])] {
+            let error = validator_from_identity(&given, &identity).unwrap_err();
+            assert!(error.is_user_error());
+            assert!(error.to_string().contains("does not match the identity keypair"));
+        }
+    }
+
+    #[test]
+    fn test_fill_validator() {
+        let identity = Pubkey::new_unique();
+        let params = |values: &[&str]| values.iter().map(|value| value.to_string()).collect::<Vec<String>>();
+
+        // pda-balance [validator]
+        assert_eq!(fill_validator(&[], 1, &identity, "the identity file").unwrap(), params(&[&identity.to_string()]));
+        let given = params(&[&identity.to_string()]);
+        assert_eq!(fill_validator(&given, 1, &identity, "the identity file").unwrap(), given);
+
+        // pda-watch [validator] [interval]: a lone interval is not taken for the validator
+        assert_eq!(fill_validator(&params(&["60"]), 2, &identity, "the identity file").unwrap(), params(&[&identity.to_string(), "60"]));
+
+        let error = fill_validator(&params(&["alias", "60"]), 2, &identity, "the identity file").unwrap_err();
+        assert!(error.is_user_error());
+        assert_eq!(error.to_string(), format!("Validator alias does not match the identity file ({})", identity));
+    }
+
+    #[test]
+    fn test_read_identity_pubkey() {
+        let keypair = Keypair::new();
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_identity_{}.json", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&keypair, &path).unwrap();
+        assert_eq!(read_identity_pubkey(&path).unwrap(), keypair.pubkey());
+
+        std::fs::write(&path, serde_json::to_string(&keypair.pubkey().to_bytes().to_vec()).unwrap()).unwrap();
+        let error = read_identity_pubkey(&path).unwrap_err();
+        assert!(matches!(error, Error::Keypair(_)));
+        assert!(error.to_string().contains("expected 64 bytes, got 32"), "{}", error);
+        std::fs::remove_file(&path).ok();
+
+        assert!(matches!(read_identity_pubkey(&path), Err(Error::Keypair(_))));
+    }
+
+    #[test]
+    fn test_parse_signer_source() {
+        assert_eq!(SignerSource::parse("/keys/funding.json"), SignerSource::File("/keys/funding.json".to_string()));
+        assert_eq!(SignerSource::parse("usb://ledger?key=0"), SignerSource::Usb("usb://ledger?key=0".to_string()));
+        assert_eq!(SignerSource::parse("USB://ledger"), SignerSource::Usb("USB://ledger".to_string()));
+
+        assert_eq!(SignerSource::parse("prompt://?key=0"), SignerSource::Prompt("prompt://?key=0".to_string()));
+        assert_eq!(SignerSource::parse("stdin://"), SignerSource::Stdin);
+        assert_eq!(SignerSource::parse("env://DZ_FUNDING_KEYPAIR"), SignerSource::Env("DZ_FUNDING_KEYPAIR".to_string()));
+        assert!(SignerSource::parse("env://DZ_FUNDING_KEYPAIR").is_unattended());
+        assert!(!SignerSource::parse("stdin://").is_unattended());
+
+        assert!(SignerSource::parse("usb://ledger").is_interactive());
+        assert!(!SignerSource::parse("keypair.json").is_interactive());
+    }
+
+    #[test]
+    fn test_keypair_from_seed_phrase() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        let legacy = keypair_from_seed_phrase(phrase, "", None).unwrap();
+        let account_0 = keypair_from_seed_phrase(phrase, "", derivation_path_from_url("prompt://?key=0").unwrap()).unwrap();
+        let full_path = derivation_path_from_url("prompt://?full-path=m/44'/501'/0'").unwrap();
+        assert_eq!(keypair_from_seed_phrase(phrase, "", full_path).unwrap().pubkey(), account_0.pubkey());
+        assert_ne!(legacy.pubkey(), account_0.pubkey());
+
+        // Extra whitespace does not change the wallet, a passphrase does
+        let spaced = format!("  {}\n", phrase.replace(' ', "  "));
+        assert_eq!(keypair_from_seed_phrase(&spaced, "", None).unwrap().pubkey(), legacy.pubkey());
+        assert_ne!(keypair_from_seed_phrase(phrase, "secret", None).unwrap().pubkey(), legacy.pubkey());
+
+        let error = keypair_from_seed_phrase("abandon about", "", None).unwrap_err();
+        assert!(matches!(error, Error::Keypair(_)));
+        assert!(!error.to_string().contains("abandon"));
+    }
+
+    #[test]
+    fn test_read_keypair() {
+        let keypair = Keypair::new();
+        let json = serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap();
+        assert_eq!(read_keypair(&mut json.as_bytes()).unwrap().pubkey(), keypair.pubkey());
+
+        assert!(matches!(read_keypair(&mut "not a keypair".as_bytes()), Err(Error::Keypair(_))));
+    }
+
+    #[test]
+    fn test_parse_env_keypair() {
+        let keypair = Keypair::new();
+        let json = serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap();
+        assert_eq!(parse_env_keypair(&json, "DZ_FUNDING_KEYPAIR").unwrap().pubkey(), keypair.pubkey());
+        assert_eq!(parse_env_keypair(&format!("{}\n", keypair.to_base58_string()), "DZ_FUNDING_KEYPAIR").unwrap().pubkey(), keypair.pubkey());
+
+        // A pubkey is not a keypair, and the value is not echoed back
+        let pubkey = keypair.pubkey().to_string();
+        let error = parse_env_keypair(&pubkey, "DZ_FUNDING_KEYPAIR").unwrap_err();
+        assert!(error.to_string().contains("env://DZ_FUNDING_KEYPAIR: expected 64 bytes, got 32"), "{}", error);
+        assert!(!error.to_string().contains(&pubkey));
+        assert!(matches!(parse_env_keypair("[1, 2", "DZ_FUNDING_KEYPAIR"), Err(Error::Keypair(_))));
+
+        let error = load_signer("env://DZ_VALIDATOR_PDA_TEST_UNSET").err().unwrap();
+        assert!(error.to_string().contains("DZ_VALIDATOR_PDA_TEST_UNSET"));
+    }
+
+    #[test]
+    fn test_load_signer_missing_file_is_keypair_error() {
+        let error = load_signer("/nonexistent/keypair.json").err().unwrap();
+        assert!(matches!(error, Error::Keypair(_)));
+    }
+
+    #[cfg(not(feature = "ledger"))]
+    #[test]
+    fn test_usb_signer_requires_ledger_feature() {
+        let error = load_signer("usb://ledger?key=0").err().unwrap();
+        assert!(error.to_string().contains("--features ledger"));
+    }
+}
@@ -0,0 +1,483 @@
+use crate::instruction::build_fund_instruction;
+use crate::report::{strict_check, WARN_VAULT_UNDERFUNDED};
+use crate::rpc::SolanaRpc;
+use crate::signer::SignerPool;
+use crate::{check_funding_policy, check_funding_sufficiency, generate_deposit_pda, load_required_signers, Error, FundingPolicy, FundingSigners, Warning};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+
+/// Squads v4 multisig program (`SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf`)
+pub const SQUADS_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+
+/// Vault of the multisig that holds the treasury and sends the funding
+pub const DEFAULT_VAULT_INDEX: u8 = 0;
+
+/// Member permission to create transactions and proposals
+pub const PERMISSION_INITIATE: u8 = 1;
+
+// Anchor discriminators: the first 8 bytes of sha256("account:Multisig") and sha256("global:<instruction>")
+const MULTISIG_DISCRIMINATOR: [u8; 8] = [224, 116, 121, 186, 68, 161, 79, 236];
+const VAULT_TRANSACTION_CREATE: [u8; 8] = [48, 250, 78, 168, 208, 226, 218, 211];
+const PROPOSAL_CREATE: [u8; 8] = [220, 60, 73, 224, 30, 108, 79, 159];
+
+const SEED_PREFIX: &[u8] = b"multisig";
+const SEED_VAULT: &[u8] = b"vault";
+const SEED_TRANSACTION: &[u8] = b"transaction";
+const SEED_PROPOSAL: &[u8] = b"proposal";
+
+/// Fields of a Squads multisig account needed to propose a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multisig {
+    /// Approvals needed to execute a transaction
+    pub threshold: u16,
+    /// Index of the last transaction created; the next one gets `transaction_index + 1`
+    pub transaction_index: u64,
+    /// Members and their permission masks
+    pub members: Vec<(Pubkey, u8)>,
+}
+
+impl Multisig {
+    /// Returns true if the key is a member allowed to create proposals
+    pub fn can_initiate(&self, key: &Pubkey) -> bool {
+        self.members.iter().any(|(member, permissions)| member == key && permissions & PERMISSION_INITIATE != 0)
+    }
+}
+
+/// Decodes a Squads v4 multisig account
+///
+/// # Arguments
+/// * `address` - Multisig account address, for error messages
+/// * `account` - Account data, or None if the account does not exist
+///
+/// # Returns
+/// * `Result<Multisig, Error>` - Threshold, transaction index and members, or `Error::InvalidInput`
+///   if the account is missing or not a Squads multisig
+pub fn decode_multisig(address: &Pubkey, account: Option<&Account>) -> Result<Multisig, Error> {
+    let invalid = || Error::InvalidInput(format!("{} is not a Squads multisig", address));
+    let account = account.ok_or_else(|| Error::InvalidInput(format!("Squads multisig {} does not exist", address)))?;
+    if account.owner != SQUADS_PROGRAM_ID || !account.data.starts_with(&MULTISIG_DISCRIMINATOR) {
+        return Err(invalid());
+    }
+
+    let mut reader = Reader { data: &account.data, offset: MULTISIG_DISCRIMINATOR.len() };
+    // create_key and config_authority
+    reader.take(64).ok_or_else(invalid)?;
+    let threshold = u16::from_le_bytes(reader.array().ok_or_else(invalid)?);
+    // time_lock
+    reader.take(4).ok_or_else(invalid)?;
+    let transaction_index = u64::from_le_bytes(reader.array().ok_or_else(invalid)?);
+    // stale_transaction_index, the optional rent collector and the bump
+    reader.take(8).ok_or_else(invalid)?;
+    if reader.take(1).ok_or_else(invalid)?[0] == 1 {
+        reader.take(32).ok_or_else(invalid)?;
+    }
+    reader.take(1).ok_or_else(invalid)?;
+    let count = u32::from_le_bytes(reader.array().ok_or_else(invalid)?);
+    let members = (0..count)
+        .map(|_| Some((Pubkey::new_from_array(reader.array()?), reader.take(1)?[0])))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(invalid)?;
+    Ok(Multisig { threshold, transaction_index, members })
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Option<&[u8]> {
+        let bytes = self.data.get(self.offset..self.offset.checked_add(len)?)?;
+        self.offset += len;
+        Some(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+}
+
+/// Returns the vault of a multisig, which signs its executed transactions
+pub fn vault_address(multisig: &Pubkey, vault_index: u8) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX, multisig.as_ref(), SEED_VAULT, &[vault_index]], &SQUADS_PROGRAM_ID).0
+}
+
+/// Returns the vault transaction account of a multisig transaction
+pub fn transaction_address(multisig: &Pubkey, transaction_index: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SEED_PREFIX, multisig.as_ref(), SEED_TRANSACTION, &transaction_index.to_le_bytes()],
+        &SQUADS_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Returns the proposal account members vote on for a multisig transaction
+pub fn proposal_address(multisig: &Pubkey, transaction_index: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SEED_PREFIX, multisig.as_ref(), SEED_TRANSACTION, &transaction_index.to_le_bytes(), SEED_PROPOSAL],
+        &SQUADS_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Serializes instructions as the transaction message a vault executes
+///
+/// The layout is a compiled message with one-byte lengths (two bytes for instruction data) and
+/// no lookup tables; the vault is its only signer.
+///
+/// # Arguments
+/// * `vault` - Vault that signs the instructions
+/// * `instructions` - Instructions to execute
+///
+/// # Returns
+/// * `Result<Vec<u8>, Error>` - Message bytes, or `Error::InvalidInput` if it has too many accounts
+pub fn vault_transaction_message(vault: &Pubkey, instructions: &[Instruction]) -> Result<Vec<u8>, Error> {
+    let message = Message::new(instructions, Some(vault));
+    let header = message.header;
+    let too_large = || Error::InvalidInput("Vault transaction has too many accounts or instructions".to_string());
+    let len = |len: usize| u8::try_from(len).map_err(|_| too_large());
+
+    let mut bytes = vec![
+        header.num_required_signatures,
+        header.num_required_signatures - header.num_readonly_signed_accounts,
+        len(message.account_keys.len() - header.num_required_signatures as usize - header.num_readonly_unsigned_accounts as usize)?,
+        len(message.account_keys.len())?,
+    ];
+    for key in &message.account_keys {
+        bytes.extend_from_slice(key.as_ref());
+    }
+    bytes.push(len(message.instructions.len())?);
+    for instruction in &message.instructions {
+        bytes.push(instruction.program_id_index);
+        bytes.push(len(instruction.accounts.len())?);
+        bytes.extend_from_slice(&instruction.accounts);
+        bytes.extend_from_slice(&u16::try_from(instruction.data.len()).map_err(|_| too_large())?.to_le_bytes());
+        bytes.extend_from_slice(&instruction.data);
+    }
+    // Address table lookups
+    bytes.push(0);
+    Ok(bytes)
+}
+
+/// Returns the instructions creating a vault transaction and its proposal
+///
+/// # Arguments
+/// * `multisig` - Multisig account
+/// * `creator` - Member creating the proposal, who also pays the rent of both accounts
+/// * `transaction_index` - Index of the new transaction (`Multisig::transaction_index + 1`)
+/// * `vault_index` - Vault executing the instructions
+/// * `instructions` - Instructions the vault executes once the proposal is approved
+/// * `memo` - Memo stored with the vault transaction (optional)
+///
+/// # Returns
+/// * `Result<Vec<Instruction>, Error>` - `vault_transaction_create`, then `proposal_create`
+pub fn proposal_instructions(
+    multisig: &Pubkey,
+    creator: &Pubkey,
+    transaction_index: u64,
+    vault_index: u8,
+    instructions: &[Instruction],
+    memo: Option<&str>,
+) -> Result<Vec<Instruction>, Error> {
+    let message = vault_transaction_message(&vault_address(multisig, vault_index), instructions)?;
+    let mut data = VAULT_TRANSACTION_CREATE.to_vec();
+    // vault_index, ephemeral_signers, transaction_message and memo
+    data.extend_from_slice(&[vault_index, 0]);
+    data.extend_from_slice(&(message.len() as u32).to_le_bytes());
+    data.extend_from_slice(&message);
+    match memo {
+        Some(memo) => {
+            data.push(1);
+            data.extend_from_slice(&(memo.len() as u32).to_le_bytes());
+            data.extend_from_slice(memo.as_bytes());
+        }
+        None => data.push(0),
+    }
+    let create_transaction = Instruction::new_with_bytes(
+        SQUADS_PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(*multisig, false),
+            AccountMeta::new(transaction_address(multisig, transaction_index), false),
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+    );
+
+    let mut data = PROPOSAL_CREATE.to_vec();
+    // transaction_index and draft
+    data.extend_from_slice(&transaction_index.to_le_bytes());
+    data.push(0);
+    let create_proposal = Instruction::new_with_bytes(
+        SQUADS_PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*multisig, false),
+            AccountMeta::new(proposal_address(multisig, transaction_index), false),
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+    );
+    Ok(vec![create_transaction, create_proposal])
+}
+
+/// Funding proposal created in a Squads multisig
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SquadsProposal {
+    /// Multisig account
+    pub multisig: String,
+    /// Vault the lamports are transferred from once the proposal is executed
+    pub vault: String,
+    /// Member that created the proposal
+    pub creator: String,
+    /// Index of the multisig transaction
+    pub transaction_index: u64,
+    /// Vault transaction account
+    pub transaction: String,
+    /// Proposal account members approve
+    pub proposal: String,
+    /// Approvals needed to execute it
+    pub threshold: u16,
+    /// Amount the vault transfers in lamports
+    pub amount_lamports: u64,
+    /// Fee of the proposal transaction in lamports
+    pub fee_lamports: u64,
+    /// Signature of the proposal transaction
+    pub signature: String,
+    /// Soft issues found by the health policy and the vault balance check
+    pub warnings: Vec<Warning>,
+}
+
+/// A checked funding proposal that has not been signed or sent yet
+pub struct PreparedSquadsProposal {
+    /// Multisig account
+    pub multisig: Pubkey,
+    /// Vault the lamports are transferred from
+    pub vault: Pubkey,
+    /// Member creating the proposal
+    pub creator: Pubkey,
+    /// Index of the multisig transaction
+    pub transaction_index: u64,
+    /// Approvals needed to execute it
+    pub threshold: u16,
+    /// Deposit PDA receiving the lamports
+    pub pda: Pubkey,
+    /// Amount the vault transfers in lamports
+    pub amount_lamports: u64,
+    /// Current balance of the vault in lamports
+    pub vault_balance: u64,
+    /// Fee of the proposal transaction in lamports
+    pub fee_lamports: u64,
+    /// Soft issues found by the health policy and the vault balance check
+    pub warnings: Vec<Warning>,
+    pool: SignerPool,
+    message: Message,
+}
+
+/// Checks a funding of a validator PDA from a Squads vault and builds its proposal without sending it
+///
+/// Nothing is transferred by the proposal itself: the vault sends the funding once enough members
+/// approve and execute it.
+///
+/// # Arguments
+/// * `validator_id` - The validator's public key
+/// * `multisig` - Squads multisig account
+/// * `amount_lamports` - Amount the vault transfers in lamports
+/// * `signers` - Member creating the proposal (`funder`), fee payer and additional signers
+/// * `policy` - Health policy the validator must satisfy (its memo is stored with the vault transaction)
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<PreparedSquadsProposal, Error>` - Proposal with its accounts and fee, or `Error::InvalidInput`
+///   if the account is not a multisig or the creator is not a member allowed to initiate
+pub async fn prepare_squads_proposal(
+    validator_id: &Pubkey,
+    multisig: &Pubkey,
+    amount_lamports: u64,
+    signers: &FundingSigners,
+    policy: &FundingPolicy,
+    rpc: &dyn SolanaRpc,
+) -> Result<PreparedSquadsProposal, Error> {
+    let mut warnings = check_funding_policy(validator_id, policy, rpc).await?;
+    let (pool, accounts) = load_required_signers(signers, None)?;
+
+    let account = rpc
+        .get_account_with_context(multisig)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get multisig account", &e))?
+        .value;
+    let state = decode_multisig(multisig, account.as_ref())?;
+    if !state.can_initiate(&accounts.funder) {
+        return Err(Error::InvalidInput(format!(
+            "{} is not a member of multisig {} with the permission to create proposals",
+            accounts.funder, multisig
+        )));
+    }
+
+    // The vault may be topped up before the proposal is executed, so a low balance is only a warning
+    let vault = vault_address(multisig, DEFAULT_VAULT_INDEX);
+    let vault_balance = rpc.get_balance(&vault).await.map_err(|e| Error::from_client_error("Failed to get vault balance", &e))?;
+    if vault_balance < amount_lamports {
+        let warning = Warning::new(
+            WARN_VAULT_UNDERFUNDED,
+            format!("Vault {} holds {} lamports, less than the {} lamports proposed", vault, vault_balance, amount_lamports),
+        );
+        if policy.strict {
+            strict_check(std::slice::from_ref(&warning))?;
+        }
+        warnings.push(warning);
+    }
+
+    let transaction_index = state.transaction_index + 1;
+    let funding = [build_fund_instruction(&vault, validator_id, amount_lamports)];
+    let instructions = proposal_instructions(multisig, &accounts.funder, transaction_index, DEFAULT_VAULT_INDEX, &funding, policy.memo.as_deref())?;
+    let recent_blockhash = rpc
+        .get_latest_blockhash()
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get recent blockhash", &e))?;
+    let message = Message::new_with_blockhash(&instructions, Some(&accounts.fee_payer), &recent_blockhash);
+    let fee_lamports = rpc
+        .get_fee_for_message(&message)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to estimate transaction fee", &e))?;
+    let fee_payer_balance = rpc
+        .get_balance(&accounts.fee_payer)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get fee payer balance", &e))?;
+    check_funding_sufficiency(fee_payer_balance, 0, fee_lamports, policy.reserve_lamports)?;
+
+    Ok(PreparedSquadsProposal {
+        multisig: *multisig,
+        vault,
+        creator: accounts.funder,
+        transaction_index,
+        threshold: state.threshold,
+        pda: generate_deposit_pda(validator_id),
+        amount_lamports,
+        vault_balance,
+        fee_lamports,
+        warnings,
+        pool,
+        message,
+    })
+}
+
+/// Signs and sends a prepared funding proposal and waits for its confirmation
+///
+/// # Arguments
+/// * `prepared` - Proposal returned by `prepare_squads_proposal`
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<SquadsProposal, Error>` - Created proposal with its signature, or error
+pub async fn send_squads_proposal(prepared: PreparedSquadsProposal, rpc: &dyn SolanaRpc) -> Result<SquadsProposal, Error> {
+    let mut transaction = Transaction::new_unsigned(prepared.message);
+    prepared.pool.sign_partial(&mut transaction)?;
+    let signature = rpc
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to send proposal transaction", &e))?;
+
+    Ok(SquadsProposal {
+        multisig: prepared.multisig.to_string(),
+        vault: prepared.vault.to_string(),
+        creator: prepared.creator.to_string(),
+        transaction_index: prepared.transaction_index,
+        transaction: transaction_address(&prepared.multisig, prepared.transaction_index).to_string(),
+        proposal: proposal_address(&prepared.multisig, prepared.transaction_index).to_string(),
+        threshold: prepared.threshold,
+        amount_lamports: prepared.amount_lamports,
+        fee_lamports: prepared.fee_lamports,
+        signature: signature.to_string(),
+        warnings: prepared.warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_system_interface::instruction::SystemInstruction;
+
+    const SOL: u64 = 1_000_000_000;
+
+    fn multisig_account(transaction_index: u64, members: &[(Pubkey, u8)]) -> Account {
+        let mut data = MULTISIG_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&transaction_index.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        // A rent collector, then the bump
+        data.push(1);
+        data.extend_from_slice(&[9; 32]);
+        data.push(255);
+        data.extend_from_slice(&(members.len() as u32).to_le_bytes());
+        for (key, permissions) in members {
+            data.extend_from_slice(key.as_ref());
+            data.push(*permissions);
+        }
+        Account { lamports: 1, data, owner: SQUADS_PROGRAM_ID, executable: false, rent_epoch: 0 }
+    }
+
+    #[test]
+    fn test_vault_transaction_message() {
+        let (multisig, validator_id) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let vault = vault_address(&multisig, 0);
+        let bytes = vault_transaction_message(&vault, &[build_fund_instruction(&vault, &validator_id, 42)]).unwrap();
+
+        // One writable signer (the vault), one writable account (the PDA) and the system program
+        assert_eq!(bytes[..4], [1, 1, 1, 3]);
+        assert_eq!(bytes[4..36], vault.to_bytes());
+        assert_eq!(bytes[36..68], generate_deposit_pda(&validator_id).to_bytes());
+        // One instruction: the system program with the vault and the PDA
+        let instruction = &bytes[100..];
+        assert_eq!(instruction[..5], [1, 2, 2, 0, 1]);
+        assert_eq!(u16::from_le_bytes([instruction[5], instruction[6]]), 12);
+        assert_eq!(bincode::deserialize::<SystemInstruction>(&instruction[7..19]).unwrap(), SystemInstruction::Transfer { lamports: 42 });
+        assert_eq!(instruction[19..], [0]);
+    }
+
+    #[tokio::test]
+    async fn test_squads_proposal() {
+        let member = Keypair::new();
+        let keypair_path = std::env::temp_dir().join(format!("dz_validator_pda_squads_{}.json", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&member, &keypair_path).unwrap();
+        let signers = FundingSigners::new(&keypair_path.to_string_lossy());
+        let (multisig, validator_id, voter) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let rpc = MockRpc::new()
+            .with_wallet(member.pubkey(), SOL)
+            .with_wallet(vault_address(&multisig, 0), SOL)
+            .with_gossip_node(validator_id)
+            .with_account(multisig, multisig_account(6, &[(member.pubkey(), 7), (voter, 2)]));
+
+        let prepared = prepare_squads_proposal(&validator_id, &multisig, 2 * SOL, &signers, &FundingPolicy::default(), &rpc).await.unwrap();
+        assert_eq!((prepared.transaction_index, prepared.threshold), (7, 2));
+        // The vault may be funded before the proposal is executed
+        assert!(prepared.warnings.iter().any(|warning| warning.code == WARN_VAULT_UNDERFUNDED));
+        let proposal = send_squads_proposal(prepared, &rpc).await.unwrap();
+        assert_eq!(proposal.proposal, proposal_address(&multisig, 7).to_string());
+
+        let sent = rpc.sent_transactions();
+        let message = &sent[0].message;
+        assert_eq!(message.instructions.len(), 2);
+        assert_eq!(message.instructions[0].data[..8], VAULT_TRANSACTION_CREATE);
+        assert_eq!(message.instructions[1].data[8..], [7, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(message.account_keys[0], member.pubkey());
+
+        // Members without the initiate permission cannot propose, and other accounts are not multisigs
+        let voters_only = decode_multisig(&multisig, Some(&multisig_account(6, &[(voter, 2)]))).unwrap();
+        assert!(!voters_only.can_initiate(&voter));
+        let mut not_multisig = multisig_account(6, &[]);
+        not_multisig.owner = solana_system_interface::program::ID;
+        assert!(decode_multisig(&multisig, Some(&not_multisig)).unwrap_err().is_user_error());
+        assert!(decode_multisig(&multisig, None).is_err());
+        std::fs::remove_file(&keypair_path).ok();
+    }
+}
@@ -0,0 +1,98 @@
+use crate::config::{default_data_dir, write_atomic};
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Number of recently used validators kept in the state file
+pub const MAX_RECENT_VALIDATORS: usize = 20;
+
+/// Local state remembered between runs (not configuration)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct State {
+    /// Recently used validator pubkeys, most recent first
+    #[serde(default)]
+    pub recent_validators: Vec<String>,
+}
+
+impl State {
+    /// Loads the state, returning an empty state if the file does not exist
+    ///
+    /// # Arguments
+    /// * `path` - Path to the state file
+    ///
+    /// # Returns
+    /// * `Result<State, Error>` - Loaded state or `Error::Io` if it cannot be read or parsed
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(State::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::Io(format!("Failed to read state {}: {}", path.display(), e)))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| Error::Io(format!("Failed to parse state {}: {}", path.display(), e)))
+    }
+
+    /// Saves the state atomically
+    ///
+    /// # Arguments
+    /// * `path` - Path to the state file
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Ok if the file was written
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Io(format!("Failed to serialize state: {}", e)))?;
+        write_atomic(path, contents.as_bytes())
+    }
+
+    /// Moves a validator to the front of the recently used list
+    ///
+    /// # Arguments
+    /// * `validator` - Validator pubkey
+    pub fn record_validator(&mut self, validator: &str) {
+        self.recent_validators.retain(|known| known != validator);
+        self.recent_validators.insert(0, validator.to_string());
+        self.recent_validators.truncate(MAX_RECENT_VALIDATORS);
+    }
+}
+
+/// Returns the default state file path (`~/.local/share/dz_validator_pda/state.json` on Linux)
+pub fn default_state_path() -> PathBuf {
+    default_data_dir().join("state.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_validator_keeps_most_recent_first() {
+        let mut state = State::default();
+        state.record_validator("A");
+        state.record_validator("B");
+        state.record_validator("A");
+        assert_eq!(state.recent_validators, vec!["A", "B"]);
+
+        for index in 0..MAX_RECENT_VALIDATORS {
+            state.record_validator(&index.to_string());
+        }
+        assert_eq!(state.recent_validators.len(), MAX_RECENT_VALIDATORS);
+        assert_eq!(state.recent_validators[0], (MAX_RECENT_VALIDATORS - 1).to_string());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_state_{}.json", std::process::id()));
+        assert_eq!(State::load(&path).unwrap(), State::default());
+
+        let mut state = State::default();
+        state.record_validator("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL");
+        state.save(&path).unwrap();
+        let loaded = State::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, state);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::audit::{AuditEntry, EVENT_PDA_FUND, STATUS_FAILED, STATUS_SUCCESS};
+use crate::fees::paid_fee;
+use crate::Error;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Window of `audit stats` when `--window` is not given
+pub const DEFAULT_STATS_WINDOW: &str = "30d";
+
+/// Time window of `audit stats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsWindow {
+    /// Entries of the last this many seconds
+    Last(u64),
+    /// Every entry of the audit log
+    All,
+}
+
+impl StatsWindow {
+    /// Parses a window: a number of minutes (`30m`), hours (`24h`), days (`7d`) or `all`
+    ///
+    /// # Arguments
+    /// * `value` - Value of `--window`
+    ///
+    /// # Returns
+    /// * `Result<StatsWindow, Error>` - Window or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        let invalid = || Error::InvalidInput(format!("Invalid window '{}': expected minutes (30m), hours (24h), days (7d) or all", value));
+        let value = value.trim().to_ascii_lowercase();
+        if value == "all" {
+            return Ok(StatsWindow::All);
+        }
+        let unit_secs = match value.chars().last() {
+            Some('m') => 60,
+            Some('h') => 3_600,
+            Some('d') => 86_400,
+            _ => return Err(invalid()),
+        };
+        let count = value[..value.len() - 1].parse::<u64>().map_err(|_| invalid())?;
+        if count == 0 {
+            return Err(invalid());
+        }
+        count.checked_mul(unit_secs).map(StatsWindow::Last).ok_or_else(invalid)
+    }
+
+    /// Returns the Unix timestamp of the start of the window
+    pub fn start(&self, now: u64) -> u64 {
+        match self {
+            StatsWindow::Last(secs) => now.saturating_sub(*secs),
+            StatsWindow::All => 0,
+        }
+    }
+}
+
+/// Fundings of one validator in the window
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ValidatorStats {
+    /// Validator identity (or `PDA <address>` for entries written before validators were recorded)
+    pub validator: String,
+    /// Successful fundings
+    pub fundings: u64,
+    /// Failed funding attempts
+    pub failed: u64,
+    /// Lamports transferred to the PDA
+    pub amount_lamports: u64,
+    /// Fees of the fundings in lamports
+    pub fee_lamports: u64,
+    /// Unix timestamp of the most recent successful funding
+    pub last_funded_at: Option<u64>,
+}
+
+/// Operational overview of the audit log over a time window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditStats {
+    /// Unix timestamp of the start of the window (0 for all entries)
+    pub since: u64,
+    /// Successful fundings
+    pub fundings: u64,
+    /// Failed funding attempts
+    pub failed: u64,
+    /// Failed attempts divided by all attempts (0 without attempts)
+    pub failure_rate: f64,
+    /// Lamports transferred to PDAs
+    pub moved_lamports: u64,
+    /// Fees paid by the tool (fundings and wallet rotations) in lamports
+    pub fee_lamports: u64,
+    /// Mean time from send to confirmation over the transactions the tool waited for
+    pub mean_confirmation_ms: Option<u64>,
+    /// Number of confirmation latencies behind the mean
+    pub confirmations: u64,
+    /// Per-validator totals, ordered by validator
+    pub validators: Vec<ValidatorStats>,
+}
+
+/// Returns the validator of a funding entry, falling back to the PDA recorded in its message
+fn funding_validator(entry: &AuditEntry) -> String {
+    match (&entry.validator, &entry.message) {
+        (Some(validator), _) => validator.clone(),
+        (None, Some(message)) if message.starts_with("PDA ") => message.clone(),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Summarizes fundings, failures, fees and confirmation latency of the audit log
+///
+/// # Arguments
+/// * `entries` - Audit log entries
+/// * `window` - Time window to summarize
+/// * `now` - Current Unix timestamp in seconds
+///
+/// # Returns
+/// * `AuditStats` - Totals and per-validator breakdown
+pub fn audit_stats(entries: &[AuditEntry], window: StatsWindow, now: u64) -> AuditStats {
+    let since = window.start(now);
+    let in_window: Vec<&AuditEntry> = entries.iter().filter(|entry| (since..=now).contains(&entry.timestamp)).collect();
+
+    let mut validators: BTreeMap<String, ValidatorStats> = BTreeMap::new();
+    for entry in in_window.iter().filter(|entry| entry.event == EVENT_PDA_FUND) {
+        let validator = funding_validator(entry);
+        let stats = validators
+            .entry(validator.clone())
+            .or_insert_with(|| ValidatorStats { validator, ..ValidatorStats::default() });
+        match entry.status.as_str() {
+            STATUS_SUCCESS => {
+                stats.fundings += 1;
+                stats.amount_lamports += entry.amount_lamports.unwrap_or(0);
+                stats.fee_lamports += entry.fee_lamports.unwrap_or(0);
+                stats.last_funded_at = stats.last_funded_at.max(Some(entry.timestamp));
+            }
+            STATUS_FAILED => stats.failed += 1,
+            _ => {}
+        }
+    }
+
+    let fundings: u64 = validators.values().map(|stats| stats.fundings).sum();
+    let failed: u64 = validators.values().map(|stats| stats.failed).sum();
+    let latencies: Vec<u64> = in_window.iter().filter_map(|entry| entry.confirmation_ms).collect();
+
+    AuditStats {
+        since,
+        fundings,
+        failed,
+        failure_rate: if fundings + failed == 0 { 0.0 } else { failed as f64 / (fundings + failed) as f64 },
+        moved_lamports: validators.values().map(|stats| stats.amount_lamports).sum(),
+        fee_lamports: in_window.iter().filter_map(|entry| paid_fee(entry)).sum(),
+        mean_confirmation_ms: (!latencies.is_empty()).then(|| latencies.iter().sum::<u64>() / latencies.len() as u64),
+        confirmations: latencies.len() as u64,
+        validators: validators.into_values().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{EVENT_DEPOSIT_CREATED, EVENT_WALLET_ROTATE};
+
+    const DAY: u64 = 86_400;
+
+    fn funding(validator: &str, timestamp: u64, status: &str, amount_lamports: u64) -> AuditEntry {
+        AuditEntry {
+            timestamp,
+            validator: Some(validator.to_string()),
+            amount_lamports: Some(amount_lamports),
+            fee_lamports: Some(5_000),
+            ..AuditEntry::new(EVENT_PDA_FUND, status)
+        }
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(StatsWindow::parse("24h").unwrap(), StatsWindow::Last(DAY));
+        assert_eq!(StatsWindow::parse("7D").unwrap(), StatsWindow::Last(7 * DAY));
+        assert_eq!(StatsWindow::parse("all").unwrap(), StatsWindow::All);
+        assert_eq!(StatsWindow::parse("30m").unwrap(), StatsWindow::Last(1_800));
+        assert_eq!(StatsWindow::parse(DEFAULT_STATS_WINDOW).unwrap().start(100 * DAY), 70 * DAY);
+        assert!(StatsWindow::parse("0d").unwrap_err().is_user_error());
+        assert!(StatsWindow::parse("7w").is_err());
+        assert!(StatsWindow::parse("d").is_err());
+    }
+
+    #[test]
+    fn test_audit_stats() {
+        let now = 100 * DAY;
+        let legacy = AuditEntry {
+            timestamp: now - 3 * DAY,
+            message: Some("PDA Legacy".to_string()),
+            amount_lamports: Some(1),
+            ..AuditEntry::new(EVENT_PDA_FUND, STATUS_SUCCESS)
+        };
+        let entries = vec![
+            funding("A", now - 40 * DAY, STATUS_SUCCESS, 1_000_000_000),
+            funding("A", now - 2 * DAY, STATUS_SUCCESS, 2_000_000_000),
+            funding("A", now - DAY, STATUS_FAILED, 0),
+            funding("B", now - DAY, STATUS_SUCCESS, 500_000_000),
+            legacy,
+            AuditEntry { timestamp: now - DAY, fee_lamports: Some(5_000), ..AuditEntry::new(EVENT_WALLET_ROTATE, STATUS_SUCCESS) },
+            AuditEntry { timestamp: now - DAY, confirmation_ms: Some(1_500), ..AuditEntry::new(EVENT_DEPOSIT_CREATED, STATUS_SUCCESS) },
+            AuditEntry { timestamp: now - DAY, confirmation_ms: Some(2_500), ..AuditEntry::new(EVENT_DEPOSIT_CREATED, STATUS_SUCCESS) },
+        ];
+
+        let stats = audit_stats(&entries, StatsWindow::Last(7 * DAY), now);
+        assert_eq!(stats.fundings, 3);
+        assert_eq!(stats.failed, 1);
+        assert_eq!(stats.failure_rate, 0.25);
+        assert_eq!(stats.moved_lamports, 2_500_000_001);
+        // Two successful fundings with a fee and the wallet rotation; the failure paid nothing
+        assert_eq!(stats.fee_lamports, 15_000);
+        assert_eq!(stats.mean_confirmation_ms, Some(2_000));
+        assert_eq!(stats.confirmations, 2);
+
+        let names: Vec<&str> = stats.validators.iter().map(|stats| stats.validator.as_str()).collect();
+        assert_eq!(names, ["A", "B", "PDA Legacy"]);
+        assert_eq!(stats.validators[0].fundings, 1);
+        assert_eq!(stats.validators[0].failed, 1);
+        assert_eq!(stats.validators[0].last_funded_at, Some(now - 2 * DAY));
+
+        let all = audit_stats(&entries, StatsWindow::All, now);
+        assert_eq!(all.fundings, 4);
+        assert_eq!(all.validators[0].amount_lamports, 3_000_000_000);
+
+        let empty = audit_stats(&[], StatsWindow::All, now);
+        assert_eq!(empty.failure_rate, 0.0);
+        assert_eq!(empty.mean_confirmation_ms, None);
+    }
+}
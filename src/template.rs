@@ -0,0 +1,195 @@
+use crate::i18n::{self, Lang};
+use crate::Error;
+
+/// A placeholder available to `--format` templates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TemplateField {
+    /// Placeholder name, written as `{{name}}`
+    pub name: &'static str,
+    /// What the placeholder is replaced with
+    pub description: &'static str,
+    /// What the placeholder is replaced with, in Russian
+    pub description_ru: &'static str,
+}
+
+impl TemplateField {
+    /// Returns the description in the language of this process
+    pub fn description_text(&self) -> &'static str {
+        match i18n::lang() {
+            Lang::En => self.description,
+            Lang::Ru => self.description_ru,
+        }
+    }
+}
+
+/// Fields of `pda-address --format`
+pub const PDA_ADDRESS_FIELDS: &[TemplateField] = &[
+    TemplateField {
+        name: "validator",
+        description: "Validator identity pubkey (or the alias as given)",
+        description_ru: "Pubkey identity валидатора (или алиас, как указан)",
+    },
+    TemplateField { name: "pda", description: "Deposit PDA", description_ru: "PDA депозита" },
+    TemplateField { name: "bump", description: "Bump seed of the PDA", description_ru: "Bump seed PDA" },
+    TemplateField { name: "program_id", description: "Program the PDA is derived for", description_ru: "Программа, для которой выводится PDA" },
+    TemplateField { name: "seed_prefix", description: "Seed prefix of the PDA", description_ru: "Префикс сида PDA" },
+    TemplateField { name: "pda_url", description: "Explorer link of the PDA (with --explorer)", description_ru: "Ссылка на PDA в обозревателе (с --explorer)" },
+];
+
+/// Fields of `pda-balance --format`
+pub const PDA_BALANCE_FIELDS: &[TemplateField] = &[
+    TemplateField {
+        name: "validator",
+        description: "Validator identity pubkey (or the alias as given)",
+        description_ru: "Pubkey identity валидатора (или алиас, как указан)",
+    },
+    TemplateField { name: "pda", description: "Deposit PDA", description_ru: "PDA депозита" },
+    TemplateField { name: "balance_lamports", description: "PDA balance in lamports", description_ru: "Баланс PDA в лампортах" },
+    TemplateField { name: "balance_sol", description: "PDA balance in SOL", description_ru: "Баланс PDA в SOL" },
+    TemplateField { name: "pda_url", description: "Explorer link of the PDA (with --explorer)", description_ru: "Ссылка на PDA в обозревателе (с --explorer)" },
+];
+
+/// Fields of `pda-batch --format`, rendered once per validator
+pub const BATCH_FIELDS: &[TemplateField] = &[
+    TemplateField { name: "name", description: "Alias of the validator (empty if none)", description_ru: "Алиас валидатора (пусто, если нет)" },
+    TemplateField { name: "validator", description: "Validator identity pubkey", description_ru: "Pubkey identity валидатора" },
+    TemplateField { name: "pda", description: "Deposit PDA", description_ru: "PDA депозита" },
+    TemplateField {
+        name: "in_gossip",
+        description: "true or false (empty with --skip-gossip-check)",
+        description_ru: "true или false (пусто с --skip-gossip-check)",
+    },
+    TemplateField {
+        name: "balance_lamports",
+        description: "PDA balance in lamports (empty if not read)",
+        description_ru: "Баланс PDA в лампортах (пусто, если не прочитан)",
+    },
+    TemplateField { name: "balance_sol", description: "PDA balance in SOL (empty if not read)", description_ru: "Баланс PDA в SOL (пусто, если не прочитан)" },
+    TemplateField {
+        name: "activated_stake",
+        description: "Activated stake in lamports (with --sort stake)",
+        description_ru: "Активированный стейк в лампортах (с --sort stake)",
+    },
+    TemplateField {
+        name: "error",
+        description: "Error of a row that could not be collected (empty otherwise)",
+        description_ru: "Ошибка строки, которую не удалось собрать (иначе пусто)",
+    },
+];
+
+/// Returns the `--format` fields of an operation
+///
+/// # Arguments
+/// * `operation` - Operation name
+///
+/// # Returns
+/// * `Option<&[TemplateField]>` - Fields, or None if the operation does not support `--format`
+pub fn template_fields(operation: &str) -> Option<&'static [TemplateField]> {
+    match operation {
+        "pda-address" => Some(PDA_ADDRESS_FIELDS),
+        "pda-balance" => Some(PDA_BALANCE_FIELDS),
+        "pda-batch" => Some(BATCH_FIELDS),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Field(&'static str),
+}
+
+/// Output template given with `--format`, e.g. `'{{validator}} {{pda}} {{balance_sol}}'`
+///
+/// Placeholders are `{{field}}` (spaces inside the braces are ignored); `\n` and `\t` in the
+/// template stand for a newline and a tab, since shells pass them through single quotes verbatim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTemplate {
+    parts: Vec<Part>,
+}
+
+impl OutputTemplate {
+    /// Parses a template, checking every placeholder against the fields of the operation
+    ///
+    /// # Arguments
+    /// * `template` - Value of `--format`
+    /// * `fields` - Fields the operation provides
+    ///
+    /// # Returns
+    /// * `Result<OutputTemplate, Error>` - Template, or `Error::InvalidInput` for an unknown or unclosed placeholder
+    pub fn parse(template: &str, fields: &[TemplateField]) -> Result<Self, Error> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            push_literal(&mut parts, &rest[..start]);
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| Error::InvalidInput(format!("Invalid --format: unclosed placeholder in '{}'", template)))?;
+            let name = after[..end].trim();
+            let field = fields.iter().find(|field| field.name == name).ok_or_else(|| {
+                let available = fields.iter().map(|field| field.name).collect::<Vec<_>>().join(", ");
+                Error::InvalidInput(format!("Invalid --format: unknown field '{}' (available: {})", name, available))
+            })?;
+            parts.push(Part::Field(field.name));
+            rest = &after[end + 2..];
+        }
+        push_literal(&mut parts, rest);
+        Ok(OutputTemplate { parts })
+    }
+
+    /// Renders the template with the values of a result
+    ///
+    /// Strings are inserted without quotes; missing and null values render as an empty string.
+    ///
+    /// # Arguments
+    /// * `values` - JSON object with the fields of the operation
+    ///
+    /// # Returns
+    /// * `String` - Rendered text
+    pub fn render(&self, values: &serde_json::Value) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(text) => text.clone(),
+                Part::Field(name) => match values.get(name) {
+                    None | Some(serde_json::Value::Null) => String::new(),
+                    Some(serde_json::Value::String(value)) => value.clone(),
+                    Some(value) => value.to_string(),
+                },
+            })
+            .collect()
+    }
+}
+
+fn push_literal(parts: &mut Vec<Part>, text: &str) {
+    if !text.is_empty() {
+        parts.push(Part::Literal(text.replace("\\n", "\n").replace("\\t", "\t")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let template = OutputTemplate::parse("{{validator}} {{ pda }}\\t{{balance_sol}}", PDA_BALANCE_FIELDS).unwrap();
+        let values = serde_json::json!({ "validator": "V", "pda": "P", "balance_lamports": 1_500_000_000u64, "balance_sol": "1.5" });
+        assert_eq!(template.render(&values), "V P\t1.5");
+
+        let template = OutputTemplate::parse("{{name}},{{balance_lamports}},{{in_gossip}}", BATCH_FIELDS).unwrap();
+        let values = serde_json::json!({ "name": null, "balance_lamports": 42, "in_gossip": true });
+        assert_eq!(template.render(&values), ",42,true");
+        assert_eq!(OutputTemplate::parse("plain", BATCH_FIELDS).unwrap().render(&values), "plain");
+    }
+
+    #[test]
+    fn test_parse_template_errors() {
+        let error = OutputTemplate::parse("{{balance_sol}}", PDA_ADDRESS_FIELDS).unwrap_err();
+        assert!(error.to_string().contains("unknown field 'balance_sol' (available: validator, pda, bump"));
+        assert!(OutputTemplate::parse("{{pda", PDA_ADDRESS_FIELDS).unwrap_err().is_user_error());
+        assert_eq!(template_fields("pda-batch"), Some(BATCH_FIELDS));
+        assert_eq!(template_fields("pda-fund-address"), None);
+    }
+}
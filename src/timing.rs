@@ -0,0 +1,154 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Calls per RPC method of this process, recorded by the HTTP transport
+static METHODS: Mutex<BTreeMap<String, MethodTiming>> = Mutex::new(BTreeMap::new());
+
+/// Latency and retries of one RPC method over the run (`--timing`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MethodTiming {
+    /// JSON-RPC method, e.g. `getLatestBlockhash`
+    pub method: String,
+    /// Requests sent, including ones repeated after a transient error
+    pub calls: u64,
+    /// Requests that failed (transport error or JSON-RPC error response)
+    pub errors: u64,
+    /// Resends after the endpoint answered HTTP 429
+    pub retries: u64,
+    /// Time spent in the requests, waits for the rate limit included
+    pub total_ms: u64,
+    /// Slowest request
+    pub max_ms: u64,
+    /// Part of `total_ms` spent waiting for the client-side rate limit or a 429 backoff
+    pub rate_limited_ms: u64,
+}
+
+impl MethodTiming {
+    /// Mean time of a request
+    pub fn avg_ms(&self) -> u64 {
+        self.total_ms.checked_div(self.calls).unwrap_or(0)
+    }
+}
+
+/// Turns recording of RPC calls on or off for the process (off by default)
+///
+/// # Arguments
+/// * `enabled` - Record every RPC request for the `--timing` report
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns true if RPC calls are recorded
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Records one RPC request (a no-op unless recording is enabled)
+///
+/// # Arguments
+/// * `method` - JSON-RPC method
+/// * `elapsed` - Time from the call until the response, including rate limit waits and 429 retries
+/// * `retries` - Resends after HTTP 429
+/// * `rate_limited` - Time spent waiting for the rate limit
+/// * `failed` - True if the request returned an error
+pub fn record(method: &str, elapsed: Duration, retries: u32, rate_limited: Duration, failed: bool) {
+    if !enabled() {
+        return;
+    }
+    let mut methods = METHODS.lock().unwrap_or_else(|e| e.into_inner());
+    let timing = methods.entry(method.to_string()).or_insert_with(|| MethodTiming { method: method.to_string(), ..MethodTiming::default() });
+    let elapsed_ms = elapsed.as_millis() as u64;
+    timing.calls += 1;
+    timing.errors += failed as u64;
+    timing.retries += retries as u64;
+    timing.total_ms += elapsed_ms;
+    timing.max_ms = timing.max_ms.max(elapsed_ms);
+    timing.rate_limited_ms += rate_limited.as_millis() as u64;
+}
+
+/// Returns the recorded methods, the most time-consuming first
+///
+/// # Returns
+/// * `Option<Vec<MethodTiming>>` - Recorded methods, or None unless recording is enabled
+pub fn report() -> Option<Vec<MethodTiming>> {
+    if !enabled() {
+        return None;
+    }
+    let mut timings: Vec<MethodTiming> = METHODS.lock().unwrap_or_else(|e| e.into_inner()).values().cloned().collect();
+    timings.sort_by(|a, b| b.total_ms.cmp(&a.total_ms).then_with(|| a.method.cmp(&b.method)));
+    Some(timings)
+}
+
+/// Formats the `--timing` table with a total row
+///
+/// # Arguments
+/// * `timings` - Recorded methods (see `report`)
+pub fn format_timing_table(timings: &[MethodTiming]) -> String {
+    let mut table = format!(
+        "{:<34} {:>6} {:>6} {:>7} {:>10} {:>8} {:>8}\n",
+        "RPC METHOD", "CALLS", "ERRORS", "RETRIES", "TOTAL ms", "AVG ms", "MAX ms"
+    );
+    let row = |timing: &MethodTiming| {
+        format!(
+            "{:<34} {:>6} {:>6} {:>7} {:>10} {:>8} {:>8}\n",
+            timing.method,
+            timing.calls,
+            timing.errors,
+            timing.retries,
+            timing.total_ms,
+            timing.avg_ms(),
+            timing.max_ms
+        )
+    };
+    for timing in timings {
+        table.push_str(&row(timing));
+    }
+    let total = timings.iter().fold(MethodTiming { method: "total".to_string(), ..MethodTiming::default() }, |mut total, timing| {
+        total.calls += timing.calls;
+        total.errors += timing.errors;
+        total.retries += timing.retries;
+        total.total_ms += timing.total_ms;
+        total.max_ms = total.max_ms.max(timing.max_ms);
+        total
+    });
+    table.push_str(&row(&total));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_report() {
+        // Nothing is recorded until --timing turns it on
+        record("testSlot", Duration::from_millis(10), 0, Duration::ZERO, false);
+        assert_eq!(report(), None);
+
+        set_enabled(true);
+        record("testBalance", Duration::from_millis(40), 0, Duration::ZERO, false);
+        record("testBalance", Duration::from_millis(120), 2, Duration::from_millis(100), true);
+        record("testBlockhash", Duration::from_millis(30), 0, Duration::ZERO, false);
+        // Requests of tests running alongside may be recorded too
+        let timings: Vec<MethodTiming> = report().unwrap().into_iter().filter(|timing| timing.method.starts_with("test")).collect();
+        set_enabled(false);
+
+        assert_eq!(timings.iter().map(|timing| timing.method.as_str()).collect::<Vec<_>>(), vec!["testBalance", "testBlockhash"]);
+        assert_eq!(
+            timings[0],
+            MethodTiming { method: "testBalance".to_string(), calls: 2, errors: 1, retries: 2, total_ms: 160, max_ms: 120, rate_limited_ms: 100 }
+        );
+        assert_eq!(timings[0].avg_ms(), 80);
+
+        let table = format_timing_table(&timings);
+        assert!(table.starts_with("RPC METHOD"));
+        assert!(table.lines().last().unwrap().starts_with("total"));
+        assert!(table.lines().last().unwrap().contains("190"));
+        assert_eq!(MethodTiming::default().avg_ms(), 0);
+    }
+}
@@ -0,0 +1,458 @@
+use crate::amount::parse_token_amount;
+use crate::priority::{self, compute_budget_instructions};
+use crate::report::strict_check;
+use crate::rpc::SolanaRpc;
+use crate::signer::SignerPool;
+use crate::{
+    check_funding_policy, check_funding_sufficiency, generate_deposit_pda, load_required_signers, Error, FundingAccounts,
+    FundingPolicy, FundingSigners, Warning,
+};
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::Message;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use spl_token_interface::state::{Account as TokenAccount, AccountState, Mint};
+
+/// SPL Token program (`TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`)
+pub const TOKEN_PROGRAM_ID: Pubkey = spl_token_interface::ID;
+
+/// Associated Token Account program
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("ATokenGPvbdGVxr1b9hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Instruction of the associated token account program that creates the account unless it already exists
+const CREATE_IDEMPOTENT: u8 = 1;
+
+/// Returns the associated token account of a wallet (or PDA) for a mint
+///
+/// # Arguments
+/// * `owner` - Owner of the token account
+/// * `mint` - Token mint
+///
+/// # Returns
+/// * `Pubkey` - Address derived from `[owner, token program, mint]` under the associated token account program
+pub fn get_associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()], &ASSOCIATED_TOKEN_PROGRAM_ID).0
+}
+
+/// Returns an instruction that creates the associated token account of `owner`, or does nothing if it exists
+///
+/// # Arguments
+/// * `payer` - Wallet paying the rent of the new account
+/// * `owner` - Owner of the token account
+/// * `mint` - Token mint
+pub fn create_associated_token_account_idempotent(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(get_associated_token_address(owner, mint), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data: vec![CREATE_IDEMPOTENT],
+    }
+}
+
+/// Decodes a mint account and returns its decimals
+///
+/// # Arguments
+/// * `mint` - Mint address
+/// * `account` - Account data, if the account exists
+///
+/// # Returns
+/// * `Result<u8, Error>` - Decimal places, or `Error::InvalidInput` if the account does not exist
+///   or is not an initialized SPL Token mint
+pub fn decode_mint(mint: &Pubkey, account: Option<&Account>) -> Result<u8, Error> {
+    let account = account.ok_or_else(|| Error::InvalidInput(format!("Mint {} does not exist", mint)))?;
+    if account.owner != TOKEN_PROGRAM_ID {
+        return Err(Error::InvalidInput(format!(
+            "Account {} is not an SPL Token mint (owner {})",
+            mint, account.owner
+        )));
+    }
+    let state = Mint::unpack(&account.data).map_err(|e| Error::InvalidInput(format!("Failed to decode mint {}: {}", mint, e)))?;
+    Ok(state.decimals)
+}
+
+/// Decodes a token account and checks that it holds `mint` for `owner`
+///
+/// # Arguments
+/// * `address` - Token account address
+/// * `account` - Account data, if the account exists
+/// * `mint` - Expected mint
+/// * `owner` - Expected owner
+///
+/// # Returns
+/// * `Result<Option<u64>, Error>` - Token balance in base units, None if the account does not exist,
+///   or `Error::InvalidInput` if it is not a usable token account of `owner` for `mint`
+pub fn decode_token_account(address: &Pubkey, account: Option<&Account>, mint: &Pubkey, owner: &Pubkey) -> Result<Option<u64>, Error> {
+    let Some(account) = account else {
+        return Ok(None);
+    };
+    if account.owner != TOKEN_PROGRAM_ID {
+        return Err(Error::InvalidInput(format!(
+            "Account {} is not an SPL token account (owner {})",
+            address, account.owner
+        )));
+    }
+    let state = TokenAccount::unpack(&account.data)
+        .map_err(|e| Error::InvalidInput(format!("Failed to decode token account {}: {}", address, e)))?;
+    if state.mint != *mint || state.owner != *owner {
+        return Err(Error::InvalidInput(format!(
+            "Token account {} holds mint {} for {}, expected mint {} for {}",
+            address, state.mint, state.owner, mint, owner
+        )));
+    }
+    if state.state == AccountState::Frozen {
+        return Err(Error::InvalidInput(format!("Token account {} is frozen", address)));
+    }
+    Ok(Some(state.amount))
+}
+
+/// Returns the instructions of a token deposit to a validator PDA
+///
+/// # Arguments
+/// * `validator_id` - The validator's public key
+/// * `accounts` - Token owner and fee payer (nonces are not supported)
+/// * `mint` - Token mint
+/// * `amount` - Amount in base units of the mint
+/// * `decimals` - Decimal places of the mint, checked by the token program
+/// * `priority_fee` - Priority fee in micro-lamports per compute unit (optional)
+///
+/// # Returns
+/// * `Result<Vec<Instruction>, Error>` - Compute budget (if prioritized), idempotent create of the PDA's
+///   token account, then the checked transfer
+pub fn token_funding_instructions(
+    validator_id: &Pubkey,
+    accounts: &FundingAccounts,
+    mint: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    priority_fee: Option<u64>,
+) -> Result<Vec<Instruction>, Error> {
+    let deposit_key = generate_deposit_pda(validator_id);
+    let mut instructions = priority_fee.map(compute_budget_instructions).unwrap_or_default();
+    instructions.push(create_associated_token_account_idempotent(&accounts.fee_payer, &deposit_key, mint));
+    instructions.push(
+        spl_token_interface::instruction::transfer_checked(
+            &TOKEN_PROGRAM_ID,
+            &get_associated_token_address(&accounts.funder, mint),
+            mint,
+            &get_associated_token_address(&deposit_key, mint),
+            &accounts.funder,
+            &[],
+            amount,
+            decimals,
+        )
+        .map_err(|e| Error::InvalidInput(format!("Failed to build token transfer: {}", e)))?,
+    );
+    Ok(instructions)
+}
+
+/// Result of a successful token deposit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenFundingOutcome {
+    /// Token owner that sent the tokens
+    pub payer: String,
+    /// Transaction signature
+    pub signature: String,
+    /// Token mint
+    pub mint: String,
+    /// Token account of the PDA that received the tokens
+    pub token_account: String,
+    /// Amount transferred in base units of the mint
+    pub amount: u64,
+    /// Decimal places of the mint
+    pub decimals: u8,
+    /// Transaction fee in lamports
+    pub fee_lamports: u64,
+    /// Soft issues found by the health policy (WARN verdict)
+    pub warnings: Vec<Warning>,
+    /// True if the PDA's token account did not exist before this transfer
+    pub creates_token_account: bool,
+}
+
+/// A checked token deposit that has not been signed or sent yet
+pub struct PreparedTokenFunding {
+    /// Token owner the tokens are transferred from
+    pub payer: Pubkey,
+    /// Deposit PDA owning the receiving token account
+    pub pda: Pubkey,
+    /// Token mint
+    pub mint: Pubkey,
+    /// Associated token account of the PDA
+    pub token_account: Pubkey,
+    /// Amount to transfer in base units of the mint
+    pub amount: u64,
+    /// Decimal places of the mint
+    pub decimals: u8,
+    /// Transaction fee in lamports
+    pub fee_lamports: u64,
+    /// Rent paid by the fee payer for the PDA's token account (0 if it exists)
+    pub rent_lamports: u64,
+    /// Token balance of the payer after the transfer, in base units
+    pub payer_token_balance_after: u64,
+    /// Soft issues found by the health policy and the pre-flight checks
+    pub warnings: Vec<Warning>,
+    /// True if the PDA's token account does not exist yet
+    pub creates_token_account: bool,
+    pool: SignerPool,
+    message: Message,
+}
+
+/// Runs every check of a token deposit and builds its transaction without signing or sending it
+///
+/// # Arguments
+/// * `validator_id` - The validator's public key
+/// * `mint` - Token mint
+/// * `amount` - Amount in whole tokens (e.g. "2.5"), converted with the mint's decimals
+/// * `signers` - Token owner, fee payer and additional signers (see `signer::FundingSigners`)
+/// * `policy` - Health policy the validator must satisfy before funding
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<PreparedTokenFunding, Error>` - Deposit with its amount, fee and resulting balances, or error
+pub async fn prepare_token_funding(
+    validator_id: &Pubkey,
+    mint: &Pubkey,
+    amount: &str,
+    signers: &FundingSigners,
+    policy: &FundingPolicy,
+    rpc: &dyn SolanaRpc,
+) -> Result<PreparedTokenFunding, Error> {
+    // Check the validator against the health policy (gossip presence, vote account)
+    let mut warnings = check_funding_policy(validator_id, policy, rpc).await?;
+    let (pool, accounts) = load_required_signers(signers, None)?;
+
+    // The amount is given in whole tokens, so the mint must be read before it can be converted
+    let mint_account = rpc
+        .get_account_with_context(mint)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get mint account", &e))?
+        .value;
+    let decimals = decode_mint(mint, mint_account.as_ref())?;
+    let amount = parse_token_amount(amount, decimals)?;
+
+    let source = get_associated_token_address(&accounts.funder, mint);
+    let source_account = rpc
+        .get_account_with_context(&source)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get payer token account", &e))?
+        .value;
+    let source_balance = decode_token_account(&source, source_account.as_ref(), mint, &accounts.funder)?
+        .ok_or_else(|| Error::InsufficientFunds(format!("Payer {} has no token account for mint {}", accounts.funder, mint)))?;
+    if source_balance < amount {
+        return Err(Error::InsufficientFunds(format!(
+            "Insufficient token balance: need {} base units, have {} in {}",
+            amount, source_balance, source
+        )));
+    }
+
+    // The PDA's token account is created in the same transaction if it is missing
+    let deposit_key = generate_deposit_pda(validator_id);
+    let token_account = get_associated_token_address(&deposit_key, mint);
+    let destination_account = rpc
+        .get_account_with_context(&token_account)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get PDA token account", &e))?
+        .value;
+    let creates_token_account = decode_token_account(&token_account, destination_account.as_ref(), mint, &deposit_key)?.is_none();
+    let rent_lamports = if creates_token_account {
+        rpc.get_minimum_balance_for_rent_exemption(TokenAccount::LEN)
+            .await
+            .map_err(|e| Error::from_client_error("Failed to get rent-exempt minimum", &e))?
+    } else {
+        0
+    };
+
+    // A user-specified priority fee is checked against recent fees paid for the same accounts
+    if let Some(priority_fee) = policy.priority_fee {
+        let stats = priority::recent_priority_fees(&[source, token_account], rpc).await?;
+        let outlier: Vec<Warning> = priority::check_priority_fee(priority_fee, stats.as_ref(), policy.force)?.into_iter().collect();
+        if policy.strict {
+            strict_check(&outlier)?;
+        }
+        warnings.extend(outlier);
+    }
+
+    let recent_blockhash = rpc
+        .get_latest_blockhash()
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get recent blockhash", &e))?;
+    let message = token_funding_message(validator_id, &accounts, mint, amount, decimals, policy.priority_fee, &recent_blockhash)?;
+    let fee_lamports = rpc
+        .get_fee_for_message(&message)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to estimate transaction fee", &e))?;
+
+    // The fee payer covers the fee and the rent of a new token account, and keeps the wallet reserve
+    let fee_payer_balance = rpc
+        .get_balance(&accounts.fee_payer)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get fee payer balance", &e))?;
+    check_funding_sufficiency(fee_payer_balance, rent_lamports, fee_lamports, policy.reserve_lamports)?;
+
+    Ok(PreparedTokenFunding {
+        payer: accounts.funder,
+        pda: deposit_key,
+        mint: *mint,
+        token_account,
+        amount,
+        decimals,
+        fee_lamports,
+        rent_lamports,
+        payer_token_balance_after: source_balance - amount,
+        warnings,
+        creates_token_account,
+        pool,
+        message,
+    })
+}
+
+fn token_funding_message(
+    validator_id: &Pubkey,
+    accounts: &FundingAccounts,
+    mint: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    priority_fee: Option<u64>,
+    blockhash: &Hash,
+) -> Result<Message, Error> {
+    let instructions = token_funding_instructions(validator_id, accounts, mint, amount, decimals, priority_fee)?;
+    Ok(Message::new_with_blockhash(&instructions, Some(&accounts.fee_payer), blockhash))
+}
+
+/// Signs and sends a prepared token deposit
+///
+/// # Arguments
+/// * `prepared` - Deposit returned by `prepare_token_funding`
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<TokenFundingOutcome, Error>` - Transaction signature with transferred amount and fee, or error
+pub async fn send_token_funding(prepared: PreparedTokenFunding, rpc: &dyn SolanaRpc) -> Result<TokenFundingOutcome, Error> {
+    let mut transaction = Transaction::new_unsigned(prepared.message);
+    prepared.pool.sign_partial(&mut transaction)?;
+    let signature = rpc
+        .send_transaction(&transaction)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to send transaction", &e))?;
+
+    Ok(TokenFundingOutcome {
+        payer: prepared.payer.to_string(),
+        signature: signature.to_string(),
+        mint: prepared.mint.to_string(),
+        token_account: prepared.token_account.to_string(),
+        amount: prepared.amount,
+        decimals: prepared.decimals,
+        fee_lamports: prepared.fee_lamports,
+        warnings: prepared.warnings,
+        creates_token_account: prepared.creates_token_account,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::{MockRpc, MOCK_FEE_PER_SIGNATURE};
+    use solana_sdk::program_option::COption;
+    use solana_sdk::signature::{Keypair, Signer};
+    use spl_token_interface::instruction::TokenInstruction;
+
+    const SOL: u64 = 1_000_000_000;
+
+    fn mint_account(decimals: u8) -> Account {
+        let mut data = vec![0; Mint::LEN];
+        let mint = Mint { mint_authority: COption::None, supply: 1_000_000_000, decimals, is_initialized: true, freeze_authority: COption::None };
+        Mint::pack(mint, &mut data).unwrap();
+        Account { lamports: 1, data, owner: TOKEN_PROGRAM_ID, executable: false, rent_epoch: 0 }
+    }
+
+    fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+        let mut data = vec![0; TokenAccount::LEN];
+        let state = TokenAccount { mint: *mint, owner: *owner, amount, state: AccountState::Initialized, ..TokenAccount::default() };
+        TokenAccount::pack(state, &mut data).unwrap();
+        Account { lamports: 1, data, owner: TOKEN_PROGRAM_ID, executable: false, rent_epoch: 0 }
+    }
+
+    #[test]
+    fn test_associated_token_address() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let expected = get_associated_token_address(&owner, &mint);
+        // Token accounts are PDAs: they have no private key, and owner and mint are not interchangeable
+        assert!(!expected.is_on_curve());
+        assert_ne!(get_associated_token_address(&owner, &mint), get_associated_token_address(&mint, &owner));
+
+        let instruction = create_associated_token_account_idempotent(&owner, &owner, &mint);
+        assert_eq!(instruction.data, vec![CREATE_IDEMPOTENT]);
+        assert_eq!(instruction.accounts[1].pubkey, expected);
+    }
+
+    #[test]
+    fn test_decode_token_accounts() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        assert_eq!(decode_mint(&mint, Some(&mint_account(6))).unwrap(), 6);
+        assert!(decode_mint(&mint, None).unwrap_err().is_user_error());
+        let mut not_a_mint = mint_account(6);
+        not_a_mint.owner = solana_system_interface::program::ID;
+        assert!(decode_mint(&mint, Some(&not_a_mint)).is_err());
+
+        let address = get_associated_token_address(&owner, &mint);
+        assert_eq!(decode_token_account(&address, None, &mint, &owner).unwrap(), None);
+        assert_eq!(decode_token_account(&address, Some(&token_account(&mint, &owner, 42)), &mint, &owner).unwrap(), Some(42));
+        let other_mint = token_account(&Pubkey::new_unique(), &owner, 42);
+        assert!(decode_token_account(&address, Some(&other_mint), &mint, &owner).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_token_funding_creates_pda_token_account() {
+        let payer = Keypair::new();
+        let keypair_path = std::env::temp_dir().join(format!("dz_validator_pda_token_{}.json", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&payer, &keypair_path).unwrap();
+        let keypair_path = keypair_path.to_string_lossy().to_string();
+        let validator_id = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let source = get_associated_token_address(&payer.pubkey(), &mint);
+        let rpc = MockRpc::new()
+            .with_wallet(payer.pubkey(), SOL)
+            .with_gossip_node(validator_id)
+            .with_account(mint, mint_account(6))
+            .with_account(source, token_account(&mint, &payer.pubkey(), 5_000_000));
+
+        let prepared = prepare_token_funding(&validator_id, &mint, "2.5", &FundingSigners::new(&keypair_path), &FundingPolicy::default(), &rpc)
+            .await
+            .unwrap();
+        assert_eq!(prepared.amount, 2_500_000);
+        assert_eq!(prepared.payer_token_balance_after, 2_500_000);
+        assert!(prepared.creates_token_account);
+        assert!(prepared.rent_lamports > 0);
+        assert_eq!(prepared.token_account, get_associated_token_address(&generate_deposit_pda(&validator_id), &mint));
+
+        let outcome = send_token_funding(prepared, &rpc).await.unwrap();
+        assert_eq!(outcome.fee_lamports, MOCK_FEE_PER_SIGNATURE);
+        let sent = rpc.sent_transactions();
+        let transaction = &sent[0];
+        let instructions = &transaction.message.instructions;
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(transaction.message.account_keys[instructions[0].program_id_index as usize], ASSOCIATED_TOKEN_PROGRAM_ID);
+        // The transfer is checked against the mint's decimals
+        let transfer = TokenInstruction::unpack(&instructions[1].data).unwrap();
+        assert_eq!(transfer, TokenInstruction::TransferChecked { amount: 2_500_000, decimals: 6 });
+
+        // More precision than the mint has, or more tokens than the payer holds, is rejected before sending
+        let signers = FundingSigners::new(&keypair_path);
+        let error = prepare_token_funding(&validator_id, &mint, "0.0000001", &signers, &FundingPolicy::default(), &rpc).await.err().unwrap();
+        assert!(error.to_string().contains("decimal places"));
+        let error = prepare_token_funding(&validator_id, &mint, "6", &signers, &FundingPolicy::default(), &rpc).await.err().unwrap();
+        assert!(matches!(error, Error::InsufficientFunds(_)), "{:?}", error);
+        assert_eq!(rpc.sent_transactions().len(), 1);
+        std::fs::remove_file(&keypair_path).ok();
+    }
+}
@@ -0,0 +1,97 @@
+use crate::Error;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// HTTP header carrying the trace id on RPC, webhook and Pushgateway requests
+pub const TRACE_ID_HEADER: &str = "x-trace-id";
+
+/// Longest accepted trace id
+const MAX_TRACE_ID_LEN: usize = 64;
+
+static TRACE_ID: OnceLock<String> = OnceLock::new();
+
+/// Generates a random 16-digit hex trace id
+pub fn new_trace_id() -> String {
+    // RandomState is seeded from the OS per instance; time and pid keep ids distinct even without it
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_nanos()).unwrap_or_default());
+    hasher.write_u32(std::process::id());
+    format!("{:016x}", hasher.finish())
+}
+
+/// Checks a trace id supplied by the caller (`--trace-id`)
+///
+/// # Arguments
+/// * `trace_id` - Trace id
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok for 1-64 ASCII letters, digits, `-` or `_`, otherwise `Error::InvalidInput`
+pub fn validate_trace_id(trace_id: &str) -> Result<(), Error> {
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if trace_id.is_empty() || trace_id.len() > MAX_TRACE_ID_LEN || !trace_id.chars().all(valid_char) {
+        return Err(Error::InvalidInput(format!(
+            "Invalid trace id '{}': expected 1-{} letters, digits, '-' or '_'",
+            trace_id, MAX_TRACE_ID_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Sets the trace id of this process (one command or funding operation)
+///
+/// Must be called before the first log event, audit entry or request; later calls are rejected.
+///
+/// # Arguments
+/// * `trace_id` - Trace id (see `new_trace_id` and `validate_trace_id`)
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if the trace id was set
+pub fn set_trace_id(trace_id: String) -> Result<(), Error> {
+    validate_trace_id(&trace_id)?;
+    TRACE_ID
+        .set(trace_id)
+        .map_err(|_| Error::InvalidInput("Trace id is already set".to_string()))
+}
+
+/// Returns the trace id of this process, if one was set
+pub fn trace_id() -> Option<&'static str> {
+    TRACE_ID.get().map(String::as_str)
+}
+
+/// Adds the trace id header to an outgoing HTTP request
+///
+/// # Arguments
+/// * `request` - Request being built
+///
+/// # Returns
+/// * `reqwest::RequestBuilder` - Request with the header if a trace id is set
+pub fn with_trace_header(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    match trace_id() {
+        Some(trace_id) => request.header(TRACE_ID_HEADER, trace_id),
+        None => request,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_trace_id() {
+        let trace_id = new_trace_id();
+        assert_eq!(trace_id.len(), 16);
+        assert!(validate_trace_id(&trace_id).is_ok());
+        assert_ne!(trace_id, new_trace_id());
+    }
+
+    #[test]
+    fn test_validate_trace_id() {
+        assert!(validate_trace_id("ci-run_42").is_ok());
+        assert!(validate_trace_id("").unwrap_err().is_user_error());
+        assert!(validate_trace_id("a b").is_err());
+        assert!(validate_trace_id("x\r\nInjected: header").is_err());
+        assert!(validate_trace_id(&"a".repeat(65)).is_err());
+    }
+}
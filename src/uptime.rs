@@ -0,0 +1,253 @@
+use crate::amount::parse_percent;
+use crate::rpc::SolanaRpc;
+use crate::Error;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_vote_interface::state::{VoteStateV3, MAX_EPOCH_CREDITS_HISTORY, VOTE_CREDITS_MAXIMUM_PER_SLOT};
+use std::collections::HashMap;
+
+/// Completed epochs `--require-uptime` looks back over when `--lookback-epochs` is not given
+pub const DEFAULT_LOOKBACK_EPOCHS: u64 = 10;
+
+/// Most epochs a vote account keeps credits for
+pub const MAX_LOOKBACK_EPOCHS: u64 = MAX_EPOCH_CREDITS_HISTORY as u64;
+
+/// Minimum uptime of a validator over recent epochs (`--require-uptime`, `--lookback-epochs`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct UptimeRequirement {
+    /// Minimum uptime in hundredths of a percent (9500 for 95%)
+    pub min_basis_points: u64,
+    /// Completed epochs to look back over
+    pub lookback_epochs: u64,
+}
+
+/// Parses the `--require-uptime` value
+///
+/// # Arguments
+/// * `value` - Percentage above 0 and at most 100 with up to two decimals, optionally with a `%` suffix
+///
+/// # Returns
+/// * `Result<u64, Error>` - Uptime in hundredths of a percent, or `Error::InvalidInput`
+pub fn parse_required_uptime(value: &str) -> Result<u64, Error> {
+    parse_percent(value, "require-uptime")
+}
+
+/// Parses the `--lookback-epochs` value
+///
+/// # Arguments
+/// * `value` - Number of completed epochs, at most `MAX_LOOKBACK_EPOCHS`
+///
+/// # Returns
+/// * `Result<u64, Error>` - Epochs, or `Error::InvalidInput`
+pub fn parse_lookback_epochs(value: &str) -> Result<u64, Error> {
+    match value.trim().parse::<u64>() {
+        Ok(epochs) if (1..=MAX_LOOKBACK_EPOCHS).contains(&epochs) => Ok(epochs),
+        _ => Err(Error::InvalidInput(format!(
+            "Invalid --lookback-epochs '{}': expected a number of epochs from 1 to {}",
+            value, MAX_LOOKBACK_EPOCHS
+        ))),
+    }
+}
+
+/// Formats hundredths of a percent (e.g. "97.31%")
+pub fn format_basis_points(basis_points: u64) -> String {
+    format!("{}.{:02}%", basis_points / 100, basis_points % 100)
+}
+
+fn basis_points(part: u64, total: u64) -> u64 {
+    (part as u128 * 10_000 / total.max(1) as u128) as u64
+}
+
+/// Vote credits of a validator in one completed epoch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EpochCredits {
+    /// Epoch
+    pub epoch: u64,
+    /// Credits earned in the epoch
+    pub credits: u64,
+    /// Credits of a validator voting on every slot in time (`VOTE_CREDITS_MAXIMUM_PER_SLOT` per slot)
+    pub max_credits: u64,
+}
+
+/// Uptime of a validator over recent epochs
+///
+/// Uptime is the lower of two rates: vote credits earned out of the most that could be earned in
+/// the completed epochs of the lookback, and blocks produced out of the leader slots of the
+/// previous and current epoch (the only ones `getBlockProduction` serves).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Uptime {
+    /// Vote account the credits were read from
+    pub vote_pubkey: String,
+    /// Completed epochs looked back over
+    pub lookback_epochs: u64,
+    /// Credits per epoch oldest first, from the first epoch the vote account has credits for
+    pub epochs: Vec<EpochCredits>,
+    /// Leader slots in the previous and current epoch
+    pub leader_slots: u64,
+    /// Blocks produced in those leader slots
+    pub blocks_produced: u64,
+}
+
+impl Uptime {
+    /// Vote credits earned out of the most possible, in hundredths of a percent (None without epochs)
+    pub fn vote_basis_points(&self) -> Option<u64> {
+        if self.epochs.is_empty() {
+            return None;
+        }
+        let credits = self.epochs.iter().map(|epoch| epoch.credits).sum();
+        let max_credits = self.epochs.iter().map(|epoch| epoch.max_credits).sum();
+        Some(basis_points(credits, max_credits))
+    }
+
+    /// Blocks produced out of the leader slots, in hundredths of a percent (None without leader slots)
+    pub fn block_basis_points(&self) -> Option<u64> {
+        (self.leader_slots > 0).then(|| basis_points(self.blocks_produced, self.leader_slots))
+    }
+
+    /// Uptime in hundredths of a percent: the lower of the vote and block rates (None without either)
+    pub fn basis_points(&self) -> Option<u64> {
+        match (self.vote_basis_points(), self.block_basis_points()) {
+            (Some(votes), Some(blocks)) => Some(votes.min(blocks)),
+            (votes, blocks) => votes.or(blocks),
+        }
+    }
+
+    /// Describes the uptime and how it was computed, e.g. "97.31% over epochs 690-699 (...)"
+    pub fn summary(&self) -> String {
+        let epochs = match (self.epochs.first(), self.epochs.last()) {
+            (Some(first), Some(last)) if first.epoch != last.epoch => format!("epochs {}-{}", first.epoch, last.epoch),
+            (Some(first), _) => format!("epoch {}", first.epoch),
+            _ => format!("the last {} epochs", self.lookback_epochs),
+        };
+        let votes = self.vote_basis_points().map(format_basis_points).unwrap_or_else(|| "none".to_string());
+        let blocks = match self.block_basis_points() {
+            Some(blocks) => format!("{} of {} leader slots, {}", self.blocks_produced, self.leader_slots, format_basis_points(blocks)),
+            None => "no leader slots".to_string(),
+        };
+        let uptime = self.basis_points().map(format_basis_points).unwrap_or_else(|| "unknown".to_string());
+        format!("{} over {} (vote credits {}; blocks {})", uptime, epochs, votes, blocks)
+    }
+}
+
+/// Returns the credits of the completed epochs of a lookback from the `epoch_credits` of a vote account
+///
+/// Epochs before the first one the vote account has credits for are left out, so a new validator
+/// is judged on the epochs it existed; later epochs without credits count as zero.
+///
+/// # Arguments
+/// * `epoch_credits` - `(epoch, credits, previous credits)` entries of the vote state
+/// * `current_epoch` - Current epoch, which is not complete and is left out
+/// * `lookback_epochs` - Completed epochs to look back over
+/// * `slots_in_epoch` - Slots per epoch
+///
+/// # Returns
+/// * `Vec<EpochCredits>` - Credits per epoch, oldest first
+pub fn epoch_credits_in_lookback(
+    epoch_credits: &[(u64, u64, u64)],
+    current_epoch: u64,
+    lookback_epochs: u64,
+    slots_in_epoch: u64,
+) -> Vec<EpochCredits> {
+    let earned: HashMap<u64, u64> = epoch_credits
+        .iter()
+        .map(|(epoch, credits, previous)| (*epoch, credits.saturating_sub(*previous)))
+        .collect();
+    let Some(first_epoch) = epoch_credits.iter().map(|(epoch, _, _)| *epoch).min() else {
+        return Vec::new();
+    };
+    (current_epoch.saturating_sub(lookback_epochs).max(first_epoch)..current_epoch)
+        .map(|epoch| EpochCredits {
+            epoch,
+            credits: earned.get(&epoch).copied().unwrap_or(0),
+            max_credits: slots_in_epoch * VOTE_CREDITS_MAXIMUM_PER_SLOT as u64,
+        })
+        .collect()
+}
+
+/// Computes the uptime of a validator from its vote account and `getBlockProduction`
+///
+/// # Arguments
+/// * `validator_id` - Validator identity, whose leader slots are counted
+/// * `vote_pubkey` - Vote account of the validator, whose credits are read
+/// * `lookback_epochs` - Completed epochs to look back over
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Uptime, Error>` - Uptime, or error if the vote account cannot be read
+pub async fn get_validator_uptime(validator_id: &Pubkey, vote_pubkey: &Pubkey, lookback_epochs: u64, rpc: &dyn SolanaRpc) -> Result<Uptime, Error> {
+    let epoch_info = rpc.get_epoch_info().await
+        .map_err(|e| Error::from_client_error("Failed to get epoch info", &e))?;
+    let account = rpc.get_account_with_context(vote_pubkey).await
+        .map_err(|e| Error::from_client_error("Failed to get vote account", &e))?
+        .value
+        .ok_or_else(|| Error::Rpc(format!("Vote account {} not found", vote_pubkey)))?;
+    let vote_state = VoteStateV3::deserialize(&account.data)
+        .map_err(|_| Error::Rpc(format!("Vote account {} has data that is not a vote state", vote_pubkey)))?;
+    let epochs = epoch_credits_in_lookback(vote_state.epoch_credits(), epoch_info.epoch, lookback_epochs, epoch_info.slots_in_epoch);
+
+    let epoch_start = epoch_info.absolute_slot.saturating_sub(epoch_info.slot_index);
+    let (leader_slots, blocks_produced) = rpc
+        .get_block_production(validator_id, epoch_start.saturating_sub(epoch_info.slots_in_epoch), epoch_info.absolute_slot)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get block production", &e))?;
+
+    Ok(Uptime { vote_pubkey: vote_pubkey.to_string(), lookback_epochs, epochs, leader_slots, blocks_produced })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::get_validator_vote_account;
+    use crate::rpc::mock::MockRpc;
+    use solana_sdk::account::Account;
+    use solana_vote_interface::state::VoteStateVersions;
+
+    #[test]
+    fn test_parse_uptime_flags() {
+        assert_eq!(parse_required_uptime("95").unwrap(), 9_500);
+        assert_eq!(parse_required_uptime("99.5%").unwrap(), 9_950);
+        assert_eq!(parse_required_uptime("100").unwrap(), 10_000);
+        for invalid in ["", "0", "100.01", "95.123", "-1", "abc", ".5"] {
+            assert!(parse_required_uptime(invalid).unwrap_err().is_user_error(), "{}", invalid);
+        }
+        assert_eq!(parse_lookback_epochs("10").unwrap(), 10);
+        assert!(parse_lookback_epochs("0").is_err() && parse_lookback_epochs("65").is_err());
+        assert_eq!(format_basis_points(9_731), "97.31%");
+    }
+
+    #[test]
+    fn test_epoch_credits_in_lookback() {
+        // Epoch 96 has no credits; epochs before 95 predate the vote account
+        let history = [(95, 100, 0), (97, 300, 100), (98, 390, 300), (100, 400, 390)];
+        let epochs = epoch_credits_in_lookback(&history, 100, 10, 10);
+        let credits: Vec<(u64, u64)> = epochs.iter().map(|epoch| (epoch.epoch, epoch.credits)).collect();
+        assert_eq!(credits, vec![(95, 100), (96, 0), (97, 200), (98, 90), (99, 0)]);
+        assert!(epochs.iter().all(|epoch| epoch.max_credits == 160));
+        assert_eq!(epoch_credits_in_lookback(&history, 100, 2, 10).len(), 2);
+        assert!(epoch_credits_in_lookback(&[], 100, 10, 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_validator_uptime() {
+        let identity = Pubkey::new_unique();
+        let rpc = MockRpc::new().with_vote_account(identity, 990, 1_000_000_000_000, false).with_block_production(identity, 40, 38);
+        let vote_pubkey = get_validator_vote_account(&identity, &rpc).await.unwrap().unwrap().vote_pubkey.parse::<Pubkey>().unwrap();
+
+        // 432,000 slots and 16 credits per slot: 6,912,000 credits at most per epoch
+        let vote_state = VoteStateV3 {
+            epoch_credits: vec![(497, 6_912_000, 0), (498, 13_478_400, 6_912_000), (499, 20_044_800, 13_478_400)],
+            ..VoteStateV3::default()
+        };
+        let data = bincode::serialize(&VoteStateVersions::new_v3(vote_state)).unwrap();
+        let rpc = rpc.with_account(vote_pubkey, Account { lamports: 1, data, owner: solana_vote_interface::program::ID, executable: false, rent_epoch: 0 });
+
+        let uptime = get_validator_uptime(&identity, &vote_pubkey, 10, &rpc).await.unwrap();
+        assert_eq!(uptime.epochs.len(), 3);
+        assert_eq!(uptime.vote_basis_points(), Some(9_666));
+        assert_eq!(uptime.block_basis_points(), Some(9_500));
+        assert_eq!(uptime.basis_points(), Some(9_500));
+        assert_eq!(uptime.summary(), "95.00% over epochs 497-499 (vote credits 96.66%; blocks 38 of 40 leader slots, 95.00%)");
+
+        assert!(get_validator_uptime(&identity, &Pubkey::new_unique(), 10, &rpc).await.is_err());
+    }
+}
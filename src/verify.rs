@@ -0,0 +1,337 @@
+use crate::rpc::{FetchedTransaction, SolanaRpc};
+use crate::{generate_deposit_pda, Error};
+use serde::Serialize;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_system_interface::instruction::SystemInstruction;
+use std::fmt;
+
+/// Verdict of `verify-deposit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositVerdict {
+    /// The transaction is finalized, succeeded and credited the PDA of the validator
+    Verified,
+    /// The transaction succeeded and credited the PDA, but is not finalized yet
+    NotFinalized,
+    /// The transaction succeeded but did not credit the PDA of the validator
+    WrongRecipient,
+    /// The transaction failed on chain, so nothing was transferred
+    Failed,
+    /// The node does not know the transaction
+    NotFound,
+}
+
+impl fmt::Display for DepositVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DepositVerdict::Verified => "VERIFIED",
+            DepositVerdict::NotFinalized => "NOT FINALIZED",
+            DepositVerdict::WrongRecipient => "WRONG RECIPIENT",
+            DepositVerdict::Failed => "FAILED",
+            DepositVerdict::NotFound => "NOT FOUND",
+        })
+    }
+}
+
+/// What a transaction did for the deposit PDA of a validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DepositVerification {
+    /// Transaction signature
+    pub signature: String,
+    /// Validator the payment is claimed for
+    pub validator: String,
+    /// Deposit PDA derived from the validator
+    pub pda: String,
+    /// Verdict
+    pub verdict: DepositVerdict,
+    /// Slot the transaction landed in (None if it was not found)
+    pub slot: Option<u64>,
+    /// Estimated time of its block as a Unix timestamp, if known
+    pub block_time: Option<i64>,
+    /// Slots the node has advanced since the transaction
+    pub depth: Option<u64>,
+    /// Whether the transaction is found at the finalized commitment
+    pub finalized: bool,
+    /// Increase of the PDA balance caused by the transaction in lamports
+    pub amount_lamports: u64,
+    /// Sources of the system transfers to the PDA (empty if it was credited through another program)
+    pub funders: Vec<String>,
+    /// Error of a transaction that failed on chain
+    pub error: Option<String>,
+}
+
+/// Returns the sources and amounts of the top-level system transfers to an account
+///
+/// # Arguments
+/// * `fetched` - Landed transaction
+/// * `recipient` - Account receiving the lamports
+///
+/// # Returns
+/// * `Vec<(Pubkey, u64)>` - Source and lamports of each transfer, in instruction order
+pub fn transfers_to(fetched: &FetchedTransaction, recipient: &Pubkey) -> Vec<(Pubkey, u64)> {
+    let keys = fetched.account_keys();
+    fetched
+        .transaction
+        .message
+        .instructions()
+        .iter()
+        .filter(|instruction| keys.get(instruction.program_id_index as usize) == Some(&solana_system_interface::program::ID))
+        .filter_map(|instruction| {
+            let key = |position: usize| keys.get(*instruction.accounts.get(position)? as usize);
+            let (from, to, lamports) = match bincode::deserialize::<SystemInstruction>(&instruction.data).ok()? {
+                SystemInstruction::Transfer { lamports } => (key(0)?, key(1)?, lamports),
+                SystemInstruction::TransferWithSeed { lamports, .. } => (key(0)?, key(2)?, lamports),
+                _ => return None,
+            };
+            (to == recipient).then_some((*from, lamports))
+        })
+        .collect()
+}
+
+/// Returns how much the balance of an account went up in a transaction, in lamports
+///
+/// Balance changes catch transfers made through other programs (e.g. a multisig) as well.
+pub fn balance_increase(fetched: &FetchedTransaction, account: &Pubkey) -> u64 {
+    let Some(index) = fetched.account_keys().iter().position(|key| key == account) else {
+        return 0;
+    };
+    match (fetched.pre_balances.get(index), fetched.post_balances.get(index)) {
+        (Some(pre), Some(post)) => post.saturating_sub(*pre),
+        _ => 0,
+    }
+}
+
+/// Checks that a transaction paid the deposit PDA of a validator (`verify-deposit`)
+///
+/// Nothing is taken from the claim but the signature and the validator: the PDA is derived locally,
+/// the transaction is fetched from the node and the PDA is credited only if its balance went up.
+///
+/// # Arguments
+/// * `signature` - Transaction signature
+/// * `validator_id` - Validator the payment is claimed for
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<DepositVerification, Error>` - Verification with its verdict, or error if the node could not be queried
+pub async fn verify_deposit(signature: &Signature, validator_id: &Pubkey, rpc: &dyn SolanaRpc) -> Result<DepositVerification, Error> {
+    let deposit_key = generate_deposit_pda(validator_id);
+    let mut verification = DepositVerification {
+        signature: signature.to_string(),
+        validator: validator_id.to_string(),
+        pda: deposit_key.to_string(),
+        verdict: DepositVerdict::NotFound,
+        slot: None,
+        block_time: None,
+        depth: None,
+        finalized: false,
+        amount_lamports: 0,
+        funders: Vec::new(),
+        error: None,
+    };
+
+    let mut fetched = rpc.get_transaction(signature, CommitmentConfig::finalized()).await
+        .map_err(|e| Error::from_client_error("Failed to get transaction", &e))?;
+    verification.finalized = fetched.is_some();
+    if fetched.is_none() {
+        fetched = rpc.get_transaction(signature, CommitmentConfig::confirmed()).await
+            .map_err(|e| Error::from_client_error("Failed to get transaction", &e))?;
+    }
+    let Some(fetched) = fetched else {
+        return Ok(verification);
+    };
+
+    let current_slot = rpc.get_slot().await
+        .map_err(|e| Error::from_client_error("Failed to get current slot", &e))?;
+    verification.slot = Some(fetched.slot);
+    verification.block_time = fetched.block_time;
+    verification.depth = Some(current_slot.saturating_sub(fetched.slot));
+    verification.error = fetched.error.as_ref().map(ToString::to_string);
+    verification.amount_lamports = balance_increase(&fetched, &deposit_key);
+    verification.funders = transfers_to(&fetched, &deposit_key).into_iter().map(|(from, _)| from.to_string()).collect();
+    verification.funders.dedup();
+    verification.verdict = if verification.error.is_some() {
+        DepositVerdict::Failed
+    } else if verification.amount_lamports == 0 {
+        DepositVerdict::WrongRecipient
+    } else if !verification.finalized {
+        DepositVerdict::NotFinalized
+    } else {
+        DepositVerdict::Verified
+    };
+    Ok(verification)
+}
+
+/// Change of the deposit PDA balance in a confirmed funding transaction (`--verify-delta`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BalanceDelta {
+    /// Transaction signature
+    pub signature: String,
+    /// Deposit PDA of the funded validator
+    pub pda: String,
+    /// Lamports the funding requested
+    pub expected_lamports: u64,
+    /// PDA balance before the transaction
+    pub pre_balance: u64,
+    /// PDA balance after the transaction
+    pub post_balance: u64,
+    /// Post-balance minus pre-balance (negative if the PDA lost lamports)
+    pub delta_lamports: i64,
+}
+
+impl BalanceDelta {
+    /// Returns whether the PDA balance changed by exactly the requested amount
+    pub fn matches(&self) -> bool {
+        i64::try_from(self.expected_lamports) == Ok(self.delta_lamports)
+    }
+
+    /// Returns `Error::BalanceMismatch` unless the PDA balance changed by exactly the requested amount
+    pub fn check(&self) -> Result<(), Error> {
+        if self.matches() {
+            return Ok(());
+        }
+        Err(Error::BalanceMismatch(format!(
+            "Transaction {} changed the balance of PDA {} by {} lamports instead of {} (pre {}, post {})",
+            self.signature, self.pda, self.delta_lamports, self.expected_lamports, self.pre_balance, self.post_balance
+        )))
+    }
+}
+
+/// Reads the PDA pre- and post-balances of a confirmed funding transaction (`--verify-delta`)
+///
+/// Side effects of other instructions in the same transaction (e.g. a program debiting the PDA)
+/// show up as a delta other than the requested amount; use `BalanceDelta::check` to reject them.
+///
+/// # Arguments
+/// * `signature` - Signature of the confirmed funding transaction
+/// * `validator_id` - Funded validator
+/// * `expected_lamports` - Lamports the funding requested
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<BalanceDelta, Error>` - Balance change, `Error::TransactionFailed` if the transaction failed,
+///   `Error::BalanceMismatch` if it does not touch the PDA, or `Error::Rpc` if it cannot be fetched
+pub async fn verify_balance_delta(
+    signature: &Signature,
+    validator_id: &Pubkey,
+    expected_lamports: u64,
+    rpc: &dyn SolanaRpc,
+) -> Result<BalanceDelta, Error> {
+    let fetched = rpc
+        .get_transaction(signature, CommitmentConfig::confirmed())
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get transaction", &e))?
+        .ok_or_else(|| Error::Rpc(format!("Transaction {} was not found by the node", signature)))?;
+    if let Some(error) = &fetched.error {
+        return Err(Error::TransactionFailed(format!("Transaction {} failed: {}", signature, error)));
+    }
+    let deposit_key = generate_deposit_pda(validator_id);
+    let index = fetched.account_keys().iter().position(|key| *key == deposit_key);
+    let balances = index.and_then(|index| Some((*fetched.pre_balances.get(index)?, *fetched.post_balances.get(index)?)));
+    let Some((pre_balance, post_balance)) = balances else {
+        return Err(Error::BalanceMismatch(format!("Transaction {} does not touch PDA {}", signature, deposit_key)));
+    };
+    Ok(BalanceDelta {
+        signature: signature.to_string(),
+        pda: deposit_key.to_string(),
+        expected_lamports,
+        pre_balance,
+        post_balance,
+        delta_lamports: post_balance as i64 - pre_balance as i64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+    use solana_sdk::message::Message;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::{Transaction, TransactionError, VersionedTransaction};
+
+    const SOL: u64 = 1_000_000_000;
+
+    fn transfer(payer: &Keypair, to: &Pubkey, lamports: u64, slot: u64) -> FetchedTransaction {
+        let instruction = solana_system_interface::instruction::transfer(&payer.pubkey(), to, lamports);
+        let message = Message::new(&[instruction], Some(&payer.pubkey()));
+        let transaction = Transaction::new(&[payer], message, solana_sdk::hash::Hash::new_unique());
+        FetchedTransaction {
+            slot,
+            block_time: Some(1_700_000_000),
+            transaction: VersionedTransaction::from(transaction),
+            error: None,
+            loaded_addresses: Vec::new(),
+            pre_balances: vec![10 * SOL, 0, 1],
+            post_balances: vec![10 * SOL - lamports - 5_000, lamports, 1],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_deposit() {
+        let (payer, validator_id) = (Keypair::new(), Pubkey::new_unique());
+        let deposit_key = generate_deposit_pda(&validator_id);
+        let paid = transfer(&payer, &deposit_key, 2 * SOL, 900);
+        let recent = transfer(&payer, &deposit_key, SOL, 990);
+        let elsewhere = transfer(&payer, &Pubkey::new_unique(), SOL, 900);
+        let mut failed = transfer(&payer, &deposit_key, 3 * SOL, 900);
+        failed.error = Some(TransactionError::InsufficientFundsForFee);
+        failed.post_balances = failed.pre_balances.clone();
+        let signature = |fetched: &FetchedTransaction| fetched.transaction.signatures[0];
+        let rpc = MockRpc::new()
+            .with_landed_transaction(paid.clone(), true)
+            .with_landed_transaction(recent.clone(), false)
+            .with_landed_transaction(elsewhere.clone(), true)
+            .with_landed_transaction(failed.clone(), true);
+
+        let verification = verify_deposit(&signature(&paid), &validator_id, &rpc).await.unwrap();
+        assert_eq!(verification.verdict, DepositVerdict::Verified);
+        assert_eq!(verification.amount_lamports, 2 * SOL);
+        assert_eq!(verification.funders, vec![payer.pubkey().to_string()]);
+        assert_eq!((verification.slot, verification.depth), (Some(900), Some(100)));
+
+        assert_eq!(verify_deposit(&signature(&recent), &validator_id, &rpc).await.unwrap().verdict, DepositVerdict::NotFinalized);
+        assert_eq!(verify_deposit(&signature(&elsewhere), &validator_id, &rpc).await.unwrap().verdict, DepositVerdict::WrongRecipient);
+        // The payment is checked against the PDA of the claimed validator, not any deposit PDA
+        assert_eq!(verify_deposit(&signature(&paid), &Pubkey::new_unique(), &rpc).await.unwrap().verdict, DepositVerdict::WrongRecipient);
+        let verification = verify_deposit(&signature(&failed), &validator_id, &rpc).await.unwrap();
+        assert_eq!(verification.verdict, DepositVerdict::Failed);
+        assert!(verification.error.is_some());
+        let unknown = verify_deposit(&Signature::new_unique(), &validator_id, &rpc).await.unwrap();
+        assert_eq!((unknown.verdict, unknown.slot), (DepositVerdict::NotFound, None));
+    }
+
+    #[tokio::test]
+    async fn test_verify_balance_delta() {
+        let (payer, validator_id) = (Keypair::new(), Pubkey::new_unique());
+        let deposit_key = generate_deposit_pda(&validator_id);
+        let exact = transfer(&payer, &deposit_key, 2 * SOL, 900);
+        // Another instruction took lamports back from the PDA in the same transaction
+        let mut partial = transfer(&payer, &deposit_key, 2 * SOL, 901);
+        partial.post_balances[1] = SOL;
+        let elsewhere = transfer(&payer, &Pubkey::new_unique(), SOL, 902);
+        let mut failed = transfer(&payer, &deposit_key, SOL, 903);
+        failed.error = Some(TransactionError::InsufficientFundsForFee);
+        let signature = |fetched: &FetchedTransaction| fetched.transaction.signatures[0];
+        let rpc = MockRpc::new()
+            .with_landed_transaction(exact.clone(), false)
+            .with_landed_transaction(partial.clone(), false)
+            .with_landed_transaction(elsewhere.clone(), false)
+            .with_landed_transaction(failed.clone(), false);
+
+        let delta = verify_balance_delta(&signature(&exact), &validator_id, 2 * SOL, &rpc).await.unwrap();
+        assert_eq!((delta.pre_balance, delta.post_balance, delta.delta_lamports), (0, 2 * SOL, 2 * SOL as i64));
+        assert!(delta.check().is_ok());
+
+        let delta = verify_balance_delta(&signature(&partial), &validator_id, 2 * SOL, &rpc).await.unwrap();
+        assert_eq!(delta.delta_lamports, SOL as i64);
+        assert!(matches!(delta.check(), Err(Error::BalanceMismatch(_))));
+
+        let result = verify_balance_delta(&signature(&elsewhere), &validator_id, SOL, &rpc).await;
+        assert!(matches!(result, Err(Error::BalanceMismatch(_))));
+        let result = verify_balance_delta(&signature(&failed), &validator_id, SOL, &rpc).await;
+        assert!(matches!(result, Err(Error::TransactionFailed(_))));
+        let result = verify_balance_delta(&Signature::new_unique(), &validator_id, SOL, &rpc).await;
+        assert!(matches!(result, Err(Error::Rpc(_))));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::rpc::SolanaRpc;
+use crate::{funding_instructions, Error, FundingAccounts};
+use solana_address_lookup_table_interface::program::ID as ADDRESS_LOOKUP_TABLE_PROGRAM_ID;
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{v0, AddressLookupTableAccount};
+use solana_sdk::pubkey::Pubkey;
+
+/// Format of the funding transaction (`--tx-version`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionVersion {
+    /// Legacy `Transaction`
+    #[default]
+    Legacy,
+    /// Versioned transaction with a v0 message, which may load accounts from lookup tables
+    V0,
+}
+
+impl TransactionVersion {
+    /// Parses `legacy`, `0` or `v0`
+    ///
+    /// # Arguments
+    /// * `value` - Value of `--tx-version`
+    ///
+    /// # Returns
+    /// * `Result<TransactionVersion, Error>` - Version or `Error::InvalidInput`
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "legacy" => Ok(TransactionVersion::Legacy),
+            "0" | "v0" => Ok(TransactionVersion::V0),
+            _ => Err(Error::InvalidInput(format!("Invalid transaction version '{}': expected legacy or 0", value))),
+        }
+    }
+}
+
+/// Decodes an address lookup table account
+///
+/// # Arguments
+/// * `address` - Lookup table address
+/// * `account` - Account data, if the account exists
+///
+/// # Returns
+/// * `Result<AddressLookupTableAccount, Error>` - Table addresses, or `Error::InvalidInput` if the account
+///   does not exist, is not a lookup table or has been deactivated
+pub fn decode_lookup_table(address: &Pubkey, account: Option<&Account>) -> Result<AddressLookupTableAccount, Error> {
+    let account = account.ok_or_else(|| Error::InvalidInput(format!("Address lookup table {} does not exist", address)))?;
+    if account.owner != ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+        return Err(Error::InvalidInput(format!(
+            "Account {} is not an address lookup table (owner {})",
+            address, account.owner
+        )));
+    }
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|e| Error::InvalidInput(format!("Failed to decode address lookup table {}: {}", address, e)))?;
+    // A deactivated table stops resolving once its deactivation slot leaves the slot hashes
+    if table.meta.deactivation_slot != u64::MAX {
+        return Err(Error::InvalidInput(format!(
+            "Address lookup table {} was deactivated at slot {}",
+            address, table.meta.deactivation_slot
+        )));
+    }
+    Ok(AddressLookupTableAccount { key: *address, addresses: table.addresses.to_vec() })
+}
+
+/// Reads an address lookup table (`--alt`)
+///
+/// # Arguments
+/// * `address` - Lookup table address
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<AddressLookupTableAccount, Error>` - Table addresses or error (see `decode_lookup_table`)
+pub async fn get_lookup_table(address: &Pubkey, rpc: &dyn SolanaRpc) -> Result<AddressLookupTableAccount, Error> {
+    let account = rpc
+        .get_account_with_context(address)
+        .await
+        .map_err(|e| Error::from_client_error("Failed to get address lookup table", &e))?
+        .value;
+    decode_lookup_table(address, account.as_ref())
+}
+
+/// Returns the v0 message of a PDA funding transaction
+///
+/// Same instructions and fee payer as `funding_message`; accounts found in the lookup tables
+/// (other than signers and invoked programs) are loaded from them instead of the message.
+///
+/// # Arguments
+/// * `validator_id` - The validator's public key
+/// * `accounts` - Funding wallet, fee payer and nonce accounts
+/// * `amount_lamports` - Amount to transfer in lamports
+/// * `priority_fee` - Priority fee in micro-lamports per compute unit (optional)
+/// * `memo` - Memo attached to the transfer (optional)
+/// * `lookup_tables` - Address lookup tables to resolve accounts from (may be empty)
+/// * `blockhash` - Recent blockhash, or the nonce value when a nonce is used
+///
+/// # Returns
+/// * `Result<v0::Message, Error>` - Message or `Error::InvalidInput` if it cannot be compiled
+pub fn funding_message_v0(
+    validator_id: &Pubkey,
+    accounts: &FundingAccounts,
+    amount_lamports: u64,
+    priority_fee: Option<u64>,
+    memo: Option<&str>,
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: &Hash,
+) -> Result<v0::Message, Error> {
+    let instructions = funding_instructions(validator_id, &accounts.funder, amount_lamports, accounts.nonce, priority_fee, memo);
+    v0::Message::try_compile(&accounts.fee_payer, &instructions, lookup_tables, *blockhash)
+        .map_err(|e| Error::InvalidInput(format!("Failed to compile v0 message: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_deposit_pda;
+    use solana_address_lookup_table_interface::state::{LookupTableMeta, LOOKUP_TABLE_META_SIZE};
+    use solana_sdk::message::VersionedMessage;
+    use std::borrow::Cow;
+
+    /// Serializes a lookup table account as the program stores it
+    fn table_account(addresses: &[Pubkey], deactivation_slot: u64) -> Account {
+        let table = AddressLookupTable {
+            meta: LookupTableMeta { deactivation_slot, ..LookupTableMeta::default() },
+            addresses: Cow::Borrowed(addresses),
+        };
+        let data = table.serialize_for_tests().unwrap();
+        assert!(data.len() >= LOOKUP_TABLE_META_SIZE);
+        Account { lamports: 1, data, owner: ADDRESS_LOOKUP_TABLE_PROGRAM_ID, executable: false, rent_epoch: 0 }
+    }
+
+    #[test]
+    fn test_parse_transaction_version() {
+        assert_eq!(TransactionVersion::parse("legacy").unwrap(), TransactionVersion::Legacy);
+        assert_eq!(TransactionVersion::parse("0").unwrap(), TransactionVersion::V0);
+        assert_eq!(TransactionVersion::parse("V0").unwrap(), TransactionVersion::V0);
+        assert!(TransactionVersion::parse("1").unwrap_err().is_user_error());
+    }
+
+    #[test]
+    fn test_funding_message_v0_uses_lookup_table() {
+        let validator_id = Pubkey::new_unique();
+        let funder = Pubkey::new_unique();
+        let pda = generate_deposit_pda(&validator_id);
+        let table_key = Pubkey::new_unique();
+        let accounts = FundingAccounts { funder, fee_payer: funder, nonce: None };
+
+        let table = decode_lookup_table(&table_key, Some(&table_account(&[pda], u64::MAX))).unwrap();
+        assert_eq!(table.addresses, vec![pda]);
+
+        let message = funding_message_v0(&validator_id, &accounts, 1_000, None, None, &[table], &Hash::default()).unwrap();
+        // The PDA is loaded from the table, so only the payer and the system program stay static
+        assert!(!message.account_keys.contains(&pda));
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert_eq!(message.address_table_lookups[0].account_key, table_key);
+        assert_eq!(message.address_table_lookups[0].writable_indexes, vec![0]);
+
+        // Without tables every account is static
+        let plain = funding_message_v0(&validator_id, &accounts, 1_000, None, None, &[], &Hash::default()).unwrap();
+        assert!(plain.account_keys.contains(&pda));
+        assert!(matches!(VersionedMessage::V0(plain), VersionedMessage::V0(_)));
+    }
+
+    #[test]
+    fn test_decode_lookup_table_rejects_other_accounts() {
+        let address = Pubkey::new_unique();
+        assert!(decode_lookup_table(&address, None).unwrap_err().is_user_error());
+
+        let mut system_account = table_account(&[], u64::MAX);
+        system_account.owner = solana_system_interface::program::ID;
+        assert!(decode_lookup_table(&address, Some(&system_account)).is_err());
+
+        let deactivated = decode_lookup_table(&address, Some(&table_account(&[], 1_000))).unwrap_err();
+        assert!(deactivated.to_string().contains("deactivated at slot 1000"));
+    }
+}
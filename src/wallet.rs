@@ -0,0 +1,142 @@
+use crate::Error;
+use crate::i18n;
+use crate::rpc::SolanaRpc;
+use crate::progress;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use std::time::{Duration, Instant};
+
+/// Default time to wait for a transaction to be confirmed
+pub const DEFAULT_CONFIRMATION_TIMEOUT_SECS: u64 = 60;
+
+/// Result of sweeping a wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepOutcome {
+    /// Transaction signature
+    pub signature: String,
+    /// Amount transferred in lamports
+    pub amount_lamports: u64,
+    /// Transaction fee in lamports
+    pub fee_lamports: u64,
+}
+
+/// Returns the amount that can be swept from a balance after paying the fee
+///
+/// # Arguments
+/// * `balance` - Wallet balance in lamports
+/// * `fee_lamports` - Transaction fee in lamports
+///
+/// # Returns
+/// * `Option<u64>` - Amount to transfer, or None if nothing is left after the fee
+pub fn sweep_amount(balance: u64, fee_lamports: u64) -> Option<u64> {
+    balance.checked_sub(fee_lamports).filter(|amount| *amount > 0)
+}
+
+/// Transfers the whole balance of a wallet (minus the fee) to another wallet and waits for confirmation
+///
+/// The source wallet is left empty, so no rent-exempt reserve is kept.
+///
+/// # Arguments
+/// * `from` - Signer of the wallet being emptied
+/// * `to` - Receiving wallet
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<Option<SweepOutcome>, Error>` - Confirmed transfer, None if the balance does not cover the fee, or error
+pub async fn sweep_wallet(from: &dyn Signer, to: &Pubkey, rpc: &dyn SolanaRpc) -> Result<Option<SweepOutcome>, Error> {
+    let balance = rpc.get_balance(&from.pubkey()).await
+        .map_err(|e| Error::from_client_error("Failed to get wallet balance", &e))?;
+
+    let recent_blockhash = rpc.get_latest_blockhash().await
+        .map_err(|e| Error::from_client_error("Failed to get recent blockhash", &e))?;
+
+    let fee_message = Message::new_with_blockhash(
+        &[solana_system_interface::instruction::transfer(&from.pubkey(), to, 0)],
+        Some(&from.pubkey()),
+        &recent_blockhash,
+    );
+    let fee_lamports = rpc.get_fee_for_message(&fee_message).await
+        .map_err(|e| Error::from_client_error("Failed to estimate transaction fee", &e))?;
+
+    let Some(amount_lamports) = sweep_amount(balance, fee_lamports) else {
+        return Ok(None);
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[solana_system_interface::instruction::transfer(&from.pubkey(), to, amount_lamports)],
+        Some(&from.pubkey()),
+        &[from],
+        recent_blockhash,
+    );
+
+    let signature = rpc.send_and_confirm_transaction(&transaction).await
+        .map_err(|e| Error::from_client_error("Failed to sweep wallet", &e))?;
+
+    Ok(Some(SweepOutcome {
+        signature: signature.to_string(),
+        amount_lamports,
+        fee_lamports,
+    }))
+}
+
+/// Waits until a transaction is confirmed
+///
+/// # Arguments
+/// * `signature` - Transaction signature
+/// * `timeout` - Maximum time to wait
+/// * `rpc` - RPC client
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok once confirmed, `Error::TransactionFailed` if it failed, or `Error::ConfirmationTimeout`
+pub async fn wait_for_confirmation(signature: &str, timeout: Duration, rpc: &dyn SolanaRpc) -> Result<(), Error> {
+    let signature = signature.parse::<Signature>()
+        .map_err(|e| Error::InvalidInput(format!("Invalid transaction signature {}: {}", signature, e)))?;
+
+    let started = Instant::now();
+    let spinner = progress::spinner(&i18n::message("progress-waiting-confirmation", &[("signature", &signature)]));
+
+    let result = loop {
+        match rpc.get_signature_status(&signature).await {
+            Ok(Some(Ok(()))) => break Ok(()),
+            Ok(Some(Err(e))) => {
+                break Err(Error::TransactionFailed(format!("Transaction {} failed: {}", signature, e)));
+            }
+            // Not seen yet, or a transient RPC error: keep polling until the timeout
+            Ok(None) | Err(_) => {}
+        }
+
+        if started.elapsed() >= timeout {
+            break Err(Error::ConfirmationTimeout(format!(
+                "Transaction {} was not confirmed within {} seconds",
+                signature,
+                timeout.as_secs()
+            )));
+        }
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    };
+    spinner.finish_and_clear();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+
+    #[test]
+    fn test_sweep_amount() {
+        assert_eq!(sweep_amount(1_000_000, 5_000), Some(995_000));
+        assert_eq!(sweep_amount(5_001, 5_000), Some(1));
+        assert_eq!(sweep_amount(5_000, 5_000), None);
+        assert_eq!(sweep_amount(0, 5_000), None);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_confirmation_rejects_invalid_signature() {
+        let error = wait_for_confirmation("not-a-signature", Duration::from_secs(1), &MockRpc::new()).await.unwrap_err();
+        assert!(error.is_user_error());
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+
+/// Default window over which the balance rate of change is computed (one hour)
+pub const DEFAULT_RATE_WINDOW_SECS: u64 = 3600;
+
+/// Tracks PDA balance samples and computes the rate of change over a sliding window
+#[derive(Debug, Clone)]
+pub struct BalanceRateTracker {
+    window_secs: u64,
+    samples: VecDeque<(u64, u64)>,
+}
+
+impl BalanceRateTracker {
+    /// Creates a tracker keeping samples for the given window
+    ///
+    /// # Arguments
+    /// * `window_secs` - Length of the sliding window in seconds
+    pub fn new(window_secs: u64) -> Self {
+        BalanceRateTracker {
+            window_secs,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Records a balance sample and drops samples that fell out of the window
+    ///
+    /// # Arguments
+    /// * `timestamp` - Unix timestamp of the sample in seconds
+    /// * `lamports` - Balance in lamports
+    pub fn record(&mut self, timestamp: u64, lamports: u64) {
+        self.samples.push_back((timestamp, lamports));
+        while let Some(&(oldest, _)) = self.samples.front() {
+            if timestamp.saturating_sub(oldest) > self.window_secs && self.samples.len() > 2 {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Returns the number of samples currently in the window
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns true if no samples have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Returns the change between the last two samples in lamports
+    pub fn last_delta(&self) -> Option<i128> {
+        let len = self.samples.len();
+        if len < 2 {
+            return None;
+        }
+        let (_, previous) = self.samples[len - 2];
+        let (_, current) = self.samples[len - 1];
+        Some(current as i128 - previous as i128)
+    }
+
+    /// Returns the net rate of change in SOL per hour (negative means the balance is decreasing)
+    pub fn net_sol_per_hour(&self) -> Option<f64> {
+        let elapsed = self.elapsed_secs()?;
+        let (_, first) = *self.samples.front()?;
+        let (_, last) = *self.samples.back()?;
+        let delta = last as f64 - first as f64;
+        Some(delta / 1_000_000_000.0 / elapsed as f64 * 3600.0)
+    }
+
+    /// Returns the outflow rate in SOL per hour
+    ///
+    /// Only decreases between consecutive samples are counted, so deposits cannot mask a drain.
+    pub fn outflow_sol_per_hour(&self) -> Option<f64> {
+        let elapsed = self.elapsed_secs()?;
+        let outflow: u64 = self
+            .samples
+            .iter()
+            .zip(self.samples.iter().skip(1))
+            .map(|(&(_, previous), &(_, current))| previous.saturating_sub(current))
+            .sum();
+        Some(outflow as f64 / 1_000_000_000.0 / elapsed as f64 * 3600.0)
+    }
+
+    /// Checks the outflow rate against a threshold
+    ///
+    /// # Arguments
+    /// * `max_outflow_sol_per_hour` - Maximum tolerated outflow in SOL per hour
+    ///
+    /// # Returns
+    /// * `Option<String>` - Alert message if the threshold is exceeded
+    pub fn outflow_alert(&self, max_outflow_sol_per_hour: f64) -> Option<String> {
+        let outflow = self.outflow_sol_per_hour()?;
+        if outflow > max_outflow_sol_per_hour {
+            Some(format!(
+                "PDA outflow {:.6} SOL/hour exceeds threshold {} SOL/hour over the last {} seconds",
+                outflow,
+                max_outflow_sol_per_hour,
+                self.elapsed_secs().unwrap_or_default()
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn elapsed_secs(&self) -> Option<u64> {
+        let (first, _) = *self.samples.front()?;
+        let (last, _) = *self.samples.back()?;
+        let elapsed = last.saturating_sub(first);
+        if elapsed == 0 { None } else { Some(elapsed) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_requires_two_samples() {
+        let mut tracker = BalanceRateTracker::new(DEFAULT_RATE_WINDOW_SECS);
+        assert!(tracker.net_sol_per_hour().is_none());
+
+        tracker.record(1_000, 5_000_000_000);
+        assert!(tracker.net_sol_per_hour().is_none());
+        assert!(tracker.outflow_alert(0.1).is_none());
+    }
+
+    #[test]
+    fn test_outflow_rate_and_alert() {
+        let mut tracker = BalanceRateTracker::new(DEFAULT_RATE_WINDOW_SECS);
+        tracker.record(0, 10_000_000_000);
+        tracker.record(900, 9_500_000_000);
+        tracker.record(1_800, 9_000_000_000);
+
+        // 1 SOL drained in half an hour
+        let outflow = tracker.outflow_sol_per_hour().unwrap();
+        assert!((outflow - 2.0).abs() < 1e-9);
+        assert!((tracker.net_sol_per_hour().unwrap() + 2.0).abs() < 1e-9);
+        assert_eq!(tracker.last_delta(), Some(-500_000_000));
+
+        assert!(tracker.outflow_alert(1.5).is_some());
+        assert!(tracker.outflow_alert(2.5).is_none());
+    }
+
+    #[test]
+    fn test_deposits_do_not_mask_outflow() {
+        let mut tracker = BalanceRateTracker::new(DEFAULT_RATE_WINDOW_SECS);
+        tracker.record(0, 1_000_000_000);
+        tracker.record(1_800, 3_000_000_000);
+        tracker.record(3_600, 2_000_000_000);
+
+        assert!(tracker.net_sol_per_hour().unwrap() > 0.0);
+        assert!((tracker.outflow_sol_per_hour().unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_old_samples_leave_window() {
+        let mut tracker = BalanceRateTracker::new(600);
+        tracker.record(0, 1_000);
+        tracker.record(300, 1_000);
+        tracker.record(600, 1_000);
+        tracker.record(1_200, 1_000);
+
+        assert_eq!(tracker.len(), 2);
+    }
+}
@@ -143,4 +143,1966 @@ mod integration_tests {
         // Вывод должен быть идентичным
         assert_eq!(stdout1, stdout2, "Output should be deterministic");
     }
+
+    #[test]
+    fn test_cli_skip_gossip_check() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("--skip-gossip-check")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        
+        // Флаг может стоять в любом месте, проверка gossip не выполняется
+        assert!(stdout.contains("Validator pubkey FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL"));
+        assert!(stdout.contains("PDA Address:"));
+        assert!(!stdout.contains("gossip network"));
+    }
+
+    #[test]
+    fn test_cli_russian_messages() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--lang")
+            .arg("ru")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        // Сообщения выводятся на русском, адреса не меняются
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("Адрес PDA: 7x59cGVPrehfD7nGvr8BjwXc6nmeTsVA6gJREtXErGsR"), "{}", stdout);
+
+        // Язык из переменной окружения, ошибки тоже переводятся
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("invalid_address")
+            .env("DZ_LANG", "ru")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail with invalid address");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Ошибка: Неверный формат адреса валидатора: В адресе найден недопустимый символ base58 'l'"), "{}", stderr);
+
+        let output = Command::new(get_binary_path())
+            .arg("--lang")
+            .arg("ru")
+            .arg("pda-address")
+            .arg("notakey")
+            .output()
+            .expect("Failed to execute command");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Ошибка: Неверный формат публичного ключа: Строка неверной длины"), "{}", stderr);
+
+        // Справка по операции тоже переведена
+        let output = Command::new(get_binary_path())
+            .arg("broadcast")
+            .arg("--help")
+            .env("DZ_LANG", "ru")
+            .output()
+            .expect("Failed to execute command");
+        let help = format!("{}{}", str::from_utf8(&output.stdout).expect("Invalid UTF-8"), str::from_utf8(&output.stderr).expect("Invalid UTF-8"));
+        assert!(help.contains("Примеры:\n# Отправить транзакцию, подписанную офлайн"), "{}", help);
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .env("DZ_LANG", "de")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail with unknown language");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid language 'de': expected en or ru (from DZ_LANG)"), "{}", stderr);
+    }
+
+    #[test]
+    fn test_cli_explorer_link_and_qr() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--explorer")
+            .arg("solanafm")
+            .arg("--qr")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        // Ссылка на PDA в выбранном обозревателе и QR-код для мобильного кошелька
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("Explorer: https://solana.fm/address/7x59cGVPrehfD7nGvr8BjwXc6nmeTsVA6gJREtXErGsR"));
+        assert!(stdout.contains("Scan to deposit:"));
+
+        // QR-код не поддерживается в JSON-выводе
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--qr")
+            .arg("--output")
+            .arg("json")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail for --qr with JSON output");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--qr is only supported by pda-address"));
+    }
+
+    #[test]
+    fn test_cli_pda_address_with_program_override() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--program-id")
+            .arg("11111111111111111111111111111112")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        // Другая программа даёт другой PDA, bump выводится вместе с адресом
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("PDA Address:"));
+        assert!(stdout.contains("Bump Seed:"));
+        assert!(!stdout.contains("7x59cGVPrehfD7nGvr8BjwXc6nmeTsVA6gJREtXErGsR"));
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-balance")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--seed-prefix")
+            .arg("staging")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail for pda-balance");
+    }
+
+    #[test]
+    fn test_cli_with_unknown_option() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--no-such-option")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with unknown option");
+        
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Error: Unknown option '--no-such-option'"));
+    }
+
+    #[test]
+    fn test_cli_wallet_rotate_requires_new_keypair() {
+        let output = Command::new(get_binary_path())
+            .arg("wallet")
+            .arg("rotate")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail without --new");
+        
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("wallet rotate requires --new <keypair_path>"));
+    }
+
+    #[test]
+    fn test_cli_airdrop_requires_exact_amount() {
+        let output = Command::new(get_binary_path())
+            .arg("airdrop")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("ALL")
+            .arg("--output")
+            .arg("json")
+            .output()
+            .expect("Failed to execute command");
+
+        // Сумма ALL для airdrop не имеет смысла - ошибка до обращения к сети
+        assert!(!output.status.success(), "Command should fail with ALL");
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("\"operation\": \"airdrop\""));
+        assert!(stdout.contains("An airdrop needs an exact amount, not ALL"));
+
+        let output = Command::new(get_binary_path())
+            .arg("airdrop")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail without an amount");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("airdrop requires a recipient and an amount"));
+    }
+
+    #[test]
+    fn test_cli_invalid_timeouts() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--rpc-timeout")
+            .arg("0")
+            .output()
+            .expect("Failed to execute command");
+
+        // Нулевой таймаут запроса отклоняется до запуска операции
+        assert!(!output.status.success(), "Command should fail with --rpc-timeout 0");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid --rpc-timeout '0'"));
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--deadline")
+            .arg("soon")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail with a non-numeric deadline");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid --deadline 'soon'"));
+
+        // Dashboard перехватывает Ctrl-C сам и не поддерживает --deadline
+        let output = Command::new(get_binary_path())
+            .arg("dashboard")
+            .arg("--deadline")
+            .arg("60")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Dashboard should reject --deadline");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--deadline is not supported by dashboard"));
+    }
+
+    #[test]
+    fn test_cli_invalid_rpc_header_and_proxy() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--rpc-header")
+            .arg("Authorization Bearer secret-token")
+            .output()
+            .expect("Failed to execute command");
+
+        // Заголовок без двоеточия отклоняется, а его значение не попадает в сообщение
+        assert!(!output.status.success(), "Command should fail with a malformed header");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid --rpc-header"));
+        assert!(!stderr.contains("secret-token"));
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--rpc-proxy")
+            .arg("not a url")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail with an invalid proxy");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid --rpc-proxy"));
+    }
+
+    #[test]
+    fn test_cli_fund_many_resume_of_finished_run() {
+        let state_path = std::env::temp_dir().join(format!("dz_validator_pda_fund_run_{}.json", std::process::id()));
+        let validator = "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL";
+        std::fs::write(
+            &state_path,
+            format!(
+                r#"{{"payer":"11111111111111111111111111111111","updated_at":1700000000,"transfers":[{{"validator":"{}","pda":"7x59cGVPrehfD7nGvr8BjwXc6nmeTsVA6gJREtXErGsR","amount_lamports":1500000000,"signature":null,"status":"confirmed","error":null}}]}}"#,
+                validator
+            ),
+        )
+        .unwrap();
+
+        // Все переводы уже подтверждены: отправлять нечего, сеть не нужна
+        let output = Command::new(get_binary_path())
+            .arg("pda-fund-many")
+            .arg("--resume")
+            .arg(&state_path)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "resume should succeed: {}", str::from_utf8(&output.stderr).unwrap_or_default());
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("Nothing left to send"));
+        assert!(stdout.contains("1 transfer(s) confirmed, 0 unconfirmed"));
+
+        // Существующий файл состояния не перезаписывается, а --resume не смешивается с новыми переводами
+        let output = Command::new(get_binary_path())
+            .arg("pda-fund-many")
+            .arg("--pairs")
+            .arg(format!("{}:1", validator))
+            .arg("--state")
+            .arg(&state_path)
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("already exists"));
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-fund-many")
+            .arg("--resume")
+            .arg(&state_path)
+            .arg("--pairs")
+            .arg(format!("{}:1", validator))
+            .output()
+            .expect("Failed to execute command");
+        std::fs::remove_file(&state_path).ok();
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("--pairs cannot be combined with --resume"));
+    }
+
+    #[test]
+    fn test_cli_derive_seed_spec() {
+        // Сиды депозитного PDA дают тот же адрес, что и pda-address
+        let output = Command::new(get_binary_path())
+            .arg("derive")
+            .arg("--seed-spec")
+            .arg("prefix:solana_validator_deposit,pubkey:FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--output")
+            .arg("json")
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success());
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("\"pda\": \"7x59cGVPrehfD7nGvr8BjwXc6nmeTsVA6gJREtXErGsR\""));
+        assert!(stdout.contains("\"pubkey:FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\""));
+
+        // Неизвестный вид сида отклоняется
+        let output = Command::new(get_binary_path())
+            .arg("derive")
+            .arg("--seed-spec")
+            .arg("blob:1")
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("Unknown seed kind"));
+    }
+
+    #[test]
+    fn test_cli_verify_deposit_rejects_invalid_input() {
+        let output = Command::new(get_binary_path())
+            .arg("verify-deposit")
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("Usage:"));
+
+        // Неверная подпись отклоняется до обращения к сети
+        let output = Command::new(get_binary_path())
+            .arg("verify-deposit")
+            .arg("not_a_signature")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--output")
+            .arg("json")
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("\"kind\": \"invalid_input\""));
+        assert!(stdout.contains("Invalid transaction signature"));
+    }
+
+    #[test]
+    fn test_cli_balance_history_rejects_invalid_input() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-balance-history")
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("Usage:"));
+
+        // An invalid window is rejected before any RPC call
+        let output = Command::new(get_binary_path())
+            .arg("pda-balance-history")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--days")
+            .arg("0")
+            .arg("--output")
+            .arg("json")
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("\"kind\": \"invalid_input\""));
+        assert!(stdout.contains("Invalid --days"));
+
+        // --out only applies to the CSV time series
+        let output = Command::new(get_binary_path())
+            .arg("pda-balance-history")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--out")
+            .arg("history.csv")
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+    }
+
+    #[test]
+    fn test_cli_revenue_report_rejects_invalid_input() {
+        let output = Command::new(get_binary_path())
+            .arg("revenue-report")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("Usage:"));
+
+        // Epochs and the share are checked before any RPC call
+        for (args, expected) in [
+            (vec!["--epochs", "650..640"], "the first epoch is after the last"),
+            (vec!["--epochs", "500..600"], "at most 64 epochs"),
+            (vec!["--epochs", "640..650", "--share", "120"], "Invalid --share '120'"),
+        ] {
+            let output = Command::new(get_binary_path())
+                .arg("revenue-report")
+                .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+                .args(&args)
+                .args(["--output", "json", "--config", "/nonexistent/config.toml"])
+                .output()
+                .expect("Failed to execute command");
+            assert_eq!(output.status.code(), Some(2), "Unexpected exit code for {:?}", args);
+            let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+            assert!(stdout.contains("\"kind\": \"invalid_input\""), "{}", stdout);
+            assert!(stdout.contains(expected), "Unexpected output for {:?}: {}", args, stdout);
+        }
+    }
+
+    #[test]
+    fn test_cli_verify_receipt() {
+        use dz_validator_pda::receipt::{DepositReceipt, RECEIPT_VERSION};
+        use solana_sdk::signer::Signer;
+
+        let payer = solana_sdk::signature::Keypair::new();
+        let validator = "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL";
+        let mut receipt = DepositReceipt {
+            version: RECEIPT_VERSION,
+            signature: solana_sdk::signature::Signature::new_unique().to_string(),
+            slot: 312_345_600,
+            block_time: Some(1_700_000_000),
+            payer: payer.pubkey().to_string(),
+            validator: validator.to_string(),
+            pda: dz_validator_pda::generate_deposit_pda(&validator.parse().unwrap()).to_string(),
+            amount_lamports: 1_500_000_000,
+            payer_signature: String::new(),
+        };
+        receipt.payer_signature = payer.sign_message(&receipt.signed_message()).to_string();
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_receipt_{}.json", std::process::id()));
+        let verify = |receipt: &DepositReceipt| {
+            std::fs::write(&path, serde_json::to_string(receipt).unwrap()).unwrap();
+            Command::new(get_binary_path()).arg("verify-receipt").arg(&path).output().expect("Failed to execute command")
+        };
+
+        let output = verify(&receipt);
+        assert!(output.status.success(), "{}", str::from_utf8(&output.stderr).unwrap());
+        assert!(str::from_utf8(&output.stdout).expect("Invalid UTF-8").contains("Receipt signature is valid"));
+
+        // An edited amount invalidates the signature
+        let output = verify(&DepositReceipt { amount_lamports: 15_000_000_000, ..receipt });
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("does not match payer"));
+        std::fs::remove_file(&path).ok();
+
+        let output = Command::new(get_binary_path()).arg("verify-receipt").output().expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("Usage:"));
+    }
+
+    #[test]
+    fn test_cli_sign_and_verify_message() {
+        use solana_sdk::signer::Signer;
+
+        let keypair = solana_sdk::signature::Keypair::new();
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_message_{}.json", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&keypair, &path).expect("Failed to write keypair");
+        let message = "register validator for revenue distribution";
+
+        let output = Command::new(get_binary_path())
+            .args(["sign-message", "--keypair"])
+            .arg(&path)
+            .arg(message)
+            .output()
+            .expect("Failed to execute command");
+        std::fs::remove_file(&path).ok();
+        assert!(output.status.success(), "{}", str::from_utf8(&output.stderr).unwrap());
+        let signature = str::from_utf8(&output.stdout).expect("Invalid UTF-8").trim().to_string();
+
+        let verify = |message: &str| {
+            Command::new(get_binary_path())
+                .args(["verify-message", &keypair.pubkey().to_string(), &signature, message])
+                .output()
+                .expect("Failed to execute command")
+        };
+        let output = verify(message);
+        assert!(output.status.success(), "{}", str::from_utf8(&output.stderr).unwrap());
+        assert!(str::from_utf8(&output.stdout).expect("Invalid UTF-8").contains("Signature is valid"));
+
+        // Another message does not match the signature
+        let output = verify("register another validator");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("does not match"));
+
+        let output = Command::new(get_binary_path()).args(["sign-message", message]).output().expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("--keypair"));
+    }
+
+    #[test]
+    fn test_cli_alias_registry() {
+        let validator = "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL";
+        let config = std::env::temp_dir().join(format!("dz_validator_pda_alias_{}.toml", std::process::id()));
+        let run = |args: &[&str]| {
+            Command::new(get_binary_path())
+                .args(args)
+                .arg("--config")
+                .arg(&config)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        let output = run(&["alias", "add", "mainnet-01", validator]);
+        assert!(output.status.success(), "{}", str::from_utf8(&output.stderr).unwrap());
+        let output = run(&["alias", "list", "--output", "json"]);
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+        assert_eq!(report["result"]["aliases"][0]["alias"], "mainnet-01");
+
+        // The alias is accepted in place of the pubkey
+        let output = run(&["pda-address", "mainnet-01", "--skip-gossip-check", "--output", "json"]);
+        assert!(output.status.success(), "{}", str::from_utf8(&output.stderr).unwrap());
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+        assert_eq!(report["result"]["validator"], validator);
+
+        // A validator has at most one alias
+        let output = run(&["alias", "add", "mainnet-02", validator]);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("already has the alias"));
+
+        let output = run(&["alias", "remove", "mainnet-01"]);
+        std::fs::remove_file(&config).ok();
+        assert!(output.status.success(), "{}", str::from_utf8(&output.stderr).unwrap());
+        assert_eq!(run(&["pda-address", "mainnet-01"]).status.code(), Some(2));
+    }
+
+    #[test]
+    fn test_cli_backend_options() {
+        let run = |extra: &[&str]| {
+            Command::new(get_binary_path())
+                .args(["pda-address", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL"])
+                .args(extra)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        let output = run(&["--backend", "websocket"]);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("expected rpc or geyser"));
+        let output = run(&["--geyser-endpoint", "https://grpc.example.com"]);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("only used with --backend geyser"));
+        let output = run(&["--backend", "geyser"]);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(run(&["--backend", "rpc"]).status.success());
+    }
+
+    #[test]
+    fn test_cli_gossip_monitor_rejects_invalid_options() {
+        let run = |extra: &[&str]| {
+            Command::new(get_binary_path())
+                .args(["gossip-monitor", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL"])
+                .args(extra)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        let output = run(&["--grace", "all"]);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("Invalid --grace"));
+        assert_eq!(run(&["--interval", "0"]).status.code(), Some(2));
+        assert_eq!(run(&["--output", "json"]).status.code(), Some(2));
+        assert_eq!(run(&["--nice"]).status.code(), Some(2));
+    }
+
+    #[test]
+    fn test_cli_rpc_check_rejects_invalid_input() {
+        // Неверный адрес узла отклоняется до обращения к сети
+        let output = Command::new(get_binary_path())
+            .args(["rpc-check", "not a url", "--output", "json"])
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("\"kind\": \"invalid_input\""), "{}", stdout);
+
+        let output = Command::new(get_binary_path())
+            .args(["rpc-check", "http://localhost:8899", "--max-slot-lag", "-5"])
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid value for --max-slot-lag"), "{}", stderr);
+    }
+
+    #[test]
+    fn test_cli_gossip_diff_of_two_snapshots() {
+        let dir = std::env::temp_dir();
+        let old_path = dir.join(format!("dz_validator_pda_gossip_old_{}.json", std::process::id()));
+        let new_path = dir.join(format!("dz_validator_pda_gossip_new_{}.json", std::process::id()));
+        let kept = "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL";
+        let left = "11111111111111111111111111111111";
+        let joined = "Vote111111111111111111111111111111111111111";
+        std::fs::write(
+            &old_path,
+            format!(r#"{{"rpc_url":"https://api.mainnet-beta.solana.com","fetched_at":1700000000,"nodes":["{}","{}"]}}"#, kept, left),
+        )
+        .unwrap();
+        std::fs::write(
+            &new_path,
+            format!(r#"{{"rpc_url":"https://api.mainnet-beta.solana.com","fetched_at":1700086400,"nodes":["{}","{}"]}}"#, kept, joined),
+        )
+        .unwrap();
+
+        let output = Command::new(get_binary_path())
+            .arg("gossip-diff")
+            .arg(&old_path)
+            .arg(&new_path)
+            .arg("--output")
+            .arg("json")
+            .output()
+            .expect("Failed to execute command");
+        std::fs::remove_file(&old_path).ok();
+        std::fs::remove_file(&new_path).ok();
+
+        // Два снимка сравниваются без обращения к сети
+        assert!(output.status.success(), "gossip-diff should succeed: {}", str::from_utf8(&output.stderr).unwrap_or_default());
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("\"operation\": \"gossip-diff\""));
+        assert!(stdout.contains(&format!("\"appeared\": [\n      \"{}\"", joined)), "{}", stdout);
+        assert!(stdout.contains(&format!("\"disappeared\": [\n      \"{}\"", left)), "{}", stdout);
+        assert!(stdout.contains("\"unchanged\": 1"));
+
+        let output = Command::new(get_binary_path())
+            .arg("gossip-snapshot")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "gossip-snapshot should fail without --out");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("gossip-snapshot requires --out <path>"));
+    }
+
+    #[test]
+    fn test_cli_show_fiat_rejects_unknown_currency() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-balance")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--show-fiat")
+            .arg("gbp")
+            .output()
+            .expect("Failed to execute command");
+
+        // Неизвестная валюта отклоняется до обращения к сети
+        assert!(!output.status.success(), "Command should fail with an unsupported currency");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Unsupported currency 'gbp': expected usd or eur"));
+    }
+
+    #[test]
+    fn test_cli_exit_codes() {
+        // Неверный ввод завершается с кодом 2
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("invalid_address_0OIl")
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+
+        // Недоступный RPC завершается с кодом 3
+        let output = Command::new(get_binary_path())
+            .arg("pda-balance")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--url")
+            .arg("http://127.0.0.1:9")
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(3));
+    }
+
+    #[test]
+    fn test_cli_compatibility_check_skipped_for_unreachable_node() {
+        // The node cannot report its version, so the run itself fails with the RPC error instead
+        let output = Command::new(get_binary_path())
+            .args(["revenue-report", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "--epochs", "640..641"])
+            .args(["--url", "http://127.0.0.1:9", "--strict", "--config", "/nonexistent/config.toml"])
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(3));
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(!stderr.contains("RPC node does not support"), "{}", stderr);
+    }
+
+    #[test]
+    fn test_cli_allocate_invalid_input() {
+        // The budget and weights are checked before any RPC request
+        let run = |args: &[&str]| {
+            Command::new(get_binary_path())
+                .args(args)
+                .args(["--url", "http://127.0.0.1:9", "--config", "/nonexistent/config.toml"])
+                .output()
+                .expect("Failed to execute command")
+        };
+        let validator = "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL";
+
+        let output = run(&["allocate", "--weights", "equal", validator]);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).unwrap().contains("allocate requires --budget and --weights"));
+
+        let output = run(&["allocate", "--budget", "0", "--weights", "equal", validator]);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).unwrap().contains("Invalid --budget '0'"));
+
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_allocate_cli_{}.csv", std::process::id()));
+        std::fs::write(&path, format!("validator,weight\n{},heavy\n", validator)).unwrap();
+        let output = run(&["allocate", "--budget", "500", "--weights", path.to_str().unwrap()]);
+        std::fs::remove_file(&path).ok();
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).unwrap().contains("heavy"));
+
+        let output = run(&["allocate", "--budget", "500", "--weights", "/nonexistent/weights.csv"]);
+        assert_eq!(output.status.code(), Some(2));
+    }
+
+    #[test]
+    fn test_cli_json_output_with_warnings() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--output")
+            .arg("json")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        
+        // Предупреждения отделены от ошибок
+        assert!(stdout.contains("\"ok\": true"));
+        assert!(stdout.contains("\"code\": \"gossip_check_skipped\""));
+        assert!(stdout.contains("\"error\": null"));
+    }
+
+    #[test]
+    fn test_cli_strict_mode_fails_on_warnings() {
+        // Пропуск проверки gossip задан явно и не считается предупреждением
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--strict")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+
+        // Системная программа никогда не бывает в gossip: предупреждение (или ошибка сети) приводит к коду 8
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("11111111111111111111111111111112")
+            .arg("--strict")
+            .arg("--output")
+            .arg("json")
+            .output()
+            .expect("Failed to execute command");
+
+        assert_eq!(output.status.code(), Some(8));
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("\"ok\": false"));
+        assert!(stdout.contains("\"kind\": \"strict_warning\""));
+        assert!(stdout.contains("\"exit_code\": 8"));
+    }
+
+    #[test]
+    fn test_cli_trace_id_in_json_report() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--output")
+            .arg("json")
+            .arg("--trace-id")
+            .arg("ci-run-42")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        // Идентификатор трассировки попадает в отчёт
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("\"trace_id\": \"ci-run-42\""));
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--trace-id")
+            .arg("bad id")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with an invalid trace id");
+    }
+
+    #[test]
+    fn test_cli_timing_report() {
+        let run = |extra: &[&str]| {
+            Command::new(get_binary_path())
+                .args(["pda-balance", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "--url", "http://127.0.0.1:1", "--timing"])
+                .args(extra)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        // Failed requests are counted per method, also when the run fails
+        let output = run(&[]);
+        assert!(!output.status.success());
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("RPC METHOD"), "{}", stderr);
+        assert!(stderr.lines().any(|line| line.starts_with("getBalance")), "{}", stderr);
+
+        let output = run(&["--output", "json"]);
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+        let timing = report["timing"].as_array().expect("No timing in the report");
+        assert!(timing.iter().any(|method| method["method"] == "getBalance" && method["errors"].as_u64() > Some(0)));
+        assert!(!str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("RPC METHOD"));
+    }
+
+    #[test]
+    fn test_cli_batch_output_is_sorted() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-batch")
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("11111111111111111111111111111112")
+            .arg("--skip-gossip-check")
+            .arg("--config")
+            .arg("/nonexistent/dz_validator_pda/config.toml")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        
+        // Порядок строк не зависит от порядка аргументов
+        let first = stdout.find("11111111111111111111111111111112").expect("First validator missing");
+        let second = stdout.find("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL").expect("Second validator missing");
+        assert!(first < second);
+    }
+
+    #[test]
+    fn test_cli_batch_csv_output() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-batch")
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--output")
+            .arg("csv")
+            .arg("--config")
+            .arg("/nonexistent/dz_validator_pda/config.toml")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        // Заголовок и одна строка на валидатора
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("name,validator,pda,balance_lamports,balance_sol,gossip"));
+        assert!(lines[1].starts_with(",FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL,"));
+
+        // CSV доступен только для pda-batch
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--output")
+            .arg("csv")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail for csv outside pda-batch");
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("--output csv is only supported by pda-batch"));
+    }
+
+    #[test]
+    fn test_cli_stdin_validators() {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        let run = |operation: &str, input: &str| {
+            let mut child = Command::new(get_binary_path())
+                .arg(operation)
+                .arg("--stdin")
+                .arg("--skip-gossip-check")
+                .arg("--output")
+                .arg("csv")
+                .arg("--config")
+                .arg("/nonexistent/dz_validator_pda/config.toml")
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("Failed to execute command");
+            child.stdin.take().expect("No stdin").write_all(input.as_bytes()).expect("Failed to write stdin");
+            child.wait_with_output().expect("Failed to wait for command")
+        };
+
+        // Lines quoted by jq without -r are accepted, duplicates are collected once
+        let output = run(
+            "pda-address",
+            "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\n\"11111111111111111111111111111112\"\nFjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\n",
+        );
+        assert!(output.status.success(), "Command should succeed");
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert_eq!(stdout.lines().count(), 3);
+        assert!(stdout.contains(",FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL,"));
+
+        // An empty pipe does not fall back to the configured aliases
+        let output = run("pda-address", "\n");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("No validators on standard input"));
+
+        let output = run("pda-fund-address", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\n");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("--stdin is only supported by"));
+    }
+
+    #[test]
+    fn test_cli_batch_with_invalid_sort() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-batch")
+            .arg("pda-balance")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--sort")
+            .arg("size")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with invalid sort key");
+        
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid sort key 'size'"));
+    }
+
+    #[test]
+    fn test_cli_consistent_snapshot_requires_balances() {
+        // Снимок на один слот имеет смысл только для балансов
+        let output = Command::new(get_binary_path())
+            .arg("pda-batch")
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--consistent-snapshot")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail without balances");
+        
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--consistent-snapshot applies to balances"));
+    }
+
+    #[test]
+    fn test_cli_batch_with_invalid_concurrency() {
+        // Без единой задачи пакет никогда не завершится
+        let output = Command::new(get_binary_path())
+            .arg("pda-batch")
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--concurrency")
+            .arg("0")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with zero concurrency");
+        
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid concurrency '0'"));
+    }
+
+    #[test]
+    fn test_cli_with_invalid_max_commission() {
+        // Комиссия задаётся в процентах от 0 до 100
+        let output = Command::new(get_binary_path())
+            .arg("validator-status")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--max-commission")
+            .arg("150")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with a commission above 100%");
+        
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid --max-commission '150'"));
+    }
+
+    #[test]
+    fn test_cli_with_invalid_uptime_requirement() {
+        // Uptime flags are validated before any RPC request
+        for (args, expected) in [
+            (vec!["--require-uptime", "101"], "Invalid --require-uptime '101'"),
+            (vec!["--require-uptime", "95", "--lookback-epochs", "100"], "Invalid --lookback-epochs '100'"),
+            (vec!["--lookback-epochs", "10"], "--lookback-epochs needs --require-uptime"),
+        ] {
+            let output = Command::new(get_binary_path())
+                .arg("validator-status")
+                .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+                .args(&args)
+                .args(["--config", "/nonexistent/config.toml"])
+                .output()
+                .expect("Failed to execute command");
+
+            assert!(!output.status.success(), "Command should fail with {:?}", args);
+            let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+            assert!(stderr.contains(expected), "Unexpected stderr for {:?}: {}", args, stderr);
+        }
+    }
+
+    #[test]
+    fn test_cli_format_template() {
+        let run = |args: &[&str]| {
+            Command::new(get_binary_path())
+                .args(args)
+                .args(["--skip-gossip-check", "--config", "/nonexistent/config.toml"])
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        // pda-address needs no RPC, so the rendered line can be checked exactly
+        let output = run(&["pda-address", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "--format", "{{validator}}\\t{{ bump }}"]);
+        assert!(output.status.success(), "{}", str::from_utf8(&output.stderr).unwrap());
+        assert_eq!(str::from_utf8(&output.stdout).expect("Invalid UTF-8"), "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\t255\n");
+
+        for (args, expected) in [
+            (vec!["pda-address", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "--format", "{{balance_sol}}"], "unknown field 'balance_sol'"),
+            (vec!["pda-balance", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "--format", "{{pda}}", "--output", "json"], "cannot be combined with --output"),
+            (vec!["fees", "--format", "{{pda}}"], "--format is only supported by"),
+        ] {
+            let output = run(&args);
+            assert_eq!(output.status.code(), Some(2), "Unexpected exit code for {:?}", args);
+            let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+            assert!(stderr.contains(expected), "Unexpected stderr for {:?}: {}", args, stderr);
+        }
+    }
+
+    #[test]
+    fn test_cli_rejects_misplaced_verify_delta() {
+        // --verify-delta only applies to a transfer that is sent, and is checked before any RPC request
+        for args in [
+            vec!["pda-balance", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "--verify-delta"],
+            vec!["pda-fund-address", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "/path/to/keypair.json", "1", "--verify-delta", "--sign-only"],
+        ] {
+            let output = Command::new(get_binary_path())
+                .args(&args)
+                .args(["--config", "/nonexistent/config.toml"])
+                .output()
+                .expect("Failed to execute command");
+
+            assert_eq!(output.status.code(), Some(2), "Unexpected exit code for {:?}", args);
+            let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+            assert!(stderr.contains("--verify-delta"), "Unexpected stderr for {:?}: {}", args, stderr);
+        }
+    }
+
+    #[test]
+    fn test_cli_with_invalid_idempotency_key() {
+        // Ключ проверяется до любых обращений к RPC
+        let output = Command::new(get_binary_path())
+            .arg("pda-fund-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("/path/to/keypair.json")
+            .arg("1")
+            .arg("--idempotency-key")
+            .arg("two words")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with an invalid idempotency key");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid idempotency key 'two words'"));
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-balance")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--duplicate-window")
+            .arg("30m")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail for an operation that does not fund");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("only supported by pda-fund-address"));
+    }
+
+    #[test]
+    fn test_cli_sign_only_requires_blockhash() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-fund-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("/path/to/keypair.json")
+            .arg("1.5")
+            .arg("--sign-only")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail without --blockhash");
+        
+        // Без blockhash подпись невозможна, RPC не используется
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--sign-only requires --blockhash <blockhash>"));
+    }
+
+    #[test]
+    fn test_cli_nice_rejects_watch() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-watch")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--nice")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with --nice");
+        
+        // Режим наблюдения постоянно опрашивает RPC и отключён в --nice
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("pda-watch is not available with --nice"));
+    }
+
+    #[test]
+    fn test_cli_dashboard_requires_terminal() {
+        // Вывод перехвачен тестом, поэтому терминала нет
+        let output = Command::new(get_binary_path())
+            .arg("dashboard")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail without a terminal");
+        
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("dashboard needs an interactive terminal"));
+
+        // Интерактивная таблица не может быть одним JSON-документом
+        let output = Command::new(get_binary_path())
+            .arg("dashboard")
+            .arg("--output")
+            .arg("json")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with --output json");
+        
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--output json is not supported by dashboard"));
+    }
+
+    #[test]
+    fn test_cli_with_invalid_commitment() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--commitment")
+            .arg("recent")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with invalid commitment");
+        
+        // Допустимы только processed, confirmed и finalized
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid commitment 'recent'"));
+    }
+
+    #[test]
+    fn test_cli_gossip_cache_file_without_ttl() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-balance")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--gossip-cache-file")
+            .arg("/tmp/dz_validator_pda_gossip_cli.json")
+            .arg("--gossip-cache-ttl")
+            .arg("0")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with a cache file and no TTL");
+        
+        // Файл кеша без срока жизни никогда не был бы прочитан
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--gossip-cache-file needs a gossip cache TTL above 0"));
+    }
+
+    #[test]
+    fn test_cli_with_invalid_rate_limit() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--rate-limit")
+            .arg("0")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with a zero rate limit");
+        
+        // Нулевая скорость запросов заблокировала бы все обращения к RPC
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid --rate-limit '0'"));
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .arg("--rate-burst")
+            .arg("10")
+            .output()
+            .expect("Failed to execute command");
+
+        // Размер пачки без скорости запросов не имеет смысла
+        assert!(!output.status.success(), "Command should fail with --rate-burst alone");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--rate-burst requires --rate-limit"));
+    }
+
+    #[test]
+    fn test_cli_with_invalid_priority_fee() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-fund-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("wallet.json")
+            .arg("1")
+            .arg("--priority-fee")
+            .arg("0.5")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with invalid priority fee");
+        
+        // Приоритетная комиссия задается целым числом микролампортов за вычислительную единицу
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid priority fee '0.5'"));
+    }
+
+    #[test]
+    fn test_cli_fund_many_rejects_invalid_transfers() {
+        let fund_many = |extra: &[&str]| {
+            Command::new(get_binary_path())
+                .arg("pda-fund-many")
+                .arg("wallet.json")
+                .args(extra)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        let output = fund_many(&[]);
+        assert!(!output.status.success(), "Command should fail without transfers");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("No transfers given"));
+
+        // Баланс общий для всех переводов, поэтому ALL не поддерживается
+        let output = fund_many(&["--pairs", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL:ALL"]);
+        assert!(!output.status.success(), "Command should fail with ALL");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("ALL is not supported"));
+
+        let output = fund_many(&["--pairs", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL:1", "--sign-only", "--output", "json"]);
+        assert!(!output.status.success(), "Command should fail with --sign-only");
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("--sign-only is not supported by pda-fund-many"));
+    }
+
+    #[test]
+    fn test_cli_fund_token_requires_mint_and_amount() {
+        let fund_token = |extra: &[&str]| {
+            Command::new(get_binary_path())
+                .arg("pda-fund-token")
+                .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+                .arg("wallet.json")
+                .args(extra)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        // Без --mint и --amount выводится usage
+        let output = fund_token(&["--amount", "1"]);
+        assert!(!output.status.success(), "Command should fail without --mint");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--mint <mint> --amount <tokens>"));
+
+        let output = fund_token(&["--mint", "not-a-mint", "--amount", "1"]);
+        assert!(!output.status.success(), "Command should fail with an invalid mint");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid mint"));
+
+        // Перевод токенов подписывается только онлайн
+        let output = fund_token(&["--mint", "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "--amount", "1", "--sign-only", "--output", "json"]);
+        assert!(!output.status.success(), "Command should fail with --sign-only");
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("--sign-only is not supported by pda-fund-token"));
+    }
+
+    #[test]
+    fn test_cli_identity_keypair_mismatch() {
+        // Ключ идентичности другого валидатора
+        let identity = std::env::temp_dir().join(format!("dz_validator_pda_identity_{}.json", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&solana_sdk::signature::Keypair::new(), &identity).expect("Failed to write identity keypair");
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-fund-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("wallet.json")
+            .arg("1")
+            .arg("--identity-keypair")
+            .arg(&identity)
+            .output()
+            .expect("Failed to execute command");
+        std::fs::remove_file(&identity).ok();
+
+        // Валидатор не совпадает с ключом идентичности - перевод отменяется до обращения к сети
+        assert!(!output.status.success(), "Command should fail on identity mismatch");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("does not match the identity keypair"));
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-balance")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--identity-keypair")
+            .arg("identity.json")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail for other operations");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("only supported by pda-fund-address"));
+    }
+
+    #[test]
+    fn test_cli_from_identity_file() {
+        // Файл ключа идентичности на хосте валидатора
+        let keypair = solana_sdk::signature::Keypair::new();
+        let identity = std::env::temp_dir().join(format!("dz_validator_pda_identity_file_{}.json", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&keypair, &identity).expect("Failed to write identity keypair");
+        let pubkey = solana_sdk::signer::Signer::pubkey(&keypair).to_string();
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("--from-identity-file")
+            .arg(&identity)
+            .arg("--skip-gossip-check")
+            .output()
+            .expect("Failed to execute command");
+
+        // Адрес валидатора берётся из публичной половины ключа
+        assert!(output.status.success(), "Command should succeed");
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains(&format!("Validator pubkey {}", pubkey)), "{}", stdout);
+
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--from-identity-file")
+            .arg(&identity)
+            .arg("--skip-gossip-check")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail on identity mismatch");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains(&format!("does not match the identity file ({})", pubkey)), "{}", stderr);
+
+        // Операции со списком валидаторов не принимают флаг
+        let output = Command::new(get_binary_path())
+            .arg("pda-batch")
+            .arg("pda-address")
+            .arg("--from-identity-file")
+            .arg(&identity)
+            .output()
+            .expect("Failed to execute command");
+        std::fs::remove_file(&identity).ok();
+        assert!(!output.status.success(), "Command should fail for pda-batch");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--from-identity-file is only supported by"));
+    }
+
+    #[test]
+    fn test_cli_with_invalid_epoch_schedule() {
+        let fund = |extra: &[&str]| {
+            Command::new(get_binary_path())
+                .arg("pda-fund-address")
+                .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+                .arg("wallet.json")
+                .arg("1")
+                .args(extra)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        // Номер эпохи должен быть числом
+        let output = fund(&["--at-epoch", "next"]);
+        assert!(!output.status.success(), "Command should fail with an invalid epoch");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid --at-epoch"));
+
+        let output = fund(&["--at-epoch", "900", "--at-epoch-boundary"]);
+        assert!(!output.status.success(), "Command should fail with both schedule flags");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("cannot be combined"));
+
+        // Офлайн-подпись не может ждать границы эпохи
+        let output = fund(&["--at-epoch-boundary", "--sign-only", "--blockhash", "11111111111111111111111111111111"]);
+        assert!(!output.status.success(), "Command should fail with --sign-only");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("cannot be used with --sign-only"));
+    }
+
+    #[test]
+    fn test_cli_keygen_new_and_grind() {
+        use solana_sdk::signer::Signer;
+
+        let dir = std::env::temp_dir().join(format!("dz_validator_pda_keygen_cli_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("Failed to create directory");
+        let outfile = dir.join("payer.json");
+
+        let output = Command::new(get_binary_path())
+            .args(["keygen", "new", "--output", "json", "--outfile"])
+            .arg(&outfile)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "keygen new failed: {}", str::from_utf8(&output.stderr).unwrap_or_default());
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+        let keypair = solana_sdk::signature::read_keypair_file(&outfile).expect("Failed to read keypair");
+        assert_eq!(report["result"]["pubkey"], keypair.pubkey().to_string());
+
+        // An existing wallet is never overwritten without --force
+        let output = Command::new(get_binary_path())
+            .args(["keygen", "new", "--outfile"])
+            .arg(&outfile)
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+
+        // Found keypairs are written to the current directory as <pubkey>.json
+        let binary = std::fs::canonicalize(get_binary_path()).expect("Binary not found");
+        let output = Command::new(binary)
+            .args(["keygen", "grind", "--starts-with", "d:1", "--ignore-case", "--threads", "2", "--output", "json"])
+            .current_dir(&dir)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "keygen grind failed: {}", str::from_utf8(&output.stderr).unwrap_or_default());
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+        let pubkey = report["result"]["keypairs"][0]["pubkey"].as_str().expect("No keypair found").to_string();
+        assert!(pubkey.to_ascii_lowercase().starts_with('d'));
+        let ground = solana_sdk::signature::read_keypair_file(dir.join(format!("{}.json", pubkey))).expect("Failed to read keypair");
+        assert_eq!(ground.pubkey().to_string(), pubkey);
+
+        // Top-level grind with --pda matches the deposit PDA of the identity written to disk
+        let output = Command::new(std::fs::canonicalize(get_binary_path()).expect("Binary not found"))
+            .args(["grind", "--starts-with", "d:1", "--ignore-case", "--pda", "--threads", "2", "--output", "json"])
+            .current_dir(&dir)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "grind --pda failed: {}", str::from_utf8(&output.stderr).unwrap_or_default());
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+        let keypair = &report["result"]["keypairs"][0];
+        let identity = keypair["pubkey"].as_str().expect("No keypair found").parse().expect("Invalid pubkey");
+        let pda = dz_validator_pda::generate_deposit_pda(&identity).to_string();
+        assert_eq!(keypair["pda"].as_str(), Some(pda.as_str()));
+        assert!(pda.to_ascii_lowercase().starts_with('d'));
+
+        // A prefix no address can have is rejected instead of searched forever
+        let output = Command::new(get_binary_path())
+            .args(["keygen", "grind", "--starts-with", "0x:1"])
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("never appears in an address"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_cli_encrypted_identity_keypair() {
+        let dir = std::env::temp_dir();
+        let plain = dir.join(format!("dz_validator_pda_plain_{}.json", std::process::id()));
+        let encrypted = dir.join(format!("dz_validator_pda_encrypted_{}.enc", std::process::id()));
+        solana_sdk::signature::write_keypair_file(&solana_sdk::signature::Keypair::new(), &plain).expect("Failed to write keypair");
+        std::fs::remove_file(&encrypted).ok();
+
+        // Шифрование ключа с паролем из переменной окружения
+        let output = Command::new(get_binary_path())
+            .args(["keygen", "encrypt"])
+            .arg(&plain)
+            .arg(&encrypted)
+            .env("DZ_KEYPAIR_PASSPHRASE", "correct horse")
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "keygen encrypt failed: {}", str::from_utf8(&output.stderr).unwrap_or_default());
+        assert!(std::fs::read(&encrypted).expect("No encrypted keypair").starts_with(b"age-encryption.org/"));
+
+        // Существующий файл не перезаписывается без --force
+        let output = Command::new(get_binary_path())
+            .args(["keygen", "encrypt"])
+            .arg(&plain)
+            .arg(&encrypted)
+            .env("DZ_KEYPAIR_PASSPHRASE", "correct horse")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail for an existing file");
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("already exists"));
+
+        let fund = |passphrase: &str| {
+            Command::new(get_binary_path())
+                .arg("pda-fund-address")
+                .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+                .arg("wallet.json")
+                .arg("1")
+                .arg("--identity-keypair")
+                .arg(&encrypted)
+                .env("DZ_KEYPAIR_PASSPHRASE", passphrase)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        // Зашифрованный ключ расшифровывается и сверяется с валидатором
+        let output = fund("correct horse");
+        assert!(!output.status.success(), "Command should fail on identity mismatch");
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("does not match the identity keypair"));
+
+        // Неверный пароль
+        let output = fund("battery staple");
+        assert!(!output.status.success(), "Command should fail with a wrong passphrase");
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("wrong passphrase"));
+
+        std::fs::remove_file(&plain).ok();
+        std::fs::remove_file(&encrypted).ok();
+    }
+
+    #[test]
+    fn test_cli_environment_settings() {
+        // Неверное значение из переменной окружения называет свой источник
+        let output = Command::new(get_binary_path())
+            .args(["pda-balance", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL"])
+            .env("DZ_COMMITMENT", "recent")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail with an invalid DZ_COMMITMENT");
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("(from DZ_COMMITMENT)"));
+
+        // Флаг важнее переменной окружения
+        let output = Command::new(get_binary_path())
+            .args(["pda-balance", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "--url", "localhost:8899"])
+            .env("DZ_RPC_URL", "https://api.mainnet-beta.solana.com")
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail with an invalid --url");
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("Invalid RPC URL 'localhost:8899'"));
+
+        // Ключ из переменной окружения (JSON-массив), здесь - как ключ идентичности другого валидатора
+        let identity = solana_sdk::signature::Keypair::new();
+        let output = Command::new(get_binary_path())
+            .args(["pda-fund-address", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "wallet.json", "1"])
+            .args(["--identity-keypair", "env://DZ_TEST_IDENTITY_KEYPAIR"])
+            .env("DZ_TEST_IDENTITY_KEYPAIR", format!("{:?}", identity.to_bytes().to_vec()))
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail on identity mismatch");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains(&format!("does not match the identity keypair ({})", solana_sdk::signer::Signer::pubkey(&identity))), "{}", stderr);
+    }
+
+    #[test]
+    fn test_cli_with_invalid_memo() {
+        let fund = |extra: &[&str]| {
+            Command::new(get_binary_path())
+                .arg("pda-fund-address")
+                .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+                .arg("wallet.json")
+                .arg("1")
+                .args(extra)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        // Мемо длиннее 566 байт не помещается в транзакцию
+        let long_memo = "x".repeat(567);
+        let output = fund(&["--memo", &long_memo]);
+        assert!(!output.status.success(), "Command should fail with a too long memo");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("at most 566 bytes"));
+
+        let output = fund(&["--memo", " "]);
+        assert!(!output.status.success(), "Command should fail with an empty memo");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Memo cannot be empty"));
+
+        // Офлайн-подпись не добавляет мемо
+        let output = fund(&["--memo", "top-up", "--sign-only", "--blockhash", "11111111111111111111111111111111"]);
+        assert!(!output.status.success(), "Command should fail with --sign-only");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--memo cannot be used with --sign-only"));
+
+        // Другие операции не поддерживают --memo
+        let output = Command::new(get_binary_path())
+            .args(["pda-balance", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "--memo", "top-up"])
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail for pda-balance");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("only supported by pda-fund-address"));
+    }
+
+    #[test]
+    fn test_cli_squads_rejects_invalid_input() {
+        let fund = |extra: &[&str]| {
+            Command::new(get_binary_path())
+                .arg("pda-fund-address")
+                .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+                .arg("wallet.json")
+                .args(extra)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        let output = fund(&["1", "--squads", "not-a-multisig"]);
+        assert!(!output.status.success(), "Command should fail with an invalid multisig");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid --squads multisig address"), "{}", stderr);
+
+        // Хранилище мультиподписи переводит точную сумму
+        let output = fund(&["ALL", "--squads", "4syr5pBaboZy4cZyF6sys82uGD7jEvoAP2ZMaoich4fZ"]);
+        assert!(!output.status.success(), "Command should fail with ALL");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("ALL is not supported"), "{}", stderr);
+
+        // Предложение отправляется сразу, офлайн-подписи нет
+        let output = fund(&["1", "--squads", "4syr5pBaboZy4cZyF6sys82uGD7jEvoAP2ZMaoich4fZ", "--sign-only", "--blockhash", "11111111111111111111111111111111"]);
+        assert!(!output.status.success(), "Command should fail with --sign-only");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--sign-only cannot be used with --squads"), "{}", stderr);
+
+        let output = Command::new(get_binary_path())
+            .args(["pda-balance", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "--squads", "4syr5pBaboZy4cZyF6sys82uGD7jEvoAP2ZMaoich4fZ"])
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail for pda-balance");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--squads is only supported by pda-fund-address"));
+    }
+
+    #[test]
+    fn test_cli_with_invalid_tx_version() {
+        let fund = |extra: &[&str]| {
+            Command::new(get_binary_path())
+                .arg("pda-fund-address")
+                .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+                .arg("wallet.json")
+                .arg("1")
+                .args(extra)
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        // Поддерживаются только legacy и v0
+        let output = fund(&["--tx-version", "1"]);
+        assert!(!output.status.success(), "Command should fail with invalid transaction version");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid transaction version '1'"));
+
+        // Таблица адресов используется только в v0-транзакции
+        let output = fund(&["--tx-version", "legacy", "--alt", "4syr5pBaboZy4cZyF6sys82uGD7jEvoAP2ZMaoich4fZ"]);
+        assert!(!output.status.success(), "Command should fail with --alt and a legacy transaction");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--alt needs a v0 transaction"));
+
+        // Офлайн-подпись создает только legacy-транзакции
+        let output = fund(&["--alt", "4syr5pBaboZy4cZyF6sys82uGD7jEvoAP2ZMaoich4fZ", "--sign-only", "--blockhash", "11111111111111111111111111111111"]);
+        assert!(!output.status.success(), "Command should fail with --alt and --sign-only");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("cannot be used with --sign-only"));
+    }
+
+    #[test]
+    fn test_cli_fees_report_with_invalid_month() {
+        let output = Command::new(get_binary_path())
+            .arg("fees")
+            .arg("report")
+            .arg("--month")
+            .arg("11-2024")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with invalid month");
+        
+        // Месяц задаётся в формате YYYY-MM
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("expected YYYY-MM"));
+    }
+
+    #[test]
+    fn test_cli_audit_stats_with_invalid_window() {
+        let output = Command::new(get_binary_path())
+            .arg("audit")
+            .arg("stats")
+            .arg("--window")
+            .arg("3w")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail with invalid window");
+        
+        // Окно задается в часах или днях
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Invalid window '3w'"));
+
+        let output = Command::new(get_binary_path())
+            .arg("audit")
+            .arg("export")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail for unknown audit command");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Unknown audit command"));
+    }
+
+    #[test]
+    fn test_cli_audit_show_filters_entries() {
+        let audit_log = std::env::temp_dir().join(format!("dz_validator_pda_audit_show_{}.log", std::process::id()));
+        std::fs::write(
+            &audit_log,
+            concat!(
+                "{\"timestamp\":100,\"event\":\"pda-fund\",\"status\":\"success\",\"validator\":\"FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\",\"amount_lamports\":1000,\"signature\":\"Sig1\"}\n",
+                "{\"timestamp\":200,\"event\":\"pda-fund\",\"status\":\"failed\",\"validator\":\"FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\",\"message\":\"low\"}\n",
+                "{\"timestamp\":300,\"event\":\"wallet-rotate\",\"status\":\"success\"}\n",
+            ),
+        )
+        .unwrap();
+
+        let output = Command::new(get_binary_path())
+            .args(["audit", "show", "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL", "--status", "success", "--output", "json"])
+            .arg("--audit-log")
+            .arg(&audit_log)
+            .output()
+            .expect("Failed to execute command");
+        std::fs::remove_file(&audit_log).ok();
+
+        // Остается только успешное пополнение этого валидатора
+        assert!(output.status.success(), "stderr: {}", str::from_utf8(&output.stderr).unwrap_or(""));
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("\"count\": 1"), "{}", stdout);
+        assert!(stdout.contains("\"signature\": \"Sig1\""));
+        assert!(!stdout.contains("wallet-rotate"));
+
+        let output = Command::new(get_binary_path())
+            .args(["audit", "show", "--status", "landed"])
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "Command should fail with an unknown status");
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("Invalid status 'landed'"));
+    }
+
+    #[test]
+    fn test_cli_state_requires_bundle() {
+        let output = Command::new(get_binary_path())
+            .arg("state")
+            .arg("export")
+            .output()
+            .expect("Failed to execute command");
+
+        // Без --out некуда писать архив
+        assert!(!output.status.success(), "Export should fail without --out");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("state export requires --out"));
+
+        let output = Command::new(get_binary_path())
+            .arg("state")
+            .arg("import")
+            .arg("/nonexistent/bundle.tar.zst")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Import should fail for a missing bundle");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Failed to read bundle"));
+    }
+
+    #[test]
+    fn test_cli_apply_requires_approval() {
+        let output = Command::new(get_binary_path())
+            .arg("apply")
+            .arg("plan.json")
+            .arg("--assume-yes")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail without approval");
+        
+        // Без подтверждения второго оператора план не исполняется
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("--require-approval-file"));
+    }
+
+    #[test]
+    fn test_cli_plan_execute_requires_matching_hash() {
+        use dz_validator_pda::plan::{plan_hash, BatchPlan, PlannedTransfer, BATCH_PLAN_VERSION};
+
+        let validator = "FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL";
+        let plan = BatchPlan {
+            version: BATCH_PLAN_VERSION,
+            created_at: 1_700_000_000,
+            payer: "5SrwvfrPoJETDFAGi6tbG6BsfcVq4eCaxGhNa7HXm9b2".to_string(),
+            rpc: "http://127.0.0.1:1".to_string(),
+            transfers: vec![PlannedTransfer {
+                validator: validator.to_string(),
+                pda: dz_validator_pda::generate_deposit_pda(&validator.parse().unwrap()).to_string(),
+                amount_lamports: 1_500_000_000,
+            }],
+            total_lamports: 1_500_000_000,
+            transaction_count: 1,
+            estimated_fee_lamports: 5_000,
+        };
+        let yaml = plan.to_yaml().unwrap();
+        let path = std::env::temp_dir().join(format!("dz_validator_pda_plan_{}.yaml", std::process::id()));
+        let execute = |approve: Option<&str>| {
+            let mut command = Command::new(get_binary_path());
+            command.args(["plan", "execute"]).arg(&path).arg("--yes");
+            if let Some(approve) = approve {
+                command.args(["--approve", approve]);
+            }
+            command.output().expect("Failed to execute command")
+        };
+
+        std::fs::write(&path, &yaml).unwrap();
+        let output = execute(None);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("--approve"));
+
+        // The amount was raised after the reviewer approved the plan
+        let approved = plan_hash(yaml.as_bytes());
+        std::fs::write(&path, yaml.replace("1500000000", "15000000000")).unwrap();
+        let output = execute(Some(&approved));
+        std::fs::remove_file(&path).ok();
+        assert_eq!(output.status.code(), Some(2));
+        assert!(str::from_utf8(&output.stderr).expect("Invalid UTF-8").contains("does not match the approved hash"));
+    }
+
+    #[test]
+    fn test_cli_run_rejects_invalid_script() {
+        let output = Command::new(get_binary_path())
+            .arg("run")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail without a script");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("run <script.yaml>"));
+
+        // Условие ссылается на шаг, который ещё не выполнен, поэтому сценарий отклоняется целиком
+        let script = std::env::temp_dir().join(format!("dz_validator_pda_run_{}.yaml", std::process::id()));
+        std::fs::write(
+            &script,
+            "steps:\n  - op: pda-balance\n    validator: FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\n    when: later.ok\n  - id: later\n    op: validator-status\n    validator: FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL\n",
+        )
+        .expect("Failed to write script");
+        let output = Command::new(get_binary_path())
+            .arg("run")
+            .arg(&script)
+            .output()
+            .expect("Failed to execute command");
+        std::fs::remove_file(&script).expect("Failed to remove script");
+
+        assert!(!output.status.success(), "Command should fail with an invalid script");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("does not refer to an earlier step"));
+        assert!(output.stdout.is_empty(), "No step should run");
+    }
+
+    #[test]
+    fn test_cli_logs_go_to_stderr() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-address")
+            .arg("FjYEr2UCeFzNfAKiFrbhG34Zv8LxbmfHYAFhAfc7SLQL")
+            .arg("--skip-gossip-check")
+            .env("RUST_LOG", "dz_validator_pda=loud")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        // Диагностика пишется в stderr, stdout содержит только результат
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        let stderr = str::from_utf8(&output.stderr).expect("Invalid UTF-8");
+        assert!(stderr.contains("Ignoring RUST_LOG"));
+        assert!(!stdout.contains("RUST_LOG"));
+        assert!(stdout.contains("PDA Address:"));
+    }
+
+    #[test]
+    fn test_cli_completion_script() {
+        let output = Command::new(get_binary_path())
+            .arg("completion")
+            .arg("bash")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        // Скрипт дополняет операции и запрашивает адреса валидаторов динамически
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("complete -F _dz_validator_pda dz_validator_pda"));
+        assert!(stdout.contains("pda-fund-address"));
+        assert!(stdout.contains("__complete validators"));
+    }
+
+    #[test]
+    fn test_cli_help_and_examples() {
+        let output = Command::new(get_binary_path())
+            .arg("pda-batch")
+            .arg("--help")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        
+        // Справка по операции содержит usage и примеры
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("Usage:"));
+        assert!(stdout.contains("Examples:"));
+        assert!(stdout.contains("pda-batch pda-balance --file validators.txt"));
+
+        let output = Command::new(get_binary_path())
+            .arg("examples")
+            .arg("broadcast")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command should succeed");
+        let stdout = str::from_utf8(&output.stdout).expect("Invalid UTF-8");
+        assert!(stdout.contains("broadcast tx.b64"));
+        assert!(!stdout.contains("pda-fund-address"));
+
+        let output = Command::new(get_binary_path())
+            .arg("examples")
+            .arg("pda-list")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "Command should fail for an unknown operation");
+    }
 }
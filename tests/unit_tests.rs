@@ -80,7 +80,7 @@ mod additional_unit_tests {
         let generated_pda = generate_deposit_pda(&validator_id);
         
         assert_eq!(generated_pda, pda);
-        assert!(bump_seed > 0 && bump_seed <= 255, "Bump seed should be in valid range");
+        assert!(bump_seed > 0, "Bump seed should be in valid range");
         
         // Проверяем, что сид действительно "solana_validator_deposit"
         let expected_seed = b"solana_validator_deposit";